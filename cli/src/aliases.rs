@@ -0,0 +1,111 @@
+//!
+//! Command alias registry.
+//!
+//! Commands may declare alternative verbs (e.g. `tx` for `history`).
+//! Aliases are resolved to the canonical verb before a command is
+//! executed. An alias colliding with another command or with an alias
+//! of another command is a programming error and results in a panic
+//! during handler registration.
+//!
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct Inner {
+    verbs: HashSet<String>,
+    aliases: HashMap<String, String>,
+}
+
+#[derive(Default)]
+pub struct Aliases {
+    inner: Mutex<Inner>,
+}
+
+impl Aliases {
+    /// Registers a command verb together with its aliases.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the verb or any of the aliases collide with a previously
+    /// registered command or with an alias of a different command.
+    pub fn register(&self, verb: &str, aliases: &[&str]) {
+        let mut inner = self.inner.lock().unwrap();
+        let verb = verb.to_lowercase();
+
+        if let Some(owner) = inner.aliases.get(&verb) {
+            panic!("command '{verb}' collides with an alias of command '{owner}'");
+        }
+        inner.verbs.insert(verb.clone());
+
+        for alias in aliases.iter().map(|alias| alias.to_lowercase()) {
+            if inner.verbs.contains(&alias) {
+                panic!("alias '{alias}' of command '{verb}' collides with command '{alias}'");
+            }
+            match inner.aliases.get(&alias) {
+                Some(owner) if owner != &verb => {
+                    panic!("alias '{alias}' of command '{verb}' is already registered for command '{owner}'")
+                }
+                _ => {
+                    inner.aliases.insert(alias, verb.clone());
+                }
+            }
+        }
+    }
+
+    /// Returns the canonical verb if the given verb is an alias.
+    pub fn resolve(&self, verb: &str) -> Option<String> {
+        self.inner.lock().unwrap().aliases.get(&verb.to_lowercase()).cloned()
+    }
+
+    /// Returns the aliases registered for the given command.
+    pub fn aliases_of(&self, verb: &str) -> Vec<String> {
+        let inner = self.inner.lock().unwrap();
+        let mut aliases = inner.aliases.iter().filter(|(_, owner)| *owner == verb).map(|(alias, _)| alias.clone()).collect::<Vec<_>>();
+        aliases.sort();
+        aliases
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alias_resolution() {
+        let aliases = Aliases::default();
+        aliases.register("history", &["tx"]);
+        aliases.register("list", &["ls", "accounts"]);
+        aliases.register("list", &["ls"]);
+
+        assert_eq!(aliases.resolve("tx").as_deref(), Some("history"));
+        assert_eq!(aliases.resolve("TX").as_deref(), Some("history"));
+        assert_eq!(aliases.resolve("ls").as_deref(), Some("list"));
+        assert_eq!(aliases.resolve("history"), None);
+        assert_eq!(aliases.aliases_of("list"), vec!["accounts".to_string(), "ls".to_string()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "alias 'tx' of command 'transfer' is already registered for command 'history'")]
+    fn test_alias_collision_across_commands() {
+        let aliases = Aliases::default();
+        aliases.register("history", &["tx"]);
+        aliases.register("transfer", &["tx"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "alias 'list' of command 'ls' collides with command 'list'")]
+    fn test_alias_collision_with_command() {
+        let aliases = Aliases::default();
+        aliases.register("list", &[]);
+        aliases.register("ls", &["list"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "command 'tx' collides with an alias of command 'history'")]
+    fn test_command_collision_with_alias() {
+        let aliases = Aliases::default();
+        aliases.register("history", &["tx"]);
+        aliases.register("tx", &[]);
+    }
+}
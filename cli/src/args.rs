@@ -0,0 +1,227 @@
+//!
+//! Declarative argument specifications for CLI commands.
+//!
+//! A command declares its positional arguments, flags and value options as
+//! an [`ArgSpec`]. The spec is used to generate the command usage text, to
+//! validate the command line before the command handler is invoked and to
+//! parse the command line into [`Args`] within the handler.
+//!
+
+use crate::error::Error;
+use crate::result::Result;
+use std::collections::HashMap;
+
+/// A single argument accepted by a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arg {
+    /// Positional argument that must be supplied.
+    Required(&'static str),
+    /// Positional argument that may be omitted. Optional arguments
+    /// must follow all required arguments.
+    Optional(&'static str),
+    /// Boolean flag supplied as `--<name>`.
+    Flag(&'static str),
+    /// Option taking a value, supplied as `--<name> <value>`.
+    /// The second field is the value name used in the usage text.
+    Value(&'static str, &'static str),
+}
+
+/// Parsed command arguments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    pub positional: Vec<String>,
+    options: HashMap<&'static str, Option<String>>,
+    usage: String,
+}
+
+impl Args {
+    /// Returns the positional argument at the given index.
+    pub fn get(&self, index: usize) -> Option<&String> {
+        self.positional.get(index)
+    }
+
+    /// Returns the positional argument at the given index, failing
+    /// with a usage error if the argument has not been supplied.
+    pub fn required(&self, index: usize) -> Result<&String> {
+        self.get(index).ok_or_else(|| Error::custom(format!("usage: {}", self.usage)))
+    }
+
+    /// Returns `true` if the given flag has been supplied.
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    /// Returns the value of the given option if it has been supplied.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options.get(name).and_then(|value| value.as_deref())
+    }
+}
+
+/// Argument specification of a command.
+#[derive(Debug, Clone, Copy)]
+pub struct ArgSpec {
+    pub verb: &'static str,
+    pub args: &'static [Arg],
+}
+
+impl ArgSpec {
+    pub const fn new(verb: &'static str, args: &'static [Arg]) -> Self {
+        Self { verb, args }
+    }
+
+    fn positional(&self) -> impl Iterator<Item = &Arg> {
+        self.args.iter().filter(|arg| matches!(arg, Arg::Required(_) | Arg::Optional(_)))
+    }
+
+    /// Names of the flags and value options.
+    fn options(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.args.iter().filter_map(|arg| match arg {
+            Arg::Flag(name) | Arg::Value(name, _) => Some(*name),
+            _ => None,
+        })
+    }
+
+    pub fn required(&self) -> usize {
+        self.positional().filter(|arg| matches!(arg, Arg::Required(_))).count()
+    }
+
+    /// Usage text, e.g. `send <address> <amount> [<priority fee>]`
+    pub fn usage(&self) -> String {
+        let mut usage = vec![self.verb.to_string()];
+        usage.extend(self.args.iter().map(|arg| match arg {
+            Arg::Required(name) => format!("<{name}>"),
+            Arg::Optional(name) => format!("[<{name}>]"),
+            Arg::Flag(name) => format!("[--{name}]"),
+            Arg::Value(name, value) => format!("[--{name} <{value}>]"),
+        }));
+        usage.join(" ")
+    }
+
+    /// Parses the command arguments (excluding the verb), returning
+    /// [`Error::Usage`] if required arguments are missing, too many
+    /// arguments are supplied, an unknown option is encountered or
+    /// an option value is missing.
+    pub fn parse(&self, argv: &[String]) -> Result<Args> {
+        let mut args = Args { usage: self.usage(), ..Default::default() };
+        let mut argv = argv.iter();
+        while let Some(arg) = argv.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                let (name, value) = match self.args.iter().find(|arg| matches!(arg, Arg::Flag(n) | Arg::Value(n, _) if *n == name)) {
+                    Some(Arg::Flag(name)) => (*name, None),
+                    Some(Arg::Value(name, _)) => (*name, Some(argv.next().ok_or_else(|| Error::Usage(self.usage()))?.clone())),
+                    _ => return Err(Error::Usage(self.usage())),
+                };
+                args.options.insert(name, value);
+            } else {
+                args.positional.push(arg.clone());
+            }
+        }
+
+        if args.positional.len() < self.required() || args.positional.len() > self.positional().count() {
+            return Err(Error::Usage(self.usage()));
+        }
+
+        Ok(args)
+    }
+
+    /// Validates the command arguments (excluding the verb), see [`ArgSpec::parse`].
+    pub fn validate(&self, argv: &[String]) -> Result<()> {
+        self.parse(argv).map(|_| ())
+    }
+
+    /// Completes the last (partial) argument against the declared options.
+    pub fn complete(&self, argv: &[String]) -> Option<Vec<String>> {
+        let partial = argv.last()?.strip_prefix("--")?;
+        let list = self.options().filter(|name| name.starts_with(partial)).map(|name| format!("--{name}")).collect::<Vec<_>>();
+        (!list.is_empty()).then_some(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: ArgSpec = ArgSpec::new(
+        "send",
+        &[
+            Arg::Required("address"),
+            Arg::Required("amount"),
+            Arg::Optional("priority fee"),
+            Arg::Flag("dry-run"),
+            Arg::Value("payload", "hexfile"),
+        ],
+    );
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_arg_spec_usage() {
+        assert_eq!(SPEC.usage(), "send <address> <amount> [<priority fee>] [--dry-run] [--payload <hexfile>]");
+        assert_eq!(ArgSpec::new("exit", &[]).usage(), "exit");
+    }
+
+    #[test]
+    fn test_arg_spec_validation() {
+        assert!(SPEC.validate(&argv(&["addr", "1"])).is_ok());
+        assert!(SPEC.validate(&argv(&["addr", "1", "0.1"])).is_ok());
+        assert!(SPEC.validate(&argv(&["addr", "--dry-run", "1"])).is_ok());
+
+        for args in [&["addr"][..], &[], &["addr", "1", "0.1", "extra"], &["addr", "1", "--unknown"], &["addr", "1", "--payload"]] {
+            match SPEC.validate(&argv(args)) {
+                Err(Error::Usage(usage)) => assert_eq!(usage, SPEC.usage()),
+                other => panic!("expected usage error for {args:?}, got {other:?}"),
+            }
+        }
+
+        let err = SPEC.validate(&argv(&["addr"])).unwrap_err();
+        assert_eq!(err.to_string(), "usage: send <address> <amount> [<priority fee>] [--dry-run] [--payload <hexfile>]");
+    }
+
+    #[test]
+    fn test_arg_spec_parse() -> Result<()> {
+        let args = SPEC.parse(&argv(&["addr", "--payload", "payload.hex", "1", "--dry-run"]))?;
+        assert_eq!(args.positional, argv(&["addr", "1"]));
+        assert_eq!(args.get(1).map(String::as_str), Some("1"));
+        assert_eq!(args.get(2), None);
+        assert_eq!(args.value("payload"), Some("payload.hex"));
+        assert!(args.flag("dry-run"));
+
+        let args = SPEC.parse(&argv(&["addr", "1", "0.1"]))?;
+        assert_eq!(args.value("payload"), None);
+        assert!(!args.flag("dry-run"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_command_arg_specs() {
+        use crate::modules::{create_unsigned_tx::CreateUnsignedTx, exit::Exit, send::Send, Command};
+
+        let spec = Send.arg_spec().expect("send arg spec");
+        let err = spec.validate(&argv(&["kaspatest:qz7ulu4c25dh7fzec9zjyrmlhnkzrg4wmf89q7gzr3gfrsj3uz6xjceef60sd"])).unwrap_err();
+        assert_eq!(err.to_string(), "usage: send <address> <amount> [<priority fee>] [--payload <hexfile>] [--yes]");
+        assert!(CreateUnsignedTx.arg_spec().is_some());
+        assert!(Exit.arg_spec().is_none());
+    }
+
+    #[test]
+    fn test_args_required() -> Result<()> {
+        let args = SPEC.parse(&argv(&["addr", "1"]))?;
+        assert_eq!(args.required(1)?, "1");
+        match args.required(2) {
+            Err(Error::Custom(usage)) => assert_eq!(usage, format!("usage: {}", SPEC.usage())),
+            other => panic!("expected usage error, got {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_arg_spec_completion() {
+        assert_eq!(SPEC.complete(&argv(&["addr", "--dr"])), Some(vec!["--dry-run".to_string()]));
+        assert_eq!(SPEC.complete(&argv(&["addr", "--pay"])), Some(vec!["--payload".to_string()]));
+        assert_eq!(SPEC.complete(&argv(&["addr", "--x"])), None);
+        assert_eq!(SPEC.complete(&argv(&["addr"])), None);
+    }
+}
@@ -1,11 +1,16 @@
+use crate::aliases::Aliases;
 use crate::error::Error;
 use crate::helpers::*;
 use crate::imports::*;
+use crate::modules::list::{format_list, ListAccount, ListKey};
 use crate::modules::miner::Miner;
 use crate::modules::node::Node;
 use crate::notifier::{Notification, Notifier};
 use crate::result::Result;
+use crate::script::{run_script, ScriptOptions};
+use crate::secrets::{SecretSource, SecretSources};
 use kaspa_daemon::{DaemonEvent, DaemonKind, Daemons};
+use kaspa_wallet_core::price::*;
 use kaspa_wallet_core::rpc::DynRpcApi;
 use kaspa_wallet_core::storage::{IdT, PrvKeyDataInfo};
 use kaspa_wrpc_client::KaspaRpcClient;
@@ -21,28 +26,40 @@ const NOTIFY: &str = "\x1B[2m⎟\x1B[0m";
 pub struct Options {
     pub daemons: Option<Arc<Daemons>>,
     pub terminal: TerminalOptions,
+    pub secrets: SecretSources,
 }
 
 impl Options {
     pub fn new(terminal_options: TerminalOptions, daemons: Option<Arc<Daemons>>) -> Self {
-        Self { daemons, terminal: terminal_options }
+        Self { daemons, terminal: terminal_options, secrets: SecretSources::from_env() }
+    }
+
+    pub fn with_secrets(mut self, secrets: SecretSources) -> Self {
+        self.secrets = secrets;
+        self
     }
 }
 
 pub struct KaspaCli {
     term: Arc<Mutex<Option<Arc<Terminal>>>>,
-    wallet: Arc<Wallet>,
+    wallets: WalletRegistry,
     notifications_task_ctl: DuplexChannel,
     mute: Arc<AtomicBool>,
     flags: Flags,
     last_interaction: Arc<Mutex<Instant>>,
     daemons: Arc<Daemons>,
     handlers: Arc<HandlerCli>,
+    commands: Mutex<HashMap<String, Arc<dyn Command>>>,
+    aliases: Aliases,
     shutdown: Arc<AtomicBool>,
+    interactive: Arc<AtomicBool>,
+    secrets: SecretSources,
     node: Mutex<Option<Arc<Node>>>,
     miner: Mutex<Option<Arc<Miner>>>,
     notifier: Notifier,
     sync_state: Mutex<Option<SyncState>>,
+    price_source: Option<DynPriceSource>,
+    price_refresh: Arc<AtomicBool>,
 }
 
 impl From<&KaspaCli> for Arc<Terminal> {
@@ -103,21 +120,37 @@ impl KaspaCli {
 
     pub async fn try_new_arc(options: Options) -> Result<Arc<Self>> {
         let wallet = Arc::new(Wallet::try_new(Wallet::local_store()?, None)?);
+        let wallets = WalletRegistry::new(wallet, Arc::new(Wallet::local_store));
+
+        cfg_if! {
+            if #[cfg(target_arch = "wasm32")] {
+                let price_source = None;
+            } else {
+                let price_source: Option<DynPriceSource> =
+                    Some(Arc::new(CachedPriceSource::new(Arc::new(HttpPriceSource::default()), DEFAULT_PRICE_TTL)));
+            }
+        }
 
         let kaspa_cli = Arc::new(KaspaCli {
             term: Arc::new(Mutex::new(None)),
-            wallet,
+            wallets,
             notifications_task_ctl: DuplexChannel::oneshot(),
             mute: Arc::new(AtomicBool::new(true)),
             flags: Flags::default(),
             last_interaction: Arc::new(Mutex::new(Instant::now())),
             handlers: Arc::new(HandlerCli::default()),
+            commands: Mutex::new(HashMap::new()),
+            aliases: Aliases::default(),
             daemons: options.daemons.unwrap_or_default(),
             shutdown: Arc::new(AtomicBool::new(false)),
+            interactive: Arc::new(AtomicBool::new(true)),
+            secrets: options.secrets,
             node: Mutex::new(None),
             miner: Mutex::new(None),
             notifier: Notifier::try_new()?,
             sync_state: Mutex::new(None),
+            price_source,
+            price_refresh: Arc::new(AtomicBool::new(false)),
         });
 
         let term = Arc::new(Terminal::try_new_with_options(kaspa_cli.clone(), options.terminal)?);
@@ -148,24 +181,29 @@ impl KaspaCli {
         env!("CARGO_PKG_VERSION").to_string()
     }
 
+    /// The current wallet, see [`KaspaCli::use_wallet()`]
     pub fn wallet(&self) -> Arc<Wallet> {
-        self.wallet.clone()
+        self.wallets.current()
+    }
+
+    pub fn wallets(&self) -> &WalletRegistry {
+        &self.wallets
     }
 
     pub fn is_connected(&self) -> bool {
-        self.wallet.is_connected()
+        self.wallet().is_connected()
     }
 
     pub fn rpc_api(&self) -> Arc<DynRpcApi> {
-        self.wallet.rpc_api().clone()
+        self.wallet().rpc_api().clone()
     }
 
     pub fn rpc_client(&self) -> Option<Arc<KaspaRpcClient>> {
-        self.wallet.wrpc_client().clone()
+        self.wallet().wrpc_client().clone()
     }
 
     pub fn store(&self) -> Arc<dyn Interface> {
-        self.wallet.store().clone()
+        self.wallet().store().clone()
     }
 
     pub fn daemons(&self) -> &Arc<Daemons> {
@@ -176,6 +214,81 @@ impl KaspaCli {
         self.handlers.clone()
     }
 
+    pub fn aliases(&self) -> &Aliases {
+        &self.aliases
+    }
+
+    /// Executes a command, resolving command aliases and validating
+    /// the arguments against the command [`ArgSpec`] (if one is declared)
+    /// before invoking the handler. Empty input is ignored.
+    pub async fn execute(self: &Arc<Self>, cmd: &str) -> Result<()> {
+        let mut argv = parse(cmd);
+        let Some(verb) = argv.first().filter(|verb| !verb.is_empty()) else {
+            return Ok(());
+        };
+        if let Some(verb) = self.aliases.resolve(verb) {
+            argv[0] = verb;
+        }
+        let verb = argv.remove(0).to_lowercase();
+
+        if let Some(spec) = self.arg_spec(&verb) {
+            spec.validate(&argv)?;
+        }
+        let handler = self.handlers.get(&verb).ok_or(workflow_terminal::error::Error::CommandNotFound(verb))?;
+        let ctx: Arc<dyn Context> = self.clone();
+        handler.handle(&ctx, argv, cmd).await?;
+        Ok(())
+    }
+
+    /// Returns the [`ArgSpec`] declared by the command with the given verb.
+    pub fn arg_spec(&self, verb: &str) -> Option<&'static ArgSpec> {
+        self.commands.lock().unwrap().get(&verb.to_lowercase()).and_then(|command| command.arg_spec())
+    }
+
+    /// Returns `false` if the cli is running in the non-interactive
+    /// (scripting) mode where user prompts are not available.
+    pub fn is_interactive(&self) -> bool {
+        self.interactive.load(Ordering::SeqCst)
+    }
+
+    pub fn set_interactive(&self, interactive: bool) {
+        self.interactive.store(interactive, Ordering::SeqCst);
+    }
+
+    /// Prompts the user for input. Fails in the non-interactive mode
+    /// instead of waiting for input that will never arrive.
+    pub async fn ask(&self, secure: bool, prompt: &str) -> Result<String> {
+        if !self.is_interactive() {
+            return Err(Error::InteractiveTerminalRequired(format!("prompt '{}'", prompt.trim())));
+        }
+        Ok(self.term().ask(secure, prompt).await?)
+    }
+
+    /// Fiat converter of the balance display, available if a currency is configured.
+    pub fn fiat(&self) -> Option<Fiat> {
+        let currency = self.wallet().settings().get::<String>(WalletSettings::Currency).filter(|currency| !currency.is_empty())?;
+        self.price_source.clone().map(|source| Fiat::new(source, &currency))
+    }
+
+    /// Formats `balance` with the fiat equivalent at the last known rate. The rate is
+    /// refreshed in the background, so the caller never waits for the price source.
+    pub fn format_balance(&self, balance: &Option<Balance>, network_type: &NetworkType) -> String {
+        let fiat = self.fiat();
+        let balance_strings = format_balance_with_cached_price(balance, network_type, fiat.as_ref());
+        if let Some(fiat) = fiat {
+            if !self.price_refresh.swap(true, Ordering::SeqCst) {
+                let price_refresh = self.price_refresh.clone();
+                workflow_core::task::spawn(async move {
+                    if let Err(err) = fiat.refresh().await {
+                        log_warning!("Unable to obtain the {} price: {err}", fiat.currency().to_uppercase());
+                    }
+                    price_refresh.store(false, Ordering::SeqCst);
+                });
+            }
+        }
+        balance_strings
+    }
+
     pub fn flags(&self) -> &Flags {
         &self.flags
     }
@@ -190,12 +303,14 @@ impl KaspaCli {
 
     pub fn register_metrics(self: &Arc<Self>) -> Result<()> {
         use crate::modules::metrics;
-        register_handlers!(self, self.handlers(), [metrics]);
+        crate::modules::register_commands!(self, [metrics]);
+        self.register_aliases();
         Ok(())
     }
 
     pub fn register_handlers(self: &Arc<Self>) -> Result<()> {
         crate::modules::register_handlers(self)?;
+        self.register_aliases();
 
         if let Some(node) = self.handlers().get("node") {
             let node = node.downcast_arc::<crate::modules::node::Node>().ok();
@@ -212,6 +327,29 @@ impl KaspaCli {
         Ok(())
     }
 
+    /// Registers a command handler, retaining its [`Command`] declarations.
+    pub fn register_command<H>(self: &Arc<Self>, handler: H)
+    where
+        H: Command + 'static,
+    {
+        let handler = Arc::new(handler);
+        self.handlers.register_arc(self, &handler);
+        let ctx: Arc<dyn Context> = self.clone();
+        if let Some(verb) = handler.verb(&ctx) {
+            self.commands.lock().unwrap().insert(verb.to_lowercase(), handler);
+        }
+    }
+
+    /// Registers aliases of all registered commands, panicking if
+    /// an alias collides with another command or alias.
+    fn register_aliases(self: &Arc<Self>) {
+        let mut commands =
+            self.commands.lock().unwrap().iter().map(|(verb, command)| (verb.clone(), command.aliases())).collect::<Vec<_>>();
+        commands.sort();
+        commands.iter().for_each(|(verb, _)| self.aliases.register(verb, &[]));
+        commands.iter().for_each(|(verb, aliases)| self.aliases.register(verb, aliases));
+    }
+
     pub async fn handle_daemon_event(self: &Arc<Self>, event: DaemonEvent) -> Result<()> {
         match event.kind() {
             DaemonKind::Kaspad => {
@@ -239,8 +377,9 @@ impl KaspaCli {
         self.start_notification_pipe_task();
         self.handlers.start(self).await?;
         // wallet starts rpc and notifier
-        self.wallet.load_settings().await.unwrap_or_else(|_| log_error!("Unable to load settings, discarding..."));
-        self.wallet.start().await?;
+        self.wallet().load_settings().await.unwrap_or_else(|_| log_error!("Unable to load settings, discarding..."));
+        self.flags.try_load().await.unwrap_or_else(|_| log_error!("Unable to load track settings, discarding..."));
+        self.wallet().start().await?;
         Ok(())
     }
 
@@ -250,7 +389,7 @@ impl KaspaCli {
     }
 
     pub async fn stop(self: &Arc<Self>) -> Result<()> {
-        self.wallet.stop().await?;
+        self.wallets.stop().await?;
 
         self.handlers.stop(self).await?;
 
@@ -264,9 +403,18 @@ impl KaspaCli {
         Ok(())
     }
 
+    /// Makes the open wallet `name` the current wallet and routes the
+    /// notifications of its accounts to the terminal.
+    pub async fn use_wallet(self: &Arc<Self>, name: &str) -> Result<Arc<Wallet>> {
+        let wallet = self.wallets.select(name)?;
+        self.stop_notification_pipe_task().await?;
+        self.start_notification_pipe_task();
+        Ok(wallet)
+    }
+
     fn start_notification_pipe_task(self: &Arc<Self>) {
         let this = self.clone();
-        let multiplexer = MultiplexerChannel::from(self.wallet.multiplexer());
+        let multiplexer = MultiplexerChannel::from(self.wallet().multiplexer());
 
         workflow_core::task::spawn(async move {
             loop {
@@ -318,7 +466,7 @@ impl KaspaCli {
 
                                     tprintln!(this, "Connected to Kaspa node version {server_version} at {}", url.unwrap_or("N/A".to_string()));
 
-                                    let is_open = this.wallet.is_open();
+                                    let is_open = this.wallet().is_open();
 
                                     if !is_synced {
                                         if is_open {
@@ -374,7 +522,7 @@ impl KaspaCli {
                                 } => {
                                     if !this.is_mutted() || (this.is_mutted() && this.flags.get(Track::Pending)) {
                                         let include_utxos = this.flags.get(Track::Utxo);
-                                        let tx = record.format_transaction_with_state(&this.wallet,Some("reorg"),include_utxos).await;
+                                        let tx = record.format_transaction_with_state(&this.wallet(),Some("reorg"),include_utxos).await;
                                         tx.iter().for_each(|line|tprintln!(this,"{NOTIFY} {line}"));
                                     }
                                 },
@@ -384,7 +532,7 @@ impl KaspaCli {
                                     // Pending and coinbase stasis fall under the same `Track` category
                                     if !this.is_mutted() || (this.is_mutted() && this.flags.get(Track::Pending)) {
                                         let include_utxos = this.flags.get(Track::Utxo);
-                                        let tx = record.format_transaction_with_state(&this.wallet,Some("stasis"),include_utxos).await;
+                                        let tx = record.format_transaction_with_state(&this.wallet(),Some("stasis"),include_utxos).await;
                                         tx.iter().for_each(|line|tprintln!(this,"{NOTIFY} {line}"));
                                     }
                                 },
@@ -393,7 +541,7 @@ impl KaspaCli {
                                 // } => {
                                 //     if !this.is_mutted() || (this.is_mutted() && this.flags.get(Track::Tx)) {
                                 //         let include_utxos = this.flags.get(Track::Utxo);
-                                //         let tx = record.format_with_state(&this.wallet,Some("external"),include_utxos).await;
+                                //         let tx = record.format_with_state(&this.wallet(),Some("external"),include_utxos).await;
                                 //         tx.iter().for_each(|line|tprintln!(this,"{NOTIFY} {line}"));
                                 //     }
                                 // },
@@ -402,7 +550,7 @@ impl KaspaCli {
                                 } => {
                                     if !this.is_mutted() || (this.is_mutted() && this.flags.get(Track::Pending)) {
                                         let include_utxos = this.flags.get(Track::Utxo);
-                                        let tx = record.format_transaction_with_state(&this.wallet,Some("pending"),include_utxos).await;
+                                        let tx = record.format_transaction_with_state(&this.wallet(),Some("pending"),include_utxos).await;
                                         tx.iter().for_each(|line|tprintln!(this,"{NOTIFY} {line}"));
                                     }
                                 },
@@ -411,7 +559,7 @@ impl KaspaCli {
                                 } => {
                                     if !this.is_mutted() || (this.is_mutted() && this.flags.get(Track::Tx)) {
                                         let include_utxos = this.flags.get(Track::Utxo);
-                                        let tx = record.format_transaction_with_state(&this.wallet,Some("confirmed"),include_utxos).await;
+                                        let tx = record.format_transaction_with_state(&this.wallet(),Some("confirmed"),include_utxos).await;
                                         tx.iter().for_each(|line|tprintln!(this,"{NOTIFY} {line}"));
                                     }
                                 },
@@ -420,7 +568,7 @@ impl KaspaCli {
                                 // } => {
                                 //     if !this.is_mutted() || (this.is_mutted() && this.flags.get(Track::Tx)) {
                                 //         let include_utxos = this.flags.get(Track::Utxo);
-                                //         let tx = record.format_with_state(&this.wallet,Some("confirmed"),include_utxos).await;
+                                //         let tx = record.format_with_state(&this.wallet(),Some("confirmed"),include_utxos).await;
                                 //         tx.iter().for_each(|line|tprintln!(this,"{NOTIFY} {line}"));
                                 //     }
                                 // },
@@ -429,7 +577,7 @@ impl KaspaCli {
                                 // } => {
                                 //     if !this.is_mutted() || (this.is_mutted() && this.flags.get(Track::Tx)) {
                                 //         let include_utxos = this.flags.get(Track::Utxo);
-                                //         let tx = record.format_with_state(&this.wallet,Some("change"),include_utxos).await;
+                                //         let tx = record.format_with_state(&this.wallet(),Some("change"),include_utxos).await;
                                 //         tx.iter().for_each(|line|tprintln!(this,"{NOTIFY} {line}"));
                                 //     }
                                 // },
@@ -439,9 +587,9 @@ impl KaspaCli {
                                 } => {
 
                                     if !this.is_mutted() || (this.is_mutted() && this.flags.get(Track::Balance)) {
-                                        let network_id = this.wallet.network_id().expect("missing network type");
+                                        let network_id = this.wallet().network_id().expect("missing network type");
                                         let network_type = NetworkType::from(network_id);
-                                        let balance_strings = BalanceStrings::from((&balance,&network_type, None));
+                                        let balance_strings = this.format_balance(&balance, &network_type);
                                         let id = id.short();
 
                                         let mature_utxo_count = balance.as_ref().map(|balance|balance.mature_utxo_count.separated_string()).unwrap_or("N/A".to_string());
@@ -474,14 +622,52 @@ impl KaspaCli {
 
     // ---
 
+    /// Reports whether the final transaction of a submitted transaction
+    /// batch has been accepted into the node mempool. The transactions are
+    /// already broadcast at this point, so a failing lookup is only reported.
+    pub(crate) async fn report_mempool_acceptance(&self, ids: &[TransactionId]) {
+        let Some(id) = ids.last() else {
+            return;
+        };
+
+        match self.wallet().get_mempool_entry(id).await {
+            Ok(Some(entry)) => {
+                let status = if entry.is_orphan { "orphan pool" } else { "mempool" };
+                tprintln!(
+                    self,
+                    "transaction {id} accepted into the {status} (fee: {} KAS, mass: {})",
+                    sompi_to_kaspa_string(entry.fee),
+                    entry.transaction.mass
+                );
+            }
+            Ok(None) => tprintln!(self, "transaction {id} is not in the mempool (it may have already been included in a block)"),
+            Err(err) => log_warning!("unable to check the mempool acceptance of transaction {id}: {err}"),
+        }
+    }
+
     /// Asks uses for a wallet secret, checks the supplied account's private key info
     /// and if it requires a payment secret, asks for it as well.
+    ///
+    /// Secrets are taken from the configured [`SecretSources`] if available.
     pub(crate) async fn ask_wallet_secret(&self, account: Option<&Arc<dyn Account>>) -> Result<(Secret, Option<Secret>)> {
-        let wallet_secret = Secret::new(self.term().ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
+        let wallet_secret = SecretSource::obtain(
+            self.secrets.wallet.as_ref(),
+            self.is_interactive(),
+            || async { Ok(self.term().ask(true, "Enter wallet password: ").await?) },
+            Error::WalletSecretRequired,
+        )
+        .await?;
 
         let payment_secret = if let Some(account) = account {
             if self.wallet().is_account_key_encrypted(account).await?.is_some_and(|f| f) {
-                Some(Secret::new(self.term().ask(true, "Enter payment password: ").await?.trim().as_bytes().to_vec()))
+                let payment_secret = SecretSource::obtain(
+                    self.secrets.payment.as_ref(),
+                    self.is_interactive(),
+                    || async { Ok(self.term().ask(true, "Enter payment password: ").await?) },
+                    Error::PaymentSecretRequired,
+                )
+                .await?;
+                Some(payment_secret)
             } else {
                 None
             }
@@ -493,11 +679,11 @@ impl KaspaCli {
     }
 
     pub async fn account(&self) -> Result<Arc<dyn Account>> {
-        if let Ok(account) = self.wallet.account() {
+        if let Ok(account) = self.wallet().account() {
             Ok(account)
         } else {
             let account = self.select_account().await?;
-            self.wallet.select(Some(&account)).await?;
+            self.wallet().select(Some(&account)).await?;
             Ok(account)
         }
     }
@@ -527,10 +713,10 @@ impl KaspaCli {
         let mut list_by_key = Vec::<(Arc<PrvKeyDataInfo>, Vec<(usize, Arc<dyn Account>)>)>::new();
         let mut flat_list = Vec::<Arc<dyn Account>>::new();
 
-        let mut keys = self.wallet.keys().await?;
+        let mut keys = self.wallet().keys().await?;
         while let Some(key) = keys.try_next().await? {
             let mut prv_key_accounts = Vec::new();
-            let mut accounts = self.wallet.accounts(Some(key.id)).await?;
+            let mut accounts = self.wallet().accounts(Some(key.id)).await?;
             while let Some(account) = accounts.next().await {
                 let account = account?;
                 prv_key_accounts.push((flat_list.len(), account.clone()));
@@ -564,7 +750,7 @@ impl KaspaCli {
             let range = if flat_list.len() > 1 { format!("[{}..{}] ", 0, flat_list.len() - 1) } else { "".to_string() };
 
             let text =
-                self.term().ask(false, &format!("Please select account {}or <enter> to abort: ", range)).await?.trim().to_string();
+                self.ask(false, &format!("Please select account {}or <enter> to abort: ", range)).await?.trim().to_string();
             if text.is_empty() {
                 return Err(Error::UserAbort);
             } else {
@@ -592,7 +778,7 @@ impl KaspaCli {
         // let mut list_by_key = Vec::<(Arc<PrvKeyDataInfo>, Vec<(usize, Arc<dyn Account>)>)>::new();
         let mut flat_list = Vec::<Arc<PrvKeyDataInfo>>::new();
 
-        let mut keys = self.wallet.keys().await?;
+        let mut keys = self.wallet().keys().await?;
         while let Some(key) = keys.try_next().await? {
             flat_list.push(key);
         }
@@ -615,7 +801,7 @@ impl KaspaCli {
             let range = if flat_list.len() > 1 { format!("[{}..{}] ", 0, flat_list.len() - 1) } else { "".to_string() };
 
             let text =
-                self.term().ask(false, &format!("Please select private key {}or <enter> to abort: ", range)).await?.trim().to_string();
+                self.ask(false, &format!("Please select private key {}or <enter> to abort: ", range)).await?.trim().to_string();
             if text.is_empty() {
                 return Err(Error::UserAbort);
             } else {
@@ -633,17 +819,27 @@ impl KaspaCli {
     }
 
     pub async fn list(&self) -> Result<()> {
-        let mut keys = self.wallet.keys().await?;
+        let mut keys = self.wallet().keys().await?;
 
-        tprintln!(self);
+        let mut list = vec![];
         while let Some(key) = keys.try_next().await? {
-            tprintln!(self, "• {}", style(&key).dim());
-            let mut accounts = self.wallet.accounts(Some(key.id)).await?;
+            let mut accounts = self.wallet().accounts(Some(key.id)).await?;
+            let mut list_accounts = vec![];
             while let Some(account) = accounts.try_next().await? {
-                let receive_address = account.receive_address()?;
-                tprintln!(self, "    • {}", account.get_list_string()?);
-                tprintln!(self, "      {}", style(receive_address.to_string()).blue());
+                list_accounts.push(ListAccount {
+                    name: account.name_with_id(),
+                    balance: account.balance().map(|balance| (balance.mature, balance.pending)),
+                    mature_utxo_count: account.utxo_context().mature_utxo_size(),
+                    pending_utxo_count: account.utxo_context().pending_utxo_size(),
+                    receive_address: account.receive_address()?.to_string(),
+                });
             }
+            list.push(ListKey { key: key.to_string(), accounts: list_accounts });
+        }
+
+        tprintln!(self);
+        for line in format_list(&list, &self.wallet().network_id()?.into()) {
+            tprintln!(self, "{line}");
         }
         tprintln!(self);
 
@@ -748,7 +944,7 @@ impl Cli for KaspaCli {
 
     async fn digest(self: Arc<Self>, term: Arc<Terminal>, cmd: String) -> TerminalResult<()> {
         *self.last_interaction.lock().unwrap() = Instant::now();
-        if let Err(err) = self.handlers.execute(&self, &cmd).await {
+        if let Err(err) = self.execute(&cmd).await {
             term.writeln(style(err.to_string()).red().to_string());
         }
         Ok(())
@@ -756,6 +952,12 @@ impl Cli for KaspaCli {
 
     async fn complete(self: Arc<Self>, _term: Arc<Terminal>, cmd: String) -> TerminalResult<Option<Vec<String>>> {
         let list = self.handlers.complete(&self, &cmd).await?;
+        if list.is_none() {
+            let argv = parse(&cmd);
+            if let Some(spec) = argv.first().and_then(|verb| self.arg_spec(verb)) {
+                return Ok(spec.complete(&argv[1..]));
+            }
+        }
         Ok(list)
     }
 
@@ -777,21 +979,21 @@ impl Cli for KaspaCli {
         //     _ => {}
         // }
 
-        if (self.wallet.is_open() && !self.wallet.is_connected()) || (node_running && !self.wallet.is_connected()) {
+        if (self.wallet().is_open() && !self.wallet().is_connected()) || (node_running && !self.wallet().is_connected()) {
             prompt.push(style("N/C").red().to_string());
-        } else if self.wallet.is_connected() && !self.wallet.is_synced() {
+        } else if self.wallet().is_connected() && !self.wallet().is_synced() {
             if let Some(state) = self.sync_state() {
                 prompt.push(state);
             }
         }
 
-        if let Some(descriptor) = self.wallet.descriptor() {
+        if let Some(descriptor) = self.wallet().descriptor() {
             let title = descriptor.title.unwrap_or(descriptor.filename);
             if title.to_lowercase().as_str() != "kaspa" {
                 prompt.push(title);
             }
 
-            if let Ok(account) = self.wallet.account() {
+            if let Ok(account) = self.wallet().account() {
                 prompt.push(style(account.name_with_id()).blue().to_string());
 
                 if let Ok(balance) = account.balance_as_strings(None) {
@@ -906,9 +1108,12 @@ where
 // }
 
 pub async fn kaspa_cli(terminal_options: TerminalOptions, banner: Option<String>) -> Result<()> {
+    kaspa_cli_with_options(Options::new(terminal_options, None), banner).await
+}
+
+pub async fn kaspa_cli_with_options(options: Options, banner: Option<String>) -> Result<()> {
     KaspaCli::init();
 
-    let options = Options::new(terminal_options, None);
     let cli = KaspaCli::try_new_arc(options).await?;
 
     let banner =
@@ -933,6 +1138,32 @@ pub async fn kaspa_cli(terminal_options: TerminalOptions, banner: Option<String>
     Ok(())
 }
 
+/// Runs the cli in the non-interactive (scripting) mode, executing the commands
+/// supplied by the script. Returns an error if any of the commands have failed.
+pub async fn kaspa_cli_script(options: Options, script_options: ScriptOptions) -> Result<()> {
+    KaspaCli::init();
+
+    let script = script_options.read()?;
+
+    let cli = KaspaCli::try_new_arc(options).await?;
+    cli.set_interactive(false);
+    cli.register_handlers()?;
+    cli.start().await?;
+
+    let summary = run_script(&cli, &script, script_options.keep_going).await;
+    for failure in summary.failures.iter() {
+        cli.term().writeln(style(failure.to_string()).red().to_string());
+    }
+
+    cli.stop().await?;
+
+    if summary.is_success() {
+        Ok(())
+    } else {
+        Err(Error::custom(format!("{} of {} script command(s) failed", summary.failures.len(), summary.executed)))
+    }
+}
+
 mod panic_handler {
     use regex::Regex;
     use wasm_bindgen::prelude::*;
@@ -1000,3 +1231,32 @@ impl KaspaCli {
         workflow_log::pipe(Some(self.clone()));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_execute_arguments() -> Result<()> {
+        let options = Options { daemons: None, terminal: TerminalOptions::default(), secrets: SecretSources::default() };
+        let cli = KaspaCli::try_new_arc(options).await?;
+        cli.register_handlers()?;
+
+        // empty and whitespace-only input is ignored
+        cli.execute("").await?;
+        cli.execute(" \t ").await?;
+
+        // missing arguments are rejected before the handler is invoked
+        let err = cli.execute("bump").await.unwrap_err();
+        assert_eq!(err.to_string(), "usage: bump <txid> <additional fee>");
+        assert!(matches!(cli.execute("unknown").await, Err(Error::TerminalError(_))));
+
+        // aliases are dispatched to the canonical command
+        assert_eq!(cli.aliases().resolve("bal").as_deref(), Some("list"));
+        let expected = cli.execute("list").await.map_err(|err| err.to_string());
+        assert!(expected.is_err());
+        assert_eq!(cli.execute("BAL").await.map_err(|err| err.to_string()), expected);
+
+        Ok(())
+    }
+}
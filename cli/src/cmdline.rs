@@ -0,0 +1,80 @@
+//!
+//! Command line arguments of the cli application.
+//!
+//! ```text
+//! [--exec <file>] [--keep-going] [--password-file <file>] [--payment-password-file <file>]
+//! ```
+//!
+
+use crate::error::Error;
+use crate::result::Result;
+use crate::script::{ScriptOptions, ScriptSource};
+use crate::secrets::SecretSources;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: [--exec <file>] [--keep-going] [--password-file <file>] [--payment-password-file <file>]";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CmdLine {
+    /// Script to execute, `None` if the cli should run interactively.
+    pub script: Option<ScriptOptions>,
+    pub secrets: SecretSources,
+}
+
+impl CmdLine {
+    /// Parses the process arguments (excluding the program name). Scripting
+    /// mode is enabled only with `--exec <file>` (`--exec -` reads stdin).
+    /// Secrets default to the environment variable sources.
+    pub fn try_from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let mut source = None;
+        let mut keep_going = false;
+        let mut secrets = SecretSources::from_env();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| Error::custom(format!("missing value for '{arg}', {USAGE}")));
+            match arg.as_str() {
+                "--exec" => {
+                    let path = value()?;
+                    source = Some(if path == "-" { ScriptSource::Stdin } else { ScriptSource::File(PathBuf::from(path)) });
+                }
+                "--keep-going" => keep_going = true,
+                "--password-file" => secrets = secrets.with_wallet_file(PathBuf::from(value()?)),
+                "--payment-password-file" => secrets = secrets.with_payment_file(PathBuf::from(value()?)),
+                _ => return Err(Error::custom(format!("unknown argument '{arg}', {USAGE}"))),
+            }
+        }
+
+        Ok(Self { script: source.map(|source| ScriptOptions { source, keep_going }), secrets })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::SecretSource;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_cmdline() -> Result<()> {
+        let cmdline = CmdLine::try_from_args(args(&["--exec", "script.txt", "--keep-going", "--password-file", "secret.txt"]))?;
+        assert_eq!(cmdline.script, Some(ScriptOptions { source: ScriptSource::File(PathBuf::from("script.txt")), keep_going: true }));
+        assert_eq!(cmdline.secrets.wallet, Some(SecretSource::File(PathBuf::from("secret.txt"))));
+
+        let cmdline = CmdLine::try_from_args(args(&["--exec", "-", "--payment-password-file", "payment.txt"]))?;
+        assert_eq!(cmdline.script, Some(ScriptOptions { source: ScriptSource::Stdin, keep_going: false }));
+        assert_eq!(cmdline.secrets.payment, Some(SecretSource::File(PathBuf::from("payment.txt"))));
+
+        // without `--exec` the cli runs interactively, regardless of stdin
+        assert_eq!(CmdLine::try_from_args(args(&["--keep-going"]))?.script, None);
+
+        assert!(CmdLine::try_from_args(args(&["--exec"])).is_err());
+        assert!(CmdLine::try_from_args(args(&["--password-file"])).is_err());
+        assert!(CmdLine::try_from_args(args(&["--unknown"])).is_err());
+
+        Ok(())
+    }
+}
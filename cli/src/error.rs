@@ -63,12 +63,18 @@ pub enum Error {
     #[error("unrecognized argument '{0}', accepted arguments are: {1}")]
     UnrecognizedArgument(String, String),
 
+    #[error("usage: {0}")]
+    Usage(String),
+
     #[error("multiple matches for argument '{0}'; please be more specific.")]
     MultipleMatches(String),
 
     #[error("account type must be <bip32|multisig|legacy>")]
     InvalidAccountKind,
 
+    #[error("{0} requires an interactive terminal")]
+    InteractiveTerminalRequired(String),
+
     #[error("wallet secret is required")]
     WalletSecretRequired,
 
@@ -81,9 +87,21 @@ pub enum Error {
     #[error("payment secrets do not match")]
     PaymentSecretMatch,
 
+    #[error("bip39 passphrases do not match")]
+    Bip39PassphraseMatch,
+
     #[error("key data not found")]
     KeyDataNotFound,
 
+    #[error("mnemonic must be 12 or 24 words, received {0}")]
+    MnemonicWordCount(usize),
+
+    #[error("mnemonic word #{0} '{1}' is not in the BIP39 wordlist{}", did_you_mean(.2))]
+    MnemonicWord(usize, String, Vec<String>),
+
+    #[error("invalid mnemonic checksum, please verify the words and their order")]
+    MnemonicChecksum,
+
     #[error("no accounts found, please create an account to continue")]
     NoAccounts,
 
@@ -117,10 +135,21 @@ pub enum Error {
     #[error("private key {0} already exists")]
     PrivateKeyAlreadyExists(String),
 
+    #[error("verification failed: the signature does not match the message and address")]
+    MessageVerification,
+
     #[error(transparent)]
     MetricsError(kaspa_metrics_core::error::Error),
 }
 
+fn did_you_mean(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(", did you mean: {}?", suggestions.join(", "))
+    }
+}
+
 impl Error {
     pub fn custom<T: Into<String>>(msg: T) -> Self {
         Error::Custom(msg.into())
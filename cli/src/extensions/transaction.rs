@@ -82,6 +82,11 @@ impl TransactionExtension for TransactionRecord {
     ) -> Vec<String> {
         let TransactionRecord { id, binding, block_daa_score, transaction_data, .. } = self;
 
+        let category = account.as_ref().filter(|_| history).and_then(|account| {
+            let addresses = account.utxo_context().addresses();
+            self.category(|address| addresses.contains(address))
+        });
+
         let name = match binding {
             Binding::Custom(id) => style(id.short()).cyan(),
             Binding::Account(account_id) => {
@@ -106,7 +111,11 @@ impl TransactionExtension for TransactionRecord {
 
         let block_daa_score = block_daa_score.separated_string();
         let state = state.unwrap_or(&maturity);
-        let mut lines = vec![format!("{name} {id} @{block_daa_score} DAA - {kind} {state}")];
+        let mut lines = if let Some(category) = category {
+            vec![format!("{name} {id} @{block_daa_score} DAA - {kind} ({}) {state}", style(category.to_string()).dim())]
+        } else {
+            vec![format!("{name} {id} @{block_daa_score} DAA - {kind} {state}")]
+        };
 
         let suffix = kaspa_suffix(&self.network_id.network_type);
 
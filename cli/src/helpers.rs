@@ -1,4 +1,13 @@
+use crate::error::Error;
+use crate::result::Result;
+use async_trait::async_trait;
 use dashmap::DashMap;
+use kaspa_bip32::{Language, Mnemonic};
+use kaspa_notify::events::EventType;
+use kaspa_wallet_core::settings::{DefaultSettings, SettingsStore};
+use kaspa_wallet_core::storage::local::Storage;
+use serde::{Deserialize, Serialize};
+use serde_json::{to_value, Value};
 use std::fmt;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -15,7 +24,8 @@ pub fn toggle(flag: &Arc<AtomicBool>) -> &'static str {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Track {
     Daa = 0,
     Balance,
@@ -24,6 +34,19 @@ pub enum Track {
     Utxo,
 }
 
+impl Track {
+    pub const ALL: [Track; 5] = [Track::Daa, Track::Balance, Track::Pending, Track::Tx, Track::Utxo];
+
+    /// Node notification event type the tracked wallet events are derived from,
+    /// ie. the event type to mute at the notifier to silence this category.
+    pub fn event_type(&self) -> EventType {
+        match self {
+            Track::Daa => EventType::VirtualDaaScoreChanged,
+            Track::Balance | Track::Pending | Track::Tx | Track::Utxo => EventType::UtxosChanged,
+        }
+    }
+}
+
 impl FromStr for Track {
     type Err = String;
     fn from_str(s: &str) -> std::result::Result<Track, String> {
@@ -50,33 +73,203 @@ impl fmt::Display for Track {
     }
 }
 
-pub struct Flags(DashMap<Track, Arc<AtomicBool>>);
+/// Track flags are persisted, all flags are off by default.
+#[async_trait]
+impl DefaultSettings for Track {
+    async fn defaults() -> Vec<(Self, Value)> {
+        Track::ALL.into_iter().map(|track| (track, to_value(false).unwrap())).collect()
+    }
+}
+
+pub struct Flags {
+    map: DashMap<Track, Arc<AtomicBool>>,
+    settings: SettingsStore<Track>,
+}
 
 impl Default for Flags {
     fn default() -> Self {
-        let mut map = DashMap::new();
-        let iter = [(Track::Daa, false), (Track::Balance, false), (Track::Pending, false), (Track::Tx, false), (Track::Utxo, false)]
-            .into_iter()
-            .map(|(flag, default)| (flag, Arc::new(AtomicBool::new(default))));
-        map.extend(iter);
-        Flags(map)
+        Self::new(SettingsStore::try_new("track").expect("Failed to create track settings store"))
     }
 }
 
 impl Flags {
+    fn new(settings: SettingsStore<Track>) -> Self {
+        let map = Track::ALL.into_iter().map(|track| (track, Arc::new(AtomicBool::new(false)))).collect();
+        Flags { map, settings }
+    }
+
+    pub fn new_with_storage(storage: Storage) -> Self {
+        Self::new(SettingsStore::new_with_storage(storage))
+    }
+
     pub fn map(&self) -> &DashMap<Track, Arc<AtomicBool>> {
-        &self.0
+        &self.map
     }
 
-    pub fn toggle(&self, track: Track) {
-        let flag = self.0.get(&track).unwrap();
-        let v = !flag.load(Ordering::SeqCst);
-        flag.store(v, Ordering::SeqCst);
+    /// Load the persisted flags
+    pub async fn try_load(&self) -> Result<()> {
+        self.settings.try_load().await?;
+        for track in Track::ALL {
+            self.map.get(&track).unwrap().store(self.settings.get(track).unwrap_or(false), Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    pub async fn toggle(&self, track: Track) -> Result<()> {
+        let v = !self.get(track);
+        self.map.get(&track).unwrap().store(v, Ordering::SeqCst);
+        self.settings.set(track, v).await?;
         let s = if v { "on" } else { "off" };
         log_info!("{} is {s}", track.to_string());
+        Ok(())
+    }
+
+    /// Turn all flags off and persist them
+    pub async fn reset(&self) -> Result<()> {
+        for track in Track::ALL {
+            self.map.get(&track).unwrap().store(false, Ordering::SeqCst);
+            self.settings.set(track, false).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the notifications of `event` type are muted, ie. if the notification
+    /// output is `muted` and none of the tracked categories is derived from `event`.
+    pub fn is_event_muted(&self, event: EventType, muted: bool) -> bool {
+        muted && !Track::ALL.into_iter().any(|track| track.event_type() == event && self.get(track))
     }
 
     pub fn get(&self, track: Track) -> bool {
-        self.0.get(&track).unwrap().load(Ordering::SeqCst)
+        self.map.get(&track).unwrap().load(Ordering::SeqCst)
+    }
+}
+
+/// Maximum edit distance of the wordlist words suggested for a misspelled mnemonic word
+const MNEMONIC_SUGGESTION_DISTANCE: usize = 2;
+
+/// Validates a 12 or 24 word BIP39 mnemonic: each word must be in the English wordlist
+/// (misspelled words are reported with their index and the closest wordlist words)
+/// and the phrase checksum must be correct.
+pub fn validate_mnemonic(words: &[String]) -> Result<Mnemonic> {
+    if words.len() != 12 && words.len() != 24 {
+        return Err(Error::MnemonicWordCount(words.len()));
+    }
+
+    let wordlist = Language::English.wordlist();
+    let words = words.iter().map(|word| word.to_lowercase()).collect::<Vec<_>>();
+    for (index, word) in words.iter().enumerate() {
+        if !wordlist.iter().any(|known| known == word) {
+            let mut suggestions = wordlist
+                .iter()
+                .map(|known| (edit_distance(word, known), known))
+                .filter(|(distance, _)| *distance <= MNEMONIC_SUGGESTION_DISTANCE)
+                .collect::<Vec<_>>();
+            suggestions.sort();
+            let suggestions = suggestions.into_iter().take(3).map(|(_, known)| known.to_string()).collect();
+            return Err(Error::MnemonicWord(index + 1, word.clone(), suggestions));
+        }
+    }
+
+    Mnemonic::new(words.join(" "), Language::English).map_err(|_| Error::MnemonicChecksum)
+}
+
+/// Levenshtein distance between two words
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_track_flags_persistence() -> Result<()> {
+        let folder = tempfile::tempdir().unwrap();
+        let path = folder.path().join("kaspa-cli.settings");
+        let storage = || Storage::try_new(path.to_str().unwrap()).unwrap();
+
+        let flags = Flags::new_with_storage(storage());
+        flags.try_load().await?;
+        assert!(Track::ALL.into_iter().all(|track| !flags.get(track)));
+        flags.toggle(Track::Daa).await?;
+        flags.toggle(Track::Utxo).await?;
+        flags.toggle(Track::Utxo).await?;
+        flags.toggle(Track::Balance).await?;
+
+        // restart
+        let flags = Flags::new_with_storage(storage());
+        flags.try_load().await?;
+        assert!(flags.get(Track::Daa) && flags.get(Track::Balance));
+        assert!(!flags.get(Track::Utxo) && !flags.get(Track::Pending) && !flags.get(Track::Tx));
+
+        flags.reset().await?;
+        let flags = Flags::new_with_storage(storage());
+        flags.try_load().await?;
+        assert!(Track::ALL.into_iter().all(|track| !flags.get(track)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_track_flags_event_muting() -> Result<()> {
+        let folder = tempfile::tempdir().unwrap();
+        let flags = Flags::new_with_storage(Storage::try_new(folder.path().join("kaspa-cli.settings").to_str().unwrap()).unwrap());
+        flags.try_load().await?;
+
+        // nothing is muted while the output is not muted
+        assert!(!flags.is_event_muted(EventType::UtxosChanged, false));
+        assert!(!flags.is_event_muted(EventType::VirtualDaaScoreChanged, false));
+
+        // muted output mutes every event type unless a category derived from it is tracked
+        assert!(flags.is_event_muted(EventType::UtxosChanged, true));
+        assert!(flags.is_event_muted(EventType::VirtualDaaScoreChanged, true));
+        flags.toggle(Track::Balance).await?;
+        assert!(!flags.is_event_muted(EventType::UtxosChanged, true));
+        assert!(flags.is_event_muted(EventType::VirtualDaaScoreChanged, true));
+        flags.toggle(Track::Daa).await?;
+        assert!(!flags.is_event_muted(EventType::VirtualDaaScoreChanged, true));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_mnemonic() {
+        let words = |phrase: &str| phrase.split_whitespace().map(String::from).collect::<Vec<_>>();
+
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert_eq!(validate_mnemonic(&words(phrase)).unwrap().phrase(), phrase);
+        let phrase = "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote";
+        assert!(validate_mnemonic(&words(phrase)).is_ok());
+        assert!(validate_mnemonic(&words(&phrase.to_uppercase())).is_ok());
+
+        // a misspelled word is reported with its index and suggestions
+        let phrase = "abandon abandon abandn abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let Err(err) = validate_mnemonic(&words(phrase)) else { panic!("misspelled word must be rejected") };
+        match &err {
+            Error::MnemonicWord(index, word, suggestions) => {
+                assert_eq!((*index, word.as_str()), (3, "abandn"));
+                assert_eq!(suggestions.first().map(String::as_str), Some("abandon"));
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+        let err = err.to_string();
+        assert!(err.contains("#3 'abandn'") && err.contains("did you mean: abandon"), "{err}");
+
+        // valid words with an invalid checksum
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert!(matches!(validate_mnemonic(&words(phrase)), Err(Error::MnemonicChecksum)));
+
+        assert!(matches!(validate_mnemonic(&words("abandon about")), Err(Error::MnemonicWordCount(2))));
     }
 }
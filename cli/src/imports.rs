@@ -1,7 +1,9 @@
+pub use crate::args::{Arg, ArgSpec};
 pub use crate::cli::KaspaCli;
 pub use crate::error::Error;
 pub use crate::extensions::*;
 pub(crate) use crate::helpers;
+pub use crate::modules::Command;
 pub use crate::notifier::Notification;
 pub use crate::result::Result;
 pub use crate::utils::*;
@@ -11,6 +13,7 @@ pub use futures::stream::{Stream, StreamExt, TryStreamExt};
 pub use futures::{future::FutureExt, select};
 pub use kaspa_consensus_core::network::{NetworkId, NetworkType};
 pub use kaspa_utils::hex::*;
+pub use kaspa_wallet_core::abortable::Abortable;
 pub use kaspa_wallet_core::derivation::gen0::import::*;
 pub use kaspa_wallet_core::prelude::*;
 pub use kaspa_wallet_core::settings::{DefaultSettings, SettingsStore, WalletSettings};
@@ -21,7 +24,6 @@ pub use separator::Separatable;
 pub use serde::{Deserialize, Serialize};
 pub use serde_json::{to_value, Value};
 pub use std::collections::HashMap;
-pub use std::collections::VecDeque;
 pub use std::ops::Deref;
 pub use std::sync::atomic::{AtomicBool, Ordering};
 pub use std::sync::{Arc, Mutex};
@@ -1,6 +1,9 @@
 extern crate self as kaspa_cli;
 
+pub mod aliases;
+pub mod args;
 mod cli;
+pub mod cmdline;
 pub mod error;
 pub mod extensions;
 mod helpers;
@@ -9,8 +12,10 @@ mod matchers;
 pub mod modules;
 mod notifier;
 pub mod result;
+pub mod script;
+pub mod secrets;
 pub mod utils;
 mod wizards;
 
-pub use cli::{kaspa_cli, KaspaCli, Options, TerminalOptions, TerminalTarget};
+pub use cli::{kaspa_cli, kaspa_cli_script, kaspa_cli_with_options, KaspaCli, Options, TerminalOptions, TerminalTarget};
 pub use workflow_terminal::Terminal;
@@ -2,13 +2,22 @@ cfg_if::cfg_if! {
     if #[cfg(target_arch = "wasm32")] {
         fn main() {}
     } else {
-        use kaspa_cli_lib::{kaspa_cli, TerminalOptions};
+        use kaspa_cli_lib::{cmdline::CmdLine, kaspa_cli_script, kaspa_cli_with_options, Options, TerminalOptions};
 
         #[tokio::main]
         async fn main() {
-            let result = kaspa_cli(TerminalOptions::new().with_prompt("$ "), None).await;
+            let result = match CmdLine::try_from_args(std::env::args().skip(1)) {
+                Ok(CmdLine { script: Some(script), secrets }) => {
+                    kaspa_cli_script(Options::new(TerminalOptions::new().with_prompt(""), None).with_secrets(secrets), script).await
+                }
+                Ok(CmdLine { script: None, secrets }) => {
+                    kaspa_cli_with_options(Options::new(TerminalOptions::new().with_prompt("$ "), None).with_secrets(secrets), None).await
+                }
+                Err(err) => Err(err),
+            };
             if let Err(err) = result {
                 println!("{err}");
+                std::process::exit(1);
             }
         }
     }
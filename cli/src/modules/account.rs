@@ -9,6 +9,8 @@ use crate::wizards;
 #[help("Account management operations")]
 pub struct Account;
 
+impl Command for Account {}
+
 impl Account {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
@@ -42,6 +44,23 @@ impl Account {
                 }
             }
             "create" => {
+                let mut account_index = None;
+                let mut account_path = None;
+                while let Some(pos) = argv.iter().position(|arg| arg == "--index" || arg == "--path") {
+                    let flag = argv.remove(pos);
+                    if pos >= argv.len() {
+                        tprintln!(ctx, "usage: 'account create [<type>] [<name>] [--index <index> | --path <path>]'");
+                        return Ok(());
+                    }
+                    let value = argv.remove(pos);
+                    if flag == "--index" {
+                        account_index =
+                            Some(value.parse::<u64>().map_err(|_| Error::custom(format!("invalid account index: {value}")))?);
+                    } else {
+                        account_path = Some(value);
+                    }
+                }
+
                 let account_kind = if argv.is_empty() {
                     BIP32_ACCOUNT_KIND.into()
                 } else {
@@ -61,7 +80,7 @@ impl Account {
                 let prv_key_data_info = ctx.select_private_key().await?;
 
                 let account_name = account_name.as_deref();
-                wizards::account::create(&ctx, prv_key_data_info, account_kind, account_name).await?;
+                wizards::account::create(&ctx, prv_key_data_info, account_kind, account_name, account_index, account_path).await?;
             }
             "import" => {
                 if argv.is_empty() {
@@ -84,6 +103,7 @@ impl Account {
                                 "account import mnemonic multisig [additional keys]",
                                 "Import mnemonic and additional keys for a multisig account",
                             ),
+                            ("account import kaspa-wallet <keys.json>", "Import a wallet file created by the golang kaspawallet"),
                         ],
                         None,
                     )?;
@@ -102,15 +122,14 @@ impl Account {
 
                         if exists_legacy_v0_keydata().await? {
                             let import_secret = Secret::new(
-                                ctx.term()
-                                    .ask(true, "Enter the password for the account you are importing: ")
+                                ctx.ask(true, "Enter the password for the account you are importing: ")
                                     .await?
                                     .trim()
                                     .as_bytes()
                                     .to_vec(),
                             );
                             let wallet_secret =
-                                Secret::new(ctx.term().ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
+                                Secret::new(ctx.ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
                             let ctx_ = ctx.clone();
                             wallet
                                 .import_legacy_keydata(
@@ -175,9 +194,18 @@ impl Account {
 
                         return Ok(());
                     }
+                    "kaspa-wallet" => {
+                        if argv.len() != 1 {
+                            tprintln!(ctx, "usage: 'account import kaspa-wallet <keys.json>'\r\n");
+                            return Ok(());
+                        }
+
+                        crate::wizards::import::import_kaspa_wallet_file(&ctx, &argv[0]).await?;
+                        return Ok(());
+                    }
                     _ => {
                         tprintln!(ctx, "unknown account import type: '{import_kind}'");
-                        tprintln!(ctx, "supported import types are: 'mnemonic' or 'legacy-data'\r\n");
+                        tprintln!(ctx, "supported import types are: 'mnemonic', 'legacy-data' or 'kaspa-wallet'\r\n");
                         return Ok(());
                     }
                 }
@@ -212,7 +240,7 @@ impl Account {
     async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
         ctx.term().help(
             &[
-                ("create [<type>] [<name>]", "Create a new account (types: 'bip32' (default), 'legacy', 'multisig')"),
+                ("create [<type>] [<name>] [--index <index> | --path <path>]", "Create a new account (types: 'bip32' (default), 'legacy', 'multisig'); bip32 accounts accept a custom account index or hardened derivation path"),
                 (
                     "import <import-type> [<key-type> [extra keys]]",
                     "Import accounts from a private key using 24 or 12 word mnemonic or legacy data \
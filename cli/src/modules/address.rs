@@ -4,6 +4,8 @@ use crate::imports::*;
 #[help("Show or generate a new address for the current wallet account")]
 pub struct Address;
 
+impl Command for Address {}
+
 impl Address {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
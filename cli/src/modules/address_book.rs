@@ -0,0 +1,89 @@
+use crate::imports::*;
+use kaspa_addresses::Prefix;
+use kaspa_wallet_core::error::Error as WalletError;
+use kaspa_wallet_core::storage::AddressBookEntry;
+
+#[derive(Default, Handler)]
+#[help("Manage the address book of payment recipients")]
+pub struct AddressBook;
+
+impl Command for AddressBook {
+    fn aliases(&self) -> &'static [&'static str] {
+        &["book"]
+    }
+}
+
+impl AddressBook {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let store = ctx.wallet().store().as_address_book_store()?;
+
+        match argv.remove(0).as_str() {
+            "list" => {
+                let mut entries = store.iter().await?;
+                let mut empty = true;
+                while let Some(entry) = entries.try_next().await? {
+                    empty = false;
+                    let title = if entry.title.is_empty() { String::new() } else { format!(" ({})", entry.title) };
+                    tprintln!(ctx, "{}{title}: {}", style(&entry.alias).blue(), entry.address);
+                }
+                if empty {
+                    tprintln!(ctx, "the address book is empty");
+                }
+            }
+            "add" => {
+                if argv.len() < 2 {
+                    tprintln!(ctx, "usage: address-book add <alias> <address> [<title>]");
+                    return Ok(());
+                }
+                let alias = argv.remove(0);
+                let address = Address::try_from(argv.remove(0).as_str())?;
+                let network_id = ctx.wallet().network_id()?;
+                if address.prefix != Prefix::from(network_id) {
+                    return Err(WalletError::AddressBookNetworkMismatch(address.to_string(), network_id.to_string()).into());
+                }
+                let title = argv.join(" ");
+                let entry = AddressBookEntry::try_new(&alias, &title, address)?;
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                store.store(entry).await?;
+                ctx.wallet().store().commit(&wallet_secret).await?;
+                tprintln!(ctx, "added '{alias}' to the address book");
+            }
+            "remove" => {
+                if argv.len() != 1 {
+                    tprintln!(ctx, "usage: address-book remove <alias>");
+                    return Ok(());
+                }
+                let alias = argv.remove(0);
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                store.remove(&alias).await?;
+                ctx.wallet().store().commit(&wallet_secret).await?;
+                tprintln!(ctx, "removed '{alias}' from the address book");
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("list", "List address book entries"),
+                ("add <alias> <address> [<title>]", "Add an entry usable as a recipient of 'send' in place of the address"),
+                ("remove <alias>", "Remove an entry"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
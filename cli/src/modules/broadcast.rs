@@ -1,13 +1,40 @@
 use crate::imports::*;
+use kaspa_wallet_core::tx::UnsignedTransaction;
+use std::path::PathBuf;
+use workflow_store::fs;
 
 #[derive(Default, Handler)]
 #[help("Broadcast signed transaction to the network")]
 pub struct Broadcast;
 
+impl Command for Broadcast {
+    fn arg_spec(&self) -> Option<&'static ArgSpec> {
+        Some(&Self::ARGS)
+    }
+}
+
 impl Broadcast {
-    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
+    pub const ARGS: ArgSpec = ArgSpec::new("broadcast", &[Arg::Required("signed transaction file")]);
+
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
-        ctx.wallet().broadcast().await?;
+        let args = Self::ARGS.parse(&argv)?;
+
+        let filename = PathBuf::from(args.required(0)?);
+        let transactions: Vec<UnsignedTransaction> = fs::read_json(&filename).await?;
+        let results = ctx.wallet().broadcast(&transactions).await?;
+
+        tprintln!(ctx, "");
+        for (index, result) in results.iter().enumerate() {
+            tprintln!(ctx, "{:>4}  {}  {}", index + 1, result.id, result.status);
+        }
+        tprintln!(ctx, "");
+
+        let failed = results.iter().filter(|result| !result.status.is_ok()).count();
+        if failed > 0 {
+            return Err(Error::custom(format!("{failed} of {} transactions were not broadcast", results.len())));
+        }
+
         Ok(())
     }
 }
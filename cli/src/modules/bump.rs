@@ -0,0 +1,36 @@
+use crate::imports::*;
+use kaspa_consensus_core::tx::TransactionId;
+
+#[derive(Default, Handler)]
+#[help("Replace a pending transaction that is no longer in the mempool with a higher fee transaction")]
+pub struct Bump;
+
+impl Command for Bump {
+    fn arg_spec(&self) -> Option<&'static ArgSpec> {
+        Some(&Self::ARGS)
+    }
+}
+
+impl Bump {
+    pub const ARGS: ArgSpec = ArgSpec::new("bump", &[Arg::Required("txid"), Arg::Required("additional fee")]);
+
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        let args = Self::ARGS.parse(&argv)?;
+
+        let account = ctx.wallet().account()?;
+
+        let transaction_id = TransactionId::from_hex(args.required(0)?)?;
+        let additional_fee_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(args.get(1))?;
+        let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+        let abortable = Abortable::default();
+
+        let (summary, ids) =
+            account.bump_fee(transaction_id, additional_fee_sompi, wallet_secret, payment_secret, &abortable).await?;
+
+        tprintln!(ctx, "Bump - {summary}");
+        ctx.report_mempool_acceptance(&ids).await;
+
+        Ok(())
+    }
+}
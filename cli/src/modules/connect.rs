@@ -4,6 +4,8 @@ use crate::imports::*;
 #[help("Connect to a Kaspa network")]
 pub struct Connect;
 
+impl Command for Connect {}
+
 impl Connect {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
@@ -0,0 +1,64 @@
+use crate::imports::*;
+
+#[derive(Default, Handler)]
+#[help("Show UTXO fragmentation of the account and offer to consolidate it (sweep)")]
+pub struct Consolidate;
+
+impl Command for Consolidate {
+    fn arg_spec(&self) -> Option<&'static ArgSpec> {
+        Some(&Self::ARGS)
+    }
+}
+
+impl Consolidate {
+    pub const ARGS: ArgSpec = ArgSpec::new("consolidate", &[Arg::Flag("yes")]);
+
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        let args = Self::ARGS.parse(&argv)?;
+
+        let account = ctx.wallet().account()?;
+        let report = account.utxo_fragmentation_report()?;
+
+        tprintln!(ctx, "UTXOs: {} ({} KAS)", report.utxo_count, sompi_to_kaspa_string(report.total_amount));
+        for bucket in report.distribution.iter().filter(|bucket| bucket.count > 0) {
+            let range = match bucket.max {
+                Some(max) => format!("{} - {} KAS", sompi_to_kaspa_string(bucket.min), sompi_to_kaspa_string(max)),
+                None => format!(">= {} KAS", sompi_to_kaspa_string(bucket.min)),
+            };
+            tprintln!(ctx, "    {range}: {} UTXOs ({} KAS)", bucket.count, sompi_to_kaspa_string(bucket.amount));
+        }
+        tprintln!(ctx, "Maximum UTXOs per transaction: {}", report.max_inputs_per_transaction);
+        tprintln!(ctx, "Estimated consolidation fees: {} KAS", sompi_to_kaspa_string(report.consolidation_fees));
+        tprintln!(
+            ctx,
+            "Estimated fee saving when spending the entire balance: {} KAS",
+            sompi_to_kaspa_string(report.estimated_fee_saving)
+        );
+
+        if !report.should_consolidate {
+            tprintln!(ctx, "\nConsolidation is not required, the entire balance can be spent by a single transaction.");
+            return Ok(());
+        }
+
+        tprintln!(ctx, "\nConsolidation is recommended, spending the entire balance requires multiple transactions.");
+        if !args.flag("yes") {
+            if !ctx.is_interactive() {
+                tprintln!(ctx, "Use 'consolidate --yes' to consolidate the UTXOs.");
+                return Ok(());
+            }
+            let answer = ctx.ask(false, "Consolidate UTXOs now by sweeping the account? [y/N]: ").await?;
+            if !answer.trim().eq_ignore_ascii_case("y") {
+                return Ok(());
+            }
+        }
+
+        let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+        let abortable = Abortable::default();
+        let (summary, _ids) = account.sweep(wallet_secret, payment_secret, &abortable, None).await?;
+
+        tprintln!(ctx, "Sweep: {summary}");
+
+        Ok(())
+    }
+}
@@ -1,13 +1,41 @@
 use crate::imports::*;
+use std::path::PathBuf;
+use workflow_store::fs;
 
 #[derive(Default, Handler)]
+#[help("Create an unsigned transaction file for offline signing")]
 pub struct CreateUnsignedTx;
 
+impl Command for CreateUnsignedTx {
+    fn arg_spec(&self) -> Option<&'static ArgSpec> {
+        Some(&Self::ARGS)
+    }
+}
+
 impl CreateUnsignedTx {
-    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
+    pub const ARGS: ArgSpec = ArgSpec::new(
+        "create-unsigned-tx",
+        &[Arg::Required("file"), Arg::Required("address"), Arg::Required("amount"), Arg::Optional("priority fee")],
+    );
+
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
-        let _account = ctx.wallet().account()?;
-        // TODO account.create_unsigned_transaction().await?;
+        let args = Self::ARGS.parse(&argv)?;
+
+        let account = ctx.wallet().account()?;
+
+        let filename = PathBuf::from(args.required(0)?);
+        let address = Address::try_from(args.required(1)?.as_str())?;
+        let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(args.get(2))?;
+        let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(args.get(3))?.unwrap_or(0);
+        let outputs = PaymentOutputs::from((address, amount_sompi));
+        let abortable = Abortable::default();
+
+        let transactions = account.create_unsigned_transactions(outputs.into(), priority_fee_sompi.into(), None, &abortable).await?;
+        fs::write_json(&filename, &transactions).await?;
+
+        tprintln!(ctx, "{} unsigned transaction(s) written to '{}'", transactions.len(), filename.display());
+
         Ok(())
     }
 }
@@ -0,0 +1,32 @@
+use crate::imports::*;
+
+#[derive(Default, Handler)]
+#[help("Set the fiat currency of the balance display (usd|eur|...|none)")]
+pub struct Currency;
+
+impl Command for Currency {}
+
+impl Currency {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if let Some(currency) = argv.first() {
+            let currency = currency.trim().to_lowercase();
+            if currency == "none" {
+                ctx.wallet().settings().set(WalletSettings::Currency, "").await?;
+                tprintln!(ctx, "Fiat conversion is disabled");
+            } else if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(Error::custom(format!("invalid currency code '{currency}', expecting an ISO 4217 code (usd|eur|...)")));
+            } else {
+                ctx.wallet().settings().set(WalletSettings::Currency, &currency).await?;
+                tprintln!(ctx, "Setting fiat currency to: {}", currency.to_uppercase());
+            }
+        } else if let Some(fiat) = ctx.fiat() {
+            tprintln!(ctx, "Current fiat currency is: {}", fiat.currency().to_uppercase());
+        } else {
+            tprintln!(ctx, "Fiat conversion is disabled");
+        }
+
+        Ok(())
+    }
+}
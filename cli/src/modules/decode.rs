@@ -0,0 +1,212 @@
+use crate::imports::*;
+use kaspa_consensus_core::standard::standard_script_address;
+use kaspa_consensus_core::tx::{PopulatedTransaction, Transaction, UtxoEntry, VerifiableTransaction};
+use kaspa_wallet_core::tx::{MassCalculator, UnsignedTransaction};
+use kaspa_wallet_core::utxo::NetworkParams;
+
+#[derive(Default, Handler)]
+#[help("Decode and display a raw transaction (wire format hex or JSON)")]
+pub struct Decode;
+
+impl Command for Decode {}
+
+impl Decode {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            tprintln!(ctx, "usage: decode <hex|json>");
+            return Ok(());
+        }
+
+        let transactions = DecodedTransaction::try_parse(&argv.join(" "))?;
+        let default_network_id = ctx.wallet().network_id().unwrap_or_else(|_| NetworkId::new(NetworkType::Mainnet));
+        for transaction in transactions {
+            tprintln!(ctx, "");
+            for line in transaction.format(transaction.network_id.unwrap_or(default_network_id)) {
+                tprintln!(ctx, "{line}");
+            }
+        }
+        tprintln!(ctx, "");
+
+        Ok(())
+    }
+}
+
+/// A transaction decoded by the `decode` command along with the UTXO entries
+/// it spends and its network, if these are known (ie. when decoding an unsigned
+/// transaction file produced by `create-unsigned-tx`).
+pub struct DecodedTransaction {
+    pub transaction: Transaction,
+    pub entries: Option<Vec<UtxoEntry>>,
+    pub network_id: Option<NetworkId>,
+}
+
+impl From<Transaction> for DecodedTransaction {
+    fn from(mut transaction: Transaction) -> Self {
+        transaction.finalize();
+        Self { transaction, entries: None, network_id: None }
+    }
+}
+
+impl From<UnsignedTransaction> for DecodedTransaction {
+    fn from(unsigned: UnsignedTransaction) -> Self {
+        let network_id = Some(unsigned.network_id);
+        Self { entries: Some(unsigned.entries), network_id, ..Self::from(unsigned.transaction) }
+    }
+}
+
+impl DecodedTransaction {
+    /// Parses a transaction encoded in the canonical wire format as hex, or a JSON
+    /// transaction, unsigned transaction or list of unsigned transactions.
+    pub fn try_parse(input: &str) -> Result<Vec<Self>> {
+        let input = input.trim();
+        if input.starts_with('[') {
+            let transactions: Vec<UnsignedTransaction> =
+                serde_json::from_str(input).map_err(|err| Error::custom(format!("invalid transaction JSON: {err}")))?;
+            Ok(transactions.into_iter().map(Self::from).collect())
+        } else if input.starts_with('{') {
+            if let Ok(unsigned) = serde_json::from_str::<UnsignedTransaction>(input) {
+                return Ok(vec![unsigned.into()]);
+            }
+            let transaction: Transaction =
+                serde_json::from_str(input).map_err(|err| Error::custom(format!("invalid transaction JSON: {err}")))?;
+            Ok(vec![transaction.into()])
+        } else {
+            let bytes = Vec::<u8>::from_hex(input).map_err(|err| Error::custom(format!("invalid transaction hex: {err}")))?;
+            let transaction =
+                Transaction::from_wire(&bytes).map_err(|err| Error::custom(format!("invalid transaction encoding: {err}")))?;
+            Ok(vec![transaction.into()])
+        }
+    }
+
+    /// Formats a human readable breakdown of the transaction, using the address
+    /// prefix and currency suffix of `network_id`.
+    pub fn format(&self, network_id: NetworkId) -> Vec<String> {
+        let tx = &self.transaction;
+        let network_type = NetworkType::from(network_id);
+        let prefix = AddressPrefix::from(network_id);
+        let kas = |sompi: u64| sompi_to_kaspa_string_with_suffix(sompi, &network_type);
+        let address = |entry_spk| {
+            standard_script_address(entry_spk, prefix)
+                .map(|address| address.to_string())
+                .unwrap_or_else(|| "non-standard script".to_string())
+        };
+        let entries = self.entries.as_ref().filter(|entries| entries.len() == tx.inputs.len());
+
+        let mut lines = vec![
+            format!("id: {}", tx.id()),
+            format!("version: {}  lock time: {}  subnetwork: {}", tx.version, tx.lock_time, tx.subnetwork_id),
+        ];
+        if tx.gas != 0 || !tx.payload.is_empty() {
+            lines.push(format!("gas: {}  payload: {} bytes", tx.gas, tx.payload.len()));
+        }
+
+        lines.push(format!("inputs: {}", tx.inputs.len()));
+        for (index, input) in tx.inputs.iter().enumerate() {
+            let outpoint = &input.previous_outpoint;
+            let mut line = format!("  #{index} {}:{}", outpoint.transaction_id, outpoint.index);
+            if let Some(entry) = entries.map(|entries| &entries[index]) {
+                line += &format!(" {} {}", kas(entry.amount), address(&entry.script_public_key));
+            }
+            lines.push(line);
+        }
+
+        lines.push(format!("outputs: {}", tx.outputs.len()));
+        for (index, output) in tx.outputs.iter().enumerate() {
+            lines.push(format!("  #{index} {} {}", kas(output.value), address(&output.script_public_key)));
+        }
+
+        if let Some(entries) = entries {
+            let populated = PopulatedTransaction::new(tx, entries.clone());
+            match (populated.total_input_amount(), populated.total_output_value()) {
+                (Ok(total_in), Ok(total_out)) if total_in >= total_out => lines.push(format!("fee: {}", kas(total_in - total_out))),
+                (Ok(_), Ok(_)) => lines.push("fee: outputs exceed inputs".to_string()),
+                (Err(err), _) | (_, Err(err)) => lines.push(format!("fee: {err}")),
+            }
+        }
+
+        let mass = if tx.mass() != 0 {
+            tx.mass()
+        } else {
+            MassCalculator::new(&network_id.into(), &NetworkParams::from(network_id)).calc_mass_for_transaction(tx)
+        };
+        lines.push(format!("mass: {mass}"));
+
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::Version;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput};
+
+    fn p2pk(key: u8) -> ScriptPublicKey {
+        let mut script = vec![0x20];
+        script.extend([key; 32]);
+        script.push(0xac);
+        ScriptPublicKey::from_vec(0, script)
+    }
+
+    fn test_transaction() -> Transaction {
+        Transaction::new(
+            0,
+            vec![TransactionInput::new(TransactionOutpoint::new(TransactionId::from_u64_word(7), 3), vec![], 0, 1)],
+            vec![TransactionOutput::new(150_000_000, p2pk(1)), TransactionOutput::new(1000, ScriptPublicKey::from_vec(0, vec![0x51]))],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_decode_wire_hex() {
+        let tx = test_transaction();
+        let decoded = DecodedTransaction::try_parse(&tx.to_wire().to_hex()).unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].transaction, tx);
+
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+        let lines = decoded[0].format(network_id);
+        let address = Address::new(AddressPrefix::Testnet, Version::PubKey, &[1u8; 32]);
+        assert_eq!(lines[0], format!("id: {}", tx.id()));
+        assert_eq!(lines[2], "inputs: 1");
+        assert_eq!(lines[3], format!("  #0 {}:3", TransactionId::from_u64_word(7)));
+        assert_eq!(lines[4], "outputs: 2");
+        assert_eq!(lines[5], format!("  #0 1.5 TKAS {address}"));
+        assert_eq!(lines[6], "  #1 0.00001 TKAS non-standard script");
+        assert!(lines[7].starts_with("mass: "));
+    }
+
+    #[test]
+    fn test_decode_json() {
+        let tx = test_transaction();
+        let decoded = DecodedTransaction::try_parse(&serde_json::to_string(&tx).unwrap()).unwrap();
+        assert_eq!(decoded[0].transaction, tx);
+        assert!(decoded[0].entries.is_none());
+
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+        let entries = vec![UtxoEntry::new(200_000_000, p2pk(2), 0, false)];
+        let unsigned = UnsignedTransaction::new(network_id, tx.clone(), entries, vec![]);
+        let decoded = DecodedTransaction::try_parse(&serde_json::to_string(&vec![unsigned]).unwrap()).unwrap();
+        assert_eq!(decoded[0].network_id, Some(network_id));
+
+        let lines = decoded[0].format(network_id);
+        let address = Address::new(AddressPrefix::Testnet, Version::PubKey, &[2u8; 32]);
+        assert_eq!(lines[3], format!("  #0 {}:3 2 TKAS {address}", TransactionId::from_u64_word(7)));
+        assert_eq!(lines[7], "fee: 0.49999 TKAS");
+    }
+
+    #[test]
+    fn test_decode_malformed() {
+        let err = |input: &str| DecodedTransaction::try_parse(input).err().unwrap().to_string();
+        assert!(err("zz").starts_with("invalid transaction hex"));
+        assert!(err("0100").starts_with("invalid transaction encoding"));
+        assert!(err("{\"version\": 0}").starts_with("invalid transaction JSON"));
+        assert!(err("[{}]").starts_with("invalid transaction JSON"));
+    }
+}
@@ -4,6 +4,8 @@ use crate::imports::*;
 #[help("Displays the detailed information about the currently selected account.")]
 pub struct Details;
 
+impl Command for Details {}
+
 impl Details {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
@@ -4,6 +4,8 @@ use crate::imports::*;
 #[help("Disconnect from the kaspa network")]
 pub struct Disconnect;
 
+impl Command for Disconnect {}
+
 impl Disconnect {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
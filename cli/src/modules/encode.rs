@@ -0,0 +1,178 @@
+use crate::imports::*;
+use kaspa_consensus_core::constants::{MAX_SOMPI, TX_VERSION};
+use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+use kaspa_consensus_core::tx::{Transaction, TransactionInput, TransactionOutpoint, TransactionOutput};
+use kaspa_txscript::pay_to_address_script;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Default, Handler)]
+#[help("Encode an unsigned transaction from a JSON spec (inline or file) as wire format hex")]
+pub struct Encode;
+
+impl Command for Encode {}
+
+impl Encode {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            tprintln!(ctx, "usage: encode <json|file>");
+            tprintln!(ctx, "");
+            tprintln!(ctx, "spec: {{ \"inputs\": [\"<txid>:<index>\", ...], \"outputs\": [{{ \"address\": \"<address>\", \"amount\": \"<KAS>\" }}, ...], \"payload\": \"<hex>\" }}");
+            tprintln!(ctx, "(inputs and payload are optional)");
+            return Ok(());
+        }
+
+        let input = argv.join(" ");
+        let json = if input.trim_start().starts_with('{') {
+            input
+        } else {
+            workflow_store::fs::read_to_string(&PathBuf::from(&input))
+                .await
+                .map_err(|err| Error::custom(format!("unable to read transaction spec file '{input}': {err}")))?
+        };
+
+        let spec = TransactionSpec::try_parse(&json)?;
+        let transaction = spec.try_into_transaction(ctx.wallet().network_id().ok())?;
+        tprintln!(ctx, "{}", transaction.to_wire().to_hex());
+
+        Ok(())
+    }
+}
+
+/// JSON specification of an unsigned transaction accepted by the `encode` command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TransactionSpec {
+    /// Outpoints spent by the transaction in the compact `txid:index` form.
+    #[serde(default)]
+    pub inputs: Vec<String>,
+    pub outputs: Vec<OutputSpec>,
+    /// Payload of the transaction as hex.
+    #[serde(default)]
+    pub payload: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputSpec {
+    pub address: String,
+    /// Amount in KAS, either as a string or as a number.
+    pub amount: Value,
+}
+
+impl TransactionSpec {
+    pub fn try_parse(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|err| Error::custom(format!("invalid transaction spec: {err}")))
+    }
+
+    /// Builds the unsigned transaction described by the spec. Output addresses must belong
+    /// to `network_id` if one is given, and to the same network otherwise.
+    pub fn try_into_transaction(&self, network_id: Option<NetworkId>) -> Result<Transaction> {
+        if self.outputs.is_empty() {
+            return Err(Error::custom("transaction spec must contain at least one output"));
+        }
+
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|outpoint| {
+                let outpoint = TransactionOutpoint::from_str(outpoint).map_err(|err| Error::custom(err.to_string()))?;
+                Ok(TransactionInput::new(outpoint, vec![], 0, 1))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut prefix = network_id.map(AddressPrefix::from);
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| {
+                let address = Address::try_from(output.address.as_str())?;
+                match prefix {
+                    Some(prefix) if prefix != address.prefix => {
+                        let network = network_id.map(|network_id| network_id.to_string()).unwrap_or_else(|| prefix.to_string());
+                        return Err(Error::custom(format!("address {address} does not belong to network {network}")));
+                    }
+                    _ => prefix = Some(address.prefix),
+                }
+
+                let amount = match &output.amount {
+                    Value::String(amount) => amount.clone(),
+                    amount => amount.to_string(),
+                };
+                let sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(Some(&amount))?;
+                if sompi > MAX_SOMPI {
+                    return Err(Error::custom(format!("amount {amount} of address {address} exceeds the maximum supply")));
+                }
+                Ok(TransactionOutput::new(sompi, pay_to_address_script(&address)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let payload = Vec::<u8>::from_hex(self.payload.trim())
+            .map_err(|err| Error::custom(format!("invalid transaction payload hex: {err}")))?;
+
+        Ok(Transaction::new(TX_VERSION, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::decode::DecodedTransaction;
+    use kaspa_addresses::Version;
+    use kaspa_consensus_core::tx::TransactionId;
+
+    fn spec(outputs: &str) -> String {
+        let outpoint = TransactionOutpoint::new(TransactionId::from_u64_word(7), 3).to_compact_string();
+        format!("{{ \"inputs\": [\"{outpoint}\"], \"outputs\": [{outputs}], \"payload\": \"cafe\" }}")
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let address = Address::new(AddressPrefix::Testnet, Version::PubKey, &[1u8; 32]);
+        let change = Address::new(AddressPrefix::Testnet, Version::ScriptHash, &[2u8; 32]);
+        let json =
+            spec(&format!("{{ \"address\": \"{address}\", \"amount\": \"1.5\" }}, {{ \"address\": \"{change}\", \"amount\": 0.25 }}"));
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+        let transaction = TransactionSpec::try_parse(&json).unwrap().try_into_transaction(Some(network_id)).unwrap();
+
+        let decoded = DecodedTransaction::try_parse(&transaction.to_wire().to_hex()).unwrap();
+        assert_eq!(decoded.len(), 1);
+        let decoded = &decoded[0].transaction;
+        assert_eq!(decoded, &transaction);
+        assert_eq!(decoded.id(), transaction.id());
+        assert_eq!(decoded.inputs[0].previous_outpoint, TransactionOutpoint::new(TransactionId::from_u64_word(7), 3));
+        assert_eq!(
+            decoded.outputs,
+            vec![
+                TransactionOutput::new(150_000_000, pay_to_address_script(&address)),
+                TransactionOutput::new(25_000_000, pay_to_address_script(&change))
+            ]
+        );
+        assert_eq!(decoded.payload, vec![0xca, 0xfe]);
+    }
+
+    #[test]
+    fn test_encode_validation() {
+        let err = |json: &str, network_id: Option<NetworkId>| {
+            TransactionSpec::try_parse(json).and_then(|spec| spec.try_into_transaction(network_id)).err().unwrap().to_string()
+        };
+        let testnet = Address::new(AddressPrefix::Testnet, Version::PubKey, &[1u8; 32]);
+        let mainnet = Address::new(AddressPrefix::Mainnet, Version::PubKey, &[1u8; 32]);
+        let mainnet_id = Some(NetworkId::new(NetworkType::Mainnet));
+
+        assert!(err("{ \"outputs\": [] }", None).contains("at least one output"));
+        assert!(err("{ \"outputs\": [], \"fee\": 1 }", None).starts_with("invalid transaction spec"));
+        assert!(err(&spec("{ \"address\": \"kaspa:invalid\", \"amount\": 1 }"), None).starts_with("The address"));
+        assert!(err(&spec(&format!("{{ \"address\": \"{testnet}\", \"amount\": 1 }}")), mainnet_id)
+            .contains("does not belong to network mainnet"));
+        let mixed = format!("{{ \"address\": \"{mainnet}\", \"amount\": 1 }}, {{ \"address\": \"{testnet}\", \"amount\": 1 }}");
+        assert!(err(&spec(&mixed), None).contains("does not belong to network"));
+        assert!(err(&spec(&format!("{{ \"address\": \"{mainnet}\", \"amount\": 0 }}")), None).contains("must not be a zero"));
+        assert!(err(&spec(&format!("{{ \"address\": \"{mainnet}\", \"amount\": \"abc\" }}")), None).contains("abc"));
+        assert!(err(&spec(&format!("{{ \"address\": \"{mainnet}\", \"amount\": 30000000000 }}")), None).contains("maximum supply"));
+        let bad_outpoint = format!("{{ \"inputs\": [\"00:1\"], \"outputs\": [{{ \"address\": \"{mainnet}\", \"amount\": 1 }}] }}");
+        assert!(err(&bad_outpoint, None).contains("outpoint"));
+    }
+}
@@ -5,19 +5,23 @@ use kaspa_wallet_core::tx::PaymentDestination;
 #[help("Estimate the fees for a transaction of a given amount")]
 pub struct Estimate;
 
+impl Command for Estimate {
+    fn arg_spec(&self) -> Option<&'static ArgSpec> {
+        Some(&Self::ARGS)
+    }
+}
+
 impl Estimate {
+    pub const ARGS: ArgSpec = ArgSpec::new("estimate", &[Arg::Required("amount"), Arg::Optional("priority fee")]);
+
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        let args = Self::ARGS.parse(&argv)?;
 
         let account = ctx.wallet().account()?;
 
-        if argv.is_empty() {
-            tprintln!(ctx, "usage: estimate <amount> [<priority fee>]");
-            return Ok(());
-        }
-
-        let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.first())?;
-        let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(1))?.unwrap_or(0);
+        let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(args.get(0))?;
+        let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(args.get(1))?.unwrap_or(0);
         let abortable = Abortable::default();
 
         // just use any address for an estimate (change address)
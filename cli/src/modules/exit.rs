@@ -4,6 +4,12 @@ use crate::imports::*;
 #[help("Exit the application")]
 pub struct Exit;
 
+impl Command for Exit {
+    fn aliases(&self) -> &'static [&'static str] {
+        &["quit"]
+    }
+}
+
 impl Exit {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> cli::Result<()> {
         let term = ctx.term();
@@ -5,12 +5,14 @@ use kaspa_wallet_core::account::{multisig::MultiSig, Account, MULTISIG_ACCOUNT_K
 #[help("Export transactions, a wallet or a private key")]
 pub struct Export;
 
+impl Command for Export {}
+
 impl Export {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
 
         if argv.is_empty() || argv.first() == Some(&"help".to_string()) {
-            tprintln!(ctx, "usage: export [mnemonic]");
+            tprintln!(ctx, "usage: export [mnemonic|descriptor]");
             return Ok(());
         }
 
@@ -25,17 +27,32 @@ impl Export {
                     export_single_key_account(ctx, account).await
                 }
             }
+            "descriptor" | "xpub" => {
+                let account = ctx.account().await?;
+                export_account_descriptor(ctx, account).await
+            }
             _ => Err(format!("Invalid argument: {}", what).into()),
         }
     }
 }
 
+async fn export_account_descriptor(ctx: Arc<KaspaCli>, account: Arc<dyn Account>) -> Result<()> {
+    let wallet_secret = Secret::new(ctx.ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
+    if wallet_secret.as_ref().is_empty() {
+        return Err(Error::WalletSecretRequired);
+    }
+
+    let descriptor = ctx.wallet().export_account_descriptor(&wallet_secret, &account).await?;
+    tprintln!(ctx, "{}", serde_json::to_string_pretty(&descriptor)?);
+    Ok(())
+}
+
 async fn export_multisig_account(ctx: Arc<KaspaCli>, account: Arc<MultiSig>) -> Result<()> {
     match &account.prv_key_data_ids() {
         None => Err(Error::KeyDataNotFound),
         Some(v) if v.is_empty() => Err(Error::KeyDataNotFound),
         Some(prv_key_data_ids) => {
-            let wallet_secret = Secret::new(ctx.term().ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
+            let wallet_secret = Secret::new(ctx.ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
             if wallet_secret.as_ref().is_empty() {
                 return Err(Error::WalletSecretRequired);
             }
@@ -73,7 +90,7 @@ async fn export_multisig_account(ctx: Arc<KaspaCli>, account: Arc<MultiSig>) ->
 async fn export_single_key_account(ctx: Arc<KaspaCli>, account: Arc<dyn Account>) -> Result<()> {
     let prv_key_data_id = account.prv_key_data_id()?;
 
-    let wallet_secret = Secret::new(ctx.term().ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
+    let wallet_secret = Secret::new(ctx.ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
     if wallet_secret.as_ref().is_empty() {
         return Err(Error::WalletSecretRequired);
     }
@@ -81,7 +98,7 @@ async fn export_single_key_account(ctx: Arc<KaspaCli>, account: Arc<dyn Account>
     let prv_key_data = ctx.store().as_prv_key_data_store()?.load_key_data(&wallet_secret, prv_key_data_id).await?;
     let Some(keydata) = prv_key_data else { return Err(Error::KeyDataNotFound) };
     let payment_secret = if keydata.payload.is_encrypted() {
-        let payment_secret = Secret::new(ctx.term().ask(true, "Enter payment password: ").await?.trim().as_bytes().to_vec());
+        let payment_secret = Secret::new(ctx.ask(true, "Enter payment password: ").await?.trim().as_bytes().to_vec());
         if payment_secret.as_ref().is_empty() {
             return Err(Error::PaymentSecretRequired);
         } else {
@@ -93,12 +110,26 @@ async fn export_single_key_account(ctx: Arc<KaspaCli>, account: Arc<dyn Account>
 
     let prv_key_data = keydata.payload.decrypt(payment_secret.as_ref())?;
     let mnemonic = prv_key_data.as_ref().as_mnemonic()?;
+    let requires_payment_secret = payment_secret.is_some() && prv_key_data.as_ref().is_payment_secret_bip39_passphrase();
 
     match mnemonic {
         None => {
             tprintln!(ctx, "mnemonic is not available for this private key");
         }
-        Some(mnemonic) if payment_secret.is_none() => {
+        Some(mnemonic) if prv_key_data.as_ref().has_bip39_passphrase() => {
+            tpara!(
+                ctx,
+                "\
+                                IMPORTANT: to recover your private key using this mnemonic in the future \
+                                you will need your bip39 mnemonic passphrase.",
+            );
+            tprintln!(ctx, "");
+            tprintln!(ctx, "mnemonic:");
+            tprintln!(ctx, "");
+            tprintln!(ctx, "{}", mnemonic.phrase());
+            tprintln!(ctx, "");
+        }
+        Some(mnemonic) if !requires_payment_secret => {
             tprintln!(ctx, "mnemonic:");
             tprintln!(ctx, "");
             tprintln!(ctx, "{}", mnemonic.phrase());
@@ -4,6 +4,8 @@ use crate::imports::*;
 #[help("Basic command guide for using this software.")]
 pub struct Guide;
 
+impl Command for Guide {}
+
 impl Guide {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> cli::Result<()> {
         let guide = include_str!("guide.txt");
@@ -4,6 +4,8 @@ use crate::imports::*;
 #[help("Displays this help message")]
 pub struct Help;
 
+impl Command for Help {}
+
 impl Help {
     async fn main(self: Arc<Self>, dyn_ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
         let term = dyn_ctx.term();
@@ -11,8 +13,20 @@ impl Help {
 
         let ctx = dyn_ctx.clone().downcast_arc::<KaspaCli>()?;
         let handlers = ctx.handlers().collect();
-        let handlers =
-            handlers.into_iter().filter_map(|h| h.verb(dyn_ctx).map(|verb| (verb, get_handler_help(h, dyn_ctx)))).collect::<Vec<_>>();
+        let handlers = handlers
+            .into_iter()
+            .filter_map(|h| {
+                h.verb(dyn_ctx).map(|verb| {
+                    let help = get_handler_help(h, dyn_ctx);
+                    let aliases = ctx.aliases().aliases_of(verb);
+                    if aliases.is_empty() {
+                        (verb, help)
+                    } else {
+                        (verb, format!("{help} (alias: {})", aliases.join(", ")))
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
 
         term.help(&handlers, None)?;
 
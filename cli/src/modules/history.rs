@@ -1,11 +1,18 @@
 use crate::imports::*;
 use kaspa_consensus_core::tx::TransactionId;
+use kaspa_wallet_core::account::history::{HistoryDateRange, HistoryExportFormat};
 use kaspa_wallet_core::error::Error as WalletError;
 use kaspa_wallet_core::storage::Binding;
 #[derive(Default, Handler)]
 #[help("Display transaction history")]
 pub struct History;
 
+impl Command for History {
+    fn aliases(&self) -> &'static [&'static str] {
+        &["tx"]
+    }
+}
+
 impl History {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
@@ -45,6 +52,19 @@ impl History {
 
                 return Ok(());
             }
+            "export" => {
+                if argv.is_empty() {
+                    tprintln!(ctx, "usage: history export <csv|json> [<from YYYY-MM-DD> [<to YYYY-MM-DD>]]");
+                    return Ok(());
+                }
+
+                let format = argv[0].parse::<HistoryExportFormat>()?;
+                let range = HistoryDateRange::from_dates(argv.get(1).map(String::as_str), argv.get(2).map(String::as_str))?;
+                let history = account.export_history(format, range).await?;
+                history.lines().for_each(|line| tprintln!(ctx, "{line}"));
+
+                return Ok(());
+            }
             "list" => {
                 let last = if argv.is_empty() { None } else { argv[0].parse::<usize>().ok() };
                 (last, false)
@@ -91,7 +111,7 @@ impl History {
 
         while let Some(id) = ids.try_next().await? {
             if index >= skip {
-                if index > 0 && index % page == 0 {
+                if ctx.is_interactive() && index > 0 && index % page == 0 {
                     tprintln!(ctx);
                     let prompt = format!(
                         "Displaying transactions {} to {} of {} (press any key to continue, 'Q' to abort)",
@@ -141,6 +161,10 @@ impl History {
                 ("list [<last N transactions>]", "List transactions"),
                 ("details [<last N transactions>]", "List transactions with UTXO details"),
                 ("lookup <transaction id>", "Lookup transaction in the history"),
+                (
+                    "export <csv|json> [<from> [<to>]]",
+                    "Export transactions, optionally within UTC dates (YYYY-MM-DD), with UTC timestamps",
+                ),
             ],
             None,
         )?;
@@ -4,6 +4,12 @@ use crate::imports::*;
 #[help("List wallet accounts and their balances")]
 pub struct List;
 
+impl Command for List {
+    fn aliases(&self) -> &'static [&'static str] {
+        &["ls", "bal"]
+    }
+}
+
 impl List {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
@@ -21,3 +27,130 @@ impl List {
         Ok(())
     }
 }
+
+/// Account entry of the `list` output
+pub struct ListAccount {
+    pub name: String,
+    /// `None` if the account balance is not yet available
+    pub balance: Option<(u64, u64)>,
+    pub mature_utxo_count: usize,
+    pub pending_utxo_count: usize,
+    pub receive_address: String,
+}
+
+impl ListAccount {
+    fn utxo_info(&self) -> String {
+        match (self.mature_utxo_count, self.pending_utxo_count) {
+            (0, 0) => "".to_string(),
+            (mature, 0) => format!("{} UTXOs", mature.separated_string()),
+            (0, pending) => format!("{} UTXOs pending", pending.separated_string()),
+            (mature, pending) => format!("{} UTXOs, {} UTXOs pending", mature.separated_string(), pending.separated_string()),
+        }
+    }
+}
+
+/// Private key entry of the `list` output along with its accounts
+pub struct ListKey {
+    pub key: String,
+    pub accounts: Vec<ListAccount>,
+}
+
+/// Formats the `list` output, aligning account names and mature balances in columns
+/// and appending a totals row. Columns are padded before styling, so the alignment
+/// holds regardless of colors being enabled (see [`workflow_log::set_colors_enabled`]).
+pub fn format_list(keys: &[ListKey], network_type: &NetworkType) -> Vec<String> {
+    let suffix = kaspa_suffix(network_type);
+    let accounts = keys.iter().flat_map(|key| key.accounts.iter());
+    let mature = accounts.clone().filter_map(|account| account.balance.map(|(mature, _)| mature)).sum::<u64>();
+    let pending = accounts.clone().filter_map(|account| account.balance.map(|(_, pending)| pending)).sum::<u64>();
+    let total = sompi_to_kaspa_string(mature);
+
+    let amount = |account: &ListAccount| account.balance.map(|(mature, _)| sompi_to_kaspa_string(mature)).unwrap_or("N/A".to_string());
+    let name_width = accounts.clone().map(|account| account.name.chars().count()).chain(["Total".len()]).max().unwrap_or_default();
+    let amount_width = accounts.clone().map(|account| amount(account).len()).chain([total.len()]).max().unwrap_or_default();
+
+    let pending_info = |pending: u64| {
+        (pending > 0).then(|| format!(" {}", style(format!("({} {suffix} pending)", sompi_to_kaspa_string(pending))).dim()))
+    };
+
+    let mut lines = vec![];
+    for key in keys {
+        lines.push(format!("• {}", style(&key.key).dim()));
+        for account in key.accounts.iter() {
+            let mut line = format!(
+                "    • {}  {} {suffix}",
+                style(account.name.pad_to_width(name_width)).blue(),
+                amount(account).pad_to_width_with_alignment(amount_width, pad::Alignment::Right)
+            );
+            if let Some(pending) = account.balance.and_then(|(_, pending)| pending_info(pending)) {
+                line.push_str(&pending);
+            }
+            let utxo_info = account.utxo_info();
+            if !utxo_info.is_empty() {
+                line.push_str(&format!("   {}", style(utxo_info).dim()));
+            }
+            lines.push(line);
+            lines.push(format!("      {}", style(&account.receive_address).blue()));
+        }
+    }
+
+    if keys.iter().any(|key| !key.accounts.is_empty()) {
+        let mut line = format!(
+            "      {}  {} {suffix}",
+            style("Total".pad_to_width(name_width)).bold(),
+            style(total.pad_to_width_with_alignment(amount_width, pad::Alignment::Right)).bold()
+        );
+        if let Some(pending) = pending_info(pending) {
+            line.push_str(&pending);
+        }
+        lines.push(line);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(name: &str, balance: Option<(u64, u64)>, mature_utxo_count: usize, receive_address: &str) -> ListAccount {
+        ListAccount {
+            name: name.to_string(),
+            balance,
+            mature_utxo_count,
+            pending_utxo_count: 0,
+            receive_address: receive_address.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_list() {
+        workflow_log::set_colors_enabled(false);
+
+        let keys = vec![
+            ListKey {
+                key: "main (0123abcd)".to_string(),
+                accounts: vec![
+                    account("savings", Some((1_234_500_000_000, 0)), 12, "kaspatest:qq0"),
+                    account("daily", Some((50_000_000, 200_000_000)), 1, "kaspatest:qq1"),
+                ],
+            },
+            ListKey { key: "cold (4567ef01)".to_string(), accounts: vec![account("vault-account", None, 0, "kaspatest:qq2")] },
+        ];
+
+        let expected = [
+            "• main (0123abcd)",
+            "    • savings          12,345 TKAS   12 UTXOs",
+            "      kaspatest:qq0",
+            "    • daily               0.5 TKAS (2 TKAS pending)   1 UTXOs",
+            "      kaspatest:qq1",
+            "• cold (4567ef01)",
+            "    • vault-account       N/A TKAS",
+            "      kaspatest:qq2",
+            "      Total          12,345.5 TKAS (2 TKAS pending)",
+        ];
+        assert_eq!(format_list(&keys, &NetworkType::Testnet), expected);
+
+        assert!(format_list(&[], &NetworkType::Mainnet).is_empty());
+    }
+}
@@ -0,0 +1,30 @@
+use crate::imports::*;
+
+#[derive(Default, Handler)]
+#[help("Sign a message with the private key of the given account address")]
+pub struct Messagesign;
+
+impl Command for Messagesign {
+    fn arg_spec(&self) -> Option<&'static ArgSpec> {
+        Some(&Self::ARGS)
+    }
+}
+
+impl Messagesign {
+    pub const ARGS: ArgSpec = ArgSpec::new("messagesign", &[Arg::Required("kaspa_address")]);
+
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        let args = Self::ARGS.parse(&argv)?;
+
+        let account = ctx.wallet().account()?;
+        let address = Address::try_from(args.required(0)?.as_str())?;
+        let message = ctx.ask(false, "Message: ").await?;
+        let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+
+        let signature = account.sign_message(&address, &message, wallet_secret, payment_secret).await?;
+        tprintln!(ctx, "Signature: {}", faster_hex::hex_string(signature.as_slice()));
+
+        Ok(())
+    }
+}
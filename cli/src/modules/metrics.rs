@@ -1,5 +1,6 @@
 use crate::imports::*;
-use kaspa_metrics_core::{Metrics as MetricsProcessor, MetricsSinkFn};
+use kaspa_metrics_core::{data::as_data_size, Metrics as MetricsProcessor, MetricsSinkFn};
+use kaspa_rpc_core::{GetMetricsResponse, RpcServiceState};
 use workflow_core::runtime::is_nw;
 
 #[derive(Describe, Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq, Ord, PartialOrd)]
@@ -22,6 +23,8 @@ pub struct Metrics {
     metrics: Arc<MetricsProcessor>,
 }
 
+impl Command for Metrics {}
+
 impl Default for Metrics {
     fn default() -> Self {
         Metrics {
@@ -108,3 +111,201 @@ impl Metrics {
 
     // --- samplers
 }
+
+/// Formats the sections of a `get-metrics` response as an aligned table, with
+/// a title line per section followed by one `label  value` line per metric.
+pub fn format_metrics_table(metrics: &GetMetricsResponse) -> Vec<String> {
+    let bytes = |bytes: u64| as_data_size(bytes as f64, true);
+    let count = |count: u64| count.separated_string();
+    let mut sections: Vec<(&str, Vec<(String, String)>)> = vec![];
+
+    if let Some(process) = &metrics.process_metrics {
+        let mut rows = vec![
+            ("Resident set size".to_string(), bytes(process.resident_set_size)),
+            ("Virtual memory".to_string(), bytes(process.virtual_memory_size)),
+            ("CPU cores".to_string(), count(process.core_num as u64)),
+            ("CPU usage".to_string(), format!("{:.2} %", process.cpu_usage / process.core_num.max(1) as f32 * 100.0)),
+            ("File descriptors".to_string(), count(process.fd_num as u64)),
+            ("Disk read".to_string(), bytes(process.disk_io_read_bytes)),
+            ("Disk write".to_string(), bytes(process.disk_io_write_bytes)),
+            ("Disk read rate".to_string(), format!("{}/s", bytes(process.disk_io_read_per_sec as u64))),
+            ("Disk write rate".to_string(), format!("{}/s", bytes(process.disk_io_write_per_sec as u64))),
+        ];
+        rows.extend(process.services.iter().map(|service| {
+            let state = match service.state {
+                RpcServiceState::Registered => "registered",
+                RpcServiceState::Running => "running",
+                RpcServiceState::Stopped => "stopped",
+                RpcServiceState::Failed => "failed",
+            };
+            (format!("Service {}", service.ident), state.to_string())
+        }));
+        sections.push(("Process", rows));
+    }
+
+    if let Some(connections) = &metrics.connection_metrics {
+        sections.push((
+            "Connections",
+            vec![
+                ("wRPC Borsh live connections".to_string(), count(connections.borsh_live_connections as u64)),
+                ("wRPC Borsh connection attempts".to_string(), count(connections.borsh_connection_attempts)),
+                ("wRPC Borsh handshake failures".to_string(), count(connections.borsh_handshake_failures)),
+                ("wRPC JSON live connections".to_string(), count(connections.json_live_connections as u64)),
+                ("wRPC JSON connection attempts".to_string(), count(connections.json_connection_attempts)),
+                ("wRPC JSON handshake failures".to_string(), count(connections.json_handshake_failures)),
+                ("P2P active peers".to_string(), count(connections.active_peers as u64)),
+            ],
+        ));
+    }
+
+    if let Some(bandwidth) = &metrics.bandwidth_metrics {
+        sections.push((
+            "Bandwidth",
+            vec![
+                ("wRPC Borsh sent".to_string(), bytes(bandwidth.borsh_bytes_tx)),
+                ("wRPC Borsh received".to_string(), bytes(bandwidth.borsh_bytes_rx)),
+                ("wRPC JSON sent".to_string(), bytes(bandwidth.json_bytes_tx)),
+                ("wRPC JSON received".to_string(), bytes(bandwidth.json_bytes_rx)),
+                ("P2P sent".to_string(), bytes(bandwidth.p2p_bytes_tx)),
+                ("P2P received".to_string(), bytes(bandwidth.p2p_bytes_rx)),
+                ("gRPC sent".to_string(), bytes(bandwidth.grpc_bytes_tx)),
+                ("gRPC received".to_string(), bytes(bandwidth.grpc_bytes_rx)),
+            ],
+        ));
+    }
+
+    if let Some(consensus) = &metrics.consensus_metrics {
+        let sync_eta = consensus.node_sync_eta_seconds.map(|secs| format!("{} s", count(secs))).unwrap_or_else(|| "-".to_string());
+        sections.push((
+            "Consensus",
+            vec![
+                ("Blocks submitted".to_string(), count(consensus.node_blocks_submitted_count)),
+                ("Headers processed".to_string(), count(consensus.node_headers_processed_count)),
+                ("Dependencies processed".to_string(), count(consensus.node_dependencies_processed_count)),
+                ("Bodies processed".to_string(), count(consensus.node_bodies_processed_count)),
+                ("Transactions processed".to_string(), count(consensus.node_transactions_processed_count)),
+                ("Chain blocks processed".to_string(), count(consensus.node_chain_blocks_processed_count)),
+                ("Mass processed".to_string(), count(consensus.node_mass_processed_count)),
+                ("Database blocks".to_string(), count(consensus.node_database_blocks_count)),
+                ("Database headers".to_string(), count(consensus.node_database_headers_count)),
+                ("Mempool size".to_string(), format!("{} txs", count(consensus.network_mempool_size))),
+                ("Tip hashes".to_string(), count(consensus.network_tip_hashes_count as u64)),
+                ("Difficulty".to_string(), format!("{:.2}", consensus.network_difficulty)),
+                ("Past median time".to_string(), format!("{} ms", consensus.network_past_median_time)),
+                ("Virtual parent hashes".to_string(), count(consensus.network_virtual_parent_hashes_count as u64)),
+                ("Virtual DAA score".to_string(), count(consensus.network_virtual_daa_score)),
+                ("Sync ETA".to_string(), sync_eta),
+            ],
+        ));
+    }
+
+    let rows = sections.iter().flat_map(|(_, rows)| rows.iter());
+    let label_width = rows.clone().map(|(label, _)| label.len()).max().unwrap_or_default();
+    let value_width = rows.map(|(_, value)| value.len()).max().unwrap_or_default();
+
+    let mut lines = vec![];
+    for (title, rows) in sections {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(style(title).bold().to_string());
+        for (label, value) in rows {
+            lines.push(format!(
+                "  {}  {}",
+                style(label.pad_to_width(label_width)).dim(),
+                value.pad_to_width_with_alignment(value_width, pad::Alignment::Right)
+            ));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_rpc_core::{ConnectionMetrics, ConsensusMetrics, ProcessMetrics, ServiceMetrics};
+
+    #[test]
+    fn test_format_metrics_table() {
+        set_colors_enabled(false);
+
+        let metrics = GetMetricsResponse::new(
+            1_700_000_000_000,
+            Some(ProcessMetrics {
+                resident_set_size: 512_000_000,
+                virtual_memory_size: 2_500_000_000,
+                core_num: 8,
+                cpu_usage: 1.0,
+                fd_num: 312,
+                disk_io_read_bytes: 1_500_000,
+                disk_io_write_bytes: 750,
+                disk_io_read_per_sec: 2048.0,
+                disk_io_write_per_sec: 0.0,
+                services: vec![ServiceMetrics {
+                    ident: "p2p-service".to_string(),
+                    state: RpcServiceState::Running,
+                    start_time: Some(0),
+                    last_error: None,
+                }],
+            }),
+            Some(ConnectionMetrics {
+                borsh_live_connections: 3,
+                borsh_connection_attempts: 1250,
+                json_live_connections: 1,
+                active_peers: 8,
+                ..Default::default()
+            }),
+            None,
+            Some(ConsensusMetrics {
+                node_blocks_submitted_count: 42,
+                node_headers_processed_count: 1_234_567,
+                network_mempool_size: 17,
+                network_difficulty: 1234.5,
+                network_virtual_daa_score: 80_000_000,
+                node_sync_eta_seconds: Some(90),
+                ..Default::default()
+            }),
+        );
+
+        let expected = "\
+Process
+  Resident set size                   512 MB
+  Virtual memory                     2.50 GB
+  CPU cores                                8
+  CPU usage                          12.50 %
+  File descriptors                       312
+  Disk read                          1.50 MB
+  Disk write                           750 B
+  Disk read rate                   2.05 KB/s
+  Disk write rate                      0 B/s
+  Service p2p-service                running
+
+Connections
+  wRPC Borsh live connections              3
+  wRPC Borsh connection attempts       1,250
+  wRPC Borsh handshake failures            0
+  wRPC JSON live connections               1
+  wRPC JSON connection attempts            0
+  wRPC JSON handshake failures             0
+  P2P active peers                         8
+
+Consensus
+  Blocks submitted                        42
+  Headers processed                1,234,567
+  Dependencies processed                   0
+  Bodies processed                         0
+  Transactions processed                   0
+  Chain blocks processed                   0
+  Mass processed                           0
+  Database blocks                          0
+  Database headers                         0
+  Mempool size                        17 txs
+  Tip hashes                               0
+  Difficulty                         1234.50
+  Past median time                      0 ms
+  Virtual parent hashes                    0
+  Virtual DAA score               80,000,000
+  Sync ETA                              90 s";
+        assert_eq!(format_metrics_table(&metrics).join("\n"), expected);
+    }
+}
@@ -37,6 +37,8 @@ pub struct Miner {
     is_running: Arc<AtomicBool>,
 }
 
+impl Command for Miner {}
+
 impl Default for Miner {
     fn default() -> Self {
         Miner {
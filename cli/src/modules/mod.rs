@@ -2,13 +2,19 @@ use crate::imports::*;
 
 pub mod account;
 pub mod address;
+pub mod address_book;
 pub mod broadcast;
+pub mod bump;
 pub mod close;
 pub mod connect;
+pub mod consolidate;
 #[path = "create-unsigned-tx.rs"]
 pub mod create_unsigned_tx;
+pub mod currency;
+pub mod decode;
 pub mod details;
 pub mod disconnect;
+pub mod encode;
 pub mod estimate;
 pub mod exit;
 pub mod export;
@@ -19,6 +25,7 @@ pub mod history;
 // pub mod import;
 pub mod list;
 pub mod message;
+pub mod messagesign;
 pub mod miner;
 pub mod monitor;
 pub mod mute;
@@ -40,25 +47,48 @@ pub mod sweep;
 pub mod theme;
 pub mod track;
 pub mod transfer;
+pub mod use_wallet;
+pub mod verify;
 pub mod wallet;
+pub mod watch;
 
 // this module is registered manually within
 // applications that support metrics
 pub mod metrics;
 
-// TODO
-// broadcast
-// create-unsigned-tx
-// sign
+/// Command declarations complementing the [`Handler`] verb and help.
+pub trait Command: Handler {
+    /// Argument specification validated by [`KaspaCli`] before the handler
+    /// is invoked. Commands without a specification parse `argv` themselves.
+    fn arg_spec(&self) -> Option<&'static ArgSpec> {
+        None
+    }
+
+    /// Aliases accepted in place of the command verb.
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// Registers [`Command`] handlers with [`KaspaCli`], where each module
+/// declares a handler type named after the module in upper camel case.
+macro_rules! register_commands {
+    ($cli:expr, [$($module:ident),* $(,)?]) => {
+        paste::paste! {
+            $( $cli.register_command($module::[<$module:camel>]::default()); )*
+        }
+    };
+}
+pub(crate) use register_commands;
 
 pub fn register_handlers(cli: &Arc<KaspaCli>) -> Result<()> {
-    register_handlers!(
+    register_commands!(
         cli,
-        cli.handlers(),
         [
-            account, address, close, connect, details, disconnect, estimate, exit, export, guide, help, history, rpc, list, miner,
-            message, monitor, mute, network, node, open, ping, reload, select, send, server, settings, sweep, track, transfer,
-            wallet,
+            account, address, address_book, broadcast, bump, close, connect, consolidate, create_unsigned_tx, currency, decode,
+            details, disconnect, encode, estimate, exit, export, guide, help, history, rpc, list, miner, message, messagesign, monitor,
+            mute, network, node, open, ping, reload, select, send, server, settings, sign, sweep, track, transfer, use_wallet, verify,
+            wallet, watch,
             // halt,
             // theme,  start, stop
         ]
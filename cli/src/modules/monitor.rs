@@ -1,15 +1,23 @@
 use crate::imports::*;
+use kaspa_utils::arc::AtomicArc;
+use kaspa_wallet_core::utxo::UtxoContextId;
 use workflow_core::channel::*;
 use workflow_terminal::clear::*;
 use workflow_terminal::cursor::*;
 
+type BalanceSnapshot = HashMap<UtxoContextId, Option<Balance>>;
+
 pub struct Monitor {
     shutdown_tx: Arc<Mutex<Option<Sender<()>>>>,
+    // latest balance of each utxo context, updated from balance events
+    balances: AtomicArc<BalanceSnapshot>,
 }
 
+impl Command for Monitor {}
+
 impl Default for Monitor {
     fn default() -> Self {
-        Monitor { shutdown_tx: Arc::new(Mutex::new(None)) }
+        Monitor { shutdown_tx: Arc::new(Mutex::new(None)), balances: AtomicArc::default() }
     }
 }
 
@@ -39,8 +47,10 @@ impl Handler for Monitor {
 
 impl Monitor {
     async fn main(self: Arc<Self>, ctx: &Arc<KaspaCli>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
-        let max_events = 16;
-        let events = Arc::new(Mutex::new(VecDeque::new()));
+        if !ctx.is_interactive() {
+            return Err(Error::InteractiveTerminalRequired("'monitor'".to_string()));
+        }
+
         let events_rx = ctx.wallet().multiplexer().channel();
 
         let (shutdown_tx, shutdown_rx) = oneshot();
@@ -61,16 +71,18 @@ impl Monitor {
 
                     event = events_rx.recv().fuse() => {
                         if let Ok(event) = event {
-                            let mut events = events.lock().unwrap();
-                            events.push_front(event);
-                            while events.len() > max_events {
-                                events.pop_back();
+                            if let Events::Balance { balance, id } = event.deref() {
+                                this.balances.update(|balances| {
+                                    let mut balances = balances.clone();
+                                    balances.insert(*id, balance.clone());
+                                    balances
+                                });
                             }
                         }
                     }
 
                     _ = interval.next().fuse() => {
-                        this.redraw(&ctx, &events).await.ok();
+                        this.redraw(&ctx).await.ok();
                         yield_executor().await;
                     }
 
@@ -84,13 +96,14 @@ impl Monitor {
             tprint!(ctx, "{}", ClearScreen);
             tprint!(ctx, "{}", Goto(1, 1));
             this.shutdown_tx.lock().unwrap().take();
+            this.balances.store(BalanceSnapshot::default());
             ctx.term().refresh_prompt();
         });
 
         Ok(())
     }
 
-    async fn redraw(self: &Arc<Self>, ctx: &Arc<KaspaCli>, events: &Arc<Mutex<VecDeque<Box<Events>>>>) -> Result<()> {
+    async fn redraw(self: &Arc<Self>, ctx: &Arc<KaspaCli>) -> Result<()> {
         tprint!(ctx, "{}", ClearScreen);
         tprint!(ctx, "{}", Goto(1, 1));
 
@@ -106,27 +119,24 @@ impl Monitor {
 
         ctx.list().await?;
 
-        let events = events.lock().unwrap();
-        events.iter().for_each(|event| match event.deref() {
-            Events::DAAScoreChange { .. } => {}
-            Events::Balance { balance, id } => {
-                let network_id = wallet.network_id().expect("missing network type");
-                let network_type = NetworkType::from(network_id);
-                let balance_strings = BalanceStrings::from((balance, &network_type, None));
-                let id = id.short();
+        let balances = self.balances.load();
+        let mut balances = balances.iter().collect::<Vec<_>>();
+        balances.sort_by_key(|(id, _)| **id);
+        for (id, balance) in balances {
+            let network_id = wallet.network_id().expect("missing network type");
+            let network_type = NetworkType::from(network_id);
+            let balance_strings = ctx.format_balance(balance, &network_type);
+            let id = id.short();
 
-                let mature_utxo_count =
-                    balance.as_ref().map(|balance| balance.mature_utxo_count.separated_string()).unwrap_or("N/A".to_string());
-                let pending_utxo_count = balance.as_ref().map(|balance| balance.pending_utxo_count).unwrap_or(0);
+            let mature_utxo_count =
+                balance.as_ref().map(|balance| balance.mature_utxo_count.separated_string()).unwrap_or("N/A".to_string());
+            let pending_utxo_count = balance.as_ref().map(|balance| balance.pending_utxo_count).unwrap_or(0);
 
-                let pending_utxo_info =
-                    if pending_utxo_count > 0 { format!("({pending_utxo_count} pending)") } else { "".to_string() };
-                let utxo_info = style(format!("{mature_utxo_count} UTXOs {pending_utxo_info}")).dim();
+            let pending_utxo_info = if pending_utxo_count > 0 { format!("({pending_utxo_count} pending)") } else { "".to_string() };
+            let utxo_info = style(format!("{mature_utxo_count} UTXOs {pending_utxo_info}")).dim();
 
-                tprintln!(ctx, "{} {id}: {balance_strings}   {utxo_info}", style("balance".pad_to_width(8)).blue());
-            }
-            _ => {}
-        });
+            tprintln!(ctx, "{} {id}: {balance_strings}   {utxo_info}", style("balance".pad_to_width(8)).blue());
+        }
 
         Ok(())
     }
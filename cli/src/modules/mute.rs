@@ -4,6 +4,8 @@ use crate::imports::*;
 #[help("Mute (toggle notification output mute)")]
 pub struct Mute;
 
+impl Command for Mute {}
+
 impl Mute {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
@@ -36,6 +36,8 @@ pub struct Node {
     is_running: Arc<AtomicBool>,
 }
 
+impl Command for Node {}
+
 impl Default for Node {
     fn default() -> Self {
         Node {
@@ -4,13 +4,14 @@ use crate::imports::*;
 #[help("Ping the connected node")]
 pub struct Ping;
 
+impl Command for Ping {}
+
 impl Ping {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
-        if ctx.wallet().ping(None).await.is_ok() {
-            tprintln!(ctx, "ping ok");
-        } else {
-            terrorln!(ctx, "ping error");
+        match ctx.wallet().ping().await {
+            Ok(round_trip_time) => tprintln!(ctx, "ping ok ({}ms)", round_trip_time.as_millis()),
+            Err(err) => terrorln!(ctx, "ping error: {err}"),
         }
         Ok(())
     }
@@ -4,6 +4,8 @@ use crate::imports::*;
 #[help("Reload the web interface (used for testing)")]
 pub struct Reload;
 
+impl Command for Reload {}
+
 impl Reload {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
         // #[cfg(target_arch = "wasm32")]
@@ -1,4 +1,5 @@
 use crate::imports::*;
+use crate::modules::metrics::format_metrics_table;
 use convert_case::{Case, Casing};
 use kaspa_rpc_core::{api::ops::RpcApiOps, *};
 
@@ -6,6 +7,8 @@ use kaspa_rpc_core::{api::ops::RpcApiOps, *};
 #[help("Execute RPC commands against the connected Kaspa node")]
 pub struct Rpc;
 
+impl Command for Rpc {}
+
 impl Rpc {
     fn println<T>(&self, ctx: &Arc<KaspaCli>, v: T)
     where
@@ -38,8 +41,14 @@ impl Rpc {
                 tprintln!(ctx, "ok");
             }
             RpcApiOps::GetMetrics => {
-                let result = rpc.get_metrics(true, true, true, true).await?;
-                self.println(&ctx, result);
+                let json = argv.iter().any(|arg| arg == "--json");
+                argv.retain(|arg| arg != "--json");
+                let result = rpc.get_metrics_call(metrics_request(&argv)?).await?;
+                if json {
+                    tprintln!(ctx, "{}", serde_json::to_string_pretty(&result)?);
+                } else {
+                    format_metrics_table(&result).into_iter().for_each(|line| tprintln!(ctx, "{line}"));
+                }
             }
             RpcApiOps::GetServerInfo => {
                 let result = rpc.get_server_info_call(GetServerInfoRequest {}).await?;
@@ -173,7 +182,16 @@ impl Rpc {
                     return Err(Error::custom("Please specify peer IP address"));
                 }
                 let ip: RpcIpAddress = argv.remove(0).parse()?;
-                let result = rpc.ban_call(BanRequest { ip }).await?;
+                let mut request = BanRequest::new(ip);
+                if !argv.is_empty() {
+                    let duration =
+                        argv.remove(0).parse::<u64>().map_err(|_| Error::custom("Could not parse ban duration (seconds)"))?;
+                    request = request.with_duration(duration);
+                }
+                if !argv.is_empty() {
+                    request = request.with_reason(argv.join(" "));
+                }
+                let result = rpc.ban_call(request).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::Unban => {
@@ -184,6 +202,21 @@ impl Rpc {
                 let result = rpc.unban_call(UnbanRequest { ip }).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetBannedPeers => {
+                let result = rpc.get_banned_peers_call(GetBannedPeersRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetFeeEstimate => {
+                let result = rpc.get_fee_estimate_call(GetFeeEstimateRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::SetLogLevel => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify the log level as [<subsystem>=]<level>"));
+                }
+                let result = rpc.set_log_level_call(log_level_request(&argv.remove(0))).await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetInfo => {
                 let result = rpc.get_info_call(GetInfoRequest {}).await?;
                 self.println(&ctx, result);
@@ -257,3 +290,65 @@ impl Rpc {
         Ok(())
     }
 }
+
+/// Builds the request of the `get-metrics` command selecting the metrics sections
+/// named in `argv`, or all sections if none is named.
+fn metrics_request(argv: &[String]) -> Result<GetMetricsRequest> {
+    let mut request = GetMetricsRequest {
+        process_metrics: argv.is_empty(),
+        connection_metrics: argv.is_empty(),
+        bandwidth_metrics: argv.is_empty(),
+        consensus_metrics: argv.is_empty(),
+    };
+    for section in argv {
+        match section.to_lowercase().as_str() {
+            "process" => request.process_metrics = true,
+            "connection" | "connections" => request.connection_metrics = true,
+            "bandwidth" => request.bandwidth_metrics = true,
+            "consensus" => request.consensus_metrics = true,
+            _ => {
+                return Err(Error::custom(format!(
+                    "Unknown metrics section '{section}' (expected process, connection, bandwidth or consensus)"
+                )))
+            }
+        }
+    }
+    Ok(request)
+}
+
+/// Builds the request of the `set-log-level` command from a `[<subsystem>=]<level>` argument,
+/// the level and subsystem names being validated by the node.
+fn log_level_request(arg: &str) -> SetLogLevelRequest {
+    match arg.split_once('=') {
+        Some((subsystem, level)) => SetLogLevelRequest::new(Some(subsystem.to_string()), level.to_string()),
+        None => SetLogLevelRequest::new(None, arg.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sections(argv: &[&str]) -> [bool; 4] {
+        let argv = argv.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        let request = metrics_request(&argv).unwrap();
+        [request.process_metrics, request.connection_metrics, request.bandwidth_metrics, request.consensus_metrics]
+    }
+
+    #[test]
+    fn test_metrics_request() {
+        assert_eq!(sections(&[]), [true, true, true, true]);
+        assert_eq!(sections(&["process"]), [true, false, false, false]);
+        assert_eq!(sections(&["Consensus", "connections"]), [false, true, false, true]);
+        assert_eq!(sections(&["bandwidth", "bandwidth"]), [false, false, true, false]);
+        assert!(metrics_request(&["memory".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_log_level_request() {
+        let request = log_level_request("kaspa_p2p_flows=debug");
+        assert_eq!((request.subsystem.as_deref(), request.level.as_str()), (Some("kaspa_p2p_flows"), "debug"));
+        let request = log_level_request("warn");
+        assert_eq!((request.subsystem.as_deref(), request.level.as_str()), (None, "warn"));
+    }
+}
@@ -4,6 +4,8 @@ use crate::imports::*;
 #[help("Select an account")]
 pub struct Select;
 
+impl Command for Select {}
+
 impl Select {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
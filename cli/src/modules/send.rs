@@ -1,47 +1,176 @@
 use crate::imports::*;
+use kaspa_wallet_core::tx::Generator;
+use std::future::Future;
+use std::path::PathBuf;
 
 #[derive(Default, Handler)]
-#[help("Send a Kaspa transaction to a public address")]
+#[help("Send a Kaspa transaction to a public address or an address book alias")]
 pub struct Send;
 
+impl Command for Send {
+    fn arg_spec(&self) -> Option<&'static ArgSpec> {
+        Some(&Self::ARGS)
+    }
+}
+
 impl Send {
+    pub const ARGS: ArgSpec = ArgSpec::new(
+        "send",
+        &[
+            Arg::Required("address"),
+            Arg::Required("amount"),
+            Arg::Optional("priority fee"),
+            Arg::Value("payload", "hexfile"),
+            Arg::Flag("yes"),
+        ],
+    );
+
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
-        // address, amount, priority fee
+        // address, amount, priority fee, payload
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        let args = Self::ARGS.parse(&argv)?;
 
         let account = ctx.wallet().account()?;
 
-        if argv.len() < 2 {
-            tprintln!(ctx, "usage: send <address> <amount> <priority fee>");
-            return Ok(());
-        }
-
-        let address = Address::try_from(argv.first().unwrap().as_str())?;
-        let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.get(1))?;
-        let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(2))?.unwrap_or(0);
+        let network_id = ctx.wallet().network_id()?;
+        let address = ctx.wallet().store().as_address_book_store()?.resolve(args.required(0)?, &network_id).await?;
+        let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(args.get(1))?;
+        let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(args.get(2))?.unwrap_or(0);
+        let payload = match args.value("payload") {
+            Some(path) => Some(read_payload(path).await?),
+            None => None,
+        };
         let outputs = PaymentOutputs::from((address.clone(), amount_sompi));
         let abortable = Abortable::default();
-        let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
-
-        // let ctx_ = ctx.clone();
-        let (summary, _ids) = account
-            .send(
-                outputs.into(),
-                priority_fee_sompi.into(),
-                None,
-                wallet_secret,
-                payment_secret,
-                &abortable,
-                Some(Arc::new(move |_ptx| {
-                    // tprintln!(ctx_, "Sending transaction: {}", ptx.id());
-                })),
-            )
-            .await?;
+
+        // generate the transactions once without signing them, so that the summarized transactions are the ones broadcasted
+        let settings = account.clone().generator_settings(outputs.into(), priority_fee_sompi.into(), payload)?;
+        let generator = Generator::try_new(settings, None, Some(&abortable))?;
+        let mut stream = generator.stream();
+        let mut transactions = vec![];
+        let mut change = None;
+        while let Some(transaction) = stream.try_next().await? {
+            if transaction.is_final() {
+                change = Some((transaction.change_value(), transaction.change_address().clone()));
+            }
+            transactions.push(transaction);
+        }
+        let summary = generator.summary();
+
+        tprintln!(ctx, "Recipient: {address}");
+        tprintln!(ctx, "Amount: {} KAS", sompi_to_kaspa_string(amount_sompi));
+        tprintln!(
+            ctx,
+            "Fees: {} KAS ({} transactions)",
+            sompi_to_kaspa_string(summary.aggregated_fees()),
+            summary.number_of_generated_transactions()
+        );
+        if let Some((change_value, change_address)) = change.filter(|(change_value, _)| *change_value > 0) {
+            tprintln!(ctx, "Change: {} KAS to {change_address}", sompi_to_kaspa_string(change_value));
+        }
+        tprintln!(ctx, "Total: {} KAS", sompi_to_kaspa_string(amount_sompi + summary.aggregated_fees()));
+
+        let sent = send_if_confirmed(
+            args.flag("yes"),
+            ctx.is_interactive(),
+            || async { Ok(ctx.ask(false, "Send the transaction? [y/N]: ").await?) },
+            || async {
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+                Ok(account.clone().sign_and_submit(transactions, wallet_secret, payment_secret, None).await?)
+            },
+        )
+        .await?;
+
+        let Some(ids) = sent else {
+            tprintln!(ctx, "Send aborted.");
+            return Ok(());
+        };
 
         tprintln!(ctx, "Send - {summary}");
+        ctx.report_mempool_acceptance(&ids).await;
         // tprintln!(ctx, "\nSending {} KAS to {address}, tx ids:", sompi_to_kaspa_string(amount_sompi));
         // tprintln!(ctx, "{}\n", ids.into_iter().map(|a| a.to_string()).collect::<Vec<_>>().join("\n"));
 
         Ok(())
     }
 }
+
+/// Runs `send` if the user confirms it, implicitly with `yes` or by answering `y`
+/// when asked by `ask`. Returns `None` if the user declines. Without `yes`, fails
+/// in a non-interactive session since there is nobody to confirm.
+async fn send_if_confirmed<T, A, AF, S, SF>(yes: bool, interactive: bool, ask: A, send: S) -> Result<Option<T>>
+where
+    A: FnOnce() -> AF,
+    AF: Future<Output = Result<String>>,
+    S: FnOnce() -> SF,
+    SF: Future<Output = Result<T>>,
+{
+    if !yes && !interactive {
+        return Err(Error::InteractiveTerminalRequired("confirming 'send' without --yes".to_string()));
+    }
+    if yes || ask().await?.trim().eq_ignore_ascii_case("y") {
+        send().await.map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Reads a hex-encoded transaction payload from the given file.
+async fn read_payload(path: &str) -> Result<Vec<u8>> {
+    let hex = workflow_store::fs::read_to_string(&PathBuf::from(path))
+        .await
+        .map_err(|err| Error::custom(format!("unable to read payload file '{path}': {err}")))?;
+    let hex = hex.trim();
+    let mut payload = vec![0u8; hex.len() / 2];
+    faster_hex::hex_decode(hex.as_bytes(), &mut payload)?;
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[tokio::test]
+    async fn test_send_confirmation() -> Result<()> {
+        let asked = AtomicUsize::new(0);
+        let broadcasts = AtomicUsize::new(0);
+        let ask = |answer: &'static str| {
+            let asked = &asked;
+            move || async move {
+                asked.fetch_add(1, Ordering::SeqCst);
+                Ok(answer.to_string())
+            }
+        };
+        let broadcast = || async {
+            broadcasts.fetch_add(1, Ordering::SeqCst);
+            Ok("txid")
+        };
+
+        // declining (or not answering) aborts without broadcasting
+        for answer in ["n", "", "yes please"] {
+            assert_eq!(send_if_confirmed(false, true, ask(answer), broadcast).await?, None);
+        }
+        assert_eq!((asked.load(Ordering::SeqCst), broadcasts.load(Ordering::SeqCst)), (3, 0));
+
+        assert_eq!(send_if_confirmed(false, true, ask(" Y\n"), broadcast).await?, Some("txid"));
+        assert_eq!((asked.load(Ordering::SeqCst), broadcasts.load(Ordering::SeqCst)), (4, 1));
+
+        // --yes proceeds without asking, also in a non-interactive session
+        assert_eq!(send_if_confirmed(true, true, ask("n"), broadcast).await?, Some("txid"));
+        assert_eq!(send_if_confirmed(true, false, ask("n"), broadcast).await?, Some("txid"));
+        assert_eq!((asked.load(Ordering::SeqCst), broadcasts.load(Ordering::SeqCst)), (4, 3));
+
+        // without --yes, a non-interactive session fails without asking or broadcasting
+        let result = send_if_confirmed(false, false, ask("y"), broadcast).await;
+        assert!(matches!(result, Err(Error::InteractiveTerminalRequired(_))));
+        assert_eq!((asked.load(Ordering::SeqCst), broadcasts.load(Ordering::SeqCst)), (4, 3));
+
+        // a failed prompt is reported without broadcasting
+        let failing = || async { Err::<String, _>(Error::custom("terminal closed")) };
+        assert!(send_if_confirmed(false, true, failing, broadcast).await.is_err());
+        assert_eq!(broadcasts.load(Ordering::SeqCst), 3);
+
+        Ok(())
+    }
+}
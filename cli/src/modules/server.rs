@@ -5,6 +5,8 @@ use kaspa_wrpc_client::parse::parse_host;
 #[help("Set RPC server address")]
 pub struct Server;
 
+impl Command for Server {}
+
 impl Server {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
@@ -4,16 +4,60 @@ use crate::imports::*;
 #[help("Manage application settings")]
 pub struct Settings;
 
+impl Command for Settings {}
+
 impl Settings {
-    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
 
+        match argv.first().map(|s| s.as_str()) {
+            None => {}
+            Some("encrypt") => {
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().enable_settings_encryption(&wallet_secret).await?;
+                tprintln!(ctx, "Settings are now encrypted with the wallet secret");
+                return Ok(());
+            }
+            Some("decrypt") => {
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().disable_settings_encryption(&wallet_secret).await?;
+                tprintln!(ctx, "Settings are now stored in plaintext");
+                return Ok(());
+            }
+            Some("rotation") => {
+                let rotation = match argv.get(1).map(|s| s.as_str()) {
+                    Some("on") => true,
+                    Some("off") => false,
+                    _ => {
+                        tprintln!(ctx, "usage: settings rotation <on|off>");
+                        return Ok(());
+                    }
+                };
+                ctx.wallet().set_change_address_rotation(rotation);
+                ctx.wallet().settings().set(WalletSettings::ChangeAddressRotation, rotation).await?;
+                tprintln!(ctx, "Change address rotation is {}", if rotation { "enabled" } else { "disabled" });
+                return Ok(());
+            }
+            Some(_) => {
+                tprintln!(ctx, "usage: settings [encrypt|decrypt|rotation <on|off>]");
+                return Ok(());
+            }
+        }
+
+        if ctx.wallet().settings().is_locked() {
+            tprintln!(ctx, "\nSecret settings are encrypted, open the wallet to unlock them");
+        }
+
         tprintln!(ctx, "\nSettings:\n");
         let list = WalletSettings::list();
         let list = list
             .iter()
             .map(|setting| {
-                let value: String = ctx.wallet().settings().get(setting.clone()).unwrap_or_else(|| "-".to_string());
+                let value = match ctx.wallet().settings().get::<Value>(setting.clone()) {
+                    Some(Value::String(value)) => value,
+                    Some(value) => value.to_string(),
+                    None => "-".to_string(),
+                };
                 let descr = setting.descr();
                 (setting.as_str().to_lowercase(), value, descr)
             })
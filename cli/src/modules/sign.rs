@@ -1,13 +1,39 @@
 use crate::imports::*;
+use kaspa_wallet_core::tx::UnsignedTransaction;
+use std::path::PathBuf;
+use workflow_store::fs;
 
 #[derive(Default, Handler)]
 #[help("Sign the given partially signed transaction")]
 pub struct Sign;
 
+impl Command for Sign {
+    fn arg_spec(&self) -> Option<&'static ArgSpec> {
+        Some(&Self::ARGS)
+    }
+}
+
 impl Sign {
-    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
-        let _ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
-        // TODO - ctx.wallet().account()?.sign().await?;
+    pub const ARGS: ArgSpec = ArgSpec::new("sign", &[Arg::Required("transaction file"), Arg::Optional("signed transaction file")]);
+
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        let args = Self::ARGS.parse(&argv)?;
+
+        let account = ctx.wallet().account()?;
+
+        let source = PathBuf::from(args.required(0)?);
+        let destination = args.get(1).map(PathBuf::from).unwrap_or_else(|| source.clone());
+
+        let transactions: Vec<UnsignedTransaction> = fs::read_json(&source).await?;
+        let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+        let mut signed = vec![];
+        for transaction in transactions {
+            signed.push(account.clone().sign_unsigned_transaction(transaction, wallet_secret.clone(), payment_secret.clone()).await?);
+        }
+        fs::write_json(&destination, &signed).await?;
+
+        tprintln!(ctx, "{} signed transaction(s) written to '{}'", signed.len(), destination.display());
 
         Ok(())
     }
@@ -4,6 +4,8 @@ use crate::imports::*;
 #[help("Reduces account UTXO size by re-sending all funds to the account's default address")]
 pub struct Sweep;
 
+impl Command for Sweep {}
+
 impl Sweep {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, _argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
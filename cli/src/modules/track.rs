@@ -2,16 +2,23 @@ use crate::helpers;
 use crate::imports::*;
 
 #[derive(Default, Handler)]
-#[help("Track specific notifications when muted (balance|pending|tx|utxo|daa)")]
+#[help("Track specific notifications when muted (balance|pending|tx|utxo|daa|reset)")]
 pub struct Track;
 
+impl Command for Track {}
+
 impl Track {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
 
         if let Some(attr) = argv.first() {
-            let track: helpers::Track = attr.parse()?;
-            ctx.flags().toggle(track);
+            if attr == "reset" {
+                ctx.flags().reset().await?;
+                tprintln!(ctx, "all tracking flags are off");
+            } else {
+                let track: helpers::Track = attr.parse()?;
+                ctx.flags().toggle(track).await?;
+            }
         } else {
             for flag in ctx.flags().map().iter() {
                 let k = flag.key().to_string();
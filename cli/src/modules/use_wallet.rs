@@ -0,0 +1,58 @@
+use crate::imports::*;
+
+#[derive(Default)]
+pub struct UseWallet;
+
+impl Command for UseWallet {}
+
+#[async_trait]
+impl Handler for UseWallet {
+    fn verb(&self, _ctx: &Arc<dyn Context>) -> Option<&'static str> {
+        Some("use")
+    }
+
+    fn help(&self, _ctx: &Arc<dyn Context>) -> &'static str {
+        "Open another wallet alongside the open wallets or switch the current wallet"
+    }
+
+    async fn handle(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, cmd: &str) -> cli::Result<()> {
+        self.main(ctx, argv, cmd).await.map_err(|e| e.into())
+    }
+}
+
+impl UseWallet {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        let Some(name) = argv.first() else {
+            let current = ctx.wallet().descriptor().map(|descriptor| descriptor.filename);
+            let names = ctx.wallets().names();
+            if names.is_empty() {
+                tprintln!(ctx, "No open wallets");
+            } else {
+                for name in names {
+                    let marker = if current.as_ref() == Some(&name) { "*" } else { " " };
+                    tprintln!(ctx, "{marker} {name}");
+                }
+            }
+            return Ok(());
+        };
+
+        if ctx.wallets().get(name).is_none() {
+            if !ctx.wallet().exists(Some(name)).await? {
+                return Err(Error::custom(format!("wallet '{name}' not found")));
+            }
+            let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+            let _ = ctx.notifier().show(Notification::Processing).await;
+            ctx.wallets().open(&wallet_secret, name, WalletOpenArgs::default_with_legacy_accounts()).await?;
+        }
+
+        let wallet = ctx.use_wallet(name).await?;
+        if wallet.active_accounts().is_empty() {
+            wallet.activate_accounts(None).await?;
+        }
+        tprintln!(ctx, "using wallet '{name}'");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::imports::*;
+use kaspa_wallet_core::message::{verify_message_with_address, PersonalMessage};
+
+#[derive(Default, Handler)]
+#[help("Verify a message signature against a kaspa address")]
+pub struct Verify;
+
+impl Command for Verify {
+    fn arg_spec(&self) -> Option<&'static ArgSpec> {
+        Some(&Self::ARGS)
+    }
+}
+
+impl Verify {
+    pub const ARGS: ArgSpec = ArgSpec::new("verify", &[Arg::Required("kaspa_address"), Arg::Required("signature")]);
+
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        let args = Self::ARGS.parse(&argv)?;
+
+        let address = Address::try_from(args.required(0)?.as_str())?;
+        let mut signature = [0u8; 64];
+        faster_hex::hex_decode(args.required(1)?.as_bytes(), &mut signature)
+            .map_err(|_| Error::custom("Invalid signature encoding"))?;
+        let message = ctx.ask(false, "Message: ").await?;
+
+        if !verify_message_with_address(&PersonalMessage(&message), &signature.to_vec(), &address) {
+            return Err(Error::MessageVerification);
+        }
+
+        tprintln!(ctx, "Message verified successfully!");
+        Ok(())
+    }
+}
@@ -1,10 +1,15 @@
 use crate::imports::*;
 use crate::wizards;
+use kaspa_wallet_core::storage::local::default_wallet_file;
+use kaspa_wallet_core::storage::{WalletDescriptor, WalletInfo};
+use workflow_core::time::unixtime_to_locale_string;
 
 #[derive(Default, Handler)]
 #[help("Wallet management operations")]
 pub struct Wallet;
 
+impl Command for Wallet {}
+
 impl Wallet {
     async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
@@ -16,7 +21,7 @@ impl Wallet {
         let op = argv.remove(0);
         match op.as_str() {
             "list" => {
-                let wallets = ctx.store().wallet_list().await?;
+                let wallets = ctx.wallet().enumerate().await?;
                 if wallets.is_empty() {
                     tprintln!(ctx, "No wallets found");
                 } else {
@@ -24,11 +29,7 @@ impl Wallet {
                     tprintln!(ctx, "Wallets:");
                     tprintln!(ctx, "");
                     for wallet in wallets {
-                        if let Some(title) = wallet.title {
-                            tprintln!(ctx, "  {}: {}", wallet.filename, title);
-                        } else {
-                            tprintln!(ctx, "  {}", wallet.filename);
-                        }
+                        tprintln!(ctx, "  {}", format_wallet_info(&wallet));
                     }
                     tprintln!(ctx, "");
                 }
@@ -51,7 +52,7 @@ impl Wallet {
                 wizards::wallet::create(&ctx, wallet_name, import_with_mnemonic).await?;
             }
             "open" => {
-                let name = if let Some(name) = argv.first().cloned() {
+                let requested = if let Some(name) = argv.first().cloned() {
                     let name_check = name.to_lowercase();
 
                     if name_check.as_str() == "wallet" {
@@ -61,18 +62,51 @@ impl Wallet {
                     }
                     Some(name)
                 } else {
-                    ctx.wallet().settings().get(WalletSettings::Wallet).clone()
+                    None
                 };
 
+                let wallets = ctx.store().wallet_list().await?;
+                let last_used = ctx.wallet().settings().get(WalletSettings::Wallet);
+                let name = select_wallet(requested.clone(), last_used, &wallets)?;
+                if requested.is_none() {
+                    tprintln!(ctx, "opening wallet '{name}'");
+                }
+                if ctx.wallets().get(&name).is_some_and(|wallet| !Arc::ptr_eq(&wallet, &ctx.wallet())) {
+                    return Err(Error::custom(format!("wallet '{name}' is already open, type 'use {name}' to switch to it")));
+                }
+
                 let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
                 let _ = ctx.notifier().show(Notification::Processing).await;
                 let args = WalletOpenArgs::default_with_legacy_accounts();
-                ctx.wallet().open(&wallet_secret, name, args).await?;
+                ctx.wallet().open(&wallet_secret, Some(name.clone()), args).await?;
                 ctx.wallet().activate_accounts(None).await?;
+                ctx.wallet().settings().set(WalletSettings::Wallet, name).await?;
             }
             "close" => {
                 ctx.wallet().close().await?;
             }
+            "rename" => {
+                let [name, new_name] = argv.as_slice() else {
+                    return Err(Error::Usage("wallet rename <name> <new name>".to_string()));
+                };
+                ctx.wallet().rename_wallet(name, new_name).await?;
+                if ctx.wallet().settings().get::<String>(WalletSettings::Wallet).as_ref() == Some(name) {
+                    ctx.wallet().settings().set(WalletSettings::Wallet, new_name.clone()).await?;
+                }
+                tprintln!(ctx, "wallet '{name}' renamed to '{new_name}'");
+            }
+            "delete" => {
+                let [name] = argv.as_slice() else {
+                    return Err(Error::Usage("wallet delete <name>".to_string()));
+                };
+                if ctx.wallets().get(name).is_some() {
+                    return Err(Error::custom(format!("wallet '{name}' is currently open, please close it first")));
+                }
+                tprintln!(ctx, "please enter the password of wallet '{name}' to confirm the deletion");
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().delete_wallet(&wallet_secret, name).await?;
+                tprintln!(ctx, "wallet '{name}' deleted");
+            }
             "hint" => {
                 if !argv.is_empty() {
                     let re = regex::Regex::new(r"wallet\s+hint\s+").unwrap();
@@ -101,7 +135,7 @@ impl Wallet {
     async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
         ctx.term().help(
             &[
-                ("list", "List available local wallet files"),
+                ("list", "List available local wallet files with their account count and creation time"),
                 ("create [<name>]", "Create a new bip32 wallet"),
                 (
                     "import [<name>]",
@@ -111,8 +145,10 @@ impl Wallet {
                 Legacy wallets can only be imported as accounts. \
                 \r\n",
                 ),
-                ("open [<name>]", "Open an existing wallet (shorthand: 'open [<name>]')"),
+                ("open [<name>]", "Open an existing wallet, defaults to the last opened wallet (shorthand: 'open [<name>]')"),
                 ("close", "Close an opened wallet (shorthand: 'close')"),
+                ("rename <name> <new name>", "Rename a wallet file (the wallet must be closed)"),
+                ("delete <name>", "Delete a wallet, requires the wallet password (the wallet must be closed)"),
                 ("hint", "Change the wallet phishing hint"),
             ],
             None,
@@ -121,3 +157,90 @@ impl Wallet {
         Ok(())
     }
 }
+
+fn format_wallet_info(info: &WalletInfo) -> String {
+    let WalletInfo { descriptor, created, account_count } = info;
+    let mut line = match &descriptor.title {
+        Some(title) => format!("{}: {}", descriptor.filename, title),
+        None => descriptor.filename.clone(),
+    };
+    let mut details = vec![];
+    if let Some(account_count) = account_count {
+        details.push(format!("{account_count} account{}", if *account_count == 1 { "" } else { "s" }));
+    }
+    if let Some(created) = created {
+        details.push(format!("created {}", unixtime_to_locale_string(*created)));
+    }
+    if !details.is_empty() {
+        line.push_str(&format!(" ({})", details.join(", ")));
+    }
+    line
+}
+
+/// Selects the wallet to open: the requested wallet if supplied, otherwise the
+/// last opened wallet, the default wallet or the only available wallet.
+fn select_wallet(requested: Option<String>, last_used: Option<String>, wallets: &[WalletDescriptor]) -> Result<String> {
+    if wallets.is_empty() {
+        return Err(Error::custom("No wallets found, please use 'wallet create' to create a new wallet"));
+    }
+
+    if let Some(name) = requested {
+        return Ok(name);
+    }
+
+    let exists = |name: &str| wallets.iter().any(|wallet| wallet.filename == name);
+    if let Some(name) = last_used.filter(|name| exists(name)) {
+        Ok(name)
+    } else if exists(default_wallet_file()) {
+        Ok(default_wallet_file().to_string())
+    } else if let [wallet] = wallets {
+        Ok(wallet.filename.clone())
+    } else {
+        let names = wallets.iter().map(|wallet| wallet.filename.as_str()).collect::<Vec<_>>().join(", ");
+        Err(Error::custom(format!("Please specify the wallet to open ('open <name>'), available wallets: {names}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallets(names: &[&str]) -> Vec<WalletDescriptor> {
+        names.iter().map(|name| WalletDescriptor::new(None, name.to_string())).collect()
+    }
+
+    #[test]
+    fn test_select_wallet_default() -> Result<()> {
+        let available = wallets(&["alpha", "beta", default_wallet_file()]);
+
+        assert_eq!(select_wallet(Some("alpha".into()), Some("beta".into()), &available)?, "alpha");
+        assert_eq!(select_wallet(None, Some("beta".into()), &available)?, "beta");
+        assert_eq!(select_wallet(None, Some("removed".into()), &available)?, default_wallet_file());
+        assert_eq!(select_wallet(None, None, &available)?, default_wallet_file());
+        assert_eq!(select_wallet(None, None, &wallets(&["alpha"]))?, "alpha");
+
+        let err = select_wallet(None, None, &wallets(&["alpha", "beta"])).unwrap_err();
+        assert!(err.to_string().contains("available wallets: alpha, beta"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_wallet_info() {
+        let info = |title: Option<&str>, account_count| WalletInfo {
+            descriptor: WalletDescriptor::new(title.map(String::from), "alpha".to_string()),
+            created: None,
+            account_count,
+        };
+        assert_eq!(format_wallet_info(&info(Some("Alpha"), Some(1))), "alpha: Alpha (1 account)");
+        assert_eq!(format_wallet_info(&info(None, Some(2))), "alpha (2 accounts)");
+        assert_eq!(format_wallet_info(&info(None, None)), "alpha");
+    }
+
+    #[test]
+    fn test_select_wallet_empty_store() {
+        let err = select_wallet(None, Some("alpha".into()), &[]).unwrap_err();
+        assert!(err.to_string().contains("wallet create"));
+        assert!(select_wallet(Some("alpha".into()), None, &[]).is_err());
+    }
+}
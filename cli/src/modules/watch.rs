@@ -0,0 +1,250 @@
+use crate::imports::*;
+use kaspa_addresses::Prefix;
+use kaspa_notify::{
+    events::EventType,
+    listener::ListenerId,
+    scope::{Scope, UtxosChangedScope},
+};
+use kaspa_rpc_core::{
+    notify::connection::{ChannelConnection, ChannelType},
+    Notification, RpcUtxosByAddressesEntry, UtxosChangedNotification,
+};
+use kaspa_wallet_core::error::Error as WalletError;
+use std::collections::HashSet;
+use workflow_core::channel::*;
+
+pub struct Watch {
+    shutdown_tx: Arc<Mutex<Option<Sender<()>>>>,
+}
+
+impl Command for Watch {}
+
+impl Default for Watch {
+    fn default() -> Self {
+        Watch { shutdown_tx: Arc::new(Mutex::new(None)) }
+    }
+}
+
+#[async_trait]
+impl Handler for Watch {
+    fn verb(&self, _ctx: &Arc<dyn Context>) -> Option<&'static str> {
+        Some("watch")
+    }
+
+    fn help(&self, _ctx: &Arc<dyn Context>) -> &'static str {
+        "Watch the UTXO activity of arbitrary addresses"
+    }
+
+    async fn stop(self: Arc<Self>, _ctx: &Arc<dyn Context>) -> cli::Result<()> {
+        let shutdown_tx = self.shutdown_tx.lock().unwrap().take();
+        if let Some(shutdown_tx) = shutdown_tx {
+            shutdown_tx.send(()).await.ok();
+        }
+        Ok(())
+    }
+
+    async fn handle(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, cmd: &str) -> cli::Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        self.main(&ctx, argv, cmd).await.map_err(|e| e.into())
+    }
+}
+
+impl Watch {
+    async fn main(self: Arc<Self>, ctx: &Arc<KaspaCli>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        if argv.is_empty() {
+            tprintln!(ctx, "usage: watch <address> [<address> ...]");
+            return Ok(());
+        }
+
+        if !ctx.is_interactive() {
+            return Err(Error::InteractiveTerminalRequired("'watch'".to_string()));
+        }
+
+        let wallet = ctx.wallet();
+        if !wallet.is_connected() {
+            return Err(WalletError::NotConnected.into());
+        }
+        let network_id = wallet.network_id()?;
+        let addresses = argv.iter().map(|s| Address::try_from(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+        if let Some(address) = addresses.iter().find(|address| address.prefix != Prefix::from(network_id)) {
+            return Err(Error::custom(format!("address {address} does not belong to network {network_id}")));
+        }
+
+        let rpc = wallet.rpc_api().clone();
+        let notifications = Channel::<Notification>::unbounded();
+        let listener_id = rpc.register_new_listener(ChannelConnection::new(notifications.sender.clone(), ChannelType::Closable));
+        let scope = Scope::UtxosChanged(UtxosChangedScope::new(addresses.clone()));
+        if let Err(err) = rpc.start_notify(listener_id, scope.clone()).await {
+            rpc.unregister_listener(listener_id).await.ok();
+            return Err(err.into());
+        }
+
+        let (shutdown_tx, shutdown_rx) = oneshot();
+        self.shutdown_tx.lock().unwrap().replace(shutdown_tx.clone());
+
+        // the UTXO notifications are muted by the node while the output is muted and not tracked
+        if let Err(err) = sync_mute(ctx, &rpc, listener_id).await {
+            terrorln!(ctx, "Unable to mute the UTXO notifications: {err}");
+        }
+
+        let (ctrl_c_tx, ctrl_c_rx) = oneshot();
+        spawn({
+            let ctx = ctx.clone();
+            let rpc = rpc.clone();
+            async move {
+                read_keys(&ctx, &rpc, listener_id).await;
+                ctrl_c_tx.send(()).await.ok();
+                shutdown_tx.send(()).await.ok();
+            }
+        });
+
+        tprintln!(ctx, "watching {} address(es), press M to toggle mute, Ctrl+C to stop...", addresses.len());
+
+        // the command lasts as long as the watch, so the UTXO events do not interleave with other commands
+        let addresses = addresses.into_iter().collect::<HashSet<_>>();
+        let network_type = NetworkType::from(network_id);
+        let shut_down = watch(notifications.receiver, shutdown_rx, &addresses, &network_type, |line| tprintln!(ctx, "{line}")).await;
+
+        if let Err(err) = rpc.stop_notify(listener_id, scope).await {
+            terrorln!(ctx, "Unable to unsubscribe from UTXO notifications: {err}");
+        }
+        rpc.unregister_listener(listener_id).await.ok();
+        self.shutdown_tx.lock().unwrap().take();
+
+        // the key listener is awaited, so it does not capture the input of later commands
+        if !shut_down {
+            tprintln!(ctx, "the notifications stopped, press Ctrl+C to continue...");
+            ctrl_c_rx.recv().await.ok();
+        }
+
+        Ok(())
+    }
+}
+
+/// Raw terminal input of the Ctrl+C key
+const CTRL_C: &str = "\u{3}";
+
+/// Toggles the mute when M is pressed until Ctrl+C is pressed, ignoring any other key
+async fn read_keys(ctx: &Arc<KaspaCli>, rpc: &Arc<DynRpcApi>, listener_id: ListenerId) {
+    while let Ok(key) = ctx.term().kbhit(None).await {
+        match key.as_str() {
+            CTRL_C => break,
+            "m" | "M" => {
+                tprintln!(ctx, "mute is {}", ctx.toggle_mute());
+                if let Err(err) = sync_mute(ctx, rpc, listener_id).await {
+                    terrorln!(ctx, "Unable to mute the UTXO notifications: {err}");
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Mutes or unmutes the UTXO notifications of the watch listener according to the
+/// mute state and the track flags of the cli
+async fn sync_mute(ctx: &Arc<KaspaCli>, rpc: &Arc<DynRpcApi>, listener_id: ListenerId) -> Result<()> {
+    let event = EventType::UtxosChanged;
+    if ctx.flags().is_event_muted(event, ctx.is_mutted()) {
+        rpc.mute_notify(listener_id, event).await?;
+    } else {
+        rpc.unmute_notify(listener_id, event).await?;
+    }
+    Ok(())
+}
+
+/// Outputs the UTXO events of the watched `addresses` until `shutdown` is signaled
+/// or the `notifications` channel is closed. Returns `true` if `shutdown` was signaled.
+pub async fn watch<F>(
+    notifications: Receiver<Notification>,
+    shutdown: Receiver<()>,
+    addresses: &HashSet<Address>,
+    network_type: &NetworkType,
+    output: F,
+) -> bool
+where
+    F: Fn(String),
+{
+    loop {
+        select! {
+            notification = notifications.recv().fuse() => {
+                match notification {
+                    Ok(Notification::UtxosChanged(notification)) => {
+                        format_utxos_changed(&notification, addresses, network_type).into_iter().for_each(&output);
+                    }
+                    Ok(_) => {}
+                    Err(_) => return false,
+                }
+            }
+
+            _ = shutdown.recv().fuse() => {
+                return true;
+            }
+        }
+    }
+}
+
+/// Formats the UTXOs of `notification` belonging to the watched `addresses`, one line per
+/// UTXO: added UTXOs are incoming (`+`) and removed UTXOs are outgoing (`-`).
+pub fn format_utxos_changed(
+    notification: &UtxosChangedNotification,
+    addresses: &HashSet<Address>,
+    network_type: &NetworkType,
+) -> Vec<String> {
+    let format = |sign: &str, entry: &RpcUtxosByAddressesEntry| {
+        let address = entry.address.as_ref().filter(|address| addresses.contains(address))?;
+        let amount = sompi_to_kaspa_string_with_suffix(entry.utxo_entry.amount, network_type);
+        Some(format!("{sign} {amount} {address} {}:{}", entry.outpoint.transaction_id, entry.outpoint.index))
+    };
+    let incoming = notification.added.iter().filter_map(|entry| format("+", entry));
+    let outgoing = notification.removed.iter().filter_map(|entry| format("-", entry));
+    incoming.chain(outgoing).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::Version;
+    use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionId, TransactionOutpoint, UtxoEntry};
+
+    fn entry(address: &Address, transaction_id: u64, amount: u64) -> RpcUtxosByAddressesEntry {
+        RpcUtxosByAddressesEntry {
+            address: Some(address.clone()),
+            outpoint: TransactionOutpoint::new(TransactionId::from_u64_word(transaction_id), 0),
+            utxo_entry: UtxoEntry::new(amount, ScriptPublicKey::default(), 0, false),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch() {
+        let watched = Address::new(Prefix::Testnet, Version::PubKey, &[1u8; 32]);
+        let other = Address::new(Prefix::Testnet, Version::PubKey, &[2u8; 32]);
+        let addresses = HashSet::from([watched.clone()]);
+
+        let notifications = Channel::<Notification>::unbounded();
+        let (_shutdown_tx, shutdown_rx) = oneshot();
+        let changes = [
+            UtxosChangedNotification {
+                added: Arc::new(vec![entry(&watched, 1, 150_000_000), entry(&other, 2, 100_000_000)]),
+                removed: Arc::new(vec![]),
+            },
+            UtxosChangedNotification {
+                added: Arc::new(vec![]),
+                removed: Arc::new(vec![entry(&other, 3, 1), entry(&watched, 1, 150_000_000)]),
+            },
+        ];
+        for change in changes {
+            notifications.sender.send(Notification::UtxosChanged(change)).await.unwrap();
+        }
+        notifications.sender.close();
+
+        let lines = Mutex::new(vec![]);
+        let output = |line: String| lines.lock().unwrap().push(line);
+        assert!(!watch(notifications.receiver, shutdown_rx, &addresses, &NetworkType::Testnet, output).await);
+
+        let outpoint = format!("{}:0", TransactionId::from_u64_word(1));
+        assert_eq!(
+            lines.into_inner().unwrap(),
+            vec![format!("+ 1.5 TKAS {watched} {outpoint}"), format!("- 1.5 TKAS {watched} {outpoint}")]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//!
+//! Non-interactive (scripting) mode.
+//!
+//! Executes a sequence of CLI commands read from a file or stdin, one command
+//! per line. Empty lines and lines starting with `#` are ignored. Execution
+//! stops on the first failing command unless `--keep-going` is specified.
+//! Commands are never able to prompt for secrets in this mode; secrets must
+//! be supplied via the environment or a file (see [`crate::secrets`]).
+//!
+
+use crate::error::Error;
+use crate::imports::*;
+use std::io::Read;
+use std::path::PathBuf;
+
+/// Source of the script commands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptSource {
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptOptions {
+    pub source: ScriptSource,
+    pub keep_going: bool,
+}
+
+impl ScriptOptions {
+    pub fn read(&self) -> Result<String> {
+        match &self.source {
+            ScriptSource::File(path) => std::fs::read_to_string(path)
+                .map_err(|err| Error::custom(format!("unable to read script '{}': {err}", path.display()))),
+            ScriptSource::Stdin => {
+                let mut script = String::new();
+                std::io::stdin().read_to_string(&mut script).map_err(|err| Error::custom(format!("unable to read stdin: {err}")))?;
+                Ok(script)
+            }
+        }
+    }
+}
+
+/// Returns script commands together with their (1-based) line numbers.
+pub fn parse_script(script: &str) -> Vec<(usize, &str)> {
+    script
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+#[async_trait]
+pub trait Executor: Send + Sync {
+    async fn execute(&self, cmd: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl Executor for Arc<KaspaCli> {
+    async fn execute(&self, cmd: &str) -> Result<()> {
+        KaspaCli::execute(self, cmd).await
+    }
+}
+
+/// A script command that failed to execute.
+#[derive(Debug)]
+pub struct ScriptFailure {
+    pub line: usize,
+    pub cmd: String,
+    pub error: Error,
+}
+
+impl std::fmt::Display for ScriptFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: '{}': {}", self.line, self.cmd, self.error)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ScriptSummary {
+    pub executed: usize,
+    pub failures: Vec<ScriptFailure>,
+}
+
+impl ScriptSummary {
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Executes script commands line by line, stopping on the first
+/// failure unless `keep_going` is set.
+pub async fn run_script<E: Executor>(executor: &E, script: &str, keep_going: bool) -> ScriptSummary {
+    let mut summary = ScriptSummary::default();
+    for (line, cmd) in parse_script(script) {
+        summary.executed += 1;
+        if let Err(error) = executor.execute(cmd).await {
+            summary.failures.push(ScriptFailure { line, cmd: cmd.to_string(), error });
+            if !keep_going {
+                break;
+            }
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = "
+        # select the network and open the wallet
+        network testnet-11
+        open test
+
+        list
+        send kaspatest:qz7ulu4c25dh7fzec9zjyrmlhnkzrg4wmf89q7gzr3gfrsj3uz6xjceef60sd 1
+        close
+    ";
+
+    /// Records executed commands, failing commands with the given verb.
+    #[derive(Default)]
+    struct MockExecutor {
+        fail: Option<&'static str>,
+        executed: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Executor for MockExecutor {
+        async fn execute(&self, cmd: &str) -> Result<()> {
+            self.executed.lock().unwrap().push(cmd.to_string());
+            match self.fail {
+                Some(verb) if cmd.starts_with(verb) => Err(Error::custom(format!("{verb} failed"))),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_script() {
+        let commands = parse_script(SCRIPT);
+        assert_eq!(commands.first(), Some(&(3, "network testnet-11")));
+        assert_eq!(commands.iter().map(|(_, cmd)| *cmd).filter(|cmd| cmd.starts_with('#')).count(), 0);
+        assert_eq!(commands.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_run_script() {
+        let executor = MockExecutor::default();
+        let summary = run_script(&executor, SCRIPT, false).await;
+        assert!(summary.is_success());
+        assert_eq!(summary.executed, 5);
+        assert_eq!(executor.executed.lock().unwrap().last().map(String::as_str), Some("close"));
+    }
+
+    #[tokio::test]
+    async fn test_run_script_stops_on_error() {
+        let executor = MockExecutor { fail: Some("list"), ..Default::default() };
+        let summary = run_script(&executor, SCRIPT, false).await;
+        assert_eq!(summary.executed, 3);
+        assert_eq!(summary.failures.len(), 1);
+        assert_eq!(summary.failures[0].line, 6);
+        assert_eq!(summary.failures[0].to_string(), "line 6: 'list': list failed");
+        assert_eq!(executor.executed.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_script_keep_going() {
+        let executor = MockExecutor { fail: Some("list"), ..Default::default() };
+        let summary = run_script(&executor, SCRIPT, true).await;
+        assert_eq!(summary.executed, 5);
+        assert_eq!(summary.failures.len(), 1);
+        assert_eq!(executor.executed.lock().unwrap().last().map(String::as_str), Some("close"));
+    }
+}
@@ -0,0 +1,131 @@
+//!
+//! Non-interactive sources of wallet and payment secrets.
+//!
+//! Secrets can be supplied from a file (`--password-file`, `--payment-password-file`)
+//! or from the `KASPA_WALLET_SECRET` and `KASPA_PAYMENT_SECRET` environment
+//! variables. If no source is configured, the user is prompted for the secret.
+//! Secrets are read directly into [`Secret`] containers that are zeroized on
+//! drop and are never echoed or logged.
+//!
+
+use crate::error::Error;
+use crate::result::Result;
+use kaspa_wallet_core::secret::Secret;
+use std::future::Future;
+use std::path::PathBuf;
+
+pub const WALLET_SECRET_ENV: &str = "KASPA_WALLET_SECRET";
+pub const PAYMENT_SECRET_ENV: &str = "KASPA_PAYMENT_SECRET";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretSource {
+    File(PathBuf),
+    Env(String),
+}
+
+impl SecretSource {
+    pub fn read(&self) -> Result<Secret> {
+        match self {
+            SecretSource::File(path) => std::fs::read_to_string(path)
+                .map(Secret::from)
+                .map_err(|err| Error::custom(format!("unable to read secret file '{}': {err}", path.display()))),
+            SecretSource::Env(name) => {
+                std::env::var(name).map(Secret::from).map_err(|_| Error::custom(format!("environment variable '{name}' is not set")))
+            }
+        }
+    }
+
+    /// Returns a secret from the given source if one is configured, otherwise
+    /// obtains it using the `prompt` function. If no source is configured and
+    /// prompting is not allowed (`interactive` is `false`), returns `missing`.
+    pub async fn obtain<F, Fut>(source: Option<&SecretSource>, interactive: bool, prompt: F, missing: Error) -> Result<Secret>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        match source {
+            Some(source) => source.read(),
+            None if interactive => Ok(Secret::from(prompt().await?)),
+            None => Err(missing),
+        }
+    }
+}
+
+/// Configured secret sources, `None` denotes an interactive prompt.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecretSources {
+    pub wallet: Option<SecretSource>,
+    pub payment: Option<SecretSource>,
+}
+
+impl SecretSources {
+    /// Creates secret sources from the environment variables that are set.
+    pub fn from_env() -> Self {
+        let source = |name: &str| std::env::var_os(name).map(|_| SecretSource::Env(name.to_string()));
+        Self { wallet: source(WALLET_SECRET_ENV), payment: source(PAYMENT_SECRET_ENV) }
+    }
+
+    pub fn with_wallet_file(mut self, path: PathBuf) -> Self {
+        self.wallet = Some(SecretSource::File(path));
+        self
+    }
+
+    pub fn with_payment_file(mut self, path: PathBuf) -> Self {
+        self.payment = Some(SecretSource::File(path));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_secret_file_source() -> Result<()> {
+        let folder = tempfile::tempdir().unwrap();
+        let path = folder.path().join("secret");
+        std::fs::write(&path, "file-secret\n").unwrap();
+
+        let sources = SecretSources::default().with_wallet_file(path.clone());
+        let secret = SecretSource::obtain(
+            sources.wallet.as_ref(),
+            true,
+            || async { panic!("secret prompt must not be used when a file source is configured") },
+            Error::WalletSecretRequired,
+        )
+        .await?;
+        assert_eq!(secret.as_ref(), b"file-secret");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(SecretSource::File(path).read().is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_secret_prompt_fallback() -> Result<()> {
+        let secret =
+            SecretSource::obtain(None, true, || async { Ok(" prompt-secret ".to_string()) }, Error::WalletSecretRequired).await?;
+        assert_eq!(secret.as_ref(), b"prompt-secret");
+
+        let result = SecretSource::obtain(
+            None,
+            false,
+            || async { panic!("non-interactive mode must not prompt") },
+            Error::PaymentSecretRequired,
+        )
+        .await;
+        assert!(matches!(result, Err(Error::PaymentSecretRequired)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_secret_env_source() {
+        let name = "KASPA_CLI_TEST_SECRET_ENV";
+        std::env::set_var(name, "env-secret");
+        assert_eq!(SecretSource::Env(name.to_string()).read().unwrap().as_ref(), b"env-secret");
+        std::env::remove_var(name);
+        assert!(SecretSource::Env(name.to_string()).read().is_err());
+    }
+}
@@ -1,42 +1,20 @@
 use crate::error::Error;
 use crate::result::Result;
-use kaspa_consensus_core::constants::SOMPI_PER_KASPA;
+use kaspa_wallet_core::utils::try_kaspa_str_to_sompi;
 use std::fmt::Display;
 
 pub fn try_parse_required_nonzero_kaspa_as_sompi_u64<S: ToString + Display>(kaspa_amount: Option<S>) -> Result<u64> {
-    if let Some(kaspa_amount) = kaspa_amount {
-        let sompi_amount = kaspa_amount
-            .to_string()
-            .parse::<f64>()
-            .map_err(|_| Error::custom(format!("Supplied Kasapa amount is not valid: '{kaspa_amount}'")))?
-            * SOMPI_PER_KASPA as f64;
-        if sompi_amount < 0.0 {
-            Err(Error::custom("Supplied Kaspa amount is not valid: '{kaspa_amount}'"))
-        } else {
-            let sompi_amount = sompi_amount as u64;
-            if sompi_amount == 0 {
-                Err(Error::custom("Supplied required kaspa amount must not be a zero: '{kaspa_amount}'"))
-            } else {
-                Ok(sompi_amount)
-            }
-        }
+    let sompi_amount = try_parse_required_kaspa_as_sompi_u64(kaspa_amount.as_ref())?;
+    if sompi_amount == 0 {
+        Err(Error::custom(format!("Supplied required kaspa amount must not be a zero: '{}'", kaspa_amount.unwrap())))
     } else {
-        Err(Error::custom("Missing Kaspa amount"))
+        Ok(sompi_amount)
     }
 }
 
 pub fn try_parse_required_kaspa_as_sompi_u64<S: ToString + Display>(kaspa_amount: Option<S>) -> Result<u64> {
     if let Some(kaspa_amount) = kaspa_amount {
-        let sompi_amount = kaspa_amount
-            .to_string()
-            .parse::<f64>()
-            .map_err(|_| Error::custom(format!("Supplied Kasapa amount is not valid: '{kaspa_amount}'")))?
-            * SOMPI_PER_KASPA as f64;
-        if sompi_amount < 0.0 {
-            Err(Error::custom("Supplied Kaspa amount is not valid: '{kaspa_amount}'"))
-        } else {
-            Ok(sompi_amount as u64)
-        }
+        try_kaspa_str_to_sompi(kaspa_amount.to_string())?.ok_or_else(|| Error::custom("Missing Kaspa amount"))
     } else {
         Err(Error::custom("Missing Kaspa amount"))
     }
@@ -44,15 +22,12 @@ pub fn try_parse_required_kaspa_as_sompi_u64<S: ToString + Display>(kaspa_amount
 
 pub fn try_parse_optional_kaspa_as_sompi_i64<S: ToString + Display>(kaspa_amount: Option<S>) -> Result<Option<i64>> {
     if let Some(kaspa_amount) = kaspa_amount {
-        let sompi_amount = kaspa_amount
-            .to_string()
-            .parse::<f64>()
-            .map_err(|_e| Error::custom(format!("Supplied Kasapa amount is not valid: '{kaspa_amount}'")))?
-            * SOMPI_PER_KASPA as f64;
-        if sompi_amount < 0.0 {
-            Err(Error::custom("Supplied Kaspa amount is not valid: '{kaspa_amount}'"))
+        if let Some(sompi_amount) = try_kaspa_str_to_sompi(kaspa_amount.to_string())? {
+            let sompi_amount =
+                i64::try_from(sompi_amount).map_err(|_| Error::custom(format!("Supplied Kaspa amount is too large: '{kaspa_amount}'")))?;
+            Ok(Some(sompi_amount))
         } else {
-            Ok(Some(sompi_amount as i64))
+            Ok(None)
         }
     } else {
         Ok(None)
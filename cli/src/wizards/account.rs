@@ -2,7 +2,7 @@ use crate::cli::KaspaCli;
 use crate::imports::*;
 use crate::result::Result;
 use kaspa_bip32::{Language, Mnemonic, WordCount};
-use kaspa_wallet_core::account::MULTISIG_ACCOUNT_KIND;
+use kaspa_wallet_core::account::{BIP32_ACCOUNT_KIND, MULTISIG_ACCOUNT_KIND};
 // use kaspa_wallet_core::runtime::wallet::AccountCreateArgsBip32;
 // use kaspa_wallet_core::runtime::{PrvKeyDataArgs, PrvKeyDataCreateArgs};
 // use kaspa_wallet_core::storage::AccountKind;
@@ -12,8 +12,9 @@ pub(crate) async fn create(
     prv_key_data_info: Arc<PrvKeyDataInfo>,
     account_kind: AccountKind,
     name: Option<&str>,
+    account_index: Option<u64>,
+    account_path: Option<String>,
 ) -> Result<()> {
-    let term = ctx.term();
     let wallet = ctx.wallet();
 
     // TODO @aspect
@@ -22,20 +23,24 @@ pub(crate) async fn create(
     let name = if let Some(name) = name {
         Some(name.to_string())
     } else {
-        Some(term.ask(false, "Please enter account name (optional, press <enter> to skip): ").await?.trim().to_string())
+        Some(ctx.ask(false, "Please enter account name (optional, press <enter> to skip): ").await?.trim().to_string())
     };
 
+    if (account_index.is_some() || account_path.is_some()) && account_kind != BIP32_ACCOUNT_KIND {
+        return Err(Error::custom("custom account index or derivation path is only supported for bip32 accounts"));
+    }
+
     if account_kind == MULTISIG_ACCOUNT_KIND {
         return create_multisig(ctx, name, word_count).await;
     }
 
-    let wallet_secret = Secret::new(term.ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
+    let wallet_secret = Secret::new(ctx.ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
     if wallet_secret.as_ref().is_empty() {
         return Err(Error::WalletSecretRequired);
     }
 
     let payment_secret = if prv_key_data_info.is_encrypted() {
-        let payment_secret = Secret::new(term.ask(true, "Enter payment password: ").await?.trim().as_bytes().to_vec());
+        let payment_secret = Secret::new(ctx.ask(true, "Enter payment password: ").await?.trim().as_bytes().to_vec());
         if payment_secret.as_ref().is_empty() {
             return Err(Error::PaymentSecretRequired);
         } else {
@@ -45,7 +50,7 @@ pub(crate) async fn create(
         None
     };
 
-    let account_create_args_bip32 = AccountCreateArgsBip32::new(name, None);
+    let account_create_args_bip32 = AccountCreateArgsBip32::new(name, account_index).with_account_path(account_path);
     let account =
         wallet.create_account_bip32(&wallet_secret, prv_key_data_info.id, payment_secret.as_ref(), account_create_args_bip32).await?;
 
@@ -55,12 +60,11 @@ pub(crate) async fn create(
 }
 
 async fn create_multisig(ctx: &Arc<KaspaCli>, account_name: Option<String>, mnemonic_phrase_word_count: WordCount) -> Result<()> {
-    let term = ctx.term();
     let wallet = ctx.wallet();
     let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
-    let minimum_signatures: u16 = term.ask(false, "Enter the minimum number of signatures required: ").await?.parse()?;
+    let minimum_signatures: u16 = ctx.ask(false, "Enter the minimum number of signatures required: ").await?.parse()?;
 
-    let prv_keys_len: usize = term.ask(false, "Enter the number of private keys to generate: ").await?.parse()?;
+    let prv_keys_len: usize = ctx.ask(false, "Enter the number of private keys to generate: ").await?.parse()?;
 
     let mut prv_key_data_args = Vec::with_capacity(prv_keys_len);
     for _ in 0..prv_keys_len {
@@ -72,10 +76,10 @@ async fn create_multisig(ctx: &Arc<KaspaCli>, account_name: Option<String>, mnem
         prv_key_data_args.push(PrvKeyDataArgs::new(prv_key_data_id, None));
     }
 
-    let additional_xpub_keys_len: usize = term.ask(false, "Enter the number of additional extended public keys: ").await?.parse()?;
+    let additional_xpub_keys_len: usize = ctx.ask(false, "Enter the number of additional extended public keys: ").await?.parse()?;
     let mut xpub_keys = Vec::with_capacity(additional_xpub_keys_len + prv_keys_len);
     for i in 1..=additional_xpub_keys_len {
-        let xpub_key = term.ask(false, &format!("Enter extended public {i} key: ")).await?;
+        let xpub_key = ctx.ask(false, &format!("Enter extended public {i} key: ")).await?;
         xpub_keys.push(xpub_key.trim().to_owned());
     }
     let account =
@@ -3,24 +3,33 @@ use crate::imports::*;
 use crate::result::Result;
 use crate::KaspaCli;
 use kaspa_bip32::{Language, Mnemonic};
+use kaspa_utils::option::non_empty;
 use kaspa_wallet_core::account::{BIP32_ACCOUNT_KIND, LEGACY_ACCOUNT_KIND, MULTISIG_ACCOUNT_KIND};
+use kaspa_wallet_core::error::Error as WalletError;
+use kaspa_wallet_core::utxo::{ScanControl, ScanProgress};
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use workflow_core::channel::oneshot;
 
-pub async fn prompt_for_mnemonic(term: &Arc<Terminal>) -> Result<Vec<String>> {
+/// Minimum number of scanned addresses between two progress reports
+const SCAN_PROGRESS_INTERVAL: usize = 100;
+
+pub async fn prompt_for_mnemonic(ctx: &Arc<KaspaCli>) -> Result<Vec<String>> {
     let mut words: Vec<String> = vec![];
     loop {
         if words.is_empty() {
-            tprintln!(term, "Please enter mnemonic (12 or 24 space separated words)");
+            tprintln!(ctx, "Please enter mnemonic (12 or 24 space separated words)");
         } else if words.len() < 12 {
             let remains_for_12 = 12 - words.len();
             let remains_for_24 = 24 - words.len();
-            tprintln!(term, "Please enter additional {} or {} words or <enter> to abort", remains_for_12, remains_for_24);
+            tprintln!(ctx, "Please enter additional {} or {} words or <enter> to abort", remains_for_12, remains_for_24);
         } else {
             let remains_for_24 = 24 - words.len();
-            tprintln!(term, "Please enter additional {} words or <enter> to abort", remains_for_24);
+            tprintln!(ctx, "Please enter additional {} words or <enter> to abort", remains_for_24);
         }
-        let text = term.ask(false, "Mnemonic:").await?;
-        let list = text.split_whitespace().map(|s| s.to_string()).collect::<Vec<String>>();
+        let text = ctx.ask(false, "Mnemonic:").await?;
+        let list = text.split_whitespace().map(|s| s.to_lowercase()).collect::<Vec<String>>();
         if list.is_empty() {
             return Err(Error::UserAbort);
         }
@@ -31,11 +40,8 @@ pub async fn prompt_for_mnemonic(term: &Arc<Terminal>) -> Result<Vec<String>> {
         }
     }
 
-    if words.len() > 24 {
-        Err("Mnemonic must be 12 or 24 words".into())
-    } else {
-        Ok(words)
-    }
+    crate::helpers::validate_mnemonic(&words)?;
+    Ok(words)
 }
 
 pub(crate) async fn import_with_mnemonic(ctx: &Arc<KaspaCli>, account_kind: AccountKind, additional_xpubs: &[String]) -> Result<()> {
@@ -45,12 +51,11 @@ pub(crate) async fn import_with_mnemonic(ctx: &Arc<KaspaCli>, account_kind: Acco
         return Err(Error::WalletIsNotOpen);
     }
 
-    let term = ctx.term();
 
     tprintln!(ctx);
-    let wallet_secret = Secret::new(term.ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
+    let wallet_secret = Secret::new(ctx.ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
     tprintln!(ctx);
-    let mnemonic = prompt_for_mnemonic(&term).await?;
+    let mnemonic = prompt_for_mnemonic(ctx).await?;
     tprintln!(ctx);
     let length = mnemonic.len();
     match account_kind.as_ref() {
@@ -61,67 +66,70 @@ pub(crate) async fn import_with_mnemonic(ctx: &Arc<KaspaCli>, account_kind: Acco
         _ => Err(Error::Custom("unsupported account kind".to_owned())),
     }?;
 
-    let payment_secret = if account_kind == LEGACY_ACCOUNT_KIND {
-        None
-    } else {
-        tpara!(
-            ctx,
-            "\
-            \
-            If your original wallet has a bip39 recovery passphrase, please enter it now.\
-            \
-            Specifically, this is not a wallet password. This is a secondary mnemonic passphrase\
-            used to encrypt your mnemonic. This is known as a 'payment passphrase'\
-            'mnemonic passphrase', or a 'recovery passphrase'. If your mnemonic was created\
-            with a payment passphrase and you do not enter it now, the import process\
-            will generate a different private key.\
-            \
-            If you do not have a bip39 recovery passphrase, press ENTER.\
-            \
-            ",
-        );
-
-        let payment_secret = term.ask(true, "Enter payment password (optional): ").await?;
-        if payment_secret.trim().is_empty() {
-            None
-        } else {
-            Some(Secret::new(payment_secret.trim().as_bytes().to_vec()))
-        }
-    };
+    let bip39_passphrase = if account_kind == LEGACY_ACCOUNT_KIND { None } else { prompt_for_bip39_passphrase(ctx).await? };
 
     let mnemonic = mnemonic.join(" ");
     let mnemonic = Mnemonic::new(mnemonic.trim(), Language::English)?;
 
     let account = if account_kind != MULTISIG_ACCOUNT_KIND {
-        wallet.import_with_mnemonic(&wallet_secret, payment_secret.as_ref(), mnemonic, account_kind).await?
+        // the bip39 passphrase is explicit (empty if not provided) so that the payment password only encrypts the private key data
+        let (payment_secret, bip39_passphrase) = if account_kind == LEGACY_ACCOUNT_KIND {
+            (None, None)
+        } else {
+            (prompt_for_payment_secret(ctx).await?, Some(bip39_passphrase.unwrap_or_else(|| Secret::from(""))))
+        };
+        let result = import_with_scan_progress(
+            ctx,
+            &wallet_secret,
+            payment_secret.as_ref(),
+            bip39_passphrase.as_ref(),
+            mnemonic,
+            account_kind,
+        )
+        .await;
+        match result {
+            Err(Error::WalletError(WalletError::PrivateKeyAlreadyExists(prv_key_data_id))) => {
+                // the mnemonic was imported before, select its account instead
+                let accounts = wallet.accounts(Some(prv_key_data_id)).await?.try_collect::<Vec<_>>().await?;
+                let Some(account) = accounts.into_iter().find(|account| account.account_kind() == account_kind) else {
+                    return Err(Error::PrivateKeyAlreadyExists(prv_key_data_id.to_hex()));
+                };
+                tprintln!(ctx, "\nthis mnemonic already exists in the wallet, selecting account: {}\n", account.get_list_string()?);
+                wallet.select(Some(&account)).await?;
+                return Ok(());
+            }
+            result => result?,
+        }
     } else {
-        let mut mnemonics_secrets = vec![(mnemonic, payment_secret)];
+        // the private key data of multisig accounts is encrypted with its bip39 passphrase,
+        // which is then required as the payment password when signing
+        let mut mnemonics_secrets = vec![(mnemonic, bip39_passphrase)];
         while matches!(
-            term.ask(false, "Do you want to add more mnemonics (type 'y' to approve)?: ").await?.trim(),
+            ctx.ask(false, "Do you want to add more mnemonics (type 'y' to approve)?: ").await?.trim(),
             "y" | "Y" | "YES" | "yes"
         ) {
             tprintln!(ctx);
-            let mnemonic = prompt_for_mnemonic(&term).await?;
+            let mnemonic = prompt_for_mnemonic(ctx).await?;
             tprintln!(ctx);
-            let payment_secret = term.ask(true, "Enter payment password (optional): ").await?;
-            let payment_secret = payment_secret.trim().is_not_empty().then(|| Secret::new(payment_secret.trim().as_bytes().to_vec()));
+            let bip39_passphrase = ctx.ask(true, "Enter bip39 mnemonic passphrase (optional): ").await?;
+            let bip39_passphrase = non_empty(bip39_passphrase).map(|bip39_passphrase| Secret::new(bip39_passphrase.into_bytes()));
             let mnemonic = mnemonic.join(" ");
             let mnemonic = Mnemonic::new(mnemonic.trim(), Language::English)?;
 
-            mnemonics_secrets.push((mnemonic, payment_secret));
+            mnemonics_secrets.push((mnemonic, bip39_passphrase));
         }
 
         let mut additional_xpubs = additional_xpubs.to_vec();
         if additional_xpubs.is_empty() {
             loop {
-                let xpub_key = term.ask(false, "Enter extended public key: (empty to skip or stop)").await?;
+                let xpub_key = ctx.ask(false, "Enter extended public key: (empty to skip or stop)").await?;
                 if xpub_key.is_empty() {
                     break;
                 }
                 additional_xpubs.push(xpub_key.trim().to_owned());
             }
         }
-        let n_required: u16 = term.ask(false, "Enter the minimum number of signatures required: ").await?.parse()?;
+        let n_required: u16 = ctx.ask(false, "Enter the minimum number of signatures required: ").await?.parse()?;
 
         wallet.import_multisig_with_mnemonic(&wallet_secret, mnemonics_secrets, n_required, additional_xpubs).await?
     };
@@ -130,3 +138,135 @@ pub(crate) async fn import_with_mnemonic(ctx: &Arc<KaspaCli>, account_kind: Acco
     wallet.select(Some(&account)).await?;
     Ok(())
 }
+
+/// Prompts for the optional bip39 passphrase (the '25th word') the mnemonic was created with
+async fn prompt_for_bip39_passphrase(ctx: &Arc<KaspaCli>) -> Result<Option<Secret>> {
+    tpara!(
+        ctx,
+        "\
+        \
+        If your original wallet has a bip39 recovery passphrase, please enter it now.\
+        \
+        Specifically, this is not a wallet password. This is a secondary mnemonic passphrase\
+        (also known as the '25th word', 'mnemonic passphrase' or 'recovery passphrase')\
+        that alters the private key derived from your mnemonic. If your mnemonic was created\
+        with a bip39 passphrase and you do not enter it now, the import process\
+        will generate a different private key.\
+        \
+        If you do not have a bip39 recovery passphrase, press ENTER.\
+        \
+        ",
+    );
+
+    let bip39_passphrase = ctx.ask(true, "Enter bip39 mnemonic passphrase (optional): ").await?;
+    Ok(non_empty(bip39_passphrase).map(|bip39_passphrase| Secret::new(bip39_passphrase.into_bytes())))
+}
+
+/// Prompts for the optional payment password encrypting the imported private key data
+async fn prompt_for_payment_secret(ctx: &Arc<KaspaCli>) -> Result<Option<Secret>> {
+    tprintln!(ctx);
+    tpara!(
+        ctx,
+        "\
+        PLEASE NOTE: The optional payment password, if provided, encrypts your private key \
+        and will be required to issue transactions. It does not alter your private key and \
+        is not required when recovering your wallet from the mnemonic. \
+        \
+        If you do not want to use a payment password, press ENTER.\
+        ",
+    );
+
+    let payment_secret = ctx.ask(true, "Enter payment password (optional): ").await?;
+    let payment_secret = non_empty(payment_secret).map(|payment_secret| Secret::new(payment_secret.into_bytes()));
+    if let Some(payment_secret) = payment_secret.as_ref() {
+        let payment_secret_validate =
+            Secret::new(ctx.ask(true, "Please re-enter payment password: ").await?.trim().as_bytes().to_vec());
+        if payment_secret_validate.as_ref() != payment_secret.as_ref() {
+            return Err(Error::PaymentSecretMatch);
+        }
+    }
+    Ok(payment_secret)
+}
+
+/// Imports a golang `kaspawallet` keys file (`keys.json`), creating the corresponding bip32 or multisig account.
+pub(crate) async fn import_kaspa_wallet_file(ctx: &Arc<KaspaCli>, filename: &str) -> Result<()> {
+    let wallet = ctx.wallet();
+
+    if !wallet.is_open() {
+        return Err(Error::WalletIsNotOpen);
+    }
+
+    let json = workflow_store::fs::read_to_string(&PathBuf::from(filename))
+        .await
+        .map_err(|err| Error::custom(format!("unable to read kaspawallet file '{filename}': {err}")))?;
+
+    tprintln!(ctx);
+    let import_secret = Secret::new(ctx.ask(true, "Enter the password of the kaspawallet file: ").await?.trim().as_bytes().to_vec());
+    let wallet_secret = Secret::new(ctx.ask(true, "Enter wallet password: ").await?.trim().as_bytes().to_vec());
+
+    let account = wallet.import_kaspawallet_golang(&import_secret, &wallet_secret, &json).await?;
+
+    tprintln!(ctx, "\naccount imported: {}\n", account.get_list_string()?);
+    wallet.select(Some(&account)).await?;
+    Ok(())
+}
+
+/// Imports an account from a mnemonic while reporting the progress of the scan of its
+/// addresses. Pressing any key aborts the scan, importing the account with the UTXOs found so far.
+async fn import_with_scan_progress(
+    ctx: &Arc<KaspaCli>,
+    wallet_secret: &Secret,
+    payment_secret: Option<&Secret>,
+    bip39_passphrase: Option<&Secret>,
+    mnemonic: Mnemonic,
+    account_kind: AccountKind,
+) -> Result<Arc<dyn Account>> {
+    let wallet = ctx.wallet();
+    let abortable = Abortable::new();
+    let last_report = Arc::new(AtomicUsize::new(0));
+    let ctx_ = ctx.clone();
+    let control = ScanControl::new(
+        &abortable,
+        Some(Arc::new(move |progress: ScanProgress| {
+            if progress.scanned >= last_report.load(Ordering::SeqCst) + SCAN_PROGRESS_INTERVAL {
+                last_report.store(progress.scanned, Ordering::SeqCst);
+                tprintln!(ctx_, "scanned {} addresses, found {} funded", progress.scanned, progress.funded);
+            }
+        })),
+    );
+
+    // the key press listener is awaited once the import completes, so it does not capture later input
+    let key_pressed = (wallet.is_connected() && ctx.is_interactive()).then(|| {
+        tprintln!(ctx, "Scanning account addresses, press any key to abort...");
+        let (sender, receiver) = oneshot();
+        let term = ctx.term();
+        let abortable = abortable.clone();
+        spawn(async move {
+            term.kbhit(None).await.ok();
+            abortable.abort();
+            sender.send(()).await.ok();
+        });
+        receiver
+    });
+
+    let result = wallet
+        .import_with_mnemonic_and_scan_control(wallet_secret, payment_secret, bip39_passphrase, mnemonic, account_kind, &control)
+        .await;
+
+    if let Some(key_pressed) = key_pressed {
+        if !abortable.is_aborted() {
+            tprintln!(ctx, "Scan complete, press any key to continue...");
+        }
+        key_pressed.recv().await.ok();
+    }
+
+    let (account, progress) = result?;
+    if progress.scanned > 0 {
+        tprintln!(ctx, "scanned {} addresses, found {} funded", progress.scanned, progress.funded);
+    }
+    if progress.aborted {
+        tprintln!(ctx, "{}", style("Scan aborted, the account balance may be incomplete (use `account scan` to rescan)").yellow());
+    }
+
+    Ok(account)
+}
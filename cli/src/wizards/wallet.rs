@@ -2,6 +2,7 @@ use crate::cli::KaspaCli;
 use crate::imports::*;
 use crate::result::Result;
 use kaspa_bip32::{Language, Mnemonic, WordCount};
+use kaspa_utils::option::non_empty;
 use kaspa_wallet_core::storage::{make_filename, Hint};
 
 pub(crate) async fn create(ctx: &Arc<KaspaCli>, name: Option<&str>, import_with_mnemonic: bool) -> Result<()> {
@@ -26,13 +27,13 @@ pub(crate) async fn create(ctx: &Arc<KaspaCli>, name: Option<&str>, import_with_
         tprintln!(ctx);
 
         let overwrite =
-            term.ask(false, "Are you sure you want to overwrite it (type 'y' to approve)?: ").await?.trim().to_string().to_lowercase();
+            ctx.ask(false, "Are you sure you want to overwrite it (type 'y' to approve)?: ").await?.trim().to_string().to_lowercase();
         if overwrite.ne("y") {
             return Ok(());
         }
     }
 
-    let account_name = term.ask(false, "Default account title: ").await?.trim().to_string();
+    let account_name = ctx.ask(false, "Default account title: ").await?.trim().to_string();
     let account_name = account_name.is_not_empty().then_some(account_name);
 
     tpara!(
@@ -48,20 +49,43 @@ pub(crate) async fn create(ctx: &Arc<KaspaCli>, name: Option<&str>, import_with_
         ",
     );
 
-    let hint = term.ask(false, "Create phishing hint (optional, press <enter> to skip): ").await?.trim().to_string();
+    let hint = ctx.ask(false, "Create phishing hint (optional, press <enter> to skip): ").await?.trim().to_string();
     let hint = hint.is_not_empty().then_some(hint).map(Hint::from);
     //if hint.is_empty() { None } else { Some(hint) };
 
-    let wallet_secret = Secret::new(term.ask(true, "Enter wallet encryption password: ").await?.trim().as_bytes().to_vec());
+    let wallet_secret = Secret::new(ctx.ask(true, "Enter wallet encryption password: ").await?.trim().as_bytes().to_vec());
     if wallet_secret.as_ref().is_empty() {
         return Err(Error::WalletSecretRequired);
     }
     let wallet_secret_validate =
-        Secret::new(term.ask(true, "Re-enter wallet encryption password: ").await?.trim().as_bytes().to_vec());
+        Secret::new(ctx.ask(true, "Re-enter wallet encryption password: ").await?.trim().as_bytes().to_vec());
     if wallet_secret_validate.as_ref() != wallet_secret.as_ref() {
         return Err(Error::WalletSecretMatch);
     }
 
+    tprintln!(ctx, "");
+    tpara!(
+        ctx,
+        "\
+        PLEASE NOTE: The optional payment password, if provided, encrypts your private key \
+        and will be required to issue transactions. It does not alter your private key and \
+        is not required when recovering your wallet from the mnemonic. \
+        \
+        If you do not want to use a payment password, press ENTER.\
+        ",
+    );
+
+    let payment_secret = ctx.ask(true, "Enter payment password (optional): ").await?;
+    let payment_secret = non_empty(payment_secret).map(|payment_secret| Secret::new(payment_secret.into_bytes()));
+
+    if let Some(payment_secret) = payment_secret.as_ref() {
+        let payment_secret_validate =
+            Secret::new(ctx.ask(true, "Please re-enter payment password: ").await?.trim().as_bytes().to_vec());
+        if payment_secret_validate.as_ref() != payment_secret.as_ref() {
+            return Err(Error::PaymentSecretMatch);
+        }
+    }
+
     tprintln!(ctx, "");
     if import_with_mnemonic {
         tpara!(
@@ -71,9 +95,9 @@ pub(crate) async fn create(ctx: &Arc<KaspaCli>, name: Option<&str>, import_with_
             If your original wallet has a bip39 recovery passphrase, please enter it now.\
             \
             Specifically, this is not a wallet password. This is a secondary mnemonic passphrase\
-            used to encrypt your mnemonic. This is known as a 'payment passphrase'\
-            'mnemonic passphrase', or a 'recovery passphrase'. If your mnemonic was created\
-            with a payment passphrase and you do not enter it now, the import process\
+            (also known as the '25th word', 'mnemonic passphrase' or 'recovery passphrase')\
+            that alters the private key derived from your mnemonic. If your mnemonic was created\
+            with a bip39 passphrase and you do not enter it now, the import process\
             will generate a different private key.\
             \
             If you do not have a bip39 recovery passphrase, press ENTER.\
@@ -84,32 +108,34 @@ pub(crate) async fn create(ctx: &Arc<KaspaCli>, name: Option<&str>, import_with_
         tpara!(
             ctx,
             "\
-            PLEASE NOTE: The optional bip39 mnemonic passphrase, if provided, will be required to \
-            issue transactions. This passphrase will also be required when recovering your wallet \
-            in addition to your private key or mnemonic. If you loose this passphrase, you will not \
-            be able to use or recover your wallet! \
+            PLEASE NOTE: The optional bip39 mnemonic passphrase (the '25th word'), if provided, \
+            alters the private key derived from your mnemonic. This passphrase will be required \
+            when recovering your wallet in addition to your mnemonic. If you loose this passphrase, \
+            you will not be able to recover your wallet! \
             \
             If you do not want to use bip39 recovery passphrase, press ENTER.\
             ",
         );
     }
 
-    let payment_secret = term.ask(true, "Enter bip39 mnemonic passphrase (optional): ").await?;
-    let payment_secret =
-        if payment_secret.trim().is_empty() { None } else { Some(Secret::new(payment_secret.trim().as_bytes().to_vec())) };
+    let bip39_passphrase = ctx.ask(true, "Enter bip39 mnemonic passphrase (optional): ").await?;
+    let bip39_passphrase = non_empty(bip39_passphrase).map(|bip39_passphrase| Secret::new(bip39_passphrase.into_bytes()));
 
-    if let Some(payment_secret) = payment_secret.as_ref() {
-        let payment_secret_validate =
-            Secret::new(term.ask(true, "Please re-enter mnemonic passphrase: ").await?.trim().as_bytes().to_vec());
-        if payment_secret_validate.as_ref() != payment_secret.as_ref() {
-            return Err(Error::PaymentSecretMatch);
+    if let Some(bip39_passphrase) = bip39_passphrase.as_ref() {
+        let bip39_passphrase_validate =
+            Secret::new(ctx.ask(true, "Please re-enter mnemonic passphrase: ").await?.trim().as_bytes().to_vec());
+        if bip39_passphrase_validate.as_ref() != bip39_passphrase.as_ref() {
+            return Err(Error::Bip39PassphraseMatch);
         }
     }
 
     tprintln!(ctx, "");
 
+    // the bip39 passphrase is always explicit (empty if not provided) so that
+    // the payment password is only used to encrypt the private key data
+    let bip39_passphrase = bip39_passphrase.unwrap_or_else(|| Secret::from(""));
     let prv_key_data_args = if import_with_mnemonic {
-        let words = crate::wizards::import::prompt_for_mnemonic(&term).await?;
+        let words = crate::wizards::import::prompt_for_mnemonic(ctx).await?;
         PrvKeyDataCreateArgs::new(None, payment_secret.clone(), words.join(" "))
     } else {
         PrvKeyDataCreateArgs::new(
@@ -117,7 +143,8 @@ pub(crate) async fn create(ctx: &Arc<KaspaCli>, name: Option<&str>, import_with_
             payment_secret.clone(),
             Mnemonic::random(word_count, Language::default())?.phrase().to_string(),
         )
-    };
+    }
+    .with_bip39_passphrase(Some(bip39_passphrase));
 
     let mnemonic_phrase = prv_key_data_args.mnemonic.clone();
 
@@ -15,13 +15,14 @@ use itertools::{
 };
 use kaspa_consensus_core::config::Config;
 use kaspa_core::{debug, info, task::tick::TickService, time::unix_now, warn};
-use kaspa_database::prelude::{CachePolicy, StoreResultExtensions, DB};
+use kaspa_database::prelude::{CachePolicy, StoreError, DB};
 use kaspa_utils::networking::IpAddress;
 use local_ip_address::list_afinet_netifas;
 use parking_lot::Mutex;
-use stores::banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, ConnectionBanTimestamp, DbBannedAddressesStore};
+use stores::banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, DbBannedAddressesStore};
 use thiserror::Error;
 
+pub use stores::banned_address_store::ConnectionBan;
 pub use stores::NetAddress;
 
 const MAX_ADDRESSES: usize = 4096;
@@ -294,36 +295,56 @@ impl AddressManager {
         self.address_store.iterate_prioritized_random_addresses(exceptions)
     }
 
-    pub fn ban(&mut self, ip: IpAddress) {
-        self.banned_address_store.set(ip.into(), ConnectionBanTimestamp(unix_now())).unwrap();
+    /// Bans `ip` for `duration`, or for the configured ban duration if `None`, replacing any existing ban.
+    pub fn ban(&mut self, ip: IpAddress, reason: String, duration: Option<Duration>) -> ConnectionBan {
+        let banned_at = unix_now();
+        let duration = duration.unwrap_or(Duration::from_secs(self.config.ban_duration)).as_millis().min(u64::MAX as u128) as u64;
+        let ban = ConnectionBan { banned_at, expires_at: banned_at.saturating_add(duration), reason };
+        self.banned_address_store.set(ip.into(), ban.clone()).unwrap();
         self.address_store.remove_by_ip(ip.into());
+        ban
     }
 
     pub fn unban(&mut self, ip: IpAddress) {
         self.banned_address_store.remove(ip.into()).unwrap();
     }
 
-    pub fn is_banned(&mut self, ip: IpAddress) -> bool {
-        const MAX_BANNED_TIME: u64 = 24 * 60 * 60 * 1000;
-        match self.banned_address_store.get(ip.into()).unwrap_option() {
-            Some(timestamp) => {
-                if unix_now() - timestamp.0 > MAX_BANNED_TIME {
-                    self.unban(ip);
-                    false
-                } else {
-                    true
-                }
+    /// Returns the ban of `ip` unless it expired, in which case it is removed.
+    pub fn get_ban(&mut self, ip: IpAddress) -> Option<ConnectionBan> {
+        match self.banned_address_store.get(ip.into()) {
+            Ok(ban) if !ban.is_expired(unix_now()) => Some(ban),
+            // Bans stored by previous versions hold only the ban time and cannot be read, they are lifted
+            Ok(_) | Err(StoreError::DeserializationError(_)) => {
+                self.unban(ip);
+                None
             }
-            None => false,
+            Err(StoreError::KeyNotFound(_)) => None,
+            Err(err) => panic!("Unexpected store error: {err:?}"),
         }
     }
 
+    pub fn is_banned(&mut self, ip: IpAddress) -> bool {
+        self.get_ban(ip).is_some()
+    }
+
     pub fn get_all_addresses(&self) -> Vec<NetAddress> {
         self.address_store.iterate_addresses().collect_vec()
     }
 
     pub fn get_all_banned_addresses(&self) -> Vec<IpAddress> {
-        self.banned_address_store.iterator().map(|x| IpAddress::from(x.unwrap().0)).collect_vec()
+        self.get_all_bans().into_iter().map(|(ip, _)| ip).collect_vec()
+    }
+
+    /// Returns all bans which did not expire, ordered by expiry.
+    pub fn get_all_bans(&self) -> Vec<(IpAddress, ConnectionBan)> {
+        let now = unix_now();
+        self.banned_address_store
+            .iterator()
+            .filter_map(|entry| entry.ok())
+            .filter(|(_, ban)| !ban.is_expired(now))
+            .map(|(ip, ban)| (IpAddress::from(ip), ban))
+            .sorted_by_key(|(_, ban)| ban.expires_at)
+            .collect_vec()
     }
 }
 
@@ -515,7 +536,7 @@ mod address_store_with_cache {
 
         use super::*;
         use address_manager::AddressManager;
-        use kaspa_consensus_core::config::{params::SIMNET_PARAMS, Config};
+        use kaspa_consensus_core::config::{params::SIMNET_PARAMS, Config, DEFAULT_BAN_DURATION_SECS};
         use kaspa_core::task::tick::TickService;
         use kaspa_database::create_temp_db;
         use kaspa_database::prelude::ConnBuilder;
@@ -536,6 +557,29 @@ mod address_store_with_cache {
             assert_eq!(iter.count(), 0);
         }
 
+        #[test]
+        fn test_ban_expiry() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Config::new(SIMNET_PARAMS);
+            let (am, _) = AddressManager::new(Arc::new(config), db.1, Arc::new(TickService::default()));
+            let mut am = am.lock();
+
+            let default_ip = IpAddress::from_str("1.2.3.4").unwrap();
+            let expired_ip = IpAddress::from_str("5.6.7.8").unwrap();
+            let ban = am.ban(default_ip, "misbehaving".to_string(), None);
+            assert_eq!(ban.expires_at - ban.banned_at, DEFAULT_BAN_DURATION_SECS * 1000);
+            am.ban(expired_ip, "expired".to_string(), Some(std::time::Duration::ZERO));
+
+            assert_eq!(am.get_ban(default_ip), Some(ban.clone()));
+            assert_eq!(am.get_all_bans(), vec![(default_ip, ban)]);
+            assert!(!am.is_banned(expired_ip));
+            assert_eq!(am.get_all_banned_addresses(), vec![default_ip]);
+
+            am.unban(default_ip);
+            assert!(!am.is_banned(default_ip));
+            assert!(am.get_all_bans().is_empty());
+        }
+
         #[test]
         fn test_network_distribution_weighting() {
             kaspa_core::log::try_init_logger("info");
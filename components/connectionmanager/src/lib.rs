@@ -9,7 +9,7 @@ use std::{
 use duration_string::DurationString;
 use futures_util::future::join_all;
 use itertools::Itertools;
-use kaspa_addressmanager::{AddressManager, NetAddress};
+use kaspa_addressmanager::{AddressManager, ConnectionBan, NetAddress};
 use kaspa_core::{debug, info, warn};
 use kaspa_p2p_lib::{common::ProtocolError, ConnectionError, Peer};
 use kaspa_utils::triggers::SingleTrigger;
@@ -279,19 +279,20 @@ impl ConnectionManager {
         }
     }
 
-    /// Bans the given IP and disconnects from all the peers with that IP.
+    /// Bans the given IP for the given duration (or the configured default) and disconnects
+    /// from all the peers with that IP. Returns `None` if the IP has a permanent connection.
     ///
     /// _GO-KASPAD: BanByIP_
-    pub async fn ban(&self, ip: IpAddr) {
+    pub async fn ban(&self, ip: IpAddr, reason: String, duration: Option<Duration>) -> Option<ConnectionBan> {
         if self.ip_has_permanent_connection(ip).await {
-            return;
+            return None;
         }
         for peer in self.p2p_adaptor.active_peers() {
             if peer.net_address().ip() == ip {
                 self.p2p_adaptor.terminate(peer.key()).await;
             }
         }
-        self.address_manager.lock().ban(ip.into());
+        Some(self.address_manager.lock().ban(ip.into(), reason, duration))
     }
 
     /// Returns whether the given address is banned.
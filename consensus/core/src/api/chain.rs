@@ -0,0 +1,238 @@
+use std::collections::VecDeque;
+
+use crate::{api::ConsensusApi, errors::consensus::ConsensusResult};
+use kaspa_hashes::Hash;
+
+/// A change of the virtual selected parent chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VirtualChainChange {
+    /// The block was removed from the selected chain by a reorg
+    Removed(Hash),
+    /// The block was added to the selected chain
+    Added(Hash),
+}
+
+impl VirtualChainChange {
+    pub fn hash(&self) -> Hash {
+        match self {
+            VirtualChainChange::Removed(hash) | VirtualChainChange::Added(hash) => *hash,
+        }
+    }
+}
+
+/// Pulls the changes of the virtual selected parent chain starting from a chain block known by the caller.
+///
+/// The changes are yielded in order, removals of a reorg coming from the highest removed block downwards
+/// before additions from the lowest added block upwards, so that applying them one by one to the chain
+/// known by the caller keeps it a gapless selected chain. The iterator returns `None` once it caught up
+/// with the sink and resumes yielding the following changes if the virtual state advances later on.
+pub struct VirtualChainIterator<'a> {
+    consensus: &'a dyn ConsensusApi,
+    tip: Hash,
+    /// Changes pulled from consensus and not yet yielded, each with the chain tip it leads to
+    pending: VecDeque<(VirtualChainChange, Hash)>,
+}
+
+impl<'a> VirtualChainIterator<'a> {
+    /// Creates an iterator yielding the chain changes from the chain block `from` onwards
+    pub fn new(consensus: &'a dyn ConsensusApi, from: Hash) -> Self {
+        Self { consensus, tip: from, pending: VecDeque::new() }
+    }
+
+    /// Returns the tip of the selected chain after applying all the changes yielded so far.
+    /// An iterator created from this tip later on resumes exactly where this one stopped.
+    pub fn tip(&self) -> Hash {
+        self.tip
+    }
+
+    fn pull(&mut self) -> ConsensusResult<()> {
+        let path = self.consensus.get_virtual_chain_from_block(self.tip)?;
+        if let Some(&lowest_removed) = path.removed.last() {
+            let common_ancestor = self.consensus.get_ghostdag_data(lowest_removed)?.selected_parent;
+            let tips = path.removed.iter().skip(1).copied().chain(std::iter::once(common_ancestor));
+            self.pending.extend(path.removed.iter().copied().map(VirtualChainChange::Removed).zip(tips));
+        }
+        self.pending.extend(path.added.into_iter().map(|hash| (VirtualChainChange::Added(hash), hash)));
+        Ok(())
+    }
+}
+
+impl Iterator for VirtualChainIterator<'_> {
+    type Item = ConsensusResult<VirtualChainChange>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending.is_empty() {
+            if let Err(err) = self.pull() {
+                return Some(Err(err));
+            }
+        }
+        let (change, tip) = self.pending.pop_front()?;
+        self.tip = tip;
+        Some(Ok(change))
+    }
+}
+
+impl dyn ConsensusApi {
+    /// Returns an iterator pulling the changes of the virtual selected parent chain from the chain block `from` onwards
+    pub fn virtual_chain_iterator(&self, from: Hash) -> VirtualChainIterator<'_> {
+        VirtualChainIterator::new(self, from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{errors::consensus::ConsensusError, trusted::ExternalGhostdagData, BlockHashMap, ChainPath};
+    use std::sync::RwLock;
+
+    /// A synthetic DAG reduced to the selected parent of each block, with a sink which tests move at will
+    struct ChainMock {
+        selected_parents: RwLock<BlockHashMap<Hash>>,
+        sink: RwLock<Hash>,
+    }
+
+    impl ChainMock {
+        fn new() -> Self {
+            Self { selected_parents: RwLock::new(BlockHashMap::default()), sink: RwLock::new(hash(0)) }
+        }
+
+        /// Adds a block per number in `blocks`, each selecting the previous one, starting from `parent`
+        fn extend(&self, parent: u64, blocks: impl IntoIterator<Item = u64>) {
+            let mut selected_parents = self.selected_parents.write().unwrap();
+            let mut parent = hash(parent);
+            for block in blocks {
+                selected_parents.insert(hash(block), parent);
+                parent = hash(block);
+            }
+        }
+
+        fn set_sink(&self, sink: u64) {
+            *self.sink.write().unwrap() = hash(sink);
+        }
+
+        fn chain(&self, mut tip: Hash) -> Vec<Hash> {
+            let selected_parents = self.selected_parents.read().unwrap();
+            let mut chain = vec![tip];
+            while let Some(&parent) = selected_parents.get(&tip) {
+                chain.push(parent);
+                tip = parent;
+            }
+            chain.reverse();
+            chain
+        }
+    }
+
+    impl ConsensusApi for ChainMock {
+        fn get_virtual_chain_from_block(&self, hash: Hash) -> ConsensusResult<ChainPath> {
+            let sink_chain = self.chain(*self.sink.read().unwrap());
+            let mut removed = self.chain(hash);
+            let common = removed.iter().zip(sink_chain.iter()).take_while(|(a, b)| a == b).count();
+            if common == 0 {
+                return Err(ConsensusError::BlockNotFound(hash));
+            }
+            let added = sink_chain[common..].to_vec();
+            removed.drain(..common);
+            removed.reverse();
+            Ok(ChainPath { added, removed })
+        }
+
+        fn get_ghostdag_data(&self, hash: Hash) -> ConsensusResult<ExternalGhostdagData> {
+            let selected_parent = *self.selected_parents.read().unwrap().get(&hash).ok_or(ConsensusError::MissingData(hash))?;
+            Ok(ExternalGhostdagData {
+                blue_score: Default::default(),
+                blue_work: Default::default(),
+                selected_parent,
+                mergeset_blues: Default::default(),
+                mergeset_reds: Default::default(),
+                blues_anticone_sizes: Default::default(),
+            })
+        }
+    }
+
+    fn hash(n: u64) -> Hash {
+        Hash::from_u64_word(n)
+    }
+
+    /// Drains the iterator, applying the changes to `chain` while asserting it remains a gapless selected chain
+    fn apply(iter: &mut VirtualChainIterator, consensus: &ChainMock, chain: &mut Vec<Hash>) -> Vec<VirtualChainChange> {
+        let mut changes = Vec::new();
+        while let Some(change) = iter.next() {
+            let change = change.unwrap();
+            match change {
+                VirtualChainChange::Removed(hash) => assert_eq!(chain.pop(), Some(hash)),
+                VirtualChainChange::Added(hash) => {
+                    assert_eq!(consensus.get_ghostdag_data(hash).unwrap().selected_parent, *chain.last().unwrap());
+                    chain.push(hash)
+                }
+            }
+            assert_eq!(iter.tip(), *chain.last().unwrap());
+            changes.push(change);
+        }
+        changes
+    }
+
+    #[test]
+    fn test_virtual_chain_iterator() {
+        use VirtualChainChange::*;
+        let consensus = ChainMock::new();
+        consensus.extend(0, [1, 2, 3]);
+        consensus.set_sink(3);
+
+        let mut chain = vec![hash(0)];
+        let mut iter = VirtualChainIterator::new(&consensus, hash(0));
+        assert_eq!(apply(&mut iter, &consensus, &mut chain), vec![Added(hash(1)), Added(hash(2)), Added(hash(3))]);
+        assert!(iter.next().is_none());
+
+        // The chain advances
+        consensus.extend(3, [4, 5]);
+        consensus.set_sink(5);
+        assert_eq!(apply(&mut iter, &consensus, &mut chain), vec![Added(hash(4)), Added(hash(5))]);
+
+        // A reorg replaces the blocks above 3
+        consensus.extend(3, [6, 7, 8]);
+        consensus.set_sink(8);
+        assert_eq!(
+            apply(&mut iter, &consensus, &mut chain),
+            vec![Removed(hash(5)), Removed(hash(4)), Added(hash(6)), Added(hash(7)), Added(hash(8))]
+        );
+
+        // The sink moves back to a chain ancestor
+        consensus.set_sink(6);
+        assert_eq!(apply(&mut iter, &consensus, &mut chain), vec![Removed(hash(8)), Removed(hash(7))]);
+        assert_eq!(iter.tip(), hash(6));
+        assert_eq!(chain, consensus.chain(hash(6)));
+
+        // A new iterator resumes from the tip of a previous one
+        consensus.extend(6, [9]);
+        consensus.set_sink(9);
+        let mut resumed = (&consensus as &dyn ConsensusApi).virtual_chain_iterator(iter.tip());
+        assert_eq!(apply(&mut resumed, &consensus, &mut chain), vec![Added(hash(9))]);
+    }
+
+    #[test]
+    fn test_virtual_chain_iterator_reorg_while_iterating() {
+        let consensus = ChainMock::new();
+        consensus.extend(0, 1..=5);
+        consensus.set_sink(5);
+
+        let mut chain = vec![hash(0)];
+        let mut iter = VirtualChainIterator::new(&consensus, hash(0));
+        assert_eq!(iter.next().unwrap().unwrap(), VirtualChainChange::Added(hash(1)));
+        chain.push(hash(1));
+
+        // The pending changes of the previous path are still yielded, followed by the reorg reverting them
+        consensus.extend(2, [10, 11]);
+        consensus.set_sink(11);
+        let changes = apply(&mut iter, &consensus, &mut chain);
+        assert_eq!(changes.len(), 4 + 3 + 2);
+        assert_eq!(chain, consensus.chain(hash(11)));
+    }
+
+    #[test]
+    fn test_virtual_chain_iterator_unknown_block() {
+        let consensus = ChainMock::new();
+        let mut iter = VirtualChainIterator::new(&consensus, hash(42));
+        assert!(matches!(iter.next(), Some(Err(ConsensusError::BlockNotFound(_)))));
+        assert_eq!(iter.tip(), hash(42));
+    }
+}
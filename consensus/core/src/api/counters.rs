@@ -1,4 +1,8 @@
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
 
 #[derive(Default)]
 pub struct ProcessingCounters {
@@ -10,9 +14,33 @@ pub struct ProcessingCounters {
     pub txs_counts: AtomicU64,
     pub chain_block_counts: AtomicU64,
     pub mass_counts: AtomicU64,
+    /// Latest [`SyncEstimate`] published by the consensus monitor: 0 if unset, 1 if synced,
+    /// `u64::MAX` if no progress and the remaining seconds + 2 otherwise
+    sync_estimate: AtomicU64,
 }
 
 impl ProcessingCounters {
+    const SYNC_ESTIMATE_NO_PROGRESS: u64 = u64::MAX;
+
+    /// Returns the latest sync estimate or `None` if no estimate was published yet.
+    pub fn sync_estimate(&self) -> Option<SyncEstimate> {
+        match self.sync_estimate.load(Ordering::Relaxed) {
+            0 => None,
+            Self::SYNC_ESTIMATE_NO_PROGRESS => Some(SyncEstimate::NoProgress),
+            1 => Some(SyncEstimate::Synced),
+            secs => Some(SyncEstimate::Remaining(Duration::from_secs(secs - 2))),
+        }
+    }
+
+    pub fn set_sync_estimate(&self, estimate: SyncEstimate) {
+        let value = match estimate {
+            SyncEstimate::NoProgress => Self::SYNC_ESTIMATE_NO_PROGRESS,
+            SyncEstimate::Synced => 1,
+            SyncEstimate::Remaining(remaining) => remaining.as_secs().saturating_add(2).min(Self::SYNC_ESTIMATE_NO_PROGRESS - 1),
+        };
+        self.sync_estimate.store(value, Ordering::Relaxed);
+    }
+
     pub fn snapshot(&self) -> ProcessingCountersSnapshot {
         ProcessingCountersSnapshot {
             blocks_submitted: self.blocks_submitted.load(Ordering::Relaxed),
@@ -55,3 +83,98 @@ impl core::ops::Sub for &ProcessingCountersSnapshot {
         }
     }
 }
+
+/// Estimate of the time remaining until the node reaches the network tip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncEstimate {
+    /// The node is synced with the network tip
+    Synced,
+    /// No blocks were processed recently, the remaining time cannot be estimated
+    NoProgress,
+    /// Estimated remaining time
+    Remaining(Duration),
+}
+
+impl SyncEstimate {
+    /// Processing rates below this value (in blocks per second) are considered as no progress
+    pub const MIN_RATE: f64 = 0.01;
+
+    /// Estimates the time needed to process the `gap` (in DAA score units, ie. blocks) separating
+    /// the node from the network tip at the current processing `rate` (blocks per second).
+    pub fn new(gap: u64, rate: f64) -> Self {
+        if gap == 0 {
+            Self::Synced
+        } else if !rate.is_finite() || rate < Self::MIN_RATE {
+            Self::NoProgress
+        } else {
+            Self::Remaining(Duration::from_secs_f64(gap as f64 / rate))
+        }
+    }
+
+    /// Returns the remaining time in seconds, zero if synced and `None` if no estimate is available
+    pub fn remaining_secs(&self) -> Option<u64> {
+        match self {
+            Self::Synced => Some(0),
+            Self::NoProgress => None,
+            Self::Remaining(remaining) => Some(remaining.as_secs()),
+        }
+    }
+}
+
+impl Display for SyncEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Synced => write!(f, "synced"),
+            Self::NoProgress => write!(f, "unknown (no progress)"),
+            Self::Remaining(remaining) => {
+                let secs = remaining.as_secs();
+                match (secs / 3600, secs / 60 % 60, secs % 60) {
+                    (0, 0, s) => write!(f, "{s}s"),
+                    (0, m, s) => write!(f, "{m}m {s}s"),
+                    (h, m, _) => write!(f, "{h}h {m}m"),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_estimate() {
+        struct Test {
+            gap: u64,
+            rate: f64,
+            expected: SyncEstimate,
+            display: &'static str,
+        }
+        let remaining = |secs| SyncEstimate::Remaining(Duration::from_secs(secs));
+        let tests = [
+            Test { gap: 0, rate: 100.0, expected: SyncEstimate::Synced, display: "synced" },
+            Test { gap: 0, rate: 0.0, expected: SyncEstimate::Synced, display: "synced" },
+            Test { gap: 1_000, rate: 0.0, expected: SyncEstimate::NoProgress, display: "unknown (no progress)" },
+            Test { gap: 1_000, rate: 0.001, expected: SyncEstimate::NoProgress, display: "unknown (no progress)" },
+            Test { gap: 1_000, rate: f64::NAN, expected: SyncEstimate::NoProgress, display: "unknown (no progress)" },
+            Test { gap: 1_000, rate: 100.0, expected: remaining(10), display: "10s" },
+            Test { gap: 90_000, rate: 1_000.0, expected: remaining(90), display: "1m 30s" },
+            Test { gap: 86_400_000, rate: 2_000.0, expected: remaining(43_200), display: "12h 0m" },
+        ];
+        for test in tests {
+            let estimate = SyncEstimate::new(test.gap, test.rate);
+            assert_eq!(estimate, test.expected, "gap {} rate {}", test.gap, test.rate);
+            assert_eq!(estimate.to_string(), test.display);
+
+            // the published estimate survives the round trip, with a second precision
+            let counters = ProcessingCounters::default();
+            assert_eq!(counters.sync_estimate(), None);
+            counters.set_sync_estimate(estimate);
+            assert_eq!(counters.sync_estimate(), Some(estimate));
+        }
+
+        assert_eq!(SyncEstimate::new(10, 3.0).remaining_secs(), Some(3));
+        assert_eq!(SyncEstimate::Synced.remaining_secs(), Some(0));
+        assert_eq!(SyncEstimate::NoProgress.remaining_secs(), None);
+    }
+}
@@ -23,8 +23,10 @@ use crate::{
 };
 use kaspa_hashes::Hash;
 
+pub use self::chain::{VirtualChainChange, VirtualChainIterator};
 pub use self::stats::{BlockCount, ConsensusStats};
 
+pub mod chain;
 pub mod counters;
 pub mod stats;
 
@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use borsh::{BorshDeserialize, BorshSerialize};
+
 use crate::{
     coinbase::MinerData,
     header::Header,
@@ -32,7 +34,7 @@ impl MutableBlock {
 /// A block structure where the inner header and transactions are wrapped by Arcs for
 /// cheap cloning and for cross-thread safety and immutability. Note: no need to wrap
 /// this struct with an additional Arc.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
 pub struct Block {
     pub header: Arc<Header>,
     pub transactions: Arc<Vec<Transaction>>,
@@ -17,6 +17,9 @@ use {
     params::Params,
 };
 
+/// Default duration of peer bans, in seconds
+pub const DEFAULT_BAN_DURATION_SECS: u64 = 24 * 60 * 60;
+
 /// Various consensus configurations all bundled up under a single struct. Use `Config::new` for directly building from
 /// a `Params` instance. For anything more complex it is recommended to use `ConfigBuilder`. NOTE: this struct can be
 /// implicitly de-refed into `Params`
@@ -68,6 +71,18 @@ pub struct Config {
 
     /// A scale factor to apply to memory allocation bounds
     pub ram_scale: f64,
+
+    /// A scale factor to apply to the P2P message rate limits, `0` disabling them
+    pub p2p_rate_limit_scale: f64,
+
+    /// Ban peers disconnected for exceeding the P2P message rate limits
+    pub p2p_rate_limit_ban: bool,
+
+    /// Duration of peer bans in seconds, applied when a ban does not specify its own duration
+    pub ban_duration: u64,
+
+    /// Minimum P2P protocol version required from peers, the lowest version supported by the node if undefined
+    pub p2p_min_protocol_version: Option<u32>,
 }
 
 impl Config {
@@ -95,6 +110,10 @@ impl Config {
             initial_utxo_set: Default::default(),
             disable_upnp: false,
             ram_scale: 1.0,
+            p2p_rate_limit_scale: 1.0,
+            p2p_rate_limit_ban: false,
+            ban_duration: DEFAULT_BAN_DURATION_SECS,
+            p2p_min_protocol_version: None,
         }
     }
 
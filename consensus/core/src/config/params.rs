@@ -4,7 +4,7 @@ pub use super::{
     genesis::{GenesisBlock, DEVNET_GENESIS, GENESIS, SIMNET_GENESIS, TESTNET11_GENESIS, TESTNET_GENESIS},
 };
 use crate::{
-    constants::STORAGE_MASS_PARAMETER,
+    constants::{MAXIMUM_STANDARD_TRANSACTION_MASS, STORAGE_MASS_PARAMETER},
     network::{NetworkId, NetworkType},
     BlockLevel, KType,
 };
@@ -260,6 +260,32 @@ impl Params {
     pub fn finality_duration(&self) -> u64 {
         self.target_time_per_block * self.finality_depth
     }
+
+    /// Returns the number of DAA score units a coinbase output must wait before it can be spent
+    pub fn coinbase_maturity(&self) -> u64 {
+        self.coinbase_maturity
+    }
+
+    /// Returns the maximum mass of a standard transaction that will be relayed by the network
+    pub fn max_transaction_mass(&self) -> u64 {
+        min(MAXIMUM_STANDARD_TRANSACTION_MASS, self.max_block_mass)
+    }
+
+    /// Returns the maximum mass of a block
+    pub fn max_block_mass(&self) -> u64 {
+        self.max_block_mass
+    }
+
+    /// Returns the maximum number of signature operations of a standard transaction, ie. the number
+    /// of signature operations whose mass alone reaches the maximum transaction mass
+    pub fn max_transaction_sig_ops(&self) -> u64 {
+        self.max_transaction_mass() / self.mass_per_sig_op
+    }
+
+    /// Returns the maximum number of signature operations of all the transactions of a block
+    pub fn max_block_sig_ops(&self) -> u64 {
+        self.max_block_mass / self.mass_per_sig_op
+    }
 }
 
 impl From<NetworkType> for Params {
@@ -586,3 +612,45 @@ pub const DEVNET_PARAMS: Params = Params {
     max_block_level: 250,
     pruning_proof_m: 1000,
 };
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_accessors() {
+        let mainnet = Params::from(NetworkId::new(NetworkType::Mainnet));
+        let testnet10 = Params::from(NetworkId::with_suffix(NetworkType::Testnet, 10));
+        let testnet11 = Params::from(NetworkId::with_suffix(NetworkType::Testnet, 11));
+
+        assert_eq!(mainnet.coinbase_maturity(), LEGACY_COINBASE_MATURITY);
+        assert_eq!(testnet10.coinbase_maturity(), LEGACY_COINBASE_MATURITY);
+        assert_eq!(testnet11.coinbase_maturity(), Testnet11Bps::coinbase_maturity());
+        assert_ne!(mainnet.coinbase_maturity(), testnet11.coinbase_maturity());
+
+        for params in [&mainnet, &testnet10, &testnet11] {
+            assert_eq!(params.max_transaction_mass(), MAXIMUM_STANDARD_TRANSACTION_MASS);
+            assert_eq!(params.max_block_mass(), 500_000);
+            assert!(params.max_transaction_mass() <= params.max_block_mass());
+            assert_eq!(params.max_transaction_sig_ops(), 100);
+            assert_eq!(params.max_block_sig_ops(), 500);
+        }
+    }
+
+    #[test]
+    fn test_params_from_network_id() {
+        let testnet10 = "testnet-10".parse::<NetworkId>().unwrap();
+        assert_eq!(testnet10.to_string(), "testnet-10");
+        let params = Params::from(testnet10);
+        assert_eq!(params.net, testnet10);
+        assert_eq!(params.genesis.hash, TESTNET_GENESIS.hash);
+
+        let testnet11 = "testnet-11".parse::<NetworkId>().unwrap();
+        let params = Params::from(testnet11);
+        assert_eq!(params.net, testnet11);
+        assert_eq!(params.genesis.hash, TESTNET11_GENESIS.hash);
+
+        let mainnet = "mainnet".parse::<NetworkId>().unwrap();
+        assert_eq!(Params::from(mainnet).net, mainnet);
+    }
+}
@@ -34,3 +34,7 @@ pub const SEQUENCE_LOCK_TIME_DISABLED: u64 = 1 << 63;
 /// UNACCEPTED_DAA_SCORE is used to for UtxoEntries that were created by
 /// transactions in the mempool, or otherwise not-yet-accepted transactions.
 pub const UNACCEPTED_DAA_SCORE: u64 = u64::MAX;
+
+/// MAXIMUM_STANDARD_TRANSACTION_MASS is the maximum mass allowed for transactions that
+/// are considered standard and will therefore be relayed and considered for mining.
+pub const MAXIMUM_STANDARD_TRANSACTION_MASS: u64 = 100_000;
@@ -67,6 +67,12 @@ pub enum RuleError {
     #[error("block difficulty of {0} is not the expected value of {1}")]
     UnexpectedDifficulty(u32, u32),
 
+    #[error("block difficulty bits {0:#010x} do not encode a valid positive target")]
+    InvalidTargetBits(u32),
+
+    #[error("block difficulty bits {0:#010x} encode a target above the maximum target")]
+    TargetAboveMaximum(u32),
+
     #[error("block timestamp of {0} is not after expected {1}")]
     TimeTooOld(u64, u64),
 
@@ -103,6 +109,9 @@ pub enum RuleError {
     #[error("block exceeded mass limit of {0}")]
     ExceedsMassLimit(u64),
 
+    #[error("block has {0} sig ops where the max allowed is {1}")]
+    ExceedsSigOpLimit(u64, u64),
+
     #[error("transaction {0} has mass field of {1} but mass should be at least {2}")]
     MassFieldTooLow(TransactionId, u64, u64),
 
@@ -14,6 +14,18 @@ pub enum ConfigError {
     #[error("Configuration: --ram-scale cannot be set above 10.0")]
     RamScaleTooHigh,
 
+    #[error("Configuration: --p2p-rate-limit-scale cannot be negative")]
+    NegativeP2pRateLimitScale,
+
+    #[error("Configuration: --min-protocol-version cannot be set above the node protocol version {0}")]
+    MinProtocolVersionTooHigh(u32),
+
+    #[error("Configuration: --rpccert and --rpckey have to be used together")]
+    MissingRpcCertOrKey,
+
+    #[error("Configuration: --rpcclientca requires --rpccert and --rpckey")]
+    RpcClientCaWithoutCert,
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
@@ -1,5 +1,5 @@
 use crate::constants::MAX_SOMPI;
-use crate::tx::TransactionOutpoint;
+use crate::tx::{ArithmeticOverflow, TransactionOutpoint};
 use kaspa_txscript_errors::TxScriptError;
 use thiserror::Error;
 
@@ -83,6 +83,9 @@ pub enum TxRuleError {
     #[error("input {0} sig op count is {1}, but the calculated value is {2}")]
     WrongSigOpCount(usize, u64, u64),
 
+    #[error("transaction has {0} sig ops where the max allowed is {1}")]
+    TooManySigOps(u64, u64),
+
     #[error("contextual mass (including storage mass) is incomputable")]
     MassIncomputable,
 
@@ -90,4 +93,13 @@ pub enum TxRuleError {
     WrongMass(u64, u64),
 }
 
+impl From<ArithmeticOverflow> for TxRuleError {
+    fn from(err: ArithmeticOverflow) -> Self {
+        match err {
+            ArithmeticOverflow::InputAmounts => TxRuleError::InputAmountOverflow,
+            ArithmeticOverflow::OutputValues => TxRuleError::OutputsValueOverflow,
+        }
+    }
+}
+
 pub type TxResult<T> = std::result::Result<T, TxRuleError>;
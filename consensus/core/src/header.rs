@@ -1,7 +1,14 @@
-use crate::{hashing, BlueWorkType};
+use crate::{
+    blockhash::BlockHashExtensions,
+    config::params::Params,
+    constants::BLOCK_VERSION,
+    errors::block::{BlockProcessResult, RuleError},
+    hashing, BlueWorkType,
+};
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use js_sys::{Array, Object};
 use kaspa_hashes::Hash;
+use kaspa_math::Uint256;
 use kaspa_utils::hex::ToHex;
 use serde::{Deserialize, Serialize};
 use serde_wasm_bindgen::*;
@@ -104,6 +111,145 @@ impl Header {
     }
 }
 
+/// Builder of finalized [`Header`]s. Fields which are not set are zeroed, except for the
+/// version which defaults to [`BLOCK_VERSION`].
+#[derive(Clone, Debug)]
+pub struct HeaderBuilder {
+    header: Header,
+}
+
+impl Default for HeaderBuilder {
+    fn default() -> Self {
+        Self { header: Header::from_precomputed_hash(Default::default(), vec![]) }
+    }
+}
+
+impl HeaderBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_version(mut self, version: u16) -> Self {
+        self.header.version = version;
+        self
+    }
+
+    /// Sets the direct parents, ie. the parents of level 0, dropping the parents of higher levels
+    pub fn with_parents(mut self, parents: Vec<Hash>) -> Self {
+        self.header.parents_by_level = vec![parents];
+        self
+    }
+
+    pub fn with_parents_by_level(mut self, parents_by_level: Vec<Vec<Hash>>) -> Self {
+        self.header.parents_by_level = parents_by_level;
+        self
+    }
+
+    pub fn with_hash_merkle_root(mut self, hash_merkle_root: Hash) -> Self {
+        self.header.hash_merkle_root = hash_merkle_root;
+        self
+    }
+
+    pub fn with_accepted_id_merkle_root(mut self, accepted_id_merkle_root: Hash) -> Self {
+        self.header.accepted_id_merkle_root = accepted_id_merkle_root;
+        self
+    }
+
+    pub fn with_utxo_commitment(mut self, utxo_commitment: Hash) -> Self {
+        self.header.utxo_commitment = utxo_commitment;
+        self
+    }
+
+    /// Sets the timestamp, in milliseconds
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.header.timestamp = timestamp;
+        self
+    }
+
+    pub fn with_bits(mut self, bits: u32) -> Self {
+        self.header.bits = bits;
+        self
+    }
+
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.header.nonce = nonce;
+        self
+    }
+
+    pub fn with_daa_score(mut self, daa_score: u64) -> Self {
+        self.header.daa_score = daa_score;
+        self
+    }
+
+    pub fn with_blue_work(mut self, blue_work: BlueWorkType) -> Self {
+        self.header.blue_work = blue_work;
+        self
+    }
+
+    pub fn with_blue_score(mut self, blue_score: u64) -> Self {
+        self.header.blue_score = blue_score;
+        self
+    }
+
+    pub fn with_pruning_point(mut self, pruning_point: Hash) -> Self {
+        self.header.pruning_point = pruning_point;
+        self
+    }
+
+    /// Builds the header, computing its hash
+    pub fn build(self) -> Header {
+        let mut header = self.header;
+        header.finalize();
+        header
+    }
+}
+
+/// Validates the rules of `header` which do not depend on the DAG, ie. its version, timestamp bounds
+/// relative to the genesis and to `now` (in milliseconds), direct parents and difficulty bits.
+///
+/// The proof of work, as well as all rules involving the parents or the past of the block, are left
+/// to the full block processing.
+pub fn validate_header(header: &Header, params: &Params, now: u64) -> BlockProcessResult<()> {
+    if header.version != BLOCK_VERSION {
+        return Err(RuleError::WrongBlockVersion(header.version));
+    }
+
+    // Timestamp deviation tolerance is in seconds so we multiply by 1000 to get milliseconds
+    let max_block_time = now + params.timestamp_deviation_tolerance(header.daa_score) * 1000;
+    if header.timestamp > max_block_time {
+        return Err(RuleError::TimeTooFarIntoTheFuture(header.timestamp, max_block_time));
+    }
+    if header.timestamp < params.genesis.timestamp {
+        return Err(RuleError::TimeTooOld(header.timestamp, params.genesis.timestamp));
+    }
+
+    if header.direct_parents().is_empty() {
+        return Err(RuleError::NoParents);
+    }
+    if header.direct_parents().len() > params.max_block_parents as usize {
+        return Err(RuleError::TooManyParents(header.direct_parents().len(), params.max_block_parents as usize));
+    }
+    if header.direct_parents().iter().any(|parent| parent.is_origin()) {
+        return Err(RuleError::OriginParent);
+    }
+
+    // The compact encoding is a 23-bit mantissa, a sign bit and an 8-bit base 256 exponent offset by 3
+    let (mantissa, exponent) = (header.bits & 0x007F_FFFF, header.bits >> 24);
+    let target_bits = (u32::BITS - mantissa.leading_zeros()) + 8 * exponent;
+    if header.bits & 0x0080_0000 != 0 || target_bits > Uint256::BITS + 24 {
+        return Err(RuleError::InvalidTargetBits(header.bits));
+    }
+    let target = Uint256::from_compact_target_bits(header.bits);
+    if target.is_zero() {
+        return Err(RuleError::InvalidTargetBits(header.bits));
+    }
+    if target > params.max_difficulty_target {
+        return Err(RuleError::TargetAboveMaximum(header.bits));
+    }
+
+    Ok(())
+}
+
 #[wasm_bindgen]
 impl Header {
     /// Finalizes the header and recomputes (updates) the header hash
@@ -284,6 +430,7 @@ impl TryFrom<JsValue> for Header {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::blockhash::ORIGIN;
     use kaspa_math::Uint192;
     use serde_json::Value;
 
@@ -317,4 +464,36 @@ mod tests {
         let h = serde_json::from_str::<Header>(&json).unwrap();
         assert!(h.blue_score == header.blue_score && h.blue_work == header.blue_work);
     }
+
+    #[test]
+    fn test_validate_header() {
+        let params = &crate::config::params::MAINNET_PARAMS;
+        let now = params.genesis.timestamp + 60_000;
+        let valid = || HeaderBuilder::new().with_parents(vec![1.into()]).with_timestamp(now).with_bits(params.genesis.bits);
+        let validate = |builder: HeaderBuilder| validate_header(&builder.build(), params, now);
+
+        assert!(validate(valid()).is_ok());
+        assert!(validate(valid().with_parents((1..=params.max_block_parents as u64).map(Hash::from_u64_word).collect())).is_ok());
+        assert!(validate(valid().with_bits(0x1d00ffff)).is_ok());
+
+        assert!(matches!(validate(valid().with_version(BLOCK_VERSION + 1)), Err(RuleError::WrongBlockVersion(_))));
+        let max_block_time = now + params.timestamp_deviation_tolerance(0) * 1000;
+        assert!(validate(valid().with_timestamp(max_block_time)).is_ok());
+        assert!(matches!(
+            validate(valid().with_timestamp(max_block_time + 1)),
+            Err(RuleError::TimeTooFarIntoTheFuture(timestamp, max)) if timestamp == max_block_time + 1 && max == max_block_time
+        ));
+        assert!(matches!(validate(valid().with_timestamp(params.genesis.timestamp - 1)), Err(RuleError::TimeTooOld(..))));
+        assert!(matches!(validate(valid().with_parents(vec![])), Err(RuleError::NoParents)));
+        assert!(matches!(validate(valid().with_parents_by_level(vec![])), Err(RuleError::NoParents)));
+        let too_many_parents = (0..=params.max_block_parents as u64).map(Hash::from_u64_word).collect();
+        assert!(matches!(validate(valid().with_parents(too_many_parents)), Err(RuleError::TooManyParents(..))));
+        assert!(matches!(validate(valid().with_parents(vec![1.into(), ORIGIN])), Err(RuleError::OriginParent)));
+
+        // negative, zero and overflowing targets
+        for bits in [0x1d800000, 0x1d80ffff, 0x03000000, 0x0100ffff, 0x227fffff, 0xff000001] {
+            assert!(matches!(validate(valid().with_bits(bits)), Err(RuleError::InvalidTargetBits(b)) if b == bits), "{bits:#x}");
+        }
+        assert!(matches!(validate(valid().with_bits(0x21008000)), Err(RuleError::TargetAboveMaximum(0x21008000))));
+    }
 }
@@ -24,7 +24,9 @@ pub mod merkle;
 pub mod muhash;
 pub mod network;
 pub mod pruning;
+pub mod pruning_snapshot;
 pub mod sign;
+pub mod standard;
 pub mod subnets;
 pub mod trusted;
 pub mod tx;
@@ -1,4 +1,7 @@
-use crate::{hashing, tx::Transaction};
+use crate::{
+    hashing,
+    tx::{Transaction, TransactionId},
+};
 use kaspa_hashes::Hash;
 use kaspa_merkle::calc_merkle_root;
 
@@ -6,18 +9,61 @@ pub fn calc_hash_merkle_root_with_options<'a>(txs: impl ExactSizeIterator<Item =
     calc_merkle_root(txs.map(|tx| hashing::tx::hash(tx, include_mass_field)))
 }
 
+/// Calculates the `hash_merkle_root` of a block from its transactions (coinbase first).
+/// An empty transaction set has the zero hash as root and a single transaction has its own hash as root.
 pub fn calc_hash_merkle_root<'a>(txs: impl ExactSizeIterator<Item = &'a Transaction>) -> Hash {
     calc_merkle_root(txs.map(|tx| hashing::tx::hash(tx, false)))
 }
 
+/// Calculates the `accepted_id_merkle_root` of a block from the ids of the transactions
+/// accepted by its selected parent chain, in acceptance order.
+pub fn calc_accepted_id_merkle_root<'a>(accepted_tx_ids: impl ExactSizeIterator<Item = &'a TransactionId>) -> Hash {
+    calc_merkle_root(accepted_tx_ids.copied())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::merkle::calc_hash_merkle_root;
+    use crate::merkle::{calc_accepted_id_merkle_root, calc_hash_merkle_root};
     use crate::{
+        hashing,
         subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
         tx::{scriptvec, ScriptPublicKey, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput},
     };
-    use kaspa_hashes::Hash;
+    use kaspa_hashes::{Hash, HasherBase, MerkleBranchHash, ZERO_HASH};
+
+    fn branch(left: Hash, right: Hash) -> Hash {
+        let mut hasher = MerkleBranchHash::new();
+        hasher.update(left).update(right);
+        hasher.finalize()
+    }
+
+    fn simple_tx(index: u32) -> Transaction {
+        let outpoint = TransactionOutpoint::new(TransactionId::from_u64_word(index as u64 + 1), index);
+        let input = TransactionInput::new(outpoint, vec![], u64::MAX, 1);
+        let output = TransactionOutput::new(1000 + index as u64, ScriptPublicKey::new(0, scriptvec![0x51]));
+        Transaction::new(0, vec![input], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![])
+    }
+
+    #[test]
+    fn merkle_root_edge_cases_test() {
+        // zero transactions
+        assert_eq!(calc_hash_merkle_root([].iter()), ZERO_HASH);
+        assert_eq!(calc_accepted_id_merkle_root([].iter()), ZERO_HASH);
+
+        // a single transaction is its own root
+        let txs = vec![simple_tx(0)];
+        assert_eq!(calc_hash_merkle_root(txs.iter()), hashing::tx::hash(&txs[0], false));
+        assert_eq!(calc_accepted_id_merkle_root([txs[0].id()].iter()), txs[0].id());
+
+        // several transactions, the missing leaves of the last level are zero hashes
+        let txs = (0..3).map(simple_tx).collect::<Vec<_>>();
+        let hashes = txs.iter().map(|tx| hashing::tx::hash(tx, false)).collect::<Vec<_>>();
+        let ids = txs.iter().map(|tx| tx.id()).collect::<Vec<_>>();
+        assert_eq!(calc_hash_merkle_root(txs[..2].iter()), branch(hashes[0], hashes[1]));
+        assert_eq!(calc_hash_merkle_root(txs.iter()), branch(branch(hashes[0], hashes[1]), branch(hashes[2], ZERO_HASH)));
+        assert_eq!(calc_accepted_id_merkle_root(ids.iter()), branch(branch(ids[0], ids[1]), branch(ids[2], ZERO_HASH)));
+        assert_ne!(calc_hash_merkle_root(txs.iter()), calc_accepted_id_merkle_root(ids.iter()));
+    }
 
     #[test]
     fn merkle_root_test() {
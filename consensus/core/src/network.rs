@@ -439,6 +439,12 @@ mod tests {
         let tests = vec![
             Test { name: "Valid mainnet", expr: "mainnet", expected: Ok(NetworkId::new(NetworkType::Mainnet)) },
             Test { name: "Valid testnet", expr: "testnet-88", expected: Ok(NetworkId::with_suffix(NetworkType::Testnet, 88)) },
+            Test { name: "Valid testnet-10", expr: "testnet-10", expected: Ok(NetworkId::with_suffix(NetworkType::Testnet, 10)) },
+            Test {
+                name: "Missing testnet suffix",
+                expr: "testnet",
+                expected: Err(NetworkIdError::MissingNetworkSuffix("testnet".to_string())),
+            },
             Test { name: "Missing network", expr: "", expected: Err(NetworkTypeError::InvalidNetworkType("".to_string()).into()) },
             Test {
                 name: "Invalid network",
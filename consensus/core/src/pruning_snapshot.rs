@@ -0,0 +1,386 @@
+//!
+//! Pruning point snapshots, packaging the data a fresh node needs in order to bootstrap
+//! from a pruning point instead of syncing from genesis.
+//!
+
+use crate::{
+    block::Block,
+    hashing,
+    header::Header,
+    muhash::MuHashExtensions,
+    pruning::{PruningPointProof, PruningPointsList},
+    trusted::{TrustedGhostdagData, TrustedHeader},
+    tx::{TransactionOutpoint, UtxoEntry},
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use std::io::{Read, Write};
+use thiserror::Error;
+
+/// Magic bytes opening a pruning point snapshot file
+const SNAPSHOT_MAGIC: &[u8; 8] = b"KASPAPPS";
+
+/// Version of the snapshot file format
+const SNAPSHOT_VERSION: u16 = 1;
+
+#[derive(Error, Debug)]
+pub enum PruningSnapshotError {
+    #[error("snapshot i/o error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("the file is not a pruning point snapshot")]
+    InvalidMagic,
+
+    #[error("unsupported pruning point snapshot version {0}")]
+    UnsupportedVersion(u16),
+
+    #[error("the snapshot has no pruning point")]
+    MissingPruningPoint,
+
+    #[error("the snapshot pruning point {0} is not the expected pruning point {1}")]
+    PruningPointMismatch(Hash, Hash),
+
+    #[error("the content of header {0} does not match its hash")]
+    HeaderHashMismatch(Hash),
+
+    #[error("the pruning point proof does not lead to the snapshot pruning point")]
+    ProofPruningPointMismatch,
+
+    #[error("the trusted blocks do not start with the snapshot pruning point")]
+    TrustedBlocksPruningPointMismatch,
+
+    #[error("the UTXO set multiset hash {0} does not match the pruning point UTXO commitment {1}")]
+    UtxoCommitmentMismatch(Hash, Hash),
+}
+
+pub type PruningSnapshotResult<T> = std::result::Result<T, PruningSnapshotError>;
+
+/// A pruning point with everything required to import it in a fresh consensus: the pruning proof,
+/// the past pruning points and the trusted data of its anticone.
+///
+/// In a snapshot file, the UTXO set of the pruning point follows in chunks which are written by
+/// [`PruningSnapshotUtxoWriter`] and read by [`PruningSnapshotUtxoReader`], so that it is never held
+/// in memory as a whole.
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct PruningPointSnapshot {
+    /// Headers of the past pruning points, from genesis up to the snapshot pruning point
+    pub pruning_points: PruningPointsList,
+
+    pub proof: PruningPointProof,
+
+    /// The pruning point and its anticone, starting with the pruning point
+    pub trusted_blocks: Vec<Block>,
+
+    /// Union of DAA window data required to verify blocks in the future of the pruning point
+    pub daa_window: Vec<TrustedHeader>,
+
+    /// Union of GHOSTDAG data required to verify blocks in the future of the pruning point
+    pub ghostdag_window: Vec<TrustedGhostdagData>,
+}
+
+impl PruningPointSnapshot {
+    /// Returns the hash of the snapshot pruning point
+    pub fn pruning_point(&self) -> Option<Hash> {
+        self.pruning_points.last().map(|header| header.hash)
+    }
+
+    /// Validates the consistency of the snapshot with `expected_pruning_point`, a pruning point hash obtained
+    /// from a trusted source. The headers are checked to match their hashes and the proof and the trusted blocks
+    /// to lead to the pruning point. The UTXO set is checked against the pruning point UTXO commitment by
+    /// [`PruningSnapshotUtxoReader`] once fully read.
+    ///
+    /// Note that this does not validate the snapshot against the consensus rules, which is done by
+    /// the consensus when importing it.
+    pub fn validate(&self, expected_pruning_point: Hash) -> PruningSnapshotResult<()> {
+        let pruning_point = self.pruning_points.last().ok_or(PruningSnapshotError::MissingPruningPoint)?;
+        if pruning_point.hash != expected_pruning_point {
+            return Err(PruningSnapshotError::PruningPointMismatch(pruning_point.hash, expected_pruning_point));
+        }
+
+        let headers = self
+            .pruning_points
+            .iter()
+            .chain(self.proof.iter().flatten())
+            .chain(self.trusted_blocks.iter().map(|block| &block.header))
+            .chain(self.daa_window.iter().map(|trusted| &trusted.header));
+        for header in headers {
+            validate_header_hash(header)?;
+        }
+
+        if self.proof.first().and_then(|level| level.last()).map(|header| header.hash) != Some(expected_pruning_point) {
+            return Err(PruningSnapshotError::ProofPruningPointMismatch);
+        }
+        if self.trusted_blocks.first().map(|block| block.hash()) != Some(expected_pruning_point) {
+            return Err(PruningSnapshotError::TrustedBlocksPruningPointMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Writes the snapshot to `writer` and returns a writer for the UTXO set chunks which must follow it
+    pub fn write<W: Write>(&self, mut writer: W) -> PruningSnapshotResult<PruningSnapshotUtxoWriter<W>> {
+        writer.write_all(SNAPSHOT_MAGIC)?;
+        writer.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+        write_section(&mut writer, &self.try_to_vec()?)?;
+        Ok(PruningSnapshotUtxoWriter { writer })
+    }
+
+    /// Reads a snapshot written by [`PruningPointSnapshot::write`] from `reader`, leaving the reader at the
+    /// start of the UTXO set chunks, which are then read through [`PruningPointSnapshot::utxo_reader`].
+    ///
+    /// The snapshot should then be validated with [`PruningPointSnapshot::validate`] before being imported.
+    pub fn read(reader: &mut impl Read) -> PruningSnapshotResult<Self> {
+        let mut magic = [0u8; SNAPSHOT_MAGIC.len()];
+        reader.read_exact(&mut magic).map_err(|_| PruningSnapshotError::InvalidMagic)?;
+        if &magic != SNAPSHOT_MAGIC {
+            return Err(PruningSnapshotError::InvalidMagic);
+        }
+        let mut version = [0u8; 2];
+        reader.read_exact(&mut version)?;
+        let version = u16::from_le_bytes(version);
+        if version != SNAPSHOT_VERSION {
+            return Err(PruningSnapshotError::UnsupportedVersion(version));
+        }
+        Ok(Self::try_from_slice(&read_section(reader)?)?)
+    }
+
+    /// Returns a reader of the UTXO set chunks following the snapshot in `reader`, checking the UTXO set
+    /// against the pruning point UTXO commitment
+    pub fn utxo_reader<R: Read>(&self, reader: R) -> PruningSnapshotResult<PruningSnapshotUtxoReader<R>> {
+        let pruning_point = self.pruning_points.last().ok_or(PruningSnapshotError::MissingPruningPoint)?;
+        Ok(PruningSnapshotUtxoReader { reader, utxo_commitment: pruning_point.utxo_commitment, multiset: MuHash::new(), done: false })
+    }
+}
+
+/// Writes the UTXO set of a snapshot chunk by chunk
+pub struct PruningSnapshotUtxoWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> PruningSnapshotUtxoWriter<W> {
+    /// Appends a chunk of UTXOs to the snapshot, empty chunks being ignored
+    pub fn write_chunk(&mut self, chunk: &[(TransactionOutpoint, UtxoEntry)]) -> PruningSnapshotResult<()> {
+        if !chunk.is_empty() {
+            write_section(&mut self.writer, &chunk.try_to_vec()?)?;
+        }
+        Ok(())
+    }
+
+    /// Ends the UTXO set and returns the underlying writer
+    pub fn finish(mut self) -> PruningSnapshotResult<W> {
+        write_section(&mut self.writer, &[])?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads the UTXO set of a snapshot chunk by chunk, checking it against the pruning point UTXO commitment
+pub struct PruningSnapshotUtxoReader<R: Read> {
+    reader: R,
+    utxo_commitment: Hash,
+    multiset: MuHash,
+    done: bool,
+}
+
+impl<R: Read> PruningSnapshotUtxoReader<R> {
+    /// Returns the next chunk of UTXOs, or `None` once the UTXO set was fully read and found to match
+    /// the pruning point UTXO commitment
+    pub fn next_chunk(&mut self) -> PruningSnapshotResult<Option<Vec<(TransactionOutpoint, UtxoEntry)>>> {
+        if self.done {
+            return Ok(None);
+        }
+        let section = read_section(&mut self.reader)?;
+        if section.is_empty() {
+            self.done = true;
+            let utxo_commitment = self.multiset.finalize();
+            if utxo_commitment != self.utxo_commitment {
+                return Err(PruningSnapshotError::UtxoCommitmentMismatch(utxo_commitment, self.utxo_commitment));
+            }
+            return Ok(None);
+        }
+        let chunk = Vec::<(TransactionOutpoint, UtxoEntry)>::try_from_slice(&section)?;
+        chunk.iter().for_each(|(outpoint, entry)| self.multiset.add_utxo(outpoint, entry));
+        Ok(Some(chunk))
+    }
+}
+
+/// Writes a section of a snapshot file, prefixed by its byte length
+fn write_section(writer: &mut impl Write, section: &[u8]) -> PruningSnapshotResult<()> {
+    writer.write_all(&(section.len() as u64).to_le_bytes())?;
+    writer.write_all(section)?;
+    Ok(())
+}
+
+/// Reads a section written by [`write_section`]
+fn read_section(reader: &mut impl Read) -> PruningSnapshotResult<Vec<u8>> {
+    let mut len = [0u8; 8];
+    reader.read_exact(&mut len)?;
+    let len = u64::from_le_bytes(len);
+    // The section is not preallocated so that a corrupted length does not exhaust the memory
+    let mut section = Vec::new();
+    reader.take(len).read_to_end(&mut section)?;
+    if section.len() as u64 != len {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(section)
+}
+
+fn validate_header_hash(header: &Header) -> PruningSnapshotResult<()> {
+    match hashing::header::hash(header) == header.hash {
+        true => Ok(()),
+        false => Err(PruningSnapshotError::HeaderHashMismatch(header.hash)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::HeaderBuilder,
+        trusted::ExternalGhostdagData,
+        tx::{ScriptPublicKey, TransactionId},
+        BlockHashMap,
+    };
+    use std::sync::Arc;
+
+    fn utxo_set() -> Vec<(TransactionOutpoint, UtxoEntry)> {
+        (0..10u64)
+            .map(|i| {
+                let outpoint = TransactionOutpoint::new(TransactionId::from_u64_word(i + 1), i as u32);
+                let entry = UtxoEntry::new(1000 * (i + 1), ScriptPublicKey::from_vec(0, vec![0x51; i as usize]), 10 + i, i == 0);
+                (outpoint, entry)
+            })
+            .collect()
+    }
+
+    fn ghostdag(selected_parent: Hash) -> ExternalGhostdagData {
+        ExternalGhostdagData {
+            blue_score: 2,
+            blue_work: 2.into(),
+            selected_parent,
+            mergeset_blues: vec![selected_parent],
+            mergeset_reds: vec![],
+            blues_anticone_sizes: BlockHashMap::from_iter([(selected_parent, 0)]),
+        }
+    }
+
+    /// Builds a synthetic snapshot of a pruning point with a single block in its anticone
+    fn snapshot(utxo_set: &[(TransactionOutpoint, UtxoEntry)]) -> PruningPointSnapshot {
+        let mut multiset = MuHash::new();
+        utxo_set.iter().for_each(|(outpoint, entry)| multiset.add_utxo(outpoint, entry));
+
+        let genesis = Arc::new(HeaderBuilder::new().with_timestamp(1).build());
+        let parent = Arc::new(HeaderBuilder::new().with_parents(vec![genesis.hash]).with_timestamp(2).with_daa_score(1).build());
+        let pruning_point = Arc::new(
+            HeaderBuilder::new()
+                .with_parents(vec![parent.hash])
+                .with_utxo_commitment(multiset.finalize())
+                .with_timestamp(3)
+                .with_daa_score(2)
+                .with_pruning_point(genesis.hash)
+                .build(),
+        );
+        let anticone = HeaderBuilder::new().with_parents(vec![parent.hash]).with_timestamp(4).with_daa_score(2).build();
+
+        PruningPointSnapshot {
+            pruning_points: vec![genesis.clone(), pruning_point.clone()],
+            proof: vec![vec![genesis.clone(), parent.clone(), pruning_point.clone()]],
+            trusted_blocks: vec![Block::from_header_arc(pruning_point.clone()), Block::from_header(anticone)],
+            daa_window: vec![TrustedHeader::new(parent.clone(), ghostdag(genesis.hash))],
+            ghostdag_window: vec![TrustedGhostdagData::new(pruning_point.hash, ghostdag(parent.hash))],
+        }
+    }
+
+    /// Writes `snapshot` followed by `utxo_set` in chunks of 3 UTXOs
+    fn write(snapshot: &PruningPointSnapshot, utxo_set: &[(TransactionOutpoint, UtxoEntry)]) -> Vec<u8> {
+        let mut utxo_writer = snapshot.write(Vec::new()).unwrap();
+        utxo_set.chunks(3).for_each(|chunk| utxo_writer.write_chunk(chunk).unwrap());
+        utxo_writer.finish().unwrap()
+    }
+
+    /// Reads a snapshot file, validating it against `pruning_point`, and returns the snapshot and its UTXO set
+    fn read(
+        mut file: &[u8],
+        pruning_point: Hash,
+    ) -> PruningSnapshotResult<(PruningPointSnapshot, Vec<Vec<(TransactionOutpoint, UtxoEntry)>>)> {
+        let snapshot = PruningPointSnapshot::read(&mut file)?;
+        snapshot.validate(pruning_point)?;
+        let mut utxo_reader = snapshot.utxo_reader(file)?;
+        let mut chunks = Vec::new();
+        while let Some(chunk) = utxo_reader.next_chunk()? {
+            chunks.push(chunk);
+        }
+        Ok((snapshot, chunks))
+    }
+
+    #[test]
+    fn test_pruning_snapshot_round_trip() {
+        let utxo_set = utxo_set();
+        let snapshot = snapshot(&utxo_set);
+        let pruning_point = snapshot.pruning_point().unwrap();
+        snapshot.validate(pruning_point).unwrap();
+
+        let (imported, chunks) = read(&write(&snapshot, &utxo_set), pruning_point).unwrap();
+        assert_eq!(imported.pruning_point(), Some(pruning_point));
+        assert_eq!(
+            imported.proof[0].iter().map(|header| header.hash).collect::<Vec<_>>(),
+            vec![snapshot.pruning_points[0].hash, snapshot.daa_window[0].header.hash, pruning_point]
+        );
+        assert_eq!(
+            imported.trusted_blocks.iter().map(|block| block.hash()).collect::<Vec<_>>(),
+            snapshot.trusted_blocks.iter().map(|block| block.hash()).collect::<Vec<_>>()
+        );
+        assert_eq!(imported.ghostdag_window[0].ghostdag.selected_parent, snapshot.daa_window[0].header.hash);
+        assert_eq!(imported.daa_window[0].ghostdag.blues_anticone_sizes, snapshot.daa_window[0].ghostdag.blues_anticone_sizes);
+        assert_eq!(chunks.iter().map(|chunk| chunk.len()).collect::<Vec<_>>(), vec![3, 3, 3, 1]);
+        assert_eq!(chunks.concat(), utxo_set);
+    }
+
+    #[test]
+    fn test_pruning_snapshot_rejects_tampering() {
+        let utxo_set = utxo_set();
+        let snapshot = snapshot(&utxo_set);
+        let pruning_point = snapshot.pruning_point().unwrap();
+
+        // Importing a snapshot of another pruning point
+        assert!(matches!(snapshot.validate(Hash::from_u64_word(7)), Err(PruningSnapshotError::PruningPointMismatch(..))));
+
+        // Tampering with the UTXO set
+        let mut tampered = utxo_set.clone();
+        tampered[3].1.amount += 1;
+        assert!(matches!(read(&write(&snapshot, &tampered), pruning_point), Err(PruningSnapshotError::UtxoCommitmentMismatch(..))));
+        assert!(matches!(
+            read(&write(&snapshot, &utxo_set[1..]), pruning_point),
+            Err(PruningSnapshotError::UtxoCommitmentMismatch(..))
+        ));
+
+        // Tampering with a header without updating its hash
+        let mut tampered = snapshot.clone();
+        let mut header = (*tampered.proof[0][1]).clone();
+        header.timestamp += 1;
+        tampered.proof[0][1] = Arc::new(header);
+        assert!(matches!(tampered.validate(pruning_point), Err(PruningSnapshotError::HeaderHashMismatch(..))));
+
+        // Proof or trusted blocks not leading to the pruning point
+        let mut tampered = snapshot.clone();
+        tampered.proof[0].pop();
+        assert!(matches!(tampered.validate(pruning_point), Err(PruningSnapshotError::ProofPruningPointMismatch)));
+        let mut tampered = snapshot.clone();
+        tampered.trusted_blocks.swap(0, 1);
+        assert!(matches!(tampered.validate(pruning_point), Err(PruningSnapshotError::TrustedBlocksPruningPointMismatch)));
+
+        // Corrupted files
+        let mut file = write(&snapshot, &utxo_set);
+        assert!(matches!(PruningPointSnapshot::read(&mut &file[1..]), Err(PruningSnapshotError::InvalidMagic)));
+        file[SNAPSHOT_MAGIC.len()] = 2;
+        assert!(matches!(PruningPointSnapshot::read(&mut file.as_slice()), Err(PruningSnapshotError::UnsupportedVersion(2))));
+        file[SNAPSHOT_MAGIC.len()] = 1;
+        // A UTXO set missing its end
+        file.truncate(file.len() - 1);
+        assert!(matches!(read(&file, pruning_point), Err(PruningSnapshotError::Io(_))));
+        // A snapshot cut within its first section
+        file.truncate(SNAPSHOT_MAGIC.len() + 2 + 8 + 10);
+        assert!(matches!(PruningPointSnapshot::read(&mut file.as_slice()), Err(PruningSnapshotError::Io(_))));
+    }
+}
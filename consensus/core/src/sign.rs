@@ -1,11 +1,12 @@
 use crate::{
     hashing::{
         sighash::{calc_schnorr_signature_hash, SigHashReusedValues},
-        sighash_type::SIG_HASH_ALL,
+        sighash_type::{SigHashType, SIG_HASH_ALL},
     },
-    tx::SignableTransaction,
+    tx::{MutableTransaction, SignableTransaction, Transaction, VerifiableTransaction},
 };
 use itertools::Itertools;
+use kaspa_hashes::Hash;
 use std::collections::BTreeMap;
 use std::iter::once;
 use thiserror::Error;
@@ -23,6 +24,9 @@ pub enum Error {
 
     #[error("The transaction is fully signed")]
     FullySigned,
+
+    #[error("The UTXO entry of input {0} is not populated")]
+    MissingUtxoEntry(usize),
 }
 
 /// A wrapper enum that represents the transaction signed state. A transaction
@@ -78,6 +82,20 @@ impl Signed {
     }
 }
 
+impl<T: AsRef<Transaction>> MutableTransaction<T> {
+    /// Returns the schnorr signature hash of each input, allowing all inputs to be signed
+    /// externally (e.g. by a hardware wallet or an air-gapped signer). All UTXO entries must
+    /// be populated, otherwise [`Error::MissingUtxoEntry`] is returned.
+    pub fn signing_hashes(&self, hash_type: SigHashType) -> Result<Vec<Hash>, Error> {
+        if let Some(index) = self.entries.iter().position(Option::is_none) {
+            return Err(Error::MissingUtxoEntry(index));
+        }
+        let tx = self.as_verifiable();
+        let mut reused_values = SigHashReusedValues::new();
+        Ok((0..tx.inputs().len()).map(|i| calc_schnorr_signature_hash(&tx, i, hash_type, &mut reused_values)).collect())
+    }
+}
+
 /// Sign a transaction using schnorr
 pub fn sign(mut signable_tx: SignableTransaction, schnorr_key: secp256k1::KeyPair) -> SignableTransaction {
     for i in 0..signable_tx.tx.inputs.len() {
@@ -246,4 +264,37 @@ mod tests {
 
         assert!(verify(&signed_tx.as_verifiable()).is_ok());
     }
+
+    #[test]
+    fn test_signing_hashes() {
+        use crate::hashing::sighash_type::{SIG_HASH_ANY_ONE_CAN_PAY, SIG_HASH_SINGLE};
+
+        let prev_tx_id = TransactionId::from_str("880eb9819a31821d9d2399e2f35e2433b72637e393d71ecc9b8d0250f49153c3").unwrap();
+        let script_public_key = ScriptPublicKey::new(0, ScriptVec::from_slice(&[0x51]));
+        let tx = Transaction::new(
+            0,
+            (0..3).map(|index| TransactionInput::new(TransactionOutpoint::new(prev_tx_id, index), vec![], index as u64, 1)).collect(),
+            vec![TransactionOutput::new(300, script_public_key.clone()), TransactionOutput::new(200, script_public_key.clone())],
+            0,
+            SubnetworkId::from_byte(0),
+            0,
+            vec![],
+        );
+        let entries = (1..=3).map(|i| UtxoEntry::new(i * 100, script_public_key.clone(), 0, false)).collect::<Vec<_>>();
+
+        let mut mutable_tx = SignableTransaction::with_entries(tx.clone(), entries.clone());
+        let populated_tx = PopulatedTransaction::new(&tx, entries);
+        for hash_type in [SIG_HASH_ALL.to_u8(), SIG_HASH_SINGLE.to_u8(), SIG_HASH_ALL.to_u8() | SIG_HASH_ANY_ONE_CAN_PAY.to_u8()] {
+            let hash_type = SigHashType::from_u8(hash_type).unwrap();
+            let hashes = mutable_tx.signing_hashes(hash_type).unwrap();
+            assert_eq!(hashes.len(), 3);
+            for (i, hash) in hashes.into_iter().enumerate() {
+                let mut reused_values = SigHashReusedValues::new();
+                assert_eq!(hash, calc_schnorr_signature_hash(&populated_tx, i, hash_type, &mut reused_values));
+            }
+        }
+
+        mutable_tx.entries[1] = None;
+        assert!(matches!(mutable_tx.signing_hashes(SIG_HASH_ALL), Err(Error::MissingUtxoEntry(1))));
+    }
 }
@@ -0,0 +1,365 @@
+//!
+//! Transaction standardness rules.
+//!
+//! A transaction which is valid by consensus may still be rejected by the mempool if it is not
+//! "standard", ie. if its scripts are of unknown forms, its outputs are dust, its fee is too low
+//! etc. These rules are policy rather than consensus, and are shared by the mempool and by the
+//! wallets which want to pre-validate their transactions before a broadcast.
+//!
+
+use crate::{
+    constants::{MAXIMUM_STANDARD_TRANSACTION_MASS, MAX_SCRIPT_PUBLIC_KEY_VERSION, MAX_SOMPI, TX_VERSION},
+    mass::transaction_output_estimated_serialized_size,
+    tx::{ArithmeticOverflow, ScriptPublicKey, Transaction, TransactionOutput, VerifiableTransaction},
+};
+use kaspa_addresses::{Address, Prefix, Version};
+use thiserror::Error;
+
+/// MAX_STANDARD_P2SH_SIG_OPS is the maximum number of signature operations
+/// that are considered standard in a pay-to-script-hash script.
+pub const MAX_STANDARD_P2SH_SIG_OPS: u8 = 15;
+
+/// MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE is the maximum size allowed for a
+/// transaction input signature script to be considered standard. This
+/// value allows for a 15-of-15 CHECKMULTISIG pay-to-script-hash with
+/// compressed keys.
+///
+/// The form of the overall script is: OP_0 <15 signatures> OP_PUSHDATA2
+/// <2 bytes len> [OP_15 <15 pubkeys> OP_15 OP_CHECKMULTISIG]
+///
+/// For the p2sh script portion, each of the 15 compressed pubkeys are
+/// 33 bytes (plus one for the OP_DATA_33 opcode), and the thus it totals
+/// to (15*34)+3 = 513 bytes. Next, each of the 15 signatures is a max
+/// of 73 bytes (plus one for the OP_DATA_73 opcode). Also, there is one
+/// extra byte for the initial extra OP_0 push and 3 bytes for the
+/// OP_PUSHDATA2 needed to specify the 513 bytes for the script push.
+/// That brings the total to 1+(15*74)+3+513 = 1627. This value also
+/// adds a few extra bytes to provide a little buffer.
+/// (1 + 15*74 + 3) + (15*34 + 3) + 23 = 1650
+pub const MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE: u64 = 1650;
+
+/// DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE is the minimum transaction fee, in sompi/kg,
+/// for a transaction to be accepted to the mempool and relayed.
+pub const DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
+
+// Opcodes of the standard script public key forms
+const OP_DATA_32: u8 = 0x20;
+const OP_DATA_33: u8 = 0x21;
+const OP_EQUAL: u8 = 0x87;
+const OP_BLAKE2B: u8 = 0xaa;
+const OP_CHECK_SIG_ECDSA: u8 = 0xab;
+const OP_CHECK_SIG: u8 = 0xac;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum StandardnessError {
+    #[error("transaction version {0} is not in the valid range of {1}-{2}")]
+    Version(u16, u16, u16),
+
+    #[error("transaction mass of {0} is larger than max allowed size of {1}")]
+    Mass(u64, u64),
+
+    #[error("transaction mass in context (including storage mass) of {0} is larger than max allowed size of {1}")]
+    ContextualMass(u64, u64),
+
+    #[error("transaction input #{0}: signature script size of {1} bytes is larger than the maximum allowed size of {2} bytes")]
+    SignatureScriptSize(usize, u64, u64),
+
+    #[error("transaction output #{0}: the version of the scriptPublicKey is higher than the known version")]
+    ScriptPublicKeyVersion(usize),
+
+    #[error("transaction output #{0}: non-standard script form")]
+    OutputScriptClass(usize),
+
+    #[error("transaction output #{0}: payment of {1} is dust")]
+    Dust(usize, u64),
+
+    #[error("transaction input #{0}: non-standard script form")]
+    InputScriptClass(usize),
+
+    #[error("transaction input #{0} has {1} signature operations which is more than the allowed max amount of {2}")]
+    SignatureCount(usize, u8, u8),
+
+    #[error("transaction has {0} fees which is under the required amount of {1}")]
+    InsufficientFee(u64, u64),
+
+    #[error(transparent)]
+    ArithmeticOverflow(#[from] ArithmeticOverflow),
+}
+
+pub type StandardnessResult<T> = std::result::Result<T, StandardnessError>;
+
+/// Policy parameters of the standardness rules
+#[derive(Debug, Clone)]
+pub struct StandardnessParams {
+    pub minimum_relay_transaction_fee: u64,
+    pub minimum_standard_transaction_version: u16,
+    pub maximum_standard_transaction_version: u16,
+}
+
+impl Default for StandardnessParams {
+    fn default() -> Self {
+        Self {
+            minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
+            minimum_standard_transaction_version: TX_VERSION,
+            maximum_standard_transaction_version: TX_VERSION,
+        }
+    }
+}
+
+impl StandardnessParams {
+    pub fn with_minimum_relay_transaction_fee(self, minimum_relay_transaction_fee: u64) -> Self {
+        Self { minimum_relay_transaction_fee, ..self }
+    }
+}
+
+/// Standard forms of script public keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StandardScript {
+    PubKey,
+    PubKeyECDSA,
+    ScriptHash,
+}
+
+impl StandardScript {
+    fn from_script(script_public_key: &ScriptPublicKey) -> Option<Self> {
+        if script_public_key.version() != MAX_SCRIPT_PUBLIC_KEY_VERSION {
+            return None;
+        }
+        match script_public_key.script() {
+            [OP_DATA_32, .., OP_CHECK_SIG] if script_public_key.script().len() == 34 => Some(Self::PubKey),
+            [OP_DATA_33, .., OP_CHECK_SIG_ECDSA] if script_public_key.script().len() == 35 => Some(Self::PubKeyECDSA),
+            [OP_BLAKE2B, OP_DATA_32, .., OP_EQUAL] if script_public_key.script().len() == 35 => Some(Self::ScriptHash),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the address paid to by a standard `script_public_key`,
+/// or `None` if the script is not of a standard form.
+pub fn standard_script_address(script_public_key: &ScriptPublicKey, prefix: Prefix) -> Option<Address> {
+    let script = script_public_key.script();
+    match StandardScript::from_script(script_public_key)? {
+        StandardScript::PubKey => Some(Address::new(prefix, Version::PubKey, &script[1..33])),
+        StandardScript::PubKeyECDSA => Some(Address::new(prefix, Version::PubKeyECDSA, &script[1..34])),
+        StandardScript::ScriptHash => Some(Address::new(prefix, Version::ScriptHash, &script[2..34])),
+    }
+}
+
+/// Returns whether `output` is dust, ie. whether the cost to the network of spending it is
+/// more than 1/3 of the minimum transaction relay fee (in sompi/kg).
+pub fn is_transaction_output_dust(output: &TransactionOutput, minimum_relay_transaction_fee: u64) -> bool {
+    // The total serialized size consists of the output and the associated
+    // input script to redeem it. Since there is no input script
+    // to redeem it yet, use the minimum size of a typical pay-to-pubkey
+    // input script: 36 prev outpoint, 1 script len, 64 script
+    // [1 OP_DATA_64, 64 sig], 4 sequence, ie. 148 bytes with the output.
+    let total_serialized_size = transaction_output_estimated_serialized_size(output) + 148;
+
+    // The following is equivalent to (value/total_serialized_size) * (1/3) * 1000
+    // without needing to do floating point math.
+    //
+    // Since the multiplication may overflow a u64, 2 separate calculation paths
+    // are considered to avoid overflowing.
+    match output.value.checked_mul(1000) {
+        Some(value_1000) => value_1000 / (3 * total_serialized_size) < minimum_relay_transaction_fee,
+        None => (output.value as u128 * 1000 / (3 * total_serialized_size as u128)) < minimum_relay_transaction_fee as u128,
+    }
+}
+
+/// Returns the minimum fee required for a transaction with the given mass to be accepted
+/// into the mempool and relayed, the minimum relay fee being in sompi/kg.
+pub fn minimum_required_transaction_relay_fee(mass: u64, minimum_relay_transaction_fee: u64) -> u64 {
+    let minimum_fee = (mass * minimum_relay_transaction_fee) / 1000;
+    if minimum_fee == 0 {
+        minimum_relay_transaction_fee.min(MAX_SOMPI)
+    } else {
+        minimum_fee.min(MAX_SOMPI)
+    }
+}
+
+/// Checks the standardness rules which do not depend on the UTXOs spent by the transaction: its version,
+/// compute mass and signature scripts are within the standard limits and its outputs are standard and not dust.
+pub fn check_transaction_standard_in_isolation(
+    tx: &Transaction,
+    compute_mass: u64,
+    params: &StandardnessParams,
+) -> StandardnessResult<()> {
+    // The transaction must be a currently supported version.
+    //
+    // This check is currently mirrored in consensus.
+    // However, in a later version of Kaspa the consensus-valid transaction version range might diverge from the
+    // standard transaction version range, and thus the validation should happen in both levels.
+    if tx.version < params.minimum_standard_transaction_version || tx.version > params.maximum_standard_transaction_version {
+        return Err(StandardnessError::Version(
+            tx.version,
+            params.minimum_standard_transaction_version,
+            params.maximum_standard_transaction_version,
+        ));
+    }
+
+    // Since extremely large transactions with a lot of inputs can cost
+    // almost as much to process as the sender fees, limit the maximum
+    // size of a transaction. This also helps mitigate CPU exhaustion
+    // attacks.
+    if compute_mass > MAXIMUM_STANDARD_TRANSACTION_MASS {
+        return Err(StandardnessError::Mass(compute_mass, MAXIMUM_STANDARD_TRANSACTION_MASS));
+    }
+
+    // Each transaction input signature script must not exceed the maximum size allowed
+    // for a standard transaction. See MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE for details.
+    for (i, input) in tx.inputs.iter().enumerate() {
+        let signature_script_len = input.signature_script.len() as u64;
+        if signature_script_len > MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE {
+            return Err(StandardnessError::SignatureScriptSize(i, signature_script_len, MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE));
+        }
+    }
+
+    // None of the output public key scripts can be a non-standard script or be "dust".
+    for (i, output) in tx.outputs.iter().enumerate() {
+        if output.script_public_key.version() > MAX_SCRIPT_PUBLIC_KEY_VERSION {
+            return Err(StandardnessError::ScriptPublicKeyVersion(i));
+        }
+        if StandardScript::from_script(&output.script_public_key).is_none() {
+            return Err(StandardnessError::OutputScriptClass(i));
+        }
+        if is_transaction_output_dust(output, params.minimum_relay_transaction_fee) {
+            return Err(StandardnessError::Dust(i, output.value));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the standardness rules which depend on the UTXOs spent by the transaction: its mass in context
+/// (including the storage mass, if set) is within the standard limit, the outputs spent by its inputs are
+/// standard, pay-to-script-hash inputs declare no more than [`MAX_STANDARD_P2SH_SIG_OPS`] signature operations
+/// and `fee` covers the minimum relay fee of the compute mass.
+pub fn check_transaction_standard_in_context(
+    tx: &impl VerifiableTransaction,
+    compute_mass: u64,
+    fee: u64,
+    params: &StandardnessParams,
+) -> StandardnessResult<()> {
+    let contextual_mass = tx.tx().mass();
+    if contextual_mass > MAXIMUM_STANDARD_TRANSACTION_MASS {
+        return Err(StandardnessError::ContextualMass(contextual_mass, MAXIMUM_STANDARD_TRANSACTION_MASS));
+    }
+
+    for (i, (input, entry)) in tx.populated_inputs().enumerate() {
+        match StandardScript::from_script(&entry.script_public_key) {
+            None => return Err(StandardnessError::InputScriptClass(i)),
+            Some(StandardScript::ScriptHash) if input.sig_op_count > MAX_STANDARD_P2SH_SIG_OPS => {
+                return Err(StandardnessError::SignatureCount(i, input.sig_op_count, MAX_STANDARD_P2SH_SIG_OPS));
+            }
+            Some(_) => {}
+        }
+    }
+
+    // TODO: For now, until wallets adapt, we don't require fee as function of full contextual_mass (but the fee/mass ratio will affect tx selection to block template)
+    let minimum_fee = minimum_required_transaction_relay_fee(compute_mass, params.minimum_relay_transaction_fee);
+    if fee < minimum_fee {
+        return Err(StandardnessError::InsufficientFee(fee, minimum_fee));
+    }
+
+    Ok(())
+}
+
+/// Checks that a populated transaction with the given compute mass is standard, applying the same rules
+/// as the mempool. The fee is derived from the populated UTXO entries.
+///
+/// The transaction is assumed to be valid by consensus.
+pub fn check_transaction_standard(
+    tx: &impl VerifiableTransaction,
+    compute_mass: u64,
+    params: &StandardnessParams,
+) -> StandardnessResult<()> {
+    check_transaction_standard_in_isolation(tx.tx(), compute_mass, params)?;
+    let fee = tx.total_input_amount()?.saturating_sub(tx.total_output_value()?);
+    check_transaction_standard_in_context(tx, compute_mass, fee, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::SOMPI_PER_KASPA,
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{PopulatedTransaction, Transaction, TransactionInput, TransactionOutpoint, UtxoEntry},
+    };
+
+    fn p2pk_script() -> ScriptPublicKey {
+        let mut script = vec![OP_DATA_32];
+        script.extend([7u8; 32]);
+        script.push(OP_CHECK_SIG);
+        ScriptPublicKey::from_vec(0, script)
+    }
+
+    fn p2sh_script() -> ScriptPublicKey {
+        let mut script = vec![OP_BLAKE2B, OP_DATA_32];
+        script.extend([9u8; 32]);
+        script.push(OP_EQUAL);
+        ScriptPublicKey::from_vec(0, script)
+    }
+
+    fn transaction(sig_op_count: u8, outputs: Vec<TransactionOutput>) -> Transaction {
+        let input = TransactionInput::new(TransactionOutpoint::new(1.into(), 0), vec![0; 66], 0, sig_op_count);
+        Transaction::new(TX_VERSION, vec![input], outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![])
+    }
+
+    fn check(tx: &Transaction, spent: ScriptPublicKey) -> StandardnessResult<()> {
+        let entries = vec![UtxoEntry::new(10 * SOMPI_PER_KASPA, spent, 0, false)];
+        check_transaction_standard(&PopulatedTransaction::new(tx, entries), 2000, &StandardnessParams::default())
+    }
+
+    #[test]
+    fn test_check_transaction_standard() {
+        let pay = TransactionOutput::new(9 * SOMPI_PER_KASPA, p2pk_script());
+        assert_eq!(check(&transaction(1, vec![pay.clone()]), p2pk_script()), Ok(()));
+
+        // non-standard output script
+        let op_return = TransactionOutput::new(SOMPI_PER_KASPA, ScriptPublicKey::from_vec(0, vec![0x6a, 0x01, 0x02]));
+        assert_eq!(check(&transaction(1, vec![pay.clone(), op_return]), p2pk_script()), Err(StandardnessError::OutputScriptClass(1)));
+        let unknown_version = TransactionOutput::new(SOMPI_PER_KASPA, ScriptPublicKey::from_vec(1, p2pk_script().script().to_vec()));
+        assert_eq!(check(&transaction(1, vec![unknown_version]), p2pk_script()), Err(StandardnessError::ScriptPublicKeyVersion(0)));
+
+        // non-standard spent script
+        let spent = ScriptPublicKey::from_vec(0, vec![0x51]);
+        assert_eq!(check(&transaction(1, vec![pay.clone()]), spent), Err(StandardnessError::InputScriptClass(0)));
+
+        // dust output
+        let dust = TransactionOutput::new(599, p2pk_script());
+        assert!(is_transaction_output_dust(&dust, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE));
+        assert!(!is_transaction_output_dust(&TransactionOutput::new(600, p2pk_script()), DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE));
+        assert_eq!(check(&transaction(1, vec![pay.clone(), dust]), p2pk_script()), Err(StandardnessError::Dust(1, 599)));
+
+        // excessive signature operations spending a P2SH output
+        let tx = transaction(MAX_STANDARD_P2SH_SIG_OPS, vec![pay.clone()]);
+        assert_eq!(check(&tx, p2sh_script()), Ok(()));
+        let tx = transaction(MAX_STANDARD_P2SH_SIG_OPS + 1, vec![pay.clone()]);
+        assert_eq!(check(&tx, p2sh_script()), Err(StandardnessError::SignatureCount(0, 16, MAX_STANDARD_P2SH_SIG_OPS)));
+
+        // insufficient fee
+        let tx = transaction(1, vec![TransactionOutput::new(10 * SOMPI_PER_KASPA - 100, p2pk_script())]);
+        assert_eq!(check(&tx, p2pk_script()), Err(StandardnessError::InsufficientFee(100, 2000)));
+
+        // mass limits
+        let tx = transaction(1, vec![pay.clone()]);
+        let entries = vec![UtxoEntry::new(10 * SOMPI_PER_KASPA, p2pk_script(), 0, false)];
+        let params = StandardnessParams::default();
+        let mass = MAXIMUM_STANDARD_TRANSACTION_MASS + 1;
+        assert_eq!(
+            check_transaction_standard(&PopulatedTransaction::new(&tx, entries.clone()), mass, &params),
+            Err(StandardnessError::Mass(mass, MAXIMUM_STANDARD_TRANSACTION_MASS))
+        );
+        tx.set_mass(mass);
+        assert_eq!(
+            check_transaction_standard(&PopulatedTransaction::new(&tx, entries), 2000, &params),
+            Err(StandardnessError::ContextualMass(mass, MAXIMUM_STANDARD_TRANSACTION_MASS))
+        );
+
+        // crafted output values overflowing u64
+        let huge = TransactionOutput::new(u64::MAX / 2 + 1, p2pk_script());
+        let tx = transaction(1, vec![huge.clone(), huge]);
+        assert_eq!(check(&tx, p2pk_script()), Err(StandardnessError::ArithmeticOverflow(ArithmeticOverflow::OutputValues)));
+    }
+}
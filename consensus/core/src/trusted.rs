@@ -1,10 +1,11 @@
 use crate::{block::Block, header::Header, BlockHashMap, BlueWorkType, KType};
+use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_hashes::Hash;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 /// Represents semi-trusted externally provided Ghostdag data (by a network peer)
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct ExternalGhostdagData {
     pub blue_score: u64,
     pub blue_work: BlueWorkType,
@@ -31,6 +32,7 @@ impl TrustedBlock {
 /// Represents an externally provided header with associated Ghostdag data which
 /// is only partially validated by the consensus layer. Note there is no actual trust
 /// but rather these headers are indirectly validated through the PoW mined over them
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
 pub struct TrustedHeader {
     pub header: Arc<Header>,
     pub ghostdag: ExternalGhostdagData,
@@ -43,6 +45,7 @@ impl TrustedHeader {
 }
 
 /// Represents externally provided Ghostdag data associated with a block Hash
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
 pub struct TrustedGhostdagData {
     pub hash: Hash,
     pub ghostdag: ExternalGhostdagData,
@@ -1,22 +1,30 @@
 mod script_public_key;
+mod sig_ops;
+mod wire;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use kaspa_addresses::Prefix;
 use kaspa_utils::hex::ToHex;
 use kaspa_utils::mem_size::MemSizeEstimator;
 use kaspa_utils::{serde_bytes, serde_bytes_fixed_ref};
-pub use script_public_key::{scriptvec, ScriptPublicKey, ScriptPublicKeyVersion, ScriptPublicKeys, ScriptVec, SCRIPT_VECTOR_SIZE};
-use serde::{Deserialize, Serialize};
+pub use script_public_key::{
+    scriptvec, sorted_script_public_keys, ScriptPublicKey, ScriptPublicKeyVersion, ScriptPublicKeys, ScriptVec, SCRIPT_VECTOR_SIZE,
+};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+pub use sig_ops::{check_block_sig_op_count, check_transaction_sig_op_count, inputs_sig_op_count, transaction_sig_op_count};
+use std::cell::Cell;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering::SeqCst;
 use std::{
     fmt::Display,
     ops::Range,
-    str::{self},
+    str::{self, FromStr},
 };
 use wasm_bindgen::prelude::*;
+pub use wire::{WireError, WireResult};
 
 use crate::{
-    hashing,
+    hashing, standard,
     subnets::{self, SubnetworkId},
 };
 
@@ -28,7 +36,12 @@ pub type TransactionId = kaspa_hashes::Hash;
 /// set such as whether or not it was contained in a coinbase tx, the daa
 /// score of the block that accepts the tx, its public key script, and how
 /// much it pays.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+///
+/// Human readable serializations (such as JSON) additionally emit the `address` paid to
+/// by a standard script public key when the network prefix is provided by
+/// [`UtxoEntry::with_address_prefix`]. The field is never part of binary serializations
+/// and is ignored when deserializing.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
 #[serde(rename_all = "camelCase")]
 #[wasm_bindgen(inspectable, js_name = TxUtxoEntry)]
 pub struct UtxoEntry {
@@ -45,6 +58,45 @@ impl UtxoEntry {
     pub fn new(amount: u64, script_public_key: ScriptPublicKey, block_daa_score: u64, is_coinbase: bool) -> Self {
         Self { amount, script_public_key, block_daa_score, is_coinbase }
     }
+
+    /// Runs `f` with `prefix` as the network prefix of the `address` field
+    /// emitted by the human readable serializations of UTXO entries on this thread.
+    pub fn with_address_prefix<R>(prefix: Prefix, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<Prefix>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                UTXO_ENTRY_ADDRESS_PREFIX.with(|prefix| prefix.set(self.0));
+            }
+        }
+        let _restore = Restore(UTXO_ENTRY_ADDRESS_PREFIX.with(|current| current.replace(Some(prefix))));
+        f()
+    }
+}
+
+thread_local! {
+    static UTXO_ENTRY_ADDRESS_PREFIX: Cell<Option<Prefix>> = const { Cell::new(None) };
+}
+
+impl Serialize for UtxoEntry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let address = if serializer.is_human_readable() {
+            UTXO_ENTRY_ADDRESS_PREFIX
+                .with(|prefix| prefix.get())
+                .and_then(|prefix| standard::standard_script_address(&self.script_public_key, prefix))
+        } else {
+            None
+        };
+
+        let mut state = serializer.serialize_struct("UtxoEntry", 4 + address.is_some() as usize)?;
+        state.serialize_field("amount", &self.amount)?;
+        state.serialize_field("scriptPublicKey", &self.script_public_key)?;
+        state.serialize_field("blockDaaScore", &self.block_daa_score)?;
+        state.serialize_field("isCoinbase", &self.is_coinbase)?;
+        if let Some(address) = address {
+            state.serialize_field("address", &address)?;
+        }
+        state.end()
+    }
 }
 
 impl MemSizeEstimator for UtxoEntry {}
@@ -64,6 +116,11 @@ impl TransactionOutpoint {
     pub fn new(transaction_id: TransactionId, index: u32) -> Self {
         Self { transaction_id, index }
     }
+
+    /// Returns the outpoint in the compact `txid:index` form accepted by [`FromStr`].
+    pub fn to_compact_string(&self) -> String {
+        format!("{}:{}", self.transaction_id, self.index)
+    }
 }
 
 impl Display for TransactionOutpoint {
@@ -72,6 +129,45 @@ impl Display for TransactionOutpoint {
     }
 }
 
+#[derive(thiserror::Error, PartialEq, Eq, Debug, Clone)]
+pub enum TransactionOutpointParseError {
+    #[error("Invalid outpoint '{0}', expected '<transaction id>:<index>'")]
+    InvalidFormat(String),
+    #[error("Invalid outpoint transaction id length {0}, expected 64 hex characters")]
+    InvalidTransactionIdLength(usize),
+    #[error("Invalid outpoint transaction id: {0}")]
+    InvalidTransactionId(String),
+    #[error("Invalid outpoint index '{0}'")]
+    InvalidIndex(String),
+}
+
+/// Error returned when an aggregate value of a transaction does not fit into `u64`
+#[derive(thiserror::Error, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ArithmeticOverflow {
+    #[error("transaction total inputs amount overflowed u64")]
+    InputAmounts,
+    #[error("transaction total outputs value overflowed u64")]
+    OutputValues,
+}
+
+impl FromStr for TransactionOutpoint {
+    type Err = TransactionOutpointParseError;
+
+    /// Parses an outpoint in the compact `txid:index` form.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (transaction_id, index) =
+            s.trim().split_once(':').ok_or_else(|| TransactionOutpointParseError::InvalidFormat(s.to_string()))?;
+        if transaction_id.len() != kaspa_hashes::HASH_SIZE * 2 {
+            return Err(TransactionOutpointParseError::InvalidTransactionIdLength(transaction_id.len()));
+        }
+        let transaction_id = TransactionId::from_str(transaction_id)
+            .map_err(|err| TransactionOutpointParseError::InvalidTransactionId(err.to_string()))?;
+        let index =
+            index.parse::<TransactionIndexType>().map_err(|_| TransactionOutpointParseError::InvalidIndex(index.to_string()))?;
+        Ok(Self { transaction_id, index })
+    }
+}
+
 /// Represents a Kaspa transaction input
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSchema)]
 #[serde(rename_all = "camelCase")]
@@ -161,8 +257,9 @@ pub struct Transaction {
     #[serde(with = "serde_bytes")]
     pub payload: Vec<u8>,
 
+    // Committed to by the transaction hash (but not by the id) once activated. Encodings predating
+    // the field decode it as zero, see `deserialize_borsh_without_mass` for the borsh encoding.
     #[serde(default)]
-    #[borsh_skip] // TODO: skipped for now as it is only required for consensus storage and miner grpc
     mass: TransactionMass,
 
     // A field that is used to cache the transaction ID.
@@ -208,7 +305,8 @@ impl Transaction {
         self.subnetwork_id == subnets::SUBNETWORK_ID_COINBASE
     }
 
-    /// Recompute and finalize the tx id based on updated tx fields
+    /// Recompute and finalize the tx id based on updated tx fields. The mass field is not
+    /// part of the id, so changing the mass never requires finalizing the transaction again.
     pub fn finalize(&mut self) {
         self.id = hashing::tx::id(self);
     }
@@ -227,6 +325,45 @@ impl Transaction {
     pub fn mass(&self) -> u64 {
         self.mass.0.load(SeqCst)
     }
+
+    /// Deserializes a transaction from its borsh encoding predating the mass field,
+    /// in which the id directly follows the payload. The mass of the returned transaction is zero.
+    pub fn deserialize_borsh_without_mass(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let version = BorshDeserialize::deserialize(buf)?;
+        let inputs = BorshDeserialize::deserialize(buf)?;
+        let outputs = BorshDeserialize::deserialize(buf)?;
+        let lock_time = BorshDeserialize::deserialize(buf)?;
+        let subnetwork_id = BorshDeserialize::deserialize(buf)?;
+        let gas = BorshDeserialize::deserialize(buf)?;
+        let payload = BorshDeserialize::deserialize(buf)?;
+        let id = BorshDeserialize::deserialize(buf)?;
+        Ok(Self { version, inputs, outputs, lock_time, subnetwork_id, gas, payload, mass: Default::default(), id })
+    }
+
+    /// Returns the total value of the transaction outputs or [`ArithmeticOverflow`]
+    /// if the sum does not fit into `u64` (the values of a crafted transaction are not bounded)
+    pub fn total_output_value(&self) -> Result<u64, ArithmeticOverflow> {
+        self.outputs.iter().try_fold(0u64, |total, output| total.checked_add(output.value)).ok_or(ArithmeticOverflow::OutputValues)
+    }
+
+    /// Returns a finalized copy of this transaction with the signature scripts of all inputs cleared.
+    /// All other fields (including `sig_op_count`) are preserved and the mass field is reset.
+    ///
+    /// Note that the transaction hash changes as a result. The id of a non-coinbase transaction
+    /// does not change since it never commits to signature scripts, the id of a coinbase
+    /// transaction with inputs does.
+    pub fn without_signatures(&self) -> Transaction {
+        let inputs = self.inputs.iter().map(|input| TransactionInput { signature_script: vec![], ..input.clone() }).collect();
+        Transaction::new(
+            self.version,
+            inputs,
+            self.outputs.clone(),
+            self.lock_time,
+            self.subnetwork_id.clone(),
+            self.gas,
+            self.payload.clone(),
+        )
+    }
 }
 
 /// Represents any kind of transaction which has populated UTXO entry data and can be verified/signed etc
@@ -252,6 +389,21 @@ pub trait VerifiableTransaction {
         &self.tx().outputs
     }
 
+    /// Returns the total amount of the populated inputs or [`ArithmeticOverflow`] if the sum does not fit into `u64`
+    fn total_input_amount(&self) -> Result<u64, ArithmeticOverflow>
+    where
+        Self: Sized,
+    {
+        self.populated_inputs()
+            .try_fold(0u64, |total, (_, entry)| total.checked_add(entry.amount))
+            .ok_or(ArithmeticOverflow::InputAmounts)
+    }
+
+    /// Returns the total value of the outputs or [`ArithmeticOverflow`] if the sum does not fit into `u64`
+    fn total_output_value(&self) -> Result<u64, ArithmeticOverflow> {
+        self.tx().total_output_value()
+    }
+
     fn is_coinbase(&self) -> bool {
         self.tx().is_coinbase()
     }
@@ -400,6 +552,12 @@ impl<T: AsRef<Transaction>> MutableTransaction<T> {
         })
     }
 
+    /// Returns the total amount of the populated UTXO entries, `None` if some entries are missing
+    /// or [`ArithmeticOverflow`] if the sum does not fit into `u64`
+    pub fn total_input_amount(&self) -> Option<Result<u64, ArithmeticOverflow>> {
+        self.is_verifiable().then(|| self.as_verifiable().total_input_amount())
+    }
+
     pub fn clear_entries(&mut self) {
         for entry in self.entries.iter_mut() {
             *entry = None;
@@ -445,7 +603,7 @@ pub type SignableTransaction = MutableTransaction<Transaction>;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use consensus_core::subnets::SUBNETWORK_ID_COINBASE;
+    use consensus_core::subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE};
     use smallvec::smallvec;
 
     fn test_transaction() -> Transaction {
@@ -508,6 +666,30 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_transaction_value_overflow() {
+        let mut tx = test_transaction();
+        assert_eq!(tx.total_output_value(), Ok(13));
+
+        let entries = vec![UtxoEntry::new(u64::MAX - 1, ScriptPublicKey::default(), 0, false); 2];
+        let populated = PopulatedTransaction::new(&tx, entries.clone());
+        assert_eq!(populated.total_input_amount(), Err(ArithmeticOverflow::InputAmounts));
+
+        let mut mutable = MutableTransaction::new(tx.clone());
+        assert_eq!(mutable.total_input_amount(), None);
+        mutable.entries[0] = Some(UtxoEntry::new(u64::MAX, ScriptPublicKey::default(), 0, false));
+        mutable.entries[1] = Some(UtxoEntry::new(0, ScriptPublicKey::default(), 0, false));
+        assert_eq!(mutable.total_input_amount(), Some(Ok(u64::MAX)));
+        mutable.entries[1] = Some(UtxoEntry::new(1, ScriptPublicKey::default(), 0, false));
+        assert_eq!(mutable.total_input_amount(), Some(Err(ArithmeticOverflow::InputAmounts)));
+
+        tx.outputs[0].value = u64::MAX - 6;
+        assert_eq!(tx.total_output_value(), Err(ArithmeticOverflow::OutputValues));
+        tx.outputs[0].value = u64::MAX - 7;
+        assert_eq!(tx.total_output_value(), Ok(u64::MAX));
+        assert_eq!(PopulatedTransaction::new(&tx, entries).total_output_value(), Ok(u64::MAX));
+    }
+
     #[test]
     fn test_transaction_bincode() {
         let tx = test_transaction();
@@ -583,6 +765,118 @@ mod tests {
         assert_eq!(tx, serde_json::from_str(&str).unwrap());
     }
 
+    #[test]
+    fn test_transaction_borsh() {
+        let tx = test_transaction();
+        tx.set_mass(0x1234);
+        let bts = tx.try_to_vec().unwrap();
+
+        // the mass (0x1234) is encoded between the payload and the id
+        let expected_bts = vec![
+            1, 0, 2, 0, 0, 0, 22, 94, 56, 232, 179, 145, 69, 149, 217, 198, 65, 243, 184, 238, 194, 243, 70, 17, 137, 107, 130, 26,
+            104, 59, 122, 78, 222, 254, 44, 0, 0, 0, 250, 255, 255, 255, 32, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13,
+            14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 2, 0, 0, 0, 0, 0, 0, 0, 3, 75, 176, 117, 53, 223,
+            213, 142, 11, 60, 214, 79, 215, 21, 82, 128, 135, 42, 4, 113, 188, 248, 48, 149, 82, 106, 206, 14, 56, 198, 0, 0, 0, 251,
+            255, 255, 255, 32, 0, 0, 0, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54,
+            55, 56, 57, 58, 59, 60, 61, 62, 63, 4, 0, 0, 0, 0, 0, 0, 0, 5, 2, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 36, 0, 0, 0, 118,
+            169, 33, 3, 47, 126, 67, 10, 164, 201, 209, 89, 67, 126, 132, 185, 117, 220, 118, 217, 0, 59, 240, 146, 44, 243, 170, 69,
+            40, 70, 75, 171, 120, 13, 186, 94, 7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 36, 0, 0, 0, 118, 169, 33, 3, 47, 126, 67, 10, 164, 201,
+            209, 89, 67, 126, 132, 185, 117, 220, 118, 217, 0, 59, 240, 146, 44, 243, 170, 69, 40, 70, 75, 171, 120, 13, 186, 94, 8,
+            0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 9, 0, 0, 0, 0, 0, 0, 0, 100, 0, 0, 0, 0,
+            1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33,
+            34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63,
+            64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93,
+            94, 95, 96, 97, 98, 99, 52, 18, 0, 0, 0, 0, 0, 0, 69, 146, 193, 64, 98, 49, 45, 0, 77, 32, 25, 122, 77, 15, 211, 252, 61,
+            210, 82, 177, 39, 153, 127, 33, 188, 172, 138, 38, 67, 75, 241, 176,
+        ];
+        assert_eq!(expected_bts, bts);
+        let decoded = Transaction::try_from_slice(&bts).unwrap();
+        assert_eq!(tx, decoded);
+        assert_eq!(decoded.mass(), 0x1234);
+    }
+
+    #[test]
+    fn test_transaction_borsh_without_mass() {
+        let tx = test_transaction();
+        let bts = tx.try_to_vec().unwrap();
+
+        // the encoding predating the mass field lacks the 8 mass bytes preceding the id
+        let legacy_bts = [&bts[..bts.len() - 40], &bts[bts.len() - 32..]].concat();
+        assert!(Transaction::try_from_slice(&legacy_bts).is_err());
+        let decoded = Transaction::deserialize_borsh_without_mass(&mut legacy_bts.as_slice()).unwrap();
+        assert_eq!(tx, decoded);
+        assert_eq!(decoded.mass(), 0);
+        assert_eq!(decoded.id(), hashing::tx::id(&decoded));
+    }
+
+    #[test]
+    fn test_transaction_json_without_mass() {
+        let tx = test_transaction();
+        tx.set_mass(100);
+        let mut value = serde_json::to_value(&tx).unwrap();
+        assert_eq!(value["mass"], 100);
+        assert_eq!(tx, serde_json::from_value(value.clone()).unwrap());
+
+        value.as_object_mut().unwrap().remove("mass");
+        let decoded: Transaction = serde_json::from_value(value).unwrap();
+        assert_eq!(decoded.mass(), 0);
+        assert_eq!(decoded.id(), tx.id());
+    }
+
+    #[test]
+    fn test_transaction_mass_commitment() {
+        let mut tx = test_transaction();
+        let id = tx.id();
+        let hash = hashing::tx::hash(&tx, true);
+        assert_eq!(hash, hashing::tx::hash(&tx, false));
+
+        // the id does not commit to the mass while the hash does once the mass field is included
+        tx.set_mass(1000);
+        tx.finalize();
+        assert_eq!(tx.id(), id);
+        assert_ne!(hashing::tx::hash(&tx, true), hash);
+        assert_eq!(hashing::tx::hash(&tx, false), hash);
+    }
+
+    #[test]
+    fn test_utxo_entry_address_serde() {
+        let payload = [7u8; 32];
+        let script_public_key = ScriptPublicKey::new(0, [&[0x20][..], &payload, &[0xac]].concat().into());
+        let entry = UtxoEntry::new(1000, script_public_key, 5, false);
+        let address = kaspa_addresses::Address::new(Prefix::Testnet, kaspa_addresses::Version::PubKey, &payload).to_string();
+
+        // the address is emitted by JSON only, and only with a network prefix in scope
+        let plain = serde_json::to_value(&entry).unwrap();
+        assert!(plain.get("address").is_none());
+        let json = UtxoEntry::with_address_prefix(Prefix::Testnet, || serde_json::to_value(&entry).unwrap());
+        assert_eq!(json["address"], address.as_str());
+        assert_eq!(json.as_object().unwrap().len(), plain.as_object().unwrap().len() + 1);
+        assert!(serde_json::to_value(&entry).unwrap().get("address").is_none());
+        assert_eq!(entry, serde_json::from_value(json).unwrap());
+
+        // binary encodings are unchanged
+        let bts = bincode::serialize(&entry).unwrap();
+        assert_eq!(bts, UtxoEntry::with_address_prefix(Prefix::Testnet, || bincode::serialize(&entry).unwrap()));
+        let expected_bts = [
+            &1000u64.to_le_bytes()[..],
+            &0u16.to_le_bytes(),
+            &34u64.to_le_bytes(),
+            &[0x20],
+            &payload,
+            &[0xac],
+            &5u64.to_le_bytes(),
+            &[0],
+        ]
+        .concat();
+        assert_eq!(bts, expected_bts);
+        assert_eq!(entry, bincode::deserialize(&bts).unwrap());
+
+        // non-standard scripts have no address
+        let entry = UtxoEntry::new(1000, ScriptPublicKey::new(0, scriptvec![0x51]), 5, false);
+        let json = UtxoEntry::with_address_prefix(Prefix::Testnet, || serde_json::to_value(&entry).unwrap());
+        assert!(json.get("address").is_none());
+    }
+
     #[test]
     fn test_spk_serde_json() {
         let vec = (0..SCRIPT_VECTOR_SIZE as u8).collect::<Vec<_>>();
@@ -599,6 +893,61 @@ mod tests {
         assert!(matches!(result, Ok(_empty)));
     }
 
+    #[test]
+    fn test_transaction_without_signatures() {
+        let tx = test_transaction();
+        let stripped = tx.without_signatures();
+
+        assert!(stripped.inputs.iter().all(|input| input.signature_script.is_empty()));
+        assert!(tx.inputs.iter().all(|input| !input.signature_script.is_empty()));
+        for (input, stripped_input) in tx.inputs.iter().zip(stripped.inputs.iter()) {
+            assert_eq!(input.previous_outpoint, stripped_input.previous_outpoint);
+            assert_eq!(input.sequence, stripped_input.sequence);
+            assert_eq!(input.sig_op_count, stripped_input.sig_op_count);
+        }
+        assert_eq!(tx.outputs, stripped.outputs);
+        assert_eq!(tx.payload, stripped.payload);
+
+        // the test transaction is a coinbase transaction, its id commits to signature scripts
+        assert_ne!(tx.id(), stripped.id());
+        assert_eq!(stripped.id(), hashing::tx::id(&stripped));
+        assert_ne!(hashing::tx::hash(&tx, false), hashing::tx::hash(&stripped, false));
+        assert_eq!(stripped.without_signatures(), stripped);
+
+        // the id of a non-coinbase transaction does not commit to signature scripts
+        let tx = Transaction::new(tx.version, tx.inputs, tx.outputs, tx.lock_time, SUBNETWORK_ID_NATIVE, tx.gas, vec![]);
+        let stripped = tx.without_signatures();
+        assert_eq!(tx.id(), stripped.id());
+        assert_ne!(hashing::tx::hash(&tx, false), hashing::tx::hash(&stripped, false));
+    }
+
+    #[test]
+    fn test_outpoint_from_str() {
+        let id = "880eb9819a31821d9d2399e2f35e2433b72637e393d71ecc9b8d0250f49153c3";
+        let outpoint = TransactionOutpoint::from_str(&format!("{id}:7")).unwrap();
+        assert_eq!(outpoint, TransactionOutpoint::new(TransactionId::from_str(id).unwrap(), 7));
+        assert_eq!(outpoint.to_compact_string(), format!("{id}:7"));
+        assert_eq!(outpoint.to_compact_string().parse::<TransactionOutpoint>(), Ok(outpoint));
+
+        assert_eq!(
+            format!("{}:0", &id[2..]).parse::<TransactionOutpoint>(),
+            Err(TransactionOutpointParseError::InvalidTransactionIdLength(62))
+        );
+        assert!(matches!(
+            format!("{}zz:0", &id[2..]).parse::<TransactionOutpoint>(),
+            Err(TransactionOutpointParseError::InvalidTransactionId(_))
+        ));
+        assert_eq!(
+            format!("{id}:one").parse::<TransactionOutpoint>(),
+            Err(TransactionOutpointParseError::InvalidIndex("one".to_string()))
+        );
+        assert_eq!(
+            format!("{id}:-1").parse::<TransactionOutpoint>(),
+            Err(TransactionOutpointParseError::InvalidIndex("-1".to_string()))
+        );
+        assert_eq!(id.parse::<TransactionOutpoint>(), Err(TransactionOutpointParseError::InvalidFormat(id.to_string())));
+    }
+
     #[test]
     fn test_spk_borsh() {
         // Tests for ScriptPublicKey Borsh ser/deser since we manually implemented them
@@ -33,8 +33,21 @@ use wasm_bindgen::prelude::wasm_bindgen;
 //Represents a Set of [`ScriptPublicKey`]s
 pub type ScriptPublicKeys = HashSet<ScriptPublicKey>;
 
+/// Returns the [`ScriptPublicKey`]s of the set ordered by version and then by
+/// script bytes. Iteration order of a [`ScriptPublicKeys`] set is not
+/// deterministic, use this function when the set needs to be serialized
+/// or compared.
+pub fn sorted_script_public_keys(script_public_keys: &ScriptPublicKeys) -> Vec<ScriptPublicKey> {
+    let mut script_public_keys = script_public_keys.iter().cloned().collect::<Vec<_>>();
+    script_public_keys.sort();
+    script_public_keys
+}
+
 /// Represents a Kaspad ScriptPublicKey
-#[derive(Default, PartialEq, Eq, Clone, Hash)]
+///
+/// Ordering is defined by the version and then by the script bytes
+/// (consistent with `Eq` and `Hash`).
+#[derive(Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
 #[wasm_bindgen(inspectable)]
 pub struct ScriptPublicKey {
     pub version: ScriptPublicKeyVersion,
@@ -420,6 +433,32 @@ mod tests {
         assert_eq!(spk, spk2);
     }
 
+    #[test]
+    fn test_spk_ordering() {
+        let a = ScriptPublicKey::from_vec(0, vec![2, 1]);
+        let b = ScriptPublicKey::from_vec(0, vec![2, 1, 0]);
+        let c = ScriptPublicKey::from_vec(0, vec![3]);
+        let d = ScriptPublicKey::from_vec(1, vec![0]);
+        assert!(a < b && b < c && c < d);
+        assert_eq!(a.cmp(&ScriptPublicKey::from_vec(0, vec![2, 1])), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn test_sorted_script_public_keys_serialization() {
+        let keys = (0..64u8).map(|i| ScriptPublicKey::from_vec((i % 3) as u16, vec![i.wrapping_mul(37); 1 + (i % 5) as usize]));
+        let forward = keys.clone().collect::<ScriptPublicKeys>();
+        let reverse = keys.rev().collect::<ScriptPublicKeys>();
+
+        let sorted = sorted_script_public_keys(&forward);
+        assert!(sorted.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(sorted.len(), forward.len());
+
+        // sets built independently use distinct hasher states and insertion orders
+        let json = serde_json::to_string(&sorted).unwrap();
+        assert_eq!(json, serde_json::to_string(&sorted_script_public_keys(&reverse)).unwrap());
+        assert_eq!(sorted.try_to_vec().unwrap(), sorted_script_public_keys(&reverse).try_to_vec().unwrap());
+    }
+
     use wasm_bindgen_test::wasm_bindgen_test;
     use workflow_wasm::serde::{from_value, to_value};
     #[wasm_bindgen_test]
@@ -0,0 +1,102 @@
+//!
+//! Aggregate signature operation counts of transactions.
+//!
+//! Every transaction input commits to the number of signature operations its script
+//! performs. The totals of a transaction and of a block are bounded by the limits of
+//! [`Params`], which are derived from the mass of a signature operation.
+//!
+
+use super::{Transaction, TransactionInput};
+use crate::{
+    config::params::Params,
+    errors::{
+        block::{BlockProcessResult, RuleError},
+        tx::{TxResult, TxRuleError},
+    },
+};
+
+/// Returns the total number of signature operations committed to by `inputs`
+pub fn inputs_sig_op_count<'a>(inputs: impl IntoIterator<Item = &'a TransactionInput>) -> u64 {
+    inputs.into_iter().map(|input| input.sig_op_count as u64).sum()
+}
+
+/// Returns the total number of signature operations committed to by the inputs of `tx`
+pub fn transaction_sig_op_count(tx: &Transaction) -> u64 {
+    inputs_sig_op_count(&tx.inputs)
+}
+
+/// Checks the total number of signature operations of `tx` against [`Params::max_transaction_sig_ops`]
+/// and returns it
+pub fn check_transaction_sig_op_count(tx: &Transaction, params: &Params) -> TxResult<u64> {
+    let sig_ops = transaction_sig_op_count(tx);
+    let max_sig_ops = params.max_transaction_sig_ops();
+    if sig_ops > max_sig_ops {
+        return Err(TxRuleError::TooManySigOps(sig_ops, max_sig_ops));
+    }
+    Ok(sig_ops)
+}
+
+/// Checks the total number of signature operations of the transactions of a block against
+/// [`Params::max_block_sig_ops`] and returns it
+pub fn check_block_sig_op_count<'a>(txs: impl IntoIterator<Item = &'a Transaction>, params: &Params) -> BlockProcessResult<u64> {
+    let sig_ops = txs.into_iter().map(transaction_sig_op_count).sum();
+    let max_sig_ops = params.max_block_sig_ops();
+    if sig_ops > max_sig_ops {
+        return Err(RuleError::ExceedsSigOpLimit(sig_ops, max_sig_ops));
+    }
+    Ok(sig_ops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::params::MAINNET_PARAMS,
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, TransactionOutpoint, TransactionOutput},
+    };
+
+    fn transaction(sig_op_counts: &[u8]) -> Transaction {
+        let inputs = sig_op_counts
+            .iter()
+            .enumerate()
+            .map(|(i, &sig_op_count)| TransactionInput::new(TransactionOutpoint::new(1.into(), i as u32), vec![], 0, sig_op_count))
+            .collect();
+        let outputs = vec![TransactionOutput::new(1, ScriptPublicKey::from_vec(0, vec![]))];
+        Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![])
+    }
+
+    #[test]
+    fn test_transaction_sig_op_count() {
+        let params = &MAINNET_PARAMS;
+        let max = params.max_transaction_sig_ops();
+
+        // at the limit
+        let mut sig_op_counts = vec![1; max as usize - 2];
+        sig_op_counts.push(2);
+        let tx = transaction(&sig_op_counts);
+        assert_eq!(transaction_sig_op_count(&tx), max);
+        assert_eq!(check_transaction_sig_op_count(&tx, params), Ok(max));
+
+        // above the limit
+        sig_op_counts.push(1);
+        let tx = transaction(&sig_op_counts);
+        assert_eq!(check_transaction_sig_op_count(&tx, params), Err(TxRuleError::TooManySigOps(max + 1, max)));
+    }
+
+    #[test]
+    fn test_block_sig_op_count() {
+        let params = &MAINNET_PARAMS;
+        let max = params.max_block_sig_ops();
+        let per_tx = params.max_transaction_sig_ops();
+        assert_eq!(max % per_tx, 0);
+
+        // at the limit
+        let mut txs = (0..max / per_tx).map(|_| transaction(&vec![1; per_tx as usize])).collect::<Vec<_>>();
+        assert_eq!(check_block_sig_op_count(&txs, params).unwrap(), max);
+
+        // above the limit
+        txs.push(transaction(&[1]));
+        assert!(matches!(check_block_sig_op_count(&txs, params), Err(RuleError::ExceedsSigOpLimit(sig_ops, _)) if sig_ops == max + 1));
+    }
+}
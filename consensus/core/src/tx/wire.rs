@@ -0,0 +1,253 @@
+//!
+//! Canonical binary encoding of transactions.
+//!
+//! The encoding is defined explicitly by this module and does not depend on
+//! serde, bincode or Borsh derives, so changes to the in-memory representation
+//! of the transaction structures can not alter it. All integers are little-endian,
+//! variable length fields (collections and byte arrays) are prefixed by their
+//! length encoded as `u64`:
+//!
+//! ```text
+//! wire format version: u8
+//! version: u16
+//! inputs: len, [ transaction id: 32 bytes, index: u32, signature script: bytes, sig op count: u8, sequence: u64 ]
+//! outputs: len, [ value: u64, script public key version: u16, script public key: bytes ]
+//! lock time: u64, subnetwork id: 20 bytes, gas: u64, payload: bytes
+//! ```
+//!
+//! The transaction mass and the cached transaction id are not part of the encoding,
+//! the id is recomputed when decoding.
+//!
+
+use super::{ScriptPublicKey, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput};
+use crate::subnets::{SubnetworkId, SUBNETWORK_ID_SIZE};
+use kaspa_hashes::HASH_SIZE;
+
+/// Version of the wire format, encoded as its first byte
+pub const WIRE_FORMAT_VERSION: u8 = 0;
+
+#[derive(thiserror::Error, PartialEq, Eq, Debug, Clone)]
+pub enum WireError {
+    #[error("Unsupported wire format version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Unexpected end of data while reading {0}")]
+    UnexpectedEnd(&'static str),
+    #[error("Length {0} of {1} exceeds the remaining data")]
+    InvalidLength(u64, &'static str),
+    #[error("{0} unexpected trailing bytes")]
+    TrailingBytes(usize),
+}
+
+pub type WireResult<T> = std::result::Result<T, WireError>;
+
+impl Transaction {
+    /// Encodes the transaction in the canonical wire format.
+    pub fn to_wire(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.u8(WIRE_FORMAT_VERSION).u16(self.version).len(self.inputs.len());
+        for input in self.inputs.iter() {
+            writer
+                .bytes(input.previous_outpoint.transaction_id.as_ref())
+                .u32(input.previous_outpoint.index)
+                .var_bytes(&input.signature_script)
+                .u8(input.sig_op_count)
+                .u64(input.sequence);
+        }
+        writer.len(self.outputs.len());
+        for output in self.outputs.iter() {
+            writer.u64(output.value).u16(output.script_public_key.version()).var_bytes(output.script_public_key.script());
+        }
+        writer.u64(self.lock_time).bytes(self.subnetwork_id.as_ref()).u64(self.gas).var_bytes(&self.payload);
+        writer.0
+    }
+
+    /// Decodes a transaction from the canonical wire format. The returned
+    /// transaction is finalized (its id is computed).
+    pub fn from_wire(data: &[u8]) -> WireResult<Transaction> {
+        let mut reader = Reader(data);
+        let wire_format_version = reader.u8("wire format version")?;
+        if wire_format_version != WIRE_FORMAT_VERSION {
+            return Err(WireError::UnsupportedVersion(wire_format_version));
+        }
+        let version = reader.u16("version")?;
+        let inputs = (0..reader.len("inputs", 32 + 4 + 8 + 1 + 8)?)
+            .map(|_| {
+                let transaction_id = TransactionId::from_bytes(reader.array::<HASH_SIZE>("outpoint transaction id")?);
+                let index = reader.u32("outpoint index")?;
+                let signature_script = reader.var_bytes("signature script")?;
+                let sig_op_count = reader.u8("sig op count")?;
+                let sequence = reader.u64("sequence")?;
+                Ok(TransactionInput::new(TransactionOutpoint::new(transaction_id, index), signature_script, sequence, sig_op_count))
+            })
+            .collect::<WireResult<Vec<_>>>()?;
+        let outputs = (0..reader.len("outputs", 8 + 2 + 8)?)
+            .map(|_| {
+                let value = reader.u64("output value")?;
+                let version = reader.u16("script public key version")?;
+                let script = reader.var_bytes("script public key")?;
+                Ok(TransactionOutput::new(value, ScriptPublicKey::from_vec(version, script)))
+            })
+            .collect::<WireResult<Vec<_>>>()?;
+        let lock_time = reader.u64("lock time")?;
+        let subnetwork_id = SubnetworkId::from(reader.array::<SUBNETWORK_ID_SIZE>("subnetwork id")?);
+        let gas = reader.u64("gas")?;
+        let payload = reader.var_bytes("payload")?;
+
+        if !reader.0.is_empty() {
+            return Err(WireError::TrailingBytes(reader.0.len()));
+        }
+
+        Ok(Transaction::new(version, inputs, outputs, lock_time, subnetwork_id, gas, payload))
+    }
+}
+
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.0.extend_from_slice(bytes);
+        self
+    }
+
+    fn u8(&mut self, value: u8) -> &mut Self {
+        self.bytes(&[value])
+    }
+
+    fn u16(&mut self, value: u16) -> &mut Self {
+        self.bytes(&value.to_le_bytes())
+    }
+
+    fn u32(&mut self, value: u32) -> &mut Self {
+        self.bytes(&value.to_le_bytes())
+    }
+
+    fn u64(&mut self, value: u64) -> &mut Self {
+        self.bytes(&value.to_le_bytes())
+    }
+
+    fn len(&mut self, len: usize) -> &mut Self {
+        self.u64(len as u64)
+    }
+
+    fn var_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.len(bytes.len()).bytes(bytes)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize, field: &'static str) -> WireResult<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(WireError::UnexpectedEnd(field));
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self, field: &'static str) -> WireResult<[u8; N]> {
+        Ok(self.take(N, field)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self, field: &'static str) -> WireResult<u8> {
+        Ok(self.array::<1>(field)?[0])
+    }
+
+    fn u16(&mut self, field: &'static str) -> WireResult<u16> {
+        Ok(u16::from_le_bytes(self.array(field)?))
+    }
+
+    fn u32(&mut self, field: &'static str) -> WireResult<u32> {
+        Ok(u32::from_le_bytes(self.array(field)?))
+    }
+
+    fn u64(&mut self, field: &'static str) -> WireResult<u64> {
+        Ok(u64::from_le_bytes(self.array(field)?))
+    }
+
+    /// Reads a collection length, rejecting lengths that can not fit into the remaining
+    /// data given the minimum encoded size of an element (prevents oversized allocations).
+    fn len(&mut self, field: &'static str, min_element_size: usize) -> WireResult<usize> {
+        let len = self.u64(field)?;
+        match usize::try_from(len) {
+            Ok(n) if n.checked_mul(min_element_size).is_some_and(|size| size <= self.0.len()) => Ok(n),
+            _ => Err(WireError::InvalidLength(len, field)),
+        }
+    }
+
+    fn var_bytes(&mut self, field: &'static str) -> WireResult<Vec<u8>> {
+        let len = self.len(field, 1)?;
+        Ok(self.take(len, field)?.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subnets::SUBNETWORK_ID_NATIVE;
+    use crate::tx::scriptvec;
+
+    fn test_transaction() -> Transaction {
+        Transaction::new(
+            1,
+            vec![TransactionInput::new(TransactionOutpoint::new(TransactionId::from_u64_word(7), 3), vec![0xaa, 0xbb], 9, 1)],
+            vec![TransactionOutput::new(1000, ScriptPublicKey::new(0, scriptvec![0x51]))],
+            5,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![0xcc],
+        )
+    }
+
+    #[test]
+    fn test_transaction_wire_format() {
+        let tx = test_transaction();
+        let bytes = tx.to_wire();
+
+        #[rustfmt::skip]
+        let expected = vec![
+            0, // wire format version
+            1, 0, // version
+            1, 0, 0, 0, 0, 0, 0, 0, // inputs
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0, // outpoint transaction id
+            3, 0, 0, 0, // outpoint index
+            2, 0, 0, 0, 0, 0, 0, 0, 170, 187, // signature script
+            1, // sig op count
+            9, 0, 0, 0, 0, 0, 0, 0, // sequence
+            1, 0, 0, 0, 0, 0, 0, 0, // outputs
+            232, 3, 0, 0, 0, 0, 0, 0, // value
+            0, 0, // script public key version
+            1, 0, 0, 0, 0, 0, 0, 0, 81, // script public key
+            5, 0, 0, 0, 0, 0, 0, 0, // lock time
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // subnetwork id
+            0, 0, 0, 0, 0, 0, 0, 0, // gas
+            1, 0, 0, 0, 0, 0, 0, 0, 204, // payload
+        ];
+        assert_eq!(bytes, expected);
+
+        let decoded = Transaction::from_wire(&bytes).unwrap();
+        assert_eq!(decoded, tx);
+        assert_eq!(decoded.id(), tx.id());
+
+        // the mass is not part of the encoding
+        tx.set_mass(2);
+        assert_eq!(tx.to_wire(), expected);
+    }
+
+    #[test]
+    fn test_transaction_wire_format_errors() {
+        let bytes = test_transaction().to_wire();
+        assert_eq!(Transaction::from_wire(&bytes[..bytes.len() - 20]), Err(WireError::UnexpectedEnd("subnetwork id")));
+        assert_eq!(Transaction::from_wire(&[bytes.as_slice(), &[0]].concat()), Err(WireError::TrailingBytes(1)));
+
+        let mut bytes = bytes;
+        bytes[3..11].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(Transaction::from_wire(&bytes), Err(WireError::InvalidLength(u64::MAX, "inputs")));
+
+        bytes[0] = 1;
+        assert_eq!(Transaction::from_wire(&bytes), Err(WireError::UnsupportedVersion(1)));
+        assert_eq!(Transaction::from_wire(&[]), Err(WireError::UnexpectedEnd("wire format version")));
+    }
+}
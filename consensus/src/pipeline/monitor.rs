@@ -1,10 +1,13 @@
 use super::ProcessingCounters;
+use kaspa_consensus_core::api::counters::SyncEstimate;
+use kaspa_consensusmanager::ConsensusManager;
 use kaspa_core::{
     info,
     task::{
         service::{AsyncService, AsyncServiceFuture},
         tick::{TickReason, TickService},
     },
+    time::unix_now,
     trace,
 };
 use std::{
@@ -20,11 +23,40 @@ pub struct ConsensusMonitor {
 
     // Tick service
     tick_service: Arc<TickService>,
+
+    // Consensus and target time per block (in milliseconds) used to estimate the time to sync
+    sync_source: Option<(Arc<ConsensusManager>, u64)>,
 }
 
 impl ConsensusMonitor {
     pub fn new(counters: Arc<ProcessingCounters>, tick_service: Arc<TickService>) -> ConsensusMonitor {
-        ConsensusMonitor { counters, tick_service }
+        ConsensusMonitor { counters, tick_service, sync_source: None }
+    }
+
+    /// Enables the estimation of the time to sync, published in the processing counters
+    pub fn with_sync_source(self, consensus_manager: Arc<ConsensusManager>, target_time_per_block: u64) -> ConsensusMonitor {
+        ConsensusMonitor { sync_source: Some((consensus_manager, target_time_per_block)), ..self }
+    }
+
+    /// Returns the gap (in DAA score units) between the virtual and the network tip, estimated
+    /// from the time elapsed since the timestamp of the selected tip (sink) or zero if the
+    /// node is nearly synced
+    async fn sync_gap(&self) -> Option<u64> {
+        let (consensus_manager, target_time_per_block) = self.sync_source.as_ref()?;
+        let session = consensus_manager.consensus().unguarded_session();
+        if session.async_is_nearly_synced().await {
+            return Some(0);
+        }
+        let elapsed = unix_now().saturating_sub(session.async_get_sink_timestamp().await);
+        Some(elapsed / (*target_time_per_block).max(1))
+    }
+
+    /// Publishes the time to sync estimated from the header processing `rate` (headers per second)
+    async fn update_sync_estimate(&self, rate: f64) -> Option<(u64, SyncEstimate)> {
+        let gap = self.sync_gap().await?;
+        let estimate = SyncEstimate::new(gap, rate);
+        self.counters.set_sync_estimate(estimate);
+        Some((gap, estimate))
     }
 
     pub async fn worker(self: &Arc<ConsensusMonitor>) {
@@ -41,6 +73,7 @@ impl ConsensusMonitor {
             let snapshot = self.counters.snapshot();
             if snapshot == last_snapshot {
                 // No update, avoid printing useless info
+                self.update_sync_estimate(0f64).await;
                 last_log_time = Instant::now();
                 continue;
             }
@@ -62,6 +95,14 @@ impl ConsensusMonitor {
                 if delta.body_counts != 0 { delta.mass_counts as f64 / delta.body_counts as f64 } else{ 0f64 },
             );
 
+            let elapsed = (now - last_log_time).as_secs_f64();
+            let rate = if elapsed > 0f64 { delta.header_counts as f64 / elapsed } else { 0f64 };
+            if let Some((gap, estimate)) = self.update_sync_estimate(rate).await {
+                if gap > 0 {
+                    info!("Estimated time to sync: {} ({} DAA score behind the network tip)", estimate, gap);
+                }
+            }
+
             last_snapshot = snapshot;
             last_log_time = now;
         }
@@ -73,8 +73,9 @@ impl MassCalculator {
             .map(|out| self.storage_mass_parameter / out.value)
             .try_fold(0u64, |total, current| total.checked_add(current))?; // C·|O|/H(O)
 
-        // Total supply is bounded, so a sum of existing UTXO entries cannot overflow (nor can it be zero)
-        let sum_ins = tx.populated_inputs().map(|(_, entry)| entry.amount).sum::<u64>(); // |I|·A(I)
+        // Total supply is bounded, so a sum of existing UTXO entries cannot overflow (nor can it be zero),
+        // entries populated by callers are not necessarily existing ones, so mass is incomputable on overflow
+        let sum_ins = tx.total_input_amount().ok()?; // |I|·A(I)
         let ins_len = tx.tx().inputs.len() as u64;
         let mean_ins = sum_ins / ins_len;
 
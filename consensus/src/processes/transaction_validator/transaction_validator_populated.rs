@@ -86,8 +86,8 @@ impl TransactionValidator {
     }
 
     fn check_transaction_output_values(tx: &impl VerifiableTransaction, total_in: u64) -> TxResult<u64> {
-        // There's no need to check for overflow here because it was already checked by check_transaction_output_value_ranges
-        let total_out: u64 = tx.outputs().iter().map(|out| out.value).sum();
+        // Overflow was already checked by check_transaction_output_value_ranges, the checked sum guards crafted transactions regardless
+        let total_out = tx.total_output_value()?;
         if total_in < total_out {
             return Err(TxRuleError::SpendTooHigh(total_out, total_in));
         }
@@ -1,5 +1,8 @@
-use super::consts::{
-    LOG_ARCHIVE_SUFFIX, LOG_FILE_BASE_ROLLS, LOG_FILE_MAX_ROLLS, LOG_FILE_MAX_SIZE, LOG_LINE_PATTERN, LOG_LINE_PATTERN_COLORED,
+use super::{
+    consts::{
+        LOG_ARCHIVE_SUFFIX, LOG_FILE_BASE_ROLLS, LOG_FILE_MAX_ROLLS, LOG_FILE_MAX_SIZE, LOG_LINE_PATTERN, LOG_LINE_PATTERN_COLORED,
+    },
+    LogFormat,
 };
 use log::LevelFilter;
 use log4rs::{
@@ -12,7 +15,7 @@ use log4rs::{
         Append,
     },
     config::Appender,
-    encode::pattern::PatternEncoder,
+    encode::{json::JsonEncoder, pattern::PatternEncoder, Encode},
     filter::{threshold::ThresholdFilter, Filter},
 };
 use std::path::PathBuf;
@@ -24,15 +27,11 @@ pub(super) struct AppenderSpec {
 }
 
 impl AppenderSpec {
-    pub fn console(name: &'static str, level: Option<LevelFilter>) -> Self {
-        Self::new(
-            name,
-            level,
-            Box::new(ConsoleAppender::builder().encoder(Box::new(PatternEncoder::new(LOG_LINE_PATTERN_COLORED))).build()),
-        )
+    pub fn console(name: &'static str, level: Option<LevelFilter>, format: LogFormat) -> Self {
+        Self::new(name, level, Box::new(ConsoleAppender::builder().encoder(encoder(format, LOG_LINE_PATTERN_COLORED)).build()))
     }
 
-    pub fn roller(name: &'static str, level: Option<LevelFilter>, log_dir: &str, file_name: &str) -> Self {
+    pub fn roller(name: &'static str, level: Option<LevelFilter>, log_dir: &str, file_name: &str, format: LogFormat) -> Self {
         let appender = {
             let trigger = Box::new(SizeTrigger::new(LOG_FILE_MAX_SIZE));
 
@@ -46,10 +45,8 @@ impl AppenderSpec {
             );
 
             let compound_policy = Box::new(CompoundPolicy::new(trigger, roller));
-            let file_appender = RollingFileAppender::builder()
-                .encoder(Box::new(PatternEncoder::new(LOG_LINE_PATTERN)))
-                .build(file_path, compound_policy)
-                .unwrap();
+            let file_appender =
+                RollingFileAppender::builder().encoder(encoder(format, LOG_LINE_PATTERN)).build(file_path, compound_policy).unwrap();
 
             Box::new(file_appender) as Box<dyn Append>
         };
@@ -66,3 +63,11 @@ impl AppenderSpec {
             .build(self.name, self.append.take().unwrap())
     }
 }
+
+/// Encoder of the log lines, using `pattern` for the text format
+fn encoder(format: LogFormat, pattern: &str) -> Box<dyn Encode> {
+    match format {
+        LogFormat::Text => Box::new(PatternEncoder::new(pattern)),
+        LogFormat::Json => Box::new(JsonEncoder::new()),
+    }
+}
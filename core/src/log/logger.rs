@@ -7,6 +7,15 @@ use thiserror::Error;
 pub enum LogError {
     #[error("Logger spec parsing error: {0}")]
     ParseLoggerSpecError(String),
+
+    #[error("Invalid log subsystem '{0}'")]
+    InvalidSubsystem(String),
+
+    #[error("Invalid log level '{0}' (expected off, error, warn, info, debug or trace)")]
+    InvalidLevel(String),
+
+    #[error("The logger is not initialized")]
+    NotInitialized,
 }
 
 #[derive(Clone)]
@@ -26,6 +35,7 @@ impl LoggerSpec {
     }
 }
 
+#[derive(Clone)]
 pub(super) struct Loggers {
     loggers: Vec<LoggerSpec>,
     root_level: LevelFilter,
@@ -36,6 +46,17 @@ impl Loggers {
         self.root_level
     }
 
+    /// Sets the level of the `subsystem` logger, or the root level if `None`
+    pub fn set_level(&mut self, subsystem: Option<&str>, level: LevelFilter) {
+        match subsystem {
+            Some(name) => match self.loggers.iter_mut().find(|x| x.name == name) {
+                Some(logger) => logger.level = level,
+                None => self.loggers.push(LoggerSpec::new(name.to_string(), level, vec![])),
+            },
+            None => self.root_level = level,
+        }
+    }
+
     pub fn items(&self) -> impl IntoIterator<Item = Logger> + '_ {
         self.loggers.iter().map(|x| x.logger())
     }
@@ -5,14 +5,18 @@
 
 #[allow(unused_imports)]
 pub use log::{Level, LevelFilter};
+use std::{fmt::Display, str::FromStr};
 
 cfg_if::cfg_if! {
     if #[cfg(not(target_arch = "wasm32"))] {
 use consts::*;
+use std::sync::Mutex;
 
 mod appender;
 mod consts;
 mod logger;
+
+pub use logger::LogError;
     }
 }
 
@@ -37,57 +41,134 @@ cfg_if::cfg_if! {
     }
 }
 
+/// Format of the lines written by the logger to the console and to the log files
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, with `time`, `level`, `target` and `message` fields
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!("invalid log format '{s}' (expected text or json)")),
+        }
+    }
+}
+
+impl Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            LogFormat::Text => "text",
+            LogFormat::Json => "json",
+        };
+        f.write_str(s)
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn init_logger(log_dir: Option<&str>, filters: &str) {
+    init_logger_with_format(log_dir, filters, LogFormat::Text)
+}
+
+/// State of the global logger, kept for reconfiguring it at runtime
+#[cfg(not(target_arch = "wasm32"))]
+struct GlobalLogger {
+    handle: log4rs::Handle,
+    log_dir: Option<String>,
+    format: LogFormat,
+    loggers: logger::Loggers,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+static GLOBAL_LOGGER: Mutex<Option<GlobalLogger>> = Mutex::new(None);
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn init_logger_with_format(log_dir: Option<&str>, filters: &str, format: LogFormat) {
+    let level = LevelFilter::Info;
+    let loggers = logger::Builder::new().root_level(level).parse_env(DEFAULT_LOGGER_ENV).parse_expression(filters).build();
+
+    let handle = log4rs::init_config(build_config(appenders(log_dir, format), &loggers)).unwrap();
+    GLOBAL_LOGGER.lock().unwrap().replace(GlobalLogger { handle, log_dir: log_dir.map(String::from), format, loggers });
+
+    set_log_level(level);
+}
+
+/// Sets at runtime the log level of a subsystem (a crate or module path, ie. `kaspa_p2p_flows`),
+/// or the level of all the subsystems not configured otherwise if `subsystem` is `None`.
+/// The new level applies to all the log calls following this one.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_subsystem_log_level(subsystem: Option<&str>, level: &str) -> Result<(), LogError> {
+    let level = level.trim().parse::<LevelFilter>().map_err(|_| LogError::InvalidLevel(level.to_string()))?;
+    if let Some(subsystem) = subsystem.filter(|x| !is_valid_subsystem(x)) {
+        return Err(LogError::InvalidSubsystem(subsystem.to_string()));
+    }
+
+    let mut global_logger = GLOBAL_LOGGER.lock().unwrap();
+    let global_logger = global_logger.as_mut().ok_or(LogError::NotInitialized)?;
+    global_logger.loggers.set_level(subsystem, level);
+    let appenders = appenders(global_logger.log_dir.as_deref(), global_logger.format);
+    global_logger.handle.set_config(build_config(appenders, &global_logger.loggers));
+    if subsystem.is_none() {
+        set_log_level(level);
+    }
+    Ok(())
+}
+
+/// Returns whether `subsystem` is a valid crate or module path
+#[cfg(not(target_arch = "wasm32"))]
+fn is_valid_subsystem(subsystem: &str) -> bool {
+    subsystem.split("::").all(|name| {
+        name.chars().next().is_some_and(|x| x.is_ascii_alphabetic() || x == '_')
+            && name.chars().all(|x| x.is_ascii_alphanumeric() || x == '_')
+    })
+}
+
+/// Appenders writing to the console and, if `log_dir` is provided, to the log files
+#[cfg(not(target_arch = "wasm32"))]
+fn appenders(log_dir: Option<&str>, format: LogFormat) -> Vec<appender::AppenderSpec> {
     use crate::log::appender::AppenderSpec;
-    use log4rs::{config::Root, Config};
     use std::iter::once;
 
     const CONSOLE_APPENDER: &str = "stdout";
     const LOG_FILE_APPENDER: &str = "log_file";
     const ERR_LOG_FILE_APPENDER: &str = "err_log_file";
 
-    let level = LevelFilter::Info;
-    let loggers = logger::Builder::new().root_level(level).parse_env(DEFAULT_LOGGER_ENV).parse_expression(filters).build();
+    once(AppenderSpec::console(CONSOLE_APPENDER, None, format))
+        .chain(log_dir.map(|x| AppenderSpec::roller(LOG_FILE_APPENDER, None, x, LOG_FILE_NAME, format)))
+        .chain(log_dir.map(|x| AppenderSpec::roller(ERR_LOG_FILE_APPENDER, Some(LevelFilter::Warn), x, ERR_LOG_FILE_NAME, format)))
+        .collect()
+}
 
-    let mut stdout_appender = AppenderSpec::console(CONSOLE_APPENDER, None);
-    let mut file_appender = log_dir.map(|x| AppenderSpec::roller(LOG_FILE_APPENDER, None, x, LOG_FILE_NAME));
-    let mut err_file_appender =
-        log_dir.map(|x| AppenderSpec::roller(ERR_LOG_FILE_APPENDER, Some(LevelFilter::Warn), x, ERR_LOG_FILE_NAME));
-    let appenders = once(&mut stdout_appender).chain(&mut file_appender).chain(&mut err_file_appender).map(|x| x.appender());
+/// Builds a config logging to all the `appenders`, filtered by `loggers`
+#[cfg(not(target_arch = "wasm32"))]
+fn build_config(appenders: Vec<appender::AppenderSpec>, loggers: &logger::Loggers) -> log4rs::Config {
+    use log4rs::{config::Root, Config};
 
-    let config = Config::builder()
-        .appenders(appenders)
+    let names = appenders.iter().map(|x| x.name).collect::<Vec<_>>();
+    Config::builder()
+        .appenders(appenders.into_iter().map(|mut x| x.appender()))
         .loggers(loggers.items())
-        .build(
-            Root::builder()
-                .appenders(once(&stdout_appender).chain(&file_appender).chain(&err_file_appender).map(|x| x.name))
-                .build(loggers.root_level()),
-        )
-        .unwrap();
-
-    let _handle = log4rs::init_config(config).unwrap();
-
-    set_log_level(level);
+        .build(Root::builder().appenders(names).build(loggers.root_level()))
+        .unwrap()
 }
 
 /// Tries to init the global logger, but does not panic if it was already setup.
 /// Should be used for tests.
 #[cfg(not(target_arch = "wasm32"))]
 pub fn try_init_logger(filters: &str) {
-    use crate::log::appender::AppenderSpec;
-    use log4rs::{config::Root, Config};
-
-    const CONSOLE_APPENDER: &str = "stdout";
-
     let loggers = logger::Builder::new().root_level(LevelFilter::Info).parse_env(DEFAULT_LOGGER_ENV).parse_expression(filters).build();
-    let mut stdout_appender = AppenderSpec::console(CONSOLE_APPENDER, None);
-    let config = Config::builder()
-        .appender(stdout_appender.appender())
-        .loggers(loggers.items())
-        .build(Root::builder().appender(CONSOLE_APPENDER).build(loggers.root_level()))
-        .unwrap();
-    let _ = log4rs::init_config(config);
+    let config = build_config(appenders(None, LogFormat::Text), &loggers);
+    if let Ok(handle) = log4rs::init_config(config) {
+        GLOBAL_LOGGER.lock().unwrap().replace(GlobalLogger { handle, log_dir: None, format: LogFormat::Text, loggers });
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -179,3 +260,80 @@ macro_rules! error {
         log::error!($($t)*);
     )
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::log::appender::AppenderSpec;
+    use log::{Log, Record};
+
+    #[test]
+    fn test_json_log_format() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let loggers = logger::Builder::new().root_level(LevelFilter::Info).parse_expression("kaspa_p2p_flows=warn").build();
+        let appenders = vec![AppenderSpec::roller("log_file", None, log_dir.path().to_str().unwrap(), LOG_FILE_NAME, LogFormat::Json)];
+        let logger = log4rs::Logger::new(build_config(appenders, &loggers));
+
+        for (target, level, message) in [
+            ("kaspa_consensus::pipeline", Level::Info, "Processed 10 blocks"),
+            ("kaspa_consensus::pipeline", Level::Debug, "Filtered by the root level"),
+            ("kaspa_p2p_flows::ibd", Level::Info, "Filtered by the subsystem level"),
+            ("kaspa_p2p_flows::ibd", Level::Warn, "Peer \"127.0.0.1:16111\" misbehaved"),
+        ] {
+            logger.log(&Record::builder().target(target).level(level).args(format_args!("{message}")).build());
+        }
+        logger.flush();
+
+        let output = std::fs::read_to_string(log_dir.path().join(LOG_FILE_NAME)).unwrap();
+        let lines = output.lines().map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()).collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        for (line, (target, level, message)) in lines.iter().zip([
+            ("kaspa_consensus::pipeline", "INFO", "Processed 10 blocks"),
+            ("kaspa_p2p_flows::ibd", "WARN", "Peer \"127.0.0.1:16111\" misbehaved"),
+        ]) {
+            assert_eq!(line["target"], target);
+            assert_eq!(line["level"], level);
+            assert_eq!(line["message"], message);
+            assert!(line["time"].as_str().is_some_and(|time| !time.is_empty()));
+        }
+    }
+
+    #[test]
+    fn test_set_subsystem_log_level() {
+        let log_dir = tempfile::tempdir().unwrap();
+        assert!(matches!(set_subsystem_log_level(Some("kaspa_core"), "info"), Err(LogError::NotInitialized)));
+        init_logger_with_format(log_dir.path().to_str(), "info", LogFormat::Json);
+
+        const SUBSYSTEM: &str = "kaspa_log_test::flows";
+        log::debug!(target: SUBSYSTEM, "Debug before the change");
+        set_subsystem_log_level(Some("kaspa_log_test"), "debug").unwrap();
+        log::debug!(target: SUBSYSTEM, "Debug after the change");
+        log::debug!(target: "kaspa_other_test", "Debug of another subsystem");
+        set_subsystem_log_level(Some("kaspa_log_test"), "WARN").unwrap();
+        log::info!(target: SUBSYSTEM, "Info below the new level");
+        log::warn!(target: SUBSYSTEM, "Warn at the new level");
+        set_subsystem_log_level(None, "error").unwrap();
+        log::info!(target: "kaspa_other_test", "Info below the new root level");
+
+        for subsystem in ["", "kaspa log", "kaspa_core::", "::kaspa_core", "1kaspa"] {
+            assert!(matches!(set_subsystem_log_level(Some(subsystem), "debug"), Err(LogError::InvalidSubsystem(_))), "{subsystem}");
+        }
+        assert!(matches!(set_subsystem_log_level(Some("kaspa_core"), "verbose"), Err(LogError::InvalidLevel(_))));
+        log::logger().flush();
+
+        let output = std::fs::read_to_string(log_dir.path().join(LOG_FILE_NAME)).unwrap();
+        let messages = output
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["message"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["Debug after the change", "Warn at the new level"]);
+    }
+
+    #[test]
+    fn test_log_format_from_str() {
+        assert_eq!("text".parse::<LogFormat>(), Ok(LogFormat::Text));
+        assert_eq!("JSON".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert!("xml".parse::<LogFormat>().is_err());
+        assert_eq!(LogFormat::default().to_string(), "text");
+    }
+}
@@ -1,5 +1,6 @@
 pub mod runtime;
 pub mod service;
+pub mod supervisor;
 pub mod tick;
 
 // TODO: Determine the most appropriate location for task
@@ -1,20 +1,71 @@
-use crate::{signals::Shutdown, task::service::AsyncServiceResult};
+use crate::{
+    signals::Shutdown,
+    task::service::{AsyncServiceError, AsyncServiceFuture, AsyncServiceResult},
+    time::unix_now,
+};
 use futures_util::future::{select_all, try_join_all};
 use kaspa_core::core::Core;
 use kaspa_core::service::Service;
 use kaspa_core::task::service::AsyncService;
 use kaspa_core::trace;
 use std::{
+    any::Any,
     sync::{Arc, Mutex},
     thread::{self, JoinHandle as ThreadJoinHandle},
 };
 use tokio::task::JoinHandle as TaskJoinHandle;
 
+/// State of a service registered in an [`AsyncRuntime`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceState {
+    /// The service is registered but was not started yet
+    Registered,
+    Running,
+    /// The service returned successfully
+    Stopped,
+    /// The service returned an error or panicked
+    Failed,
+}
+
+/// Metrics of a service registered in an [`AsyncRuntime`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceMetrics {
+    pub ident: &'static str,
+    pub state: ServiceState,
+    /// Time the service was started at, in milliseconds since UNIX EPOCH
+    pub start_time: Option<u64>,
+    /// Error returned by the service or message of the panic it raised
+    pub last_error: Option<String>,
+}
+
+impl ServiceMetrics {
+    fn new(ident: &'static str) -> Self {
+        Self { ident, state: ServiceState::Registered, start_time: None, last_error: None }
+    }
+
+    fn started(&mut self) {
+        self.state = ServiceState::Running;
+        self.start_time = Some(unix_now());
+    }
+
+    fn finished(&mut self, result: &AsyncServiceResult<()>) {
+        match result {
+            Ok(()) => self.state = ServiceState::Stopped,
+            Err(err) => {
+                self.state = ServiceState::Failed;
+                self.last_error = Some(err.to_string());
+            }
+        }
+    }
+}
+
 /// AsyncRuntime registers async services and provides
 /// a tokio Runtime to run them.
 pub struct AsyncRuntime {
     threads: usize,
     services: Mutex<Vec<Arc<dyn AsyncService>>>,
+    // Metrics of the registered services, outliving the services themselves
+    metrics: Mutex<Vec<Arc<Mutex<ServiceMetrics>>>>,
 }
 
 impl Default for AsyncRuntime {
@@ -29,15 +80,32 @@ impl AsyncRuntime {
 
     pub fn new(threads: usize) -> Self {
         trace!("Creating the async-runtime service");
-        Self { threads, services: Mutex::new(Vec::new()) }
+        Self { threads, services: Mutex::new(Vec::new()), metrics: Mutex::new(Vec::new()) }
     }
 
     pub fn register<T>(&self, service: Arc<T>)
     where
         T: AsyncService,
     {
-        trace!("async-runtime registering service {}", service.clone().ident());
-        self.services.lock().unwrap().push(service);
+        let ident = service.clone().ident();
+        trace!("async-runtime registering service {}", ident);
+        let mut services = self.services.lock().unwrap();
+        services.push(service);
+        self.metrics.lock().unwrap().push(Arc::new(Mutex::new(ServiceMetrics::new(ident))));
+    }
+
+    /// Returns a snapshot of the metrics of all registered services, in registration order
+    pub fn services_metrics(&self) -> Vec<ServiceMetrics> {
+        self.metrics.lock().unwrap().iter().map(|metrics| metrics.lock().unwrap().clone()).collect()
+    }
+
+    /// Runs `service`, recording its state in `metrics`. A panicking service is recorded
+    /// as failed and reported as an error.
+    async fn run_service(service: Arc<dyn AsyncService>, metrics: Arc<Mutex<ServiceMetrics>>) -> AsyncServiceResult<()> {
+        metrics.lock().unwrap().started();
+        let result = spawn_catching_panic(service.start()).await;
+        metrics.lock().unwrap().finished(&result);
+        result
     }
 
     pub fn init(self: Arc<AsyncRuntime>, core: Arc<Core>) -> Vec<ThreadJoinHandle<()>> {
@@ -75,7 +143,8 @@ impl AsyncRuntime {
             .lock()
             .unwrap()
             .iter()
-            .map(|x| tokio::spawn(x.clone().start()))
+            .zip(self.metrics.lock().unwrap().iter())
+            .map(|(x, metrics)| tokio::spawn(Self::run_service(x.clone(), metrics.clone())))
             .collect::<Vec<TaskJoinHandle<AsyncServiceResult<()>>>>();
 
         // wait for at least one service to return
@@ -120,6 +189,27 @@ impl AsyncRuntime {
     }
 }
 
+/// Runs the future of a service in a dedicated task, reporting a panic as an error
+pub(crate) async fn spawn_catching_panic(future: AsyncServiceFuture) -> AsyncServiceResult<()> {
+    match tokio::spawn(future).await {
+        Ok(result) => result,
+        Err(err) => match err.try_into_panic() {
+            Ok(panic) => Err(AsyncServiceError::Service(format!("service panicked: {}", panic_message(&panic)))),
+            Err(err) => Err(AsyncServiceError::Service(err.to_string())),
+        },
+    }
+}
+
+fn panic_message(panic: &Box<dyn Any + Send>) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown"
+    }
+}
+
 impl Service for AsyncRuntime {
     fn ident(self: Arc<AsyncRuntime>) -> &'static str {
         Self::IDENT
@@ -133,3 +223,62 @@ impl Service for AsyncRuntime {
         self.signal_exit()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Outcome {
+        Success,
+        Error,
+        Panic,
+    }
+
+    struct ServiceMock {
+        ident: &'static str,
+        outcome: Outcome,
+    }
+
+    impl AsyncService for ServiceMock {
+        fn ident(self: Arc<Self>) -> &'static str {
+            self.ident
+        }
+
+        fn start(self: Arc<Self>) -> AsyncServiceFuture {
+            Box::pin(async move {
+                match self.outcome {
+                    Outcome::Success => Ok(()),
+                    Outcome::Error => Err(AsyncServiceError::Service("mock error".to_string())),
+                    Outcome::Panic => panic!("mock panic"),
+                }
+            })
+        }
+
+        fn signal_exit(self: Arc<Self>) {}
+
+        fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+            Box::pin(async move { Ok(()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_services_metrics() {
+        let runtime = Arc::new(AsyncRuntime::new(1));
+        runtime.register(Arc::new(ServiceMock { ident: "success", outcome: Outcome::Success }));
+        runtime.register(Arc::new(ServiceMock { ident: "error", outcome: Outcome::Error }));
+        runtime.register(Arc::new(ServiceMock { ident: "panic", outcome: Outcome::Panic }));
+
+        let metrics = runtime.services_metrics();
+        assert_eq!(metrics.iter().map(|m| m.ident).collect::<Vec<_>>(), vec!["success", "error", "panic"]);
+        assert!(metrics.iter().all(|m| m.state == ServiceState::Registered && m.start_time.is_none()));
+
+        let core = Arc::new(Core::new());
+        runtime.worker_impl(core).await;
+
+        let metrics = runtime.services_metrics();
+        assert!(metrics.iter().all(|m| m.start_time.is_some()));
+        assert_eq!((metrics[0].state, metrics[0].last_error.as_deref()), (ServiceState::Stopped, None));
+        assert_eq!((metrics[1].state, metrics[1].last_error.as_deref()), (ServiceState::Failed, Some("mock error")));
+        assert_eq!((metrics[2].state, metrics[2].last_error.as_deref()), (ServiceState::Failed, Some("service panicked: mock panic")));
+    }
+}
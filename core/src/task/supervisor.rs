@@ -0,0 +1,230 @@
+use super::{
+    runtime::spawn_catching_panic,
+    service::{AsyncService, AsyncServiceFuture, AsyncServiceResult},
+};
+use kaspa_core::{trace, warn};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use triggered::{trigger, Listener, Trigger};
+
+/// Policy of restarting a [`SupervisedService`] which returned an error or panicked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    /// Maximum number of consecutive restarts before giving up
+    pub max_retries: usize,
+    /// Delay before the first restart, doubled on every consecutive restart
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay before a restart
+    pub max_backoff: Duration,
+    /// Minimum duration of a successful run, which resets the count of consecutive restarts
+    pub stable_period: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self::new(5, Duration::from_secs(1), Duration::from_secs(60))
+    }
+}
+
+impl RestartPolicy {
+    const DEFAULT_STABLE_PERIOD: Duration = Duration::from_secs(300);
+
+    pub fn new(max_retries: usize, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self { max_retries, initial_backoff, max_backoff, stable_period: Self::DEFAULT_STABLE_PERIOD }
+    }
+
+    pub fn with_stable_period(self, stable_period: Duration) -> Self {
+        Self { stable_period, ..self }
+    }
+
+    /// Returns the delay before the restart following `retries` consecutive restarts
+    pub fn backoff(&self, retries: usize) -> Duration {
+        let factor = 1u32.checked_shl(retries.min(u32::BITS as usize) as u32).unwrap_or(u32::MAX);
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// Supervisor running an [`AsyncService`] and restarting it with a capped exponential
+/// backoff whenever it returns an error or panics.
+///
+/// The supervised service must support being started again after its start future completed.
+/// Once the [`RestartPolicy::max_retries`] limit is reached, the supervisor gives up and returns
+/// the last error, which causes the async runtime to signal the core shutdown.
+pub struct SupervisedService<T: AsyncService> {
+    service: Arc<T>,
+    policy: RestartPolicy,
+    restarts: AtomicUsize,
+    shutdown_trigger: Trigger,
+    shutdown_listener: Listener,
+}
+
+impl<T: AsyncService> SupervisedService<T> {
+    pub fn new(service: Arc<T>, policy: RestartPolicy) -> Self {
+        let (shutdown_trigger, shutdown_listener) = trigger();
+        Self { service, policy, restarts: AtomicUsize::new(0), shutdown_trigger, shutdown_listener }
+    }
+
+    /// Returns the number of times the service was restarted
+    pub fn restarts(&self) -> usize {
+        self.restarts.load(Ordering::SeqCst)
+    }
+
+    async fn supervise(self: Arc<Self>) -> AsyncServiceResult<()> {
+        let ident = self.service.clone().ident();
+        let mut retries = 0;
+        loop {
+            let run_start = Instant::now();
+            let err = match spawn_catching_panic(self.service.clone().start()).await {
+                Ok(()) => return Ok(()),
+                Err(err) => err,
+            };
+            if self.shutdown_listener.is_triggered() {
+                trace!("supervised service {} exited with error during shutdown: {}", ident, err);
+                return Err(err);
+            }
+            if run_start.elapsed() >= self.policy.stable_period {
+                // the service ran successfully before failing, so the failure is not consecutive to the previous ones
+                retries = 0;
+            }
+            if retries >= self.policy.max_retries {
+                warn!("supervised service {} failed {} times, giving up: {}", ident, retries + 1, err);
+                return Err(err);
+            }
+
+            let backoff = self.policy.backoff(retries);
+            warn!("supervised service {} failed, restarting in {:?}: {}", ident, backoff, err);
+            if tokio::time::timeout(backoff, self.shutdown_listener.clone()).await.is_ok() {
+                return Err(err);
+            }
+            retries += 1;
+            self.restarts.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+impl<T: AsyncService> AsyncService for SupervisedService<T> {
+    fn ident(self: Arc<Self>) -> &'static str {
+        self.service.clone().ident()
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(self.supervise())
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        self.shutdown_trigger.trigger();
+        self.service.clone().signal_exit();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        self.service.clone().stop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Service panicking on its first `panics` runs, each run lasting `duration`
+    struct FlakyService {
+        panics: usize,
+        duration: Duration,
+        runs: AtomicUsize,
+    }
+
+    impl FlakyService {
+        fn new(panics: usize) -> Self {
+            Self { panics, duration: Duration::ZERO, runs: AtomicUsize::new(0) }
+        }
+
+        fn with_duration(self, duration: Duration) -> Self {
+            Self { duration, ..self }
+        }
+    }
+
+    impl AsyncService for FlakyService {
+        fn ident(self: Arc<Self>) -> &'static str {
+            "flaky"
+        }
+
+        fn start(self: Arc<Self>) -> AsyncServiceFuture {
+            Box::pin(async move {
+                tokio::time::sleep(self.duration).await;
+                if self.runs.fetch_add(1, Ordering::SeqCst) < self.panics {
+                    panic!("flaky service panic");
+                }
+                Ok(())
+            })
+        }
+
+        fn signal_exit(self: Arc<Self>) {}
+
+        fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+            Box::pin(async move { Ok(()) })
+        }
+    }
+
+    #[test]
+    fn test_restart_policy_backoff() {
+        let policy = RestartPolicy::new(10, Duration::from_millis(100), Duration::from_secs(1));
+        let backoffs = (0..6).map(|retries| policy.backoff(retries).as_millis()).collect::<Vec<_>>();
+        assert_eq!(backoffs, vec![100, 200, 400, 800, 1000, 1000]);
+        assert_eq!(policy.backoff(usize::MAX), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_supervised_service_stabilizes() {
+        let service = Arc::new(FlakyService::new(3));
+        let policy = RestartPolicy::new(5, Duration::from_millis(1), Duration::from_millis(4));
+        let supervised = Arc::new(SupervisedService::new(service.clone(), policy));
+
+        assert!(supervised.clone().start().await.is_ok());
+        assert_eq!(service.runs.load(Ordering::SeqCst), 4);
+        assert_eq!(supervised.restarts(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_supervised_service_gives_up() {
+        let service = Arc::new(FlakyService::new(3));
+        let policy = RestartPolicy::new(2, Duration::from_millis(1), Duration::from_millis(4));
+        let supervised = Arc::new(SupervisedService::new(service.clone(), policy));
+
+        let err = supervised.clone().start().await.unwrap_err();
+        assert_eq!(err.to_string(), "service panicked: flaky service panic");
+        assert_eq!(service.runs.load(Ordering::SeqCst), 3);
+        assert_eq!(supervised.restarts(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_supervised_service_stable_runs_reset_retries() {
+        // every run lasts for the stable period, so the failures never add up to the retries limit
+        let service = Arc::new(FlakyService::new(4).with_duration(Duration::from_millis(20)));
+        let policy =
+            RestartPolicy::new(2, Duration::from_millis(1), Duration::from_millis(4)).with_stable_period(Duration::from_millis(10));
+        let supervised = Arc::new(SupervisedService::new(service.clone(), policy));
+
+        assert!(supervised.clone().start().await.is_ok());
+        assert_eq!(service.runs.load(Ordering::SeqCst), 5);
+        assert_eq!(supervised.restarts(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_supervised_service_exit_interrupts_backoff() {
+        let service = Arc::new(FlakyService::new(usize::MAX));
+        let policy = RestartPolicy::new(5, Duration::from_secs(60), Duration::from_secs(60));
+        let supervised = Arc::new(SupervisedService::new(service.clone(), policy));
+
+        let handle = tokio::spawn(supervised.clone().start());
+        while service.runs.load(Ordering::SeqCst) == 0 {
+            tokio::task::yield_now().await;
+        }
+        supervised.clone().signal_exit();
+        assert!(handle.await.unwrap().is_err());
+        assert_eq!(supervised.restarts(), 0);
+    }
+}
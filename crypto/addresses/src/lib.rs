@@ -32,6 +32,9 @@ pub enum AddressError {
     #[error("The address is invalid")]
     InvalidAddress,
 
+    #[error("The address prefix {1} does not match the expected network prefix {0}")]
+    PrefixMismatch(Prefix, Prefix),
+
     #[error("{0}")]
     WASM(String),
 }
@@ -335,6 +338,24 @@ impl TryFrom<&str> for Address {
     }
 }
 
+/// Parses a batch of address strings and validates that each address belongs to the
+/// network identified by `expected_prefix`. A result is produced for every input (in the
+/// same order), allowing callers to report all offending entries at once, e.g. before
+/// constructing a multi-output transaction.
+pub fn validate_addresses<S: AsRef<str>>(addresses: &[S], expected_prefix: Prefix) -> Vec<Result<Address, AddressError>> {
+    addresses
+        .iter()
+        .map(|address| {
+            let address = Address::try_from(address.as_ref())?;
+            if address.prefix != expected_prefix {
+                Err(AddressError::PrefixMismatch(expected_prefix, address.prefix))
+            } else {
+                Ok(address)
+            }
+        })
+        .collect()
+}
+
 impl Serialize for Address {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -622,6 +643,30 @@ mod tests {
         // cspell:enable
     }
 
+    #[test]
+    fn test_validate_addresses() {
+        // cspell:disable
+        let mainnet = "kaspa:qpauqsvk7yf9unexwmxsnmg547mhyga37csh0kj53q6xxgl24ydxjsgzthw5j";
+        let testnet = "kaspatest:qz7ulu4c25dh7fzec9zjyrmlhnkzrg4wmf89q7gzr3gfrsj3uz6xjceef60sd";
+        let bad_checksum = "kaspa:qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqkx9awp4l";
+        // cspell:enable
+        let inputs = [mainnet, testnet, "garbage", bad_checksum, ""];
+
+        let results = validate_addresses(&inputs, Prefix::Mainnet);
+        assert_eq!(results.len(), inputs.len());
+        assert_eq!(results[0], Ok(Address::try_from(mainnet).unwrap()));
+        assert_eq!(results[1], Err(AddressError::PrefixMismatch(Prefix::Mainnet, Prefix::Testnet)));
+        assert_eq!(results[2], Err(AddressError::MissingPrefix));
+        assert_eq!(results[3], Err(AddressError::BadChecksum));
+        assert_eq!(results[4], Err(AddressError::MissingPrefix));
+
+        let results = validate_addresses(&inputs, Prefix::Testnet);
+        assert_eq!(results[0], Err(AddressError::PrefixMismatch(Prefix::Testnet, Prefix::Mainnet)));
+        assert_eq!(results[1], Ok(Address::try_from(testnet).unwrap()));
+
+        assert!(validate_addresses::<&str>(&[], Prefix::Mainnet).is_empty());
+    }
+
     use js_sys::Object;
     use wasm_bindgen::{JsValue, __rt::IntoJsResult};
     use wasm_bindgen_test::wasm_bindgen_test;
@@ -0,0 +1,238 @@
+//!
+//! Domain separated hashing. A [`Domain`] names the use of a hash (transaction id,
+//! sighash, merkle branch, block hash, ...) and [`DomainHasher`] produces a hasher
+//! of the requested hash function configured with the domain tag, so that identical
+//! inputs hashed for different uses never produce the same digest.
+//!
+
+use crate::pow_hashers::keccak256;
+use crate::{Hash, HasherBase, HASH_SIZE};
+
+/// Tag of a hashing domain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Domain(&'static [u8]);
+
+impl Domain {
+    pub const TRANSACTION_HASH: Domain = Domain(b"TransactionHash");
+    pub const TRANSACTION_ID: Domain = Domain(b"TransactionID");
+    pub const TRANSACTION_SIGNING_HASH: Domain = Domain(b"TransactionSigningHash");
+    pub const TRANSACTION_SIGNING_HASH_ECDSA: Domain = Domain(b"TransactionSigningHashECDSA");
+    pub const BLOCK_HASH: Domain = Domain(b"BlockHash");
+    pub const PROOF_OF_WORK_HASH: Domain = Domain(b"ProofOfWorkHash");
+    pub const HEAVY_HASH: Domain = Domain(b"HeavyHash");
+    pub const MERKLE_BRANCH_HASH: Domain = Domain(b"MerkleBranchHash");
+    pub const MU_HASH_ELEMENT: Domain = Domain(b"MuHashElement");
+    pub const MU_HASH_FINALIZE: Domain = Domain(b"MuHashFinalize");
+    pub const PERSONAL_MESSAGE_SIGNING_HASH: Domain = Domain(b"PersonalMessageSigningHash");
+
+    /// Creates a domain with the given tag.
+    ///
+    /// # Panics
+    /// Panics if the tag is empty or longer than the 64 bytes accepted as a blake2b key.
+    pub const fn new(tag: &'static [u8]) -> Self {
+        assert!(!tag.is_empty() && tag.len() <= blake2b_simd::KEYBYTES, "domain tag must be 1 to 64 bytes long");
+        Self(tag)
+    }
+
+    #[inline(always)]
+    pub const fn tag(&self) -> &'static [u8] {
+        self.0
+    }
+}
+
+/// A 32 byte hasher separated by a [`Domain`].
+#[derive(Clone)]
+pub struct DomainHasher(Inner);
+
+#[derive(Clone)]
+enum Inner {
+    Blake2b(blake2b_simd::State),
+    Sha256(sha2::Sha256),
+    Keccak(CShake256),
+}
+
+impl DomainHasher {
+    /// Blake2b keyed with the domain tag.
+    pub fn blake2b(domain: Domain) -> Self {
+        Self(Inner::Blake2b(blake2b_simd::Params::new().hash_length(HASH_SIZE).key(domain.tag()).to_state()))
+    }
+
+    /// SHA256 prefixed with the SHA256 hash of the domain tag, since SHA256
+    /// doesn't natively support domain separation.
+    pub fn sha256(domain: Domain) -> Self {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(Sha256::digest(domain.tag()));
+        Self(Inner::Sha256(hasher))
+    }
+
+    /// cSHAKE256 with the domain tag as customization string.
+    pub fn keccak(domain: Domain) -> Self {
+        Self(Inner::Keccak(CShake256::new(domain.tag())))
+    }
+
+    pub fn write<A: AsRef<[u8]>>(&mut self, data: A) {
+        match &mut self.0 {
+            Inner::Blake2b(state) => {
+                state.update(data.as_ref());
+            }
+            Inner::Sha256(state) => sha2::Digest::update(state, data.as_ref()),
+            Inner::Keccak(state) => state.absorb(data.as_ref()),
+        }
+    }
+
+    pub fn finalize(self) -> Hash {
+        let mut out = [0u8; HASH_SIZE];
+        match self.0 {
+            Inner::Blake2b(state) => out.copy_from_slice(state.finalize().as_bytes()),
+            Inner::Sha256(state) => out.copy_from_slice(sha2::Digest::finalize(state).as_slice()),
+            Inner::Keccak(state) => out = state.finalize(),
+        }
+        Hash(out)
+    }
+
+    #[inline]
+    pub fn hash<A: AsRef<[u8]>>(mut self, data: A) -> Hash {
+        self.write(data);
+        self.finalize()
+    }
+}
+
+impl HasherBase for DomainHasher {
+    #[inline(always)]
+    fn update<A: AsRef<[u8]>>(&mut self, data: A) -> &mut Self {
+        self.write(data);
+        self
+    }
+}
+
+/// Minimal cSHAKE256 sponge (NIST SP 800-185) with a 32 byte output,
+/// built on the keccak permutation used by the proof of work hashers.
+#[derive(Clone)]
+struct CShake256 {
+    state: [u64; 25],
+    offset: usize,
+}
+
+impl CShake256 {
+    const RATE: usize = 136;
+
+    fn new(customization: &[u8]) -> Self {
+        let mut sponge = Self { state: [0; 25], offset: 0 };
+        // bytepad(encode_string(N) || encode_string(S), rate) with an empty function name N
+        sponge.absorb(&left_encode(Self::RATE as u64));
+        sponge.absorb(&left_encode(0));
+        sponge.absorb(&left_encode(customization.len() as u64 * 8));
+        sponge.absorb(customization);
+        if sponge.offset != 0 {
+            sponge.permute();
+        }
+        sponge
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        for byte in data {
+            self.state[self.offset / 8] ^= (*byte as u64) << (8 * (self.offset % 8));
+            self.offset += 1;
+            if self.offset == Self::RATE {
+                self.permute();
+            }
+        }
+    }
+
+    fn permute(&mut self) {
+        keccak256::f1600(&mut self.state);
+        self.offset = 0;
+    }
+
+    fn finalize(mut self) -> [u8; HASH_SIZE] {
+        self.state[self.offset / 8] ^= 0x04 << (8 * (self.offset % 8));
+        self.state[(Self::RATE - 1) / 8] ^= 0x80 << (8 * ((Self::RATE - 1) % 8));
+        keccak256::f1600(&mut self.state);
+        let mut out = [0u8; HASH_SIZE];
+        for (chunk, word) in out.chunks_exact_mut(8).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+}
+
+fn left_encode(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = bytes.iter().take_while(|byte| **byte == 0).count().min(7);
+    let mut encoded = vec![(8 - skip) as u8];
+    encoded.extend_from_slice(&bytes[skip..]);
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockHash, Hasher, KHeavyHash, MerkleBranchHash, TransactionID, TransactionSigningHash, TransactionSigningHashECDSA};
+    use sha3::digest::{ExtendableOutput, Update, XofReader};
+    use sha3::CShake256Core;
+
+    const DOMAINS: [Domain; 11] = [
+        Domain::TRANSACTION_HASH,
+        Domain::TRANSACTION_ID,
+        Domain::TRANSACTION_SIGNING_HASH,
+        Domain::TRANSACTION_SIGNING_HASH_ECDSA,
+        Domain::BLOCK_HASH,
+        Domain::PROOF_OF_WORK_HASH,
+        Domain::HEAVY_HASH,
+        Domain::MERKLE_BRANCH_HASH,
+        Domain::MU_HASH_ELEMENT,
+        Domain::MU_HASH_FINALIZE,
+        Domain::PERSONAL_MESSAGE_SIGNING_HASH,
+    ];
+
+    #[test]
+    fn test_domains_produce_distinct_digests() {
+        let data = [42u8; 64];
+        for constructor in [DomainHasher::blake2b, DomainHasher::sha256, DomainHasher::keccak] {
+            let digests = DOMAINS.iter().map(|domain| constructor(*domain).hash(data)).collect::<std::collections::HashSet<_>>();
+            assert_eq!(digests.len(), DOMAINS.len());
+        }
+
+        // the same domain hashed by different hash functions
+        let domain = Domain::new(b"Test");
+        let blake2b = DomainHasher::blake2b(domain).hash(data);
+        assert_ne!(blake2b, DomainHasher::sha256(domain).hash(data));
+        assert_ne!(blake2b, DomainHasher::keccak(domain).hash(data));
+    }
+
+    #[test]
+    fn test_domain_hashers_match_dedicated_hashers() {
+        let data = [5u8, 199, 126, 44, 71, 32, 82, 139];
+        assert_eq!(DomainHasher::blake2b(Domain::TRANSACTION_ID).hash(data), TransactionID::hash(data));
+        assert_eq!(DomainHasher::blake2b(Domain::TRANSACTION_SIGNING_HASH).hash(data), TransactionSigningHash::hash(data));
+        assert_eq!(DomainHasher::blake2b(Domain::BLOCK_HASH).hash(data), BlockHash::hash(data));
+        assert_eq!(DomainHasher::blake2b(Domain::MERKLE_BRANCH_HASH).hash(data), MerkleBranchHash::hash(data));
+        assert_eq!(DomainHasher::sha256(Domain::TRANSACTION_SIGNING_HASH_ECDSA).hash(data), TransactionSigningHashECDSA::hash(data));
+
+        let value = Hash([42; 32]);
+        assert_eq!(DomainHasher::keccak(Domain::HEAVY_HASH).hash(value), KHeavyHash::hash(value));
+    }
+
+    #[test]
+    fn test_keccak_matches_reference_cshake256() {
+        for len in [0, 1, 31, 135, 136, 137, 500] {
+            let data = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            for tag in [&b"ProofOfWorkHash"[..], &[7u8; 64][..], &[1u8; 130][..]] {
+                let hasher = sha3::CShake256::from_core(CShake256Core::new(tag)).chain(&data);
+                let mut expected = [0u8; HASH_SIZE];
+                hasher.finalize_xof().read(&mut expected);
+
+                let mut sponge = CShake256::new(tag);
+                sponge.absorb(&data);
+                assert_eq!(sponge.finalize(), expected, "tag length {}, data length {len}", tag.len());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_domain_tag() {
+        Domain::new(b"");
+    }
+}
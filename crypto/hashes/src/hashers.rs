@@ -1,4 +1,5 @@
 // use sha3::CShake256;
+use crate::Domain;
 use once_cell::sync::Lazy;
 
 pub trait HasherBase {
@@ -21,23 +22,23 @@ pub trait Hasher: HasherBase + Clone + Default {
 //  struct KHeavyHash => `cSHAKE256("HeavyHash")
 pub use crate::pow_hashers::{KHeavyHash, PowHash};
 blake2b_hasher! {
-    struct TransactionHash => b"TransactionHash",
-    struct TransactionID => b"TransactionID",
-    struct TransactionSigningHash => b"TransactionSigningHash",
-    struct BlockHash => b"BlockHash",
-    struct ProofOfWorkHash => b"ProofOfWorkHash",
-    struct MerkleBranchHash => b"MerkleBranchHash",
-    struct MuHashElementHash => b"MuHashElement",
-    struct MuHashFinalizeHash => b"MuHashFinalize",
-    struct PersonalMessageSigningHash => b"PersonalMessageSigningHash",
+    struct TransactionHash => Domain::TRANSACTION_HASH,
+    struct TransactionID => Domain::TRANSACTION_ID,
+    struct TransactionSigningHash => Domain::TRANSACTION_SIGNING_HASH,
+    struct BlockHash => Domain::BLOCK_HASH,
+    struct ProofOfWorkHash => Domain::PROOF_OF_WORK_HASH,
+    struct MerkleBranchHash => Domain::MERKLE_BRANCH_HASH,
+    struct MuHashElementHash => Domain::MU_HASH_ELEMENT,
+    struct MuHashFinalizeHash => Domain::MU_HASH_FINALIZE,
+    struct PersonalMessageSigningHash => Domain::PERSONAL_MESSAGE_SIGNING_HASH,
 }
 
 sha256_hasher! {
-    struct TransactionSigningHashECDSA => "TransactionSigningHashECDSA",
+    struct TransactionSigningHashECDSA => Domain::TRANSACTION_SIGNING_HASH_ECDSA,
 }
 
 macro_rules! sha256_hasher {
-    ($(struct $name:ident => $domain_sep:literal),+ $(,)? ) => {$(
+    ($(struct $name:ident => $domain:expr),+ $(,)? ) => {$(
         #[derive(Clone)]
         pub struct $name(sha2::Sha256);
 
@@ -50,7 +51,7 @@ macro_rules! sha256_hasher {
                 static HASHER: Lazy<$name> = Lazy::new(|| {
                     // SHA256 doesn't natively support domain separation, so we hash it to make it constant size.
                     let mut tmp_state = Sha256::new();
-                    tmp_state.update($domain_sep);
+                    tmp_state.update($domain.tag());
                     let mut out = Self(Sha256::new());
                     out.write(tmp_state.finalize());
 
@@ -75,7 +76,7 @@ macro_rules! sha256_hasher {
 }
 
 macro_rules! blake2b_hasher {
-    ($(struct $name:ident => $domain_sep:literal),+ $(,)? ) => {$(
+    ($(struct $name:ident => $domain:expr),+ $(,)? ) => {$(
         #[derive(Clone)]
         pub struct $name(blake2b_simd::State);
 
@@ -85,7 +86,7 @@ macro_rules! blake2b_hasher {
                 Self(
                     blake2b_simd::Params::new()
                         .hash_length(32)
-                        .key($domain_sep)
+                        .key($domain.tag())
                         .to_state(),
                 )
             }
@@ -1,3 +1,4 @@
+mod domain;
 mod hashers;
 mod pow_hashers;
 
@@ -18,6 +19,7 @@ use workflow_wasm::prelude::*;
 
 pub const HASH_SIZE: usize = 32;
 
+pub use domain::{Domain, DomainHasher};
 pub use hashers::*;
 
 // TODO: Check if we use hash more as an array of u64 or of bytes and change the default accordingly
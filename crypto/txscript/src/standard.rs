@@ -1,5 +1,5 @@
 use crate::{
-    opcodes::codes::{OpBlake2b, OpCheckSig, OpCheckSigECDSA, OpData32, OpData33, OpEqual},
+    opcodes::codes::{OpBlake2b, OpCheckSig, OpCheckSigECDSA, OpEqual},
     script_builder::{ScriptBuilder, ScriptBuilderResult},
     script_class::ScriptClass,
 };
@@ -7,33 +7,36 @@ use blake2b_simd::Params;
 use kaspa_addresses::{Address, Prefix, Version};
 use kaspa_consensus_core::tx::{ScriptPublicKey, ScriptVec};
 use kaspa_txscript_errors::TxScriptError;
-use smallvec::SmallVec;
-use std::iter::once;
 
 mod multisig;
 
 pub use multisig::{multisig_redeem_script, multisig_redeem_script_ecdsa, Error as MultisigCreateError};
 
+/// Builds a script of a standard form. The standard forms are well within
+/// the script size limits, so building them can not fail.
+fn standard_script(build: impl FnOnce(&mut ScriptBuilder) -> ScriptBuilderResult<&mut ScriptBuilder>) -> ScriptVec {
+    let mut builder = ScriptBuilder::new();
+    build(&mut builder).expect("standard script within the script size limits");
+    builder.drain().into()
+}
+
 /// Creates a new script to pay a transaction output to a 32-byte pubkey.
 fn pay_to_pub_key(address_payload: &[u8]) -> ScriptVec {
-    // TODO: use ScriptBuilder when add_op and add_data fns or equivalents are available
     assert_eq!(address_payload.len(), 32);
-    SmallVec::from_iter(once(OpData32).chain(address_payload.iter().copied()).chain(once(OpCheckSig)))
+    standard_script(|builder| builder.add_data(address_payload)?.add_op(OpCheckSig))
 }
 
 /// Creates a new script to pay a transaction output to a 33-byte ECDSA pubkey.
 fn pay_to_pub_key_ecdsa(address_payload: &[u8]) -> ScriptVec {
-    // TODO: use ScriptBuilder when add_op and add_data fns or equivalents are available
     assert_eq!(address_payload.len(), 33);
-    SmallVec::from_iter(once(OpData33).chain(address_payload.iter().copied()).chain(once(OpCheckSigECDSA)))
+    standard_script(|builder| builder.add_data(address_payload)?.add_op(OpCheckSigECDSA))
 }
 
 /// Creates a new script to pay a transaction output to a script hash.
 /// It is expected that the input is a valid hash.
 fn pay_to_script_hash(script_hash: &[u8]) -> ScriptVec {
-    // TODO: use ScriptBuilder when add_op and add_data fns or equivalents are available
     assert_eq!(script_hash.len(), 32);
-    SmallVec::from_iter([OpBlake2b, OpData32].iter().copied().chain(script_hash.iter().copied()).chain(once(OpEqual)))
+    standard_script(|builder| builder.add_op(OpBlake2b)?.add_data(script_hash)?.add_op(OpEqual))
 }
 
 /// Creates a new script to pay a transaction output to the specified address.
@@ -184,4 +187,22 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_standard_script_layouts() {
+        let script = |script_public_key: ScriptPublicKey| (script_public_key.version(), hex::encode(script_public_key.script()));
+
+        let address = Address::new(Prefix::Mainnet, Version::PubKey, &[0x11; 32]);
+        assert_eq!(script(pay_to_address_script(&address)), (0, format!("20{}ac", "11".repeat(32))));
+
+        let address = Address::new(Prefix::Testnet, Version::PubKeyECDSA, &[0x22; 33]);
+        assert_eq!(script(pay_to_address_script(&address)), (0, format!("21{}ab", "22".repeat(33))));
+
+        let address = Address::new(Prefix::Devnet, Version::ScriptHash, &[0x33; 32]);
+        assert_eq!(script(pay_to_address_script(&address)), (0, format!("aa20{}87", "33".repeat(32))));
+
+        let redeem_script_hash = Params::new().hash_length(32).to_state().update(&[OpCheckSig]).finalize();
+        let expected = format!("aa20{}87", hex::encode(redeem_script_hash.as_bytes()));
+        assert_eq!(script(pay_to_script_hash_script(&[OpCheckSig])), (0, expected));
+    }
 }
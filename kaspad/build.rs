@@ -0,0 +1,21 @@
+use std::{path::Path, process::Command};
+
+fn main() {
+    let git_commit = command_output("git", &["rev-parse", "HEAD"]).unwrap_or_else(|| "unknown".to_string());
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = command_output(&rustc, &["--version"]).unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=KASPAD_GIT_COMMIT={git_commit}");
+    println!("cargo:rustc-env=KASPAD_RUSTC_VERSION={rustc_version}");
+
+    // refresh the commit hash whenever HEAD moves, if building from a git checkout
+    for path in ["../.git/HEAD", "../.git/refs"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string()).filter(|output| !output.is_empty())
+}
@@ -1,6 +1,6 @@
-use clap::ArgAction;
 #[allow(unused)]
 use clap::{arg, command, Arg, Command};
+use clap::{error::ErrorKind, parser::ValueSource, ArgAction, ArgMatches};
 
 #[cfg(feature = "devnet-prealloc")]
 use kaspa_addresses::Address;
@@ -8,16 +8,18 @@ use kaspa_addresses::Address;
 use kaspa_consensus_core::tx::{TransactionOutpoint, UtxoEntry};
 #[cfg(feature = "devnet-prealloc")]
 use kaspa_txscript::pay_to_address_script;
-use std::ffi::OsString;
 #[cfg(feature = "devnet-prealloc")]
 use std::sync::Arc;
+use std::{collections::HashMap, ffi::OsString};
 
 use kaspa_consensus_core::{
-    config::Config,
+    config::{Config, DEFAULT_BAN_DURATION_SECS},
     network::{NetworkId, NetworkType},
 };
 
-use kaspa_core::kaspad_env::version;
+use crate::version::VersionInfo;
+use kaspa_core::{kaspad_env::version, log::LogFormat};
+use kaspa_hashes::Hash;
 
 use kaspa_utils::networking::ContextualNetAddress;
 use kaspa_wrpc_server::address::WrpcNetAddress;
@@ -25,6 +27,7 @@ use kaspa_wrpc_server::address::WrpcNetAddress;
 #[derive(Debug, Clone)]
 pub struct Args {
     // NOTE: it is best if property names match config file fields
+    pub config_file: Option<String>,
     pub appdir: Option<String>,
     pub logdir: Option<String>,
     pub no_log_files: bool,
@@ -34,6 +37,7 @@ pub struct Args {
     pub unsafe_rpc: bool,
     pub wrpc_verbose: bool,
     pub log_level: String,
+    pub log_format: LogFormat,
     pub async_threads: usize,
     pub connect_peers: Vec<ContextualNetAddress>,
     pub add_peers: Vec<ContextualNetAddress>,
@@ -44,6 +48,15 @@ pub struct Args {
     pub outbound_target: usize,
     pub inbound_limit: usize,
     pub rpc_max_clients: usize,
+    pub rpc_cert: Option<String>,
+    pub rpc_key: Option<String>,
+    pub rpc_client_ca: Option<String>,
+    pub rpc_auth_token: Option<String>,
+    pub rpc_write_auth_token: Option<String>,
+    pub rpc_read_auth_token: Option<String>,
+    pub export_snapshot: Option<String>,
+    pub import_snapshot: Option<String>,
+    pub import_snapshot_hash: Option<Hash>,
     pub enable_unsynced_mining: bool,
     pub enable_mainnet_mining: bool,
     pub testnet: bool,
@@ -69,11 +82,16 @@ pub struct Args {
     pub disable_dns_seeding: bool,
     pub disable_grpc: bool,
     pub ram_scale: f64,
+    pub p2p_rate_limit_scale: f64,
+    pub p2p_rate_limit_ban: bool,
+    pub ban_duration: u64,
+    pub min_protocol_version: Option<u32>,
 }
 
 impl Default for Args {
     fn default() -> Self {
         Self {
+            config_file: None,
             appdir: Some("datadir".into()),
             no_log_files: false,
             rpclisten_borsh: None,
@@ -85,6 +103,15 @@ impl Default for Args {
             outbound_target: 8,
             inbound_limit: 128,
             rpc_max_clients: 128,
+            rpc_cert: None,
+            rpc_key: None,
+            rpc_client_ca: None,
+            rpc_auth_token: None,
+            rpc_write_auth_token: None,
+            rpc_read_auth_token: None,
+            export_snapshot: None,
+            import_snapshot: None,
+            import_snapshot_hash: None,
             enable_unsynced_mining: false,
             enable_mainnet_mining: false,
             testnet: false,
@@ -97,6 +124,7 @@ impl Default for Args {
             rpclisten: None,
             wrpc_verbose: false,
             log_level: "INFO".into(),
+            log_format: LogFormat::default(),
             connect_peers: vec![],
             add_peers: vec![],
             listen: None,
@@ -118,6 +146,10 @@ impl Default for Args {
             disable_dns_seeding: false,
             disable_grpc: false,
             ram_scale: 1.0,
+            p2p_rate_limit_scale: 1.0,
+            p2p_rate_limit_ban: false,
+            ban_duration: DEFAULT_BAN_DURATION_SECS,
+            min_protocol_version: None,
         }
     }
 }
@@ -137,6 +169,10 @@ impl Args {
         config.p2p_listen_address = self.listen.unwrap_or(ContextualNetAddress::unspecified());
         config.externalip = self.externalip.map(|v| v.normalize(config.default_p2p_port()));
         config.ram_scale = self.ram_scale;
+        config.p2p_rate_limit_scale = self.p2p_rate_limit_scale;
+        config.p2p_rate_limit_ban = self.p2p_rate_limit_ban;
+        config.ban_duration = self.ban_duration;
+        config.p2p_min_protocol_version = self.min_protocol_version;
 
         #[cfg(feature = "devnet-prealloc")]
         if let Some(num_prealloc_utxos) = self.num_prealloc_utxos {
@@ -176,6 +212,31 @@ pub fn cli() -> Command {
     let cmd = Command::new("kaspad")
         .about(format!("{} (rusty-kaspa) v{}", env!("CARGO_PKG_DESCRIPTION"), version()))
         .version(env!("CARGO_PKG_VERSION"))
+        .disable_version_flag(true)
+        .after_help(format!(
+            "Every argument can also be set by the environment variable {ENV_PREFIX}<NAME>, where NAME is the uppercase long name of the argument with dashes replaced by underscores (ie. {ENV_PREFIX}RPCLISTEN, {ENV_PREFIX}LOGLEVEL). Repeatable arguments accept comma separated values.\nPrecedence: command line, then environment variables, then the configuration file, then the defaults."
+        ))
+        .arg(Arg::new("version").short('V').long("version").action(ArgAction::SetTrue).help("Print version information and exit."))
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .requires("version")
+                .help("Print the version information as JSON (used with --version)."),
+        )
+        .subcommand(
+            Command::new("version")
+                .about("Print version information and exit")
+                .arg(Arg::new("json").long("json").action(ArgAction::SetTrue).help("Print the version information as JSON.")),
+        )
+        .arg(
+            Arg::new("config")
+                .short('C')
+                .long("config")
+                .value_name("PATH")
+                .require_equals(true)
+                .help("Path of a TOML configuration file, whose keys are the long names of the arguments (ie. `utxoindex = true`). Arguments given on the command line take precedence over the file."),
+        )
         .arg(arg!(-b --appdir <DATA_DIR> "Directory to store data."))
         .arg(arg!(--logdir <LOG_DIR> "Directory to log output."))
         .arg(arg!(--nologfiles "Disable logging to files."))
@@ -197,6 +258,14 @@ pub fn cli() -> Command {
                 .require_equals(true)
                 .help("Logging level for all subsystems {off, error, warn, info, debug, trace}\n-- You may also specify <subsystem>=<level>,<subsystem2>=<level>,... to set the log level for individual subsystems.".to_string()),
         )
+        .arg(
+            Arg::new("logformat")
+                .long("logformat")
+                .value_name("FORMAT")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(LogFormat))
+                .help("Format of the log lines {text, json} (default: text)."),
+        )
         .arg(
             Arg::new("rpclisten")
                 .long("rpclisten")
@@ -278,6 +347,81 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(usize))
                 .help("Max number of RPC clients for standard connections (default: 128)."),
         )
+        .arg(
+            Arg::new("rpccert")
+                .long("rpccert")
+                .value_name("rpccert")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("PEM certificate file securing the gRPC server with TLS (requires --rpckey, default: plaintext)."),
+        )
+        .arg(
+            Arg::new("rpckey")
+                .long("rpckey")
+                .value_name("rpckey")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("PEM private key file of the gRPC server TLS certificate."),
+        )
+        .arg(
+            Arg::new("rpcclientca")
+                .long("rpcclientca")
+                .value_name("rpcclientca")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("PEM CA certificate file authenticating gRPC clients (mutual TLS, requires --rpccert)."),
+        )
+        .arg(
+            Arg::new("rpcauthtoken")
+                .long("rpcauthtoken")
+                .value_name("rpcauthtoken")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Token granting RPC clients full access when connecting (default: no authentication)."),
+        )
+        .arg(
+            Arg::new("rpcwriteauthtoken")
+                .long("rpcwriteauthtoken")
+                .value_name("rpcwriteauthtoken")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Token granting RPC clients read access and the submission of blocks and transactions when connecting (default: no authentication)."),
+        )
+        .arg(
+            Arg::new("rpcreadauthtoken")
+                .long("rpcreadauthtoken")
+                .value_name("rpcreadauthtoken")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Token granting RPC clients read-only access when connecting (default: no authentication)."),
+        )
+        .arg(
+            Arg::new("exportsnapshot")
+                .long("exportsnapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .conflicts_with("importsnapshot")
+                .help("Export a snapshot of the current pruning point and its UTXO set to FILE when starting."),
+        )
+        .arg(
+            Arg::new("importsnapshot")
+                .long("importsnapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .requires("importsnapshothash")
+                .help("Bootstrap a fresh node from the pruning point snapshot FILE, validated against --importsnapshothash."),
+        )
+        .arg(
+            Arg::new("importsnapshothash")
+                .long("importsnapshothash")
+                .value_name("HASH")
+                .require_equals(true)
+                .value_parser(|hash: &str| hash.parse::<Hash>().map_err(|err| err.to_string()))
+                .requires("importsnapshot")
+                .help("Hash of the pruning point of the imported snapshot, obtained from a trusted source."),
+        )
         .arg(arg!(--"reset-db" "Reset database before starting node. It's needed when switching between subnetworks."))
         .arg(arg!(--"enable-unsynced-mining" "Allow the node to accept blocks from RPC while not synced (this flag is mainly used for testing)"))
         .arg(
@@ -337,6 +481,28 @@ pub fn cli() -> Command {
                 .help("Apply a scale factor to memory allocation bounds. Nodes with limited RAM (~4-8GB) should set this to ~0.3-0.5 respectively. Nodes with 
 a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance especially for syncing peers faster"),
         )
+        .arg(
+            Arg::new("p2p-rate-limit-scale")
+                .long("p2p-rate-limit-scale")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Apply a scale factor to the rate limits of unsolicited P2P messages received from peers (default: 1.0). Set to 0 to disable rate limiting."),
+        )
+        .arg(arg!(--"p2p-rate-limit-ban" "Ban peers disconnected for exceeding the P2P message rate limits"))
+        .arg(
+            Arg::new("ban-duration")
+                .long("ban-duration")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Duration of peer bans in seconds, unless specified by the ban itself (default: 86400)."),
+        )
+        .arg(
+            Arg::new("min-protocol-version")
+                .long("min-protocol-version")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u32))
+                .help("Minimum P2P protocol version required from peers (default: the lowest version supported by the node)."),
+        )
         ;
 
     #[cfg(feature = "devnet-prealloc")]
@@ -348,7 +514,26 @@ a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance
     cmd
 }
 
+/// Returns `Some(json)` if the arguments request the version information, either
+/// with `--version [--json]` or with the `version [--json]` subcommand
+fn version_request(m: &ArgMatches) -> Option<bool> {
+    match m.subcommand() {
+        Some(("version", sub)) => Some(sub.get_flag("json")),
+        _ => m.get_flag("version").then(|| m.get_flag("json")),
+    }
+}
+
 pub fn parse_args() -> Args {
+    if let Some(json) = cli().try_get_matches_from(std::env::args_os()).ok().as_ref().and_then(version_request) {
+        let info = VersionInfo::default();
+        if json {
+            println!("{}", info.to_json());
+        } else {
+            println!("{info}");
+        }
+        std::process::exit(0);
+    }
+
     match Args::parse(std::env::args_os()) {
         Ok(args) => args,
         Err(err) => {
@@ -359,15 +544,48 @@ pub fn parse_args() -> Args {
 }
 
 impl Args {
+    /// Parses the arguments of `itr`, falling back to the environment variables of the process
+    /// (see [`Args::parse_with_env`]).
     pub fn parse<I, T>(itr: I) -> Result<Args, clap::Error>
     where
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
     {
-        let m: clap::ArgMatches = cli().try_get_matches_from(itr)?;
+        Self::parse_with_env(itr, |name| std::env::var(name).ok())
+    }
+
+    /// Parses the arguments of `itr`, reading the environment variables through `env`.
+    ///
+    /// The value of an argument is taken from, in order of precedence, the command line, the
+    /// `KASPAD_<NAME>` environment variable, the configuration file and finally the default.
+    pub fn parse_with_env<I, T, E>(itr: I, env: E) -> Result<Args, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+        E: Fn(&str) -> Option<String>,
+    {
+        let argv = itr.into_iter().map(Into::into).collect::<Vec<OsString>>();
+        // Lower precedence arguments are inserted before the command line ones, after the binary name
+        let matches_with = |args: &[String]| {
+            cli().try_get_matches_from(
+                argv.iter().take(1).cloned().chain(args.iter().map(OsString::from)).chain(argv.iter().skip(1).cloned()),
+            )
+        };
+
+        let mut env_args = env_args(&cli().try_get_matches_from(argv.clone())?, env)?;
+        let mut m: clap::ArgMatches = matches_with(&env_args)?;
+        let config_file = m.get_one::<String>("config").cloned();
+        if let Some(path) = config_file.as_ref() {
+            let content = std::fs::read_to_string(path)
+                .map_err(|err| cli().error(ErrorKind::Io, format!("cannot read the configuration file {path}: {err}")))?;
+            let mut args = config_file_args(&content, &m)?;
+            args.append(&mut env_args);
+            m = matches_with(&args)?;
+        }
         let defaults: Args = Default::default();
 
         let args = Args {
+            config_file,
             appdir: m.get_one::<String>("appdir").cloned(),
             logdir: m.get_one::<String>("logdir").cloned(),
             no_log_files: m.get_one::<bool>("nologfiles").cloned().unwrap_or(defaults.no_log_files),
@@ -377,6 +595,7 @@ impl Args {
             unsafe_rpc: m.get_one::<bool>("unsaferpc").cloned().unwrap_or(defaults.unsafe_rpc),
             wrpc_verbose: false,
             log_level: m.get_one::<String>("log_level").cloned().unwrap(),
+            log_format: m.get_one::<LogFormat>("logformat").cloned().unwrap_or(defaults.log_format),
             async_threads: m.get_one::<usize>("async_threads").cloned().unwrap_or(defaults.async_threads),
             connect_peers: m.get_many::<ContextualNetAddress>("connect-peers").unwrap_or_default().copied().collect(),
             add_peers: m.get_many::<ContextualNetAddress>("add-peers").unwrap_or_default().copied().collect(),
@@ -384,6 +603,15 @@ impl Args {
             outbound_target: m.get_one::<usize>("outpeers").cloned().unwrap_or(defaults.outbound_target),
             inbound_limit: m.get_one::<usize>("maxinpeers").cloned().unwrap_or(defaults.inbound_limit),
             rpc_max_clients: m.get_one::<usize>("rpcmaxclients").cloned().unwrap_or(defaults.rpc_max_clients),
+            rpc_cert: m.get_one::<String>("rpccert").cloned().or(defaults.rpc_cert),
+            rpc_key: m.get_one::<String>("rpckey").cloned().or(defaults.rpc_key),
+            rpc_client_ca: m.get_one::<String>("rpcclientca").cloned().or(defaults.rpc_client_ca),
+            rpc_auth_token: m.get_one::<String>("rpcauthtoken").cloned().or(defaults.rpc_auth_token),
+            rpc_write_auth_token: m.get_one::<String>("rpcwriteauthtoken").cloned().or(defaults.rpc_write_auth_token),
+            rpc_read_auth_token: m.get_one::<String>("rpcreadauthtoken").cloned().or(defaults.rpc_read_auth_token),
+            export_snapshot: m.get_one::<String>("exportsnapshot").cloned().or(defaults.export_snapshot),
+            import_snapshot: m.get_one::<String>("importsnapshot").cloned().or(defaults.import_snapshot),
+            import_snapshot_hash: m.get_one::<Hash>("importsnapshothash").cloned().or(defaults.import_snapshot_hash),
             reset_db: m.get_one::<bool>("reset-db").cloned().unwrap_or(defaults.reset_db),
             enable_unsynced_mining: m.get_one::<bool>("enable-unsynced-mining").cloned().unwrap_or(defaults.enable_unsynced_mining),
             enable_mainnet_mining: m.get_one::<bool>("enable-mainnet-mining").cloned().unwrap_or(defaults.enable_mainnet_mining),
@@ -408,6 +636,10 @@ impl Args {
             disable_dns_seeding: m.get_one::<bool>("nodnsseed").cloned().unwrap_or(defaults.disable_dns_seeding),
             disable_grpc: m.get_one::<bool>("nogrpc").cloned().unwrap_or(defaults.disable_grpc),
             ram_scale: m.get_one::<f64>("ram-scale").cloned().unwrap_or(defaults.ram_scale),
+            p2p_rate_limit_scale: m.get_one::<f64>("p2p-rate-limit-scale").cloned().unwrap_or(defaults.p2p_rate_limit_scale),
+            p2p_rate_limit_ban: m.get_one::<bool>("p2p-rate-limit-ban").cloned().unwrap_or(defaults.p2p_rate_limit_ban),
+            ban_duration: m.get_one::<u64>("ban-duration").cloned().unwrap_or(defaults.ban_duration),
+            min_protocol_version: m.get_one::<u32>("min-protocol-version").cloned().or(defaults.min_protocol_version),
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
@@ -420,6 +652,96 @@ impl Args {
     }
 }
 
+/// Arguments which are not read from the environment nor from the configuration file
+const COMMAND_LINE_ONLY_ARGS: &[&str] = &["version", "json"];
+
+/// Prefix of the environment variables of the arguments
+pub const ENV_PREFIX: &str = "KASPAD_";
+
+/// Returns the name of the environment variable of the argument with long name `long`, ie. `KASPAD_RAM_SCALE` for `ram-scale`
+pub fn env_var_name(long: &str) -> String {
+    format!("{ENV_PREFIX}{}", long.to_uppercase().replace('-', "_"))
+}
+
+/// Converts the environment variables of the arguments absent from `cli_matches` into command line arguments.
+/// Flags accept `true`/`false` (or `1`/`0`) and repeatable arguments accept comma separated values.
+fn env_args<E: Fn(&str) -> Option<String>>(cli_matches: &ArgMatches, env: E) -> Result<Vec<String>, clap::Error> {
+    let mut cmd = cli();
+    let arguments = cmd
+        .get_arguments()
+        .filter(|arg| !COMMAND_LINE_ONLY_ARGS.contains(&arg.get_id().as_str()))
+        .filter(|arg| cli_matches.value_source(arg.get_id().as_str()) != Some(ValueSource::CommandLine))
+        .filter_map(|arg| arg.get_long().map(|long| (long.to_string(), arg.get_action().clone())))
+        .collect::<Vec<_>>();
+
+    let mut args = vec![];
+    for (long, action) in arguments {
+        let name = env_var_name(&long);
+        let Some(value) = env(&name) else {
+            continue;
+        };
+        if !action.takes_values() {
+            match value.trim().to_lowercase().as_str() {
+                "true" | "1" | "" => args.push(format!("--{long}")),
+                "false" | "0" => {}
+                _ => return Err(cmd.error(ErrorKind::InvalidValue, format!("invalid boolean value of {name}: {value}"))),
+            }
+        } else if matches!(action, ArgAction::Append) {
+            args.extend(value.split(',').map(str::trim).filter(|value| !value.is_empty()).map(|value| format!("--{long}={value}")));
+        } else {
+            args.push(format!("--{long}={value}"));
+        }
+    }
+    Ok(args)
+}
+
+/// Converts the content of a TOML configuration file into command line arguments. Keys are the long names of
+/// the arguments and arrays stand for repeated arguments (ie. `addpeer = ["1.2.3.4", "5.6.7.8"]`).
+///
+/// Keys of arguments present in `cli_matches` are skipped, so the command line takes precedence over the file.
+/// Unknown keys are reported as an error.
+fn config_file_args(content: &str, cli_matches: &ArgMatches) -> Result<Vec<String>, clap::Error> {
+    let mut cmd = cli();
+    let table = toml::from_str::<toml::value::Table>(content)
+        .map_err(|err| cmd.error(ErrorKind::InvalidValue, format!("invalid configuration file: {err}")))?;
+
+    // Map the long name of every argument to its id and whether it takes values
+    let arguments = cmd
+        .get_arguments()
+        .filter(|arg| arg.get_id() != "config" && !COMMAND_LINE_ONLY_ARGS.contains(&arg.get_id().as_str()))
+        .filter_map(|arg| arg.get_long().map(|long| (long.to_string(), (arg.get_id().to_string(), arg.get_action().takes_values()))))
+        .collect::<HashMap<_, _>>();
+
+    let unknown_keys = table.keys().filter(|key| !arguments.contains_key(key.as_str())).cloned().collect::<Vec<_>>();
+    if !unknown_keys.is_empty() {
+        return Err(cmd.error(ErrorKind::UnknownArgument, format!("unknown configuration file keys: {}", unknown_keys.join(", "))));
+    }
+
+    let mut args = vec![];
+    for (key, value) in table {
+        let (id, takes_values) = &arguments[&key];
+        if cli_matches.value_source(id) == Some(ValueSource::CommandLine) {
+            continue;
+        }
+        let values = match value {
+            toml::Value::Array(values) => values,
+            value => vec![value],
+        };
+        for value in values {
+            match value {
+                toml::Value::Boolean(true) if !takes_values => args.push(format!("--{key}")),
+                toml::Value::Boolean(false) if !takes_values => {}
+                toml::Value::String(value) => args.push(format!("--{key}={value}")),
+                toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => args.push(format!("--{key}={value}")),
+                value => {
+                    return Err(cmd.error(ErrorKind::InvalidValue, format!("invalid value of configuration file key {key}: {value}")));
+                }
+            }
+        }
+    }
+    Ok(args)
+}
+
 /*
 
   -V, --version                             Display version information and exit
@@ -503,3 +825,154 @@ impl Args {
   -s, --service=                            Service command {install, remove, start, stop}
       --nogrpc                              Don't initialize the gRPC server
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn config_file(content: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_config_file_precedence() {
+        let file = config_file(
+            r#"
+            outpeers = 16
+            utxoindex = true
+            ram-scale = 0.5
+            addpeer = ["10.0.0.1:16111", "10.0.0.2"]
+            "#,
+        );
+        let config = format!("--config={}", file.path().display());
+        let args = Args::parse(["kaspad", config.as_str(), "--outpeers=32"]).unwrap();
+
+        // the command line overrides the file, which overrides the defaults
+        assert_eq!(args.outbound_target, 32);
+        assert!(args.utxoindex);
+        assert_eq!(args.ram_scale, 0.5);
+        assert_eq!(args.add_peers.len(), 2);
+        assert_eq!(args.inbound_limit, Args::default().inbound_limit);
+        assert_eq!(args.config_file, Some(file.path().display().to_string()));
+    }
+
+    #[test]
+    fn test_env_fallback() {
+        let env = HashMap::from([
+            ("KASPAD_RPCLISTEN", "0.0.0.0:16110"),
+            ("KASPAD_LOGLEVEL", "debug"),
+            ("KASPAD_UTXOINDEX", "true"),
+            ("KASPAD_TESTNET", "0"),
+            ("KASPAD_ADDPEER", "10.0.0.1, 10.0.0.2:16111"),
+            ("KASPAD_OUTPEERS", "16"),
+            ("OUTPEERS", "64"),
+        ]);
+        let env = |name: &str| env.get(name).map(|value| value.to_string());
+
+        let args = Args::parse_with_env(["kaspad"], env).unwrap();
+        assert_eq!(args.rpclisten.unwrap().to_string(), "0.0.0.0:16110");
+        assert_eq!(args.log_level, "debug");
+        assert!(args.utxoindex);
+        assert!(!args.testnet);
+        assert_eq!(args.add_peers.len(), 2);
+        assert_eq!(args.outbound_target, 16);
+        assert_eq!(args.inbound_limit, Args::default().inbound_limit);
+
+        // the command line overrides the environment, which overrides the configuration file
+        let file = config_file("outpeers = 8\nloglevel = \"warn\"\nmaxinpeers = 64\n");
+        let config = format!("--config={}", file.path().display());
+        let args = Args::parse_with_env(["kaspad", config.as_str(), "--loglevel=trace", "--addpeer=10.0.0.3"], env).unwrap();
+        assert_eq!(args.log_level, "trace");
+        assert_eq!(args.add_peers.len(), 1);
+        assert_eq!(args.outbound_target, 16);
+        assert_eq!(args.inbound_limit, 64);
+
+        // the configuration file can be set by the environment as well
+        let env = |name: &str| (name == "KASPAD_CONFIG").then(|| file.path().display().to_string());
+        assert_eq!(Args::parse_with_env(["kaspad"], env).unwrap().outbound_target, 8);
+
+        let err = Args::parse_with_env(["kaspad"], |name| (name == "KASPAD_UTXOINDEX").then(|| "maybe".to_string())).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn test_version_request() {
+        let request = |argv: &[&str]| version_request(&cli().try_get_matches_from(argv.iter().copied()).unwrap());
+        assert_eq!(request(&["kaspad"]), None);
+        assert_eq!(request(&["kaspad", "--version"]), Some(false));
+        assert_eq!(request(&["kaspad", "-V", "--json"]), Some(true));
+        assert_eq!(request(&["kaspad", "version", "--json"]), Some(true));
+        assert!(cli().try_get_matches_from(["kaspad", "--json"]).is_err());
+        // the version flag is not read from the environment
+        assert!(Args::parse_with_env(["kaspad"], |name| (name == "KASPAD_VERSION").then(|| "0.13.4".to_string())).is_ok());
+    }
+
+    #[test]
+    fn test_rpc_tls_args() {
+        let args = Args::parse(["kaspad"]).unwrap();
+        assert_eq!((args.rpc_cert, args.rpc_key, args.rpc_client_ca), (None, None, None));
+
+        let file = config_file("rpckey = \"rpc.key\"\n");
+        let config = format!("--config={}", file.path().display());
+        let args = Args::parse(["kaspad", config.as_str(), "--rpccert=rpc.cert", "--rpcclientca=ca.cert"]).unwrap();
+        assert_eq!(args.rpc_cert.as_deref(), Some("rpc.cert"));
+        assert_eq!(args.rpc_key.as_deref(), Some("rpc.key"));
+        assert_eq!(args.rpc_client_ca.as_deref(), Some("ca.cert"));
+    }
+
+    #[test]
+    fn test_log_format_args() {
+        assert_eq!(Args::parse(["kaspad"]).unwrap().log_format, LogFormat::Text);
+        assert_eq!(Args::parse(["kaspad", "--logformat=json"]).unwrap().log_format, LogFormat::Json);
+        let args = Args::parse_with_env(["kaspad"], |name| (name == "KASPAD_LOGFORMAT").then(|| "json".to_string())).unwrap();
+        assert_eq!(args.log_format, LogFormat::Json);
+        assert!(Args::parse(["kaspad", "--logformat=xml"]).is_err());
+    }
+
+    #[test]
+    fn test_rpc_auth_token_args() {
+        assert_eq!(Args::parse(["kaspad"]).unwrap().rpc_auth_token, None);
+        let args = Args::parse(["kaspad", "--rpcauthtoken=secret"]).unwrap();
+        assert_eq!(args.rpc_auth_token.as_deref(), Some("secret"));
+        let args = Args::parse_with_env(["kaspad"], |name| (name == "KASPAD_RPCAUTHTOKEN").then(|| "from-env".to_string())).unwrap();
+        assert_eq!(args.rpc_auth_token.as_deref(), Some("from-env"));
+
+        let args = Args::parse(["kaspad", "--rpcauthtoken=secret", "--rpcwriteauthtoken=miner", "--rpcreadauthtoken=public"]).unwrap();
+        assert_eq!(
+            (args.rpc_auth_token.as_deref(), args.rpc_write_auth_token.as_deref(), args.rpc_read_auth_token.as_deref()),
+            (Some("secret"), Some("miner"), Some("public"))
+        );
+    }
+
+    #[test]
+    fn test_snapshot_args() {
+        let args = Args::parse(["kaspad"]).unwrap();
+        assert_eq!((args.export_snapshot, args.import_snapshot, args.import_snapshot_hash), (None, None, None));
+        let args = Args::parse(["kaspad", "--exportsnapshot=pp.snapshot"]).unwrap();
+        assert_eq!(args.export_snapshot.as_deref(), Some("pp.snapshot"));
+
+        let hash = "8a5fd9e7d5ba4e3c19bfb1a9be0d4b3a2a8e3b7f7a0f0c9b0d2b5d9e0b5b9a31";
+        let import = format!("--importsnapshothash={hash}");
+        let args = Args::parse(["kaspad", "--importsnapshot=pp.snapshot", import.as_str()]).unwrap();
+        assert_eq!(args.import_snapshot.as_deref(), Some("pp.snapshot"));
+        assert_eq!(args.import_snapshot_hash, Some(hash.parse().unwrap()));
+
+        // The pruning point hash is required and must be valid
+        assert!(Args::parse(["kaspad", "--importsnapshot=pp.snapshot"]).is_err());
+        assert!(Args::parse(["kaspad", "--importsnapshot=pp.snapshot", "--importsnapshothash=xyz"]).is_err());
+        assert!(Args::parse(["kaspad", "--exportsnapshot=a", "--importsnapshot=b", import.as_str()]).is_err());
+    }
+
+    #[test]
+    fn test_config_file_unknown_keys() {
+        let file = config_file("utxoindex = true\nmaxpeers = 8\nrpc-listen = \"0.0.0.0\"\n");
+        let config = format!("--config={}", file.path().display());
+        let err = Args::parse(["kaspad", config.as_str()]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnknownArgument);
+        let message = err.to_string();
+        assert!(message.contains("maxpeers") && message.contains("rpc-listen"), "{message}");
+    }
+}
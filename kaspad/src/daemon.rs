@@ -9,7 +9,8 @@ use kaspa_consensus_notify::{root::ConsensusNotificationRoot, service::NotifySer
 use kaspa_core::{core::Core, info, trace};
 use kaspa_core::{kaspad_env::version, task::tick::TickService};
 use kaspa_database::prelude::CachePolicy;
-use kaspa_grpc_server::service::GrpcService;
+use kaspa_grpc_server::{service::GrpcService, tls::GrpcTlsConfig};
+use kaspa_rpc_core::api::auth::RpcAuthTokens;
 use kaspa_rpc_service::service::RpcCoreService;
 use kaspa_txscript::caches::TxScriptCacheCounters;
 use kaspa_utils::networking::ContextualNetAddress;
@@ -28,7 +29,11 @@ use kaspa_mining::{
     monitor::MiningMonitor,
     MiningCounters,
 };
-use kaspa_p2p_flows::{flow_context::FlowContext, service::P2pService};
+use kaspa_p2p_flows::{
+    flow_context::{FlowContext, PROTOCOL_VERSION},
+    service::P2pService,
+    snapshot::SnapshotOperation,
+};
 
 use kaspa_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
 use kaspa_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
@@ -91,6 +96,18 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.ram_scale > 10.0 {
         return Err(ConfigError::RamScaleTooHigh);
     }
+    if args.p2p_rate_limit_scale < 0.0 {
+        return Err(ConfigError::NegativeP2pRateLimitScale);
+    }
+    if args.min_protocol_version.is_some_and(|version| version > PROTOCOL_VERSION) {
+        return Err(ConfigError::MinProtocolVersionTooHigh(PROTOCOL_VERSION));
+    }
+    if args.rpc_cert.is_some() != args.rpc_key.is_some() {
+        return Err(ConfigError::MissingRpcCertOrKey);
+    }
+    if args.rpc_client_ca.is_some() && args.rpc_cert.is_none() {
+        return Err(ConfigError::RpcClientCaWithoutCert);
+    }
     Ok(())
 }
 
@@ -152,6 +169,15 @@ pub fn get_log_dir(args: &Args) -> Option<String> {
     log_dir
 }
 
+/// Get the pruning point snapshot operation requested by the supplied [`Args`].
+fn snapshot_operation(args: &Args) -> Option<SnapshotOperation> {
+    match (&args.export_snapshot, &args.import_snapshot, args.import_snapshot_hash) {
+        (Some(path), _, _) => Some(SnapshotOperation::Export(PathBuf::from(path))),
+        (None, Some(path), Some(hash)) => Some(SnapshotOperation::Import(PathBuf::from(path), hash)),
+        _ => None,
+    }
+}
+
 impl Runtime {
     pub fn from_args(args: &Args) -> Self {
         // Configure the panic behavior
@@ -160,7 +186,7 @@ impl Runtime {
         let log_dir = get_log_dir(args);
 
         // Initialize the logger
-        kaspa_core::log::init_logger(log_dir.as_deref(), &args.log_level);
+        kaspa_core::log::init_logger_with_format(log_dir.as_deref(), &args.log_level, args.log_format);
 
         Self { log_dir: log_dir.map(|log_dir| log_dir.to_owned()) }
     }
@@ -222,7 +248,7 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     let db_dir = app_dir.join(network.to_prefixed()).join(DEFAULT_DATA_DIR);
 
     // Print package name and version
-    info!("{} v{}", env!("CARGO_PKG_NAME"), version());
+    info!("{} v{} (commit {})", env!("CARGO_PKG_NAME"), version(), env!("KASPAD_GIT_COMMIT"));
 
     assert!(!db_dir.to_str().unwrap().is_empty());
     info!("Application directory: {}", app_dir.display());
@@ -235,6 +261,10 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
             info!("Logs to console only");
         }
     }
+    if let Some(config_file) = args.config_file.as_ref() {
+        info!("Configuration file: {}", config_file);
+    }
+    info!("Effective arguments: {:?}", args);
 
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
@@ -378,7 +408,10 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         fd_remaining,
     ));
     let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
-    let consensus_monitor = Arc::new(ConsensusMonitor::new(processing_counters.clone(), tick_service.clone()));
+    let consensus_monitor = Arc::new(
+        ConsensusMonitor::new(processing_counters.clone(), tick_service.clone())
+            .with_sync_source(consensus_manager.clone(), config.target_time_per_block),
+    );
 
     let perf_monitor_builder = PerfMonitorBuilder::new()
         .with_fetch_interval(Duration::from_secs(args.perf_metrics_interval_sec))
@@ -440,8 +473,12 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         dns_seeders,
         config.default_p2p_port(),
         p2p_tower_counters.clone(),
+        snapshot_operation(args),
     ));
 
+    // Create an async runtime
+    let async_runtime = Arc::new(AsyncRuntime::new(args.async_threads));
+
     let rpc_core_service = Arc::new(RpcCoreService::new(
         consensus_manager.clone(),
         notify_service.notifier(),
@@ -451,6 +488,7 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         index_service.as_ref().map(|x| x.utxoindex().unwrap()),
         config.clone(),
         core.clone(),
+        async_runtime.clone(),
         processing_counters,
         wrpc_borsh_counters.clone(),
         wrpc_json_counters.clone(),
@@ -459,8 +497,7 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         grpc_tower_counters.clone(),
     ));
 
-    // Create an async runtime and register the top-level async services
-    let async_runtime = Arc::new(AsyncRuntime::new(args.async_threads));
+    // Register the top-level async services
     async_runtime.register(tick_service);
     async_runtime.register(notify_service);
     if let Some(index_service) = index_service {
@@ -470,9 +507,26 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         async_runtime.register(Arc::new(port_mapping_extender_svc))
     };
     async_runtime.register(rpc_core_service.clone());
+    let rpc_auth_tokens =
+        RpcAuthTokens::new(args.rpc_auth_token.clone(), args.rpc_write_auth_token.clone(), args.rpc_read_auth_token.clone());
     if !args.disable_grpc {
-        let grpc_service =
-            Arc::new(GrpcService::new(grpc_server_addr, config, rpc_core_service.clone(), args.rpc_max_clients, grpc_tower_counters));
+        // The gRPC server falls back to plaintext unless a TLS certificate is configured
+        let grpc_tls_config = args.rpc_cert.as_ref().zip(args.rpc_key.as_ref()).map(|(cert, key)| {
+            let tls_config = GrpcTlsConfig::new(cert.into(), key.into()).with_client_ca(args.rpc_client_ca.as_ref().map(Into::into));
+            tls_config.load().unwrap_or_else(|err| {
+                println!("{}", err);
+                exit(1);
+            })
+        });
+        let grpc_service = Arc::new(GrpcService::new(
+            grpc_server_addr,
+            config,
+            rpc_core_service.clone(),
+            args.rpc_max_clients,
+            grpc_tower_counters,
+            grpc_tls_config,
+            rpc_auth_tokens.clone(),
+        ));
         async_runtime.register(grpc_service);
     }
     async_runtime.register(p2p_service);
@@ -496,6 +550,7 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
                 WrpcServerOptions {
                     listen_address: listen_address.to_address(&network.network_type, &encoding).to_string(), // TODO: use a normalized ContextualNetAddress instead of a String
                     verbose: args.wrpc_verbose,
+                    auth_tokens: rpc_auth_tokens.clone(),
                     ..WrpcServerOptions::default()
                 },
             ))
@@ -0,0 +1,65 @@
+use kaspa_core::kaspad_env::version;
+use kaspa_p2p_flows::flow_context::{MIN_PROTOCOL_VERSION, PROTOCOL_VERSION};
+use serde::Serialize;
+use std::fmt::Display;
+
+/// Build information of the node, printed by `kaspad --version`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionInfo {
+    pub version: &'static str,
+    /// Hash of the git commit the node was built from, `unknown` if built outside of a git checkout
+    pub git_commit: &'static str,
+    pub rustc_version: &'static str,
+    /// Enabled cargo features of kaspad
+    pub features: Vec<&'static str>,
+    pub protocol_version: u32,
+    pub min_protocol_version: u32,
+}
+
+impl Default for VersionInfo {
+    fn default() -> Self {
+        let features = [("heap", cfg!(feature = "heap")), ("devnet-prealloc", cfg!(feature = "devnet-prealloc"))];
+        Self {
+            version: version(),
+            git_commit: env!("KASPAD_GIT_COMMIT"),
+            rustc_version: env!("KASPAD_RUSTC_VERSION"),
+            features: features.into_iter().filter_map(|(name, enabled)| enabled.then_some(name)).collect(),
+            protocol_version: PROTOCOL_VERSION,
+            min_protocol_version: MIN_PROTOCOL_VERSION,
+        }
+    }
+}
+
+impl VersionInfo {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+impl Display for VersionInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "kaspad v{}", self.version)?;
+        writeln!(f, "git commit: {}", self.git_commit)?;
+        writeln!(f, "rustc: {}", self.rustc_version)?;
+        let features = if self.features.is_empty() { "none".to_string() } else { self.features.join(", ") };
+        writeln!(f, "features: {features}")?;
+        write!(f, "p2p protocol version: {} (minimum {})", self.protocol_version, self.min_protocol_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_json() {
+        let json = serde_json::from_str::<serde_json::Value>(&VersionInfo::default().to_json()).unwrap();
+        for key in ["version", "gitCommit", "rustcVersion", "features", "protocolVersion", "minProtocolVersion"] {
+            assert!(json.get(key).is_some(), "missing key {key}");
+        }
+        assert_eq!(json["version"], version());
+        assert_eq!(json["protocolVersion"], PROTOCOL_VERSION);
+        assert!(json["features"].is_array());
+    }
+}
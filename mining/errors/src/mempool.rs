@@ -1,6 +1,7 @@
 use kaspa_consensus_core::{
     errors::tx::TxRuleError,
-    tx::{TransactionId, TransactionOutpoint},
+    standard::StandardnessError,
+    tx::{ArithmeticOverflow, TransactionId, TransactionOutpoint},
 };
 use thiserror::Error;
 
@@ -33,7 +34,7 @@ pub enum RuleError {
 
     /// An error emitted by mining\src\mempool\check_transaction_standard.rs
     #[error("transaction {0} is not standard: {1}")]
-    RejectNonStandard(TransactionId, String),
+    RejectNonStandard(TransactionId, NonStandardError),
 
     #[error("one of the transaction inputs spends an immature UTXO: {0}")]
     RejectImmatureSpend(TxRuleError),
@@ -79,7 +80,7 @@ pub enum RuleError {
 
 impl From<NonStandardError> for RuleError {
     fn from(item: NonStandardError) -> Self {
-        RuleError::RejectNonStandard(*item.transaction_id(), item.to_string())
+        RuleError::RejectNonStandard(*item.transaction_id(), item)
     }
 }
 
@@ -126,9 +127,29 @@ pub enum NonStandardError {
 
     #[error("transaction input #{1} has {2} signature operations which is more than the allowed max amount of {3}")]
     RejectSignatureCount(TransactionId, usize, u8, u8),
+
+    #[error("transaction amounts overflow: {1}")]
+    RejectArithmeticOverflow(TransactionId, ArithmeticOverflow),
 }
 
 impl NonStandardError {
+    /// Attaches the transaction id to a standardness error of [`kaspa_consensus_core::standard`]
+    pub fn new(transaction_id: TransactionId, err: StandardnessError) -> Self {
+        match err {
+            StandardnessError::Version(version, min, max) => Self::RejectVersion(transaction_id, version, min, max),
+            StandardnessError::Mass(mass, max) => Self::RejectMass(transaction_id, mass, max),
+            StandardnessError::ContextualMass(mass, max) => Self::RejectContextualMass(transaction_id, mass, max),
+            StandardnessError::SignatureScriptSize(i, size, max) => Self::RejectSignatureScriptSize(transaction_id, i, size, max),
+            StandardnessError::ScriptPublicKeyVersion(i) => Self::RejectScriptPublicKeyVersion(transaction_id, i),
+            StandardnessError::OutputScriptClass(i) => Self::RejectOutputScriptClass(transaction_id, i),
+            StandardnessError::Dust(i, value) => Self::RejectDust(transaction_id, i, value),
+            StandardnessError::InputScriptClass(i) => Self::RejectInputScriptClass(transaction_id, i),
+            StandardnessError::SignatureCount(i, count, max) => Self::RejectSignatureCount(transaction_id, i, count, max),
+            StandardnessError::InsufficientFee(fee, required) => Self::RejectInsufficientFee(transaction_id, fee, required),
+            StandardnessError::ArithmeticOverflow(err) => Self::RejectArithmeticOverflow(transaction_id, err),
+        }
+    }
+
     pub fn transaction_id(&self) -> &TransactionId {
         match self {
             NonStandardError::RejectVersion(id, _, _, _) => id,
@@ -141,6 +162,7 @@ impl NonStandardError {
             NonStandardError::RejectInputScriptClass(id, _) => id,
             NonStandardError::RejectInsufficientFee(id, _, _) => id,
             NonStandardError::RejectSignatureCount(id, _, _, _) => id,
+            NonStandardError::RejectArithmeticOverflow(id, _) => id,
         }
     }
 }
@@ -13,6 +13,7 @@ use crate::{
     },
     model::{
         candidate_tx::CandidateTransaction,
+        fee_rate::FeeRateEstimate,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         topological_sort::IntoIterTopologically,
         tx_query::TransactionQuery,
@@ -22,7 +23,7 @@ use crate::{
 use itertools::Itertools;
 use kaspa_consensus_core::{
     api::ConsensusApi,
-    block::{BlockTemplate, TemplateBuildMode},
+    block::{BlockTemplate, TemplateBuildMode, VirtualStateApproxId},
     coinbase::MinerData,
     errors::{block::RuleError as BlockRuleError, tx::TxRuleError},
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutput},
@@ -30,7 +31,7 @@ use kaspa_consensus_core::{
 use kaspa_consensusmanager::{spawn_blocking, ConsensusProxy};
 use kaspa_core::{debug, error, info, time::Stopwatch, warn};
 use kaspa_mining_errors::{manager::MiningManagerError, mempool::RuleError};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
 use tokio::sync::mpsc::UnboundedSender;
 
@@ -39,6 +40,9 @@ pub struct MiningManager {
     block_template_cache: BlockTemplateCache,
     mempool: RwLock<Mempool>,
     counters: Arc<MiningCounters>,
+
+    /// The last fee rate estimate along with the virtual state it was computed for
+    fee_rate_estimate: Mutex<Option<(VirtualStateApproxId, FeeRateEstimate)>>,
 }
 
 impl MiningManager {
@@ -70,7 +74,7 @@ impl MiningManager {
         let config = Arc::new(config);
         let mempool = RwLock::new(Mempool::new(config.clone(), counters.clone()));
         let block_template_cache = BlockTemplateCache::new(cache_lifetime);
-        Self { config, block_template_cache, mempool, counters }
+        Self { config, block_template_cache, mempool, counters, fee_rate_estimate: Mutex::new(None) }
     }
 
     pub fn get_block_template(&self, consensus: &dyn ConsensusApi, miner_data: &MinerData) -> MiningManagerResult<BlockTemplate> {
@@ -481,6 +485,23 @@ impl MiningManager {
         self.mempool.read().transaction_count(query)
     }
 
+    /// Returns the fee rates, in sompi per gram of mass, estimated from the current content of the mempool.
+    ///
+    /// The estimate is computed at most once per virtual state, so transactions entering
+    /// the mempool in between are only accounted for after the next virtual state update.
+    pub fn estimate_fee_rates(&self, consensus: &dyn ConsensusApi) -> FeeRateEstimate {
+        let virtual_state_approx_id = consensus.get_virtual_state_approx_id();
+        let mut fee_rate_estimate = self.fee_rate_estimate.lock();
+        match fee_rate_estimate.as_ref() {
+            Some((id, estimate)) if *id == virtual_state_approx_id => *estimate,
+            _ => {
+                let estimate = self.mempool.read().estimate_fee_rates();
+                fee_rate_estimate.replace((virtual_state_approx_id, estimate));
+                estimate
+            }
+        }
+    }
+
     pub fn handle_new_block_transactions(
         &self,
         consensus: &dyn ConsensusApi,
@@ -828,6 +849,10 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.transaction_count(query)).await.unwrap()
     }
 
+    pub async fn estimate_fee_rates(self, consensus: &ConsensusProxy) -> FeeRateEstimate {
+        consensus.clone().spawn_blocking(move |c| self.inner.estimate_fee_rates(c)).await
+    }
+
     pub async fn get_all_transactions(self, query: TransactionQuery) -> (Vec<MutableTransaction>, Vec<MutableTransaction>) {
         spawn_blocking(move || self.inner.get_all_transactions(query)).await.unwrap()
     }
@@ -9,7 +9,11 @@ mod tests {
             errors::RuleError,
             tx::{Orphan, Priority},
         },
-        model::{candidate_tx::CandidateTransaction, tx_query::TransactionQuery},
+        model::{
+            candidate_tx::CandidateTransaction,
+            fee_rate::{FeeRateEstimate, LOW_BLOCKS, NORMAL_BLOCKS, PRIORITY_BLOCKS},
+            tx_query::TransactionQuery,
+        },
         testutils::consensus_mock::ConsensusMock,
         MiningCounters,
     };
@@ -294,6 +298,66 @@ mod tests {
         }
     }
 
+    // test_estimate_fee_rates verifies that the fee rate buckets are derived from the mempool content
+    // and that the estimate is cached until the next virtual state update.
+    #[test]
+    fn test_estimate_fee_rates() {
+        const TX_COUNT: u64 = 130;
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+
+        // Transactions all have the same mass and higher fees for lower indexes
+        let transactions = (0..TX_COUNT)
+            .map(|i| {
+                let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_KASPA + i]);
+                let tx = create_transaction(&funding_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE * (TX_COUNT + 1 - i));
+                consensus.add_transaction(funding_tx, 1);
+                tx
+            })
+            .collect::<Vec<_>>();
+        let mass = transaction_estimated_serialized_size(&transactions[0]);
+        let fee_rate = |i: u64| (DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE * (TX_COUNT + 1 - i)) as f64 / mass as f64;
+        let minimum_fee_rate = DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE as f64 / 1000.0;
+
+        // Blocks fit exactly 2 transactions
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, 2 * mass, None, counters);
+        let estimate = mining_manager.estimate_fee_rates(consensus.as_ref());
+        assert_eq!(estimate, FeeRateEstimate { low: minimum_fee_rate, normal: minimum_fee_rate, priority: minimum_fee_rate });
+
+        // Inserting the transactions does not affect the estimate cached for the current virtual state
+        for transaction in transactions.iter() {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+            );
+            assert!(result.is_ok(), "inserting a valid transaction failed");
+        }
+        assert_eq!(mining_manager.estimate_fee_rates(consensus.as_ref()), estimate);
+
+        // The bucket boundaries are the first transactions not fitting in 1, 10 and 60 blocks respectively
+        consensus.set_virtual_daa_score(1);
+        let estimate = mining_manager.estimate_fee_rates(consensus.as_ref());
+        assert_eq!(
+            estimate,
+            FeeRateEstimate {
+                low: fee_rate(2 * LOW_BLOCKS),
+                normal: fee_rate(2 * NORMAL_BLOCKS),
+                priority: fee_rate(2 * PRIORITY_BLOCKS)
+            }
+        );
+
+        // Removing the transactions mined by a block updates the estimate
+        let block_transactions = build_block_transactions(transactions.iter().take(2));
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &block_transactions);
+        assert!(result.is_ok(), "handling the transactions of a block failed: {result:?}");
+        consensus.set_virtual_daa_score(2);
+        let estimate = mining_manager.estimate_fee_rates(consensus.as_ref());
+        assert_eq!(estimate.priority, fee_rate(2 * PRIORITY_BLOCKS + 2));
+        assert_eq!(estimate.low, fee_rate(2 * LOW_BLOCKS + 2));
+    }
+
     #[test]
     // test_double_spend_with_block verifies that any transactions which are now double spends as a result of the block's new transactions
     // will be removed from the mempool.
@@ -3,104 +3,30 @@ use crate::mempool::{
     Mempool,
 };
 use kaspa_consensus_core::{
-    constants::{MAX_SCRIPT_PUBLIC_KEY_VERSION, MAX_SOMPI},
-    mass,
+    standard::{self, StandardnessParams},
     tx::{MutableTransaction, PopulatedTransaction, TransactionOutput},
 };
-use kaspa_txscript::{get_sig_op_count, is_unspendable, script_class::ScriptClass};
-
-/// MAX_STANDARD_P2SH_SIG_OPS is the maximum number of signature operations
-/// that are considered standard in a pay-to-script-hash script.
-const MAX_STANDARD_P2SH_SIG_OPS: u8 = 15;
-
-/// MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE is the maximum size allowed for a
-/// transaction input signature script to be considered standard. This
-/// value allows for a 15-of-15 CHECKMULTISIG pay-to-script-hash with
-/// compressed keys.
-///
-/// The form of the overall script is: OP_0 <15 signatures> OP_PUSHDATA2
-/// <2 bytes len> [OP_15 <15 pubkeys> OP_15 OP_CHECKMULTISIG]
-///
-/// For the p2sh script portion, each of the 15 compressed pubkeys are
-/// 33 bytes (plus one for the OP_DATA_33 opcode), and the thus it totals
-/// to (15*34)+3 = 513 bytes. Next, each of the 15 signatures is a max
-/// of 73 bytes (plus one for the OP_DATA_73 opcode). Also, there is one
-/// extra byte for the initial extra OP_0 push and 3 bytes for the
-/// OP_PUSHDATA2 needed to specify the 513 bytes for the script push.
-/// That brings the total to 1+(15*74)+3+513 = 1627. This value also
-/// adds a few extra bytes to provide a little buffer.
-/// (1 + 15*74 + 3) + (15*34 + 3) + 23 = 1650
-const MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE: u64 = 1650;
-
-/// MAXIMUM_STANDARD_TRANSACTION_MASS is the maximum mass allowed for transactions that
-/// are considered standard and will therefore be relayed and considered for mining.
-const MAXIMUM_STANDARD_TRANSACTION_MASS: u64 = 100_000;
+use kaspa_txscript::is_unspendable;
 
 impl Mempool {
-    pub(crate) fn check_transaction_standard_in_isolation(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
-        let transaction_id = transaction.id();
-
-        // The transaction must be a currently supported version.
-        //
-        // This check is currently mirrored in consensus.
-        // However, in a later version of Kaspa the consensus-valid transaction version range might diverge from the
-        // standard transaction version range, and thus the validation should happen in both levels.
-        if transaction.tx.version > self.config.maximum_standard_transaction_version
-            || transaction.tx.version < self.config.minimum_standard_transaction_version
-        {
-            return Err(NonStandardError::RejectVersion(
-                transaction_id,
-                transaction.tx.version,
-                self.config.minimum_standard_transaction_version,
-                self.config.maximum_standard_transaction_version,
-            ));
-        }
-
-        // Since extremely large transactions with a lot of inputs can cost
-        // almost as much to process as the sender fees, limit the maximum
-        // size of a transaction. This also helps mitigate CPU exhaustion
-        // attacks.
-        if transaction.calculated_compute_mass.unwrap() > MAXIMUM_STANDARD_TRANSACTION_MASS {
-            return Err(NonStandardError::RejectMass(
-                transaction_id,
-                transaction.calculated_compute_mass.unwrap(),
-                MAXIMUM_STANDARD_TRANSACTION_MASS,
-            ));
-        }
-
-        for (i, input) in transaction.tx.inputs.iter().enumerate() {
-            // Each transaction input signature script must not exceed the
-            // maximum size allowed for a standard transaction.
-            //
-            // See the comment on MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE for
-            // more details.
-            let signature_script_len = input.signature_script.len() as u64;
-            if signature_script_len > MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE {
-                return Err(NonStandardError::RejectSignatureScriptSize(
-                    transaction_id,
-                    i,
-                    signature_script_len,
-                    MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE,
-                ));
-            }
-        }
-
-        // None of the output public key scripts can be a non-standard script or be "dust".
-        for (i, output) in transaction.tx.outputs.iter().enumerate() {
-            if output.script_public_key.version() > MAX_SCRIPT_PUBLIC_KEY_VERSION {
-                return Err(NonStandardError::RejectScriptPublicKeyVersion(transaction_id, i));
-            }
-
-            if ScriptClass::from_script(&output.script_public_key) == ScriptClass::NonStandard {
-                return Err(NonStandardError::RejectOutputScriptClass(transaction_id, i));
-            }
-
-            if self.is_transaction_output_dust(output) {
-                return Err(NonStandardError::RejectDust(transaction_id, i, output.value));
-            }
+    fn standardness_params(&self) -> StandardnessParams {
+        StandardnessParams {
+            minimum_relay_transaction_fee: self.config.minimum_relay_transaction_fee,
+            minimum_standard_transaction_version: self.config.minimum_standard_transaction_version,
+            maximum_standard_transaction_version: self.config.maximum_standard_transaction_version,
         }
+    }
 
-        Ok(())
+    /// check_transaction_standard_in_isolation applies the standardness rules of
+    /// [`standard::check_transaction_standard_in_isolation`] to a transaction whose
+    /// compute mass was calculated by consensus.
+    pub(crate) fn check_transaction_standard_in_isolation(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
+        standard::check_transaction_standard_in_isolation(
+            &transaction.tx,
+            transaction.calculated_compute_mass.unwrap(),
+            &self.standardness_params(),
+        )
+        .map_err(|err| NonStandardError::new(transaction.id(), err))
     }
 
     /// is_transaction_output_dust returns whether or not the passed transaction output
@@ -118,47 +44,10 @@ impl Mempool {
             return true;
         }
 
-        // The total serialized size consists of the output and the associated
-        // input script to redeem it. Since there is no input script
-        // to redeem it yet, use the minimum size of a typical input script.
-        //
-        // Pay-to-pubkey bytes breakdown:
-        //
-        //  Output to pubkey (43 bytes):
-        //   8 value, 1 script len, 34 script [1 OP_DATA_32,
-        //   32 pubkey, 1 OP_CHECKSIG]
-        //
-        //  Input (105 bytes):
-        //   36 prev outpoint, 1 script len, 64 script [1 OP_DATA_64,
-        //   64 sig], 4 sequence
-        //
-        // The most common scripts are pay-to-pubkey, and as per the above
-        // breakdown, the minimum size of a p2pk input script is 148 bytes. So
-        // that figure is used.
-        let total_serialized_size = mass::transaction_output_estimated_serialized_size(transaction_output) + 148;
-
         // The output is considered dust if the cost to the network to spend the
         // coins is more than 1/3 of the minimum free transaction relay fee.
-        // mp.config.MinimumRelayTransactionFee is in sompi/KB, so multiply
-        // by 1000 to convert to bytes.
-        //
-        // Using the typical values for a pay-to-pubkey transaction from
-        // the breakdown above and the default minimum free transaction relay
-        // fee of 1000, this equates to values less than 546 sompi being
-        // considered dust.
-        //
-        // The following is equivalent to (value/total_serialized_size) * (1/3) * 1000
-        // without needing to do floating point math.
-        //
-        // Since the multiplication may overflow a u64, 2 separate calculation paths
-        // are considered to avoid overflowing.
-        match transaction_output.value.checked_mul(1000) {
-            Some(value_1000) => value_1000 / (3 * total_serialized_size) < self.config.minimum_relay_transaction_fee,
-            None => {
-                (transaction_output.value as u128 * 1000 / (3 * total_serialized_size as u128))
-                    < self.config.minimum_relay_transaction_fee as u128
-            }
-        }
+        // See [`standard::is_transaction_output_dust`].
+        standard::is_transaction_output_dust(transaction_output, self.config.minimum_relay_transaction_fee)
     }
 
     /// check_transaction_standard_in_context performs a series of checks on a transaction's
@@ -168,62 +57,17 @@ impl Mempool {
     /// maxStandardP2SHSigOps signature operations.
     /// In addition, makes sure that the transaction's fee is above the minimum for acceptance
     /// into the mempool and relay.
+    ///
+    /// See [`standard::check_transaction_standard_in_context`].
     pub(crate) fn check_transaction_standard_in_context(&self, transaction: &MutableTransaction) -> NonStandardResult<()> {
-        let transaction_id = transaction.id();
-        let contextual_mass = transaction.tx.mass();
-        assert!(contextual_mass > 0, "expected to be set by consensus");
-        if contextual_mass > MAXIMUM_STANDARD_TRANSACTION_MASS {
-            return Err(NonStandardError::RejectContextualMass(transaction_id, contextual_mass, MAXIMUM_STANDARD_TRANSACTION_MASS));
-        }
-
-        for (i, input) in transaction.tx.inputs.iter().enumerate() {
-            // It is safe to elide existence and index checks here since
-            // they have already been checked prior to calling this
-            // function.
-            let entry = transaction.entries[i].as_ref().unwrap();
-            match ScriptClass::from_script(&entry.script_public_key) {
-                ScriptClass::NonStandard => {
-                    return Err(NonStandardError::RejectInputScriptClass(transaction_id, i));
-                }
-                ScriptClass::PubKey => {}
-                ScriptClass::PubKeyECDSA => {}
-                ScriptClass::ScriptHash => {
-                    get_sig_op_count::<PopulatedTransaction>(&input.signature_script, &entry.script_public_key);
-                    let num_sig_ops = 1;
-                    if num_sig_ops > MAX_STANDARD_P2SH_SIG_OPS {
-                        return Err(NonStandardError::RejectSignatureCount(transaction_id, i, num_sig_ops, MAX_STANDARD_P2SH_SIG_OPS));
-                    }
-                }
-            }
-
-            // TODO: For now, until wallets adapt, we don't require fee as function of full contextual_mass (but the fee/mass ratio will affect tx selection to block template)
-            let minimum_fee = self.minimum_required_transaction_relay_fee(transaction.calculated_compute_mass.unwrap());
-            if transaction.calculated_fee.unwrap() < minimum_fee {
-                return Err(NonStandardError::RejectInsufficientFee(transaction_id, transaction.calculated_fee.unwrap(), minimum_fee));
-            }
-        }
-
-        Ok(())
-    }
-
-    /// minimum_required_transaction_relay_fee returns the minimum transaction fee required
-    /// for a transaction with the passed mass to be accepted into the mempool and relayed.
-    fn minimum_required_transaction_relay_fee(&self, mass: u64) -> u64 {
-        // Calculate the minimum fee for a transaction to be allowed into the
-        // mempool and relayed by scaling the base fee. MinimumRelayTransactionFee is in
-        // sompi/kg so multiply by mass (which is in grams) and divide by 1000 to get
-        // minimum sompis.
-        let mut minimum_fee = (mass * self.config.minimum_relay_transaction_fee) / 1000;
-
-        if minimum_fee == 0 {
-            minimum_fee = self.config.minimum_relay_transaction_fee;
-        }
-
-        // Set the minimum fee to the maximum possible value if the calculated
-        // fee is not in the valid range for monetary amounts.
-        minimum_fee = minimum_fee.min(MAX_SOMPI);
-
-        minimum_fee
+        assert!(transaction.tx.mass() > 0, "expected to be set by consensus");
+        standard::check_transaction_standard_in_context(
+            &transaction.as_verifiable(),
+            transaction.calculated_compute_mass.unwrap(),
+            transaction.calculated_fee.unwrap(),
+            &self.standardness_params(),
+        )
+        .map_err(|err| NonStandardError::new(transaction.id(), err))
     }
 }
 
@@ -237,7 +81,10 @@ mod tests {
     use kaspa_addresses::{Address, Prefix, Version};
     use kaspa_consensus_core::{
         config::params::Params,
-        constants::{MAX_TX_IN_SEQUENCE_NUM, SOMPI_PER_KASPA, TX_VERSION},
+        constants::{
+            MAXIMUM_STANDARD_TRANSACTION_MASS, MAX_SCRIPT_PUBLIC_KEY_VERSION, MAX_SOMPI, MAX_TX_IN_SEQUENCE_NUM, SOMPI_PER_KASPA,
+            TX_VERSION,
+        },
         network::NetworkType,
         subnets::SUBNETWORK_ID_NATIVE,
         tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput},
@@ -246,6 +93,10 @@ mod tests {
         opcodes::codes::{OpReturn, OpTrue},
         script_builder::ScriptBuilder,
     };
+    use kaspa_consensus_core::{
+        standard::{MAXIMUM_STANDARD_SIGNATURE_SCRIPT_SIZE, MAX_STANDARD_P2SH_SIG_OPS},
+        tx::UtxoEntry,
+    };
     use smallvec::smallvec;
     use std::sync::Arc;
 
@@ -294,7 +145,7 @@ mod tests {
                 let counters = Arc::new(MiningCounters::default());
                 let mempool = Mempool::new(Arc::new(config), counters);
 
-                let got = mempool.minimum_required_transaction_relay_fee(test.size);
+                let got = standard::minimum_required_transaction_relay_fee(test.size, mempool.config.minimum_relay_transaction_fee);
                 if got != test.want {
                     println!("test_calc_min_required_tx_relay_fee test '{}' failed: got {}, want {}", test.name, got, test.want);
                 }
@@ -579,4 +430,37 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_check_transaction_standard_in_context() {
+        let params: Params = NetworkType::Mainnet.into();
+        let config = Config::build_default(params.target_time_per_block, false, params.max_block_mass);
+        let mempool = Mempool::new(Arc::new(config), Arc::new(MiningCounters::default()));
+
+        let p2sh_script_public_key = kaspa_txscript::pay_to_script_hash_script(&[OpTrue]);
+        let addr = Address::new(Prefix::Mainnet, Version::PubKey, &[1u8; 32]);
+        let output = TransactionOutput::new(SOMPI_PER_KASPA, kaspa_txscript::pay_to_address_script(&addr));
+
+        let new_mtx = |sig_op_count: u8, fee: u64| {
+            let prev_out = TransactionOutpoint::new(kaspa_hashes::Hash::from_u64_word(1), 0);
+            let input = TransactionInput::new(prev_out, vec![0u8; 65], MAX_TX_IN_SEQUENCE_NUM, sig_op_count);
+            let tx = Transaction::new(TX_VERSION, vec![input], vec![output.clone()], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+            tx.set_mass(2000);
+            let entry = UtxoEntry::new(SOMPI_PER_KASPA + fee, p2sh_script_public_key.clone(), 0, false);
+            let mut mtx = MutableTransaction::with_entries(Arc::new(tx), vec![entry]);
+            mtx.calculated_compute_mass = Some(2000);
+            mtx.calculated_fee = Some(fee);
+            mtx
+        };
+
+        assert!(mempool.check_transaction_standard_in_context(&new_mtx(MAX_STANDARD_P2SH_SIG_OPS, 2000)).is_ok());
+        assert!(matches!(
+            mempool.check_transaction_standard_in_context(&new_mtx(MAX_STANDARD_P2SH_SIG_OPS + 1, 2000)),
+            Err(NonStandardError::RejectSignatureCount(_, 0, 16, MAX_STANDARD_P2SH_SIG_OPS))
+        ));
+        assert!(matches!(
+            mempool.check_transaction_standard_in_context(&new_mtx(1, 1999)),
+            Err(NonStandardError::RejectInsufficientFee(_, 1999, 2000))
+        ));
+    }
 }
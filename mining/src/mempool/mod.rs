@@ -1,6 +1,7 @@
 use crate::{
     model::{
         candidate_tx::CandidateTransaction,
+        fee_rate::FeeRateEstimate,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         tx_query::TransactionQuery,
     },
@@ -111,6 +112,15 @@ impl Mempool {
         count
     }
 
+    /// Estimates the fee rates required for a transaction to be mined within the fee rate buckets,
+    /// based on the transactions of the transaction pool (orphans are excluded).
+    pub(crate) fn estimate_fee_rates(&self) -> FeeRateEstimate {
+        let transactions = self.transaction_pool.all().values().map(|tx| (tx.fee_rate(), tx.mtx.tx.mass()));
+        // The minimum relay fee is in sompi/kg while fee rates are in sompi/gram
+        let minimum_fee_rate = self.config.minimum_relay_transaction_fee as f64 / 1000.0;
+        FeeRateEstimate::from_transactions(transactions, self.config.maximum_mass_per_block, minimum_fee_rate)
+    }
+
     pub(crate) fn block_candidate_transactions(&self) -> Vec<CandidateTransaction> {
         let _sw = Stopwatch::<10>::with_threshold("block_candidate_transactions op");
         self.transaction_pool.all_ready_transactions()
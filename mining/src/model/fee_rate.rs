@@ -0,0 +1,74 @@
+/// Number of blocks within which a transaction paying the priority fee rate is expected to be mined
+pub const PRIORITY_BLOCKS: u64 = 1;
+
+/// Number of blocks within which a transaction paying the normal fee rate is expected to be mined
+pub const NORMAL_BLOCKS: u64 = 10;
+
+/// Number of blocks within which a transaction paying the low fee rate is expected to be mined
+pub const LOW_BLOCKS: u64 = 60;
+
+/// Fee rates, in sompi per gram of mass, estimated from the transactions currently in the mempool.
+///
+/// The fee rate of a bucket is a mass-weighted percentile of the mempool fee rates: the lowest
+/// rate a transaction must pay to outbid enough mempool transactions to fit in the mass of the
+/// bucket blocks. All rates are at least the minimum relay fee rate, which is the estimate of
+/// all buckets when the mempool is empty or not congested.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeRateEstimate {
+    pub low: f64,
+    pub normal: f64,
+    pub priority: f64,
+}
+
+impl FeeRateEstimate {
+    /// Estimates the bucket fee rates from the `(fee rate, mass)` pairs of the mempool transactions.
+    pub fn from_transactions(
+        transactions: impl IntoIterator<Item = (f64, u64)>,
+        maximum_mass_per_block: u64,
+        minimum_fee_rate: f64,
+    ) -> Self {
+        let mut transactions = transactions.into_iter().collect::<Vec<_>>();
+        transactions.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        // Returns the rate of the first transaction not fitting in `blocks` blocks, ie. the rate to outbid
+        let bucket_fee_rate = |blocks: u64| {
+            let capacity = blocks.saturating_mul(maximum_mass_per_block);
+            let mut mass = 0u64;
+            for &(fee_rate, tx_mass) in transactions.iter() {
+                mass = mass.saturating_add(tx_mass);
+                if mass > capacity {
+                    return fee_rate.max(minimum_fee_rate);
+                }
+            }
+            minimum_fee_rate
+        };
+
+        Self { low: bucket_fee_rate(LOW_BLOCKS), normal: bucket_fee_rate(NORMAL_BLOCKS), priority: bucket_fee_rate(PRIORITY_BLOCKS) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_rate_estimate() {
+        const BLOCK_MASS: u64 = 500_000;
+
+        // an empty or uncongested mempool only requires the minimum fee rate
+        let estimate = FeeRateEstimate::from_transactions([], BLOCK_MASS, 1.0);
+        assert_eq!(estimate, FeeRateEstimate { low: 1.0, normal: 1.0, priority: 1.0 });
+        let estimate = FeeRateEstimate::from_transactions([(5.0, 100_000), (3.0, 100_000)], BLOCK_MASS, 1.0);
+        assert_eq!(estimate, FeeRateEstimate { low: 1.0, normal: 1.0, priority: 1.0 });
+
+        // 20 blocks worth of transactions with decreasing rates: 2 blocks at rate 20, 2 at rate 19, ...
+        let transactions = (0..200u64).map(|i| ((20 - i / 10) as f64, 100_000));
+        let estimate = FeeRateEstimate::from_transactions(transactions, BLOCK_MASS, 1.0);
+        assert_eq!(estimate, FeeRateEstimate { low: 1.0, normal: 15.0, priority: 20.0 });
+
+        // rates below the minimum are never suggested
+        let transactions = (0..200u64).map(|i| (0.5, 100_000 + i));
+        let estimate = FeeRateEstimate::from_transactions(transactions, BLOCK_MASS, 1.0);
+        assert_eq!(estimate, FeeRateEstimate { low: 1.0, normal: 1.0, priority: 1.0 });
+    }
+}
@@ -2,6 +2,7 @@ use kaspa_consensus_core::tx::TransactionId;
 use std::collections::HashSet;
 
 pub(crate) mod candidate_tx;
+pub mod fee_rate;
 pub mod owner_txs;
 pub mod topological_index;
 pub mod topological_sort;
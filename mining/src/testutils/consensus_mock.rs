@@ -19,12 +19,19 @@ use kaspa_core::time::unix_now;
 use kaspa_hashes::ZERO_HASH;
 
 use parking_lot::RwLock;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 pub(crate) struct ConsensusMock {
     transactions: RwLock<HashMap<TransactionId, Arc<Transaction>>>,
     statuses: RwLock<HashMap<TransactionId, TxResult<()>>>,
     utxos: RwLock<UtxoCollection>,
+    virtual_daa_score: AtomicU64,
 }
 
 impl ConsensusMock {
@@ -33,9 +40,14 @@ impl ConsensusMock {
             transactions: RwLock::new(HashMap::default()),
             statuses: RwLock::new(HashMap::default()),
             utxos: RwLock::new(HashMap::default()),
+            virtual_daa_score: AtomicU64::new(0),
         }
     }
 
+    pub(crate) fn set_virtual_daa_score(&self, virtual_daa_score: u64) {
+        self.virtual_daa_score.store(virtual_daa_score, Ordering::SeqCst);
+    }
+
     pub(crate) fn set_status(&self, transaction_id: TransactionId, status: TxResult<()>) {
         self.statuses.write().insert(transaction_id, status);
     }
@@ -159,7 +171,7 @@ impl ConsensusApi for ConsensusMock {
     }
 
     fn get_virtual_daa_score(&self) -> u64 {
-        0
+        self.virtual_daa_score.load(Ordering::SeqCst)
     }
 
     fn get_virtual_state_approx_id(&self) -> VirtualStateApproxId {
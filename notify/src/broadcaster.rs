@@ -1,6 +1,10 @@
 extern crate derive_more;
 use super::{
-    connection::Connection, error::Result, events::EventArray, listener::ListenerId, notification::Notification,
+    connection::Connection,
+    error::Result,
+    events::{EventArray, EventType},
+    listener::ListenerId,
+    notification::Notification,
     subscription::DynSubscription,
 };
 use async_channel::{Receiver, Sender};
@@ -9,7 +13,7 @@ use derive_more::Deref;
 use futures::{future::FutureExt, select};
 use kaspa_core::{debug, trace};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -82,6 +86,7 @@ where
 {
     Register(DynSubscription, ListenerId, C),
     Unregister(DynSubscription, ListenerId),
+    Mute(EventType, ListenerId, bool),
 }
 
 #[derive(Debug)]
@@ -128,6 +133,8 @@ where
         workflow_core::task::spawn(async move {
             // Broadcasting plan by event type
             let mut plan = EventArray::<Plan<C>>::default();
+            // Listeners muted by event type
+            let mut muted = EventArray::<HashSet<ListenerId>>::default();
             // Create a store for closed connections to be removed from the plan
             let mut purge: Vec<ListenerId> = Vec::new();
             loop {
@@ -141,6 +148,12 @@ where
                                 Ctl::Unregister(subscription, id) => {
                                     plan[subscription.event_type()].remove(&id);
                                 },
+                                Ctl::Mute(event, id, true) => {
+                                    muted[event].insert(id);
+                                },
+                                Ctl::Mute(event, id, false) => {
+                                    muted[event].remove(&id);
+                                },
                             }
                         }
                     },
@@ -155,8 +168,8 @@ where
                                     for (encoding, connection_set) in encoding_set.iter() {
                                         // ... by message encoding
                                         let message = C::into_message(&applied_notification, encoding);
-                                        for (id, connection) in connection_set.iter() {
-                                            // ... to listeners connections
+                                        for (id, connection) in connection_set.iter().filter(|(id, _)| !muted[event].contains(id)) {
+                                            // ... to unmuted listeners connections
                                             match connection.send(message.clone()).await {
                                                 Ok(_) => {
                                                     trace!("[Broadcaster-{}] sent notification {notification} to listener {id}", self.name);
@@ -202,6 +215,12 @@ where
         Ok(())
     }
 
+    /// Stop (`muted` = true) or resume sending notifications of `event` type to listener `id`.
+    pub fn mute(&self, event: EventType, id: ListenerId, muted: bool) -> Result<()> {
+        self.ctl.try_send(Ctl::Mute(event, id, muted))?;
+        Ok(())
+    }
+
     async fn join_notification_broadcasting_task(&self) -> Result<()> {
         trace!("[Broadcaster-{}] joining", self.name);
         self.shutdown.recv().await?;
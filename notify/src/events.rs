@@ -1,4 +1,6 @@
 use super::scope::Scope;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
 use std::ops::{Index, IndexMut};
 use workflow_core::enums::usize_try_from;
 
@@ -25,7 +27,7 @@ macro_rules! event_type_enum {
 }
 
 event_type_enum! {
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub enum EventType {
     BlockAdded = 0,
     VirtualChainChanged,
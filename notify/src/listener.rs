@@ -2,7 +2,7 @@ use std::fmt::Debug;
 extern crate derive_more;
 use super::{
     connection::Connection,
-    events::EventArray,
+    events::{EventArray, EventSwitches, EventType},
     subscription::{array::ArrayBuilder, Mutation, SingleSubscription},
 };
 
@@ -16,6 +16,9 @@ where
 {
     connection: C,
     pub(crate) subscriptions: EventArray<SingleSubscription>,
+    /// Event types whose notifications are muted, ie. not sent to the
+    /// listener while leaving its subscriptions intact
+    pub(crate) muted: EventSwitches,
 }
 
 impl<C> Listener<C>
@@ -23,7 +26,7 @@ where
     C: Connection,
 {
     pub fn new(connection: C) -> Self {
-        Self { connection, subscriptions: ArrayBuilder::single() }
+        Self { connection, subscriptions: ArrayBuilder::single(), muted: EventSwitches::default() }
     }
 
     pub fn connection(&self) -> C {
@@ -38,6 +41,10 @@ where
         self.subscriptions[mutation.event_type()].mutate(mutation)
     }
 
+    pub fn is_muted(&self, event: EventType) -> bool {
+        self.muted[event]
+    }
+
     pub fn close(&self) {
         if !self.is_closed() {
             self.connection.close();
@@ -123,6 +123,23 @@ where
         self.inner.clone().stop_notify(id, scope)
     }
 
+    /// Stop sending notifications of `event` type to listener `id` while keeping its subscription alive.
+    ///
+    /// Muting acts as a filter applied by the broadcasters, so the upstream subscriptions are left intact
+    /// and the notifications flow again as soon as the event type gets unmuted.
+    pub fn try_mute(&self, id: ListenerId, event: EventType) -> Result<()> {
+        self.inner.set_muted(id, event, true)
+    }
+
+    /// Resume sending notifications of `event` type to listener `id`.
+    pub fn try_unmute(&self, id: ListenerId, event: EventType) -> Result<()> {
+        self.inner.set_muted(id, event, false)
+    }
+
+    pub fn is_muted(&self, id: ListenerId, event: EventType) -> bool {
+        self.inner.listeners.lock().get(&id).is_some_and(|listener| listener.is_muted(event))
+    }
+
     pub fn unregister_listener(&self, id: ListenerId) -> Result<()> {
         self.inner.unregister_listener(id)
     }
@@ -273,6 +290,13 @@ where
                 let _ = self.execute_subscribe_command_impl(id, &mut listener, scope, Command::Stop);
             });
 
+            // Clear the muted event types from the broadcasters
+            EVENT_TYPE_ARRAY.iter().copied().filter(|event| listener.is_muted(*event)).for_each(|event| {
+                self.broadcasters.iter().for_each(|broadcaster| {
+                    let _ = broadcaster.mute(event, id, false);
+                });
+            });
+
             // Close the listener
             trace!("[Notifier {}] closing listener {id}", self.name);
             listener.close();
@@ -342,6 +366,23 @@ where
         self.execute_subscribe_command(id, scope, Command::Start)
     }
 
+    fn set_muted(&self, id: ListenerId, event: EventType, muted: bool) -> Result<()> {
+        let action = if muted { "mute" } else { "unmute" };
+        if !self.enabled_events[event] {
+            trace!("[Notifier {}] {action} notifying listener {id} about {event:?} error: event type is disabled", self.name);
+            return Err(Error::EventTypeDisabled);
+        }
+        let mut listeners = self.listeners.lock();
+        if let Some(listener) = listeners.get_mut(&id) {
+            debug!("[Notifier {}] {action} notifying about {event:?} to listener {id}", self.name);
+            listener.muted[event] = muted;
+            self.broadcasters.iter().try_for_each(|broadcaster| broadcaster.mute(event, id, muted))?;
+        } else {
+            trace!("[Notifier {}] {action} notifying listener {id} about {event:?} error: listener id not found", self.name);
+        }
+        Ok(())
+    }
+
     fn notify(&self, notification: N) -> Result<()> {
         if self.enabled_events[notification.event_type()] {
             self.notification_channel.try_send(notification)?;
@@ -699,6 +740,7 @@ mod tests {
         converter::ConverterFrom,
         events::EVENT_TYPE_ARRAY,
         notification::test_helpers::*,
+        scope::{BlockAddedScope, VirtualChainChangedScope},
         subscriber::test_helpers::{SubscriptionManagerMock, SubscriptionMessage},
     };
     use async_channel::{unbounded, Receiver, Sender};
@@ -858,4 +900,48 @@ mod tests {
         let test = Test::new("UtxosChanged broadcast", 3, utxos_changed_test_steps(SUBSCRIPTION_MANAGER_ID));
         test.run().await;
     }
+
+    #[tokio::test]
+    async fn test_mute() {
+        kaspa_core::log::try_init_logger("trace,kaspa_notify=trace");
+        let (sync_sender, sync_receiver) = unbounded();
+        let notifier = Arc::new(TestNotifier::with_sync("test", EVENT_TYPE_ARRAY[..].into(), vec![], vec![], 1, Some(sync_sender)));
+        let (sender, receiver) = unbounded();
+        let id = notifier.register_new_listener(TestConnection::new(sender, ChannelType::Closable));
+        notifier.clone().start();
+
+        let block_added = || TestNotification::BlockAdded(BlockAddedNotification::default());
+        let virtual_chain_changed = || TestNotification::VirtualChainChanged(VirtualChainChangedNotification::default());
+        let notify = |notification: TestNotification| {
+            let notifier = notifier.clone();
+            let sync_receiver = sync_receiver.clone();
+            async move {
+                notifier.notify(notification).unwrap();
+                sync_receiver.recv().await.unwrap();
+            }
+        };
+
+        for scope in [Scope::BlockAdded(BlockAddedScope {}), Scope::VirtualChainChanged(VirtualChainChangedScope::new(false))] {
+            notifier.try_start_notify(id, scope).unwrap();
+            sync_receiver.recv().await.unwrap();
+        }
+
+        // the muted event type is not delivered while the others still are
+        notifier.try_mute(id, EventType::BlockAdded).unwrap();
+        sync_receiver.recv().await.unwrap();
+        assert!(notifier.is_muted(id, EventType::BlockAdded));
+        notify(block_added()).await;
+        assert!(receiver.is_empty(), "a muted notification was delivered");
+        notify(virtual_chain_changed()).await;
+        assert_eq!(receiver.recv().await.unwrap(), virtual_chain_changed());
+
+        // unmuting resumes the delivery without having to subscribe again
+        notifier.try_unmute(id, EventType::BlockAdded).unwrap();
+        sync_receiver.recv().await.unwrap();
+        assert!(!notifier.is_muted(id, EventType::BlockAdded));
+        notify(block_added()).await;
+        assert_eq!(receiver.recv().await.unwrap(), block_added());
+
+        assert!(notifier.join().await.is_ok(), "notifier failed to stop");
+    }
 }
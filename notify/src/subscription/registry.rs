@@ -0,0 +1,162 @@
+use crate::scope::UtxosChangedScope;
+use kaspa_addresses::Address;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+/// Reference counted registry of subscription items shared by multiple subscribers
+/// of a single listener.
+///
+/// A listener holds a single subscription per event type, so subscribers sharing a
+/// listener (ie. wallet accounts sharing a connection) must not start or stop items
+/// of the underlying subscription on their own. The registry counts the subscribers
+/// of every item, reporting the items to be started when the first subscriber
+/// arrives and the items to be stopped when the last subscriber leaves.
+#[derive(Clone, Debug)]
+pub struct SubscriptionRegistry<T: Hash + Eq + Clone> {
+    counters: HashMap<T, usize>,
+}
+
+impl<T: Hash + Eq + Clone> Default for SubscriptionRegistry<T> {
+    fn default() -> Self {
+        Self { counters: HashMap::default() }
+    }
+}
+
+impl<T: Hash + Eq + Clone> SubscriptionRegistry<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a subscriber of `items` and returns the items that had no subscriber,
+    /// which must be started in the underlying subscription. Duplicate items are counted once.
+    pub fn register(&mut self, items: impl IntoIterator<Item = T>) -> Vec<T> {
+        let items: HashSet<T> = items.into_iter().collect();
+        items
+            .into_iter()
+            .filter(|item| {
+                let counter = self.counters.entry(item.clone()).or_default();
+                *counter += 1;
+                *counter == 1
+            })
+            .collect()
+    }
+
+    /// Unregisters a subscriber of `items` and returns the items that have no subscriber left,
+    /// which must be stopped in the underlying subscription. Unknown items are ignored.
+    pub fn unregister(&mut self, items: impl IntoIterator<Item = T>) -> Vec<T> {
+        let items: HashSet<T> = items.into_iter().collect();
+        items
+            .into_iter()
+            .filter(|item| match self.counters.get_mut(item) {
+                Some(counter) if *counter > 1 => {
+                    *counter -= 1;
+                    false
+                }
+                Some(_) => {
+                    self.counters.remove(item);
+                    true
+                }
+                None => false,
+            })
+            .collect()
+    }
+
+    /// Returns the number of subscribers of `item`.
+    pub fn subscribers(&self, item: &T) -> usize {
+        self.counters.get(item).copied().unwrap_or_default()
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.counters.contains_key(item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.counters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty()
+    }
+
+    /// Returns all items having at least one subscriber.
+    pub fn items(&self) -> impl Iterator<Item = &T> {
+        self.counters.keys()
+    }
+
+    /// Removes all items, typically when the underlying subscription has been dropped.
+    pub fn clear(&mut self) {
+        self.counters.clear();
+    }
+}
+
+/// Registry of the addresses of [`UtxosChangedScope`] subscriptions.
+pub type UtxosChangedRegistry = SubscriptionRegistry<Address>;
+
+impl SubscriptionRegistry<Address> {
+    /// Registers a subscriber of the `scope` addresses. Returns the scope to be
+    /// started in the underlying subscription, if any address had no subscriber.
+    pub fn start(&mut self, scope: UtxosChangedScope) -> Option<UtxosChangedScope> {
+        let addresses = self.register(scope.addresses);
+        (!addresses.is_empty()).then(|| UtxosChangedScope::new(addresses))
+    }
+
+    /// Unregisters a subscriber of the `scope` addresses. Returns the scope to be
+    /// stopped in the underlying subscription, if any address has no subscriber left.
+    pub fn stop(&mut self, scope: UtxosChangedScope) -> Option<UtxosChangedScope> {
+        let addresses = self.unregister(scope.addresses);
+        (!addresses.is_empty()).then(|| UtxosChangedScope::new(addresses))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::test_helpers::get_3_addresses;
+
+    #[test]
+    fn test_subscription_registry() {
+        let mut registry = SubscriptionRegistry::new();
+        assert_eq!(registry.register([1, 2, 2]).len(), 2);
+        assert_eq!(registry.register([2]), Vec::<i32>::new());
+        assert_eq!(registry.subscribers(&2), 2);
+        assert_eq!(registry.unregister([1, 3]), vec![1]);
+        assert_eq!(registry.unregister([2]), Vec::<i32>::new());
+        assert_eq!(registry.unregister([2]), vec![2]);
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_utxos_changed_registry() {
+        let addresses = get_3_addresses(true);
+        let scope = |indexes: &[usize]| UtxosChangedScope::new(indexes.iter().map(|i| addresses[*i].clone()).collect());
+        let sorted = |scope: Option<UtxosChangedScope>| {
+            scope.map(|mut scope| {
+                scope.addresses.sort();
+                scope
+            })
+        };
+        let mut registry = UtxosChangedRegistry::new();
+
+        // subscribing twice to the same scope yields a single underlying subscription
+        assert_eq!(sorted(registry.start(scope(&[0, 1]))), Some(scope(&[0, 1])));
+        assert_eq!(registry.start(scope(&[0, 1])), None);
+
+        // overlapping scopes only start the addresses having no subscriber
+        assert_eq!(registry.start(scope(&[1, 2])), Some(scope(&[2])));
+        assert_eq!(registry.subscribers(&addresses[1]), 3);
+
+        // unsubscribing once keeps the underlying subscription alive
+        assert_eq!(registry.stop(scope(&[0, 1])), None);
+        assert!(registry.contains(&addresses[0]));
+
+        // the last subscriber leaving tears down the underlying subscription
+        assert_eq!(registry.stop(scope(&[0, 1])), Some(scope(&[0])));
+        assert_eq!(sorted(registry.stop(scope(&[1, 2]))), Some(scope(&[1, 2])));
+        assert!(registry.is_empty());
+
+        // stopping an unknown scope is a no-op
+        assert_eq!(registry.stop(scope(&[0])), None);
+    }
+}
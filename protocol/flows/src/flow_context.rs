@@ -31,12 +31,12 @@ use kaspa_notify::notifier::Notify;
 use kaspa_p2p_lib::{
     common::ProtocolError,
     convert::model::version::Version,
-    make_message,
+    make_message, negotiate_protocol_version,
     pb::{kaspad_message::Payload, InvRelayBlockMessage},
-    ConnectionInitializer, Hub, KaspadHandshake, PeerKey, PeerProperties, Router,
+    ConnectionInitializer, Hub, KaspadHandshake, PeerBanner, PeerKey, PeerProperties, RateLimits, Router,
 };
 use kaspa_utils::iter::IterExtensions;
-use kaspa_utils::networking::PeerId;
+use kaspa_utils::networking::{IpAddress, PeerId};
 use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
 use std::time::Instant;
@@ -57,8 +57,11 @@ use tokio::sync::{
 use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 use uuid::Uuid;
 
-/// The P2P protocol version. Currently the only one supported.
-const PROTOCOL_VERSION: u32 = 6;
+/// The P2P protocol version of this node
+pub const PROTOCOL_VERSION: u32 = 6;
+
+/// The lowest P2P protocol version supported by this node
+pub const MIN_PROTOCOL_VERSION: u32 = 5;
 
 /// See `check_orphan_resolution_range`
 const BASELINE_ORPHAN_RESOLUTION_RANGE: u32 = 5;
@@ -221,6 +224,8 @@ pub struct FlowContextInner {
     ibd_metadata: Arc<RwLock<Option<IbdMetadata>>>,
     pub address_manager: Arc<Mutex<AddressManager>>,
     connection_manager: RwLock<Option<Arc<ConnectionManager>>>,
+    rate_limits: Option<Arc<RateLimits>>,
+    min_protocol_version: u32,
     mining_manager: MiningManagerProxy,
     pub(crate) tick_service: Arc<TickService>,
     notification_root: Arc<ConsensusNotificationRoot>,
@@ -311,6 +316,15 @@ impl FlowContext {
         // The maximum amount of orphans allowed in the orphans pool. This number is an approximation
         // of how many orphans there can possibly be on average bounded by an upper bound.
         let max_orphans = (2u64.pow(orphan_resolution_range) as usize * config.ghostdag_k as usize).min(MAX_ORPHANS_UPPER_BOUND);
+
+        let rate_limits = (config.p2p_rate_limit_scale > 0.0).then(|| {
+            let rate_limits = RateLimits::default().scaled(config.p2p_rate_limit_scale);
+            Arc::new(if config.p2p_rate_limit_ban {
+                rate_limits.with_banner(Arc::new(AddressManagerBanner(address_manager.clone())))
+            } else {
+                rate_limits
+            })
+        });
         Self {
             inner: Arc::new(FlowContextInner {
                 node_id: Uuid::new_v4().into(),
@@ -324,6 +338,8 @@ impl FlowContext {
                 hub,
                 address_manager,
                 connection_manager: Default::default(),
+                rate_limits,
+                min_protocol_version: config.p2p_min_protocol_version.unwrap_or_default().max(MIN_PROTOCOL_VERSION),
                 mining_manager,
                 tick_service,
                 notification_root,
@@ -656,9 +672,36 @@ impl FlowContext {
     }
 }
 
+/// Bans the peers disconnected by the P2P rate limits in the address manager
+struct AddressManagerBanner(Arc<Mutex<AddressManager>>);
+
+impl std::fmt::Debug for AddressManagerBanner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AddressManagerBanner")
+    }
+}
+
+impl PeerBanner for AddressManagerBanner {
+    fn ban(&self, ip: IpAddress, reason: String) {
+        self.0.lock().ban(ip, reason, None);
+    }
+}
+
 #[async_trait]
 impl ConnectionInitializer for FlowContext {
     async fn initialize_connection(&self, router: Arc<Router>) -> Result<(), ProtocolError> {
+        // Reject banned inbound peers
+        if !router.is_outbound() {
+            let ban = self.address_manager.lock().get_ban(router.net_address().ip().into());
+            if let Some(ban) = ban {
+                return Err(ProtocolError::PeerBanned(router.net_address().into(), ban.reason));
+            }
+        }
+
+        if let Some(rate_limits) = self.rate_limits.as_ref() {
+            router.set_rate_limits(rate_limits.clone());
+        }
+
         // Build the handshake object and subscribe to handshake messages
         let mut handshake = KaspadHandshake::new(&router);
 
@@ -698,10 +741,12 @@ impl ConnectionInitializer for FlowContext {
 
         debug!("protocol versions - self: {}, peer: {}", PROTOCOL_VERSION, peer_version.protocol_version);
 
-        // Register all flows according to version
-        let (flows, applied_protocol_version) = match peer_version.protocol_version {
-            v if v >= PROTOCOL_VERSION => (v6::register(self.clone(), router.clone()), PROTOCOL_VERSION),
-            5 => (v5::register(self.clone(), router.clone()), 5),
+        // Register all flows according to the negotiated version
+        let applied_protocol_version =
+            negotiate_protocol_version(PROTOCOL_VERSION, self.min_protocol_version, peer_version.protocol_version)?;
+        let flows = match applied_protocol_version {
+            6 => v6::register(self.clone(), router.clone()),
+            5 => v5::register(self.clone(), router.clone()),
             v => return Err(ProtocolError::VersionMismatch(PROTOCOL_VERSION, v)),
         };
 
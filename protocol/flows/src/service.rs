@@ -3,14 +3,14 @@ use std::sync::Arc;
 use kaspa_addressmanager::NetAddress;
 use kaspa_connectionmanager::ConnectionManager;
 use kaspa_core::{
-    task::service::{AsyncService, AsyncServiceFuture},
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
     trace,
 };
 use kaspa_p2p_lib::Adaptor;
 use kaspa_utils::triggers::SingleTrigger;
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 
-use crate::flow_context::FlowContext;
+use crate::{flow_context::FlowContext, snapshot::SnapshotOperation};
 
 const P2P_CORE_SERVICE: &str = "p2p-service";
 
@@ -25,6 +25,8 @@ pub struct P2pService {
     default_port: u16,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    /// Pruning point snapshot export or import performed before connecting to peers
+    snapshot_operation: Option<SnapshotOperation>,
 }
 
 impl P2pService {
@@ -38,6 +40,7 @@ impl P2pService {
         dns_seeders: &'static [&'static str],
         default_port: u16,
         counters: Arc<TowerConnectionCounters>,
+        snapshot_operation: Option<SnapshotOperation>,
     ) -> Self {
         Self {
             flow_context,
@@ -50,6 +53,7 @@ impl P2pService {
             dns_seeders,
             default_port,
             counters,
+            snapshot_operation,
         }
     }
 }
@@ -65,23 +69,28 @@ impl AsyncService for P2pService {
         // Prepare a shutdown signal receiver
         let shutdown_signal = self.shutdown.listener.clone();
 
-        let p2p_adaptor =
-            Adaptor::bidirectional(self.listen, self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
-                .unwrap();
-        let connection_manager = ConnectionManager::new(
-            p2p_adaptor.clone(),
-            self.outbound_target,
-            self.inbound_limit,
-            self.dns_seeders,
-            self.default_port,
-            self.flow_context.address_manager.clone(),
-        );
-
-        self.flow_context.set_connection_manager(connection_manager.clone());
-        self.flow_context.start_async_services();
-
         // Launch the service and wait for a shutdown signal
         Box::pin(async move {
+            // A snapshot is imported before any peer can trigger an IBD
+            if let Some(snapshot_operation) = self.snapshot_operation.clone() {
+                snapshot_operation.run(&self.flow_context).await.map_err(|err| AsyncServiceError::Service(err.to_string()))?;
+            }
+
+            let p2p_adaptor =
+                Adaptor::bidirectional(self.listen, self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
+                    .unwrap();
+            let connection_manager = ConnectionManager::new(
+                p2p_adaptor.clone(),
+                self.outbound_target,
+                self.inbound_limit,
+                self.dns_seeders,
+                self.default_port,
+                self.flow_context.address_manager.clone(),
+            );
+
+            self.flow_context.set_connection_manager(connection_manager.clone());
+            self.flow_context.start_async_services();
+
             for peer_address in self.connect_peers.iter().cloned().chain(self.add_peers.iter().cloned()) {
                 connection_manager.add_connection_request(peer_address.into(), true).await;
             }
@@ -0,0 +1,225 @@
+//!
+//! Export and import of pruning point snapshots, allowing a fresh node to bootstrap from a pruning
+//! point obtained out of band instead of syncing it from its peers.
+//!
+
+use crate::flow_context::FlowContext;
+use kaspa_consensus_core::{
+    errors::consensus::ConsensusError,
+    pruning::{PruningPointProof, PruningPointsList},
+    pruning_snapshot::{PruningPointSnapshot, PruningSnapshotError, PruningSnapshotUtxoReader},
+    trusted::TrustedBlock,
+};
+use kaspa_consensusmanager::{spawn_blocking, ConsensusProxy};
+use kaspa_core::info;
+use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    convert::model::trusted::{TrustedDataEntry, TrustedDataPackage},
+};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+};
+
+const UTXO_CHUNK_SIZE: usize = 1000;
+
+/// A pruning point snapshot operation performed when the node starts
+#[derive(Clone, Debug)]
+pub enum SnapshotOperation {
+    /// Export the current pruning point to a file
+    Export(PathBuf),
+    /// Import the snapshot file of the pruning point with the given hash
+    Import(PathBuf, Hash),
+}
+
+impl SnapshotOperation {
+    pub async fn run(self, ctx: &FlowContext) -> Result<(), ProtocolError> {
+        match self {
+            SnapshotOperation::Export(path) => export_pruning_point_snapshot(ctx, path).await.map(|_| ()),
+            SnapshotOperation::Import(path, pruning_point) => import_pruning_point_snapshot(ctx, path, pruning_point).await,
+        }
+    }
+}
+
+/// Exports the current pruning point, with its proof, anticone and UTXO set, to the file at `path`.
+/// Returns the hash of the exported pruning point.
+///
+/// The export fails with [`ConsensusError::UnexpectedPruningPoint`] if the pruning point moves while
+/// being exported, in which case the partially written file is removed.
+pub async fn export_pruning_point_snapshot(ctx: &FlowContext, path: PathBuf) -> Result<Hash, ProtocolError> {
+    let session = ctx.consensus().session().await;
+    let pruning_point = session.async_pruning_point().await;
+    if pruning_point == ctx.config.genesis.hash {
+        return Err(ProtocolError::Other("the node has no pruning point to export yet"));
+    }
+    info!("Exporting a snapshot of pruning point {} to {}", pruning_point, path.display());
+
+    let pruning_points = session.async_pruning_point_headers().await;
+    let proof = session.async_get_pruning_point_proof().await.as_ref().clone();
+    let trusted_data = session.async_get_pruning_point_anticone_and_trusted_data().await?;
+    let mut trusted_blocks = Vec::with_capacity(trusted_data.anticone.len());
+    for hash in trusted_data.anticone.iter().copied() {
+        trusted_blocks.push(session.async_get_block(hash).await?);
+    }
+    // The data above was queried by separate calls, so we recheck it all relates to the same pruning point.
+    // The UTXO set queries fail by themselves if the pruning point moves.
+    if session.async_pruning_point().await != pruning_point {
+        return Err(ProtocolError::ConsensusError(ConsensusError::UnexpectedPruningPoint));
+    }
+
+    let snapshot = PruningPointSnapshot {
+        pruning_points,
+        proof,
+        trusted_blocks,
+        daa_window: trusted_data.daa_window_blocks.clone(),
+        ghostdag_window: trusted_data.ghostdag_blocks.clone(),
+    };
+    let file_path = path.clone();
+    let file =
+        spawn_blocking(move || File::create(file_path)).await.unwrap().map_err(|err| ProtocolError::OtherOwned(err.to_string()))?;
+    match write_pruning_point_snapshot(&session, pruning_point, snapshot, file).await {
+        Ok(utxos) => {
+            info!("Exported a snapshot of pruning point {} with {} UTXOs", pruning_point, utxos);
+            Ok(pruning_point)
+        }
+        Err(err) => {
+            let _ = std::fs::remove_file(path);
+            Err(err)
+        }
+    }
+}
+
+/// Writes `snapshot` to `file`, followed by the UTXO set of `pruning_point` which is written chunk by chunk
+/// as it is queried, so that it is never held in memory as a whole. Returns the number of written UTXOs.
+async fn write_pruning_point_snapshot(
+    session: &ConsensusProxy,
+    pruning_point: Hash,
+    snapshot: PruningPointSnapshot,
+    file: File,
+) -> Result<usize, ProtocolError> {
+    let mut utxo_writer = spawn_blocking(move || snapshot.write(BufWriter::new(file)))
+        .await
+        .unwrap()
+        .map_err(|err| ProtocolError::OtherOwned(err.to_string()))?;
+
+    let mut utxos = 0;
+    let mut from_outpoint = None;
+    loop {
+        let chunk =
+            session.async_get_pruning_point_utxos(pruning_point, from_outpoint, UTXO_CHUNK_SIZE, from_outpoint.is_some()).await?;
+        let done = chunk.len() < UTXO_CHUNK_SIZE;
+        from_outpoint = chunk.last().map(|(outpoint, _)| *outpoint);
+        utxos += chunk.len();
+        utxo_writer = spawn_blocking(move || utxo_writer.write_chunk(&chunk).map(|()| utxo_writer))
+            .await
+            .unwrap()
+            .map_err(|err| ProtocolError::OtherOwned(err.to_string()))?;
+        if done {
+            break;
+        }
+    }
+
+    spawn_blocking(move || utxo_writer.finish()).await.unwrap().map_err(|err| ProtocolError::OtherOwned(err.to_string()))?;
+    Ok(utxos)
+}
+
+/// Imports the snapshot file at `path` into a fresh node after validating it against `expected_pruning_point`,
+/// a pruning point hash obtained from a trusted source. The node then syncs from its peers the blocks
+/// following the imported pruning point.
+pub async fn import_pruning_point_snapshot(
+    ctx: &FlowContext,
+    path: PathBuf,
+    expected_pruning_point: Hash,
+) -> Result<(), ProtocolError> {
+    info!("Importing the snapshot of pruning point {} from {}", expected_pruning_point, path.display());
+    let (snapshot, utxo_reader) = spawn_blocking(move || {
+        let mut reader = BufReader::new(File::open(path)?);
+        let snapshot = PruningPointSnapshot::read(&mut reader)?;
+        snapshot.validate(expected_pruning_point)?;
+        let utxo_reader = snapshot.utxo_reader(reader)?;
+        Ok::<_, PruningSnapshotError>((snapshot, utxo_reader))
+    })
+    .await
+    .unwrap()
+    .map_err(|err| ProtocolError::OtherOwned(err.to_string()))?;
+
+    if snapshot.pruning_points.first().map(|header| header.hash) != Some(ctx.config.genesis.hash) {
+        return Err(ProtocolError::Other("the first pruning point in the list is expected to be genesis"));
+    }
+
+    let consensus = ctx.consensus().session().await;
+    if consensus.async_pruning_point().await != ctx.config.genesis.hash {
+        return Err(ProtocolError::Other("a pruning point snapshot can only be imported by a node without a pruning point"));
+    }
+    // The proof is validated in the context of current consensus
+    let PruningPointSnapshot { pruning_points, proof, trusted_blocks, daa_window, ghostdag_window } = snapshot;
+    let proof = consensus.clone().spawn_blocking(move |c| c.validate_pruning_proof(&proof).map(|()| proof)).await?;
+    drop(consensus);
+
+    let entries = trusted_blocks.into_iter().map(|block| TrustedDataEntry::new(block, vec![], vec![])).collect();
+    let trusted_set = TrustedDataPackage::new(daa_window, ghostdag_window).build_trusted_subdag(entries)?;
+
+    let staging = ctx.consensus_manager.new_staging_consensus();
+    let result = {
+        let staging_session = staging.session().await;
+        import_into_staging(&staging_session, expected_pruning_point, pruning_points, proof, trusted_set, utxo_reader).await
+    };
+    match result {
+        Ok(()) => {
+            spawn_blocking(|| staging.commit()).await.unwrap();
+            info!("Imported the snapshot of pruning point {}. Committed staging consensus.", expected_pruning_point);
+            Ok(())
+        }
+        Err(err) => {
+            staging.cancel();
+            Err(err)
+        }
+    }
+}
+
+async fn import_into_staging(
+    staging: &ConsensusProxy,
+    pruning_point: Hash,
+    pruning_points: PruningPointsList,
+    proof: PruningPointProof,
+    trusted_set: Vec<TrustedBlock>,
+    mut utxo_reader: PruningSnapshotUtxoReader<BufReader<File>>,
+) -> Result<(), ProtocolError> {
+    let trusted_set = staging
+        .clone()
+        .spawn_blocking(move |c| {
+            c.apply_pruning_proof(proof, &trusted_set)?;
+            c.import_pruning_points(pruning_points);
+            Result::<_, ProtocolError>::Ok(trusted_set)
+        })
+        .await?;
+
+    info!("Processing {} trusted blocks", trusted_set.len());
+    for tb in trusted_set {
+        staging.validate_and_insert_trusted_block(tb).virtual_state_task.await?;
+    }
+    staging.async_validate_pruning_points().await?;
+
+    info!("Importing the pruning point UTXO set");
+    // The UTXO set is streamed from the file chunk by chunk, the reader failing if it does not match the commitment
+    let (multiset, utxos) = staging
+        .clone()
+        .spawn_blocking(move |c| {
+            let mut multiset = MuHash::new();
+            let mut utxos = 0;
+            while let Some(chunk) = utxo_reader.next_chunk()? {
+                c.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+                utxos += chunk.len();
+            }
+            Ok::<_, PruningSnapshotError>((multiset, utxos))
+        })
+        .await
+        .map_err(|err| ProtocolError::OtherOwned(err.to_string()))?;
+    info!("Imported {} pruning point UTXOs", utxos);
+    // The consensus verifies the imported UTXO set against the pruning point UTXO commitment
+    staging.clone().spawn_blocking(move |c| c.import_pruning_point_utxo_set(pruning_point, multiset)).await?;
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::{convert::error::ConversionError, core::peer::PeerKey, KaspadMessagePayloadType};
 use kaspa_consensus_core::errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError};
 use kaspa_mining_errors::manager::MiningManagerError;
+use kaspa_utils::networking::NetAddress;
 use std::time::Duration;
 use thiserror::Error;
 
@@ -15,6 +16,9 @@ pub enum ProtocolError {
     #[error("P2P protocol version mismatch - local: {0}, remote: {1}")]
     VersionMismatch(u32, u32),
 
+    #[error("P2P protocol version {0} of the peer is below the minimum supported version {1}")]
+    ProtocolVersionTooOld(u32, u32),
+
     #[error("Network mismatch - local: {0}, remote: {1}")]
     WrongNetwork(String, String),
 
@@ -55,6 +59,9 @@ pub enum ProtocolError {
     #[error("incoming route capacity for message type {0:?} has been reached (peer: {1})")]
     IncomingRouteCapacityReached(KaspadMessagePayloadType, String),
 
+    #[error("rate limit of message type {0:?} has been exceeded (peer: {1})")]
+    RateLimitExceeded(KaspadMessagePayloadType, String),
+
     #[error("outgoing route capacity has been reached (peer: {0})")]
     OutgoingRouteCapacityReached(String),
 
@@ -67,6 +74,9 @@ pub enum ProtocolError {
     #[error("loopback connection - node is connecting to itself")]
     LoopbackConnection(PeerKey),
 
+    #[error("peer {0} is banned: {1}")]
+    PeerBanned(NetAddress, String),
+
     #[error("got reject message: {0}")]
     Rejected(String),
 
@@ -51,7 +51,12 @@ impl Hub {
                                     new_router.try_sending_reject_message(&err).await;
                                     // Ignoring the new router
                                     new_router.close().await;
-                                    if matches!(err, ProtocolError::LoopbackConnection(_) | ProtocolError::PeerAlreadyExists(_)) {
+                                    if matches!(
+                                        err,
+                                        ProtocolError::LoopbackConnection(_)
+                                            | ProtocolError::PeerAlreadyExists(_)
+                                            | ProtocolError::PeerBanned(..)
+                                    ) {
                                         debug!("P2P, handshake failed for inbound peer {}: {}", new_router, err);
                                     } else {
                                         warn!("P2P, handshake failed for inbound peer {}: {}", new_router, err);
@@ -172,7 +177,10 @@ impl Hub {
         }
     }
 
-    /// Returns a list of all currently active peers
+    /// Returns a list of all currently active peers along with their handshake properties.
+    ///
+    /// The list is a consistent snapshot taken under a single lock, unaffected by peers
+    /// connecting or disconnecting while it is being processed.
     pub fn active_peers(&self) -> Vec<Peer> {
         self.peers.read().values().map(|r| r.as_ref().into()).collect()
     }
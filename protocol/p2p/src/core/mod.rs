@@ -3,4 +3,5 @@ pub mod connection_handler;
 pub mod hub;
 pub mod payload_type;
 pub mod peer;
+pub mod rate_limit;
 pub mod router;
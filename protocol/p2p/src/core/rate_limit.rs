@@ -0,0 +1,219 @@
+use crate::KaspadMessagePayloadType;
+use kaspa_utils::networking::IpAddress;
+use std::{collections::HashMap, fmt::Debug, net::SocketAddr, sync::Arc, time::Instant};
+
+/// Default number of consecutive throttled messages of a type after which a peer is disconnected
+pub const DEFAULT_MAX_VIOLATIONS: u32 = 100;
+
+/// Token bucket limit of a message type: `burst` messages may be received at once and the
+/// allowance refills at `rate` messages per second
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub rate: f64,
+    pub burst: f64,
+}
+
+impl RateLimit {
+    pub fn new(rate: f64, burst: f64) -> Self {
+        Self { rate, burst }
+    }
+}
+
+/// Verdict of a [`PeerRateLimiter`] for an incoming message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitVerdict {
+    /// The message is within the limits of its type
+    Pass,
+    /// The message exceeds the limits of its type and should be dropped
+    Throttle,
+    /// The peer kept exceeding the limits of the message type and should be disconnected
+    Disconnect,
+}
+
+/// Bans the IPs of peers disconnected for exceeding the limits (see [`RateLimits::with_banner`])
+pub trait PeerBanner: Debug + Send + Sync {
+    /// Bans `ip` for the default ban duration
+    fn ban(&self, ip: IpAddress, reason: String);
+}
+
+/// Per message type limits of the unsolicited messages received from a peer.
+///
+/// Only message types which a peer may send on its own initiative are limited. Responses to
+/// requests of this node, which make up the bulk of the sync traffic, are never limited, and
+/// bursts are tolerated up to the capacity of the bucket of the message type.
+#[derive(Debug)]
+pub struct RateLimits {
+    limits: HashMap<KaspadMessagePayloadType, RateLimit>,
+    max_violations: u32,
+    banner: Option<Arc<dyn PeerBanner>>,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        use KaspadMessagePayloadType::*;
+        let limits = HashMap::from([
+            (InvRelayBlock, RateLimit::new(50.0, 500.0)),
+            (RequestRelayBlocks, RateLimit::new(50.0, 500.0)),
+            (InvTransactions, RateLimit::new(50.0, 500.0)),
+            (RequestTransactions, RateLimit::new(50.0, 500.0)),
+            (RequestAddresses, RateLimit::new(1.0, 10.0)),
+            (Ping, RateLimit::new(1.0, 10.0)),
+        ]);
+        Self { limits, max_violations: DEFAULT_MAX_VIOLATIONS, banner: None }
+    }
+}
+
+impl RateLimits {
+    pub fn new(limits: HashMap<KaspadMessagePayloadType, RateLimit>, max_violations: u32) -> Self {
+        Self { limits, max_violations, banner: None }
+    }
+
+    /// Scales the rate and burst of all limits by `factor`
+    pub fn scaled(self, factor: f64) -> Self {
+        let limits = self.limits.into_iter().map(|(t, l)| (t, RateLimit::new(l.rate * factor, l.burst * factor))).collect();
+        Self { limits, ..self }
+    }
+
+    /// Bans the peers disconnected for exceeding the limits using `banner`
+    pub fn with_banner(self, banner: Arc<dyn PeerBanner>) -> Self {
+        Self { banner: Some(banner), ..self }
+    }
+
+    pub fn limit(&self, msg_type: KaspadMessagePayloadType) -> Option<RateLimit> {
+        self.limits.get(&msg_type).copied()
+    }
+
+    pub fn max_violations(&self) -> u32 {
+        self.max_violations
+    }
+
+    /// Applies the consequences of a peer being disconnected for exceeding the limit of `msg_type`
+    pub(crate) fn on_disconnect(&self, address: SocketAddr, msg_type: KaspadMessagePayloadType) {
+        if let Some(banner) = self.banner.as_ref() {
+            banner.ban(address.ip().into(), format!("exceeded the rate limit of {msg_type:?} messages"));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    violations: u32,
+}
+
+/// Token buckets of the messages received from a single peer
+#[derive(Debug)]
+pub struct PeerRateLimiter {
+    limits: Arc<RateLimits>,
+    buckets: HashMap<KaspadMessagePayloadType, Bucket>,
+}
+
+impl PeerRateLimiter {
+    pub fn new(limits: Arc<RateLimits>) -> Self {
+        Self { limits, buckets: HashMap::new() }
+    }
+
+    pub fn limits(&self) -> &Arc<RateLimits> {
+        &self.limits
+    }
+
+    /// Accounts a message of type `msg_type` received at `now`. Violations are counted
+    /// until the bucket of the type is full again, ie. until the peer calmed down.
+    pub fn check(&mut self, msg_type: KaspadMessagePayloadType, now: Instant) -> RateLimitVerdict {
+        let Some(limit) = self.limits.limit(msg_type) else {
+            return RateLimitVerdict::Pass;
+        };
+        let bucket = self.buckets.entry(msg_type).or_insert(Bucket { tokens: limit.burst, updated: now, violations: 0 });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * limit.rate).min(limit.burst);
+        bucket.updated = now;
+        if bucket.tokens >= limit.burst {
+            bucket.violations = 0;
+        }
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            RateLimitVerdict::Pass
+        } else {
+            bucket.violations += 1;
+            if bucket.violations > self.limits.max_violations {
+                RateLimitVerdict::Disconnect
+            } else {
+                RateLimitVerdict::Throttle
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use KaspadMessagePayloadType::*;
+
+    #[derive(Debug, Default)]
+    struct PeerBannerMock {
+        bans: Mutex<Vec<(IpAddress, String)>>,
+    }
+
+    impl PeerBanner for PeerBannerMock {
+        fn ban(&self, ip: IpAddress, reason: String) {
+            self.bans.lock().unwrap().push((ip, reason));
+        }
+    }
+
+    fn limits(max_violations: u32) -> Arc<RateLimits> {
+        Arc::new(RateLimits::new(HashMap::from([(InvRelayBlock, RateLimit::new(10.0, 20.0))]), max_violations))
+    }
+
+    #[test]
+    fn test_rate_limit_throttle_then_disconnect() {
+        let mut limiter = PeerRateLimiter::new(limits(5));
+        let now = Instant::now();
+
+        // a burst up to the bucket capacity passes
+        assert!((0..20).all(|_| limiter.check(InvRelayBlock, now) == RateLimitVerdict::Pass));
+        // unlimited types always pass
+        assert!((0..100).all(|_| limiter.check(Block, now) == RateLimitVerdict::Pass));
+
+        // exceeding the rate throttles, and disconnects beyond the violations threshold
+        assert!((0..5).all(|_| limiter.check(InvRelayBlock, now) == RateLimitVerdict::Throttle));
+        assert_eq!(limiter.check(InvRelayBlock, now), RateLimitVerdict::Disconnect);
+    }
+
+    #[test]
+    fn test_rate_limit_refill() {
+        let mut limiter = PeerRateLimiter::new(limits(5));
+        let start = Instant::now();
+        assert!((0..20).all(|_| limiter.check(InvRelayBlock, start) == RateLimitVerdict::Pass));
+        assert!((0..5).all(|_| limiter.check(InvRelayBlock, start) == RateLimitVerdict::Throttle));
+
+        // messages at the sustained rate pass
+        let mut now = start;
+        for _ in 0..50 {
+            now += Duration::from_millis(110);
+            assert_eq!(limiter.check(InvRelayBlock, now), RateLimitVerdict::Pass);
+        }
+
+        // violations are forgotten once the bucket refilled
+        now += Duration::from_secs(2);
+        assert!((0..20).all(|_| limiter.check(InvRelayBlock, now) == RateLimitVerdict::Pass));
+        assert!((0..5).all(|_| limiter.check(InvRelayBlock, now) == RateLimitVerdict::Throttle));
+    }
+
+    #[test]
+    fn test_rate_limit_ban() {
+        let banner = Arc::new(PeerBannerMock::default());
+        let limits = RateLimits::default().scaled(0.1).with_banner(banner.clone());
+        assert_eq!(limits.limit(Ping), Some(RateLimit::new(0.1, 1.0)));
+
+        let address = SocketAddr::from(([10, 0, 0, 1], 50123));
+        limits.on_disconnect(address, Ping);
+        let bans = banner.bans.lock().unwrap();
+        assert_eq!(bans.len(), 1);
+        assert_eq!(bans[0].0, IpAddress::from(address.ip()));
+        assert!(bans[0].1.contains("Ping"));
+    }
+}
@@ -20,6 +20,7 @@ use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
 use tonic::Streaming;
 
 use super::peer::{PeerKey, PeerProperties};
+use super::rate_limit::{PeerRateLimiter, RateLimitVerdict, RateLimits};
 
 pub struct IncomingRoute {
     rx: MpscReceiver<KaspadMessage>,
@@ -139,6 +140,9 @@ pub struct Router {
 
     /// Used for managing router mutable state
     mutable_state: Mutex<RouterMutableState>,
+
+    /// Limits of the unsolicited messages received from this peer, if any
+    rate_limiter: Mutex<Option<PeerRateLimiter>>,
 }
 
 impl Display for Router {
@@ -193,6 +197,7 @@ impl Router {
             outgoing_route,
             hub_sender,
             mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender))),
+            rate_limiter: Mutex::new(None),
         });
 
         let router_clone = router.clone();
@@ -294,6 +299,11 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    /// Limits the rate of the unsolicited messages received from this peer
+    pub fn set_rate_limits(&self, limits: Arc<RateLimits>) {
+        *self.rate_limiter.lock() = Some(PeerRateLimiter::new(limits));
+    }
+
     pub fn incoming_flow_baseline_channel_size() -> usize {
         256
     }
@@ -374,6 +384,23 @@ impl Router {
             return Err(ProtocolError::from_reject_message(reject.reason));
         }
 
+        // Throttle peers flooding unsolicited messages and disconnect the persistent ones
+        if msg.response_id == BLANK_ROUTE_ID {
+            if let Some(rate_limiter) = self.rate_limiter.lock().as_mut() {
+                match rate_limiter.check(msg_type, Instant::now()) {
+                    RateLimitVerdict::Pass => {}
+                    RateLimitVerdict::Throttle => {
+                        trace!("P2P, throttling message of type {:?} from peer: {}", msg_type, self);
+                        return Ok(());
+                    }
+                    RateLimitVerdict::Disconnect => {
+                        rate_limiter.limits().on_disconnect(self.net_address, msg_type);
+                        return Err(ProtocolError::RateLimitExceeded(msg_type, self.to_string()));
+                    }
+                }
+            }
+        }
+
         let op = if msg.response_id != BLANK_ROUTE_ID {
             self.routing_map_by_id.read().get(&msg.response_id).cloned()
         } else {
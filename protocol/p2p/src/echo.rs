@@ -1,11 +1,12 @@
 use crate::{
     common::ProtocolError,
-    core::adaptor::ConnectionInitializer,
-    handshake::KaspadHandshake,
+    core::{adaptor::ConnectionInitializer, peer::PeerProperties},
+    handshake::{negotiate_protocol_version, KaspadHandshake},
     pb::{self, VersionMessage},
     IncomingRoute, KaspadMessagePayloadType, Router,
 };
 use kaspa_core::{debug, time::unix_now, trace, warn};
+use kaspa_utils::networking::PeerId;
 use std::sync::Arc;
 use tonic::async_trait;
 use uuid::Uuid;
@@ -86,18 +87,21 @@ impl EchoFlow {
     }
 }
 
+const ECHO_PROTOCOL_VERSION: u32 = 5;
+const ECHO_USER_AGENT: &str = "/echo:0.1/";
+
 /// An example initializer, performing handshake and registering a simple echo flow
 #[derive(Default)]
 pub struct EchoFlowInitializer {}
 
 fn build_dummy_version_message() -> VersionMessage {
     pb::VersionMessage {
-        protocol_version: 5,
+        protocol_version: ECHO_PROTOCOL_VERSION,
         services: 0,
         timestamp: unix_now() as i64,
         address: None,
         id: Vec::from(Uuid::new_v4().as_ref()),
-        user_agent: String::new(),
+        user_agent: ECHO_USER_AGENT.to_string(),
         disable_relay_tx: false,
         subnetwork_id: None,
         network: "kaspa-mainnet".to_string(),
@@ -128,7 +132,19 @@ impl ConnectionInitializer for EchoFlowInitializer {
 
         // Perform the handshake
         let peer_version_message = handshake.handshake(self_version_message).await?;
-        debug!("protocol versions - self: {}, peer: {}", 5, peer_version_message.protocol_version);
+        debug!("protocol versions - self: {}, peer: {}", ECHO_PROTOCOL_VERSION, peer_version_message.protocol_version);
+
+        let protocol_version =
+            negotiate_protocol_version(ECHO_PROTOCOL_VERSION, ECHO_PROTOCOL_VERSION, peer_version_message.protocol_version)?;
+
+        // Register the peer identity and properties
+        router.set_identity(PeerId::from_slice(&peer_version_message.id)?);
+        router.set_properties(Arc::new(PeerProperties {
+            user_agent: peer_version_message.user_agent.clone(),
+            advertised_protocol_version: peer_version_message.protocol_version,
+            protocol_version,
+            ..Default::default()
+        }));
 
         // Subscribe to remaining messages. In this example we simply subscribe to all messages with a single echo flow
         EchoFlow::register(router.clone()).await;
@@ -201,4 +217,52 @@ mod tests {
         drop(adaptor2);
         tokio::time::sleep(std::time::Duration::from_secs(2)).await;
     }
+
+    #[tokio::test]
+    async fn test_active_peers_info() {
+        kaspa_core::log::try_init_logger("debug");
+
+        let adaptor = |port: u16| {
+            let address = NetAddress::from_str(&format!("[::1]:{port}")).unwrap();
+            Adaptor::bidirectional(address, Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default()).unwrap()
+        };
+        let hub_adaptor = adaptor(50055);
+        let others = [adaptor(50056), adaptor(50057), adaptor(50058)];
+
+        // `others[0]` is connected outbound while `others[1..]` are connecting inbound
+        let mut keys = vec![];
+        keys.push(hub_adaptor.connect_peer_with_retries(String::from("[::1]:50056"), 16, Duration::from_secs(1)).await.unwrap());
+        for other in others[1..].iter() {
+            other.connect_peer_with_retries(String::from("[::1]:50055"), 16, Duration::from_secs(1)).await.unwrap();
+        }
+
+        // Wait for handshake completion
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+        let peers = hub_adaptor.active_peers();
+        assert_eq!(peers.len(), 3, "handshake failed -- some peers are missing");
+        assert_eq!(peers.iter().filter(|peer| peer.is_outbound()).count(), 1);
+        assert!(peers.iter().find(|peer| peer.is_outbound()).unwrap().net_address().port() == 50056);
+        for peer in peers.iter() {
+            let properties = peer.properties();
+            assert_eq!(properties.user_agent, ECHO_USER_AGENT);
+            assert_eq!(properties.advertised_protocol_version, ECHO_PROTOCOL_VERSION);
+            assert_eq!(properties.protocol_version, ECHO_PROTOCOL_VERSION);
+            assert!(peer.time_connected() >= 2000, "the connection duration is too short");
+        }
+
+        // The snapshot taken before a disconnection is unaffected by it
+        hub_adaptor.terminate(keys.pop().unwrap()).await;
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        assert_eq!(peers.len(), 3);
+        let peers = hub_adaptor.active_peers();
+        assert_eq!(peers.len(), 2);
+        assert!(peers.iter().all(|peer| !peer.is_outbound()));
+
+        hub_adaptor.close().await;
+        for other in others.iter() {
+            other.close().await;
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    }
 }
@@ -5,6 +5,15 @@ use crate::{common::ProtocolError, dequeue_with_timeout, make_message};
 use crate::{IncomingRoute, KaspadMessagePayloadType, Router};
 use kaspa_core::debug;
 
+/// Negotiates the protocol version applied to a connection, ie. the highest version supported by both
+/// this node and the peer, provided the peer supports at least `min_version`
+pub fn negotiate_protocol_version(self_version: u32, min_version: u32, peer_version: u32) -> Result<u32, ProtocolError> {
+    if peer_version < min_version {
+        return Err(ProtocolError::ProtocolVersionTooOld(peer_version, min_version));
+    }
+    Ok(self_version.min(peer_version))
+}
+
 /// Implements the Kaspa peer-to-peer handshake protocol
 pub struct KaspadHandshake<'a> {
     router: &'a Router,
@@ -78,3 +87,19 @@ impl<'a> KaspadHandshake<'a> {
         recv_res
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_protocol_version() {
+        // equal and newer peer versions negotiate down to the local version
+        assert_eq!(negotiate_protocol_version(6, 5, 6).unwrap(), 6);
+        assert_eq!(negotiate_protocol_version(6, 5, 7).unwrap(), 6);
+        // older peer versions are applied as long as they meet the minimum
+        assert_eq!(negotiate_protocol_version(6, 5, 5).unwrap(), 5);
+        assert!(matches!(negotiate_protocol_version(6, 5, 4), Err(ProtocolError::ProtocolVersionTooOld(4, 5))));
+        assert!(matches!(negotiate_protocol_version(6, 6, 5), Err(ProtocolError::ProtocolVersionTooOld(5, 6))));
+    }
+}
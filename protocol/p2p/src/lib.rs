@@ -15,5 +15,6 @@ pub use crate::core::connection_handler::ConnectionError;
 pub use crate::core::hub::Hub;
 pub use crate::core::payload_type::KaspadMessagePayloadType;
 pub use crate::core::peer::{Peer, PeerKey, PeerProperties};
+pub use crate::core::rate_limit::{PeerBanner, PeerRateLimiter, RateLimit, RateLimitVerdict, RateLimits};
 pub use crate::core::router::{IncomingRoute, Router, SharedIncomingRoute, BLANK_ROUTE_ID};
-pub use handshake::KaspadHandshake;
+pub use handshake::{negotiate_protocol_version, KaspadHandshake};
@@ -0,0 +1,145 @@
+//!
+//! Token authentication of RPC connections.
+//!
+//! A wRPC server configured with authentication tokens expects the client to send
+//! its token as the first WebSocket message of the connection. The server acknowledges
+//! a valid token with [`WRPC_AUTH_ACCEPTED`] before any RPC traffic, and closes the
+//! connection otherwise.
+//!
+//! A gRPC client sends its token in the [`GRPC_AUTH_TOKEN_METADATA_KEY`] metadata of the
+//! message stream request, which the server refuses if the token is missing or invalid.
+//!
+//! The token also determines the [`RpcAccessLevel`] granted to the connection, which
+//! gates the RPC methods it is allowed to call (see [`RpcApiOps::access_level`]).
+//!
+
+use crate::{api::ops::RpcApiOps, RpcError, RpcResult};
+use std::fmt::{Display, Formatter};
+
+/// Access level required by an RPC method, or granted to an RPC connection.
+/// Each level includes the methods of the lower ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RpcAccessLevel {
+    /// Querying the node and subscribing to notifications
+    Read,
+    /// Submitting blocks and transactions, mining
+    Write,
+    /// Managing peers and the node itself
+    Admin,
+}
+
+impl RpcAccessLevel {
+    /// Checks that a connection granted this access level may call `op`
+    pub fn authorize(self, op: RpcApiOps) -> RpcResult<()> {
+        let required = op.access_level();
+        if required <= self {
+            Ok(())
+        } else {
+            Err(RpcError::AccessDenied(op, required, self))
+        }
+    }
+}
+
+impl Display for RpcAccessLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RpcAccessLevel::Read => "read",
+            RpcAccessLevel::Write => "write",
+            RpcAccessLevel::Admin => "admin",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Tokens an RPC server authenticates its connections with, along with the access level each one grants.
+/// Connections are not authenticated and are granted [`RpcAccessLevel::Admin`] if no token is set.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RpcAuthTokens {
+    tokens: Vec<(String, RpcAccessLevel)>,
+}
+
+impl RpcAuthTokens {
+    pub fn new(admin: Option<String>, write: Option<String>, read: Option<String>) -> Self {
+        let tokens = [(admin, RpcAccessLevel::Admin), (write, RpcAccessLevel::Write), (read, RpcAccessLevel::Read)]
+            .into_iter()
+            .filter_map(|(token, access_level)| token.map(|token| (token, access_level)))
+            .collect();
+        Self { tokens }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Returns the access level granted by the `provided` token, or `None` if it matches no token
+    pub fn access_level(&self, provided: &str) -> Option<RpcAccessLevel> {
+        self.tokens.iter().find(|(token, _)| is_auth_token_valid(token, provided)).map(|(_, access_level)| *access_level)
+    }
+}
+
+/// Message sent by the server to acknowledge a valid authentication token
+pub const WRPC_AUTH_ACCEPTED: &str = "kaspa-wrpc-auth-accepted";
+
+/// Metadata key of the authentication token in a gRPC message stream request
+pub const GRPC_AUTH_TOKEN_METADATA_KEY: &str = "kaspa-auth-token";
+
+/// Returns whether the `provided` token matches the `expected` one. The comparison
+/// time does not depend on the position of the first mismatching byte.
+pub fn is_auth_token_valid(expected: &str, provided: &str) -> bool {
+    let (expected, provided) = (expected.as_bytes(), provided.as_bytes());
+    expected.len() == provided.len() && expected.iter().zip(provided).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_auth_token_valid() {
+        assert!(is_auth_token_valid("secret", "secret"));
+        assert!(!is_auth_token_valid("secret", "secreT"));
+        assert!(!is_auth_token_valid("secret", "secret "));
+        assert!(!is_auth_token_valid("secret", ""));
+        assert!(!is_auth_token_valid("secret", WRPC_AUTH_ACCEPTED));
+    }
+
+    #[test]
+    fn test_rpc_auth_tokens() {
+        assert!(RpcAuthTokens::default().is_empty());
+        let tokens = RpcAuthTokens::new(Some("secret".to_string()), Some("miner".to_string()), Some("public".to_string()));
+        assert_eq!(tokens.access_level("secret"), Some(RpcAccessLevel::Admin));
+        assert_eq!(tokens.access_level("miner"), Some(RpcAccessLevel::Write));
+        assert_eq!(tokens.access_level("public"), Some(RpcAccessLevel::Read));
+        assert_eq!(tokens.access_level("guess"), None);
+        assert_eq!(tokens.access_level(""), None);
+
+        let tokens = RpcAuthTokens::new(None, None, Some("public".to_string()));
+        assert!(!tokens.is_empty());
+        assert_eq!(tokens.access_level("public"), Some(RpcAccessLevel::Read));
+        assert_eq!(tokens.access_level("secret"), None);
+    }
+
+    #[test]
+    fn test_rpc_access_level() {
+        let read_only = RpcAccessLevel::Read;
+        for op in [RpcApiOps::GetInfo, RpcApiOps::GetBlock, RpcApiOps::GetUtxosByAddresses, RpcApiOps::NotifyBlockAdded] {
+            assert!(read_only.authorize(op).is_ok(), "{op:?}");
+        }
+        for op in [RpcApiOps::SubmitTransaction, RpcApiOps::SubmitBlock, RpcApiOps::Ban, RpcApiOps::Shutdown, RpcApiOps::SetLogLevel] {
+            assert!(matches!(read_only.authorize(op), Err(RpcError::AccessDenied(denied, _, RpcAccessLevel::Read)) if denied == op));
+        }
+
+        let write = RpcAccessLevel::Write;
+        assert!(write.authorize(RpcApiOps::GetInfo).is_ok());
+        assert!(write.authorize(RpcApiOps::SubmitTransaction).is_ok());
+        assert!(write.authorize(RpcApiOps::Shutdown).is_err());
+
+        for op in RpcApiOps::list() {
+            assert!(RpcAccessLevel::Admin.authorize(op).is_ok(), "{op:?}");
+        }
+        assert_eq!(
+            read_only.authorize(RpcApiOps::SubmitTransaction).unwrap_err().to_string(),
+            "Method SubmitTransaction requires write access, the connection is granted read access."
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod auth;
 pub mod ctl;
 pub mod notifications;
 pub mod ops;
@@ -1,3 +1,4 @@
+use crate::api::auth::RpcAccessLevel;
 use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_notify::events::EventType;
 use serde::{Deserialize, Serialize};
@@ -13,7 +14,7 @@ use workflow_core::enums::Describe;
 /// or using Serde attributes. This applies only to RPC infrastructure that uses internal
 /// data structures and does not affect gRPC. gRPC should issue and handle its
 /// own versioning.
-pub const RPC_API_VERSION: [u16; 4] = [0, 1, 0, 0];
+pub const RPC_API_VERSION: [u16; 4] = [0, 2, 0, 0];
 
 #[derive(Describe, Clone, Copy, Debug, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -86,6 +87,12 @@ pub enum RpcApiOps {
     GetCoinSupply,
     /// Get DAA Score timestamp estimate
     GetDaaScoreTimestampEstimate,
+    /// Get the list of banned peers
+    GetBannedPeers,
+    /// Get the fee rates estimated from the mempool
+    GetFeeEstimate,
+    /// Set the log level of a node subsystem
+    SetLogLevel,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -114,6 +121,10 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification,
     PruningPointUtxoSetOverrideNotification,
     NewBlockTemplateNotification,
+
+    // Muting commands for pausing/resuming notifications without unsubscribing
+    Mute,
+    Unmute,
 }
 
 impl RpcApiOps {
@@ -133,6 +144,70 @@ impl RpcApiOps {
                 | RpcApiOps::Unsubscribe
         )
     }
+
+    /// Access level a connection must be granted to call this method
+    pub fn access_level(&self) -> RpcAccessLevel {
+        use RpcApiOps::*;
+        // Every op is listed explicitly so that adding a new op requires classifying it
+        match self {
+            Ping
+            | GetMetrics
+            | GetServerInfo
+            | GetSyncStatus
+            | GetCurrentNetwork
+            | GetSink
+            | GetMempoolEntry
+            | GetMempoolEntries
+            | GetBlock
+            | GetSubnetwork
+            | GetVirtualChainFromBlock
+            | GetBlocks
+            | GetBlockCount
+            | GetBlockDagInfo
+            | GetHeaders
+            | GetUtxosByAddresses
+            | GetBalanceByAddress
+            | GetBalancesByAddresses
+            | GetSinkBlueScore
+            | GetInfo
+            | EstimateNetworkHashesPerSecond
+            | GetMempoolEntriesByAddresses
+            | GetCoinSupply
+            | GetDaaScoreTimestampEstimate
+            | GetFeeEstimate => RpcAccessLevel::Read,
+
+            NotifyBlockAdded
+            | NotifyNewBlockTemplate
+            | NotifyUtxosChanged
+            | NotifyPruningPointUtxoSetOverride
+            | NotifyFinalityConflict
+            | NotifyFinalityConflictResolved
+            | NotifyVirtualDaaScoreChanged
+            | NotifyVirtualChainChanged
+            | NotifySinkBlueScoreChanged
+            | Subscribe
+            | Unsubscribe
+            | Mute
+            | Unmute => RpcAccessLevel::Read,
+
+            // Notifications are sent by the server, so are never called by a client
+            BlockAddedNotification
+            | VirtualChainChangedNotification
+            | FinalityConflictNotification
+            | FinalityConflictResolvedNotification
+            | UtxosChangedNotification
+            | SinkBlueScoreChangedNotification
+            | VirtualDaaScoreChangedNotification
+            | PruningPointUtxoSetOverrideNotification
+            | NewBlockTemplateNotification => RpcAccessLevel::Read,
+
+            SubmitBlock | SubmitTransaction | GetBlockTemplate => RpcAccessLevel::Write,
+
+            // Peer addresses are not exposed to regular users
+            GetPeerAddresses | GetConnectedPeerInfo | GetBannedPeers => RpcAccessLevel::Admin,
+            AddPeer | Ban | Unban | ResolveFinalityConflict | Shutdown | SetLogLevel => RpcAccessLevel::Admin,
+        }
+    }
 }
 
 impl From<RpcApiOps> for u32 {
@@ -7,7 +7,7 @@
 use crate::{model::*, notify::connection::ChannelConnection, RpcResult};
 use async_trait::async_trait;
 use downcast::{downcast_sync, AnySync};
-use kaspa_notify::{listener::ListenerId, scope::Scope, subscription::Command};
+use kaspa_notify::{events::EventType, listener::ListenerId, scope::Scope, subscription::Command};
 use std::sync::Arc;
 
 pub const MAX_SAFE_WINDOW_SIZE: u32 = 10_000;
@@ -128,7 +128,7 @@ pub trait RpcApi: Sync + Send + AnySync {
 
     /// Submits a transaction to the mempool.
     async fn submit_transaction(&self, transaction: RpcTransaction, allow_orphan: bool) -> RpcResult<RpcTransactionId> {
-        Ok(self.submit_transaction_call(SubmitTransactionRequest { transaction, allow_orphan }).await?.transaction_id)
+        Ok(self.submit_transaction_call(SubmitTransactionRequest { transaction, allow_orphan }).await?.into_result()?.transaction_id)
     }
     async fn submit_transaction_call(&self, request: SubmitTransactionRequest) -> RpcResult<SubmitTransactionResponse>;
 
@@ -304,6 +304,25 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetDaaScoreTimestampEstimateRequest,
     ) -> RpcResult<GetDaaScoreTimestampEstimateResponse>;
 
+    /// Returns the peers currently banned.
+    async fn get_banned_peers(&self) -> RpcResult<Vec<RpcBannedPeer>> {
+        Ok(self.get_banned_peers_call(GetBannedPeersRequest {}).await?.banned_peers)
+    }
+    async fn get_banned_peers_call(&self, request: GetBannedPeersRequest) -> RpcResult<GetBannedPeersResponse>;
+
+    /// Returns the fee rates, in sompi per gram of mass, estimated from the content of the mempool.
+    async fn get_fee_estimate(&self) -> RpcResult<RpcFeeEstimate> {
+        Ok(self.get_fee_estimate_call(GetFeeEstimateRequest {}).await?.estimate)
+    }
+    async fn get_fee_estimate_call(&self, request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse>;
+
+    /// Sets the log level of a node subsystem (ie. `kaspa_p2p_flows`), or the default level of the node if `subsystem` is `None`.
+    async fn set_log_level(&self, subsystem: Option<String>, level: String) -> RpcResult<()> {
+        self.set_log_level_call(SetLogLevelRequest::new(subsystem, level)).await?;
+        Ok(())
+    }
+    async fn set_log_level_call(&self, request: SetLogLevelRequest) -> RpcResult<SetLogLevelResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -321,6 +340,13 @@ pub trait RpcApi: Sync + Send + AnySync {
     /// Stop sending notifications of some type to a listener.
     async fn stop_notify(&self, id: ListenerId, scope: Scope) -> RpcResult<()>;
 
+    /// Stop sending notifications of some type to a listener while keeping its subscription,
+    /// so that [`RpcApi::unmute_notify`] resumes them without having to subscribe again.
+    async fn mute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()>;
+
+    /// Resume sending notifications of some type muted by [`RpcApi::mute_notify`] to a listener.
+    async fn unmute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()>;
+
     /// Execute a subscription command leading to either start or stop sending notifications
     /// of some type to a listener.
     async fn execute_subscribe_command(&self, id: ListenerId, scope: Scope, command: Command) -> RpcResult<()> {
@@ -1,10 +1,19 @@
-use kaspa_consensus_core::tx::TransactionId;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use kaspa_consensus_core::{errors::tx::TxRuleError, tx::TransactionId};
+use kaspa_mining_errors::{
+    manager::MiningManagerError,
+    mempool::{NonStandardError, RuleError as MempoolRuleError},
+};
 use kaspa_utils::networking::IpAddress;
+use serde::{Deserialize, Serialize};
 use std::{net::AddrParseError, num::TryFromIntError};
 use thiserror::Error;
 use workflow_core::channel::ChannelError;
 
-use crate::{api::ctl::RpcState, RpcHash, RpcTransactionId, SubmitBlockRejectReason};
+use crate::{
+    api::{auth::RpcAccessLevel, ctl::RpcState, ops::RpcApiOps},
+    RpcHash, RpcTransactionId, SubmitBlockRejectReason,
+};
 
 #[derive(Clone, Debug, Error)]
 pub enum RpcError {
@@ -47,6 +56,9 @@ pub enum RpcError {
     #[error("Rejected transaction {0}: {1}")]
     RejectedTransaction(RpcTransactionId, String),
 
+    #[error("Rejected transaction {0}: {1}")]
+    SubmitTransactionError(RpcTransactionId, RpcSubmitTransactionError),
+
     #[error("Block {0} is invalid. No verbose data can be built.")]
     InvalidBlock(RpcHash),
 
@@ -71,6 +83,9 @@ pub enum RpcError {
     #[error("Method unavailable in safe mode. Run the node with --unsafe argument.")]
     UnavailableInSafeMode,
 
+    #[error("Method {0:?} requires {1} access, the connection is granted {2} access.")]
+    AccessDenied(RpcApiOps, RpcAccessLevel, RpcAccessLevel),
+
     #[error("Cannot ban IP {0} because it has some permanent connection.")]
     IpHasPermanentConnection(IpAddress),
 
@@ -117,6 +132,93 @@ pub enum RpcError {
     InconsistentMempoolTxQuery,
 }
 
+/// Reason of the rejection of a transaction submitted to the node
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase", tag = "type", content = "data")]
+pub enum RpcSubmitTransactionError {
+    #[error("transaction is an orphan: at least one of its inputs is missing")]
+    Orphan,
+
+    #[error("transaction is already in the mempool")]
+    Duplicate,
+
+    #[error("transaction was already accepted by the consensus")]
+    AlreadyAccepted,
+
+    #[error("transaction double spends an input of transaction {0}")]
+    DoubleSpend(RpcTransactionId),
+
+    #[error("transaction has {0} fees which is under the required amount of {1}")]
+    InsufficientFee(u64, u64),
+
+    #[error("transaction output #{0}: payment of {1} is dust")]
+    Dust(u32, u64),
+
+    #[error("transaction mass of {0} is larger than max allowed size of {1}")]
+    MassTooHigh(u64, u64),
+
+    #[error("transaction is not standard: {0}")]
+    NonStandard(String),
+
+    #[error("failed to verify a signature script: {0}")]
+    InvalidSignature(String),
+
+    #[error("transaction spends an immature UTXO")]
+    ImmatureSpend,
+
+    #[error("the mempool is full")]
+    MempoolFull,
+
+    #[error("transaction was rejected as spam")]
+    Spam,
+
+    #[error("transaction is invalid: {0}")]
+    Invalid(String),
+}
+
+impl RpcSubmitTransactionError {
+    /// Returns `true` if the node already has the transaction
+    pub fn is_already_known(&self) -> bool {
+        matches!(self, Self::Duplicate | Self::AlreadyAccepted)
+    }
+}
+
+impl From<&MempoolRuleError> for RpcSubmitTransactionError {
+    fn from(err: &MempoolRuleError) -> Self {
+        use MempoolRuleError::*;
+        match err {
+            RejectTxRule(TxRuleError::SignatureInvalid(err)) => Self::InvalidSignature(err.to_string()),
+            RejectTxRule(err) => Self::Invalid(err.to_string()),
+            RejectMissingOutpoint | RejectDisallowedOrphan(_) => Self::Orphan,
+            RejectAlreadyAccepted(_) => Self::AlreadyAccepted,
+            RejectDuplicate(_) | RejectDuplicateOrphan(_) => Self::Duplicate,
+            RejectDoubleSpendInMempool(_, id) | RejectDoubleSpendOrphan(_, id) => Self::DoubleSpend(*id),
+            RejectMempoolIsFull(_, _) | RejectOrphanPoolIsFull(_, _) => Self::MempoolFull,
+            RejectNonStandard(_, err) => match err {
+                NonStandardError::RejectInsufficientFee(_, fee, required) => Self::InsufficientFee(*fee, *required),
+                NonStandardError::RejectDust(_, index, value) => Self::Dust(*index as u32, *value),
+                NonStandardError::RejectMass(_, mass, max) | NonStandardError::RejectContextualMass(_, mass, max) => {
+                    Self::MassTooHigh(*mass, *max)
+                }
+                err => Self::NonStandard(err.to_string()),
+            },
+            RejectImmatureSpend(_) => Self::ImmatureSpend,
+            RejectBadOrphanMass(mass, max) => Self::MassTooHigh(*mass, *max),
+            RejectSpamTransaction(_) => Self::Spam,
+            err => Self::Invalid(err.to_string()),
+        }
+    }
+}
+
+impl From<&MiningManagerError> for RpcSubmitTransactionError {
+    fn from(err: &MiningManagerError) -> Self {
+        match err {
+            MiningManagerError::MempoolError(err) => err.into(),
+            err => Self::Invalid(err.to_string()),
+        }
+    }
+}
+
 impl From<String> for RpcError {
     fn from(value: String) -> Self {
         RpcError::General(value)
@@ -136,3 +238,93 @@ impl From<ChannelError<RpcState>> for RpcError {
 }
 
 pub type RpcResult<T> = std::result::Result<T, crate::RpcError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::tx::TransactionOutpoint;
+    use kaspa_txscript_errors::TxScriptError;
+
+    #[test]
+    fn test_submit_transaction_error_mapping() {
+        let id = TransactionId::from_bytes([1; 32]);
+        let other = TransactionId::from_bytes([2; 32]);
+        let outpoint = TransactionOutpoint::new(other, 0);
+        let tests = vec![
+            (MempoolRuleError::RejectDisallowedOrphan(id), RpcSubmitTransactionError::Orphan),
+            (MempoolRuleError::RejectMissingOutpoint, RpcSubmitTransactionError::Orphan),
+            (MempoolRuleError::RejectDuplicate(id), RpcSubmitTransactionError::Duplicate),
+            (MempoolRuleError::RejectDuplicateOrphan(id), RpcSubmitTransactionError::Duplicate),
+            (MempoolRuleError::RejectAlreadyAccepted(id), RpcSubmitTransactionError::AlreadyAccepted),
+            (MempoolRuleError::RejectDoubleSpendInMempool(outpoint, other), RpcSubmitTransactionError::DoubleSpend(other)),
+            (MempoolRuleError::RejectDoubleSpendOrphan(id, other), RpcSubmitTransactionError::DoubleSpend(other)),
+            (NonStandardError::RejectInsufficientFee(id, 10, 2036).into(), RpcSubmitTransactionError::InsufficientFee(10, 2036)),
+            (NonStandardError::RejectDust(id, 1, 500).into(), RpcSubmitTransactionError::Dust(1, 500)),
+            (NonStandardError::RejectMass(id, 200_000, 100_000).into(), RpcSubmitTransactionError::MassTooHigh(200_000, 100_000)),
+            (MempoolRuleError::RejectBadOrphanMass(200_000, 100_000), RpcSubmitTransactionError::MassTooHigh(200_000, 100_000)),
+            (
+                NonStandardError::RejectOutputScriptClass(id, 0).into(),
+                RpcSubmitTransactionError::NonStandard("transaction output #0: non-standard script form".to_string()),
+            ),
+            (
+                TxRuleError::SignatureInvalid(TxScriptError::NullFail).into(),
+                RpcSubmitTransactionError::InvalidSignature(TxScriptError::NullFail.to_string()),
+            ),
+            (TxRuleError::ImmatureCoinbaseSpend(0, outpoint, 1, 2, 3).into(), RpcSubmitTransactionError::ImmatureSpend),
+            (MempoolRuleError::RejectMempoolIsFull(10, 10), RpcSubmitTransactionError::MempoolFull),
+            (MempoolRuleError::RejectOrphanPoolIsFull(10, 10), RpcSubmitTransactionError::MempoolFull),
+            (MempoolRuleError::RejectSpamTransaction(id), RpcSubmitTransactionError::Spam),
+            (TxRuleError::NoTxInputs.into(), RpcSubmitTransactionError::Invalid(TxRuleError::NoTxInputs.to_string())),
+        ];
+
+        for (rule_error, expected) in tests {
+            assert_eq!(RpcSubmitTransactionError::from(&rule_error), expected, "wrong mapping of {rule_error}");
+            let mining_error = MiningManagerError::MempoolError(rule_error);
+            assert_eq!(RpcSubmitTransactionError::from(&mining_error), expected);
+        }
+        assert!(RpcSubmitTransactionError::Duplicate.is_already_known());
+        assert!(RpcSubmitTransactionError::AlreadyAccepted.is_already_known());
+        assert!(!RpcSubmitTransactionError::Orphan.is_already_known());
+    }
+
+    #[test]
+    fn test_submit_transaction_response_encodings() {
+        let id = TransactionId::from_bytes([1; 32]);
+        let reasons = vec![
+            None,
+            Some(RpcSubmitTransactionError::Orphan),
+            Some(RpcSubmitTransactionError::DoubleSpend(TransactionId::from_bytes([2; 32]))),
+            Some(RpcSubmitTransactionError::InsufficientFee(10, 2036)),
+            Some(RpcSubmitTransactionError::Dust(1, 500)),
+            Some(RpcSubmitTransactionError::NonStandard("non-standard script form".to_string())),
+        ];
+
+        for reject_reason in reasons {
+            let response = crate::SubmitTransactionResponse { transaction_id: id, reject_reason: reject_reason.clone() };
+
+            let borsh_bytes = response.try_to_vec().unwrap();
+            let decoded = crate::SubmitTransactionResponse::try_from_slice(&borsh_bytes).unwrap();
+            assert_eq!(decoded.transaction_id, id);
+            assert_eq!(decoded.reject_reason, reject_reason);
+
+            let json = serde_json::to_string(&response).unwrap();
+            let decoded: crate::SubmitTransactionResponse = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded.transaction_id, id);
+            assert_eq!(decoded.reject_reason, reject_reason);
+
+            match (decoded.into_result(), reject_reason) {
+                (Ok(response), None) => assert_eq!(response.transaction_id, id),
+                (Err(RpcError::SubmitTransactionError(err_id, err)), Some(reason)) => {
+                    assert_eq!(err_id, id);
+                    assert_eq!(err, reason);
+                }
+                (result, reason) => panic!("unexpected result {result:?} for reject reason {reason:?}"),
+            }
+        }
+
+        // responses of nodes unaware of the reject reason
+        let json = format!("{{\"transactionId\":\"{id}\"}}");
+        let decoded: crate::SubmitTransactionResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.reject_reason, None);
+    }
+}
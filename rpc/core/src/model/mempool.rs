@@ -28,3 +28,35 @@ impl RpcMempoolEntryByAddress {
         Self { address, sending, receiving }
     }
 }
+
+/// A fee rate, in sompi per gram of mass, along with the estimated time, in seconds,
+/// within which a transaction paying it is expected to be mined.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeeRateBucket {
+    pub feerate: f64,
+    pub estimated_seconds: f64,
+}
+
+impl RpcFeeRateBucket {
+    pub fn new(feerate: f64, estimated_seconds: f64) -> Self {
+        Self { feerate, estimated_seconds }
+    }
+}
+
+/// Fee rate buckets estimated by the node from the content of its mempool. A transaction paying
+/// the `priority`, `normal` or `low` bucket rate is expected to be mined within the next block,
+/// the next 10 blocks or the next 60 blocks respectively.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeeEstimate {
+    pub priority_bucket: RpcFeeRateBucket,
+    pub normal_bucket: RpcFeeRateBucket,
+    pub low_bucket: RpcFeeRateBucket,
+}
+
+impl RpcFeeEstimate {
+    pub fn new(priority_bucket: RpcFeeRateBucket, normal_bucket: RpcFeeRateBucket, low_bucket: RpcFeeRateBucket) -> Self {
+        Self { priority_bucket, normal_bucket, low_bucket }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::model::*;
+use crate::{RpcError, RpcResult, RpcSubmitTransactionError};
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use kaspa_consensus_core::api::stats::BlockCount;
 use kaspa_core::debug;
@@ -289,11 +290,26 @@ impl SubmitTransactionRequest {
 #[serde(rename_all = "camelCase")]
 pub struct SubmitTransactionResponse {
     pub transaction_id: RpcTransactionId,
+    /// Reason of the rejection of the transaction by the mempool, if rejected
+    #[serde(default)]
+    pub reject_reason: Option<RpcSubmitTransactionError>,
 }
 
 impl SubmitTransactionResponse {
     pub fn new(transaction_id: RpcTransactionId) -> Self {
-        Self { transaction_id }
+        Self { transaction_id, reject_reason: None }
+    }
+
+    pub fn new_rejected(transaction_id: RpcTransactionId, reject_reason: RpcSubmitTransactionError) -> Self {
+        Self { transaction_id, reject_reason: Some(reject_reason) }
+    }
+
+    /// Converts a rejection into a [`RpcError::SubmitTransactionError`]
+    pub fn into_result(self) -> RpcResult<Self> {
+        match self.reject_reason {
+            Some(reject_reason) => Err(RpcError::SubmitTransactionError(self.transaction_id, reject_reason)),
+            None => Ok(self),
+        }
     }
 }
 
@@ -574,11 +590,22 @@ impl GetUtxosByAddressesResponse {
 #[serde(rename_all = "camelCase")]
 pub struct BanRequest {
     pub ip: RpcIpAddress,
+    /// Duration of the ban in seconds, the node default if `None`
+    pub duration: Option<u64>,
+    pub reason: Option<String>,
 }
 
 impl BanRequest {
     pub fn new(ip: RpcIpAddress) -> Self {
-        Self { ip }
+        Self { ip, duration: None, reason: None }
+    }
+
+    pub fn with_duration(self, duration: u64) -> Self {
+        Self { duration: Some(duration), ..self }
+    }
+
+    pub fn with_reason(self, reason: String) -> Self {
+        Self { reason: Some(reason), ..self }
     }
 }
 
@@ -702,6 +729,29 @@ pub struct ProcessMetrics {
     pub disk_io_write_bytes: u64,
     pub disk_io_read_per_sec: f32,
     pub disk_io_write_per_sec: f32,
+
+    pub services: Vec<ServiceMetrics>,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcServiceState {
+    Registered,
+    Running,
+    Stopped,
+    Failed,
+}
+
+/// Metrics of a service running in the node
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceMetrics {
+    pub ident: String,
+    pub state: RpcServiceState,
+    /// Time the service was started at, in milliseconds since UNIX EPOCH
+    pub start_time: Option<u64>,
+    /// Error returned by the service or message of the panic it raised
+    pub last_error: Option<String>,
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
@@ -750,6 +800,9 @@ pub struct ConsensusMetrics {
     pub network_past_median_time: u64,
     pub network_virtual_parent_hashes_count: u32,
     pub network_virtual_daa_score: u64,
+
+    /// Estimated time to sync in seconds (`Some(0)` if the node is synced, `None` if the estimate is not available)
+    pub node_sync_eta_seconds: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
@@ -787,6 +840,10 @@ pub struct GetServerInfoResponse {
     pub has_utxo_index: bool,
     pub is_synced: bool,
     pub virtual_daa_score: u64,
+    /// P2P protocol version of the server
+    pub p2p_protocol_version: u32,
+    /// Number of transactions in the mempool, orphans excluded
+    pub mempool_size: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
@@ -823,6 +880,57 @@ impl GetDaaScoreTimestampEstimateResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBannedPeersRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBannedPeersResponse {
+    pub banned_peers: Vec<RpcBannedPeer>,
+}
+
+impl GetBannedPeersResponse {
+    pub fn new(banned_peers: Vec<RpcBannedPeer>) -> Self {
+        Self { banned_peers }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeEstimateRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeEstimateResponse {
+    pub estimate: RpcFeeEstimate,
+}
+
+impl GetFeeEstimateResponse {
+    pub fn new(estimate: RpcFeeEstimate) -> Self {
+        Self { estimate }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetLogLevelRequest {
+    /// Crate or module path of the subsystem, the default level of the node if `None`
+    pub subsystem: Option<String>,
+    /// One of `off`, `error`, `warn`, `info`, `debug` or `trace`
+    pub level: String,
+}
+
+impl SetLogLevelRequest {
+    pub fn new(subsystem: Option<String>, level: String) -> Self {
+        Self { subsystem, level }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetLogLevelResponse {}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
@@ -1143,3 +1251,17 @@ impl SubscribeResponse {
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UnsubscribeResponse {}
+
+///
+///  wRPC response for RpcApiOps::Mute request
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MuteResponse {}
+
+///
+///  wRPC response for RpcApiOps::Unmute request
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UnmuteResponse {}
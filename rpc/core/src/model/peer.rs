@@ -18,6 +18,17 @@ pub struct RpcPeerInfo {
     pub user_agent: String,
 
     pub advertised_protocol_version: u32,
+    /// Protocol version applied to the connection
+    pub protocol_version: u32,
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     pub is_ibd_peer: bool,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct RpcBannedPeer {
+    /// Banned address, a port `0` meaning all ports of the IP
+    pub address: RpcPeerAddress,
+    pub reason: String,
+    pub banned_at: u64,  // NOTE: i64 in gRPC protowire
+    pub expires_at: u64, // NOTE: i64 in gRPC protowire
+}
@@ -225,6 +225,9 @@ impl RpcApi for GrpcClient {
     route!(get_mempool_entries_by_addresses_call, GetMempoolEntriesByAddresses);
     route!(get_coin_supply_call, GetCoinSupply);
     route!(get_daa_score_timestamp_estimate_call, GetDaaScoreTimestampEstimate);
+    route!(get_banned_peers_call, GetBannedPeers);
+    route!(get_fee_estimate_call, GetFeeEstimate);
+    route!(set_log_level_call, SetLogLevel);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -283,6 +286,31 @@ impl RpcApi for GrpcClient {
             Err(RpcError::UnsupportedFeature)
         }
     }
+
+    /// Stop sending notifications of some type to a listener while keeping its subscription.
+    ///
+    /// The gRPC protocol has no mute message, so the notifications are only muted
+    /// by the client-side notifier of the [`NotificationMode::MultiListeners`] mode.
+    async fn mute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()> {
+        match self.notification_mode {
+            NotificationMode::MultiListeners => {
+                self.notifier.as_ref().unwrap().try_mute(id, event)?;
+                Ok(())
+            }
+            NotificationMode::Direct => Err(RpcError::UnsupportedFeature),
+        }
+    }
+
+    /// Resume sending notifications of some type to a listener.
+    async fn unmute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()> {
+        match self.notification_mode {
+            NotificationMode::MultiListeners => {
+                self.notifier.as_ref().unwrap().try_unmute(id, event)?;
+                Ok(())
+            }
+            NotificationMode::Direct => Err(RpcError::UnsupportedFeature),
+        }
+    }
 }
 
 pub const CONNECT_TIMEOUT_DURATION: u64 = 20_000;
@@ -16,15 +16,17 @@
 //! of data fields and provide no error or provide no data fields but an error field in case
 //! of Err(RpcError).
 //!
-//! The SubmitBlockResponse is a notable exception to this general rule.
+//! The SubmitBlockResponse and SubmitTransactionResponse are notable exceptions to this general rule.
 
-use crate::protowire::{self, submit_block_response_message::RejectReason};
+use crate::protowire::{
+    self, rpc_submit_transaction_reject_reason::Kind as SubmitTransactionRejectKind, submit_block_response_message::RejectReason,
+};
 use kaspa_consensus_core::network::NetworkId;
 use kaspa_core::debug;
 use kaspa_notify::subscription::Command;
 use kaspa_rpc_core::{
     RpcContextualPeerAddress, RpcError, RpcExtraData, RpcHash, RpcIpAddress, RpcNetworkType, RpcPeerAddress, RpcResult,
-    SubmitBlockRejectReason, SubmitBlockReport,
+    RpcSubmitTransactionError, SubmitBlockRejectReason, SubmitBlockReport,
 };
 use std::str::FromStr;
 
@@ -244,8 +246,59 @@ from!(RpcResult<&kaspa_rpc_core::AddPeerResponse>, protowire::AddPeerResponseMes
 from!(item: &kaspa_rpc_core::SubmitTransactionRequest, protowire::SubmitTransactionRequestMessage, {
     Self { transaction: Some((&item.transaction).into()), allow_orphan: item.allow_orphan }
 });
+from!(item: &RpcSubmitTransactionError, protowire::RpcSubmitTransactionRejectReason, {
+    use SubmitTransactionRejectKind as Kind;
+    let mut message = Self::default();
+    let kind = match item {
+        RpcSubmitTransactionError::Orphan => Kind::Orphan,
+        RpcSubmitTransactionError::Duplicate => Kind::Duplicate,
+        RpcSubmitTransactionError::AlreadyAccepted => Kind::AlreadyAccepted,
+        RpcSubmitTransactionError::DoubleSpend(transaction_id) => {
+            message.transaction_id = transaction_id.to_string();
+            Kind::DoubleSpend
+        }
+        RpcSubmitTransactionError::InsufficientFee(fee, required) => {
+            (message.value, message.limit) = (*fee, *required);
+            Kind::InsufficientFee
+        }
+        RpcSubmitTransactionError::Dust(index, value) => {
+            (message.output_index, message.value) = (*index, *value);
+            Kind::Dust
+        }
+        RpcSubmitTransactionError::MassTooHigh(mass, max) => {
+            (message.value, message.limit) = (*mass, *max);
+            Kind::MassTooHigh
+        }
+        RpcSubmitTransactionError::NonStandard(reason) => {
+            message.message = reason.clone();
+            Kind::NonStandard
+        }
+        RpcSubmitTransactionError::InvalidSignature(reason) => {
+            message.message = reason.clone();
+            Kind::InvalidSignature
+        }
+        RpcSubmitTransactionError::ImmatureSpend => Kind::ImmatureSpend,
+        RpcSubmitTransactionError::MempoolFull => Kind::MempoolFull,
+        RpcSubmitTransactionError::Spam => Kind::Spam,
+        RpcSubmitTransactionError::Invalid(reason) => {
+            message.message = reason.clone();
+            Kind::Invalid
+        }
+    };
+    message.kind = kind as i32;
+    message
+});
+
+// This conversion breaks the general conversion convention (see file header) since a rejected
+// transaction is reported by a response providing both the reject reason and a matching error
+// message, the latter allowing clients unaware of the reject reason to detect the rejection.
 from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionResponse>, protowire::SubmitTransactionResponseMessage, {
-    Self { transaction_id: item.transaction_id.to_string(), error: None }
+    let reject_reason = item.reject_reason.as_ref().map(|reason| reason.into());
+    let error = item
+        .reject_reason
+        .as_ref()
+        .map(|reason| RpcError::SubmitTransactionError(item.transaction_id, reason.clone()).into());
+    Self { transaction_id: item.transaction_id.to_string(), reject_reason, error }
 });
 
 from!(item: &kaspa_rpc_core::GetSubnetworkRequest, protowire::GetSubnetworkRequestMessage, {
@@ -352,7 +405,9 @@ from!(item: RpcResult<&kaspa_rpc_core::GetSinkBlueScoreResponse>, protowire::Get
     Self { blue_score: item.blue_score, error: None }
 });
 
-from!(item: &kaspa_rpc_core::BanRequest, protowire::BanRequestMessage, { Self { ip: item.ip.to_string() } });
+from!(item: &kaspa_rpc_core::BanRequest, protowire::BanRequestMessage, {
+    Self { ip: item.ip.to_string(), duration: item.duration.unwrap_or_default(), reason: item.reason.clone().unwrap_or_default() }
+});
 from!(_item: RpcResult<&kaspa_rpc_core::BanResponse>, protowire::BanResponseMessage, { Self { error: None } });
 
 from!(item: &kaspa_rpc_core::UnbanRequest, protowire::UnbanRequestMessage, { Self { ip: item.ip.to_string() } });
@@ -394,6 +449,21 @@ from!(item: RpcResult<&kaspa_rpc_core::GetDaaScoreTimestampEstimateResponse>, pr
     Self { timestamps: item.timestamps.clone(), error: None }
 });
 
+from!(&kaspa_rpc_core::GetBannedPeersRequest, protowire::GetBannedPeersRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetBannedPeersResponse>, protowire::GetBannedPeersResponseMessage, {
+    Self { banned_peers: item.banned_peers.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(&kaspa_rpc_core::GetFeeEstimateRequest, protowire::GetFeeEstimateRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetFeeEstimateResponse>, protowire::GetFeeEstimateResponseMessage, {
+    Self { estimate: Some((&item.estimate).into()), error: None }
+});
+
+from!(item: &kaspa_rpc_core::SetLogLevelRequest, protowire::SetLogLevelRequestMessage, {
+    Self { subsystem: item.subsystem.clone().unwrap_or_default(), level: item.level.clone() }
+});
+from!(RpcResult<&kaspa_rpc_core::SetLogLevelResponse>, protowire::SetLogLevelResponseMessage);
+
 from!(&kaspa_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&kaspa_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -424,6 +494,8 @@ from!(item: RpcResult<&kaspa_rpc_core::GetServerInfoResponse>, protowire::GetSer
         has_utxo_index: item.has_utxo_index,
         is_synced: item.is_synced,
         virtual_daa_score: item.virtual_daa_score,
+        p2p_protocol_version: item.p2p_protocol_version,
+        mempool_size: item.mempool_size,
         error: None,
     }
 });
@@ -643,10 +715,40 @@ try_from!(item: &protowire::SubmitTransactionRequestMessage, kaspa_rpc_core::Sub
         allow_orphan: item.allow_orphan,
     }
 });
-try_from!(item: &protowire::SubmitTransactionResponseMessage, RpcResult<kaspa_rpc_core::SubmitTransactionResponse>, {
-    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+try_from!(item: &protowire::RpcSubmitTransactionRejectReason, RpcSubmitTransactionError, {
+    use SubmitTransactionRejectKind as Kind;
+    match Kind::try_from(item.kind).map_err(|_| RpcError::PrimitiveToEnumConversionError)? {
+        Kind::Orphan => Self::Orphan,
+        Kind::Duplicate => Self::Duplicate,
+        Kind::AlreadyAccepted => Self::AlreadyAccepted,
+        Kind::DoubleSpend => Self::DoubleSpend(RpcHash::from_str(&item.transaction_id)?),
+        Kind::InsufficientFee => Self::InsufficientFee(item.value, item.limit),
+        Kind::Dust => Self::Dust(item.output_index, item.value),
+        Kind::MassTooHigh => Self::MassTooHigh(item.value, item.limit),
+        Kind::NonStandard => Self::NonStandard(item.message.clone()),
+        Kind::InvalidSignature => Self::InvalidSignature(item.message.clone()),
+        Kind::ImmatureSpend => Self::ImmatureSpend,
+        Kind::MempoolFull => Self::MempoolFull,
+        Kind::Spam => Self::Spam,
+        Kind::Invalid => Self::Invalid(item.message.clone()),
+    }
 });
 
+impl TryFrom<&protowire::SubmitTransactionResponseMessage> for kaspa_rpc_core::SubmitTransactionResponse {
+    type Error = RpcError;
+    // This conversion breaks the general conversion convention (see file header) since the
+    // message of a rejected transaction contains both a reject reason and a matching error.
+    fn try_from(item: &protowire::SubmitTransactionResponseMessage) -> RpcResult<Self> {
+        match (&item.reject_reason, &item.error) {
+            (Some(reject_reason), _) => {
+                Ok(Self::new_rejected(RpcHash::from_str(&item.transaction_id)?, reject_reason.try_into()?))
+            }
+            (None, Some(err)) => Err(err.into()),
+            (None, None) => Ok(Self::new(RpcHash::from_str(&item.transaction_id)?)),
+        }
+    }
+}
+
 try_from!(item: &protowire::GetSubnetworkRequestMessage, kaspa_rpc_core::GetSubnetworkRequest, {
     Self { subnetwork_id: kaspa_rpc_core::RpcSubnetworkId::from_str(&item.subnetwork_id)? }
 });
@@ -746,7 +848,13 @@ try_from!(item: &protowire::GetSinkBlueScoreResponseMessage, RpcResult<kaspa_rpc
     Self { blue_score: item.blue_score }
 });
 
-try_from!(item: &protowire::BanRequestMessage, kaspa_rpc_core::BanRequest, { Self { ip: RpcIpAddress::from_str(&item.ip)? } });
+try_from!(item: &protowire::BanRequestMessage, kaspa_rpc_core::BanRequest, {
+    Self {
+        ip: RpcIpAddress::from_str(&item.ip)?,
+        duration: (item.duration > 0).then_some(item.duration),
+        reason: (!item.reason.is_empty()).then(|| item.reason.clone()),
+    }
+});
 try_from!(&protowire::BanResponseMessage, RpcResult<kaspa_rpc_core::BanResponse>);
 
 try_from!(item: &protowire::UnbanRequestMessage, kaspa_rpc_core::UnbanRequest, { Self { ip: RpcIpAddress::from_str(&item.ip)? } });
@@ -791,6 +899,27 @@ try_from!(item: &protowire::GetDaaScoreTimestampEstimateResponseMessage, RpcResu
     Self { timestamps: item.timestamps.clone() }
 });
 
+try_from!(&protowire::GetBannedPeersRequestMessage, kaspa_rpc_core::GetBannedPeersRequest);
+try_from!(item: &protowire::GetBannedPeersResponseMessage, RpcResult<kaspa_rpc_core::GetBannedPeersResponse>, {
+    Self { banned_peers: item.banned_peers.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(&protowire::GetFeeEstimateRequestMessage, kaspa_rpc_core::GetFeeEstimateRequest);
+try_from!(item: &protowire::GetFeeEstimateResponseMessage, RpcResult<kaspa_rpc_core::GetFeeEstimateResponse>, {
+    Self {
+        estimate: item
+            .estimate
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetFeeEstimateResponse".to_string(), "estimate".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::SetLogLevelRequestMessage, kaspa_rpc_core::SetLogLevelRequest, {
+    Self { subsystem: (!item.subsystem.is_empty()).then(|| item.subsystem.clone()), level: item.level.clone() }
+});
+try_from!(&protowire::SetLogLevelResponseMessage, RpcResult<kaspa_rpc_core::SetLogLevelResponse>);
+
 try_from!(&protowire::PingRequestMessage, kaspa_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<kaspa_rpc_core::PingResponse>);
 
@@ -816,6 +945,8 @@ try_from!(item: &protowire::GetServerInfoResponseMessage, RpcResult<kaspa_rpc_co
         has_utxo_index: item.has_utxo_index,
         is_synced: item.is_synced,
         virtual_daa_score: item.virtual_daa_score,
+        p2p_protocol_version: item.p2p_protocol_version,
+        mempool_size: item.mempool_size,
     }
 });
 
@@ -888,9 +1019,17 @@ try_from!(&protowire::NotifySinkBlueScoreChangedResponseMessage, RpcResult<kaspa
 
 #[cfg(test)]
 mod tests {
-    use kaspa_rpc_core::{RpcError, RpcResult, SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse};
+    use kaspa_consensus_core::network::{NetworkId, NetworkType};
+    use kaspa_rpc_core::{
+        BandwidthMetrics, ConsensusMetrics, GetMetricsRequest, GetMetricsResponse, GetServerInfoResponse, ProcessMetrics, RpcError,
+        RpcHash, RpcResult, RpcSubmitTransactionError, SubmitBlockRejectReason, SubmitBlockReport, SubmitBlockResponse,
+        SubmitTransactionResponse,
+    };
 
-    use crate::protowire::{self, submit_block_response_message::RejectReason, SubmitBlockResponseMessage};
+    use crate::protowire::{
+        self, submit_block_response_message::RejectReason, GetMetricsRequestMessage, GetMetricsResponseMessage,
+        GetServerInfoResponseMessage, SubmitBlockResponseMessage, SubmitTransactionResponseMessage,
+    };
 
     #[test]
     fn test_submit_block_response() {
@@ -960,4 +1099,129 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_submit_transaction_response() {
+        let id = RpcHash::from_bytes([1; 32]);
+        let reasons = vec![
+            RpcSubmitTransactionError::Orphan,
+            RpcSubmitTransactionError::Duplicate,
+            RpcSubmitTransactionError::AlreadyAccepted,
+            RpcSubmitTransactionError::DoubleSpend(RpcHash::from_bytes([2; 32])),
+            RpcSubmitTransactionError::InsufficientFee(10, 2036),
+            RpcSubmitTransactionError::Dust(1, 500),
+            RpcSubmitTransactionError::MassTooHigh(200_000, 100_000),
+            RpcSubmitTransactionError::NonStandard("non-standard script form".to_string()),
+            RpcSubmitTransactionError::InvalidSignature("signature verification failed".to_string()),
+            RpcSubmitTransactionError::ImmatureSpend,
+            RpcSubmitTransactionError::MempoolFull,
+            RpcSubmitTransactionError::Spam,
+            RpcSubmitTransactionError::Invalid("transaction has no inputs".to_string()),
+        ];
+
+        let accepted: SubmitTransactionResponseMessage = Ok(&SubmitTransactionResponse::new(id)).into();
+        assert!(accepted.reject_reason.is_none() && accepted.error.is_none());
+        let cnv_rpc_core: SubmitTransactionResponse = (&accepted).try_into().unwrap();
+        assert_eq!(cnv_rpc_core.transaction_id, id);
+        assert_eq!(cnv_rpc_core.reject_reason, None);
+
+        for reason in reasons {
+            let response = SubmitTransactionResponse::new_rejected(id, reason.clone());
+            let cnv_protowire: SubmitTransactionResponseMessage = Ok(&response).into();
+            // clients unaware of the reject reason still see the rejection as an error
+            assert_eq!(
+                cnv_protowire.error,
+                Some(protowire::RpcError { message: RpcError::SubmitTransactionError(id, reason.clone()).to_string() })
+            );
+
+            // the protobuf encoding preserves the reject reason
+            let bytes = prost::Message::encode_to_vec(&cnv_protowire);
+            let decoded: SubmitTransactionResponseMessage = prost::Message::decode(bytes.as_slice()).unwrap();
+            let cnv_rpc_core: SubmitTransactionResponse = (&decoded).try_into().unwrap();
+            assert_eq!(cnv_rpc_core.transaction_id, id);
+            assert_eq!(cnv_rpc_core.reject_reason, Some(reason));
+        }
+
+        // messages of nodes unaware of the reject reason
+        let legacy = SubmitTransactionResponseMessage {
+            transaction_id: id.to_string(),
+            reject_reason: None,
+            error: Some(protowire::RpcError { message: "Rejected transaction".to_string() }),
+        };
+        let cnv_rpc_core: RpcResult<SubmitTransactionResponse> = (&legacy).try_into();
+        assert_eq!(cnv_rpc_core.unwrap_err().to_string(), RpcError::General("Rejected transaction".to_string()).to_string());
+    }
+
+    #[test]
+    fn test_get_server_info_response() {
+        let response = GetServerInfoResponse {
+            rpc_api_version: [0, 1, 0, 0],
+            server_version: "0.13.4".to_string(),
+            network_id: NetworkId::with_suffix(NetworkType::Testnet, 11),
+            has_utxo_index: true,
+            is_synced: true,
+            virtual_daa_score: 1_234_567,
+            p2p_protocol_version: 6,
+            mempool_size: 42,
+        };
+
+        let message: GetServerInfoResponseMessage = Ok(&response).into();
+        assert_eq!(message.rpc_api_version, vec![0, 1, 0, 0]);
+        assert_eq!(message.server_version, "0.13.4");
+        assert_eq!(message.network_id, "testnet-11");
+        assert!(message.has_utxo_index && message.is_synced);
+        assert_eq!(message.virtual_daa_score, 1_234_567);
+        assert_eq!(message.p2p_protocol_version, 6);
+        assert_eq!(message.mempool_size, 42);
+        assert!(message.error.is_none());
+
+        let converted: RpcResult<GetServerInfoResponse> = (&message).try_into();
+        let converted = converted.unwrap();
+        assert_eq!(converted.rpc_api_version, response.rpc_api_version);
+        assert_eq!(converted.server_version, response.server_version);
+        assert_eq!(converted.network_id, response.network_id);
+        assert_eq!(converted.has_utxo_index, response.has_utxo_index);
+        assert_eq!(converted.is_synced, response.is_synced);
+        assert_eq!(converted.virtual_daa_score, response.virtual_daa_score);
+        assert_eq!(converted.p2p_protocol_version, response.p2p_protocol_version);
+        assert_eq!(converted.mempool_size, response.mempool_size);
+    }
+
+    #[test]
+    fn test_get_metrics_sections() {
+        let request =
+            GetMetricsRequest { process_metrics: true, connection_metrics: false, bandwidth_metrics: true, consensus_metrics: false };
+        let message: GetMetricsRequestMessage = (&request).into();
+        let converted: GetMetricsRequest = (&message).try_into().unwrap();
+        assert!(converted.process_metrics && converted.bandwidth_metrics);
+        assert!(!converted.connection_metrics && !converted.consensus_metrics);
+
+        // Sections not requested are left out of the response
+        let response = GetMetricsResponse::new(
+            1_700_000_000_000,
+            Some(ProcessMetrics { resident_set_size: 1024, cpu_usage: 0.5, ..Default::default() }),
+            None,
+            Some(BandwidthMetrics { p2p_bytes_rx: 2048, ..Default::default() }),
+            None,
+        );
+        let message: GetMetricsResponseMessage = Ok(&response).into();
+        assert!(message.process_metrics.is_some() && message.bandwidth_metrics.is_some());
+        assert!(message.connection_metrics.is_none() && message.consensus_metrics.is_none());
+
+        let converted: RpcResult<GetMetricsResponse> = (&message).try_into();
+        let converted = converted.unwrap();
+        assert_eq!(converted.server_time, response.server_time);
+        let process_metrics = converted.process_metrics.unwrap();
+        assert_eq!((process_metrics.resident_set_size, process_metrics.cpu_usage), (1024, 0.5));
+        assert_eq!(converted.bandwidth_metrics.unwrap().p2p_bytes_rx, 2048);
+        assert!(converted.connection_metrics.is_none());
+        assert!(converted.consensus_metrics.is_none());
+
+        let response =
+            GetMetricsResponse::new(0, None, None, None, Some(ConsensusMetrics { network_mempool_size: 7, ..Default::default() }));
+        let converted: RpcResult<GetMetricsResponse> = (&GetMetricsResponseMessage::from(Ok(&response))).try_into();
+        let converted = converted.unwrap();
+        assert_eq!(converted.consensus_metrics.unwrap().network_mempool_size, 7);
+        assert!(converted.process_metrics.is_none() && converted.bandwidth_metrics.is_none());
+    }
 }
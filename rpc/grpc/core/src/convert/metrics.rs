@@ -1,4 +1,4 @@
-use crate::protowire;
+use crate::protowire::{self, service_metrics::State};
 use crate::{from, try_from};
 use kaspa_rpc_core::RpcError;
 
@@ -17,6 +17,25 @@ from!(item: &kaspa_rpc_core::ProcessMetrics, protowire::ProcessMetrics, {
         disk_io_write_bytes: item.disk_io_write_bytes,
         disk_io_read_per_sec: item.disk_io_read_per_sec,
         disk_io_write_per_sec: item.disk_io_write_per_sec,
+        services: item.services.iter().map(|x| x.into()).collect(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcServiceState, State, {
+    match item {
+        kaspa_rpc_core::RpcServiceState::Registered => State::Registered,
+        kaspa_rpc_core::RpcServiceState::Running => State::Running,
+        kaspa_rpc_core::RpcServiceState::Stopped => State::Stopped,
+        kaspa_rpc_core::RpcServiceState::Failed => State::Failed,
+    }
+});
+
+from!(item: &kaspa_rpc_core::ServiceMetrics, protowire::ServiceMetrics, {
+    Self {
+        ident: item.ident.clone(),
+        state: State::from(&item.state) as i32,
+        start_time: item.start_time.unwrap_or_default(),
+        last_error: item.last_error.clone().unwrap_or_default(),
     }
 });
 
@@ -63,6 +82,7 @@ from!(item: &kaspa_rpc_core::ConsensusMetrics, protowire::ConsensusMetrics, {
         past_median_time: item.network_past_median_time,
         virtual_parent_hashes_count: item.network_virtual_parent_hashes_count,
         virtual_daa_score: item.network_virtual_daa_score,
+        sync_eta_seconds: item.node_sync_eta_seconds.map(|eta| eta as i64).unwrap_or(-1),
     }
 });
 
@@ -81,6 +101,25 @@ try_from!(item: &protowire::ProcessMetrics, kaspa_rpc_core::ProcessMetrics, {
         disk_io_write_bytes: item.disk_io_write_bytes,
         disk_io_read_per_sec: item.disk_io_read_per_sec,
         disk_io_write_per_sec: item.disk_io_write_per_sec,
+        services: item.services.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, RpcError>>()?,
+    }
+});
+
+from!(item: State, kaspa_rpc_core::RpcServiceState, {
+    match item {
+        State::Registered => kaspa_rpc_core::RpcServiceState::Registered,
+        State::Running => kaspa_rpc_core::RpcServiceState::Running,
+        State::Stopped => kaspa_rpc_core::RpcServiceState::Stopped,
+        State::Failed => kaspa_rpc_core::RpcServiceState::Failed,
+    }
+});
+
+try_from!(item: &protowire::ServiceMetrics, kaspa_rpc_core::ServiceMetrics, {
+    Self {
+        ident: item.ident.clone(),
+        state: State::try_from(item.state).map_err(|_| RpcError::PrimitiveToEnumConversionError)?.into(),
+        start_time: (item.start_time > 0).then_some(item.start_time),
+        last_error: (!item.last_error.is_empty()).then(|| item.last_error.clone()),
     }
 });
 
@@ -127,5 +166,6 @@ try_from!(item: &protowire::ConsensusMetrics, kaspa_rpc_core::ConsensusMetrics,
         network_past_median_time: item.past_median_time,
         network_virtual_parent_hashes_count: item.virtual_parent_hashes_count,
         network_virtual_daa_score: item.virtual_daa_score,
+        node_sync_eta_seconds: (item.sync_eta_seconds >= 0).then_some(item.sync_eta_seconds as u64),
     }
 });
@@ -17,11 +17,21 @@ from!(item: &kaspa_rpc_core::RpcPeerInfo, protowire::GetConnectedPeerInfoMessage
         time_offset: item.time_offset,
         user_agent: item.user_agent.clone(),
         advertised_protocol_version: item.advertised_protocol_version,
+        protocol_version: item.protocol_version,
         time_connected: item.time_connected as i64,
         is_ibd_peer: item.is_ibd_peer,
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcBannedPeer, protowire::RpcBannedPeer, {
+    Self {
+        address: item.address.to_string(),
+        reason: item.reason.clone(),
+        banned_at: item.banned_at as i64,
+        expires_at: item.expires_at as i64,
+    }
+});
+
 from!(item: &kaspa_rpc_core::RpcPeerAddress, protowire::GetPeerAddressesKnownAddressMessage, { Self { addr: item.to_string() } });
 from!(item: &kaspa_rpc_core::RpcIpAddress, protowire::GetPeerAddressesKnownAddressMessage, { Self { addr: item.to_string() } });
 
@@ -38,10 +48,20 @@ try_from!(item: &protowire::GetConnectedPeerInfoMessage, kaspa_rpc_core::RpcPeer
         time_offset: item.time_offset,
         user_agent: item.user_agent.clone(),
         advertised_protocol_version: item.advertised_protocol_version,
+        protocol_version: item.protocol_version,
         time_connected: item.time_connected as u64,
         is_ibd_peer: item.is_ibd_peer,
     }
 });
 
+try_from!(item: &protowire::RpcBannedPeer, kaspa_rpc_core::RpcBannedPeer, {
+    Self {
+        address: RpcPeerAddress::from_str(&item.address)?,
+        reason: item.reason.clone(),
+        banned_at: item.banned_at as u64,
+        expires_at: item.expires_at as u64,
+    }
+});
+
 try_from!(item: &protowire::GetPeerAddressesKnownAddressMessage, kaspa_rpc_core::RpcPeerAddress, { Self::from_str(&item.addr)? });
 try_from!(item: &protowire::GetPeerAddressesKnownAddressMessage, kaspa_rpc_core::RpcIpAddress, { Self::from_str(&item.addr)? });
@@ -81,6 +81,9 @@ pub enum KaspadPayloadOps {
     GetServerInfo,
     GetSyncStatus,
     GetDaaScoreTimestampEstimate,
+    GetBannedPeers,
+    GetFeeEstimate,
+    SetLogLevel,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
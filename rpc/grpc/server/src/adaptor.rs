@@ -1,11 +1,16 @@
 use crate::{connection_handler::ConnectionHandler, manager::Manager};
 use kaspa_core::debug;
 use kaspa_notify::notifier::Notifier;
-use kaspa_rpc_core::{api::rpc::DynRpcService, notify::connection::ChannelConnection, Notification, RpcResult};
+use kaspa_rpc_core::{
+    api::{auth::RpcAuthTokens, rpc::DynRpcService},
+    notify::connection::ChannelConnection,
+    Notification, RpcResult,
+};
 use kaspa_utils::networking::NetAddress;
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 use std::{ops::Deref, sync::Arc};
 use tokio::sync::{mpsc::channel as mpsc_channel, oneshot::Sender as OneshotSender};
+use tonic::transport::ServerTlsConfig;
 
 pub struct Adaptor {
     /// If a server was started, it will get cleaned up when this sender is dropped or invoked
@@ -38,10 +43,13 @@ impl Adaptor {
         core_service: DynRpcService,
         core_notifier: Arc<Notifier<Notification, ChannelConnection>>,
         counters: Arc<TowerConnectionCounters>,
+        tls_config: Option<ServerTlsConfig>,
+        auth_tokens: RpcAuthTokens,
     ) -> Arc<Self> {
         let (manager_sender, manager_receiver) = mpsc_channel(Self::manager_channel_size());
-        let connection_handler = ConnectionHandler::new(network_bps, manager_sender, core_service.clone(), core_notifier, counters);
-        let server_termination = connection_handler.serve(serve_address);
+        let connection_handler =
+            ConnectionHandler::new(network_bps, manager_sender, core_service.clone(), core_notifier, counters, auth_tokens);
+        let server_termination = connection_handler.serve(serve_address, tls_config);
         let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, manager, serve_address));
         adaptor.manager.clone().start_event_loop(manager_receiver);
         adaptor.start();
@@ -18,7 +18,10 @@ use kaspa_grpc_core::{
 use kaspa_notify::{
     connection::Connection as ConnectionT, error::Error as NotificationError, listener::ListenerId, notifier::Notifier,
 };
-use kaspa_rpc_core::Notification;
+use kaspa_rpc_core::{
+    api::{auth::RpcAccessLevel, ops::RpcApiOps},
+    Notification, RpcResult,
+};
 use parking_lot::Mutex;
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -66,6 +69,9 @@ struct Inner {
     /// The socket address of this client
     net_address: SocketAddr,
 
+    /// The access level granted to the connection by its authentication token
+    access_level: RpcAccessLevel,
+
     /// The outgoing route for sending messages to this client
     outgoing_route: GrpcSender,
 
@@ -212,6 +218,7 @@ impl Display for Connection {
 impl Connection {
     pub(crate) fn new(
         net_address: SocketAddr,
+        access_level: RpcAccessLevel,
         server_context: ServerContext,
         interface: Arc<Interface>,
         manager_sender: MpscSender<ManagerEvent>,
@@ -224,6 +231,7 @@ impl Connection {
             inner: Arc::new(Inner {
                 connection_id: Uuid::new_v4(),
                 net_address,
+                access_level,
                 outgoing_route,
                 manager_sender,
                 server_context,
@@ -307,6 +315,16 @@ impl Connection {
         self.inner.net_address
     }
 
+    /// Get the access level granted to the connection
+    pub fn access_level(&self) -> RpcAccessLevel {
+        self.inner.access_level
+    }
+
+    /// Checks that the connection is allowed to call the RPC method `op`
+    pub fn authorize(&self, op: RpcApiOps) -> RpcResult<()> {
+        self.inner.access_level.authorize(op)
+    }
+
     pub fn identity(&self) -> ConnectionId {
         self.inner.connection_id
     }
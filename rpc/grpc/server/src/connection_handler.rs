@@ -15,7 +15,10 @@ use kaspa_grpc_core::{
 };
 use kaspa_notify::{connection::ChannelType, events::EVENT_TYPE_ARRAY, notifier::Notifier, subscriber::Subscriber};
 use kaspa_rpc_core::{
-    api::rpc::DynRpcService,
+    api::{
+        auth::{RpcAccessLevel, RpcAuthTokens, GRPC_AUTH_TOKEN_METADATA_KEY},
+        rpc::DynRpcService,
+    },
     notify::{channel::NotificationChannel, connection::ChannelConnection},
     Notification, RpcResult,
 };
@@ -39,7 +42,11 @@ use tokio::{
     time::timeout,
 };
 use tokio_stream::{wrappers::ReceiverStream, StreamExt};
-use tonic::{codec::CompressionEncoding, transport::Server as TonicServer, Request, Response};
+use tonic::{
+    codec::CompressionEncoding,
+    transport::{Server as TonicServer, ServerTlsConfig},
+    Request, Response,
+};
 
 #[derive(Clone)]
 pub struct ServerContext {
@@ -69,6 +76,7 @@ pub struct ConnectionHandler {
     interface: Arc<Interface>,
     running: Arc<AtomicBool>,
     counters: Arc<TowerConnectionCounters>,
+    auth_tokens: Arc<RpcAuthTokens>,
 }
 
 const GRPC_SERVER: &str = "grpc-server";
@@ -80,6 +88,7 @@ impl ConnectionHandler {
         core_service: DynRpcService,
         core_notifier: Arc<Notifier<Notification, ChannelConnection>>,
         counters: Arc<TowerConnectionCounters>,
+        auth_tokens: RpcAuthTokens,
     ) -> Self {
         // Prepare core objects
         let core_channel = NotificationChannel::default();
@@ -97,15 +106,15 @@ impl ConnectionHandler {
         let interface = Arc::new(Factory::new_interface(server_context.clone(), network_bps));
         let running = Default::default();
 
-        Self { manager_sender, server_context, interface, running, counters }
+        Self { manager_sender, server_context, interface, running, counters, auth_tokens: Arc::new(auth_tokens) }
     }
 
-    /// Launches a gRPC server listener loop
-    pub(crate) fn serve(&self, serve_address: NetAddress) -> OneshotSender<()> {
+    /// Launches a gRPC server listener loop, secured by TLS if a `tls_config` is provided
+    pub(crate) fn serve(&self, serve_address: NetAddress, tls_config: Option<ServerTlsConfig>) -> OneshotSender<()> {
         let (termination_sender, termination_receiver) = oneshot_channel::<()>();
         let (signal_sender, signal_receiver) = oneshot_channel::<()>();
         let connection_handler = self.clone();
-        info!("GRPC Server starting on: {}", serve_address);
+        info!("GRPC Server starting on: {}{}", serve_address, if tls_config.is_some() { " (TLS)" } else { "" });
 
         let bytes_tx = self.counters.bytes_tx.clone();
         let bytes_rx = self.counters.bytes_rx.clone();
@@ -120,7 +129,12 @@ impl ConnectionHandler {
             // TODO: check whether we should set tcp_keepalive
             const GRPC_KEEP_ALIVE_PING_INTERVAL: Duration = Duration::from_secs(3);
             const GRPC_KEEP_ALIVE_PING_TIMEOUT: Duration = Duration::from_secs(10);
-            let serve_result = TonicServer::builder()
+            let mut server_builder = TonicServer::builder();
+            if let Some(tls_config) = tls_config {
+                server_builder =
+                    server_builder.tls_config(tls_config).expect("the TLS configuration is validated by GrpcTlsConfig::load");
+            }
+            let serve_result = server_builder
                 .http2_keepalive_interval(Some(GRPC_KEEP_ALIVE_PING_INTERVAL))
                 .http2_keepalive_timeout(Some(GRPC_KEEP_ALIVE_PING_TIMEOUT))
                 .layer(measure_request_body_size_layer(bytes_rx, |b| b))
@@ -171,6 +185,20 @@ impl ConnectionHandler {
         self.server_context.notifier.clone()
     }
 
+    /// Returns the access level granted by the authentication token found in the metadata of `request`.
+    /// Connections are granted full access if the server has no authentication token.
+    fn authenticate<T>(&self, request: &Request<T>) -> Result<RpcAccessLevel, tonic::Status> {
+        if self.auth_tokens.is_empty() {
+            return Ok(RpcAccessLevel::Admin);
+        }
+        request
+            .metadata()
+            .get(GRPC_AUTH_TOKEN_METADATA_KEY)
+            .and_then(|token| token.to_str().ok())
+            .and_then(|token| self.auth_tokens.access_level(token))
+            .ok_or_else(|| tonic::Status::new(tonic::Code::Unauthenticated, "Missing or invalid authentication token"))
+    }
+
     pub fn start(&self) {
         debug!("GRPC, Starting the connection handler");
 
@@ -233,6 +261,11 @@ impl Rpc for ConnectionHandler {
 
         debug!("GRPC, Incoming message stream from {:?}", remote_address);
 
+        let access_level = self.authenticate(&request).map_err(|status| {
+            debug!("GRPC, Refusing incoming message stream from {:?} - {}", remote_address, status.message());
+            status
+        })?;
+
         // Build the in/out pipes
         let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_route_channel_size());
         let incoming_stream = request.into_inner();
@@ -240,6 +273,7 @@ impl Rpc for ConnectionHandler {
         // Build the connection object
         let connection = Connection::new(
             remote_address,
+            access_level,
             self.server_context(),
             self.interface(),
             self.manager_sender(),
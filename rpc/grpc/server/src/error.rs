@@ -27,6 +27,12 @@ pub enum GrpcServerError {
 
     #[error("outgoing route capacity has been reached (client: {0})")]
     OutgoingRouteCapacityReached(String),
+
+    #[error("unable to read the TLS {0} file '{1}': {2}")]
+    TlsFile(&'static str, String, String),
+
+    #[error("invalid TLS configuration: {0}")]
+    TlsConfig(String),
 }
 
 impl From<GrpcServerError> for kaspa_rpc_core::error::RpcError {
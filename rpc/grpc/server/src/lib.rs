@@ -6,6 +6,7 @@ pub mod error;
 pub mod manager;
 pub mod request_handler;
 pub mod service;
+pub mod tls;
 
 #[cfg(test)]
 pub mod tests;
@@ -5,10 +5,12 @@ use kaspa_core::{
     task::service::{AsyncService, AsyncServiceFuture},
     trace, warn,
 };
+use kaspa_rpc_core::api::auth::RpcAuthTokens;
 use kaspa_rpc_service::service::RpcCoreService;
 use kaspa_utils::{networking::NetAddress, triggers::SingleTrigger};
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 use std::sync::Arc;
+use tonic::transport::ServerTlsConfig;
 
 const GRPC_SERVICE: &str = "grpc-service";
 
@@ -19,6 +21,8 @@ pub struct GrpcService {
     rpc_max_clients: usize,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    tls_config: Option<ServerTlsConfig>,
+    auth_tokens: RpcAuthTokens,
 }
 
 impl GrpcService {
@@ -28,8 +32,19 @@ impl GrpcService {
         core_service: Arc<RpcCoreService>,
         rpc_max_clients: usize,
         counters: Arc<TowerConnectionCounters>,
+        tls_config: Option<ServerTlsConfig>,
+        auth_tokens: RpcAuthTokens,
     ) -> Self {
-        Self { net_address: address, config, core_service, rpc_max_clients, shutdown: Default::default(), counters }
+        Self {
+            net_address: address,
+            config,
+            core_service,
+            rpc_max_clients,
+            shutdown: Default::default(),
+            counters,
+            tls_config,
+            auth_tokens,
+        }
     }
 }
 
@@ -52,6 +67,8 @@ impl AsyncService for GrpcService {
             self.core_service.clone(),
             self.core_service.notifier(),
             self.counters.clone(),
+            self.tls_config.clone(),
+            self.auth_tokens.clone(),
         );
 
         // Launch the service and wait for a shutdown signal
@@ -1,11 +1,22 @@
 use super::rpc_core_mock::RpcCoreMock;
-use crate::{adaptor::Adaptor, manager::Manager};
+use crate::{adaptor::Adaptor, manager::Manager, tls::GrpcTlsConfig};
+use futures::StreamExt;
 use kaspa_core::info;
 use kaspa_grpc_client::GrpcClient;
+use kaspa_grpc_core::protowire::{rpc_client::RpcClient, GetInfoRequestMessage, KaspadRequest, KaspadResponse, ShutdownRequestMessage};
 use kaspa_notify::scope::{NewBlockTemplateScope, Scope};
-use kaspa_rpc_core::{api::rpc::RpcApi, notify::mode::NotificationMode};
+use kaspa_rpc_core::{
+    api::{
+        auth::{RpcAccessLevel, RpcAuthTokens, GRPC_AUTH_TOKEN_METADATA_KEY},
+        ops::RpcApiOps,
+        rpc::RpcApi,
+    },
+    notify::mode::NotificationMode,
+    GetInfoResponse, RpcError, RpcResult, ShutdownResponse,
+};
 use kaspa_utils::networking::{ContextualNetAddress, NetAddress};
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Identity, ServerTlsConfig};
 
 #[tokio::test]
 async fn test_client_server_sanity_check() {
@@ -187,9 +198,204 @@ async fn test_client_server_notifications() {
     tokio::time::sleep(std::time::Duration::from_millis(50)).await;
 }
 
+#[tokio::test]
+async fn test_client_server_tls() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start a server requiring TLS with a self-signed certificate
+    let tls_config = GrpcTlsConfig::new(tls_file("localhost.crt"), tls_file("localhost.key")).load().unwrap();
+    let server = create_server_with_tls(rpc_core_service.clone(), Some(tls_config));
+
+    // A client trusting the certificate establishes a TLS connection and gets responses
+    let tls_client_info = tls_get_info(server.serve_address(), None).await;
+    assert!(tls_client_info.is_ok(), "the TLS client failed to query the server: {:?}", tls_client_info.err());
+
+    // A plaintext client is rejected
+    let server_url = format!("grpc://localhost:{}", server.serve_address().port);
+    let plaintext_client =
+        GrpcClient::connect(NotificationMode::Direct, server_url, false, None, false, Some(1000), Default::default()).await;
+    assert!(plaintext_client.is_err(), "a plaintext client should not be able to connect to a TLS server");
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_client_server_access_level() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start a server granting full access to "secret" and read-only access to "public"
+    let auth_tokens = RpcAuthTokens::new(Some("secret".to_string()), None, Some("public".to_string()));
+    let server = create_server_with_auth_tokens(rpc_core_service.clone(), None, auth_tokens);
+    let address = server.serve_address();
+
+    // Connections without a valid token are refused
+    for auth_token in [None, Some("guess")] {
+        let status = call_with_auth_token(address, auth_token, GetInfoRequestMessage {}.into()).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+    }
+
+    // A read-only connection can query but not shut down the node
+    let response = call_with_auth_token(address, Some("public"), GetInfoRequestMessage {}.into()).await.unwrap();
+    let response: RpcResult<GetInfoResponse> = (&response).try_into();
+    assert!(response.is_ok(), "a read-only connection should be able to query: {:?}", response.err());
+    let response = call_with_auth_token(address, Some("public"), ShutdownRequestMessage {}.into()).await.unwrap();
+    let response: RpcResult<ShutdownResponse> = (&response).try_into();
+    assert_eq!(
+        response.unwrap_err().to_string(),
+        RpcError::AccessDenied(RpcApiOps::Shutdown, RpcAccessLevel::Admin, RpcAccessLevel::Read).to_string()
+    );
+
+    // An admin connection reaches the core service
+    let response = call_with_auth_token(address, Some("secret"), ShutdownRequestMessage {}.into()).await.unwrap();
+    let response: RpcResult<ShutdownResponse> = (&response).try_into();
+    assert_eq!(response.unwrap_err().to_string(), RpcError::NotImplemented.to_string());
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[tokio::test]
+async fn test_client_server_mutual_tls() {
+    kaspa_core::log::try_init_logger("info, kaspa_grpc_core=trace, kaspa_grpc_server=trace, kaspa_grpc_client=trace");
+
+    // Create and start a fake core service
+    let rpc_core_service = Arc::new(RpcCoreMock::new());
+    rpc_core_service.start();
+
+    // Create and start a server requiring clients to authenticate with a certificate signed by the client CA
+    let tls_config = GrpcTlsConfig::new(tls_file("localhost.crt"), tls_file("localhost.key"))
+        .with_client_ca(Some(tls_file("client-ca.crt")))
+        .load()
+        .unwrap();
+    let server = create_server_with_tls(rpc_core_service.clone(), Some(tls_config));
+
+    // A client presenting a certificate signed by the client CA gets responses
+    let identity = Identity::from_pem(std::fs::read(tls_file("client.crt")).unwrap(), std::fs::read(tls_file("client.key")).unwrap());
+    let tls_client_info = tls_get_info(server.serve_address(), Some(identity)).await;
+    assert!(tls_client_info.is_ok(), "the authenticated client failed to query the server: {:?}", tls_client_info.err());
+
+    // A client without a certificate is rejected
+    let tls_client_info = tls_get_info(server.serve_address(), None).await;
+    assert!(tls_client_info.is_err(), "a client without a certificate should be rejected");
+
+    // A client presenting a certificate not signed by the client CA is rejected
+    let identity =
+        Identity::from_pem(std::fs::read(tls_file("localhost.crt")).unwrap(), std::fs::read(tls_file("localhost.key")).unwrap());
+    let tls_client_info = tls_get_info(server.serve_address(), Some(identity)).await;
+    assert!(tls_client_info.is_err(), "a client with an untrusted certificate should be rejected");
+
+    // Stop the fake service
+    rpc_core_service.join().await;
+
+    // Stop the server
+    assert!(server.stop().await.is_ok(), "error stopping the server");
+    drop(server);
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+}
+
+#[test]
+fn test_tls_config_invalid_pem() {
+    // A certificate is not a valid private key
+    let tls_config = GrpcTlsConfig::new(tls_file("localhost.crt"), tls_file("localhost.crt"));
+    let err = tls_config.load().err().expect("loading an invalid key should fail");
+    assert!(err.to_string().starts_with("invalid TLS configuration"), "unexpected error: {err}");
+}
+
+#[test]
+fn test_tls_config_missing_file() {
+    let tls_config = GrpcTlsConfig::new(tls_file("localhost.crt"), tls_file("missing.key"));
+    let err = tls_config.load().err().expect("loading a missing key file should fail");
+    assert!(err.to_string().starts_with("unable to read the TLS key file"), "unexpected error: {err}");
+}
+
 fn create_server(core_service: Arc<RpcCoreMock>) -> Arc<Adaptor> {
+    create_server_with_tls(core_service, None)
+}
+
+fn create_server_with_tls(core_service: Arc<RpcCoreMock>, tls_config: Option<ServerTlsConfig>) -> Arc<Adaptor> {
+    create_server_with_auth_tokens(core_service, tls_config, Default::default())
+}
+
+fn create_server_with_auth_tokens(
+    core_service: Arc<RpcCoreMock>,
+    tls_config: Option<ServerTlsConfig>,
+    auth_tokens: RpcAuthTokens,
+) -> Arc<Adaptor> {
     let manager = Manager::new(128);
-    Adaptor::server(get_free_net_address(), 1, manager, core_service.clone(), core_service.core_notifier(), Default::default())
+    Adaptor::server(
+        get_free_net_address(),
+        1,
+        manager,
+        core_service.clone(),
+        core_service.core_notifier(),
+        Default::default(),
+        tls_config,
+        auth_tokens,
+    )
+}
+
+fn tls_file(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/tests/tls").join(name)
+}
+
+/// Queries the server info over a TLS connection trusting the self-signed test certificate,
+/// authenticating the client with `identity` if provided
+async fn tls_get_info(server_address: NetAddress, identity: Option<Identity>) -> Result<GetInfoResponse, String> {
+    let certificate = Certificate::from_pem(std::fs::read(tls_file("localhost.crt")).unwrap());
+    let mut tls_config = ClientTlsConfig::new().ca_certificate(certificate).domain_name("localhost");
+    if let Some(identity) = identity {
+        tls_config = tls_config.identity(identity);
+    }
+    let channel = Channel::from_shared(format!("https://localhost:{}", server_address.port))
+        .unwrap()
+        .tls_config(tls_config)
+        .map_err(|err| err.to_string())?
+        .connect()
+        .await
+        .map_err(|err| err.to_string())?;
+
+    // Keep the request stream open so the server does not close the connection before responding
+    let requests = futures::stream::iter([KaspadRequest::from(GetInfoRequestMessage {})]).chain(futures::stream::pending());
+    let mut responses = RpcClient::new(channel).message_stream(requests).await.map_err(|err| err.to_string())?.into_inner();
+    let response = responses.message().await.map_err(|err| err.to_string())?.ok_or("the stream was closed by the server")?;
+    let response: RpcResult<GetInfoResponse> = (&response).try_into();
+    response.map_err(|err| err.to_string())
+}
+
+/// Sends `request` over a plaintext message stream authenticated with `auth_token` and returns the response
+async fn call_with_auth_token(
+    server_address: NetAddress,
+    auth_token: Option<&str>,
+    request: KaspadRequest,
+) -> Result<KaspadResponse, tonic::Status> {
+    let channel = Channel::from_shared(format!("http://localhost:{}", server_address.port)).unwrap().connect().await.unwrap();
+
+    // Keep the request stream open so the server does not close the connection before responding
+    let mut stream_request = tonic::Request::new(futures::stream::iter([request]).chain(futures::stream::pending()));
+    if let Some(auth_token) = auth_token {
+        stream_request.metadata_mut().insert(GRPC_AUTH_TOKEN_METADATA_KEY, auth_token.parse().unwrap());
+    }
+    let mut responses = RpcClient::new(channel).message_stream(stream_request).await?.into_inner();
+    Ok(responses.message().await?.expect("the stream was closed by the server"))
 }
 
 async fn create_client(server_address: NetAddress) -> GrpcClient {
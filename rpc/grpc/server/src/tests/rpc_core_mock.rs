@@ -1,6 +1,6 @@
 use async_channel::{unbounded, Receiver};
 use async_trait::async_trait;
-use kaspa_notify::events::EVENT_TYPE_ARRAY;
+use kaspa_notify::events::{EventType, EVENT_TYPE_ARRAY};
 use kaspa_notify::listener::ListenerId;
 use kaspa_notify::notifier::{Notifier, Notify};
 use kaspa_notify::scope::Scope;
@@ -218,6 +218,18 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_banned_peers_call(&self, _request: GetBannedPeersRequest) -> RpcResult<GetBannedPeersResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_fee_estimate_call(&self, _request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn set_log_level_call(&self, _request: SetLogLevelRequest) -> RpcResult<SetLogLevelResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -239,4 +251,14 @@ impl RpcApi for RpcCoreMock {
         self.core_notifier.try_stop_notify(id, scope)?;
         Ok(())
     }
+
+    async fn mute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()> {
+        self.core_notifier.try_mute(id, event)?;
+        Ok(())
+    }
+
+    async fn unmute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()> {
+        self.core_notifier.try_unmute(id, event)?;
+        Ok(())
+    }
 }
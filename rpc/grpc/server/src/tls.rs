@@ -0,0 +1,42 @@
+use crate::error::{GrpcServerError, GrpcServerResult};
+use std::path::{Path, PathBuf};
+use tonic::transport::{Certificate, Identity, Server as TonicServer, ServerTlsConfig};
+
+/// Paths of the PEM files securing the gRPC server with TLS.
+///
+/// When a client CA is provided, clients are required to authenticate with a certificate
+/// signed by this CA (mutual TLS).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrpcTlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+    pub client_ca: Option<PathBuf>,
+}
+
+impl GrpcTlsConfig {
+    pub fn new(cert: PathBuf, key: PathBuf) -> Self {
+        Self { cert, key, client_ca: None }
+    }
+
+    pub fn with_client_ca(self, client_ca: Option<PathBuf>) -> Self {
+        Self { client_ca, ..self }
+    }
+
+    /// Reads the PEM files into a server TLS configuration.
+    ///
+    /// The PEM content is only parsed when a server gets built with the configuration,
+    /// so one is built here in order for invalid files to fail at startup.
+    pub fn load(&self) -> GrpcServerResult<ServerTlsConfig> {
+        let identity = Identity::from_pem(read_pem("certificate", &self.cert)?, read_pem("key", &self.key)?);
+        let mut tls_config = ServerTlsConfig::new().identity(identity);
+        if let Some(client_ca) = self.client_ca.as_ref() {
+            tls_config = tls_config.client_ca_root(Certificate::from_pem(read_pem("client CA", client_ca)?));
+        }
+        TonicServer::builder().tls_config(tls_config.clone()).map_err(|err| GrpcServerError::TlsConfig(format!("{err:?}")))?;
+        Ok(tls_config)
+    }
+}
+
+fn read_pem(kind: &'static str, path: &Path) -> GrpcServerResult<Vec<u8>> {
+    std::fs::read(path).map_err(|err| GrpcServerError::TlsFile(kind, path.display().to_string(), err.to_string()))
+}
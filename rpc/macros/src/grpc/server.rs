@@ -68,11 +68,14 @@ impl ToTokens for RpcTable {
                     targets.push(quote! {
                         #payload_ops::#handler => {
                             let method: Method<#server_ctx_type, #connection_ctx_type, #kaspad_request_type, #kaspad_response_type> =
-                            Method::new(|server_ctx: #server_ctx_type, _: #connection_ctx_type, request: #kaspad_request_type| {
+                            Method::new(|server_ctx: #server_ctx_type, connection: #connection_ctx_type, request: #kaspad_request_type| {
                                 Box::pin(async move {
                                     let mut response: #kaspad_response_type = match request.payload {
                                         Some(Payload::#request_type(ref request)) => match request.try_into() {
-                                            Ok(request) => server_ctx.core_service.#fn_call(request).await.into(),
+                                            Ok(request) => match connection.authorize(kaspa_rpc_core::api::ops::RpcApiOps::#handler) {
+                                                Ok(()) => server_ctx.core_service.#fn_call(request).await.into(),
+                                                Err(err) => #response_message_type::from(err).into(),
+                                            },
                                             Err(err) => #response_message_type::from(err).into(),
                                         },
                                         _ => {
@@ -24,6 +24,7 @@ impl ProtocolConverter {
             time_offset: properties.time_offset,
             user_agent: properties.user_agent.clone(),
             advertised_protocol_version: properties.advertised_protocol_version,
+            protocol_version: properties.protocol_version,
             time_connected: peer.time_connected(),
         }
     }
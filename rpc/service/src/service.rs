@@ -22,9 +22,10 @@ use kaspa_consensusmanager::ConsensusManager;
 use kaspa_core::time::unix_now;
 use kaspa_core::{
     core::Core,
-    debug,
+    debug, info,
     kaspad_env::version,
     signals::Shutdown,
+    task::runtime::{AsyncRuntime, ServiceState},
     task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
     task::tick::TickService,
     trace, warn,
@@ -34,7 +35,10 @@ use kaspa_index_core::{
     connection::IndexChannelConnection, indexed_utxos::UtxoSetByScriptPublicKey, notification::Notification as IndexNotification,
     notifier::IndexNotifier,
 };
-use kaspa_mining::model::tx_query::TransactionQuery;
+use kaspa_mining::model::{
+    fee_rate::{LOW_BLOCKS, NORMAL_BLOCKS, PRIORITY_BLOCKS},
+    tx_query::TransactionQuery,
+};
 use kaspa_mining::{manager::MiningManagerProxy, mempool::tx::Orphan};
 use kaspa_notify::{
     collector::DynCollector,
@@ -45,7 +49,7 @@ use kaspa_notify::{
     scope::Scope,
     subscriber::{Subscriber, SubscriptionManager},
 };
-use kaspa_p2p_flows::flow_context::FlowContext;
+use kaspa_p2p_flows::flow_context::{FlowContext, PROTOCOL_VERSION};
 use kaspa_p2p_lib::common::ProtocolError;
 use kaspa_perf_monitor::{counters::CountersSnapshot, Monitor as PerfMonitor};
 use kaspa_rpc_core::{
@@ -58,13 +62,14 @@ use kaspa_rpc_core::{
     Notification, RpcError, RpcResult,
 };
 use kaspa_txscript::{extract_script_pub_key_address, pay_to_address_script};
-use kaspa_utils::{channel::Channel, triggers::SingleTrigger};
+use kaspa_utils::{channel::Channel, networking::NetAddress, triggers::SingleTrigger};
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 use kaspa_utxoindex::api::UtxoIndexProxy;
 use std::{
     collections::HashMap,
     iter::once,
     sync::{atomic::Ordering, Arc},
+    time::Duration,
     vec,
 };
 use tokio::join;
@@ -98,6 +103,7 @@ pub struct RpcCoreService {
     index_converter: Arc<IndexConverter>,
     protocol_converter: Arc<ProtocolConverter>,
     core: Arc<Core>,
+    async_runtime: Arc<AsyncRuntime>,
     processing_counters: Arc<ProcessingCounters>,
     wrpc_borsh_counters: Arc<WrpcServerCounters>,
     wrpc_json_counters: Arc<WrpcServerCounters>,
@@ -120,6 +126,7 @@ impl RpcCoreService {
         utxoindex: Option<UtxoIndexProxy>,
         config: Arc<Config>,
         core: Arc<Core>,
+        async_runtime: Arc<AsyncRuntime>,
         processing_counters: Arc<ProcessingCounters>,
         wrpc_borsh_counters: Arc<WrpcServerCounters>,
         wrpc_json_counters: Arc<WrpcServerCounters>,
@@ -183,6 +190,7 @@ impl RpcCoreService {
             index_converter,
             protocol_converter,
             core,
+            async_runtime,
             processing_counters,
             wrpc_borsh_counters,
             wrpc_json_counters,
@@ -471,12 +479,20 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             true => Orphan::Allowed,
             false => Orphan::Forbidden,
         };
-        self.flow_context.submit_rpc_transaction(&session, transaction, orphan).await.map_err(|err| {
-            let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
-            debug!("{err}");
-            err
-        })?;
-        Ok(SubmitTransactionResponse::new(transaction_id))
+        // mempool rejections are reported in the response so that the reason survives the
+        // wire encodings, see `SubmitTransactionResponse::into_result()`
+        match self.flow_context.submit_rpc_transaction(&session, transaction, orphan).await {
+            Ok(()) => Ok(SubmitTransactionResponse::new(transaction_id)),
+            Err(ProtocolError::MiningManagerError(err)) => {
+                debug!("{}", RpcError::SubmitTransactionError(transaction_id, (&err).into()));
+                Ok(SubmitTransactionResponse::new_rejected(transaction_id, (&err).into()))
+            }
+            Err(err) => {
+                let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
+                debug!("{err}");
+                Err(err)
+            }
+        }
     }
 
     async fn get_current_network_call(&self, _: GetCurrentNetworkRequest) -> RpcResult<GetCurrentNetworkResponse> {
@@ -706,7 +722,11 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             if connection_manager.ip_has_permanent_connection(ip).await {
                 return Err(RpcError::IpHasPermanentConnection(request.ip));
             }
-            connection_manager.ban(ip).await;
+            let reason = request.reason.unwrap_or_else(|| "banned via RPC".to_string());
+            match connection_manager.ban(ip, reason, request.duration.map(Duration::from_secs)).await {
+                Some(ban) => info!("Banned {} until {}: {}", request.ip, ban.expires_at, ban.reason),
+                None => return Err(RpcError::IpHasPermanentConnection(request.ip)),
+            }
         } else {
             return Err(RpcError::NoConnectionManager);
         }
@@ -727,6 +747,47 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(UnbanResponse {})
     }
 
+    async fn get_banned_peers_call(&self, _: GetBannedPeersRequest) -> RpcResult<GetBannedPeersResponse> {
+        let banned_peers = self
+            .flow_context
+            .address_manager
+            .lock()
+            .get_all_bans()
+            .into_iter()
+            .map(|(ip, ban)| RpcBannedPeer {
+                address: NetAddress::new(ip, 0),
+                reason: ban.reason,
+                banned_at: ban.banned_at,
+                expires_at: ban.expires_at,
+            })
+            .collect();
+        Ok(GetBannedPeersResponse::new(banned_peers))
+    }
+
+    async fn get_fee_estimate_call(&self, _: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let estimate = self.mining_manager.clone().estimate_fee_rates(&session).await;
+        // target_time_per_block is in milliseconds
+        let bucket =
+            |feerate: f64, blocks: u64| RpcFeeRateBucket::new(feerate, (blocks * self.config.target_time_per_block) as f64 / 1000.0);
+        Ok(GetFeeEstimateResponse::new(RpcFeeEstimate::new(
+            bucket(estimate.priority, PRIORITY_BLOCKS),
+            bucket(estimate.normal, NORMAL_BLOCKS),
+            bucket(estimate.low, LOW_BLOCKS),
+        )))
+    }
+
+    async fn set_log_level_call(&self, request: SetLogLevelRequest) -> RpcResult<SetLogLevelResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("SetLogLevel RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        kaspa_core::log::set_subsystem_log_level(request.subsystem.as_deref(), &request.level)
+            .map_err(|err| RpcError::General(err.to_string()))?;
+        info!("Log level of {} set to {}", request.subsystem.as_deref().unwrap_or("the node"), request.level);
+        Ok(SetLogLevelResponse {})
+    }
+
     async fn get_connected_peer_info_call(&self, _: GetConnectedPeerInfoRequest) -> RpcResult<GetConnectedPeerInfoResponse> {
         let peers = self.flow_context.hub().active_peers();
         let peer_info = self.protocol_converter.get_peers_info(&peers);
@@ -774,7 +835,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             disk_io_write_per_sec,
         } = self.perf_monitor.snapshot();
 
-        let process_metrics = req.process_metrics.then_some(ProcessMetrics {
+        let process_metrics = req.process_metrics.then(|| ProcessMetrics {
             resident_set_size,
             virtual_memory_size,
             core_num: core_num as u32,
@@ -784,6 +845,22 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             disk_io_write_bytes,
             disk_io_read_per_sec: disk_io_read_per_sec as f32,
             disk_io_write_per_sec: disk_io_write_per_sec as f32,
+            services: self
+                .async_runtime
+                .services_metrics()
+                .into_iter()
+                .map(|metrics| ServiceMetrics {
+                    ident: metrics.ident.to_string(),
+                    state: match metrics.state {
+                        ServiceState::Registered => RpcServiceState::Registered,
+                        ServiceState::Running => RpcServiceState::Running,
+                        ServiceState::Stopped => RpcServiceState::Stopped,
+                        ServiceState::Failed => RpcServiceState::Failed,
+                    },
+                    start_time: metrics.start_time,
+                    last_error: metrics.last_error,
+                })
+                .collect(),
         });
 
         let connection_metrics = req.connection_metrics.then_some(ConnectionMetrics {
@@ -830,6 +907,8 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 network_past_median_time: consensus_stats.virtual_stats.past_median_time,
                 network_virtual_parent_hashes_count: consensus_stats.virtual_stats.num_parents,
                 network_virtual_daa_score: consensus_stats.virtual_stats.daa_score,
+                // ---
+                node_sync_eta_seconds: self.processing_counters.sync_estimate().and_then(|estimate| estimate.remaining_secs()),
             })
         } else {
             None
@@ -854,6 +933,8 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             has_utxo_index: self.config.utxoindex,
             is_synced,
             virtual_daa_score,
+            p2p_protocol_version: PROTOCOL_VERSION,
+            mempool_size: self.mining_manager.transaction_count_sample(TransactionQuery::TransactionsOnly),
         })
     }
 
@@ -904,6 +985,18 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         self.notifier.clone().stop_notify(id, scope).await?;
         Ok(())
     }
+
+    /// Stop sending notifications of some type to a listener while keeping its subscription.
+    async fn mute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()> {
+        self.notifier.try_mute(id, event)?;
+        Ok(())
+    }
+
+    /// Resume sending notifications of some type to a listener.
+    async fn unmute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()> {
+        self.notifier.try_unmute(id, event)?;
+        Ok(())
+    }
 }
 
 const RPC_CORE_SERVICE: &str = "rpc-core-service";
@@ -0,0 +1,61 @@
+//!
+//! Client side of the wRPC token authentication (see [`kaspa_rpc_core::api::auth`]).
+//!
+
+use crate::imports::*;
+use kaspa_rpc_core::api::auth::WRPC_AUTH_ACCEPTED;
+use workflow_core::channel::Sender;
+use workflow_websocket::client::{Error as WebSocketError, Handshake, Message, Result as WebSocketResult};
+
+/// WebSocket handshake sending the authentication token to the server
+/// and awaiting its acknowledgement.
+pub struct AuthTokenHandshake {
+    token: String,
+}
+
+impl AuthTokenHandshake {
+    pub fn new(token: String) -> Self {
+        Self { token }
+    }
+}
+
+#[async_trait]
+impl Handshake for AuthTokenHandshake {
+    async fn handshake(&self, sender: &Sender<Message>, receiver: &Receiver<Message>) -> WebSocketResult<()> {
+        sender.send(Message::Text(self.token.clone())).await?;
+        match receiver.recv().await? {
+            Message::Text(response) if response == WRPC_AUTH_ACCEPTED => Ok(()),
+            _ => Err(WebSocketError::NegotiationFailure),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_token_handshake() {
+        futures::executor::block_on(auth_token_handshake());
+    }
+
+    async fn auth_token_handshake() {
+        let handshake = AuthTokenHandshake::new("secret".to_string());
+
+        // the server acknowledges the token
+        let (outgoing, incoming) = (Channel::unbounded(), Channel::unbounded());
+        incoming.sender.send(Message::Text(WRPC_AUTH_ACCEPTED.to_string())).await.unwrap();
+        assert!(handshake.handshake(&outgoing.sender, &incoming.receiver).await.is_ok());
+        assert_eq!(outgoing.receiver.try_recv().unwrap(), Message::Text("secret".to_string()));
+
+        // the server rejects the token by closing the connection
+        let (outgoing, incoming) = (Channel::unbounded(), Channel::<Message>::unbounded());
+        incoming.sender.close();
+        assert!(handshake.handshake(&outgoing.sender, &incoming.receiver).await.is_err());
+
+        // the server responds with anything but an acknowledgement
+        let (outgoing, incoming) = (Channel::unbounded(), Channel::unbounded());
+        incoming.sender.send(Message::Text("{}".to_string())).await.unwrap();
+        assert!(matches!(handshake.handshake(&outgoing.sender, &incoming.receiver).await, Err(WebSocketError::NegotiationFailure)));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::auth::AuthTokenHandshake;
 use crate::error::Error;
 use crate::imports::*;
 use crate::parse::parse_host;
@@ -12,6 +13,7 @@ use workflow_core::{channel::Multiplexer, runtime as application_runtime};
 use workflow_dom::utils::window;
 use workflow_rpc::client::Ctl as WrpcCtl;
 pub use workflow_rpc::client::{ConnectOptions, ConnectResult, ConnectStrategy, WebSocketConfig};
+use workflow_websocket::client::Handshake;
 
 // /// [`NotificationMode`] controls notification delivery process
 // #[wasm_bindgen]
@@ -38,12 +40,13 @@ struct Inner {
 }
 
 impl Inner {
-    pub fn new(encoding: Encoding, url: &str) -> Result<Inner> {
+    pub fn new(encoding: Encoding, url: &str, auth_token: Option<String>) -> Result<Inner> {
         // log_trace!("Kaspa wRPC::{encoding} connecting to: {url}");
         let rpc_ctl = RpcCtl::with_descriptor(url);
         let wrpc_ctl_multiplexer = Multiplexer::<WrpcCtl>::new();
 
-        let options = RpcClientOptions { url, ctl_multiplexer: Some(wrpc_ctl_multiplexer.clone()), ..RpcClientOptions::default() };
+        let handshake = auth_token.map(|token| Arc::new(AuthTokenHandshake::new(token)) as Arc<dyn Handshake>);
+        let options = RpcClientOptions { url, ctl_multiplexer: Some(wrpc_ctl_multiplexer.clone()), handshake };
 
         let notification_channel = Channel::unbounded();
 
@@ -125,6 +128,18 @@ impl Inner {
             self.rpc_client.call(RpcApiOps::Unsubscribe, scope).await.map_err(|err| err.to_string())?;
         Ok(())
     }
+
+    /// Stop sending notifications of some type to the client while keeping its subscription.
+    async fn mute_notify_to_client(&self, event: EventType) -> RpcResult<()> {
+        let _response: MuteResponse = self.rpc_client.call(RpcApiOps::Mute, event).await.map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    /// Resume sending notifications of some type to the client.
+    async fn unmute_notify_to_client(&self, event: EventType) -> RpcResult<()> {
+        let _response: UnmuteResponse = self.rpc_client.call(RpcApiOps::Unmute, event).await.map_err(|err| err.to_string())?;
+        Ok(())
+    }
 }
 
 impl Debug for Inner {
@@ -178,7 +193,18 @@ impl KaspaRpcClient {
 
     /// Extended constructor that accepts [`NotificationMode`] argument.
     pub fn new_with_args(encoding: Encoding, notification_mode: NotificationMode, url: &str) -> Result<KaspaRpcClient> {
-        let inner = Arc::new(Inner::new(encoding, url)?);
+        Self::new_with_auth_token(encoding, notification_mode, url, None)
+    }
+
+    /// Extended constructor authenticating with `auth_token` to servers requiring
+    /// token authentication.
+    pub fn new_with_auth_token(
+        encoding: Encoding,
+        notification_mode: NotificationMode,
+        url: &str,
+        auth_token: Option<String>,
+    ) -> Result<KaspaRpcClient> {
+        let inner = Arc::new(Inner::new(encoding, url, auth_token)?);
         let notifier = if matches!(notification_mode, NotificationMode::MultiListeners) {
             let enabled_events = EVENT_TYPE_ARRAY[..].into();
             let converter = Arc::new(RpcCoreConverter::new());
@@ -414,6 +440,9 @@ impl RpcApi for KaspaRpcClient {
             GetCoinSupply,
             GetConnectedPeerInfo,
             GetDaaScoreTimestampEstimate,
+            GetBannedPeers,
+            GetFeeEstimate,
+            SetLogLevel,
             GetServerInfo,
             GetCurrentNetwork,
             GetHeaders,
@@ -487,4 +516,34 @@ impl RpcApi for KaspaRpcClient {
         }
         Ok(())
     }
+
+    /// Stop sending notifications of some type to a listener while keeping its subscription.
+    ///
+    /// In the [`NotificationMode::MultiListeners`] mode, the notifications are muted by the client-side
+    /// notifier since the server subscription is shared by all the listeners. In the [`NotificationMode::Direct`]
+    /// mode, they are muted by the server, so they are not sent over the connection anymore.
+    async fn mute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()> {
+        match self.notification_mode {
+            NotificationMode::MultiListeners => {
+                self.notifier.as_ref().unwrap().try_mute(id, event)?;
+            }
+            NotificationMode::Direct => {
+                self.inner.mute_notify_to_client(event).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Resume sending notifications of some type to a listener.
+    async fn unmute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()> {
+        match self.notification_mode {
+            NotificationMode::MultiListeners => {
+                self.notifier.as_ref().unwrap().try_unmute(id, event)?;
+            }
+            NotificationMode::Direct => {
+                self.inner.unmute_notify_to_client(event).await?;
+            }
+        }
+        Ok(())
+    }
 }
@@ -5,7 +5,7 @@ pub use futures::*;
 pub use js_sys::Function;
 pub use kaspa_notify::{
     error::{Error as NotifyError, Result as NotifyResult},
-    events::EVENT_TYPE_ARRAY,
+    events::{EventType, EVENT_TYPE_ARRAY},
     listener::ListenerId,
     notifier::{Notifier, Notify},
     scope::*,
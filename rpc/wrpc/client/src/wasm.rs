@@ -303,6 +303,8 @@ build_wrpc_wasm_bindgen_interface!(
         Shutdown,
         GetServerInfo,
         GetSyncStatus,
+        GetBannedPeers,
+        GetFeeEstimate,
     ],
     [
         // functions with `request` argument
@@ -324,6 +326,7 @@ build_wrpc_wasm_bindgen_interface!(
         // GetUtxosByAddresses,
         GetVirtualChainFromBlock,
         ResolveFinalityConflict,
+        SetLogLevel,
         SubmitBlock,
         // SubmitTransaction,
         Unban,
@@ -377,7 +380,7 @@ impl RpcClient {
 impl RpcClient {
     pub async fn submit_transaction(&self, request: SubmitTransactionRequest) -> Result<SubmitTransactionResponse> {
         let result: RpcResult<SubmitTransactionResponse> = self.client.submit_transaction_call(request).await;
-        let response: SubmitTransactionResponse = result?;
+        let response: SubmitTransactionResponse = result?.into_result()?;
         Ok(response)
     }
 }
@@ -6,7 +6,11 @@ use kaspa_notify::{
     notification::Notification as NotificationT,
     notifier::Notify,
 };
-use kaspa_rpc_core::{api::ops::RpcApiOps, notify::mode::NotificationMode, Notification};
+use kaspa_rpc_core::{
+    api::{auth::RpcAccessLevel, ops::RpcApiOps},
+    notify::mode::NotificationMode,
+    Notification, RpcResult,
+};
 use std::{
     fmt::Debug,
     sync::{Arc, Mutex},
@@ -48,6 +52,7 @@ struct ConnectionInner {
     pub peer: SocketAddr,
     pub messenger: Arc<Messenger>,
     pub grpc_client: Option<Arc<GrpcClient>>,
+    pub access_level: RpcAccessLevel,
     // not using an atomic in case an Id will change type in the future...
     pub listener_id: Mutex<Option<ListenerId>>,
 }
@@ -76,12 +81,18 @@ pub struct Connection {
 }
 
 impl Connection {
-    pub fn new(id: u64, peer: &SocketAddr, messenger: Arc<Messenger>, grpc_client: Option<Arc<GrpcClient>>) -> Connection {
+    pub fn new(
+        id: u64,
+        peer: &SocketAddr,
+        messenger: Arc<Messenger>,
+        grpc_client: Option<Arc<GrpcClient>>,
+        access_level: RpcAccessLevel,
+    ) -> Connection {
         // If a GrpcClient is provided, it has to come configured in direct mode
         assert!(grpc_client.is_none() || grpc_client.as_ref().unwrap().notification_mode() == NotificationMode::Direct);
         // Should a gRPC client be provided, no listener_id is required for subscriptions so the listener id is set to default
         let listener_id = Mutex::new(grpc_client.clone().map(|_| ListenerId::default()));
-        Connection { inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, listener_id }) }
+        Connection { inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, access_level, listener_id }) }
     }
 
     /// Obtain the connection id
@@ -118,6 +129,16 @@ impl Connection {
         &self.inner.peer
     }
 
+    /// Get the access level granted to the connection at handshake
+    pub fn access_level(&self) -> RpcAccessLevel {
+        self.inner.access_level
+    }
+
+    /// Checks that the connection is allowed to call the RPC method `op`
+    pub fn authorize(&self, op: RpcApiOps) -> RpcResult<()> {
+        self.inner.access_level.authorize(op)
+    }
+
     /// Creates a WebSocket [`Message`] that can be posted to the connection ([`Messenger`]) sink
     /// directly.
     pub fn create_serialized_notification_message<Ops, Msg>(encoding: Encoding, op: Ops, msg: Msg) -> WrpcResult<Message>
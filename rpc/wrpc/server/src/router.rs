@@ -1,5 +1,5 @@
 use crate::{connection::*, server::*};
-use kaspa_notify::scope::Scope;
+use kaspa_notify::{events::EventType, scope::Scope};
 use kaspa_rpc_core::{api::ops::RpcApiOps, prelude::*};
 use kaspa_rpc_macros::build_wrpc_server_interface;
 use std::sync::Arc;
@@ -45,6 +45,9 @@ impl Router {
                 GetCoinSupply,
                 GetConnectedPeerInfo,
                 GetDaaScoreTimestampEstimate,
+                GetBannedPeers,
+                GetFeeEstimate,
+                SetLogLevel,
                 GetServerInfo,
                 GetCurrentNetwork,
                 GetHeaders,
@@ -92,6 +95,26 @@ impl Router {
             }),
         );
 
+        interface.method(
+            RpcApiOps::Mute,
+            workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, event: EventType| {
+                Box::pin(async move {
+                    manager.mute_notify(&connection, event, true).await.map_err(|err| err.to_string())?;
+                    Ok(MuteResponse {})
+                })
+            }),
+        );
+
+        interface.method(
+            RpcApiOps::Unmute,
+            workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, event: EventType| {
+                Box::pin(async move {
+                    manager.mute_notify(&connection, event, false).await.map_err(|err| err.to_string())?;
+                    Ok(UnmuteResponse {})
+                })
+            }),
+        );
+
         Router { interface: Arc::new(interface), server_context }
     }
 }
@@ -5,9 +5,18 @@ use crate::{
     service::Options,
 };
 use kaspa_grpc_client::GrpcClient;
-use kaspa_notify::{connection::ChannelType, events::EVENT_TYPE_ARRAY, notifier::Notifier, scope::Scope, subscriber::Subscriber};
+use kaspa_notify::{
+    connection::ChannelType,
+    events::{EventType, EVENT_TYPE_ARRAY},
+    notifier::Notifier,
+    scope::Scope,
+    subscriber::Subscriber,
+};
 use kaspa_rpc_core::{
-    api::rpc::{DynRpcService, RpcApi},
+    api::{
+        auth::RpcAccessLevel,
+        rpc::{DynRpcService, RpcApi},
+    },
     notify::{channel::NotificationChannel, connection::ChannelConnection, mode::NotificationMode},
     Notification, RpcResult,
 };
@@ -88,7 +97,7 @@ impl Server {
         }
     }
 
-    pub async fn connect(&self, peer: &SocketAddr, messenger: Arc<Messenger>) -> Result<Connection> {
+    pub async fn connect(&self, peer: &SocketAddr, messenger: Arc<Messenger>, access_level: RpcAccessLevel) -> Result<Connection> {
         // log_trace!("WebSocket connected: {}", peer);
         let id = self.inner.next_connection_id.fetch_add(1, Ordering::SeqCst);
 
@@ -112,7 +121,7 @@ impl Server {
         } else {
             None
         };
-        let connection = Connection::new(id, peer, messenger, grpc_client);
+        let connection = Connection::new(id, peer, messenger, grpc_client, access_level);
         if self.inner.options.grpc_proxy_address.is_some() {
             // log_trace!("starting gRPC");
             connection.grpc_client().start(Some(connection.grpc_client_notify_target())).await;
@@ -190,6 +199,23 @@ impl Server {
         Ok(())
     }
 
+    /// Stop (`muted` = true) or resume sending the notifications of `event` type to `connection`
+    /// while keeping its subscriptions.
+    pub async fn mute_notify(&self, connection: &Connection, event: EventType, muted: bool) -> RpcResult<()> {
+        if let Some(listener_id) = connection.listener_id() {
+            workflow_log::log_trace!("notification mute[0x{listener_id:x}] {event:?} {muted}");
+            match (&self.inner.rpc_core, muted) {
+                (Some(rpc_core), true) => rpc_core.wrpc_notifier.try_mute(listener_id, event)?,
+                (Some(rpc_core), false) => rpc_core.wrpc_notifier.try_unmute(listener_id, event)?,
+                (None, true) => connection.grpc_client().mute_notify(listener_id, event).await?,
+                (None, false) => connection.grpc_client().unmute_notify(listener_id, event).await?,
+            }
+        } else {
+            workflow_log::log_trace!("notification mute[N/A] {event:?} {muted}");
+        }
+        Ok(())
+    }
+
     pub fn verbose(&self) -> bool {
         self.inner.options.verbose
     }
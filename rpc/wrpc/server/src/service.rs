@@ -1,30 +1,44 @@
 use crate::{connection::*, router::*, server::*};
 use async_trait::async_trait;
+use futures::{Sink, SinkExt, Stream, StreamExt};
 use kaspa_core::{
-    info,
+    debug, info,
     task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
     trace, warn,
 };
-use kaspa_rpc_core::api::ops::RpcApiOps;
+use kaspa_rpc_core::api::{
+    auth::{RpcAccessLevel, RpcAuthTokens, WRPC_AUTH_ACCEPTED},
+    ops::RpcApiOps,
+};
 use kaspa_rpc_service::service::RpcCoreService;
 use kaspa_utils::triggers::SingleTrigger;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
 use workflow_rpc::server::prelude::*;
 pub use workflow_rpc::server::{Encoding as WrpcEncoding, WebSocketConfig, WebSocketCounters};
 
 static MAX_WRPC_MESSAGE_SIZE: usize = 1024 * 1024 * 128; // 128MB
 
+/// Time given to a client to send its authentication token once connected
+const WRPC_AUTH_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Options for configuring the wRPC server
 pub struct Options {
     pub listen_address: String,
     pub grpc_proxy_address: Option<String>,
     pub verbose: bool,
+    /// Tokens clients authenticate with, see [`kaspa_rpc_core::api::auth`].
+    pub auth_tokens: RpcAuthTokens,
 }
 
 impl Default for Options {
     fn default() -> Self {
-        Options { listen_address: "127.0.0.1:17110".to_owned(), verbose: false, grpc_proxy_address: None }
+        Options {
+            listen_address: "127.0.0.1:17110".to_owned(),
+            verbose: false,
+            grpc_proxy_address: None,
+            auth_tokens: RpcAuthTokens::default(),
+        }
     }
 }
 
@@ -66,20 +80,20 @@ impl RpcHandler for KaspaRpcHandler {
     async fn handshake(
         self: Arc<Self>,
         peer: &SocketAddr,
-        _sender: &mut WebSocketSender,
-        _receiver: &mut WebSocketReceiver,
+        sender: &mut WebSocketSender,
+        receiver: &mut WebSocketReceiver,
         messenger: Arc<Messenger>,
     ) -> WebSocketResult<Connection> {
-        // TODO - discuss and implement handshake
-        // handshake::greeting(
-        //     std::time::Duration::from_millis(3000),
-        //     sender,
-        //     receiver,
-        //     Box::pin(|msg| if msg != "kaspa" { Err(WebSocketError::NegotiationFailure) } else { Ok(()) }),
-        // )
-        // .await
-
-        let connection = self.server.connect(peer, messenger).await.map_err(|err| err.to_string())?;
+        let access_level = if self.options.auth_tokens.is_empty() {
+            RpcAccessLevel::Admin
+        } else {
+            authenticate(&self.options.auth_tokens, sender, receiver).await.map_err(|err| {
+                debug!("wRPC rejected the connection of {peer}: {err}");
+                err
+            })?
+        };
+
+        let connection = self.server.connect(peer, messenger, access_level).await.map_err(|err| err.to_string())?;
         Ok(connection)
     }
 
@@ -91,6 +105,31 @@ impl RpcHandler for KaspaRpcHandler {
     }
 }
 
+/// Server side of the wRPC token authentication: the first message of the client
+/// must be one of the `auth_tokens`, which is then acknowledged with [`WRPC_AUTH_ACCEPTED`].
+/// Returns the access level granted by the token.
+async fn authenticate<S, R, E>(
+    auth_tokens: &RpcAuthTokens,
+    sender: &mut S,
+    receiver: &mut R,
+) -> WebSocketResult<RpcAccessLevel>
+where
+    S: Sink<Message> + Unpin,
+    WebSocketError: From<S::Error>,
+    R: Stream<Item = Result<Message, E>> + Unpin,
+{
+    let token = match tokio::time::timeout(WRPC_AUTH_TIMEOUT, receiver.next()).await {
+        Ok(Some(Ok(Message::Text(token)))) => token,
+        Ok(_) => return Err(WebSocketError::NegotiationFailure),
+        Err(_) => return Err(WebSocketError::ConnectionTimeout),
+    };
+    let Some(access_level) = auth_tokens.access_level(&token) else {
+        return Err(WebSocketError::NegotiationFailure);
+    };
+    sender.send(Message::Text(WRPC_AUTH_ACCEPTED.to_string())).await?;
+    Ok(access_level)
+}
+
 ///
 ///  wRPC Server - A wrapper around and an initializer of the RpcServer
 ///
@@ -205,3 +244,44 @@ impl AsyncService for WrpcService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::channel::mpsc;
+
+    const AUTH_TOKENS: [(&str, RpcAccessLevel); 3] =
+        [("secret", RpcAccessLevel::Admin), ("miner", RpcAccessLevel::Write), ("public", RpcAccessLevel::Read)];
+
+    fn auth_tokens() -> RpcAuthTokens {
+        RpcAuthTokens::new(Some("secret".to_string()), Some("miner".to_string()), Some("public".to_string()))
+    }
+
+    async fn handshake(client_message: Option<Message>) -> (WebSocketResult<RpcAccessLevel>, Option<Message>) {
+        let (client_tx, mut server_rx) = mpsc::unbounded::<Result<Message, ()>>();
+        let (server_tx, mut client_rx) = mpsc::unbounded::<Message>();
+        if let Some(message) = client_message {
+            client_tx.unbounded_send(Ok(message)).unwrap();
+        }
+        drop(client_tx);
+        let mut server_tx = server_tx.sink_map_err(std::io::Error::other);
+        let result = authenticate(&auth_tokens(), &mut server_tx, &mut server_rx).await;
+        drop(server_tx);
+        (result, client_rx.next().await)
+    }
+
+    #[tokio::test]
+    async fn test_authenticate() {
+        for (token, access_level) in AUTH_TOKENS {
+            let (result, response) = handshake(Some(Message::Text(token.to_string()))).await;
+            assert_eq!(result.unwrap(), access_level);
+            assert_eq!(response, Some(Message::Text(WRPC_AUTH_ACCEPTED.to_string())));
+        }
+
+        for client_message in [Some(Message::Text("guess".to_string())), Some(Message::Binary(b"secret".to_vec())), None] {
+            let (result, response) = handshake(client_message).await;
+            assert!(matches!(result, Err(WebSocketError::NegotiationFailure)));
+            assert_eq!(response, None);
+        }
+    }
+}
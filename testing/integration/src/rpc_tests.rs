@@ -273,14 +273,17 @@ async fn sanity_test() {
                     let ip = peer_address.normalize(1).ip;
 
                     let _ = rpc_client.add_peer_call(AddPeerRequest { peer_address, is_permanent: false }).await.unwrap();
-                    let _ = rpc_client.ban_call(BanRequest { ip }).await.unwrap();
+                    let _ = rpc_client.ban_call(BanRequest::new(ip).with_reason("test".to_string())).await.unwrap();
 
                     let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest {}).await.unwrap();
                     assert!(response.banned_addresses.contains(&ip));
+                    let banned_peers = rpc_client.get_banned_peers().await.unwrap();
+                    assert!(banned_peers.iter().any(|peer| peer.address.ip == ip && peer.reason == "test"));
 
                     let _ = rpc_client.unban_call(UnbanRequest { ip }).await.unwrap();
                     let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest {}).await.unwrap();
                     assert!(!response.banned_addresses.contains(&ip));
+                    assert!(rpc_client.get_banned_peers().await.unwrap().is_empty());
                 })
             }
 
@@ -288,6 +291,10 @@ async fn sanity_test() {
                 tst!(op, "see Ban")
             }
 
+            KaspadPayloadOps::GetBannedPeers => {
+                tst!(op, "see Ban")
+            }
+
             KaspadPayloadOps::SubmitTransaction => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -443,6 +450,29 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::GetFeeEstimate => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // An empty mempool only requires the minimum relay fee rate
+                    let estimate = rpc_client.get_fee_estimate().await.unwrap();
+                    for bucket in [estimate.priority_bucket, estimate.normal_bucket, estimate.low_bucket] {
+                        assert_eq!(bucket.feerate, 1.0);
+                    }
+                    assert!(estimate.priority_bucket.estimated_seconds < estimate.normal_bucket.estimated_seconds);
+                    assert!(estimate.normal_bucket.estimated_seconds < estimate.low_bucket.estimated_seconds);
+                })
+            }
+
+            KaspadPayloadOps::SetLogLevel => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    rpc_client.set_log_level(Some("kaspa_rpc_service".to_string()), "debug".to_string()).await.unwrap();
+                    rpc_client.set_log_level(None, "info".to_string()).await.unwrap();
+                    assert!(rpc_client.set_log_level(Some("kaspa rpc".to_string()), "debug".to_string()).await.is_err());
+                    assert!(rpc_client.set_log_level(None, "verbose".to_string()).await.is_err());
+                })
+            }
+
             KaspadPayloadOps::Ping => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -509,6 +539,7 @@ async fn sanity_test() {
                     let response = rpc_client.get_server_info_call(GetServerInfoRequest {}).await.unwrap();
                     assert!(response.has_utxo_index); // we set utxoindex above
                     assert_eq!(response.network_id, daemon.network);
+                    assert!(response.p2p_protocol_version > 0);
                 })
             }
 
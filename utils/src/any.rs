@@ -1,3 +1,6 @@
+use std::any::Any;
+use std::sync::Arc;
+
 /// Provides a best-effort short type name
 pub fn type_name_short<T: ?Sized>() -> &'static str {
     let s = std::any::type_name::<T>();
@@ -12,6 +15,54 @@ pub fn type_name_short<T: ?Sized>() -> &'static str {
     }
 }
 
+/// Error returned by [`downcast_arc`] and [`downcast_ref`] when the value is not of the expected type
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("downcast failed: expected `{expected}`, found `{actual}`")]
+pub struct DowncastError {
+    pub expected: &'static str,
+    pub actual: &'static str,
+}
+
+/// A type-erased value that retains the name of its concrete type,
+/// allowing failed downcasts to report what was actually received.
+/// Implemented for all `Send + Sync + 'static` types.
+pub trait NamedAny: Any + Send + Sync {
+    /// The name of the concrete type
+    fn type_name(&self) -> &'static str;
+
+    fn as_any(&self) -> &(dyn Any + Send + Sync);
+
+    fn into_any_arc(self: Arc<Self>) -> Arc<dyn Any + Send + Sync>;
+}
+
+impl<T: Any + Send + Sync> NamedAny for T {
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+
+    fn into_any_arc(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
+        self
+    }
+}
+
+/// Downcasts a type-erased [`Arc`] to the concrete type `T`
+pub fn downcast_arc<T: Any + Send + Sync>(value: Arc<dyn NamedAny>) -> Result<Arc<T>, DowncastError> {
+    let actual = NamedAny::type_name(&*value);
+    value.into_any_arc().downcast::<T>().map_err(|_| DowncastError { expected: std::any::type_name::<T>(), actual })
+}
+
+/// Downcasts a type-erased reference to the concrete type `T`
+pub fn downcast_ref<T: Any + Send + Sync>(value: &dyn NamedAny) -> Result<&T, DowncastError> {
+    value
+        .as_any()
+        .downcast_ref::<T>()
+        .ok_or_else(|| DowncastError { expected: std::any::type_name::<T>(), actual: NamedAny::type_name(value) })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -27,4 +78,25 @@ mod tests {
             "generic types are expected to remain long"
         );
     }
+
+    #[derive(Debug)]
+    struct Handler;
+
+    #[test]
+    fn test_downcast() {
+        let value: Arc<dyn NamedAny> = Arc::new(Handler);
+        assert!(downcast_ref::<Handler>(value.as_ref()).is_ok());
+        assert!(downcast_arc::<Handler>(value).is_ok());
+
+        let value: Arc<dyn NamedAny> = Arc::new(String::from("handler"));
+        let err = downcast_ref::<Handler>(value.as_ref()).unwrap_err();
+        assert_eq!(err.expected, std::any::type_name::<Handler>());
+        assert_eq!(err.actual, std::any::type_name::<String>());
+
+        let err = downcast_arc::<Handler>(value).unwrap_err();
+        assert_eq!(err, DowncastError { expected: std::any::type_name::<Handler>(), actual: std::any::type_name::<String>() });
+        let message = err.to_string();
+        assert!(message.contains("any::tests::Handler"), "{message}");
+        assert!(message.contains("alloc::string::String"), "{message}");
+    }
 }
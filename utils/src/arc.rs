@@ -1,3 +1,4 @@
+use arc_swap::ArcSwap;
 use std::sync::Arc;
 
 pub trait ArcExtensions<T> {
@@ -10,3 +11,106 @@ impl<T: Clone> ArcExtensions<T> for Arc<T> {
         Arc::try_unwrap(self).unwrap_or_else(|arc| (*arc).clone())
     }
 }
+
+/// A shared value that can be atomically replaced. Readers obtain the current
+/// snapshot using [`AtomicArc::load`] without locking, while writers publish
+/// a new snapshot using [`AtomicArc::store`]. Intended for read-mostly data.
+pub struct AtomicArc<T> {
+    inner: ArcSwap<T>,
+}
+
+impl<T> AtomicArc<T> {
+    pub fn new(value: T) -> Self {
+        Self { inner: ArcSwap::from_pointee(value) }
+    }
+
+    /// Returns the current snapshot
+    pub fn load(&self) -> Arc<T> {
+        self.inner.load_full()
+    }
+
+    /// Replaces the current snapshot with `value`
+    pub fn store(&self, value: T) {
+        self.inner.store(Arc::new(value))
+    }
+
+    /// Replaces the current snapshot with `value`, returning the previous snapshot
+    pub fn swap(&self, value: T) -> Arc<T> {
+        self.inner.swap(Arc::new(value))
+    }
+
+    /// Atomically replaces the current snapshot with the value produced by `f`.
+    /// `f` may be called multiple times if the snapshot is concurrently replaced.
+    pub fn update(&self, f: impl Fn(&T) -> T) {
+        self.inner.rcu(|current| f(current));
+    }
+}
+
+impl<T: Default> Default for AtomicArc<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<Arc<T>> for AtomicArc<T> {
+    fn from(value: Arc<T>) -> Self {
+        Self { inner: ArcSwap::new(value) }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for AtomicArc<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AtomicArc").field(&self.load()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_atomic_arc() {
+        let value = AtomicArc::new(1);
+        let snapshot = value.load();
+        value.store(2);
+        assert_eq!(*snapshot, 1);
+        assert_eq!(*value.load(), 2);
+        assert_eq!(*value.swap(3), 2);
+        value.update(|v| v + 1);
+        assert_eq!(*value.load(), 4);
+    }
+
+    #[test]
+    fn test_atomic_arc_concurrent_load_store() {
+        // each snapshot holds a pair that must always be observed consistently
+        let value = Arc::new(AtomicArc::new((0u64, 0u64)));
+        let writers = (0..4)
+            .map(|_| {
+                let value = value.clone();
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        value.update(|(a, _)| (a + 1, (a + 1) * 2));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        let readers = (0..4)
+            .map(|_| {
+                let value = value.clone();
+                thread::spawn(move || {
+                    let mut last = 0;
+                    for _ in 0..1000 {
+                        let snapshot = value.load();
+                        assert_eq!(snapshot.1, snapshot.0 * 2);
+                        assert!(snapshot.0 >= last);
+                        last = snapshot.0;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        writers.into_iter().chain(readers).for_each(|handle| handle.join().unwrap());
+        assert_eq!(*value.load(), (4000, 8000));
+    }
+}
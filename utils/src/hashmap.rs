@@ -49,3 +49,394 @@ where
         result
     }
 }
+
+/// A hash map based counter tallying occurrences of keys
+#[derive(Debug, Clone)]
+pub struct Counter<K> {
+    counts: HashMap<K, u64>,
+}
+
+impl<K> Default for Counter<K> {
+    fn default() -> Self {
+        Self { counts: HashMap::new() }
+    }
+}
+
+impl<K: Hash + Eq> Counter<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Increments the count of `key` by one and returns the updated count
+    pub fn incr(&mut self, key: K) -> u64 {
+        self.incr_by(key, 1)
+    }
+
+    /// Increments the count of `key` by `n` and returns the updated count
+    pub fn incr_by(&mut self, key: K, n: u64) -> u64 {
+        let count = self.counts.entry(key).or_default();
+        *count += n;
+        *count
+    }
+
+    /// Returns the count of `key` (zero if the key was never counted)
+    pub fn get(&self, key: &K) -> u64 {
+        self.counts.get(key).copied().unwrap_or_default()
+    }
+
+    /// Returns the number of distinct keys
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Returns the sum of all counts
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.clear()
+    }
+
+    /// Iterates over `(key, count)` pairs in descending count order. Keys
+    /// with equal counts are ordered by ascending key order.
+    pub fn iter_by_count(&self) -> impl Iterator<Item = (&K, u64)>
+    where
+        K: Ord,
+    {
+        let mut entries = self.counts.iter().map(|(k, &c)| (k, c)).collect::<Vec<_>>();
+        entries.sort_unstable_by(|(k1, c1), (k2, c2)| c2.cmp(c1).then_with(|| k1.cmp(k2)));
+        entries.into_iter()
+    }
+
+    /// Returns the `n` keys with the highest counts (see [`Counter::iter_by_count`] for tie ordering)
+    pub fn top_n(&self, n: usize) -> Vec<(&K, u64)>
+    where
+        K: Ord,
+    {
+        self.iter_by_count().take(n).collect()
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        iter.into_iter().for_each(|key| {
+            counter.incr(key);
+        });
+        counter
+    }
+}
+
+const NIL: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+struct LruNode<K, V> {
+    key: K,
+    value: V,
+    prev: usize,
+    next: usize,
+}
+
+/// A capacity bounded cache evicting the least recently used entry once full.
+///
+/// Entries are kept in a vector linked into a recency list by index, giving
+/// O(1) [`get`](LruCache::get), [`put`](LruCache::put) and [`remove`](LruCache::remove).
+/// Lookups via [`get`](LruCache::get) are tallied as hits and misses for metrics.
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    map: HashMap<K, usize>,
+    nodes: Vec<LruNode<K, V>>,
+    /// Most recently used node
+    head: usize,
+    /// Least recently used node
+    tail: usize,
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// Creates a cache holding at most `capacity` entries. Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "LruCache capacity must be non-zero");
+        Self {
+            map: HashMap::with_capacity(capacity),
+            nodes: Vec::with_capacity(capacity),
+            head: NIL,
+            tail: NIL,
+            capacity,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the value of `key` marking it as the most recently used entry
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        match self.map.get(key).copied() {
+            Some(index) => {
+                self.hits += 1;
+                self.touch(index);
+                Some(&self.nodes[index].value)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Returns the value of `key` without affecting the recency order or the counters
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.map.get(key).map(|&index| &self.nodes[index].value)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Inserts `value` as the most recently used entry, evicting the least recently
+    /// used entry if the cache is full. Returns the previous value of `key`, if any.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&index) = self.map.get(&key) {
+            self.touch(index);
+            return Some(std::mem::replace(&mut self.nodes[index].value, value));
+        }
+
+        if self.nodes.len() == self.capacity {
+            self.remove_at(self.tail);
+        }
+
+        let index = self.nodes.len();
+        self.nodes.push(LruNode { key: key.clone(), value, prev: NIL, next: NIL });
+        self.map.insert(key, index);
+        self.attach_front(index);
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.map.get(key).copied().map(|index| self.remove_at(index).1)
+    }
+
+    /// Removes all entries, retaining the hit and miss counters
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.nodes.clear();
+        self.head = NIL;
+        self.tail = NIL;
+    }
+
+    /// Number of [`get`](LruCache::get) calls that found their key
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of [`get`](LruCache::get) calls that did not find their key
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn reset_counters(&mut self) {
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Iterates over `(key, value)` pairs from the most to the least recently used
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let first = (self.head != NIL).then_some(self.head);
+        std::iter::successors(first, |&index| {
+            let next = self.nodes[index].next;
+            (next != NIL).then_some(next)
+        })
+        .map(|index| (&self.nodes[index].key, &self.nodes[index].value))
+    }
+
+    fn touch(&mut self, index: usize) {
+        if self.head != index {
+            self.detach(index);
+            self.attach_front(index);
+        }
+    }
+
+    fn detach(&mut self, index: usize) {
+        let (prev, next) = (self.nodes[index].prev, self.nodes[index].next);
+        if prev != NIL {
+            self.nodes[prev].next = next;
+        } else {
+            self.head = next;
+        }
+        if next != NIL {
+            self.nodes[next].prev = prev;
+        } else {
+            self.tail = prev;
+        }
+    }
+
+    fn attach_front(&mut self, index: usize) {
+        self.nodes[index].prev = NIL;
+        self.nodes[index].next = self.head;
+        if self.head != NIL {
+            self.nodes[self.head].prev = index;
+        } else {
+            self.tail = index;
+        }
+        self.head = index;
+    }
+
+    fn remove_at(&mut self, index: usize) -> (K, V) {
+        self.detach(index);
+        let node = self.nodes.swap_remove(index);
+        self.map.remove(&node.key);
+        if index < self.nodes.len() {
+            // the last node has been moved into the vacated slot, relink it
+            let (prev, next) = (self.nodes[index].prev, self.nodes[index].next);
+            if prev != NIL {
+                self.nodes[prev].next = index;
+            } else {
+                self.head = index;
+            }
+            if next != NIL {
+                self.nodes[next].prev = index;
+            } else {
+                self.tail = index;
+            }
+            *self.map.get_mut(&self.nodes[index].key).expect("moved LruCache node must be mapped") = index;
+        }
+        (node.key, node.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter() {
+        let mut counter = Counter::new();
+        assert!(counter.is_empty());
+        assert_eq!(counter.incr("a"), 1);
+        assert_eq!(counter.incr("a"), 2);
+        assert_eq!(counter.incr_by("b", 5), 5);
+        assert_eq!(counter.incr("c"), 1);
+        assert_eq!(counter.get(&"a"), 2);
+        assert_eq!(counter.get(&"d"), 0);
+        assert_eq!(counter.len(), 3);
+        assert_eq!(counter.total(), 8);
+
+        assert_eq!(counter.top_n(2), vec![(&"b", 5), (&"a", 2)]);
+        assert_eq!(counter.top_n(10).len(), 3);
+        assert!(counter.top_n(0).is_empty());
+    }
+
+    #[test]
+    fn test_counter_ties() {
+        let counter = ["d", "b", "c", "a", "b", "d"].into_iter().collect::<Counter<_>>();
+        assert_eq!(counter.iter_by_count().collect::<Vec<_>>(), vec![(&"b", 2), (&"d", 2), (&"a", 1), (&"c", 1)]);
+        assert_eq!(counter.top_n(3), vec![(&"b", 2), (&"d", 2), (&"a", 1)]);
+    }
+
+    #[test]
+    fn test_lru_cache_eviction_order() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, "a");
+        cache.put(2, "b");
+        cache.put(3, "c");
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![3, 2, 1]);
+
+        // touching 1 makes 2 the least recently used entry
+        assert_eq!(cache.get(&1), Some(&"a"));
+        cache.put(4, "d");
+        assert!(!cache.contains_key(&2));
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![4, 1, 3]);
+
+        // replacing a value refreshes the entry without evicting
+        assert_eq!(cache.put(3, "C"), Some("c"));
+        assert_eq!(cache.len(), 3);
+        cache.put(5, "e");
+        assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(&5, &"e"), (&3, &"C"), (&4, &"d")]);
+
+        // peek does not affect the recency order
+        assert_eq!(cache.peek(&4), Some(&"d"));
+        cache.put(6, "f");
+        assert!(!cache.contains_key(&4));
+
+        assert_eq!(cache.remove(&5), Some("e"));
+        assert_eq!(cache.remove(&5), None);
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![6, 3]);
+        cache.put(7, "g");
+        cache.put(8, "h");
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![8, 7, 6]);
+    }
+
+    #[test]
+    fn test_lru_cache_capacity() {
+        let mut cache = LruCache::new(10);
+        for i in 0..100 {
+            cache.put(i, i * 2);
+            assert!(cache.len() <= cache.capacity());
+        }
+        assert_eq!(cache.len(), 10);
+        assert!((0..90).all(|i| !cache.contains_key(&i)));
+        assert!((90..100).all(|i| cache.peek(&i) == Some(&(i * 2))));
+
+        // interleaved removals and insertions keep the list consistent
+        for i in (90..100).step_by(2) {
+            cache.remove(&i);
+        }
+        for i in 100..110 {
+            cache.put(i, i * 2);
+        }
+        assert_eq!(cache.len(), 10);
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), (100..110).rev().collect::<Vec<_>>());
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.iter().count(), 0);
+        cache.put(1, 1);
+        assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(&1, &1)]);
+
+        let mut cache = LruCache::new(1);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(&"b", &2)]);
+    }
+
+    #[test]
+    fn test_lru_cache_counters() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, ());
+        cache.put(2, ());
+        assert!(cache.get(&1).is_some());
+        assert!(cache.get(&3).is_none());
+        cache.put(3, ());
+        assert!(cache.get(&2).is_none());
+        assert!(cache.get(&3).is_some());
+        assert!(cache.get(&1).is_some());
+        // peek and contains_key are not tallied
+        assert!(cache.peek(&1).is_some());
+        assert!(!cache.contains_key(&4));
+        assert_eq!((cache.hits(), cache.misses()), (3, 2));
+
+        cache.clear();
+        assert_eq!((cache.hits(), cache.misses()), (3, 2));
+        cache.reset_counters();
+        assert!(cache.get(&1).is_none());
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+    }
+}
@@ -8,6 +8,7 @@ pub mod iter;
 pub mod mem_size;
 pub mod networking;
 pub mod option;
+pub mod rate;
 pub mod refs;
 
 pub mod as_slice;
@@ -66,6 +67,29 @@ pub mod as_slice;
 /// let from_json: MyStructSmallVec = serde_json::from_str(&json).unwrap();
 /// assert_eq!(test_struct, from_json);
 /// ```
+/// ## Serialize the Vec field of the struct without a length prefix
+/// ```
+/// #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+/// struct MyStructRaw {
+///     #[serde(
+///         serialize_with = "kaspa_utils::serde_bytes::raw::serialize",
+///         deserialize_with = "kaspa_utils::serde_bytes::raw::deserialize::<_, _, 4>"
+///     )]
+///     v: Vec<u8>,
+/// }
+/// let test_struct = MyStructRaw { v: vec![0, 1, 2, 3] };
+///
+/// // Serialize using bincode
+/// let encoded = bincode::serialize(&test_struct).unwrap();
+/// assert_eq!(encoded, [0, 1, 2, 3]);
+/// // Deserialize using bincode
+/// let decoded: MyStructRaw = bincode::deserialize(&encoded).unwrap();
+/// assert_eq!(test_struct, decoded);
+///
+/// // Serialize using serde_json
+/// let json = serde_json::to_string(&test_struct).unwrap();
+/// assert_eq!(r#"{"v":"00010203"}"#, json);
+/// ```
 pub mod serde_bytes;
 
 /// # Examples
@@ -1,5 +1,9 @@
 pub trait OptionExtensions<T> {
     fn is_none_or(&self, f: impl FnOnce(&T) -> bool) -> bool;
+
+    /// Applies a fallible function to the contained value (if any), transposing the
+    /// result so that `None` maps to `Ok(None)` and errors are propagated
+    fn try_map<U, E>(self, f: impl FnOnce(T) -> Result<U, E>) -> Result<Option<U>, E>;
 }
 
 impl<T> OptionExtensions<T> for Option<T> {
@@ -9,4 +13,50 @@ impl<T> OptionExtensions<T> for Option<T> {
             None => true,
         }
     }
+
+    fn try_map<U, E>(self, f: impl FnOnce(T) -> Result<U, E>) -> Result<Option<U>, E> {
+        self.map(f).transpose()
+    }
+}
+
+/// Returns the trimmed string slice or `None` if it is empty or contains only whitespace
+pub fn non_empty_str(s: &str) -> Option<&str> {
+    let s = s.trim();
+    (!s.is_empty()).then_some(s)
+}
+
+/// Returns the trimmed string or `None` if it is empty or contains only whitespace.
+/// Trimming is performed in place, reusing the original allocation.
+pub fn non_empty(mut s: String) -> Option<String> {
+    let end = s.trim_end().len();
+    s.truncate(end);
+    let start = s.len() - s.trim_start().len();
+    s.drain(..start);
+    (!s.is_empty()).then_some(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_empty() {
+        assert_eq!(non_empty(String::new()), None);
+        assert_eq!(non_empty(" \t\n ".to_string()), None);
+        assert_eq!(non_empty("secret".to_string()), Some("secret".to_string()));
+        assert_eq!(non_empty("  my secret \n".to_string()), Some("my secret".to_string()));
+
+        assert_eq!(non_empty_str(""), None);
+        assert_eq!(non_empty_str("   "), None);
+        assert_eq!(non_empty_str(" 1.5 "), Some("1.5"));
+    }
+
+    #[test]
+    fn test_try_map() {
+        let parse = |s: &str| s.parse::<u64>();
+        assert_eq!(None.try_map(parse), Ok(None));
+        assert_eq!(Some("10").try_map(parse), Ok(Some(10)));
+        assert!(Some("x").try_map(parse).is_err());
+        assert_eq!(non_empty_str(" ").try_map(parse), Ok(None));
+    }
 }
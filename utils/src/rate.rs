@@ -0,0 +1,170 @@
+//!
+//! Meter of the rate of bytes or events over a sliding time window.
+//!
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Bits of a bucket slot holding the index of the bucket, modulo `1 << TAG_BITS`
+const TAG_BITS: u32 = 24;
+const TAG_MASK: u64 = (1 << TAG_BITS) - 1;
+
+/// Bits of a bucket slot holding the amount recorded in the bucket
+const AMOUNT_BITS: u32 = u64::BITS - TAG_BITS;
+const AMOUNT_MASK: u64 = (1 << AMOUNT_BITS) - 1;
+
+/// Records amounts (ie. bytes or events) and reports their per-second rate over a sliding window.
+///
+/// The window is split into buckets of `resolution` duration, each stored in a single atomic
+/// tagging the amount with the index of its bucket, so that recording is lock-free and a bucket
+/// falling out of the window is recycled by the first recording reaching it.
+pub struct RateMeter {
+    start: Instant,
+    resolution: Duration,
+    buckets: Box<[AtomicU64]>,
+    total: AtomicU64,
+}
+
+impl RateMeter {
+    /// Creates a meter over a `window` divided in buckets of `resolution` duration.
+    ///
+    /// Panics if `resolution` is zero or longer than `window`.
+    pub fn new(window: Duration, resolution: Duration) -> Self {
+        assert!(!resolution.is_zero() && resolution <= window, "resolution must be positive and not exceed the window");
+        let len = window.as_nanos().div_ceil(resolution.as_nanos()) as usize;
+        assert!(len < (1 << (TAG_BITS - 1)), "too many buckets in the window");
+        Self { start: Instant::now(), resolution, buckets: (0..len).map(|_| AtomicU64::new(0)).collect(), total: AtomicU64::new(0) }
+    }
+
+    /// Records `amount` now
+    pub fn record(&self, amount: u64) {
+        self.record_at(amount, Instant::now())
+    }
+
+    /// Records `amount` at instant `now`
+    pub fn record_at(&self, amount: u64, now: Instant) {
+        self.total.fetch_add(amount, Ordering::Relaxed);
+        let index = self.bucket_index(now);
+        // Tag 0 is reserved to empty buckets, hence tags are shifted by one
+        let tag = (index + 1) & TAG_MASK;
+        let slot = &self.buckets[(index % self.buckets.len() as u64) as usize];
+        let mut current = slot.load(Ordering::Relaxed);
+        loop {
+            let current_tag = current >> AMOUNT_BITS;
+            let next = if current_tag == tag {
+                (tag << AMOUNT_BITS) | (current & AMOUNT_MASK).saturating_add(amount).min(AMOUNT_MASK)
+            } else if current_tag != 0 && (current_tag.wrapping_sub(tag) & TAG_MASK) < (1 << (TAG_BITS - 1)) {
+                // The slot already holds a more recent bucket, ie. `now` was taken before a concurrent recording
+                return;
+            } else {
+                (tag << AMOUNT_BITS) | amount.min(AMOUNT_MASK)
+            };
+            match slot.compare_exchange_weak(current, next, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => return,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Returns the per-second rate over the window ending now
+    pub fn rate(&self) -> f64 {
+        self.rate_at(Instant::now())
+    }
+
+    /// Returns the per-second rate over the window ending at instant `now`
+    pub fn rate_at(&self, now: Instant) -> f64 {
+        let index = self.bucket_index(now);
+        let len = self.buckets.len() as u64;
+        let first = (index + 1).saturating_sub(len);
+        let amount: u64 = (first..=index)
+            .map(|i| {
+                let value = self.buckets[(i % len) as usize].load(Ordering::Relaxed);
+                if value >> AMOUNT_BITS == (i + 1) & TAG_MASK {
+                    value & AMOUNT_MASK
+                } else {
+                    0
+                }
+            })
+            .sum();
+        // The window starts at the first bucket, or at the creation of the meter if more recent
+        let span = now.saturating_duration_since(self.start).as_nanos() - self.resolution.as_nanos() * first as u128;
+        if span == 0 {
+            0.0
+        } else {
+            amount as f64 / Duration::from_nanos(span as u64).as_secs_f64()
+        }
+    }
+
+    /// Returns the total amount recorded since the creation of the meter
+    pub fn total(&self) -> u64 {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    fn bucket_index(&self, now: Instant) -> u64 {
+        (now.saturating_duration_since(self.start).as_nanos() / self.resolution.as_nanos()) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn test_rate_meter() {
+        let meter = RateMeter::new(10 * SECOND, SECOND);
+        let start = meter.start;
+        assert_eq!(meter.rate_at(start), 0.0);
+
+        // A burst within the first bucket is averaged over the elapsed time only
+        meter.record_at(500, start + SECOND / 2);
+        assert_eq!(meter.rate_at(start + SECOND), 500.0);
+        assert_eq!(meter.rate_at(start + 2 * SECOND), 250.0);
+
+        // A steady throughput of 1000 per second fills the window, the burst falling out of it
+        for i in 1..20 {
+            meter.record_at(1000, start + i * SECOND);
+        }
+        assert_eq!(meter.rate_at(start + 19 * SECOND + SECOND / 2), 10_000.0 / 9.5);
+        assert_eq!(meter.rate_at(start + 20 * SECOND), 1000.0);
+
+        // The rate decays as the window slides over an idle period
+        assert_eq!(meter.rate_at(start + 25 * SECOND), 4000.0 / 9.0);
+        assert_eq!(meter.rate_at(start + 40 * SECOND), 0.0);
+
+        // Recycled buckets only count the amounts recorded after recycling
+        meter.record_at(3000, start + 40 * SECOND);
+        assert_eq!(meter.rate_at(start + 41 * SECOND), 3000.0 / 9.0);
+        assert_eq!(meter.total(), 500 + 19 * 1000 + 3000);
+    }
+
+    #[test]
+    fn test_rate_meter_late_recordings() {
+        let meter = RateMeter::new(4 * SECOND, SECOND);
+        let start = meter.start;
+        meter.record_at(100, start + 5 * SECOND);
+        // A recording taken before a concurrent one moved the slot to a newer bucket is dropped
+        meter.record_at(100, start + SECOND);
+        assert_eq!(meter.rate_at(start + 6 * SECOND), 100.0 / 3.0);
+        // Recordings of buckets still in the window are accounted
+        meter.record_at(100, start + 4 * SECOND);
+        assert_eq!(meter.rate_at(start + 6 * SECOND), 200.0 / 3.0);
+    }
+
+    #[test]
+    fn test_rate_meter_concurrent_recordings() {
+        let meter = RateMeter::new(60 * SECOND, SECOND);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| (0..1000).for_each(|_| meter.record(10)));
+            }
+        });
+        assert_eq!(meter.total(), 40_000);
+        let now = Instant::now();
+        let elapsed = now.saturating_duration_since(meter.start).as_secs_f64();
+        assert!((meter.rate_at(now) * elapsed - 40_000.0).abs() < 1.0);
+    }
+}
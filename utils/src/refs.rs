@@ -1,4 +1,8 @@
-use std::{ops::Deref, rc::Rc, sync::Arc};
+use std::{
+    ops::{Deref, DerefMut},
+    rc::Rc,
+    sync::Arc,
+};
 
 /// Enum used to represent a concrete varying pointer type which only needs to be accessed by ref.
 /// We avoid adding a `Val(T)` variant in order to keep the size of the enum minimal
@@ -56,3 +60,130 @@ impl<T> From<Box<T>> for Refs<'_, T> {
         Self::Box(b)
     }
 }
+
+/// A copy-on-write shared value. Cloning a [`SharedMut`] only clones the inner [`Arc`],
+/// allowing large, mostly read-only data (such as UTXO entry sets) to be passed around
+/// cheaply. The inner value is cloned lazily on the first mutable access
+/// ([`SharedMut::make_mut`] or [`DerefMut`]) and only if it is shared with
+/// other [`SharedMut`] or [`Arc`] instances; reads never clone.
+#[derive(Debug, Default)]
+pub struct SharedMut<T> {
+    inner: Arc<T>,
+}
+
+impl<T> SharedMut<T> {
+    pub fn new(value: T) -> Self {
+        Self { inner: Arc::new(value) }
+    }
+
+    /// Returns `true` if the inner value is shared and a mutation would trigger a clone
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.inner) > 1 || Arc::weak_count(&self.inner) > 0
+    }
+
+    /// Returns the inner [`Arc`]
+    pub fn as_arc(&self) -> &Arc<T> {
+        &self.inner
+    }
+}
+
+impl<T: Clone> SharedMut<T> {
+    /// Returns a mutable reference to the inner value, cloning it first if it is shared
+    pub fn make_mut(&mut self) -> &mut T {
+        Arc::make_mut(&mut self.inner)
+    }
+
+    /// Returns the inner value, cloning it if it is shared
+    pub fn into_inner(self) -> T {
+        Arc::try_unwrap(self.inner).unwrap_or_else(|arc| (*arc).clone())
+    }
+}
+
+impl<T> Clone for SharedMut<T> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Deref for SharedMut<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T: Clone> DerefMut for SharedMut<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.make_mut()
+    }
+}
+
+impl<T> AsRef<T> for SharedMut<T> {
+    fn as_ref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> From<T> for SharedMut<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> From<Arc<T>> for SharedMut<T> {
+    fn from(inner: Arc<T>) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CloneCounter {
+        items: Vec<u64>,
+        clones: Arc<AtomicUsize>,
+    }
+
+    impl Clone for CloneCounter {
+        fn clone(&self) -> Self {
+            self.clones.fetch_add(1, Ordering::SeqCst);
+            Self { items: self.items.clone(), clones: self.clones.clone() }
+        }
+    }
+
+    #[test]
+    fn test_shared_mut_clone_on_write() {
+        let clones = Arc::new(AtomicUsize::new(0));
+        let original = SharedMut::new(CloneCounter { items: vec![1, 2, 3], clones: clones.clone() });
+        let mut copy = original.clone();
+        assert!(copy.is_shared());
+
+        // reads do not clone
+        assert_eq!(original.items, copy.items);
+        assert_eq!(copy.items.iter().sum::<u64>(), 6);
+        assert_eq!(clones.load(Ordering::SeqCst), 0);
+
+        // first write clones once, subsequent writes do not
+        copy.items.push(4);
+        assert_eq!(clones.load(Ordering::SeqCst), 1);
+        assert!(!copy.is_shared());
+        copy.make_mut().items.push(5);
+        assert_eq!(clones.load(Ordering::SeqCst), 1);
+
+        assert_eq!(original.items, vec![1, 2, 3]);
+        assert_eq!(copy.items, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_shared_mut_unique_write() {
+        let clones = Arc::new(AtomicUsize::new(0));
+        let mut value = SharedMut::new(CloneCounter { items: vec![], clones: clones.clone() });
+        value.items.push(1);
+        let items = value.into_inner().items;
+        assert_eq!(items, vec![1]);
+        assert_eq!(clones.load(Ordering::SeqCst), 0);
+    }
+}
@@ -1,4 +1,16 @@
+//! Serialization of byte sequences as hex strings in human-readable formats
+//! and as binary data otherwise. The binary form is either length-prefixed
+//! (the default, see [`length_prefixed`]) or raw, without a length prefix
+//! (see [`raw`]), in which case the length must be known by the reader.
+//!
+//! Transaction fields use the following modes:
+//! - `TransactionInput::signature_script` and `Transaction::payload` are
+//!   variable length and use the default length-prefixed mode.
+//! - `TransactionOutpoint::transaction_id` and the transaction id are fixed
+//!   size hashes serialized raw by [`serde_bytes_fixed_ref`](crate::serde_bytes_fixed_ref).
+
 mod de;
+pub mod raw;
 mod ser;
 
 pub use crate::serde_bytes::de::{Deserialize, FromHexVisitor};
@@ -18,3 +30,9 @@ where
 {
     Deserialize::deserialize(deserializer)
 }
+
+/// Length-prefixed mode of [`serde_bytes`](crate::serde_bytes) (the default mode),
+/// allowing callers to name the encoding explicitly.
+pub mod length_prefixed {
+    pub use super::{deserialize, serialize};
+}
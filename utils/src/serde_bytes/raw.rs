@@ -0,0 +1,111 @@
+//! Raw mode of [`serde_bytes`](crate::serde_bytes): the binary form of the bytes is
+//! encoded without a length prefix (as a tuple of `N` bytes), so the length must be
+//! known by the reader and supplied as a const generic argument when deserializing.
+//! The human-readable form remains a hex string.
+
+use crate::hex::FromHex;
+use serde::ser::SerializeTuple;
+use serde::Serializer;
+use std::fmt::Display;
+use std::str;
+
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + AsRef<[u8]>,
+    S: Serializer,
+{
+    let bytes = bytes.as_ref();
+    if serializer.is_human_readable() {
+        let mut hex = vec![0u8; bytes.len() * 2];
+        faster_hex::hex_encode(bytes, &mut hex[..]).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(unsafe { str::from_utf8_unchecked(&hex) })
+    } else {
+        let mut t = serializer.serialize_tuple(bytes.len())?;
+        for v in bytes {
+            t.serialize_element(v)?;
+        }
+        t.end()
+    }
+}
+
+pub fn deserialize<'de, T, D, const N: usize>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromHex + TryFrom<Vec<u8>>,
+    <T as FromHex>::Error: Display,
+    <T as TryFrom<Vec<u8>>>::Error: std::fmt::Debug,
+    D: serde::Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let hex = <String as serde::Deserialize>::deserialize(deserializer)?;
+        if hex.len() != N * 2 {
+            return Err(serde::de::Error::invalid_length(hex.len() / 2, &RawBytesVisitor::<N>));
+        }
+        T::from_hex(&hex).map_err(serde::de::Error::custom)
+    } else {
+        let bytes = deserializer.deserialize_tuple(N, RawBytesVisitor::<N>)?;
+        T::try_from(bytes).map_err(|err| serde::de::Error::custom(format!("{err:?}")))
+    }
+}
+
+struct RawBytesVisitor<const N: usize>;
+
+impl<'de, const N: usize> serde::de::Visitor<'de> for RawBytesVisitor<N> {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "{N} raw bytes")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity(N);
+        for index in 0..N {
+            bytes.push(seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(index, &self))?);
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    struct Modes {
+        #[serde(with = "crate::serde_bytes::length_prefixed")]
+        prefixed: Vec<u8>,
+        #[serde(
+            serialize_with = "crate::serde_bytes::raw::serialize",
+            deserialize_with = "crate::serde_bytes::raw::deserialize::<_, _, 4>"
+        )]
+        raw: Vec<u8>,
+    }
+
+    #[test]
+    fn test_serde_bytes_modes_bincode() {
+        let modes = Modes { prefixed: vec![1, 2, 3], raw: vec![4, 5, 6, 7] };
+        let encoded = bincode::serialize(&modes).unwrap();
+        // the length-prefixed field carries its length as u64, the raw field does not
+        assert_eq!(encoded, [3u64.to_le_bytes().as_slice(), &[1, 2, 3], &[4, 5, 6, 7]].concat());
+        let decoded: Modes = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(modes, decoded);
+
+        // truncated raw bytes are rejected
+        assert!(bincode::deserialize::<Modes>(&encoded[..encoded.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_serde_bytes_modes_json() {
+        let modes = Modes { prefixed: vec![1, 2, 3], raw: vec![4, 5, 6, 7] };
+        // the human-readable form is the same hex string in both modes
+        let json = serde_json::to_string(&modes).unwrap();
+        assert_eq!(json, r#"{"prefixed":"010203","raw":"04050607"}"#);
+        let decoded: Modes = serde_json::from_str(&json).unwrap();
+        assert_eq!(modes, decoded);
+
+        // raw fields have a fixed length
+        assert!(serde_json::from_str::<Modes>(r#"{"prefixed":"010203","raw":"040506"}"#).is_err());
+    }
+}
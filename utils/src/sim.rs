@@ -4,7 +4,9 @@
 //! Users should define the message type `T` required for the simulation, derive `Process<T>` with
 //! various simulation actor logic and plug the processes into a `Simulation<T>` instance.
 
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::ops::Range;
 
 /// Internal structure representing a scheduled simulator event
 struct Event<T> {
@@ -156,3 +158,139 @@ impl<T: Clone> Simulation<T> {
         self.processes.clear();
     }
 }
+
+/// A deterministic virtual clock measuring time in arbitrary units (typically milliseconds)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SimClock {
+    now: u64,
+}
+
+impl SimClock {
+    pub fn new(start_time: u64) -> Self {
+        Self { now: start_time }
+    }
+
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Advances the clock by `delta` and returns the new time
+    pub fn advance(&mut self, delta: u64) -> u64 {
+        self.now += delta;
+        self.now
+    }
+
+    /// Moves the clock forward to `timestamp`. Virtual time never goes backwards.
+    pub fn advance_to(&mut self, timestamp: u64) -> u64 {
+        assert!(timestamp >= self.now, "virtual clock can not move backwards");
+        self.now = timestamp;
+        self.now
+    }
+}
+
+/// A simulation context combining a virtual clock and a seeded RNG. Both are derived
+/// from a single seed, so two contexts created with the same seed and driven by the
+/// same sequence of calls produce identical timings and random values.
+pub struct SimContext {
+    seed: u64,
+    clock: SimClock,
+    rng: StdRng,
+}
+
+impl SimContext {
+    pub fn new(seed: u64) -> Self {
+        Self::with_start_time(seed, 0)
+    }
+
+    pub fn with_start_time(seed: u64, start_time: u64) -> Self {
+        Self { seed, clock: SimClock::new(start_time), rng: StdRng::seed_from_u64(seed) }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn now(&self) -> u64 {
+        self.clock.now()
+    }
+
+    pub fn clock(&self) -> &SimClock {
+        &self.clock
+    }
+
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Advances the virtual clock by `delta` and returns the new time
+    pub fn advance(&mut self, delta: u64) -> u64 {
+        self.clock.advance(delta)
+    }
+
+    /// Advances the virtual clock by a random delta drawn from `range` and returns the new time
+    pub fn advance_random(&mut self, range: Range<u64>) -> u64 {
+        let delta = self.rng.gen_range(range);
+        self.clock.advance(delta)
+    }
+
+    /// Returns `base` adjusted by a random jitter within `±spread`, saturating at the bounds of `u64`
+    pub fn jitter(&mut self, base: u64, spread: u64) -> u64 {
+        let offset = self.rng.gen_range(0..=spread.saturating_mul(2));
+        base.saturating_add(offset).saturating_sub(spread)
+    }
+
+    /// Creates an independent child context (e.g. for a simulated peer) seeded
+    /// deterministically from this context and sharing its current time
+    pub fn fork(&mut self) -> Self {
+        let seed = self.rng.gen();
+        Self::with_start_time(seed, self.now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Simulates a retry loop with jittered exponential backoff and random failures,
+    /// recording the `(time, attempt, success)` sequence of events
+    fn backoff_events(seed: u64) -> Vec<(u64, u32, bool)> {
+        let mut ctx = SimContext::with_start_time(seed, 1_000);
+        let mut peer = ctx.fork();
+        let mut events = vec![];
+        for attempt in 0..10 {
+            let delay = ctx.jitter(100 << attempt, 50);
+            ctx.advance(delay);
+            let success = peer.rng().gen_bool(0.3);
+            peer.advance_random(1..20);
+            events.push((ctx.now(), attempt, success));
+        }
+        events
+    }
+
+    #[test]
+    fn test_sim_context_is_deterministic() {
+        assert_eq!(backoff_events(42), backoff_events(42));
+        assert_ne!(backoff_events(42), backoff_events(43));
+    }
+
+    #[test]
+    fn test_sim_clock() {
+        let mut ctx = SimContext::with_start_time(7, 500);
+        assert_eq!(ctx.seed(), 7);
+        assert_eq!(ctx.now(), 500);
+        assert_eq!(ctx.advance(10), 510);
+        let now = ctx.advance_random(5..10);
+        assert!((515..520).contains(&now));
+        assert_eq!(ctx.clock().now(), now);
+
+        let mut clock = SimClock::default();
+        assert_eq!(clock.advance_to(100), 100);
+        for _ in 0..100 {
+            let value = ctx.jitter(100, 10);
+            assert!((90..=110).contains(&value));
+        }
+        assert!(ctx.jitter(5, 10) <= 15);
+        assert!(ctx.jitter(u64::MAX - 5, 10) >= u64::MAX - 15);
+        ctx.jitter(u64::MAX, u64::MAX);
+    }
+}
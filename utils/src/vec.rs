@@ -1,3 +1,6 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
 pub trait VecExtensions<T> {
     /// Pushes the provided value to the container if the container is empty
     fn push_if_empty(self, value: T) -> Self;
@@ -20,3 +23,64 @@ impl<T> VecExtensions<T> for Vec<T> {
         self.swap(index, loc);
     }
 }
+
+/// Removes duplicate items from `v`, keeping the first occurrence of each item
+/// and preserving the original order of the retained items
+pub fn dedup_keep_order<T: Hash + Eq>(v: &mut Vec<T>) {
+    let keep = {
+        let mut seen = HashSet::with_capacity(v.len());
+        v.iter().map(|item| seen.insert(item)).collect::<Vec<_>>()
+    };
+    let mut keep = keep.into_iter();
+    v.retain(|_| keep.next().unwrap());
+}
+
+/// Drains `v` in chunks of `n` items (the last chunk may be shorter). The vector
+/// is left empty once the iterator is dropped, even if not fully consumed.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+pub fn drain_chunks<T>(v: &mut Vec<T>, n: usize) -> impl Iterator<Item = Vec<T>> + '_ {
+    assert!(n > 0, "chunk size must be non-zero");
+    let mut drain = v.drain(..);
+    std::iter::from_fn(move || {
+        let chunk = drain.by_ref().take(n).collect::<Vec<_>>();
+        (!chunk.is_empty()).then_some(chunk)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup_keep_order() {
+        let mut v = vec![3, 1, 3, 2, 1, 4];
+        dedup_keep_order(&mut v);
+        assert_eq!(v, vec![3, 1, 2, 4]);
+
+        let mut v = vec!["a"; 5];
+        dedup_keep_order(&mut v);
+        assert_eq!(v, vec!["a"]);
+
+        let mut v: Vec<u32> = vec![];
+        dedup_keep_order(&mut v);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn test_drain_chunks() {
+        let mut v = (0..7).collect::<Vec<_>>();
+        let chunks = drain_chunks(&mut v, 3).collect::<Vec<_>>();
+        assert_eq!(chunks, vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+        assert!(v.is_empty());
+
+        let mut v = (0..4).collect::<Vec<_>>();
+        assert_eq!(drain_chunks(&mut v, 2).next(), Some(vec![0, 1]));
+        assert!(v.is_empty());
+
+        let mut v: Vec<u32> = vec![];
+        assert_eq!(drain_chunks(&mut v, 2).count(), 0);
+    }
+}
@@ -0,0 +1,121 @@
+//!
+//! Abortable trigger carrying an abort reason and supporting timeouts.
+//!
+//! Extends [`workflow_core::abortable::Abortable`] used to cancel long-running
+//! tasks such as transaction generation. An abort can carry a reason that is
+//! surfaced as [`Error::AbortedWithReason`] and the trigger can be configured
+//! to abort automatically once a timeout elapses.
+//!
+
+use crate::error::Error;
+use crate::result::Result;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use workflow_core::time::Instant;
+
+#[derive(Default, Clone)]
+pub struct Abortable {
+    inner: workflow_core::abortable::Abortable,
+    reason: Arc<Mutex<Option<String>>>,
+    deadline: Option<(Instant, Duration)>,
+}
+
+impl Abortable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a trigger that aborts automatically once the
+    /// `timeout` elapses (measured from the time of creation).
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self { deadline: Some((Instant::now() + timeout, timeout)), ..Self::default() }
+    }
+
+    /// Returns `true` if the trigger has been aborted or the timeout has elapsed.
+    pub fn is_aborted(&self) -> bool {
+        if self.inner.is_aborted() {
+            return true;
+        }
+
+        match self.deadline {
+            Some((deadline, timeout)) if Instant::now() >= deadline => {
+                self.abort_with_reason(format!("timed out after {timeout:?}"));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn abort(&self) {
+        self.inner.abort();
+    }
+
+    /// Aborts the trigger, the `reason` is surfaced as the resulting error.
+    /// If the trigger has already been aborted with a reason, the reason is retained.
+    pub fn abort_with_reason<S: Into<String>>(&self, reason: S) {
+        self.reason.lock().unwrap().get_or_insert_with(|| reason.into());
+        self.inner.abort();
+    }
+
+    /// Returns the reason of the abort if one has been supplied.
+    pub fn reason(&self) -> Option<String> {
+        self.reason.lock().unwrap().clone()
+    }
+
+    /// Returns [`Error::Aborted`] or [`Error::AbortedWithReason`] if the trigger has been aborted.
+    pub fn check(&self) -> Result<()> {
+        if !self.is_aborted() {
+            Ok(())
+        } else if let Some(reason) = self.reason() {
+            Err(Error::AbortedWithReason(reason))
+        } else {
+            Err(Error::Aborted)
+        }
+    }
+
+    /// Resets the abort state and the reason. The timeout (if any) is retained,
+    /// so a timed out trigger aborts again on the next check.
+    pub fn reset(&self) {
+        self.reason.lock().unwrap().take();
+        self.inner.reset();
+    }
+}
+
+impl From<workflow_core::abortable::Abortable> for Abortable {
+    fn from(inner: workflow_core::abortable::Abortable) -> Self {
+        Self { inner, ..Self::default() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abortable_reason() {
+        let abortable = Abortable::new();
+        assert!(abortable.check().is_ok());
+
+        abortable.abort();
+        assert!(matches!(abortable.check(), Err(Error::Aborted)));
+
+        abortable.reset();
+        let clone = abortable.clone();
+        clone.abort_with_reason("user request");
+        clone.abort_with_reason("ignored");
+        assert_eq!(abortable.reason().as_deref(), Some("user request"));
+        assert_eq!(abortable.check().unwrap_err().to_string(), "task aborted: user request");
+
+        abortable.reset();
+        assert!(clone.check().is_ok());
+        assert_eq!(clone.reason(), None);
+    }
+
+    #[test]
+    fn test_abortable_timeout() {
+        let abortable = Abortable::with_timeout(Duration::from_millis(10));
+        assert!(!abortable.is_aborted());
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(matches!(abortable.check(), Err(Error::AbortedWithReason(reason)) if reason == "timed out after 10ms"));
+    }
+}
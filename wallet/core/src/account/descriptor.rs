@@ -47,6 +47,9 @@ pub enum AccountDescriptorProperty {
     Ecdsa,
     DerivationMeta,
     Other(String),
+    DerivationPath,
+    MinimumSignatures,
+    Bip39Passphrase,
 }
 
 impl std::fmt::Display for AccountDescriptorProperty {
@@ -57,6 +60,9 @@ impl std::fmt::Display for AccountDescriptorProperty {
             AccountDescriptorProperty::Ecdsa => write!(f, "ECDSA"),
             AccountDescriptorProperty::DerivationMeta => write!(f, "Derivation Indexes"),
             AccountDescriptorProperty::Other(other) => write!(f, "{}", other),
+            AccountDescriptorProperty::DerivationPath => write!(f, "Derivation Path"),
+            AccountDescriptorProperty::MinimumSignatures => write!(f, "Minimum Signatures"),
+            AccountDescriptorProperty::Bip39Passphrase => write!(f, "BIP39 Passphrase"),
         }
     }
 }
@@ -179,3 +185,63 @@ impl AccountDescriptor {
         &self.receive_address
     }
 }
+
+/// Watch-only export of an account, holding the extended public keys and the derivation
+/// information needed to derive and monitor the account addresses. The export doesn't
+/// contain any private key material.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountExportDescriptor {
+    pub kind: AccountKind,
+    pub account_id: AccountId,
+    pub account_name: Option<String>,
+    pub xpub_keys: Vec<String>,
+    pub address_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_signatures: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_index: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+    pub receive_index: u32,
+    pub change_index: u32,
+    pub gap_limit: usize,
+}
+
+impl AccountDescriptor {
+    /// Creates the watch-only export of the account. Fails for accounts
+    /// that are not backed by extended public keys.
+    pub fn to_export_descriptor(&self, gap_limit: usize) -> Result<AccountExportDescriptor> {
+        let Some(AccountDescriptorValue::XPubKeys(xpub_keys)) = self.properties.get(&AccountDescriptorProperty::XpubKeys) else {
+            return Err(Error::AccountKindFeature);
+        };
+
+        let u64_property = |property| match self.properties.get(&property) {
+            Some(AccountDescriptorValue::U64(value)) => Some(*value),
+            _ => None,
+        };
+        let ecdsa = matches!(self.properties.get(&AccountDescriptorProperty::Ecdsa), Some(AccountDescriptorValue::Bool(true)));
+        let derivation_path = match self.properties.get(&AccountDescriptorProperty::DerivationPath) {
+            Some(AccountDescriptorValue::String(path)) => Some(path.clone()),
+            _ => None,
+        };
+        let derivation_meta = match self.properties.get(&AccountDescriptorProperty::DerivationMeta) {
+            Some(AccountDescriptorValue::AddressDerivationMeta(meta)) => meta.clone(),
+            _ => AddressDerivationMeta::default(),
+        };
+
+        Ok(AccountExportDescriptor {
+            kind: self.kind,
+            account_id: self.account_id,
+            account_name: self.account_name.clone(),
+            xpub_keys: xpub_keys.iter().map(|xpub| xpub.to_string(None)).collect(),
+            address_type: if ecdsa { "ecdsa" } else { "schnorr" }.to_string(),
+            minimum_signatures: u64_property(AccountDescriptorProperty::MinimumSignatures),
+            account_index: u64_property(AccountDescriptorProperty::AccountIndex),
+            derivation_path,
+            receive_index: derivation_meta.receive(),
+            change_index: derivation_meta.change(),
+            gap_limit,
+        })
+    }
+}
@@ -0,0 +1,310 @@
+//!
+//! Export of the account transaction history in CSV or JSON format.
+//!
+//! All timestamps are expressed in UTC. Records stored without a
+//! timestamp are dated using the node estimate of the unixtime
+//! corresponding to their DAA score.
+//!
+
+use crate::imports::*;
+use crate::storage::transaction::TransactionCategory;
+use crate::utils::{sompi_to_kaspa_string_with_options, KaspaStringOptions};
+use kaspa_txscript::extract_script_pub_key_address;
+
+const MSEC_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+/// CSV columns, in the order of the [`HistoryEntry`] fields
+pub const HISTORY_CSV_HEADER: &str = "timestamp,daa_score,transaction_id,category,amount,fee,counterparty";
+
+/// Output format of [`Account::export_history`](super::Account::export_history)
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum HistoryExportFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for HistoryExportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(HistoryExportFormat::Csv),
+            "json" => Ok(HistoryExportFormat::Json),
+            _ => Err(Error::custom(format!("invalid history export format '{s}', expected 'csv' or 'json'"))),
+        }
+    }
+}
+
+/// Range of transaction timestamps, in UTC unixtime milliseconds,
+/// including `from` and excluding `to`. Unset bounds are open.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct HistoryDateRange {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+impl HistoryDateRange {
+    /// Creates a range covering the days from `from` to `to` inclusive,
+    /// both given as UTC dates in the `YYYY-MM-DD` format.
+    pub fn from_dates(from: Option<&str>, to: Option<&str>) -> Result<Self> {
+        let from = from.map(parse_utc_date).transpose()?;
+        let to = to.map(|to| parse_utc_date(to).map(|to| to + MSEC_PER_DAY)).transpose()?;
+        Ok(Self { from, to })
+    }
+
+    pub fn contains(&self, unixtime_msec: u64) -> bool {
+        self.from.map_or(true, |from| unixtime_msec >= from) && self.to.map_or(true, |to| unixtime_msec < to)
+    }
+}
+
+/// A row of the exported transaction history
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    #[serde(skip)]
+    pub unixtime_msec: u64,
+    /// UTC timestamp in the RFC 3339 format
+    pub timestamp: String,
+    pub daa_score: u64,
+    pub transaction_id: TransactionId,
+    pub category: TransactionCategory,
+    /// Signed KAS amount, negative for outgoing transactions
+    pub amount: String,
+    /// KAS fees paid by the account
+    pub fee: String,
+    /// The foreign destination address of outgoing transactions. The sender
+    /// address of incoming transactions is not known to the wallet.
+    pub counterparty: Option<Address>,
+}
+
+impl HistoryEntry {
+    /// Creates the history entry of `record` dated at `unixtime_msec`. Returns `None` for records
+    /// not presented to the user and for change records, which duplicate their outgoing transaction.
+    pub fn try_new<F>(record: &TransactionRecord, unixtime_msec: u64, is_wallet_address: F) -> Option<Self>
+    where
+        F: Fn(&Address) -> bool,
+    {
+        if record.is_change() {
+            return None;
+        }
+        let category = record.category(&is_wallet_address)?;
+
+        let (fee, counterparty) = match record.transaction_data() {
+            TransactionData::Outgoing { fees, transaction, .. }
+            | TransactionData::Batch { fees, transaction, .. }
+            | TransactionData::TransferOutgoing { fees, transaction, .. } => {
+                let prefix = Prefix::from(*record.network_id());
+                let counterparty = transaction
+                    .outputs
+                    .iter()
+                    .filter_map(|output| extract_script_pub_key_address(&output.script_public_key, prefix).ok())
+                    .find(|address| !is_wallet_address(address));
+                (*fees, counterparty)
+            }
+            _ => (0, None),
+        };
+
+        let amount = kaspa_string(record.value());
+        let amount = if category == TransactionCategory::Outgoing { format!("-{amount}") } else { amount };
+
+        Some(Self {
+            unixtime_msec,
+            timestamp: format_utc_timestamp(unixtime_msec),
+            daa_score: record.block_daa_score(),
+            transaction_id: *record.id(),
+            category,
+            amount,
+            fee: kaspa_string(fee),
+            counterparty,
+        })
+    }
+
+    fn to_csv(&self) -> String {
+        let counterparty = self.counterparty.as_ref().map(|address| address.to_string()).unwrap_or_default();
+        format!(
+            "{},{},{},{},{},{},{}",
+            self.timestamp, self.daa_score, self.transaction_id, self.category, self.amount, self.fee, counterparty
+        )
+    }
+}
+
+/// Formats history entries in the given format
+pub fn format_history(entries: &[HistoryEntry], format: HistoryExportFormat) -> Result<String> {
+    match format {
+        HistoryExportFormat::Csv => {
+            let mut lines = vec![HISTORY_CSV_HEADER.to_string()];
+            lines.extend(entries.iter().map(HistoryEntry::to_csv));
+            Ok(lines.join("\n") + "\n")
+        }
+        HistoryExportFormat::Json => Ok(serde_json::to_string_pretty(entries)?),
+    }
+}
+
+fn kaspa_string(sompi: u64) -> String {
+    sompi_to_kaspa_string_with_options(sompi, &KaspaStringOptions { trailing_zeroes: true, ..Default::default() })
+}
+
+/// Formats a unixtime in milliseconds as an RFC 3339 UTC timestamp (`YYYY-MM-DDThh:mm:ss.sssZ`)
+pub fn format_utc_timestamp(unixtime_msec: u64) -> String {
+    let (days, msec) = (unixtime_msec / MSEC_PER_DAY, unixtime_msec % MSEC_PER_DAY);
+    let (year, month, day) = civil_from_days(days as i64);
+    let (seconds, msec) = (msec / 1000, msec % 1000);
+    format!("{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{msec:03}Z", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+/// Parses a `YYYY-MM-DD` UTC date into the unixtime in milliseconds of its first instant
+pub fn parse_utc_date(date: &str) -> Result<u64> {
+    let invalid = || Error::custom(format!("invalid date '{date}', expected the YYYY-MM-DD format"));
+    let parts = date.split('-').map(|part| part.parse::<u32>().map_err(|_| invalid())).collect::<Result<Vec<_>>>()?;
+    let [year, month, day] = parts[..] else {
+        return Err(invalid());
+    };
+    if year < 1970 || !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return Err(invalid());
+    }
+    Ok(days_from_civil(year as i64, month, day) as u64 * MSEC_PER_DAY)
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Conversions between days since the unix epoch and proleptic Gregorian
+// calendar dates, see http://howardhinnant.github.io/date_algorithms.html
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{address, utxo};
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use kaspa_consensus_core::tx::{Transaction, TransactionInput, TransactionOutpoint, TransactionOutput};
+    use kaspa_txscript::pay_to_address_script;
+
+    fn record(id: u64, value: u64, transaction_data: TransactionData) -> TransactionRecord {
+        let block_daa_score = 1_000_000 + id;
+        TransactionRecord { id: TransactionId::from_u64_word(id), value, block_daa_score, ..crate::tests::record(transaction_data) }
+    }
+
+    fn records() -> Vec<TransactionRecord> {
+        let inputs = vec![TransactionInput::new(TransactionOutpoint::new(TransactionId::default(), 0), vec![], 0, 1)];
+        let outputs = vec![
+            TransactionOutput::new(150_000_000, pay_to_address_script(&address(20))),
+            TransactionOutput::new(49_990_000, pay_to_address_script(&address(2))),
+        ];
+        let transaction = Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        vec![
+            record(
+                1,
+                200_000_000,
+                TransactionData::Incoming { utxo_entries: vec![utxo(1, 200_000_000, false)], aggregate_input_value: 200_000_000 },
+            ),
+            record(
+                2,
+                150_000_000,
+                TransactionData::Outgoing {
+                    fees: 10_000,
+                    aggregate_input_value: 200_000_000,
+                    aggregate_output_value: 199_990_000,
+                    transaction,
+                    payment_value: Some(150_000_000),
+                    change_value: 49_990_000,
+                    accepted_daa_score: None,
+                    utxo_entries: vec![utxo(1, 200_000_000, false)],
+                },
+            ),
+            record(
+                3,
+                50_000_000_000,
+                TransactionData::Incoming { utxo_entries: vec![utxo(1, 50_000_000_000, true)], aggregate_input_value: 50_000_000_000 },
+            ),
+            record(4, 100, TransactionData::Reorg { utxo_entries: vec![utxo(1, 100, false)], aggregate_input_value: 100 }),
+        ]
+    }
+
+    fn entries(range: HistoryDateRange) -> Vec<HistoryEntry> {
+        // 2024-03-01T00:00:00Z plus a day per record
+        let start = 1_709_251_200_000;
+        records()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, record)| {
+                HistoryEntry::try_new(record, start + i as u64 * MSEC_PER_DAY + 1234, |address| {
+                    *address == self::address(1) || *address == self::address(2)
+                })
+            })
+            .filter(|entry| range.contains(entry.unixtime_msec))
+            .collect()
+    }
+
+    #[test]
+    fn test_history_csv_export() {
+        let csv = format_history(&entries(HistoryDateRange::default()), HistoryExportFormat::Csv).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], HISTORY_CSV_HEADER);
+        assert_eq!(
+            lines[2],
+            format!(
+                "2024-03-02T00:00:01.234Z,1000002,{},outgoing,-1.50000000,0.00010000,{}",
+                TransactionId::from_u64_word(2),
+                address(20)
+            )
+        );
+        assert!(lines[1].starts_with("2024-03-01T00:00:01.234Z,1000001,") && lines[1].ends_with(",incoming,2.00000000,0.00000000,"));
+        assert!(lines[3].contains(",coinbase,500.00000000,"));
+    }
+
+    #[test]
+    fn test_history_json_export() {
+        let range = HistoryDateRange::from_dates(Some("2024-03-02"), Some("2024-03-02")).unwrap();
+        let entries = entries(range);
+        assert_eq!(entries.len(), 1);
+        let json: serde_json::Value = serde_json::from_str(&format_history(&entries, HistoryExportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["timestamp"], "2024-03-02T00:00:01.234Z");
+        assert_eq!(json[0]["category"], "outgoing");
+        assert_eq!(json[0]["amount"], "-1.50000000");
+        assert_eq!(json[0]["counterparty"], address(20).to_string());
+    }
+
+    #[test]
+    fn test_utc_dates() {
+        assert_eq!(format_utc_timestamp(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format_utc_timestamp(951_868_799_999), "2000-02-29T23:59:59.999Z");
+        assert_eq!(parse_utc_date("2000-02-29").unwrap(), 951_782_400_000);
+        assert_eq!(parse_utc_date("2024-03-01").unwrap(), 1_709_251_200_000);
+        assert!(parse_utc_date("2023-02-29").is_err());
+        assert!(parse_utc_date("2024-3").is_err());
+        for days in [0, 365, 11_016, 19_783, 47_482] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+}
@@ -4,25 +4,30 @@
 //!
 
 pub mod descriptor;
+pub mod history;
 pub mod kind;
 pub mod variants;
 pub use kind::*;
 pub use variants::*;
 
-use crate::derivation::build_derivate_paths;
+use crate::account::history::{format_history, HistoryDateRange, HistoryEntry, HistoryExportFormat};
 use crate::derivation::gen0;
 use crate::derivation::AddressDerivationManagerTrait;
+use crate::derivation::{build_derivate_paths, build_derivate_paths_with_account_path};
 use crate::imports::*;
+use crate::message::{sign_message, PersonalMessage};
 use crate::storage::account::AccountSettings;
 use crate::storage::AccountMetadata;
 use crate::storage::{PrvKeyData, PrvKeyDataId};
 use crate::tx::PaymentOutput;
-use crate::tx::{Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Signer};
+use crate::tx::{
+    Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Signer, SignerT, UnsignedTransaction,
+};
 use crate::utxo::balance::{AtomicBalance, BalanceStrings};
-use crate::utxo::UtxoContextBinding;
-use kaspa_bip32::{ChildNumber, ExtendedPrivateKey, PrivateKey};
+use crate::utxo::{UtxoContextBinding, UtxoFragmentationReport};
+use kaspa_bip32::{ChildNumber, DerivationPath, ExtendedPrivateKey, PrivateKey};
+use kaspa_consensus_core::sign::sign_with_multiple_v2;
 use kaspa_consensus_wasm::UtxoEntryReference;
-use workflow_core::abortable::Abortable;
 
 /// Notification callback type used by [`Account::sweep`] and [`Account::send`].
 /// Allows tracking in-flight transactions during transaction generation.
@@ -112,6 +117,55 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok(BalanceStrings::from((&self.balance(), &self.wallet().network_id()?.into(), padding)))
     }
 
+    /// Analyze fragmentation of the mature UTXO set of the account,
+    /// see [`UtxoFragmentationReport`] for the consolidation heuristic.
+    fn utxo_fragmentation_report(&self) -> Result<UtxoFragmentationReport> {
+        let network_id = self.wallet().network_id()?;
+        let utxo_entries = self.utxo_context().context().mature.clone();
+        Ok(UtxoFragmentationReport::new(network_id, &utxo_entries, self.sig_op_count(), self.minimum_signatures()))
+    }
+
+    /// Export the account transaction history within `range` as CSV or JSON,
+    /// see [`HistoryEntry`] for the exported fields.
+    async fn export_history(&self, format: HistoryExportFormat, range: HistoryDateRange) -> Result<String> {
+        let binding = Binding::Account(*self.id());
+        let network_id = self.wallet().network_id()?;
+        let store = self.wallet().store().as_transaction_record_store()?;
+        let records = store.transaction_data_iter(&binding, &network_id).await?.try_collect::<Vec<_>>().await?;
+
+        // records stored without a timestamp are dated using the node estimate for their DAA score,
+        // or a local estimate at the network block rate if the node is unable to provide it
+        let undated = records
+            .iter()
+            .filter(|record| record.unixtime_msec().is_none())
+            .map(|record| record.block_daa_score())
+            .collect::<Vec<_>>();
+        let estimates = if undated.is_empty() {
+            HashMap::new()
+        } else {
+            match self.wallet().rpc_api().get_daa_score_timestamp_estimate(undated.clone()).await {
+                Ok(timestamps) => undated.into_iter().zip(timestamps).collect::<HashMap<_, _>>(),
+                Err(err) => {
+                    let estimator = self.wallet().daa_score_time_estimator().ok_or_else(|| Error::from(err))?;
+                    undated.into_iter().map(|daa_score| (daa_score, estimator.unixtime_msec(daa_score))).collect()
+                }
+            }
+        };
+
+        let addresses = self.utxo_context().addresses();
+        let mut entries = records
+            .iter()
+            .filter_map(|record| {
+                let unixtime_msec = record.unixtime_msec().or_else(|| estimates.get(&record.block_daa_score()).copied())?;
+                HistoryEntry::try_new(record, unixtime_msec, |address| addresses.contains(address))
+            })
+            .filter(|entry| range.contains(entry.unixtime_msec))
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| (entry.unixtime_msec, entry.daa_score));
+
+        format_history(&entries, format)
+    }
+
     fn name(&self) -> Option<String> {
         self.context().settings.name.clone()
     }
@@ -196,6 +250,18 @@ pub trait Account: AnySync + Send + Sync + 'static {
     fn descriptor(&self) -> Result<descriptor::AccountDescriptor>;
 
     async fn scan(self: Arc<Self>, window_size: Option<usize>, extent: Option<u32>) -> Result<()> {
+        self.scan_with_control(window_size, extent, &ScanControl::default()).await?;
+        Ok(())
+    }
+
+    /// Scans the account addresses, reporting the progress to `control`. If the scan is aborted,
+    /// the account retains the UTXOs found so far and the returned progress is marked as aborted.
+    async fn scan_with_control(
+        self: Arc<Self>,
+        window_size: Option<usize>,
+        extent: Option<u32>,
+        control: &ScanControl,
+    ) -> Result<ScanProgress> {
         self.utxo_context().clear().await?;
 
         let current_daa_score = self.wallet().current_daa_score().ok_or(Error::NotConnected)?;
@@ -217,14 +283,16 @@ pub trait Account: AnySync + Send + Sync + 'static {
                         current_daa_score,
                         window_size,
                         Some(extent),
-                    ),
+                    )
+                    .with_control(control),
                     Scan::new_with_address_manager(
                         derivation.change_address_manager(),
                         &balance,
                         current_daa_score,
                         window_size,
                         Some(extent),
-                    ),
+                    )
+                    .with_control(control),
                 ];
 
                 let futures = scans.iter().map(|scan| scan.scan(self.utxo_context())).collect::<Vec<_>>();
@@ -236,14 +304,14 @@ pub trait Account: AnySync + Send + Sync + 'static {
                 address_set.insert(self.receive_address()?);
                 address_set.insert(self.change_address()?);
 
-                let scan = Scan::new_with_address_set(address_set, &balance, current_daa_score);
+                let scan = Scan::new_with_address_set(address_set, &balance, current_daa_score).with_control(control);
                 scan.scan(self.utxo_context()).await?;
             }
         }
 
         self.utxo_context().update_balance().await?;
 
-        Ok(())
+        Ok(control.progress())
     }
 
     fn sig_op_count(&self) -> u8;
@@ -284,6 +352,33 @@ pub trait Account: AnySync + Send + Sync + 'static {
 
     fn as_dyn_arc(self: Arc<Self>) -> Arc<dyn Account>;
 
+    /// Create [`GeneratorSettings`] for transactions submitted by this account. If change
+    /// address rotation is enabled in the wallet settings (see [`Wallet::change_address_rotation`]),
+    /// derivation capable accounts derive a fresh change address for each transaction.
+    fn generator_settings(
+        self: Arc<Self>,
+        destination: PaymentDestination,
+        priority_fee_sompi: Fees,
+        payload: Option<Vec<u8>>,
+    ) -> Result<GeneratorSettings> {
+        let settings = GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, priority_fee_sompi, payload)?;
+        match self.as_derivation_capable() {
+            Ok(account) if account.wallet().change_address_rotation() => {
+                Ok(settings.change_address_rotation(account.derivation().change_address_manager()))
+            }
+            _ => Ok(settings),
+        }
+    }
+
+    /// Register and persist the change address derived for the transaction (if any).
+    /// Must be called before the transaction is submitted.
+    async fn commit_derived_change_address(self: Arc<Self>, transaction: &PendingTransaction) -> Result<()> {
+        if let Some(address) = transaction.derived_change_address() {
+            self.as_derivation_capable()?.commit_change_address(address).await?;
+        }
+        Ok(())
+    }
+
     /// Aggregate all account UTXOs into the change address.
     /// Also known as "compounding".
     async fn sweep(
@@ -295,14 +390,14 @@ pub trait Account: AnySync + Send + Sync + 'static {
     ) -> Result<(GeneratorSummary, Vec<kaspa_hashes::Hash>)> {
         let keydata = self.prv_key_data(wallet_secret).await?;
         let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
-        let settings =
-            GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), PaymentDestination::Change, Fees::None, None)?;
+        let settings = self.clone().generator_settings(PaymentDestination::Change, Fees::None, None)?;
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
         let mut stream = generator.stream();
         let mut ids = vec![];
         while let Some(transaction) = stream.try_next().await? {
             transaction.try_sign()?;
+            self.clone().commit_derived_change_address(&transaction).await?;
             ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
 
             if let Some(notifier) = notifier.as_ref() {
@@ -314,6 +409,38 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok((generator.summary(), ids))
     }
 
+    /// Sign a personal message using the private key of the given account address.
+    /// Only [`PubKey`](kaspa_addresses::Version::PubKey) addresses are supported.
+    async fn sign_message(
+        self: Arc<Self>,
+        address: &Address,
+        message: &str,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+    ) -> Result<Vec<u8>> {
+        if address.version != kaspa_addresses::Version::PubKey {
+            return Err(Error::custom("Address not supported for message signing. Only supports PubKey addresses"));
+        }
+
+        let keydata = self.prv_key_data(wallet_secret).await?;
+        let private_key = if let Ok(account) = self.clone().as_derivation_capable() {
+            let (receive, change) = account.derivation().addresses_indexes(&[address])?;
+            let private_keys = account.create_private_keys(&keydata, &payment_secret, &receive, &change)?;
+            private_keys
+                .into_iter()
+                .find_map(|(key_address, private_key)| (key_address == address).then_some(private_key))
+                .ok_or_else(|| Error::custom("Could not find address in any derivation path in account"))?
+        } else {
+            if self.receive_address()? != *address {
+                return Err(Error::custom("Address does not belong to this account"));
+            }
+            let payload = keydata.payload.decrypt(payment_secret.as_ref())?;
+            payload.as_secret_key()?.ok_or_else(|| Error::custom("Account private key data does not contain a secret key"))?
+        };
+
+        Ok(sign_message(&PersonalMessage(message), &private_key.secret_bytes())?)
+    }
+
     /// Send funds to a [`PaymentDestination`] comprised of one or multiple [`PaymentOutputs`](crate::tx::PaymentOutputs)
     /// or [`PaymentDestination::Change`] variant that will forward funds to the change address.
     async fn send(
@@ -328,8 +455,22 @@ pub trait Account: AnySync + Send + Sync + 'static {
     ) -> Result<(GeneratorSummary, Vec<kaspa_hashes::Hash>)> {
         let keydata = self.prv_key_data(wallet_secret).await?;
         let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
+        self.send_with_signer(destination, priority_fee_sompi, payload, signer, abortable, notifier).await
+    }
 
-        let settings = GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, priority_fee_sompi, payload)?;
+    /// Send funds signing transactions with the supplied signer (for example an
+    /// [`AccountInputSigner`](crate::tx::AccountInputSigner) backed by a hardware wallet).
+    /// See [`Account::send`].
+    async fn send_with_signer(
+        self: Arc<Self>,
+        destination: PaymentDestination,
+        priority_fee_sompi: Fees,
+        payload: Option<Vec<u8>>,
+        signer: Arc<dyn SignerT>,
+        abortable: &Abortable,
+        notifier: Option<GenerationNotifier>,
+    ) -> Result<(GeneratorSummary, Vec<kaspa_hashes::Hash>)> {
+        let settings = self.clone().generator_settings(destination, priority_fee_sompi, payload)?;
 
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
@@ -337,6 +478,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         let mut ids = vec![];
         while let Some(transaction) = stream.try_next().await? {
             transaction.try_sign()?;
+            self.clone().commit_derived_change_address(&transaction).await?;
             ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
 
             if let Some(notifier) = notifier.as_ref() {
@@ -348,6 +490,82 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok((generator.summary(), ids))
     }
 
+    /// Sign and submit transactions produced by a [`Generator`] created without a signer from
+    /// [`Account::generator_settings`]. This allows to summarize the transactions to the user before
+    /// broadcasting exactly the transactions that were summarized.
+    async fn sign_and_submit(
+        self: Arc<Self>,
+        transactions: Vec<PendingTransaction>,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+        notifier: Option<GenerationNotifier>,
+    ) -> Result<Vec<kaspa_hashes::Hash>> {
+        let keydata = self.prv_key_data(wallet_secret).await?;
+        let signer: Arc<dyn SignerT> = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
+
+        let mut ids = vec![];
+        for transaction in transactions {
+            transaction.try_sign_with_signer(&signer)?;
+            self.clone().commit_derived_change_address(&transaction).await?;
+            ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
+
+            if let Some(notifier) = notifier.as_ref() {
+                notifier(&transaction);
+            }
+            yield_executor().await;
+        }
+
+        Ok(ids)
+    }
+
+    /// Replace a pending outgoing transaction that has not been accepted yet with a
+    /// transaction paying `additional_fee` more in fees, deducted from its change.
+    /// The replacement spends the same inputs and pays the same recipient outputs.
+    /// See [`GeneratorSettings::try_new_with_replacement`].
+    ///
+    /// The node mempool does not replace transactions by fee: a replacement spending
+    /// the inputs of a transaction that is still in the mempool is rejected as a double
+    /// spend. Only transactions that are no longer in the mempool (evicted) or that are
+    /// kept in the orphan pool can therefore be replaced.
+    async fn bump_fee(
+        self: Arc<Self>,
+        transaction_id: TransactionId,
+        additional_fee: u64,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+        abortable: &Abortable,
+    ) -> Result<(GeneratorSummary, Vec<kaspa_hashes::Hash>)> {
+        let original = self
+            .utxo_context()
+            .context()
+            .outgoing
+            .get(&transaction_id)
+            .filter(|outgoing| !outgoing.is_accepted())
+            .map(|outgoing| outgoing.pending_transaction().clone())
+            .ok_or(Error::GeneratorReplacementNotSupported)?;
+
+        match self.wallet().get_mempool_entry(&transaction_id).await {
+            Ok(Some(entry)) if !entry.is_orphan => return Err(Error::GeneratorReplacementInMempool(transaction_id)),
+            Ok(_) => {}
+            // the node refuses to replace a transaction of its mempool anyway
+            Err(err) => log_warning!("unable to look up transaction {transaction_id} in the mempool: {err}"),
+        }
+
+        let keydata = self.prv_key_data(wallet_secret).await?;
+        let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
+        let settings = GeneratorSettings::try_new_with_replacement(&original, additional_fee)?;
+        let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
+
+        let mut ids = vec![];
+        while let Some(transaction) = generator.generate_transaction()? {
+            transaction.try_sign()?;
+            ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
+            self.clone().commit_derived_change_address(&transaction).await?;
+        }
+
+        Ok((generator.summary(), ids))
+    }
+
     /// Execute a transfer to another wallet account.
     async fn transfer(
         self: Arc<Self>,
@@ -372,13 +590,10 @@ pub trait Account: AnySync + Send + Sync + 'static {
         let final_transaction_destination = PaymentDestination::from(PaymentOutput::new(destination_address, transfer_amount_sompi));
         let final_transaction_payload = None;
 
-        let settings = GeneratorSettings::try_new_with_account(
-            self.clone().as_dyn_arc(),
-            final_transaction_destination,
-            priority_fee_sompi,
-            final_transaction_payload,
-        )?
-        .utxo_context_transfer(destination_account.utxo_context());
+        let settings = self
+            .clone()
+            .generator_settings(final_transaction_destination, priority_fee_sompi, final_transaction_payload)?
+            .utxo_context_transfer(destination_account.utxo_context());
 
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
@@ -386,6 +601,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         let mut ids = vec![];
         while let Some(transaction) = stream.try_next().await? {
             transaction.try_sign()?;
+            self.clone().commit_derived_change_address(&transaction).await?;
             ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
 
             if let Some(notifier) = notifier.as_ref() {
@@ -416,6 +632,57 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok(generator.summary())
     }
 
+    /// Create transactions sending funds to the [`PaymentDestination`] without signing them.
+    /// The resulting [`UnsignedTransaction`] containers can be exported to an offline wallet
+    /// holding the private keys and signed using [`Account::sign_unsigned_transaction`].
+    async fn create_unsigned_transactions(
+        self: Arc<Self>,
+        destination: PaymentDestination,
+        priority_fee_sompi: Fees,
+        payload: Option<Vec<u8>>,
+        abortable: &Abortable,
+    ) -> Result<Vec<UnsignedTransaction>> {
+        let network_id = self.wallet().network_id()?;
+        let derivation = self.clone().as_derivation_capable()?.derivation();
+        let settings = GeneratorSettings::try_new_with_account(self.as_dyn_arc(), destination, priority_fee_sompi, payload)?;
+
+        let generator = Generator::try_new(settings, None, Some(abortable))?;
+
+        let mut stream = generator.stream();
+        let mut transactions = vec![];
+        while let Some(transaction) = stream.try_next().await? {
+            transactions.push(UnsignedTransaction::try_from_pending(network_id, &transaction, &derivation)?);
+            yield_executor().await;
+        }
+
+        Ok(transactions)
+    }
+
+    /// Sign an [`UnsignedTransaction`] created by [`Account::create_unsigned_transactions`].
+    /// Private keys are derived from the derivation data carried by the container, so the
+    /// signing wallet does not need to be synchronized with the network.
+    async fn sign_unsigned_transaction(
+        self: Arc<Self>,
+        unsigned_transaction: UnsignedTransaction,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+    ) -> Result<UnsignedTransaction> {
+        unsigned_transaction.validate(&self.wallet().network_id()?)?;
+
+        let keydata = self.prv_key_data(wallet_secret).await?;
+        let account = self.clone().as_derivation_capable()?;
+
+        let derivations = &unsigned_transaction.derivations;
+        let receive = derivations.iter().filter(|d| !d.change).map(|d| (&d.address, d.index)).collect::<Vec<_>>();
+        let change = derivations.iter().filter(|d| d.change).map(|d| (&d.address, d.index)).collect::<Vec<_>>();
+        let private_keys = account.create_private_keys(&keydata, &payment_secret, &receive, &change)?;
+        let private_keys = private_keys.into_iter().map(|(_, key)| key.secret_bytes()).collect::<Vec<_>>();
+
+        let signed_tx = sign_with_multiple_v2(unsigned_transaction.signable_transaction(), private_keys).fully_signed()?;
+
+        Ok(UnsignedTransaction { transaction: signed_tx.tx, ..unsigned_transaction })
+    }
+
     fn as_derivation_capable(self: Arc<Self>) -> Result<Arc<dyn DerivationCapableAccount>> {
         Err(Error::AccountAddressDerivationCaps)
     }
@@ -447,6 +714,11 @@ pub trait DerivationCapableAccount: Account {
 
     fn account_index(&self) -> u64;
 
+    /// Custom derivation path of the account, replacing the standard path built from the account index.
+    fn account_path(&self) -> Option<&DerivationPath> {
+        None
+    }
+
     async fn derivation_scan(
         self: Arc<Self>,
         wallet_secret: Secret,
@@ -589,6 +861,16 @@ pub trait DerivationCapableAccount: Account {
 
     async fn new_change_address(self: Arc<Self>) -> Result<Address> {
         let address = self.derivation().change_address_manager().new_address()?;
+        self.clone().commit_change_address(&address).await?;
+
+        Ok(address)
+    }
+
+    /// Register and persist a change address derived by the [`Generator`] when
+    /// change address rotation is enabled (see [`GeneratorSettings::change_address_rotation`]).
+    /// This must be called before the transaction is submitted so that the advanced
+    /// change address index is not lost if the wallet terminates unexpectedly.
+    async fn commit_change_address(self: Arc<Self>, address: &Address) -> Result<()> {
         self.utxo_context().register_addresses(&[address.clone()]).await?;
 
         let metadata = self.metadata()?.expect("derivation accounts must provide metadata");
@@ -597,7 +879,7 @@ pub trait DerivationCapableAccount: Account {
 
         self.wallet().notify(Events::AccountUpdate { account_descriptor: self.descriptor()? }).await?;
 
-        Ok(address)
+        Ok(())
     }
 
     fn cosigner_index(&self) -> u32 {
@@ -613,7 +895,15 @@ pub trait DerivationCapableAccount: Account {
     ) -> Result<Vec<(&'l Address, secp256k1::SecretKey)>> {
         let payload = key_data.payload.decrypt(payment_secret.as_ref())?;
         let xkey = payload.get_xprv(payment_secret.as_ref())?;
-        create_private_keys(&self.account_kind(), self.cosigner_index(), self.account_index(), &xkey, receive, change)
+        create_private_keys(
+            &self.account_kind(),
+            self.cosigner_index(),
+            self.account_index(),
+            self.account_path(),
+            &xkey,
+            receive,
+            change,
+        )
     }
 }
 
@@ -623,11 +913,16 @@ pub(crate) fn create_private_keys<'l>(
     account_kind: &AccountKind,
     cosigner_index: u32,
     account_index: u64,
+    account_path: Option<&DerivationPath>,
     xkey: &ExtendedPrivateKey<secp256k1::SecretKey>,
     receive: &[(&'l Address, u32)],
     change: &[(&'l Address, u32)],
 ) -> Result<Vec<(&'l Address, secp256k1::SecretKey)>> {
-    let paths = build_derivate_paths(account_kind, account_index, cosigner_index)?;
+    let paths = if let Some(account_path) = account_path {
+        build_derivate_paths_with_account_path(account_path)?
+    } else {
+        build_derivate_paths(account_kind, account_index, cosigner_index)?
+    };
     let mut private_keys = vec![];
     if matches!(account_kind.as_ref(), LEGACY_ACCOUNT_KIND) {
         let (private_key, attrs) = gen0::WalletDerivationManagerV0::derive_key_by_path(xkey, paths.0)?;
@@ -661,7 +956,9 @@ pub(crate) fn create_private_keys<'l>(
 #[cfg(test)]
 mod tests {
     use super::create_private_keys;
+    use super::descriptor::AccountDescriptorValue;
     use super::ExtendedPrivateKey;
+    use super::GenerationNotifier;
     use crate::derivation::gen0::PubkeyDerivationManagerV0;
     use crate::imports::LEGACY_ACCOUNT_KIND;
     use kaspa_addresses::Address;
@@ -671,6 +968,20 @@ mod tests {
     use kaspa_bip32::SecretKeyExt;
     use std::str::FromStr;
 
+    use crate::imports::*;
+    use crate::tests::RpcCoreMock;
+    use crate::tx::{AccountInputSigner, Fees, Generator, InputSignerT, PaymentOutputs, PendingTransaction, UnsignedTransaction};
+    use crate::utils::kaspa_to_sompi;
+    use crate::wallet::args::{AccountCreateArgs, AccountCreateArgsBip32, PrvKeyDataArgs, PrvKeyDataCreateArgs, WalletCreateArgs};
+    use crate::wallet::Wallet;
+    use kaspa_bip32::{DerivationPath, Mnemonic, WordCount};
+    use kaspa_consensus_core::sign::verify;
+    use kaspa_consensus_core::tx::{PopulatedTransaction, Transaction, TransactionOutput};
+    use kaspa_consensus_wasm::UtxoEntryReference;
+    use kaspa_rpc_core::RpcMempoolEntry;
+    use kaspa_txscript::pay_to_address_script;
+    use secp256k1::schnorr::Signature;
+
     fn gen0_receive_addresses() -> Vec<&'static str> {
         vec![
             "kaspatest:qqnapngv3zxp305qf06w6hpzmyxtx2r99jjhs04lu980xdyd2ulwwmx9evrfz",
@@ -800,18 +1111,663 @@ mod tests {
         let receive_keys = gen0_receive_keys();
         let change_keys = gen0_change_keys();
 
-        let keys = create_private_keys(&LEGACY_ACCOUNT_KIND.into(), 0, 0, &xkey, &receive_addresses, &[]).unwrap();
+        let keys = create_private_keys(&LEGACY_ACCOUNT_KIND.into(), 0, 0, None, &xkey, &receive_addresses, &[]).unwrap();
         for (index, (a, key)) in keys.iter().enumerate() {
             let address = PubkeyDerivationManagerV0::create_address(&key.get_public_key(), Prefix::Testnet, false).unwrap();
             assert_eq!(*a, &address, "receive address at {index} failed");
             assert_eq!(bytes_str(&key.to_bytes()), receive_keys[index], "receive key at {index} failed");
         }
 
-        let keys = create_private_keys(&LEGACY_ACCOUNT_KIND.into(), 0, 0, &xkey, &[], &change_addresses).unwrap();
+        let keys = create_private_keys(&LEGACY_ACCOUNT_KIND.into(), 0, 0, None, &xkey, &[], &change_addresses).unwrap();
         for (index, (a, key)) in keys.iter().enumerate() {
             let address = PubkeyDerivationManagerV0::create_address(&key.get_public_key(), Prefix::Testnet, false).unwrap();
             assert_eq!(*a, &address, "change address at {index} failed");
             assert_eq!(bytes_str(&key.to_bytes()), change_keys[index], "change key at {index} failed");
         }
     }
+
+    /// Creates a wallet connected to [`RpcCoreMock`] containing `count` BIP32 accounts
+    /// derived from the same private key data.
+    async fn create_wallet_with_accounts(
+        rpc_mock: &Arc<RpcCoreMock>,
+        wallet_secret: &Secret,
+        count: usize,
+    ) -> crate::result::Result<(Arc<Wallet>, Vec<Arc<dyn Account>>)> {
+        let mnemonic = Mnemonic::random(WordCount::Words12, Default::default())?;
+        create_wallet_with_mnemonic(Some(rpc_mock), wallet_secret, &mnemonic, count).await
+    }
+
+    async fn create_wallet_with_mnemonic(
+        rpc_mock: Option<&Arc<RpcCoreMock>>,
+        wallet_secret: &Secret,
+        mnemonic: &Mnemonic,
+        count: usize,
+    ) -> crate::result::Result<(Arc<Wallet>, Vec<Arc<dyn Account>>)> {
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+        let rpc = rpc_mock.map(|rpc_mock| rpc_mock.clone().into());
+        let wallet = Arc::new(Wallet::try_with_rpc(rpc, Wallet::resident_store()?, Some(network_id))?);
+
+        let wallet_args = WalletCreateArgs::new(None, None, EncryptionKind::XChaCha20Poly1305, None, false);
+        wallet.create_wallet(wallet_secret, wallet_args).await?;
+
+        let prv_key_data_args = PrvKeyDataCreateArgs::new(None, None, mnemonic.phrase().to_string());
+        let prv_key_data_id = wallet.create_prv_key_data(wallet_secret, prv_key_data_args).await?;
+
+        let mut accounts = vec![];
+        for _ in 0..count {
+            let account_args = AccountCreateArgs::new_bip32(prv_key_data_id, None, None, None);
+            accounts.push(wallet.create_account(wallet_secret, account_args, false).await?);
+        }
+
+        let utxo_processor = wallet.utxo_processor();
+        utxo_processor.mock_set_connected(true);
+        utxo_processor.handle_daa_score_change(1).await?;
+
+        Ok((wallet, accounts))
+    }
+
+    #[tokio::test]
+    async fn test_transfer_between_accounts() -> crate::result::Result<()> {
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let wallet_secret = Secret::from("wallet-secret");
+        let (_wallet, accounts) = create_wallet_with_accounts(&rpc_mock, &wallet_secret, 2).await?;
+        let (account_a, account_b) = (&accounts[0], &accounts[1]);
+        assert_ne!(account_a.id(), account_b.id());
+
+        let utxo_entry = UtxoEntryReference::simulated_with_address(kaspa_to_sompi(100.0), &account_a.receive_address()?);
+        account_a.utxo_context().insert(utxo_entry, 1, true).await?;
+
+        let destination_address = account_b.receive_address()?;
+        let transfer_amount = kaspa_to_sompi(10.0);
+        let (summary, ids) = account_a
+            .clone()
+            .transfer(*account_b.id(), transfer_amount, Fees::SenderPays(0), wallet_secret, None, &Abortable::default(), None)
+            .await?;
+
+        let submitted = rpc_mock.submitted_transactions();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(submitted.len(), 1);
+        assert_eq!(summary.final_transaction_amount(), Some(transfer_amount));
+
+        let transaction = Transaction::try_from(&submitted[0])?;
+        assert_eq!(transaction.id(), ids[0]);
+        let destination_script = pay_to_address_script(&destination_address);
+        assert!(
+            transaction.outputs.iter().any(|output| output.script_public_key == destination_script && output.value == transfer_amount),
+            "transfer output to the destination account is missing"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sweep_consolidates_utxos() -> crate::result::Result<()> {
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let wallet_secret = Secret::from("wallet-secret");
+        let (_wallet, accounts) = create_wallet_with_accounts(&rpc_mock, &wallet_secret, 1).await?;
+        let account = &accounts[0];
+
+        let receive_address = account.receive_address()?;
+        for amount in [1.0, 2.5, 10.0, 0.75, 5.0] {
+            let utxo_entry = UtxoEntryReference::simulated_with_address(kaspa_to_sompi(amount), &receive_address);
+            account.utxo_context().insert(utxo_entry, 1, true).await?;
+        }
+
+        let (summary, ids) = account.clone().sweep(wallet_secret, None, &Abortable::default(), None).await?;
+
+        let submitted = rpc_mock.submitted_transactions();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(submitted.len(), 1);
+        assert_eq!(summary.aggregated_utxos(), 5);
+
+        let transaction = Transaction::try_from(&submitted[0])?;
+        assert_eq!(transaction.inputs.len(), 5);
+        assert_eq!(transaction.outputs.len(), 1);
+        assert_eq!(transaction.outputs[0].value, kaspa_to_sompi(19.25) - summary.aggregated_fees());
+        assert_eq!(transaction.outputs[0].script_public_key, pay_to_address_script(&account.change_address()?));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_transaction_offline_signing() -> crate::result::Result<()> {
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let wallet_secret = Secret::from("wallet-secret");
+        let mnemonic = Mnemonic::random(WordCount::Words12, Default::default())?;
+        let (online_wallet, online_accounts) = create_wallet_with_mnemonic(Some(&rpc_mock), &wallet_secret, &mnemonic, 1).await?;
+        let (_offline_wallet, offline_accounts) = create_wallet_with_mnemonic(None, &wallet_secret, &mnemonic, 1).await?;
+        let (online_account, offline_account) = (&online_accounts[0], &offline_accounts[0]);
+        assert_eq!(online_account.receive_address()?, offline_account.receive_address()?);
+
+        let utxo_entry = UtxoEntryReference::simulated_with_address(kaspa_to_sompi(100.0), &online_account.receive_address()?);
+        online_account.utxo_context().insert(utxo_entry, 1, true).await?;
+
+        let destination = Address::try_from("kaspatest:qpakxqlesqywgkq7rg4wyhjd93kmw7trkl3gpa3vd5flyt59a43yyn8vu0w8c")?;
+        let outputs = PaymentOutputs::from((destination.clone(), kaspa_to_sompi(10.0)));
+        let unsigned = online_account
+            .clone()
+            .create_unsigned_transactions(outputs.into(), Fees::SenderPays(0), None, &Abortable::default())
+            .await?;
+        assert_eq!(unsigned.len(), 1);
+        assert!(!unsigned[0].is_signed());
+        assert!(unsigned[0].try_submit(&online_wallet.rpc_api()).await.is_err());
+
+        // transfer to the offline wallet and back in both supported encodings
+        let unsigned = UnsignedTransaction::try_from_json(&unsigned[0].try_to_json()?)?;
+        let signed = offline_account.clone().sign_unsigned_transaction(unsigned, wallet_secret.clone(), None).await?;
+        assert!(signed.is_signed());
+        let signed = UnsignedTransaction::try_from_borsh(&signed.try_to_borsh()?)?;
+
+        signed.validate(&online_wallet.network_id()?)?;
+        let id = signed.try_submit(&online_wallet.rpc_api()).await?;
+        assert_eq!(id, signed.id());
+
+        let submitted = rpc_mock.submitted_transactions();
+        assert_eq!(submitted.len(), 1);
+        let transaction = Transaction::try_from(&submitted[0])?;
+        assert!(transaction.inputs.iter().all(|input| !input.signature_script.is_empty()));
+        assert!(transaction
+            .outputs
+            .iter()
+            .any(|output| output.script_public_key == pay_to_address_script(&destination) && output.value == kaspa_to_sompi(10.0)));
+
+        // a container destined for a different network must be rejected
+        let mut foreign = signed.clone();
+        foreign.network_id = NetworkId::new(NetworkType::Mainnet);
+        assert!(offline_account.clone().sign_unsigned_transaction(foreign, wallet_secret, None).await.is_err());
+
+        Ok(())
+    }
+
+    /// Input signer holding the extended private key in memory, standing in for a hardware wallet.
+    struct MockInputSigner {
+        xprv: ExtendedPrivateKey<SecretKey>,
+        paths: Mutex<Vec<String>>,
+    }
+
+    impl InputSignerT for MockInputSigner {
+        fn sign_input(&self, digest: &kaspa_hashes::Hash, derivation_path: &DerivationPath) -> crate::result::Result<Signature> {
+            self.paths.lock().unwrap().push(derivation_path.to_string());
+            let private_key = self.xprv.clone().derive_path(derivation_path.clone())?;
+            let keypair = secp256k1::KeyPair::from_secret_key(secp256k1::SECP256K1, private_key.private_key());
+            Ok(keypair.sign_schnorr(secp256k1::Message::from_slice(digest.as_bytes().as_slice())?))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_with_input_signer() -> crate::result::Result<()> {
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let wallet_secret = Secret::from("wallet-secret");
+        let mnemonic = Mnemonic::random(WordCount::Words12, Default::default())?;
+        let (_wallet, accounts) = create_wallet_with_mnemonic(Some(&rpc_mock), &wallet_secret, &mnemonic, 1).await?;
+        let account = &accounts[0];
+
+        let mut utxo_entries = HashMap::new();
+        for amount in [5.0, 7.0] {
+            let utxo_entry = UtxoEntryReference::simulated_with_address(kaspa_to_sompi(amount), &account.receive_address()?);
+            utxo_entries.insert(utxo_entry.transaction_id(), utxo_entry.utxo.entry.clone());
+            account.utxo_context().insert(utxo_entry, 1, true).await?;
+        }
+
+        let xprv = ExtendedPrivateKey::<SecretKey>::new(mnemonic.to_seed(""))?;
+        let input_signer = Arc::new(MockInputSigner { xprv, paths: Mutex::new(vec![]) });
+        let signer = Arc::new(AccountInputSigner::try_new(account.clone(), input_signer.clone())?);
+
+        let destination = Address::try_from("kaspatest:qpakxqlesqywgkq7rg4wyhjd93kmw7trkl3gpa3vd5flyt59a43yyn8vu0w8c")?;
+        let outputs = PaymentOutputs::from((destination, kaspa_to_sompi(10.0)));
+        let (_, ids) =
+            account.clone().send_with_signer(outputs.into(), Fees::SenderPays(0), None, signer, &Abortable::default(), None).await?;
+        assert_eq!(ids.len(), 1);
+
+        // both inputs are signed with the key at the first receive address path
+        assert_eq!(*input_signer.paths.lock().unwrap(), vec!["m/44'/111111'/0'/0/0".to_string(); 2]);
+
+        let transaction = Transaction::try_from(&rpc_mock.submitted_transactions()[0])?;
+        assert_eq!(transaction.id(), ids[0]);
+        let entries =
+            transaction.inputs.iter().map(|input| utxo_entries[&input.previous_outpoint.transaction_id].clone()).collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        verify(&PopulatedTransaction::new(&transaction, entries))?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_with_input_signer_custom_account_path() -> crate::result::Result<()> {
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let wallet_secret = Secret::from("wallet-secret");
+        let mnemonic = Mnemonic::random(WordCount::Words12, Default::default())?;
+        let (wallet, accounts) = create_wallet_with_mnemonic(Some(&rpc_mock), &wallet_secret, &mnemonic, 1).await?;
+        let prv_key_data_id = *accounts[0].prv_key_data_id()?;
+
+        let account_args = AccountCreateArgsBip32::new(None, None).with_account_path(Some("m/44'/972'/2'".into()));
+        let account = wallet.create_account_bip32(&wallet_secret, prv_key_data_id, None, account_args).await?;
+
+        let utxo_entry = UtxoEntryReference::simulated_with_address(kaspa_to_sompi(5.0), &account.receive_address()?);
+        let entry = utxo_entry.utxo.entry.clone();
+        account.utxo_context().insert(utxo_entry, 1, true).await?;
+
+        let xprv = ExtendedPrivateKey::<SecretKey>::new(mnemonic.to_seed(""))?;
+        let input_signer = Arc::new(MockInputSigner { xprv, paths: Mutex::new(vec![]) });
+        let signer = Arc::new(AccountInputSigner::try_new(account.clone(), input_signer.clone())?);
+
+        let destination = Address::try_from("kaspatest:qpakxqlesqywgkq7rg4wyhjd93kmw7trkl3gpa3vd5flyt59a43yyn8vu0w8c")?;
+        let outputs = PaymentOutputs::from((destination, kaspa_to_sompi(1.0)));
+        account.clone().send_with_signer(outputs.into(), Fees::SenderPays(0), None, signer, &Abortable::default(), None).await?;
+
+        // the input is signed with the key below the custom account path
+        assert_eq!(*input_signer.paths.lock().unwrap(), vec!["m/44'/972'/2'/0/0".to_string()]);
+
+        let transaction = Transaction::try_from(&rpc_mock.submitted_transactions()[0])?;
+        verify(&PopulatedTransaction::new(&transaction, vec![entry]))?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_input_signer_rejects_multisig_account() -> crate::result::Result<()> {
+        let wallet_secret = Secret::from("wallet-secret");
+        let mnemonic = Mnemonic::random(WordCount::Words12, Default::default())?;
+        let (wallet, accounts) = create_wallet_with_mnemonic(None, &wallet_secret, &mnemonic, 1).await?;
+        let prv_key_data_id = *accounts[0].prv_key_data_id()?;
+
+        let multisig = wallet
+            .create_account_multisig(&wallet_secret, vec![PrvKeyDataArgs::new(prv_key_data_id, None)], vec![], None, 1)
+            .await?;
+        assert_eq!(multisig.account_kind().as_ref(), MULTISIG_ACCOUNT_KIND);
+
+        let xprv = ExtendedPrivateKey::<SecretKey>::new(mnemonic.to_seed(""))?;
+        let input_signer = Arc::new(MockInputSigner { xprv, paths: Mutex::new(vec![]) });
+        assert!(AccountInputSigner::try_new(multisig, input_signer.clone()).is_err());
+        assert!(AccountInputSigner::try_new(accounts[0].clone(), input_signer).is_ok());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bump_fee_requires_evicted_original() -> crate::result::Result<()> {
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let wallet_secret = Secret::from("wallet-secret");
+        let (_wallet, accounts) = create_wallet_with_accounts(&rpc_mock, &wallet_secret, 1).await?;
+        let account = &accounts[0];
+
+        // each send aggregates two inputs to lower the storage mass
+        for _ in 0..4 {
+            let utxo_entry = UtxoEntryReference::simulated_with_address(kaspa_to_sompi(50.0), &account.receive_address()?);
+            account.utxo_context().insert(utxo_entry, 1, true).await?;
+        }
+
+        let destination = Address::try_from("kaspatest:qpakxqlesqywgkq7rg4wyhjd93kmw7trkl3gpa3vd5flyt59a43yyn8vu0w8c")?;
+        let send = || async {
+            let outputs = PaymentOutputs::from((destination.clone(), kaspa_to_sompi(10.0)));
+            let (_, ids) = account
+                .clone()
+                .send(outputs.into(), Fees::SenderPays(0), None, wallet_secret.clone(), None, &Abortable::default(), None)
+                .await?;
+            Ok::<_, Error>(ids[0])
+        };
+        let additional_fee = kaspa_to_sompi(0.1);
+
+        // an original evicted from the mempool is replaced
+        let original_id = send().await?;
+        let (summary, ids) =
+            account.clone().bump_fee(original_id, additional_fee, wallet_secret.clone(), None, &Abortable::default()).await?;
+        assert_eq!(ids.len(), 1);
+        assert_ne!(ids[0], original_id);
+        assert_eq!(summary.number_of_generated_transactions(), 1);
+        assert_eq!(rpc_mock.submitted_transactions().len(), 2);
+
+        // the mempool rejects a replacement of a transaction it still holds
+        let original_id = send().await?;
+        rpc_mock.add_mempool_entry(RpcMempoolEntry::new(0, rpc_mock.submitted_transactions()[2].clone(), false));
+        assert!(matches!(
+            account.clone().bump_fee(original_id, additional_fee, wallet_secret.clone(), None, &Abortable::default()).await,
+            Err(Error::GeneratorReplacementInMempool(id)) if id == original_id
+        ));
+        assert_eq!(rpc_mock.submitted_transactions().len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_account_message_signing() -> crate::result::Result<()> {
+        use crate::message::{verify_message_with_address, PersonalMessage};
+
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let wallet_secret = Secret::from("wallet-secret");
+        let (_wallet, accounts) = create_wallet_with_accounts(&rpc_mock, &wallet_secret, 2).await?;
+        let (account, other_account) = (&accounts[0], &accounts[1]);
+
+        let address = account.receive_address()?;
+        let message = "Hello Kaspa!";
+        let signature = account.clone().sign_message(&address, message, wallet_secret.clone(), None).await?;
+        assert!(verify_message_with_address(&PersonalMessage(message), &signature, &address));
+
+        let mut tampered = signature.clone();
+        tampered[10] ^= 0xff;
+        assert!(!verify_message_with_address(&PersonalMessage(message), &tampered, &address));
+        assert!(!verify_message_with_address(&PersonalMessage("Hello Kaspa?"), &signature, &address));
+        assert!(!verify_message_with_address(&PersonalMessage(message), &signature, &other_account.receive_address()?));
+
+        assert!(account.clone().sign_message(&other_account.receive_address()?, message, wallet_secret, None).await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_change_address_rotation() -> crate::result::Result<()> {
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let wallet_secret = Secret::from("wallet-secret");
+        let (wallet, accounts) = create_wallet_with_accounts(&rpc_mock, &wallet_secret, 1).await?;
+        let account = &accounts[0];
+
+        let receive_address = account.receive_address()?;
+        for _ in 0..200 {
+            let utxo_entry = UtxoEntryReference::simulated_with_address(kaspa_to_sompi(1.0), &receive_address);
+            account.utxo_context().insert(utxo_entry, 1, true).await?;
+        }
+
+        let change_address_manager = account.clone().as_derivation_capable()?.derivation().change_address_manager();
+        let initial_change_index = change_address_manager.index();
+
+        // register the notification listener so that derived change addresses can be subscribed
+        wallet.utxo_processor().mock_connect().await?;
+        wallet.set_change_address_rotation(true);
+
+        let derived_change_addresses = Arc::new(Mutex::new(vec![]));
+        let notifier: GenerationNotifier = {
+            let derived_change_addresses = derived_change_addresses.clone();
+            Arc::new(move |transaction: &PendingTransaction| {
+                let change_address = transaction.derived_change_address().cloned().expect("change address must be derived");
+                derived_change_addresses.lock().unwrap().push(change_address);
+            })
+        };
+
+        let destination = Address::try_from("kaspatest:qpakxqlesqywgkq7rg4wyhjd93kmw7trkl3gpa3vd5flyt59a43yyn8vu0w8c")?;
+        let outputs = PaymentOutputs::from((destination, kaspa_to_sompi(150.0)));
+        let (_, ids) = account
+            .clone()
+            .send(outputs.into(), Fees::SenderPays(0), None, wallet_secret.clone(), None, &Abortable::default(), Some(notifier))
+            .await?;
+
+        let change_addresses = derived_change_addresses.lock().unwrap().clone();
+        assert_eq!(change_addresses.len(), ids.len());
+        let submitted = rpc_mock.submitted_transactions();
+        for (transaction, change_address) in submitted.iter().zip(change_addresses.iter()) {
+            let change_script = pay_to_address_script(change_address);
+            assert!(transaction.outputs.iter().any(|output| output.script_public_key == change_script));
+            assert!(account.utxo_context().addresses().contains(change_address));
+        }
+
+        assert!(change_addresses.len() > 1, "expecting chained transactions");
+        let unique = change_addresses.iter().collect::<HashSet<_>>();
+        assert_eq!(unique.len(), change_addresses.len());
+        assert!(!change_addresses.contains(&account.receive_address()?));
+
+        let change_index = initial_change_index + change_addresses.len() as u32;
+        assert_eq!(change_address_manager.index(), change_index);
+
+        let (_, metadata) = wallet.store().as_account_store()?.load_single(account.id()).await?.expect("account must be stored");
+        let indexes = metadata.and_then(|metadata| metadata.indexes.clone()).expect("account metadata must contain indexes");
+        assert_eq!(indexes.change(), change_index);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_submit_generated_transactions() -> crate::result::Result<()> {
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let wallet_secret = Secret::from("wallet-secret");
+        let (_wallet, accounts) = create_wallet_with_accounts(&rpc_mock, &wallet_secret, 1).await?;
+        let account = &accounts[0];
+
+        let receive_address = account.receive_address()?;
+        for _ in 0..200 {
+            let utxo_entry = UtxoEntryReference::simulated_with_address(kaspa_to_sompi(1.0), &receive_address);
+            account.utxo_context().insert(utxo_entry, 1, true).await?;
+        }
+
+        // the transactions are generated without a signer so that they can be summarized before being submitted
+        let destination = Address::try_from("kaspatest:qpakxqlesqywgkq7rg4wyhjd93kmw7trkl3gpa3vd5flyt59a43yyn8vu0w8c")?;
+        let outputs = PaymentOutputs::from((destination, kaspa_to_sompi(150.0)));
+        let settings = account.clone().generator_settings(outputs.into(), Fees::SenderPays(0), None)?;
+        let generator = Generator::try_new(settings, None, None)?;
+        let mut stream = generator.stream();
+        let mut transactions = vec![];
+        while let Some(transaction) = stream.try_next().await? {
+            transactions.push(transaction);
+        }
+        let summary = generator.summary();
+        let generated = transactions.iter().map(|transaction| transaction.id()).collect::<Vec<_>>();
+        assert!(generated.len() > 1, "expecting chained transactions");
+        assert_eq!(transactions.iter().map(|transaction| transaction.fees()).sum::<u64>(), summary.aggregated_fees());
+
+        // exactly the summarized transactions are signed and submitted
+        let ids = account.clone().sign_and_submit(transactions, wallet_secret, None, None).await?;
+        assert_eq!(ids, generated);
+        let submitted = rpc_mock.submitted_transactions();
+        assert_eq!(submitted.len(), generated.len());
+        for (transaction, id) in submitted.iter().zip(generated.iter()) {
+            let transaction = Transaction::try_from(transaction)?;
+            assert_eq!(transaction.id(), *id);
+            assert!(transaction.inputs.iter().all(|input| !input.signature_script.is_empty()));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_to_address_book_alias() -> crate::result::Result<()> {
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let wallet_secret = Secret::from("wallet-secret");
+        let (wallet, accounts) = create_wallet_with_accounts(&rpc_mock, &wallet_secret, 1).await?;
+        let account = &accounts[0];
+
+        let receive_address = account.receive_address()?;
+        let utxo_entry = UtxoEntryReference::simulated_with_address(kaspa_to_sompi(10.0), &receive_address);
+        account.utxo_context().insert(utxo_entry, 1, true).await?;
+
+        let destination = Address::try_from("kaspatest:qpakxqlesqywgkq7rg4wyhjd93kmw7trkl3gpa3vd5flyt59a43yyn8vu0w8c")?;
+        let mainnet_destination = Address::try_from("kaspa:qpakxqlesqywgkq7rg4wyhjd93kmw7trkl3gpa3vd5flyt59a43yyjp28qsku")?;
+        let book = wallet.store().as_address_book_store()?;
+        book.store(AddressBookEntry::try_new("bob", "", destination.clone())?).await?;
+        book.store(AddressBookEntry::try_new("carol", "", mainnet_destination)?).await?;
+
+        // an alias of another network is rejected instead of being paid to
+        let network_id = wallet.network_id()?;
+        assert!(matches!(book.resolve("carol", &network_id).await, Err(Error::AddressBookNetworkMismatch(_, _))));
+
+        // sending to an alias pays the address of the entry
+        let address = book.resolve("bob", &network_id).await?;
+        let outputs = PaymentOutputs::from((address, kaspa_to_sompi(1.0)));
+        let settings = account.clone().generator_settings(outputs.into(), Fees::SenderPays(0), None)?;
+        let generator = Generator::try_new(settings, None, None)?;
+        let mut stream = generator.stream();
+        let mut transactions = vec![];
+        while let Some(transaction) = stream.try_next().await? {
+            transactions.push(transaction);
+        }
+        account.clone().sign_and_submit(transactions, wallet_secret, None, None).await?;
+
+        let submitted = rpc_mock.submitted_transactions();
+        assert_eq!(submitted.len(), 1);
+        let transaction = Transaction::try_from(&submitted[0])?;
+        let payment = TransactionOutput::new(kaspa_to_sompi(1.0), pay_to_address_script(&destination));
+        assert!(transaction.outputs.contains(&payment));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn custom_account_path_prv_keys() -> crate::result::Result<()> {
+        use crate::derivation::gen1::PubkeyDerivationManager;
+        use crate::derivation::{create_xpub_from_xprv, create_xpub_from_xprv_with_path, parse_account_derivation_path};
+
+        // reference vectors of the first BIP32 account (`m/44'/111111'/0'`) derived by the gen1 wallet
+        let master_xprv =
+            "kprv5y2qurMHCsXYrNfU3GCihuwG3vMqFji7PZXajMEqyBkNh9UZUJgoHYBLTKu1eM4MvUtomcXPQ3Sw9HZ5ebbM4byoUciHo1zrPJBQfqpLorQ";
+        let receive_addresses = [
+            "kaspa:qz7ulu4c25dh7fzec9zjyrmlhnkzrg4wmf89q7gzr3gfrsj3uz6xjellj43pf",
+            "kaspa:qzn3qjzf2nzyd3zj303nk4sgv0aae42v3ufutk5xsxckfels57dxjjed4qvlx",
+            "kaspa:qpakxqlesqywgkq7rg4wyhjd93kmw7trkl3gpa3vd5flyt59a43yyjp28qsku",
+        ];
+        let change_addresses = [
+            "kaspa:qrqrnyzdwh9ec2q05guzy3vv33f86nvdyw52qwlmk0mewzx3dgdss3pmcd692",
+            "kaspa:qqx8jlz0hh0wun5ru4glt9za3v8wj3jn7v3w55a0lyud74ppetqfqny4yhw87",
+        ];
+
+        let xprv = ExtendedPrivateKey::<SecretKey>::from_str(master_xprv)?;
+        let account_path = parse_account_derivation_path("m/44'/111111'/0'")?;
+
+        let xpub = create_xpub_from_xprv_with_path(xprv.clone(), &account_path).await?;
+        assert_eq!(xpub, create_xpub_from_xprv(xprv.clone(), BIP32_ACCOUNT_KIND.into(), 0).await?);
+
+        let receive = receive_addresses.iter().map(|address| Address::try_from(*address).unwrap()).collect::<Vec<_>>();
+        let receive = receive.iter().enumerate().map(|(index, address)| (address, index as u32)).collect::<Vec<_>>();
+        let change = change_addresses.iter().map(|address| Address::try_from(*address).unwrap()).collect::<Vec<_>>();
+        let change = change.iter().enumerate().map(|(index, address)| (address, index as u32)).collect::<Vec<_>>();
+
+        // an account index the custom path disagrees with must not be used for the derivation
+        let keys = create_private_keys(&BIP32_ACCOUNT_KIND.into(), 0, 7, Some(&account_path), &xprv, &receive, &change)?;
+        assert_eq!(keys.len(), receive.len() + change.len());
+        for (address, key) in keys {
+            let derived = PubkeyDerivationManager::create_address(&key.get_public_key(), Prefix::Mainnet, false)?;
+            assert_eq!(address, &derived);
+        }
+
+        // a different account below the standard purpose and coin type
+        let account_path = parse_account_derivation_path("m/44'/111111'/5'")?;
+        let xpub = create_xpub_from_xprv_with_path(xprv.clone(), &account_path).await?;
+        assert_eq!(xpub, create_xpub_from_xprv(xprv, BIP32_ACCOUNT_KIND.into(), 5).await?);
+
+        for path in ["44'/111111'/0'", "m/44'/111111'/0", "m/44'/111111'/x'"] {
+            assert!(matches!(parse_account_derivation_path(path), Err(Error::InvalidDerivationPath(..))), "{path}");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_account_with_custom_path() -> crate::result::Result<()> {
+        let wallet_secret = Secret::from("wallet-secret");
+        let mnemonic = Mnemonic::random(WordCount::Words12, Default::default())?;
+        let (wallet, accounts) = create_wallet_with_mnemonic(None, &wallet_secret, &mnemonic, 4).await?;
+        let prv_key_data_id = *accounts[0].prv_key_data_id()?;
+
+        let account_args =
+            AccountCreateArgsBip32::new(Some("recovered".to_string()), None).with_account_path(Some("m/44'/111111'/3'".into()));
+        let account = wallet.create_account_bip32(&wallet_secret, prv_key_data_id, None, account_args).await?;
+
+        // the account derives the addresses of the account created with the same index,
+        // while keeping a distinct id
+        assert_ne!(account.id(), accounts[3].id());
+        assert_eq!(account.receive_address()?, accounts[3].receive_address()?);
+        assert_eq!(account.change_address()?, accounts[3].change_address()?);
+        let descriptor = account.descriptor()?;
+        assert!(matches!(
+            descriptor.properties.get(&AccountDescriptorProperty::DerivationPath),
+            Some(AccountDescriptorValue::String(path)) if path == "m/44'/111111'/3'"
+        ));
+
+        // the custom path survives storage round trips
+        let (stored, _) = wallet.store().as_account_store()?.load_single(account.id()).await?.expect("account must be stored");
+        let payload = bip32::Payload::try_load(&stored)?;
+        assert_eq!(payload.account_path.as_deref(), Some("m/44'/111111'/3'"));
+
+        let account_args = AccountCreateArgsBip32::new(None, Some(1)).with_account_path(Some("m/44'/111111'/1'".into()));
+        assert!(wallet.create_account_bip32(&wallet_secret, prv_key_data_id, None, account_args).await.is_err());
+        let account_args = AccountCreateArgsBip32::new(None, None).with_account_path(Some("m/44'/111111'/1".into()));
+        assert!(matches!(
+            wallet.create_account_bip32(&wallet_secret, prv_key_data_id, None, account_args).await,
+            Err(Error::InvalidDerivationPath(..))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_account_descriptor() -> crate::result::Result<()> {
+        let wallet_secret = Secret::from("wallet-secret");
+        let mnemonic = Mnemonic::random(WordCount::Words12, Default::default())?;
+        let (wallet, accounts) = create_wallet_with_mnemonic(None, &wallet_secret, &mnemonic, 2).await?;
+        let account = &accounts[1];
+        account.clone().as_derivation_capable()?.derivation().receive_address_manager().new_address()?;
+
+        let export = wallet.export_account_descriptor(&wallet_secret, account).await?;
+        let Some(AccountDescriptorValue::XPubKeys(xpub_keys)) =
+            account.descriptor()?.properties.get(&AccountDescriptorProperty::XpubKeys).cloned()
+        else {
+            panic!("bip32 account must have xpub keys");
+        };
+        assert_eq!(export.kind, BIP32_ACCOUNT_KIND);
+        assert_eq!(&export.account_id, account.id());
+        assert_eq!(export.xpub_keys, xpub_keys.iter().map(|xpub| xpub.to_string(None)).collect::<Vec<_>>());
+        assert_eq!(export.address_type, "schnorr");
+        assert_eq!(export.account_index, Some(1));
+        assert_eq!(export.derivation_path, None);
+        assert_eq!(export.minimum_signatures, None);
+        assert_eq!((export.receive_index, export.change_index), (1, 0));
+        assert_eq!(export.gap_limit, crate::utxo::scan::DEFAULT_WINDOW_SIZE);
+
+        // no private key material is included
+        let json = serde_json::to_string(&export)?;
+        let prv_key_data = wallet.get_prv_key_data(&wallet_secret, account.prv_key_data_id()?).await?.unwrap();
+        let xprv = prv_key_data.get_xprv(None)?;
+        assert!(!json.contains(xprv.to_string(kaspa_bip32::Prefix::KPRV).as_str()));
+        assert!(!json.contains(xprv.to_string(kaspa_bip32::Prefix::XPRV).as_str()));
+        assert!(!json.contains("prv"));
+        assert!(!json.contains(mnemonic.phrase()));
+
+        // the export is gated by the wallet secret
+        assert!(wallet.export_account_descriptor(&Secret::from("invalid"), account).await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_account_with_bip39_passphrase() -> crate::result::Result<()> {
+        use crate::derivation::gen1::PubkeyDerivationManager;
+
+        // BIP39 reference vector (mnemonic and "TREZOR" passphrase) and its master key
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let master_xprv =
+            "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF";
+        let xprv = ExtendedPrivateKey::<SecretKey>::from_str(master_xprv)?;
+        let key = xprv.derive_path("m/44'/111111'/0'/0/0".parse::<DerivationPath>()?)?;
+        let expected = PubkeyDerivationManager::create_address(&key.private_key().get_public_key(), Prefix::Testnet, false)?;
+
+        let wallet_secret = Secret::from("wallet-secret");
+        let payment_secret = Secret::from("payment-secret");
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+        let wallet = Arc::new(Wallet::try_with_rpc(None, Wallet::resident_store()?, Some(network_id))?);
+        let wallet_args = WalletCreateArgs::new(None, None, EncryptionKind::XChaCha20Poly1305, None, false);
+        wallet.create_wallet(&wallet_secret, wallet_args).await?;
+
+        let prv_key_data_args = PrvKeyDataCreateArgs::new(None, Some(payment_secret.clone()), mnemonic.to_string())
+            .with_bip39_passphrase(Some(Secret::from("TREZOR")));
+        let prv_key_data_id = wallet.create_prv_key_data(&wallet_secret, prv_key_data_args).await?;
+        let account_args = AccountCreateArgsBip32::new(None, None);
+        let account = wallet.create_account_bip32(&wallet_secret, prv_key_data_id, Some(&payment_secret), account_args).await?;
+        assert_eq!(account.receive_address()?, expected);
+        assert!(matches!(
+            account.descriptor()?.properties.get(&AccountDescriptorProperty::Bip39Passphrase),
+            Some(AccountDescriptorValue::Bool(true))
+        ));
+        let (stored, _) = wallet.store().as_account_store()?.load_single(account.id()).await?.expect("account must be stored");
+        assert!(bip32::Payload::try_load(&stored)?.bip39_passphrase);
+
+        // the same mnemonic without a passphrase derives different keys
+        let prv_key_data_args = PrvKeyDataCreateArgs::new(None, Some(payment_secret.clone()), mnemonic.to_string())
+            .with_bip39_passphrase(Some(Secret::from("")));
+        let prv_key_data_id = wallet.create_prv_key_data(&wallet_secret, prv_key_data_args).await?;
+        let account_args = AccountCreateArgsBip32::new(None, None);
+        let account = wallet.create_account_bip32(&wallet_secret, prv_key_data_id, Some(&payment_secret), account_args).await?;
+        assert_ne!(account.receive_address()?, expected);
+        assert!(matches!(
+            account.descriptor()?.properties.get(&AccountDescriptorProperty::Bip39Passphrase),
+            Some(AccountDescriptorValue::Bool(false))
+        ));
+
+        Ok(())
+    }
 }
@@ -3,8 +3,9 @@
 //!
 
 use crate::account::Inner;
-use crate::derivation::{AddressDerivationManager, AddressDerivationManagerTrait};
+use crate::derivation::{parse_account_derivation_path, AddressDerivationManager, AddressDerivationManagerTrait};
 use crate::imports::*;
+use kaspa_bip32::DerivationPath;
 
 pub const BIP32_ACCOUNT_KIND: &str = "kaspa-bip32-standard";
 
@@ -36,11 +37,25 @@ pub struct Payload {
     pub xpub_keys: Arc<Vec<ExtendedPublicKeySecp256k1>>,
     pub account_index: u64,
     pub ecdsa: bool,
+    /// Custom derivation path of the account, `None` for the standard path of the account index.
+    pub account_path: Option<String>,
+    /// Whether the private key data of the account derives its seed with a BIP39 passphrase.
+    pub bip39_passphrase: bool,
 }
 
 impl Payload {
     pub fn new(account_index: u64, xpub_keys: Arc<Vec<ExtendedPublicKeySecp256k1>>, ecdsa: bool) -> Self {
-        Self { account_index, xpub_keys, ecdsa }
+        Self { account_index, xpub_keys, ecdsa, account_path: None, bip39_passphrase: false }
+    }
+
+    pub fn with_account_path(mut self, account_path: Option<&DerivationPath>) -> Self {
+        self.account_path = account_path.map(|path| path.to_string());
+        self
+    }
+
+    pub fn with_bip39_passphrase(mut self, bip39_passphrase: bool) -> Self {
+        self.bip39_passphrase = bip39_passphrase;
+        self
     }
 
     pub fn try_load(storage: &AccountStorage) -> Result<Self> {
@@ -53,7 +68,7 @@ impl Storable for Payload {
     // serialization data alignment check
     const STORAGE_MAGIC: u32 = 0x32335042;
     // binary serialization version
-    const STORAGE_VERSION: u32 = 0;
+    const STORAGE_VERSION: u32 = 2;
 }
 
 impl AccountStorable for Payload {}
@@ -64,6 +79,8 @@ impl BorshSerialize for Payload {
         BorshSerialize::serialize(&self.xpub_keys, writer)?;
         BorshSerialize::serialize(&self.account_index, writer)?;
         BorshSerialize::serialize(&self.ecdsa, writer)?;
+        BorshSerialize::serialize(&self.account_path, writer)?;
+        BorshSerialize::serialize(&self.bip39_passphrase, writer)?;
 
         Ok(())
     }
@@ -71,14 +88,16 @@ impl BorshSerialize for Payload {
 
 impl BorshDeserialize for Payload {
     fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
-        let StorageHeader { version: _, .. } =
+        let StorageHeader { version, .. } =
             StorageHeader::deserialize(buf)?.try_magic(Self::STORAGE_MAGIC)?.try_version(Self::STORAGE_VERSION)?;
 
         let xpub_keys = BorshDeserialize::deserialize(buf)?;
         let account_index = BorshDeserialize::deserialize(buf)?;
         let ecdsa = BorshDeserialize::deserialize(buf)?;
+        let account_path = if version > 0 { BorshDeserialize::deserialize(buf)? } else { None };
+        let bip39_passphrase = if version > 1 { BorshDeserialize::deserialize(buf)? } else { false };
 
-        Ok(Self { xpub_keys, account_index, ecdsa })
+        Ok(Self { xpub_keys, account_index, ecdsa, account_path, bip39_passphrase })
     }
 }
 
@@ -86,8 +105,10 @@ pub struct Bip32 {
     inner: Arc<Inner>,
     prv_key_data_id: PrvKeyDataId,
     account_index: u64,
+    account_path: Option<DerivationPath>,
     xpub_keys: ExtendedPublicKeys,
     ecdsa: bool,
+    bip39_passphrase: bool,
     derivation: Arc<AddressDerivationManager>,
 }
 
@@ -100,7 +121,21 @@ impl Bip32 {
         xpub_keys: ExtendedPublicKeys,
         ecdsa: bool,
     ) -> Result<Self> {
-        let storable = Payload::new(account_index, xpub_keys.clone(), ecdsa);
+        Self::try_new_with_account_path(wallet, name, prv_key_data_id, account_index, None, xpub_keys, ecdsa).await
+    }
+
+    /// Creates an account whose `xpub_keys` were derived with the custom `account_path`
+    /// instead of the standard path of `account_index`.
+    pub async fn try_new_with_account_path(
+        wallet: &Arc<Wallet>,
+        name: Option<String>,
+        prv_key_data_id: PrvKeyDataId,
+        account_index: u64,
+        account_path: Option<DerivationPath>,
+        xpub_keys: ExtendedPublicKeys,
+        ecdsa: bool,
+    ) -> Result<Self> {
+        let storable = Payload::new(account_index, xpub_keys.clone(), ecdsa).with_account_path(account_path.as_ref());
         let settings = AccountSettings { name, ..Default::default() };
         let (id, storage_key) = make_account_hashes(from_bip32(&prv_key_data_id, &storable));
         let inner = Arc::new(Inner::new(wallet, id, storage_key, settings));
@@ -111,13 +146,24 @@ impl Bip32 {
             &xpub_keys,
             ecdsa,
             account_index,
+            account_path.clone(),
             None,
             1,
             Default::default(),
         )
         .await?;
 
-        Ok(Self { inner, prv_key_data_id, account_index, xpub_keys, ecdsa, derivation })
+        Ok(Self { inner, prv_key_data_id, account_index, account_path, xpub_keys, ecdsa, bip39_passphrase: false, derivation })
+    }
+
+    /// Marks the account as derived from private key data using a BIP39 passphrase.
+    pub fn with_bip39_passphrase(mut self, bip39_passphrase: bool) -> Self {
+        self.bip39_passphrase = bip39_passphrase;
+        self
+    }
+
+    pub fn bip39_passphrase(&self) -> bool {
+        self.bip39_passphrase
     }
 
     pub async fn try_load(wallet: &Arc<Wallet>, storage: &AccountStorage, meta: Option<Arc<AccountMetadata>>) -> Result<Self> {
@@ -125,7 +171,8 @@ impl Bip32 {
         let prv_key_data_id: PrvKeyDataId = storage.prv_key_data_ids.clone().try_into()?;
         let inner = Arc::new(Inner::from_storage(wallet, storage));
 
-        let Payload { account_index, xpub_keys, ecdsa, .. } = storable;
+        let Payload { account_index, xpub_keys, ecdsa, account_path, bip39_passphrase } = storable;
+        let account_path = account_path.as_deref().map(parse_account_derivation_path).transpose()?;
 
         let address_derivation_indexes = meta.and_then(|meta| meta.address_derivation_indexes()).unwrap_or_default();
 
@@ -135,6 +182,7 @@ impl Bip32 {
             &xpub_keys,
             ecdsa,
             account_index,
+            account_path.clone(),
             None,
             1,
             address_derivation_indexes,
@@ -149,7 +197,7 @@ impl Bip32 {
             .await?
             .ok_or_else(|| Error::PrivateKeyNotFound(prv_key_data_id))?;
 
-        Ok(Self { inner, prv_key_data_id, account_index, xpub_keys, ecdsa, derivation })
+        Ok(Self { inner, prv_key_data_id, account_index, account_path, xpub_keys, ecdsa, bip39_passphrase, derivation })
     }
 
     pub fn get_address_range_for_scan(&self, range: std::ops::Range<u32>) -> Result<Vec<Address>> {
@@ -194,7 +242,9 @@ impl Account for Bip32 {
 
     fn to_storage(&self) -> Result<AccountStorage> {
         let settings = self.context().settings.clone();
-        let storable = Payload::new(self.account_index, self.xpub_keys.clone(), self.ecdsa);
+        let storable = Payload::new(self.account_index, self.xpub_keys.clone(), self.ecdsa)
+            .with_account_path(self.account_path.as_ref())
+            .with_bip39_passphrase(self.bip39_passphrase);
         let storage = AccountStorage::try_new(
             BIP32_ACCOUNT_KIND.into(),
             self.id(),
@@ -224,8 +274,15 @@ impl Account for Bip32 {
         .with_property(AccountDescriptorProperty::AccountIndex, self.account_index.into())
         .with_property(AccountDescriptorProperty::XpubKeys, self.xpub_keys.clone().into())
         .with_property(AccountDescriptorProperty::Ecdsa, self.ecdsa.into())
+        .with_property(AccountDescriptorProperty::Bip39Passphrase, self.bip39_passphrase.into())
         .with_property(AccountDescriptorProperty::DerivationMeta, self.derivation.address_derivation_meta().into());
 
+        let descriptor = if let Some(account_path) = &self.account_path {
+            descriptor.with_property(AccountDescriptorProperty::DerivationPath, account_path.to_string().into())
+        } else {
+            descriptor
+        };
+
         Ok(descriptor)
     }
 
@@ -242,6 +299,10 @@ impl DerivationCapableAccount for Bip32 {
     fn account_index(&self) -> u64 {
         self.account_index
     }
+
+    fn account_path(&self) -> Option<&DerivationPath> {
+        self.account_path.as_ref()
+    }
 }
 
 #[cfg(test)]
@@ -251,12 +312,13 @@ mod tests {
 
     #[test]
     fn test_storage_bip32() -> Result<()> {
-        let storable_in = Payload::new(0xbaadf00d, vec![make_xpub()].into(), false);
+        let storable_in = Payload::new(0xbaadf00d, vec![make_xpub()].into(), false).with_bip39_passphrase(true);
         let guard = StorageGuard::new(&storable_in);
         let storable_out = guard.validate()?;
 
         assert_eq!(storable_in.account_index, storable_out.account_index);
         assert_eq!(storable_in.ecdsa, storable_out.ecdsa);
+        assert_eq!(storable_in.bip39_passphrase, storable_out.bip39_passphrase);
         assert_eq!(storable_in.xpub_keys.len(), storable_out.xpub_keys.len());
         for idx in 0..storable_in.xpub_keys.len() {
             assert_eq!(storable_in.xpub_keys[idx], storable_out.xpub_keys[idx]);
@@ -114,6 +114,7 @@ impl MultiSig {
             &xpub_keys,
             ecdsa,
             0,
+            None,
             cosigner_index.map(|v| v as u32),
             minimum_signatures,
             Default::default(),
@@ -137,6 +138,7 @@ impl MultiSig {
             &xpub_keys,
             ecdsa,
             0,
+            None,
             cosigner_index.map(|v| v as u32),
             minimum_signatures,
             address_derivation_indexes,
@@ -228,6 +230,7 @@ impl Account for MultiSig {
         )
         .with_property(AccountDescriptorProperty::XpubKeys, self.xpub_keys.clone().into())
         .with_property(AccountDescriptorProperty::Ecdsa, self.ecdsa.into())
+        .with_property(AccountDescriptorProperty::MinimumSignatures, (self.minimum_signatures as u64).into())
         .with_property(AccountDescriptorProperty::DerivationMeta, self.derivation.address_derivation_meta().into());
 
         Ok(descriptor)
@@ -19,6 +19,8 @@ pub struct PingRequest {
 #[serde(rename_all = "camelCase")]
 pub struct PingResponse {
     pub payload: Option<u64>,
+    /// Round-trip time to the node, in microseconds
+    pub round_trip_time_usec: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -46,11 +46,13 @@ pub trait WalletApi: Send + Sync + AnySync {
 
     // ---
 
-    /// Wrapper around `ping_call()`.
-    async fn ping(self: Arc<Self>, payload: Option<u64>) -> Result<Option<u64>> {
-        Ok(self.ping_call(PingRequest { payload }).await?.payload)
+    /// Wrapper around `ping_call()` returning the round-trip time to the node.
+    async fn ping(self: Arc<Self>) -> Result<Duration> {
+        Ok(Duration::from_micros(self.ping_call(PingRequest { payload: None }).await?.round_trip_time_usec))
     }
-    /// Ping the wallet service. Accepts an optional `u64` value that is returned in the response.
+    /// Ping the node the wallet is connected to, measuring the round-trip time. Fails with
+    /// [`Error::PingTimeout`](crate::error::Error::PingTimeout) if the node does not respond in time.
+    /// Accepts an optional `u64` value that is returned in the response.
     async fn ping_call(self: Arc<Self>, request: PingRequest) -> Result<PingResponse>;
 
     async fn batch(self: Arc<Self>) -> Result<()> {
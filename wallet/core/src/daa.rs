@@ -0,0 +1,114 @@
+//!
+//! Estimation of the wall-clock time corresponding to a DAA score.
+//!
+
+use crate::account::history::format_utc_timestamp;
+use crate::imports::*;
+use kaspa_consensus_core::config::params::Params;
+
+/// Tolerated deviation, in percent, of the long-term rate of DAA score
+/// increase from the network target block rate.
+pub const DAA_RATE_TOLERANCE_PERCENT: u64 = 2;
+
+/// Estimates the UTC time at which a DAA score was (or will be) reached by extrapolating
+/// from a reference point of known DAA score and time at the network target block rate.
+///
+/// The difficulty adjustment keeps the DAA score increasing by one per target block time
+/// on average, so the estimate is accurate for scores close to the reference. The error
+/// grows with the distance from the reference: hashrate variations are compensated only
+/// after a difficulty window, and the average rate deviates from the target over long
+/// periods. [`DaaScoreTimeEstimator::error_bound_msec`] reports an approximate bound of
+/// this error, assuming the rate stays within [`DAA_RATE_TOLERANCE_PERCENT`] of the target.
+/// Exact timestamps should be obtained from the node when available.
+#[derive(Debug, Clone, Copy)]
+pub struct DaaScoreTimeEstimator {
+    reference_daa_score: u64,
+    reference_unixtime_msec: u64,
+    target_time_per_block: u64,
+    daa_window_duration_msec: u64,
+}
+
+impl DaaScoreTimeEstimator {
+    /// Creates an estimator for the given network from a reference DAA score
+    /// reached at `reference_unixtime_msec` (for example the current DAA score
+    /// of the node and the current time).
+    pub fn new(network_id: NetworkId, reference_daa_score: u64, reference_unixtime_msec: u64) -> Self {
+        let params = Params::from(network_id);
+        let target_time_per_block = params.target_time_per_block(reference_daa_score);
+        Self {
+            reference_daa_score,
+            reference_unixtime_msec,
+            target_time_per_block,
+            daa_window_duration_msec: params.daa_window_duration_in_blocks(reference_daa_score) * target_time_per_block,
+        }
+    }
+
+    /// Returns the estimated unixtime in milliseconds at which `daa_score` was reached
+    pub fn unixtime_msec(&self, daa_score: u64) -> u64 {
+        if daa_score >= self.reference_daa_score {
+            self.reference_unixtime_msec.saturating_add((daa_score - self.reference_daa_score) * self.target_time_per_block)
+        } else {
+            self.reference_unixtime_msec.saturating_sub((self.reference_daa_score - daa_score) * self.target_time_per_block)
+        }
+    }
+
+    /// Returns the estimated time at which `daa_score` was reached as an RFC 3339 UTC timestamp
+    pub fn utc_timestamp(&self, daa_score: u64) -> String {
+        format_utc_timestamp(self.unixtime_msec(daa_score))
+    }
+
+    /// Returns the approximate bound, in milliseconds, of the error of the estimate for `daa_score`
+    pub fn error_bound_msec(&self, daa_score: u64) -> u64 {
+        let distance_msec = daa_score.abs_diff(self.reference_daa_score) * self.target_time_per_block;
+        distance_msec * DAA_RATE_TOLERANCE_PERCENT / 100 + self.daa_window_duration_msec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 60 * 60 * 1000;
+    const DAY: u64 = 24 * HOUR;
+
+    #[test]
+    fn test_daa_score_time_estimate() {
+        // 2024-03-01T00:00:00Z on mainnet (1 BPS)
+        let reference_unixtime = 1_709_251_200_000;
+        let reference_daa_score = 75_000_000;
+        let estimator = DaaScoreTimeEstimator::new(NetworkId::new(NetworkType::Mainnet), reference_daa_score, reference_unixtime);
+
+        assert_eq!(estimator.unixtime_msec(reference_daa_score), reference_unixtime);
+        assert_eq!(estimator.utc_timestamp(reference_daa_score - 86_400), "2024-02-29T00:00:00.000Z");
+        assert_eq!(estimator.utc_timestamp(reference_daa_score + 3_600), "2024-03-01T01:00:00.000Z");
+
+        // A day of blocks is estimated a day away, within a bound of about an hour
+        let daa_score = reference_daa_score - 86_400;
+        let bound = estimator.error_bound_msec(daa_score);
+        assert!(bound > HOUR / 2 && bound < 2 * HOUR);
+        let window = (reference_unixtime - DAY - bound)..=(reference_unixtime - DAY + bound);
+        assert!(window.contains(&estimator.unixtime_msec(daa_score)));
+
+        // The bound grows with the distance from the reference
+        assert!(estimator.error_bound_msec(reference_daa_score - 30 * 86_400) > 10 * HOUR);
+
+        // Estimates do not underflow the unix epoch
+        assert_eq!(estimator.unixtime_msec(0), reference_unixtime - reference_daa_score * 1000);
+        let early = DaaScoreTimeEstimator::new(NetworkId::new(NetworkType::Mainnet), reference_daa_score, 1000);
+        assert_eq!(early.unixtime_msec(0), 0);
+    }
+
+    #[test]
+    fn test_daa_score_time_estimate_by_network_rate() {
+        // Testnet 11 produces 10 blocks per second
+        let reference_unixtime = 1_709_251_200_000;
+        let estimator =
+            DaaScoreTimeEstimator::new(NetworkId::with_suffix(NetworkType::Testnet, 11), 1_000_000_000, reference_unixtime);
+        let daa_score = 1_000_000_000 + 36_000;
+        let bound = estimator.error_bound_msec(daa_score);
+        let estimate = estimator.unixtime_msec(daa_score);
+        assert_eq!(estimate, reference_unixtime + HOUR);
+        assert!(((reference_unixtime + HOUR - bound)..=(reference_unixtime + HOUR + bound)).contains(&estimate));
+        assert_eq!(estimator.utc_timestamp(daa_score), "2024-03-01T01:00:00.000Z");
+    }
+}
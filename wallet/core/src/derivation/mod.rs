@@ -15,7 +15,7 @@ use crate::derivation::gen1::{PubkeyDerivationManager, WalletDerivationManager};
 use crate::error::Error;
 use crate::imports::*;
 use crate::result::Result;
-use kaspa_bip32::{AddressType, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey, Language, Mnemonic, SecretKeyExt};
+use kaspa_bip32::{AddressType, ChildNumber, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey, Language, Mnemonic, SecretKeyExt};
 use kaspa_consensus_core::network::NetworkType;
 use kaspa_txscript::{
     extract_script_pub_key_address, multisig_redeem_script, multisig_redeem_script_ecdsa, pay_to_script_hash_script,
@@ -176,6 +176,7 @@ impl AddressManager {
 pub struct AddressDerivationManager {
     pub account_kind: AccountKind,
     pub account_index: u64,
+    pub account_path: Option<DerivationPath>,
     pub cosigner_index: Option<u32>,
     pub derivators: Vec<Arc<dyn WalletDerivationManagerTrait>>,
     #[allow(dead_code)]
@@ -191,6 +192,7 @@ impl AddressDerivationManager {
         keys: &ExtendedPublicKeys,
         ecdsa: bool,
         account_index: u64,
+        account_path: Option<DerivationPath>,
         cosigner_index: Option<u32>,
         minimum_signatures: u16,
         address_derivation_indexes: AddressDerivationMeta,
@@ -240,6 +242,7 @@ impl AddressDerivationManager {
         let manager = Self {
             account_kind,
             account_index,
+            account_path,
             cosigner_index,
             derivators,
             wallet: wallet.clone(),
@@ -279,6 +282,7 @@ impl AddressDerivationManager {
         let manager = Self {
             account_kind,
             account_index,
+            account_path: None,
             cosigner_index: None,
             derivators: vec![derivator],
             wallet: wallet.clone(),
@@ -333,8 +337,15 @@ impl AddressDerivationManager {
 
         let (receive, change) = if change_address { (vec![], addresses) } else { (addresses, vec![]) };
 
-        let private_keys =
-            create_private_keys(&self.account_kind, self.cosigner_index.unwrap_or(0), self.account_index, xkey, &receive, &change)?;
+        let private_keys = create_private_keys(
+            &self.account_kind,
+            self.cosigner_index.unwrap_or(0),
+            self.account_index,
+            self.account_path.as_ref(),
+            xkey,
+            &receive,
+            &change,
+        )?;
 
         let mut result = vec![];
         for (address, private_key) in private_keys {
@@ -548,6 +559,40 @@ pub async fn create_xpub_from_xprv(
     Ok(xkey)
 }
 
+/// Creates the extended public key of an account derived from `xprv` with the custom `account_path`.
+pub async fn create_xpub_from_xprv_with_path(
+    xprv: ExtendedPrivateKey<secp256k1::SecretKey>,
+    account_path: &DerivationPath,
+) -> Result<ExtendedPublicKey<secp256k1::PublicKey>> {
+    Ok(xprv.derive_path(account_path.clone())?.public_key())
+}
+
+/// Parses a custom BIP32 account derivation path such as `m/44'/111111'/5'`,
+/// used to recover accounts created by other wallets. The path must only contain
+/// hardened child numbers; receive and change addresses are derived below it.
+pub fn parse_account_derivation_path(path: &str) -> Result<DerivationPath> {
+    let invalid = |reason: &str| Error::InvalidDerivationPath(path.to_string(), reason.to_string());
+    let path = path.trim();
+    if !path.starts_with("m/") {
+        return Err(invalid("the path must start with 'm/'"));
+    }
+    let account_path = path.parse::<DerivationPath>().map_err(|err| invalid(&err.to_string()))?;
+    if account_path.iter().any(|child_number| !child_number.is_hardened()) {
+        return Err(invalid("all path components must be hardened"));
+    }
+    Ok(account_path)
+}
+
+/// Builds the receive and change derivation paths below a custom `account_path`.
+pub fn build_derivate_paths_with_account_path(account_path: &DerivationPath) -> Result<(DerivationPath, DerivationPath)> {
+    let derivate_path = |address_type: AddressType| -> Result<DerivationPath> {
+        let mut path = account_path.clone();
+        path.push(ChildNumber::new(address_type.index(), false)?);
+        Ok(path)
+    };
+    Ok((derivate_path(AddressType::Receive)?, derivate_path(AddressType::Change)?))
+}
+
 pub fn build_derivate_path(
     account_kind: &AccountKind,
     account_index: u64,
@@ -96,7 +96,7 @@ pub fn from_bip32<const N: usize>(prv_key_data_id: &PrvKeyDataId, data: &bip32::
         ecdsa: Some(data.ecdsa),
         account_index: Some(data.account_index),
         secp256k1_public_key: None,
-        data: None,
+        data: data.account_path.as_ref().map(|account_path| account_path.as_bytes().to_vec()),
     };
     make_hashes(hashable)
 }
@@ -5,8 +5,10 @@
 use crate::imports::{AccountId, AccountKind, AssocPrvKeyDataIds, PrvKeyDataId};
 use base64::DecodeError;
 use downcast::DowncastError;
+use kaspa_addresses::Address;
 use kaspa_bip32::Error as BIP32Error;
 use kaspa_consensus_core::sign::Error as CoreSignError;
+use kaspa_consensus_core::tx::TransactionId;
 use kaspa_rpc_core::RpcError as KaspaRpcError;
 use kaspa_wrpc_client::error::Error as KaspaWorkflowRpcError;
 use std::sync::PoisonError;
@@ -69,6 +71,15 @@ pub enum Error {
     #[error("Wallet is not open")]
     WalletNotOpen,
 
+    #[error("Wallet '{0}' is currently open")]
+    WalletIsOpen(String),
+
+    #[error("Wallet '{0}' is not open")]
+    WalletNameNotOpen(String),
+
+    #[error("Invalid account derivation path '{0}': {1}")]
+    InvalidDerivationPath(String, String),
+
     #[error("Wallet is not connected")]
     NotConnected,
 
@@ -174,6 +185,9 @@ pub enum Error {
     #[error("task aborted")]
     Aborted,
 
+    #[error("task aborted: {0}")]
+    AbortedWithReason(String),
+
     #[error("{0}")]
     TryFromEnum(#[from] workflow_core::enums::TryFromError),
 
@@ -240,6 +254,9 @@ pub enum Error {
     #[error("Invalid transaction amount")]
     GeneratorPaymentOutputZeroAmount,
 
+    #[error("Payment output of {amount} sompi to {address} is below the dust threshold")]
+    DustOutput { address: Address, amount: u64 },
+
     #[error("Priority fees can not be included into transactions with multiple outputs")]
     GeneratorIncludeFeesRequiresOneOutput,
 
@@ -249,6 +266,30 @@ pub enum Error {
     #[error("Transaction exceeds the maximum allowed mass")]
     GeneratorTransactionIsTooHeavy,
 
+    #[error("Transaction payload of {size} bytes exceeds the maximum of {max} bytes")]
+    GeneratorPayloadTooLarge { size: usize, max: usize },
+
+    #[error("Maximum number of inputs per transaction must be at least 2 (got {0})")]
+    GeneratorMaxInputsPerTransaction(usize),
+
+    #[error("Maximum number of outputs per transaction must be at least 1 (got {0})")]
+    GeneratorMaxOutputsPerTransaction(usize),
+
+    #[error("Only final payment transactions that have not been accepted can be replaced")]
+    GeneratorReplacementNotSupported,
+
+    #[error("Transaction {0} is in the node mempool and cannot be replaced until it is evicted")]
+    GeneratorReplacementInMempool(TransactionId),
+
+    #[error("Replacement fee of {additional_fee} sompi exceeds the change of {change} sompi of the original transaction")]
+    GeneratorReplacementInsufficientChange { additional_fee: u64, change: u64 },
+
+    #[error("Replacement transaction must spend the same inputs and pay the same recipient outputs as the original transaction")]
+    GeneratorReplacementAltersOutputs,
+
+    #[error("Replacement transaction must pay higher fees than the original transaction")]
+    GeneratorReplacementFees,
+
     #[error("Storage mass exceeds maximum")]
     StorageMassExceedsMaximumTransactionMass { storage_mass: u64 },
 
@@ -261,6 +302,9 @@ pub enum Error {
     #[error(transparent)]
     TxScriptError(#[from] kaspa_txscript_errors::TxScriptError),
 
+    #[error(transparent)]
+    TxRuleError(#[from] kaspa_consensus_core::errors::tx::TxRuleError),
+
     #[error("Legacy account is not initialized")]
     LegacyAccountNotInitialized,
 
@@ -281,6 +325,69 @@ pub enum Error {
 
     #[error("Mass calculation error")]
     MassCalculationError,
+
+    #[error("Arithmetic overflow")]
+    Overflow,
+
+    #[error("Invalid amount '{0}'")]
+    InvalidAmount(String),
+
+    #[error("Negative amount '{0}' is not allowed")]
+    NegativeAmount(String),
+
+    #[error("Amount '{0}' uses scientific notation, please supply a decimal value")]
+    ScientificNotationAmount(String),
+
+    #[error("Amount '{0}' exceeds the maximum precision of 8 decimal places")]
+    AmountPrecision(String),
+
+    #[error("Amount '{0}' is too large")]
+    AmountOverflow(String),
+
+    #[error("Unsupported unsigned transaction version {0} (the maximum supported version is {1})")]
+    UnsignedTransactionVersion(u32, u32),
+
+    #[error("Unsigned transaction network mismatch - expected: {0} received: {1}")]
+    UnsignedTransactionNetworkMismatch(String, String),
+
+    #[error("Transaction is not fully signed")]
+    UnsignedTransactionNotSigned,
+
+    #[error("Price source error: {0}")]
+    PriceSource(String),
+
+    #[error("Ping timed out after {0:?}")]
+    PingTimeout(std::time::Duration),
+
+    #[error("Settings are encrypted, please unlock them with the wallet secret")]
+    SettingsLocked,
+
+    #[error("Settings are encrypted with a different secret")]
+    SettingsSecretMismatch,
+
+    #[error("Unsupported kaspawallet file version {0} (supported versions are 0 and 1)")]
+    KaspaWalletFileVersion(u32),
+
+    #[error("Invalid kaspawallet file: {0}")]
+    KaspaWalletFile(String),
+
+    #[error("Unable to decrypt the kaspawallet file - invalid password")]
+    KaspaWalletDecrypt,
+
+    #[error("Invalid address book alias '{0}' (aliases can not be empty, contain whitespace or ':' or be an address)")]
+    InvalidAddressBookAlias(String),
+
+    #[error("Address book alias '{0}' already exists")]
+    AddressBookAliasExists(String),
+
+    #[error("Address book alias '{0}' not found")]
+    AddressBookAliasNotFound(String),
+
+    #[error("'{0}' is neither a valid address nor an address book alias")]
+    UnknownRecipient(String),
+
+    #[error("Address book address '{0}' does not belong to network '{1}'")]
+    AddressBookNetworkMismatch(String, String),
 }
 
 impl From<Aborted> for Error {
@@ -3,7 +3,8 @@
 //! are used internally in the wallet framework core.
 //!
 
-pub use crate::account::descriptor::{AccountDescriptor, AccountDescriptorProperty};
+pub use crate::abortable::Abortable;
+pub use crate::account::descriptor::{AccountDescriptor, AccountDescriptorProperty, AccountExportDescriptor};
 pub use crate::account::variants::*;
 pub use crate::account::{Account, AccountKind, DerivationCapableAccount};
 pub use crate::deterministic::*;
@@ -20,7 +21,7 @@ pub use crate::storage::*;
 pub use crate::tx::MassCombinationStrategy;
 pub use crate::types::*;
 pub use crate::utxo::balance::Balance;
-pub use crate::utxo::scan::{Scan, ScanExtent};
+pub use crate::utxo::scan::{Scan, ScanControl, ScanExtent, ScanProgress};
 pub use crate::utxo::{Maturity, NetworkParams, OutgoingTransaction, UtxoContext, UtxoEntryReference, UtxoProcessor};
 pub use crate::wallet::*;
 pub use crate::{storage, utils};
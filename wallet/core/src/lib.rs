@@ -54,8 +54,10 @@
 extern crate alloc;
 extern crate self as kaspa_wallet_core;
 
+pub mod abortable;
 pub mod account;
 pub mod api;
+pub mod daa;
 pub mod derivation;
 pub mod deterministic;
 pub mod encryption;
@@ -65,6 +67,7 @@ pub mod factory;
 mod imports;
 pub mod message;
 pub mod prelude;
+pub mod price;
 pub mod result;
 pub mod rpc;
 pub mod secret;
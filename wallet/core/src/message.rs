@@ -2,6 +2,7 @@
 //! Message signing and verification functions.
 //!
 
+use kaspa_addresses::{Address, Version};
 use kaspa_hashes::{Hash, PersonalMessageSigningHash};
 use secp256k1::{Error, XOnlyPublicKey};
 
@@ -37,6 +38,22 @@ pub fn verify_message(msg: &PersonalMessage, signature: &Vec<u8>, pubkey: &XOnly
     sig.verify(&msg, pubkey)
 }
 
+/// Verifies signed message against a Kaspa address.
+///
+/// Produces `true` only if the address is a [`Version::PubKey`] address and the
+/// signature was produced by the key of that address. Signatures made by other keys,
+/// malformed signatures and unsupported address types all produce `false`.
+pub fn verify_message_with_address(msg: &PersonalMessage, signature: &Vec<u8>, address: &Address) -> bool {
+    if address.version != Version::PubKey {
+        return false;
+    }
+
+    match XOnlyPublicKey::from_slice(address.payload.as_slice()) {
+        Ok(pubkey) => verify_message(msg, signature, &pubkey).is_ok(),
+        Err(_) => false,
+    }
+}
+
 fn calc_personal_message_hash(msg: &PersonalMessage) -> Hash {
     let mut hasher = PersonalMessageSigningHash::new();
     hasher.write(msg);
@@ -132,6 +149,31 @@ Ut omnis magnam et accusamus earum rem impedit provident eum commodi repellat qu
         assert!(verify_result.is_err());
     }
 
+    #[test]
+    fn test_verify_with_address() {
+        let pm = PersonalMessage("Hello Kaspa!");
+        let privkey: [u8; 32] = [
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03,
+        ];
+        let pubkey = secp256k1::KeyPair::from_seckey_slice(secp256k1::SECP256K1, &privkey).unwrap().x_only_public_key().0;
+        let address = Address::new(kaspa_addresses::Prefix::Testnet, Version::PubKey, &pubkey.serialize());
+
+        let signature = sign_message(&pm, &privkey).expect("sign_message failed");
+        assert!(verify_message_with_address(&pm, &signature, &address));
+
+        let mut tampered = signature.clone();
+        tampered[0] ^= 0x01;
+        assert!(!verify_message_with_address(&pm, &tampered, &address));
+        assert!(!verify_message_with_address(&PersonalMessage("Hello Kaspa?"), &signature, &address));
+
+        let other = Address::new(kaspa_addresses::Prefix::Testnet, Version::PubKey, &[0x79; 32]);
+        assert!(!verify_message_with_address(&pm, &signature, &other));
+
+        let script_hash = Address::new(kaspa_addresses::Prefix::Testnet, Version::ScriptHash, &pubkey.serialize());
+        assert!(!verify_message_with_address(&pm, &signature, &script_hash));
+    }
+
     #[test]
     fn test_sign_and_verify_test_case_0() {
         let pm = PersonalMessage("Hello Kaspa!");
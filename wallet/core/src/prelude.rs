@@ -16,7 +16,7 @@ pub use crate::storage::{IdT, Interface, PrvKeyDataId, PrvKeyDataInfo, Transacti
 pub use crate::tx::{Fees, PaymentDestination, PaymentOutput, PaymentOutputs};
 pub use crate::utxo::balance::{Balance, BalanceStrings};
 pub use crate::wallet::args::*;
-pub use crate::wallet::Wallet;
+pub use crate::wallet::{Wallet, WalletRegistry};
 pub use kaspa_addresses::{Address, Prefix as AddressPrefix};
 pub use kaspa_bip32::{Language, Mnemonic, WordCount};
 pub use kaspa_wrpc_client::{KaspaRpcClient, WrpcEncoding};
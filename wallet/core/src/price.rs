@@ -0,0 +1,299 @@
+//!
+//! Fiat conversion of KAS amounts via a pluggable [`PriceSource`].
+//!
+//! Balances are displayed without a fiat equivalent unless a [`Fiat`]
+//! converter (a price source and a currency) is supplied. Rates obtained
+//! from a price source can be cached for a TTL using [`CachedPriceSource`],
+//! which also allows formatting balances with the last known rate without
+//! waiting for the price source (see [`format_balance_with_cached_price`]).
+//! An HTTPS price source querying a CoinGecko-compatible `simple/price`
+//! endpoint is available on native platforms behind the `http-price-source`
+//! feature (see [`HttpPriceSource`]).
+//!
+
+use crate::imports::*;
+use crate::result::Result;
+use crate::utxo::balance::{Balance, BalanceStrings};
+use kaspa_consensus_core::constants::SOMPI_PER_KASPA;
+use std::time::Duration;
+use workflow_core::time::Instant;
+
+/// Default time during which [`CachedPriceSource`] retains a rate.
+pub const DEFAULT_PRICE_TTL: Duration = Duration::from_secs(300);
+
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    /// Returns the price of 1 KAS in the fiat `currency` (a lowercase ISO 4217 code, ie. `usd`).
+    async fn price(&self, currency: &str) -> Result<f64>;
+
+    /// Returns the last known price of 1 KAS in the fiat `currency` without querying
+    /// the source, `None` if the source does not retain rates or no rate is known yet.
+    fn cached_price(&self, _currency: &str) -> Option<f64> {
+        None
+    }
+}
+
+pub type DynPriceSource = Arc<dyn PriceSource>;
+
+/// Price source caching the rates of the underlying source for a TTL.
+/// If the underlying source fails to refresh an expired rate, the expired
+/// rate is returned (and retained for another TTL) instead of the error.
+pub struct CachedPriceSource {
+    source: DynPriceSource,
+    ttl: Duration,
+    rates: Mutex<HashMap<String, (f64, Instant)>>,
+}
+
+impl CachedPriceSource {
+    pub fn new(source: DynPriceSource, ttl: Duration) -> Self {
+        Self { source, ttl, rates: Mutex::new(HashMap::new()) }
+    }
+}
+
+#[async_trait]
+impl PriceSource for CachedPriceSource {
+    async fn price(&self, currency: &str) -> Result<f64> {
+        if let Some((rate, ts)) = self.rates.lock().unwrap().get(currency) {
+            if ts.elapsed() < self.ttl {
+                return Ok(*rate);
+            }
+        }
+
+        match self.source.price(currency).await {
+            Ok(rate) => {
+                self.rates.lock().unwrap().insert(currency.to_string(), (rate, Instant::now()));
+                Ok(rate)
+            }
+            Err(err) => {
+                let mut rates = self.rates.lock().unwrap();
+                let (rate, ts) = rates.get_mut(currency).ok_or(err)?;
+                *ts = Instant::now();
+                Ok(*rate)
+            }
+        }
+    }
+
+    fn cached_price(&self, currency: &str) -> Option<f64> {
+        self.rates.lock().unwrap().get(currency).map(|(rate, _)| *rate)
+    }
+}
+
+/// Fiat converter: the price source and the currency balances are converted to.
+#[derive(Clone)]
+pub struct Fiat {
+    source: DynPriceSource,
+    currency: String,
+}
+
+impl Fiat {
+    pub fn new(source: DynPriceSource, currency: &str) -> Self {
+        Self { source, currency: currency.trim().to_lowercase() }
+    }
+
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    /// Converts `sompi` to the fiat currency.
+    pub async fn convert(&self, sompi: u64) -> Result<f64> {
+        let rate = self.source.price(&self.currency).await?;
+        Ok(sompi as f64 / SOMPI_PER_KASPA as f64 * rate)
+    }
+
+    /// Formats the fiat equivalent of `sompi`, ie. `12.34 USD`.
+    pub async fn format(&self, sompi: u64) -> Result<String> {
+        Ok(self.format_with_rate(sompi, self.source.price(&self.currency).await?))
+    }
+
+    /// Formats the fiat equivalent of `sompi` using the last known rate of the
+    /// price source, `None` if no rate is known (see [`PriceSource::cached_price`]).
+    pub fn format_with_cached_price(&self, sompi: u64) -> Option<String> {
+        self.source.cached_price(&self.currency).map(|rate| self.format_with_rate(sompi, rate))
+    }
+
+    /// Queries the price source, updating the rate used by [`Fiat::format_with_cached_price`].
+    pub async fn refresh(&self) -> Result<()> {
+        self.source.price(&self.currency).await?;
+        Ok(())
+    }
+
+    fn format_with_rate(&self, sompi: u64, rate: f64) -> String {
+        format!("{:.2} {}", sompi as f64 / SOMPI_PER_KASPA as f64 * rate, self.currency.to_uppercase())
+    }
+}
+
+/// Formats `balance` as [`BalanceStrings`] followed by the fiat equivalent of the mature
+/// balance when a `fiat` converter is supplied. The fiat equivalent is omitted if the
+/// balance is not available or the price source fails.
+pub async fn format_balance(balance: &Option<Balance>, network_type: &NetworkType, fiat: Option<&Fiat>) -> String {
+    let balance_strings = BalanceStrings::from((balance, network_type, None)).to_string();
+    match (balance, fiat) {
+        (Some(balance), Some(fiat)) => match fiat.format(balance.mature).await {
+            Ok(fiat) => format!("{balance_strings} ≈ {fiat}"),
+            Err(err) => {
+                log_warning!("Unable to obtain the {} price: {err}", fiat.currency().to_uppercase());
+                balance_strings
+            }
+        },
+        _ => balance_strings,
+    }
+}
+
+/// Formats `balance` like [`format_balance`] without querying the price source: the fiat
+/// equivalent uses the last known rate and is omitted if no rate is known. Intended for
+/// event handlers that must not wait for the price source (see [`Fiat::refresh`]).
+pub fn format_balance_with_cached_price(balance: &Option<Balance>, network_type: &NetworkType, fiat: Option<&Fiat>) -> String {
+    let balance_strings = BalanceStrings::from((balance, network_type, None)).to_string();
+    match balance.as_ref().zip(fiat).and_then(|(balance, fiat)| fiat.format_with_cached_price(balance.mature)) {
+        Some(fiat) => format!("{balance_strings} ≈ {fiat}"),
+        None => balance_strings,
+    }
+}
+
+cfg_if! {
+    if #[cfg(all(feature = "http-price-source", not(target_arch = "wasm32")))] {
+        use hyper::client::HttpConnector;
+        use hyper_rustls::HttpsConnector;
+
+        /// Default endpoint of [`HttpPriceSource`].
+        pub const DEFAULT_PRICE_SOURCE_URL: &str = "https://api.coingecko.com/api/v3/simple/price";
+
+        /// Default time after which a request of [`HttpPriceSource`] is abandoned.
+        pub const DEFAULT_PRICE_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+        /// Price source querying a CoinGecko-compatible `simple/price` endpoint
+        /// (`<url>?ids=kaspa&vs_currencies=<currency>`) over HTTPS.
+        pub struct HttpPriceSource {
+            url: String,
+            timeout: Duration,
+            client: hyper::Client<HttpsConnector<HttpConnector>>,
+        }
+
+        impl Default for HttpPriceSource {
+            fn default() -> Self {
+                Self::new(DEFAULT_PRICE_SOURCE_URL)
+            }
+        }
+
+        impl HttpPriceSource {
+            pub fn new(url: &str) -> Self {
+                let connector = hyper_rustls::HttpsConnectorBuilder::new().with_webpki_roots().https_only().enable_http1().build();
+                let client = hyper::Client::builder().build(connector);
+                Self { url: url.to_string(), timeout: DEFAULT_PRICE_REQUEST_TIMEOUT, client }
+            }
+
+            pub fn with_timeout(mut self, timeout: Duration) -> Self {
+                self.timeout = timeout;
+                self
+            }
+
+            async fn request(&self, currency: &str) -> Result<f64> {
+                let uri = format!("{}?ids=kaspa&vs_currencies={currency}", self.url)
+                    .parse::<hyper::Uri>()
+                    .map_err(|err| Error::PriceSource(err.to_string()))?;
+                let response = self.client.get(uri).await.map_err(|err| Error::PriceSource(err.to_string()))?;
+                if !response.status().is_success() {
+                    return Err(Error::PriceSource(format!("HTTP status {}", response.status())));
+                }
+                let body = hyper::body::to_bytes(response.into_body()).await.map_err(|err| Error::PriceSource(err.to_string()))?;
+                let value: serde_json::Value = serde_json::from_slice(&body)?;
+                value["kaspa"][currency].as_f64().ok_or_else(|| Error::PriceSource(format!("no KAS price for currency '{currency}'")))
+            }
+        }
+
+        #[async_trait]
+        impl PriceSource for HttpPriceSource {
+            async fn price(&self, currency: &str) -> Result<f64> {
+                select! {
+                    result = self.request(currency).fuse() => result,
+                    _ = sleep(self.timeout).fuse() => Err(Error::PriceSource(format!("request timed out after {:?}", self.timeout))),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct PriceSourceMock {
+        requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PriceSource for PriceSourceMock {
+        async fn price(&self, currency: &str) -> Result<f64> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            match currency {
+                "usd" => Ok(0.125),
+                "eur" => Ok(0.1),
+                _ => Err(Error::PriceSource(format!("unsupported currency '{currency}'"))),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fiat_balance_format() {
+        let balance = Some(Balance::new(1_000 * SOMPI_PER_KASPA, 0, 0, 1, 0, 0));
+        let source = Arc::new(PriceSourceMock::default());
+
+        assert_eq!(format_balance(&balance, &NetworkType::Mainnet, None).await, "1,000 KAS");
+        let usd = Fiat::new(source.clone(), "USD");
+        assert_eq!(format_balance(&balance, &NetworkType::Mainnet, Some(&usd)).await, "1,000 KAS ≈ 125.00 USD");
+        let eur = Fiat::new(source.clone(), "eur");
+        assert_eq!(format_balance(&balance, &NetworkType::Testnet, Some(&eur)).await, "1,000 TKAS ≈ 100.00 EUR");
+
+        // the fiat equivalent is omitted if unavailable
+        let xyz = Fiat::new(source.clone(), "xyz");
+        assert_eq!(format_balance(&balance, &NetworkType::Mainnet, Some(&xyz)).await, "1,000 KAS");
+        assert_eq!(format_balance(&None, &NetworkType::Mainnet, Some(&usd)).await, "N/A KAS");
+    }
+
+    #[tokio::test]
+    async fn test_cached_price_source() {
+        let source = Arc::new(PriceSourceMock::default());
+        let cached = CachedPriceSource::new(source.clone(), Duration::from_millis(50));
+
+        assert_eq!(cached.price("usd").await.unwrap(), 0.125);
+        assert_eq!(cached.price("usd").await.unwrap(), 0.125);
+        assert_eq!(cached.price("eur").await.unwrap(), 0.1);
+        assert_eq!(source.requests.load(Ordering::SeqCst), 2);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(cached.price("usd").await.unwrap(), 0.125);
+        assert_eq!(source.requests.load(Ordering::SeqCst), 3);
+
+        // failures are not cached
+        assert!(cached.price("xyz").await.is_err());
+        assert!(cached.price("xyz").await.is_err());
+        assert_eq!(source.requests.load(Ordering::SeqCst), 5);
+        assert_eq!(cached.cached_price("usd"), Some(0.125));
+        assert_eq!(cached.cached_price("xyz"), None);
+    }
+
+    #[tokio::test]
+    async fn test_cached_price_fallback() {
+        let source = Arc::new(PriceSourceMock::default());
+        let cached = Arc::new(CachedPriceSource::new(source.clone(), Duration::from_millis(50)));
+        cached.rates.lock().unwrap().insert("xyz".to_string(), (2.0, Instant::now()));
+
+        // an expired rate is returned if the source fails to refresh it
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(cached.price("xyz").await.unwrap(), 2.0);
+        assert_eq!(cached.price("xyz").await.unwrap(), 2.0);
+        assert_eq!(source.requests.load(Ordering::SeqCst), 1);
+
+        // balances are formatted with the last known rate without querying the source
+        let balance = Some(Balance::new(1_000 * SOMPI_PER_KASPA, 0, 0, 1, 0, 0));
+        let usd = Fiat::new(cached.clone(), "usd");
+        assert_eq!(format_balance_with_cached_price(&balance, &NetworkType::Mainnet, Some(&usd)), "1,000 KAS");
+        usd.refresh().await.unwrap();
+        assert_eq!(format_balance_with_cached_price(&balance, &NetworkType::Mainnet, Some(&usd)), "1,000 KAS ≈ 125.00 USD");
+        let xyz = Fiat::new(cached, "xyz");
+        assert_eq!(format_balance_with_cached_price(&balance, &NetworkType::Mainnet, Some(&xyz)), "1,000 KAS ≈ 2000.00 XYZ");
+        assert_eq!(source.requests.load(Ordering::SeqCst), 2);
+    }
+}
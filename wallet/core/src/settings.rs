@@ -2,8 +2,10 @@
 //! Multi-platform storage for wallet and application settings.
 //!
 
+use crate::encryption::{Encryptable, EncryptionKind};
 use crate::imports::*;
 use crate::result::Result;
+use crate::secret::Secret;
 use crate::storage::local::Storage;
 use serde::de::DeserializeOwned;
 use serde_json::{from_value, to_value, Map, Value};
@@ -22,6 +24,10 @@ pub enum WalletSettings {
     Server,
     #[describe("Wallet storage or file name (default 'kaspa')")]
     Wallet,
+    #[describe("Fiat currency of the balance display (usd|eur|...)")]
+    Currency,
+    #[describe("Derive a fresh change address for each transaction (true|false)")]
+    ChangeAddressRotation,
 }
 
 #[async_trait]
@@ -34,28 +40,94 @@ impl DefaultSettings for WalletSettings {
 #[async_trait]
 pub trait DefaultSettings: Sized {
     async fn defaults() -> Vec<(Self, Value)>;
+
+    /// Secret settings, encrypted at rest when settings encryption is enabled.
+    /// Settings required before a wallet is opened (such as the server or
+    /// the default wallet) must not be sensitive.
+    fn sensitive() -> Vec<Self> {
+        vec![]
+    }
 }
 
-#[derive(Debug, Clone)]
+/// Key of the encrypted section of the settings file
+const ENCRYPTED_SETTINGS_KEY: &str = "encrypted";
+
+/// Settings store persisted as a JSON file.
+///
+/// The sensitive settings (see [`DefaultSettings::sensitive()`]) can optionally be
+/// encrypted at rest with the wallet secret (see [`SettingsStore::enable_encryption()`]).
+/// Encrypted settings are locked after loading: until [`SettingsStore::unlock()`] is
+/// called with the secret, sensitive settings are unavailable. Other settings remain
+/// readable and writable, while sensitive settings changed during this time are
+/// persisted once the store is unlocked.
+///
+/// Clones of the store share the same settings.
+#[derive(Clone)]
 pub struct SettingsStore<K>
 where
     K: DefaultSettings + Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
 {
-    map: DashMap<String, Value>,
+    map: Arc<DashMap<String, Value>>,
     storage: Storage,
+    secret: Arc<Mutex<Option<Secret>>>,
+    locked: Arc<AtomicBool>,
+    /// Encrypted section of the settings file while the store is locked
+    encrypted: Arc<Mutex<Option<Value>>>,
+    /// Sensitive settings changed while the store is locked
+    modified_while_locked: Arc<Mutex<HashSet<String>>>,
     phantom: PhantomData<K>,
 }
 
+impl<K> std::fmt::Debug for SettingsStore<K>
+where
+    K: DefaultSettings + Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SettingsStore")
+            .field("map", &self.map)
+            .field("storage", &self.storage)
+            .field("encrypted", &self.is_encrypted())
+            .field("locked", &self.is_locked())
+            .finish()
+    }
+}
+
 impl<K> SettingsStore<K>
 where
     K: DefaultSettings + Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
 {
     pub fn try_new(filename: &str) -> Result<Self> {
-        Ok(Self { map: DashMap::default(), storage: Storage::try_new(&format!("{filename}.settings"))?, phantom: PhantomData })
+        Ok(Self::new_with_storage(Storage::try_new(&format!("{filename}.settings"))?))
     }
 
     pub fn new_with_storage(storage: Storage) -> Self {
-        Self { map: DashMap::default(), storage, phantom: PhantomData }
+        Self {
+            map: Arc::new(DashMap::default()),
+            storage,
+            secret: Arc::new(Mutex::new(None)),
+            locked: Arc::new(AtomicBool::new(false)),
+            encrypted: Arc::new(Mutex::new(None)),
+            modified_while_locked: Arc::new(Mutex::new(HashSet::default())),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the settings are encrypted at rest.
+    pub fn is_encrypted(&self) -> bool {
+        self.is_locked() || self.secret.lock().unwrap().is_some()
+    }
+
+    /// Returns `true` if the settings loaded from storage are encrypted
+    /// and have not been unlocked with the secret yet.
+    pub fn is_locked(&self) -> bool {
+        self.locked.load(Ordering::SeqCst)
+    }
+
+    fn sensitive_keys() -> Vec<String> {
+        K::sensitive()
+            .into_iter()
+            .map(|key| to_value(key).unwrap().as_str().expect("Unable to convert key to string").to_string())
+            .collect()
     }
 
     pub fn get<V>(&self, key: K) -> Option<V>
@@ -86,24 +158,25 @@ where
         let ks = ks.as_str().expect("Unable to convert key to string");
 
         let v = to_value(value)?;
-        self.map.insert(ks.to_string(), v);
+        self.insert(ks.to_string(), v);
         self.try_store().await?;
         Ok(())
     }
 
+    fn insert(&self, key: String, value: Value) {
+        if self.is_locked() && Self::sensitive_keys().contains(&key) {
+            self.modified_while_locked.lock().unwrap().insert(key.clone());
+        }
+        self.map.insert(key, value);
+    }
+
     pub async fn try_load(&self) -> Result<()> {
-        let list: Option<Value> = if self.storage.exists().await? {
-            let v: Result<Value> = workflow_store::fs::read_json(self.storage.filename()).await.map_err(|err| err.into());
-            match v {
-                Ok(v) => v.is_object().then_some(v),
-                Err(err) => {
-                    log_error!("Unable to read settings file: `{err}`");
-                    None
-                }
-            }
-        } else {
-            None
-        };
+        self.secret.lock().unwrap().take();
+        self.modified_while_locked.lock().unwrap().clear();
+        let mut list = self.read().await?;
+        let encrypted = list.as_mut().and_then(|value| value.as_object_mut()?.remove(ENCRYPTED_SETTINGS_KEY));
+        self.locked.store(encrypted.is_some(), Ordering::SeqCst);
+        *self.encrypted.lock().unwrap() = encrypted;
 
         let list = if let Some(value) = list {
             value
@@ -116,22 +189,136 @@ where
             })))
         };
 
-        self.map.clear();
-        if let Value::Object(map) = list {
-            map.into_iter().for_each(|(k, v)| {
+        self.replace(list);
+
+        Ok(())
+    }
+
+    /// Decrypts the locked sensitive settings with `secret`. Sensitive settings
+    /// changed while locked take precedence and are persisted. Does nothing if
+    /// the settings are not encrypted or have already been unlocked.
+    pub async fn unlock(&self, secret: &Secret) -> Result<()> {
+        if !self.is_locked() {
+            return Ok(());
+        }
+
+        let encrypted = self.encrypted.lock().unwrap().clone();
+        if let Some(Value::Object(sensitive)) = encrypted.map(|value| decrypt(value, secret)).transpose()? {
+            let modified = self.modified_while_locked.lock().unwrap().clone();
+            sensitive.into_iter().filter(|(k, _)| !modified.contains(k)).for_each(|(k, v)| {
                 self.map.insert(k, v);
             });
         }
+        self.secret.lock().unwrap().replace(secret.clone());
+        self.encrypted.lock().unwrap().take();
+        self.locked.store(false, Ordering::SeqCst);
+
+        let modified = std::mem::take(&mut *self.modified_while_locked.lock().unwrap());
+        if !modified.is_empty() {
+            self.try_store().await?;
+        }
+        Ok(())
+    }
+
+    /// Locks encrypted settings, discarding the secret and the
+    /// sensitive settings from memory. Does nothing if the settings
+    /// are not encrypted.
+    pub fn lock(&self) -> Result<()> {
+        let Some(secret) = self.secret.lock().unwrap().take() else {
+            return Ok(());
+        };
 
+        let sensitive = Self::sensitive_keys().into_iter().filter_map(|key| self.map.remove(&key)).collect::<Map<String, Value>>();
+        *self.encrypted.lock().unwrap() = Some(encrypt(Value::Object(sensitive), &secret)?);
+        self.locked.store(true, Ordering::SeqCst);
         Ok(())
     }
 
+    /// Encrypts the sensitive settings at rest with `secret`, migrating
+    /// existing plaintext settings. Locked settings must be unlocked first.
+    /// The caller is responsible for verifying that `secret` is the wallet secret.
+    pub async fn enable_encryption(&self, secret: &Secret) -> Result<()> {
+        if self.is_locked() {
+            return Err(Error::SettingsLocked);
+        }
+        if self.secret.lock().unwrap().as_ref().is_some_and(|current| current.as_ref() != secret.as_ref()) {
+            return Err(Error::SettingsSecretMismatch);
+        }
+        self.secret.lock().unwrap().replace(secret.clone());
+        self.try_store().await
+    }
+
+    /// Stores the settings in plaintext. The `secret` must match the secret
+    /// the settings are encrypted with.
+    pub async fn disable_encryption(&self, secret: &Secret) -> Result<()> {
+        self.unlock(secret).await?;
+        if self.secret.lock().unwrap().as_ref().is_some_and(|current| current.as_ref() != secret.as_ref()) {
+            return Err(Error::SettingsSecretMismatch);
+        }
+        self.secret.lock().unwrap().take();
+        self.try_store().await
+    }
+
+    /// Persists the settings. While the settings are locked, the sensitive
+    /// settings are not persisted and their stored (encrypted) values are kept.
     pub async fn try_store(&self) -> Result<()> {
-        let map = Map::from_iter(self.map.clone().into_iter());
+        let sensitive_keys = Self::sensitive_keys();
+        let mut value = Map::from_iter(self.map.iter().map(|entry| (entry.key().clone(), entry.value().clone())));
+
+        let encrypted = if self.is_locked() {
+            sensitive_keys.iter().for_each(|key| {
+                value.remove(key);
+            });
+            self.encrypted.lock().unwrap().clone()
+        } else if let Some(secret) = self.secret.lock().unwrap().as_ref() {
+            let sensitive = sensitive_keys.iter().filter_map(|key| value.remove_entry(key)).collect::<Map<String, Value>>();
+            Some(encrypt(Value::Object(sensitive), secret)?)
+        } else {
+            None
+        };
+        if let Some(encrypted) = encrypted {
+            value.insert(ENCRYPTED_SETTINGS_KEY.to_string(), encrypted);
+        }
+
         self.storage.ensure_dir().await?;
-        workflow_store::fs::write_json(self.storage.filename(), &Value::Object(map)).await?;
+        workflow_store::fs::write_json(self.storage.filename(), &Value::Object(value)).await?;
         Ok(())
     }
+
+    async fn read(&self) -> Result<Option<Value>> {
+        if !self.storage.exists().await? {
+            return Ok(None);
+        }
+
+        let v: Result<Value> = workflow_store::fs::read_json(self.storage.filename()).await.map_err(|err| err.into());
+        match v {
+            Ok(v) => Ok(v.is_object().then_some(v)),
+            Err(err) => {
+                log_error!("Unable to read settings file: `{err}`");
+                Ok(None)
+            }
+        }
+    }
+
+    fn replace(&self, list: Value) {
+        self.map.clear();
+        if let Value::Object(map) = list {
+            map.into_iter().for_each(|(k, v)| {
+                self.map.insert(k, v);
+            });
+        }
+    }
+}
+
+fn encrypt(value: Value, secret: &Secret) -> Result<Value> {
+    let data = serde_json::to_vec(&value)?;
+    Ok(to_value(Encryptable::<Vec<u8>>::from(data).into_encrypted(secret, EncryptionKind::XChaCha20Poly1305)?)?)
+}
+
+fn decrypt(value: Value, secret: &Secret) -> Result<Value> {
+    let encryptable: Encryptable<Vec<u8>> = from_value(value)?;
+    let data = encryptable.decrypt(Some(secret))?;
+    Ok(serde_json::from_slice(&data)?)
 }
 
 #[async_trait]
@@ -164,7 +351,7 @@ where
     where
         V: Serialize + DeserializeOwned + Send + Sync + 'static,
     {
-        self.map.insert(key.to_string(), to_value(value).unwrap());
+        self.insert(key.to_string(), to_value(value).unwrap());
         self.try_store().await?;
         Ok(())
     }
@@ -180,3 +367,113 @@ pub async fn ensure_application_folder() -> Result<()> {
     fs::create_dir_all(&path).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum TestSettings {
+        Currency,
+        Wallet,
+        Token,
+    }
+
+    #[async_trait]
+    impl DefaultSettings for TestSettings {
+        async fn defaults() -> Vec<(Self, Value)> {
+            vec![(Self::Wallet, to_value("kaspa").unwrap())]
+        }
+
+        fn sensitive() -> Vec<Self> {
+            vec![Self::Token]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_settings_encryption() -> Result<()> {
+        let folder = tempfile::tempdir()?;
+        let path = folder.path().join("kaspa-wallet.settings");
+        let storage = || Storage::try_new(path.to_str().unwrap()).unwrap();
+        let secret = Secret::from("secret");
+        let token = "4f2b4c6a1d9e";
+
+        // plaintext settings
+        let settings = SettingsStore::<TestSettings>::new_with_storage(storage());
+        settings.try_load().await?;
+        settings.set(TestSettings::Token, token).await?;
+        settings.set(TestSettings::Currency, "eur").await?;
+        assert!(!settings.is_encrypted());
+
+        // migration from plaintext
+        let settings = SettingsStore::<TestSettings>::new_with_storage(storage());
+        settings.try_load().await?;
+        settings.enable_encryption(&secret).await?;
+        assert!(settings.is_encrypted() && !settings.is_locked());
+        assert!(matches!(settings.enable_encryption(&Secret::from("wrong")).await, Err(Error::SettingsSecretMismatch)));
+        let stored = std::fs::read_to_string(&path)?;
+        assert!(!stored.contains(token) && stored.contains("eur") && stored.contains("kaspa"));
+
+        // sensitive settings are locked until unlocked with the secret,
+        // other settings remain readable and writable
+        let settings = SettingsStore::<TestSettings>::new_with_storage(storage());
+        settings.try_load().await?;
+        assert!(settings.is_locked());
+        assert_eq!(settings.get::<String>(TestSettings::Token), None);
+        assert_eq!(settings.get::<String>(TestSettings::Wallet).as_deref(), Some("kaspa"));
+        assert_eq!(settings.get::<String>(TestSettings::Currency).as_deref(), Some("eur"));
+        settings.set(TestSettings::Currency, "usd").await?;
+        settings.set(TestSettings::Token, "other").await?;
+        assert!(settings.unlock(&Secret::from("wrong")).await.is_err());
+        assert!(settings.is_locked());
+
+        // encrypted round-trip, sensitive settings changed while locked are persisted on unlock
+        settings.unlock(&secret).await?;
+        assert_eq!(settings.get::<String>(TestSettings::Token).as_deref(), Some("other"));
+        settings.set(TestSettings::Token, token).await?;
+        let settings = SettingsStore::<TestSettings>::new_with_storage(storage());
+        settings.try_load().await?;
+        assert_eq!(settings.get::<String>(TestSettings::Currency).as_deref(), Some("usd"));
+        settings.unlock(&secret).await?;
+        assert_eq!(settings.get::<String>(TestSettings::Token).as_deref(), Some(token));
+
+        // locking discards the secret and the sensitive settings from memory
+        settings.lock()?;
+        assert!(settings.is_locked());
+        assert_eq!(settings.get::<String>(TestSettings::Token), None);
+        settings.unlock(&secret).await?;
+        assert_eq!(settings.get::<String>(TestSettings::Token).as_deref(), Some(token));
+
+        // back to plaintext
+        assert!(settings.disable_encryption(&Secret::from("wrong")).await.is_err());
+        settings.disable_encryption(&secret).await?;
+        let settings = SettingsStore::<TestSettings>::new_with_storage(storage());
+        settings.try_load().await?;
+        assert!(!settings.is_encrypted());
+        assert_eq!(settings.get::<String>(TestSettings::Token).as_deref(), Some(token));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wallet_settings_are_plaintext() -> Result<()> {
+        let folder = tempfile::tempdir()?;
+        let path = folder.path().join("kaspa-wallet.settings");
+        let storage = || Storage::try_new(path.to_str().unwrap()).unwrap();
+        let server = "wss://private.example.com";
+
+        let settings = SettingsStore::<WalletSettings>::new_with_storage(storage());
+        settings.try_load().await?;
+        settings.set(WalletSettings::Server, server).await?;
+        settings.enable_encryption(&Secret::from("secret")).await?;
+
+        // the server and the default wallet are available before a wallet is opened
+        let settings = SettingsStore::<WalletSettings>::new_with_storage(storage());
+        settings.try_load().await?;
+        assert_eq!(settings.get::<String>(WalletSettings::Server).as_deref(), Some(server));
+        assert_eq!(settings.get::<String>(WalletSettings::Wallet).as_deref(), Some("kaspa"));
+
+        Ok(())
+    }
+}
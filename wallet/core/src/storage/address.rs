@@ -4,10 +4,30 @@
 
 use crate::imports::*;
 
-// TODO
 #[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct AddressBookEntry {
     pub alias: String,
     pub title: String,
     pub address: Address,
 }
+
+impl AddressBookEntry {
+    pub fn try_new(alias: &str, title: &str, address: Address) -> Result<Self> {
+        Self::validate_alias(alias)?;
+        Ok(Self { alias: alias.to_string(), title: title.to_string(), address })
+    }
+
+    /// Aliases must not be mistaken for addresses when resolving a recipient,
+    /// hence they can not contain the `:` separator of address prefixes.
+    pub fn validate_alias(alias: &str) -> Result<()> {
+        if alias.is_empty() || alias.contains(|c: char| c.is_whitespace() || c == ':') || Address::try_from(alias).is_ok() {
+            Err(Error::InvalidAddressBookAlias(alias.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn matches(&self, alias: &str) -> bool {
+        self.alias.eq_ignore_ascii_case(alias)
+    }
+}
@@ -28,6 +28,18 @@ impl WalletDescriptor {
     }
 }
 
+/// Wallet summary obtained without decrypting the wallet payload.
+/// `created` and `account_count` are not available for wallets
+/// stored prior to storage version 1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletInfo {
+    pub descriptor: WalletDescriptor,
+    /// wallet creation time (unix time in milliseconds)
+    pub created: Option<u64>,
+    pub account_count: Option<u32>,
+}
+
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Serialize, Deserialize, BorshSerialize, BorshDeserialize, BorshSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "kind", content = "meta")]
@@ -81,6 +93,33 @@ pub trait AddressBookStore: Send + Sync {
     async fn search(&self, _search: &str) -> Result<Vec<Arc<AddressBookEntry>>> {
         Err(Error::NotImplemented)
     }
+    /// Loads the entry matching `alias` (case-insensitively)
+    async fn load(&self, _alias: &str) -> Result<Option<Arc<AddressBookEntry>>> {
+        Err(Error::NotImplemented)
+    }
+    /// Stores a new entry, failing if its alias is invalid or already exists
+    async fn store(&self, _entry: AddressBookEntry) -> Result<()> {
+        Err(Error::NotImplemented)
+    }
+    /// Removes the entry matching `alias`, failing if there is none
+    async fn remove(&self, _alias: &str) -> Result<()> {
+        Err(Error::NotImplemented)
+    }
+    /// Resolves a payment recipient, given either as an address or as the alias of an entry.
+    /// Aliases can not be valid addresses, so a recipient never resolves ambiguously.
+    /// An alias only resolves if its address belongs to `network_id`.
+    async fn resolve(&self, recipient: &str, network_id: &NetworkId) -> Result<Address> {
+        if let Ok(address) = Address::try_from(recipient) {
+            return Ok(address);
+        }
+        match self.load(recipient).await? {
+            Some(entry) if entry.address.prefix != Prefix::from(*network_id) => {
+                Err(Error::AddressBookNetworkMismatch(entry.address.to_string(), network_id.to_string()))
+            }
+            Some(entry) => Ok(entry.address.clone()),
+            None => Err(Error::UnknownRecipient(recipient.to_string())),
+        }
+    }
 }
 
 pub struct TransactionRangeResult {
@@ -164,6 +203,10 @@ pub trait Interface: Send + Sync + AnySync {
     /// enumerate all wallets available in the storage
     async fn wallet_list(&self) -> Result<Vec<WalletDescriptor>>;
 
+    /// enumerate all wallets available in the storage including
+    /// their creation time and account count
+    async fn wallet_info_list(&self) -> Result<Vec<WalletInfo>>;
+
     /// check if a wallet is currently open
     fn is_open(&self) -> bool;
 
@@ -179,9 +222,19 @@ pub trait Interface: Send + Sync + AnySync {
     /// rename the currently open wallet (title or the filename)
     async fn rename(&self, wallet_secret: &Secret, title: Option<&str>, filename: Option<&str>) -> Result<()>;
 
+    /// rename a wallet by its filename; the wallet must not be open
+    async fn wallet_rename(&self, filename: &str, new_filename: &str) -> Result<()>;
+
+    /// delete a wallet by its filename; the wallet must not be open and
+    /// the wallet secret is required to confirm the deletion
+    async fn wallet_delete(&self, wallet_secret: &Secret, filename: &str) -> Result<()>;
+
     /// change the secret of the currently open wallet
     async fn change_secret(&self, old_wallet_secret: &Secret, new_wallet_secret: &Secret) -> Result<()>;
 
+    /// verify the secret of the currently open wallet
+    async fn verify_secret(&self, wallet_secret: &Secret) -> Result<()>;
+
     /// checks if the wallet storage is present
     async fn exists(&self, name: Option<&str>) -> Result<bool>;
 
@@ -2,9 +2,9 @@
 //! Private key storage and encryption.
 //!
 
-use crate::derivation::create_xpub_from_xprv;
+use crate::derivation::{create_xpub_from_xprv, create_xpub_from_xprv_with_path};
 use crate::imports::*;
-use kaspa_bip32::{ExtendedPrivateKey, ExtendedPublicKey, Language, Mnemonic};
+use kaspa_bip32::{DerivationPath, ExtendedPrivateKey, ExtendedPublicKey, Language, Mnemonic};
 use kaspa_utils::hex::ToHex;
 use secp256k1::SecretKey;
 use xxhash_rust::xxh3::xxh3_64;
@@ -113,19 +113,70 @@ impl Drop for PrvKeyDataVariant {
 
 impl ZeroizeOnDrop for PrvKeyDataVariant {}
 
-#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PrvKeyDataPayload {
     prv_key_variant: PrvKeyDataVariant,
+    /// BIP39 mnemonic passphrase used to derive the seed. When `None`, the payment
+    /// secret doubles as the BIP39 passphrase (legacy behavior).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bip39_passphrase: Option<String>,
+}
+
+impl BorshSerialize for PrvKeyDataPayload {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        StorageHeader::new(Self::MAGIC, Self::VERSION).serialize(writer)?;
+        BorshSerialize::serialize(&self.prv_key_variant, writer)?;
+        BorshSerialize::serialize(&self.bip39_passphrase, writer)?;
+
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for PrvKeyDataPayload {
+    fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
+        // legacy payloads consist of the key variant only
+        if buf.get(..4) == Some(PrvKeyDataVariant::MAGIC.to_le_bytes().as_slice()) {
+            let prv_key_variant = BorshDeserialize::deserialize(buf)?;
+            return Ok(Self { prv_key_variant, bip39_passphrase: None });
+        }
+
+        let StorageHeader { version: _, .. } = StorageHeader::deserialize(buf)?.try_magic(Self::MAGIC)?.try_version(Self::VERSION)?;
+        let prv_key_variant = BorshDeserialize::deserialize(buf)?;
+        let bip39_passphrase = BorshDeserialize::deserialize(buf)?;
+
+        Ok(Self { prv_key_variant, bip39_passphrase })
+    }
 }
 
 impl PrvKeyDataPayload {
+    const MAGIC: u32 = 0x4450524b;
+    const VERSION: u32 = 0;
+
     pub fn try_new_with_mnemonic(mnemonic: Mnemonic) -> Result<Self> {
-        Ok(Self { prv_key_variant: PrvKeyDataVariant::from_mnemonic(mnemonic) })
+        Ok(Self { prv_key_variant: PrvKeyDataVariant::from_mnemonic(mnemonic), bip39_passphrase: None })
+    }
+
+    /// Creates a payload deriving the seed from `mnemonic` and `bip39_passphrase` regardless
+    /// of the payment secret, which is then only used to encrypt the payload. An empty
+    /// passphrase derives the same seed as a mnemonic without a passphrase.
+    pub fn try_new_with_mnemonic_and_bip39_passphrase(mnemonic: Mnemonic, bip39_passphrase: &Secret) -> Result<Self> {
+        let bip39_passphrase = std::str::from_utf8(bip39_passphrase.as_ref())?.to_string();
+        Ok(Self { prv_key_variant: PrvKeyDataVariant::from_mnemonic(mnemonic), bip39_passphrase: Some(bip39_passphrase) })
     }
 
     pub fn try_new_with_secret_key(secret_key: SecretKey) -> Result<Self> {
-        Ok(Self { prv_key_variant: PrvKeyDataVariant::from_secret_key(secret_key) })
+        Ok(Self { prv_key_variant: PrvKeyDataVariant::from_secret_key(secret_key), bip39_passphrase: None })
+    }
+
+    /// Returns `true` if the seed is derived with a non-empty BIP39 passphrase distinct from the payment secret.
+    pub fn has_bip39_passphrase(&self) -> bool {
+        self.bip39_passphrase.as_ref().is_some_and(|passphrase| !passphrase.is_empty())
+    }
+
+    /// Returns `true` if the payment secret is used as the BIP39 passphrase (legacy behavior).
+    pub fn is_payment_secret_bip39_passphrase(&self) -> bool {
+        matches!(self.prv_key_variant, PrvKeyDataVariant::Mnemonic(_)) && self.bip39_passphrase.is_none()
     }
 
     pub fn get_xprv(&self, payment_secret: Option<&Secret>) -> Result<ExtendedPrivateKey<SecretKey>> {
@@ -134,7 +185,8 @@ impl PrvKeyDataPayload {
         match &self.prv_key_variant {
             PrvKeyDataVariant::Mnemonic(mnemonic) => {
                 let mnemonic = Mnemonic::new(mnemonic, Language::English)?;
-                let xkey = ExtendedPrivateKey::<SecretKey>::new(mnemonic.to_seed(payment_secret.unwrap_or_default()))?;
+                let passphrase = self.bip39_passphrase.as_deref().or(payment_secret).unwrap_or_default();
+                let xkey = ExtendedPrivateKey::<SecretKey>::new(mnemonic.to_seed(passphrase))?;
                 Ok(xkey)
             }
             PrvKeyDataVariant::Bip39Seed(seed) => {
@@ -169,13 +221,21 @@ impl PrvKeyDataPayload {
     }
 
     pub fn id(&self) -> PrvKeyDataId {
-        self.prv_key_variant.id()
+        match &self.bip39_passphrase {
+            // the same mnemonic with different passphrases yields different keys
+            Some(bip39_passphrase) if !bip39_passphrase.is_empty() => {
+                let s = Zeroizing::new(format!("{}:{}", self.prv_key_variant.get_string().as_str(), bip39_passphrase));
+                PrvKeyDataId::new(xxh3_64(s.as_bytes()))
+            }
+            _ => self.prv_key_variant.id(),
+        }
     }
 }
 
 impl Zeroize for PrvKeyDataPayload {
     fn zeroize(&mut self) {
         self.prv_key_variant.zeroize();
+        self.bip39_passphrase.zeroize();
     }
 }
 
@@ -207,6 +267,16 @@ impl PrvKeyData {
         create_xpub_from_xprv(xprv, account_kind, account_index).await
     }
 
+    pub async fn create_xpub_with_path(
+        &self,
+        payment_secret: Option<&Secret>,
+        account_path: &DerivationPath,
+    ) -> Result<ExtendedPublicKey<secp256k1::PublicKey>> {
+        let payload = self.payload.decrypt(payment_secret)?;
+        let xprv = payload.get_xprv(payment_secret)?;
+        create_xpub_from_xprv_with_path(xprv, account_path).await
+    }
+
     pub fn get_xprv(&self, payment_secret: Option<&Secret>) -> Result<ExtendedPrivateKey<secp256k1::SecretKey>> {
         let payload = self.payload.decrypt(payment_secret)?;
         payload.get_xprv(payment_secret)
@@ -266,7 +336,21 @@ impl PrvKeyData {
         payment_secret: Option<&Secret>,
         encryption_kind: EncryptionKind,
     ) -> Result<Self> {
-        let payload = PrvKeyDataPayload::try_new_with_mnemonic(mnemonic)?;
+        Self::try_new_from_mnemonic_with_bip39_passphrase(mnemonic, None, payment_secret, encryption_kind)
+    }
+
+    /// Creates key data from `mnemonic`, deriving the seed with `bip39_passphrase` when provided
+    /// (the payment secret then only encrypts the key data) or with the payment secret otherwise.
+    pub fn try_new_from_mnemonic_with_bip39_passphrase(
+        mnemonic: Mnemonic,
+        bip39_passphrase: Option<&Secret>,
+        payment_secret: Option<&Secret>,
+        encryption_kind: EncryptionKind,
+    ) -> Result<Self> {
+        let payload = match bip39_passphrase {
+            Some(bip39_passphrase) => PrvKeyDataPayload::try_new_with_mnemonic_and_bip39_passphrase(mnemonic, bip39_passphrase)?,
+            None => PrvKeyDataPayload::try_new_with_mnemonic(mnemonic)?,
+        };
         let mut prv_key_data = Self { id: payload.id(), payload: Encryptable::Plain(payload), name: None };
         if let Some(payment_secret) = payment_secret {
             prv_key_data.encrypt(payment_secret, encryption_kind)?;
@@ -313,4 +397,52 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_storage_prv_key_data_payload() -> Result<()> {
+        let mnemonic = Mnemonic::random(kaspa_bip32::WordCount::Words12, Language::English)?;
+
+        // legacy payloads consist of the serialized key variant only
+        let legacy = PrvKeyDataPayload::try_new_with_mnemonic(mnemonic.clone())?;
+        let payload = PrvKeyDataPayload::try_from_slice(&legacy.prv_key_variant.try_to_vec()?)?;
+        assert_eq!(payload.id(), legacy.id());
+        assert!(payload.is_payment_secret_bip39_passphrase());
+
+        let payload_in = PrvKeyDataPayload::try_new_with_mnemonic_and_bip39_passphrase(mnemonic, &Secret::from("passphrase"))?;
+        let payload_out = PrvKeyDataPayload::try_from_slice(&payload_in.try_to_vec()?)?;
+        assert_eq!(payload_out.bip39_passphrase.as_deref(), Some("passphrase"));
+        assert!(payload_out.has_bip39_passphrase());
+        assert!(!payload_out.is_payment_secret_bip39_passphrase());
+        assert_ne!(payload_out.id(), legacy.id());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bip39_passphrase_reference_vector() -> Result<()> {
+        // BIP39 reference vector (https://github.com/trezor/python-mnemonic/blob/master/vectors.json)
+        let mnemonic = Mnemonic::new(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            Language::English,
+        )?;
+        let xprv = "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF";
+
+        let payment_secret = Secret::from("payment");
+        let prv_key_data = PrvKeyData::try_new_from_mnemonic_with_bip39_passphrase(
+            mnemonic.clone(),
+            Some(&Secret::from("TREZOR")),
+            Some(&payment_secret),
+            EncryptionKind::XChaCha20Poly1305,
+        )?;
+        // the payment secret encrypts the key data without altering the seed
+        assert!(prv_key_data.get_xprv(None).is_err());
+        assert_eq!(prv_key_data.get_xprv(Some(&payment_secret))?.to_string(kaspa_bip32::Prefix::XPRV).as_str(), xprv);
+
+        // without a BIP39 passphrase, the payment secret is used as the passphrase
+        let legacy = PrvKeyData::try_new_from_mnemonic(mnemonic, Some(&Secret::from("TREZOR")), EncryptionKind::XChaCha20Poly1305)?;
+        assert_eq!(legacy.get_xprv(Some(&Secret::from("TREZOR")))?.to_string(kaspa_bip32::Prefix::XPRV).as_str(), xprv);
+        assert_ne!(legacy.id, prv_key_data.id);
+
+        Ok(())
+    }
 }
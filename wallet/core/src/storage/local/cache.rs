@@ -8,6 +8,7 @@ use crate::secret::Secret;
 use crate::storage::local::wallet::WalletStorage;
 use crate::storage::local::*;
 use std::collections::HashMap;
+use workflow_core::time::unixtime_as_millis_u64;
 
 pub struct Cache {
     pub wallet_title: Option<String>,
@@ -18,6 +19,7 @@ pub struct Cache {
     pub accounts: Collection<AccountId, AccountStorage>,
     pub metadata: Collection<AccountId, AccountMetadata>,
     pub address_book: Vec<AddressBookEntry>,
+    pub created: Option<u64>,
 }
 
 impl Cache {
@@ -36,8 +38,19 @@ impl Cache {
         let user_hint = wallet.user_hint;
         let wallet_title = wallet.title;
         let address_book = payload.0.address_book.into_iter().collect();
+        let created = wallet.created;
 
-        Ok(Cache { wallet_title, user_hint, encryption_kind, prv_key_data, prv_key_data_info, accounts, metadata, address_book })
+        Ok(Cache {
+            wallet_title,
+            user_hint,
+            encryption_kind,
+            prv_key_data,
+            prv_key_data_info,
+            accounts,
+            metadata,
+            address_book,
+            created,
+        })
     }
 
     pub fn from_payload(
@@ -55,8 +68,19 @@ impl Cache {
         let accounts: Collection<AccountId, AccountStorage> = payload.accounts.try_into()?;
         let metadata: Collection<AccountId, AccountMetadata> = Collection::default();
         let address_book = payload.address_book.into_iter().collect();
+        let created = Some(unixtime_as_millis_u64());
 
-        Ok(Cache { wallet_title, user_hint, encryption_kind, prv_key_data, prv_key_data_info, accounts, metadata, address_book })
+        Ok(Cache {
+            wallet_title,
+            user_hint,
+            encryption_kind,
+            prv_key_data,
+            prv_key_data_info,
+            accounts,
+            metadata,
+            address_book,
+            created,
+        })
     }
 
     pub fn to_wallet(
@@ -79,6 +103,8 @@ impl Cache {
             user_hint: self.user_hint.clone(),
             title: self.wallet_title.clone(),
             transactions,
+            created: self.created,
+            account_count: Some(self.accounts.len() as u32),
         })
     }
 }
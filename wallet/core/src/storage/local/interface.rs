@@ -8,7 +8,7 @@ use crate::imports::*;
 use crate::result::Result;
 use crate::secret::Secret;
 use crate::storage::interface::{
-    AddressBookStore, CreateArgs, OpenArgs, StorageDescriptor, StorageStream, WalletDescriptor, WalletExportOptions,
+    AddressBookStore, CreateArgs, OpenArgs, StorageDescriptor, StorageStream, WalletDescriptor, WalletExportOptions, WalletInfo,
 };
 use crate::storage::local::cache::*;
 use crate::storage::local::streams::*;
@@ -308,6 +308,64 @@ impl LocalStore {
         self.location.lock().unwrap().clone()
     }
 
+    /// Creates a store using the given folder instead of the default storage folder.
+    #[cfg(test)]
+    pub fn try_new_with_folder(is_resident: bool, folder: &str) -> Result<Self> {
+        Ok(Self {
+            location: Arc::new(Mutex::new(Some(Arc::new(Location::new(folder))))),
+            inner: Arc::new(Mutex::new(None)),
+            is_resident,
+            batch: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Returns the wallet file storage and the transaction data folder of the given
+    /// (closed) wallet, rejecting resident stores and the currently open wallet.
+    fn closed_wallet_storage(&self, filename: &str) -> Result<(Storage, PathBuf)> {
+        if self.is_resident {
+            return Err(Error::ResidentWallet);
+        }
+
+        let filename = make_filename(&None, &Some(filename.to_string()));
+        if self.descriptor().is_some_and(|descriptor| descriptor.filename == filename) {
+            return Err(Error::WalletIsOpen(filename));
+        }
+
+        let location = self.location().expect("initialized wallet storage location");
+        let storage = Storage::try_new_with_folder(&location.folder, &format!("{filename}.wallet"))?;
+        let transactions = fs::resolve_path(&location.folder)?.join(format!("{filename}.transactions"));
+        Ok((storage, transactions))
+    }
+
+    /// Reads all wallet files in the storage folder, returning the wallet filename
+    /// (without the extension) and the wallet storage if it could be deserialized.
+    /// The wallet payload is not decrypted.
+    async fn wallet_storage_list(&self) -> Result<Vec<(String, Option<WalletStorage>)>> {
+        let location = self.location().expect("initialized wallet storage location");
+
+        let folder = fs::resolve_path(&location.folder)?;
+        let files = fs::readdir(folder.clone(), false).await?;
+        let mut wallets = files
+            .iter()
+            .filter_map(|de| {
+                let file_name = de.file_name();
+                file_name.ends_with(".wallet").then(|| file_name.trim_end_matches(".wallet").to_string())
+            })
+            .collect::<Vec<_>>();
+        wallets.sort();
+
+        let mut list = vec![];
+        for filename in wallets.into_iter() {
+            let path = folder.join(format!("{}.wallet", filename));
+            // TODO - refactor on native to read directly from file (skip temporary buffer creation)
+            let wallet_data = fs::read(&path).await;
+            let wallet = wallet_data.ok().and_then(|data| WalletStorage::try_from_slice(data.as_slice()).ok());
+            list.push((filename, wallet));
+        }
+
+        Ok(list)
+    }
+
     #[allow(dead_code)]
     async fn wallet_export_impl(&self, wallet_secret: &Secret, _options: WalletExportOptions) -> Result<Vec<u8>> {
         self.inner()?.try_export(wallet_secret, _options).await
@@ -361,6 +419,46 @@ impl Interface for LocalStore {
         Ok(())
     }
 
+    async fn wallet_rename(&self, filename: &str, new_filename: &str) -> Result<()> {
+        let (storage, transactions) = self.closed_wallet_storage(filename)?;
+        let (target, target_transactions) = self.closed_wallet_storage(new_filename)?;
+
+        if !storage.exists().await? {
+            return Err(Error::NoWalletInStorage(filename.to_string()));
+        }
+        if target.exists().await? {
+            return Err(Error::WalletAlreadyExists);
+        }
+
+        fs::rename(storage.filename(), target.filename()).await?;
+        // web transaction records are kept in IndexedDB and are not relocated
+        if !is_web() && fs::exists(&transactions).await? {
+            fs::rename(&transactions, &target_transactions).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn wallet_delete(&self, wallet_secret: &Secret, filename: &str) -> Result<()> {
+        let (storage, transactions) = self.closed_wallet_storage(filename)?;
+
+        // decrypting the payload confirms the wallet secret
+        let wallet = WalletStorage::try_load(&storage).await?;
+        let payload = wallet.payload(wallet_secret)?;
+
+        storage.purge().await?;
+        if is_web() {
+            // web transaction records are kept in the IndexedDB databases of the wallet accounts
+            let bindings = payload.accounts.iter().map(|account| Binding::Account(account.id)).collect::<Vec<_>>();
+            indexdb::TransactionStore::new(filename).delete_databases(&bindings).await?;
+        } else if fs::exists(&transactions).await? {
+            #[cfg(not(target_arch = "wasm32"))]
+            std::fs::remove_dir_all(&transactions)?;
+        }
+
+        Ok(())
+    }
+
     /// change the secret of the currently open wallet
     async fn change_secret(&self, old_wallet_secret: &Secret, new_wallet_secret: &Secret) -> Result<()> {
         let inner = self.inner.lock().unwrap().clone().ok_or(Error::WalletNotOpen)?;
@@ -368,6 +466,12 @@ impl Interface for LocalStore {
         Ok(())
     }
 
+    async fn verify_secret(&self, wallet_secret: &Secret) -> Result<()> {
+        // decrypting the private key data collection confirms the wallet secret
+        let _: Decrypted<PrvKeyDataMap> = self.inner()?.cache.read().unwrap().prv_key_data.decrypt(wallet_secret)?;
+        Ok(())
+    }
+
     async fn exists(&self, name: Option<&str>) -> Result<bool> {
         let location = self.location.lock().unwrap().clone().unwrap();
         let store =
@@ -399,29 +503,27 @@ impl Interface for LocalStore {
     }
 
     async fn wallet_list(&self) -> Result<Vec<WalletDescriptor>> {
-        let location = self.location.lock().unwrap().clone().unwrap();
+        Ok(self
+            .wallet_storage_list()
+            .await?
+            .into_iter()
+            .map(|(filename, wallet)| WalletDescriptor::new(wallet.and_then(|wallet| wallet.title), filename))
+            .collect())
+    }
 
-        let folder = fs::resolve_path(&location.folder)?;
-        let files = fs::readdir(folder.clone(), false).await?;
-        let wallets = files
-            .iter()
-            .filter_map(|de| {
-                let file_name = de.file_name();
-                file_name.ends_with(".wallet").then(|| file_name.trim_end_matches(".wallet").to_string())
+    async fn wallet_info_list(&self) -> Result<Vec<WalletInfo>> {
+        let wallets = self.wallet_storage_list().await?;
+        Ok(wallets
+            .into_iter()
+            .map(|(filename, wallet)| match wallet {
+                Some(wallet) => WalletInfo {
+                    descriptor: WalletDescriptor::new(wallet.title, filename),
+                    created: wallet.created,
+                    account_count: wallet.account_count,
+                },
+                None => WalletInfo { descriptor: WalletDescriptor::new(None, filename), created: None, account_count: None },
             })
-            .collect::<Vec<_>>();
-
-        let mut descriptors = vec![];
-        for filename in wallets.into_iter() {
-            let path = folder.join(format!("{}.wallet", filename));
-            // TODO - refactor on native to read directly from file (skip temporary buffer creation)
-            let wallet_data = fs::read(&path).await;
-            let title =
-                wallet_data.ok().and_then(|data| WalletStorage::try_from_slice(data.as_slice()).ok()).and_then(|wallet| wallet.title);
-            descriptors.push(WalletDescriptor { title, filename });
-        }
-
-        Ok(descriptors)
+            .collect())
     }
 
     fn is_open(&self) -> bool {
@@ -619,4 +721,140 @@ impl AddressBookStore for LocalStoreInner {
 
         Ok(matches)
     }
+
+    async fn load(&self, alias: &str) -> Result<Option<Arc<AddressBookEntry>>> {
+        Ok(self.cache.read().unwrap().address_book.iter().find(|entry| entry.matches(alias)).cloned().map(Arc::new))
+    }
+
+    async fn store(&self, entry: AddressBookEntry) -> Result<()> {
+        AddressBookEntry::validate_alias(&entry.alias)?;
+        let mut cache = self.cache.write().unwrap();
+        if cache.address_book.iter().any(|existing| existing.matches(&entry.alias)) {
+            return Err(Error::AddressBookAliasExists(entry.alias));
+        }
+        cache.address_book.push(entry);
+        self.set_modified(true);
+        Ok(())
+    }
+
+    async fn remove(&self, alias: &str) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        let index = cache
+            .address_book
+            .iter()
+            .position(|entry| entry.matches(alias))
+            .ok_or_else(|| Error::AddressBookAliasNotFound(alias.to_string()))?;
+        cache.address_book.remove(index);
+        self.set_modified(true);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestStore {
+        store: LocalStore,
+        _folder: tempfile::TempDir,
+    }
+
+    impl TestStore {
+        async fn try_new(wallets: &[&str], wallet_secret: &Secret) -> Result<Self> {
+            let folder = tempfile::tempdir()?;
+            let store = LocalStore::try_new_with_folder(false, folder.path().to_str().unwrap())?;
+            for filename in wallets {
+                let args = CreateArgs::new(None, Some(filename.to_string()), EncryptionKind::XChaCha20Poly1305, None, false);
+                store.create(wallet_secret, args).await?;
+                store.commit(wallet_secret).await?;
+                store.close().await?;
+            }
+            Ok(Self { store, _folder: folder })
+        }
+
+        async fn filenames(&self) -> Result<Vec<String>> {
+            Ok(self.store.wallet_list().await?.into_iter().map(|descriptor| descriptor.filename).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_storage_wallet_rename() -> Result<()> {
+        let wallet_secret = Secret::from("secret");
+        let test = TestStore::try_new(&["alpha", "beta"], &wallet_secret).await?;
+
+        assert!(matches!(test.store.wallet_rename("alpha", "beta").await, Err(Error::WalletAlreadyExists)));
+        assert!(matches!(test.store.wallet_rename("gamma", "delta").await, Err(Error::NoWalletInStorage(_))));
+        assert_eq!(test.filenames().await?, vec!["alpha", "beta"]);
+
+        test.store.wallet_rename("alpha", "gamma").await?;
+        assert_eq!(test.filenames().await?, vec!["beta", "gamma"]);
+        test.store.open(&wallet_secret, OpenArgs::new(Some("gamma".to_string()))).await?;
+        test.store.close().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_storage_wallet_delete() -> Result<()> {
+        let wallet_secret = Secret::from("secret");
+        let test = TestStore::try_new(&["alpha", "beta"], &wallet_secret).await?;
+
+        test.store.open(&wallet_secret, OpenArgs::new(Some("alpha".to_string()))).await?;
+        assert!(matches!(test.store.wallet_delete(&wallet_secret, "alpha").await, Err(Error::WalletIsOpen(_))));
+        assert!(matches!(test.store.wallet_rename("alpha", "gamma").await, Err(Error::WalletIsOpen(_))));
+        test.store.close().await?;
+
+        assert!(test.store.wallet_delete(&Secret::from("wrong"), "alpha").await.is_err());
+        assert_eq!(test.filenames().await?, vec!["alpha", "beta"]);
+
+        test.store.wallet_delete(&wallet_secret, "alpha").await?;
+        assert_eq!(test.filenames().await?, vec!["beta"]);
+        assert!(matches!(test.store.wallet_delete(&wallet_secret, "alpha").await, Err(Error::NoWalletInStorage(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_storage_address_book() -> Result<()> {
+        let wallet_secret = Secret::from("secret");
+        let test = TestStore::try_new(&["alpha"], &wallet_secret).await?;
+        let address = Address::try_from("kaspatest:qz7ulu4c25dh7fzec9zjyrmlhnkzrg4wmf89q7gzr3gfrsj3uz6xjceef60sd")?;
+        let other = Address::try_from("kaspatest:qzn3qjzf2nzyd3zj303nk4sgv0aae42v3ufutk5xsxckfels57dxjnltw0jwz")?;
+
+        test.store.open(&wallet_secret, OpenArgs::new(Some("alpha".to_string()))).await?;
+        let book = test.store.as_address_book_store()?;
+        book.store(AddressBookEntry::try_new("alice", "Alice", address.clone())?).await?;
+
+        assert!(matches!(
+            book.store(AddressBookEntry::try_new("Alice", "", other.clone())?).await,
+            Err(Error::AddressBookAliasExists(_))
+        ));
+        for alias in ["", "bob smith", "kaspatest:bob", other.to_string().as_str()] {
+            assert!(matches!(AddressBookEntry::try_new(alias, "", other.clone()), Err(Error::InvalidAddressBookAlias(_))));
+        }
+
+        // Recipients resolve to addresses given directly or through a case-insensitive alias
+        let testnet = NetworkId::with_suffix(NetworkType::Testnet, 10);
+        assert_eq!(book.resolve("ALICE", &testnet).await?, address);
+        assert_eq!(book.resolve(&other.to_string(), &testnet).await?, other);
+        assert!(matches!(book.resolve("bob", &testnet).await, Err(Error::UnknownRecipient(_))));
+
+        // An alias of another network does not resolve
+        let mainnet = NetworkId::new(NetworkType::Mainnet);
+        assert!(matches!(book.resolve("alice", &mainnet).await, Err(Error::AddressBookNetworkMismatch(_, _))));
+
+        test.store.commit(&wallet_secret).await?;
+        test.store.close().await?;
+
+        test.store.open(&wallet_secret, OpenArgs::new(Some("alpha".to_string()))).await?;
+        let book = test.store.as_address_book_store()?;
+        assert_eq!(book.load("alice").await?.map(|entry| entry.title.clone()), Some("Alice".to_string()));
+        book.remove("alice").await?;
+        assert!(matches!(book.remove("alice").await, Err(Error::AddressBookAliasNotFound(_))));
+        assert!(matches!(book.resolve("alice", &testnet).await, Err(Error::UnknownRecipient(_))));
+        test.store.commit(&wallet_secret).await?;
+        test.store.close().await?;
+
+        Ok(())
+    }
 }
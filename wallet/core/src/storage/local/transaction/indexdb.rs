@@ -115,6 +115,24 @@ impl TransactionStore {
         Ok(())
     }
 
+    /// Deletes the transaction record databases of the given bindings on all networks.
+    pub async fn delete_databases(&self, bindings: &[Binding]) -> Result<()> {
+        let db_names = bindings
+            .iter()
+            .flat_map(|binding| NetworkId::iter().map(|network_id| self.make_db_name(&binding.to_hex(), &network_id.to_string())))
+            .collect::<Vec<_>>();
+
+        call_async_no_send!(async move {
+            for db_name in db_names {
+                IdbDatabase::delete_by_name(&db_name)
+                    .map_err(|err| Error::Custom(format!("Failed to delete indexdb database {:?}", err)))?
+                    .await
+                    .map_err(|err| Error::Custom(format!("Delete database request failed for indexdb database {:?}", err)))?;
+            }
+            Ok(())
+        })
+    }
+
     #[allow(dead_code)]
     async fn ensure_database(&self, binding: &Binding, network_id: &NetworkId) -> Result<()> {
         let binding_hex = binding.to_hex();
@@ -10,6 +10,7 @@ use crate::storage::local::Storage;
 use crate::storage::Encryptable;
 use crate::storage::TransactionRecord;
 use crate::storage::{AccountMetadata, Decrypted, Encrypted, Hint, PrvKeyData, PrvKeyDataId};
+use workflow_core::time::unixtime_as_millis_u64;
 use workflow_store::fs;
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -23,11 +24,17 @@ pub struct WalletStorage {
     pub metadata: Vec<AccountMetadata>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transactions: Option<Encryptable<HashMap<AccountId, Vec<TransactionRecord>>>>,
+    /// wallet creation time (unix time in milliseconds), available since storage version 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<u64>,
+    /// number of accounts in the encrypted payload, available since storage version 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_count: Option<u32>,
 }
 
 impl WalletStorage {
     pub const STORAGE_MAGIC: u32 = 0x5753414b;
-    pub const STORAGE_VERSION: u32 = 0;
+    pub const STORAGE_VERSION: u32 = 1;
 
     pub fn try_new(
         title: Option<String>,
@@ -37,8 +44,18 @@ impl WalletStorage {
         payload: Payload,
         metadata: Vec<AccountMetadata>,
     ) -> Result<Self> {
+        let account_count = Some(payload.accounts.len() as u32);
         let payload = Decrypted::new(payload).encrypt(secret, encryption_kind)?;
-        Ok(Self { title, encryption_kind, payload, metadata, user_hint, transactions: None })
+        Ok(Self {
+            title,
+            encryption_kind,
+            payload,
+            metadata,
+            user_hint,
+            transactions: None,
+            created: Some(unixtime_as_millis_u64()),
+            account_count,
+        })
     }
 
     pub fn payload(&self, secret: &Secret) -> Result<Decrypted<Payload>> {
@@ -94,6 +111,8 @@ impl BorshSerialize for WalletStorage {
         BorshSerialize::serialize(&self.payload, writer)?;
         BorshSerialize::serialize(&self.metadata, writer)?;
         BorshSerialize::serialize(&self.transactions, writer)?;
+        BorshSerialize::serialize(&self.created, writer)?;
+        BorshSerialize::serialize(&self.account_count, writer)?;
 
         Ok(())
     }
@@ -123,8 +142,10 @@ impl BorshDeserialize for WalletStorage {
         let payload = BorshDeserialize::deserialize(buf)?;
         let metadata = BorshDeserialize::deserialize(buf)?;
         let transactions = BorshDeserialize::deserialize(buf)?;
+        let (created, account_count) =
+            if version > 0 { (BorshDeserialize::deserialize(buf)?, BorshDeserialize::deserialize(buf)?) } else { (None, None) };
 
-        Ok(Self { title, user_hint, encryption_kind, payload, metadata, transactions })
+        Ok(Self { title, user_hint, encryption_kind, payload, metadata, transactions, created, account_count })
     }
 }
 
@@ -148,4 +169,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_storage_wallet_storage_v0() -> Result<()> {
+        let storable = WalletStorage::try_new(
+            Some("title".to_string()),
+            None,
+            &Secret::from("secret"),
+            EncryptionKind::XChaCha20Poly1305,
+            Payload::new(vec![], vec![], vec![]),
+            vec![],
+        )?;
+        assert_eq!(storable.account_count, Some(0));
+
+        // version 0 data lacks the trailing `created` (9 bytes) and `account_count` (5 bytes) fields
+        let mut data = storable.try_to_vec()?;
+        data.truncate(data.len() - 14);
+        data[4..8].copy_from_slice(&0u32.to_le_bytes());
+
+        let storable_v0 = WalletStorage::try_from_slice(&data)?;
+        assert_eq!(storable_v0.title.as_deref(), Some("title"));
+        assert_eq!(storable_v0.created, None);
+        assert_eq!(storable_v0.account_count, None);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,185 @@
+//!
+//! Categorization of wallet transaction records.
+//!
+
+use super::*;
+use crate::imports::*;
+use kaspa_consensus_core::tx::Transaction;
+use kaspa_txscript::extract_script_pub_key_address;
+
+/// User-facing category of a [`TransactionRecord`], derived from
+/// the wallet addresses appearing in its inputs and outputs.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TransactionCategory {
+    /// Funds received from addresses not belonging to the wallet.
+    Incoming,
+    /// Funds sent to at least one address not belonging to the wallet.
+    Outgoing,
+    /// Funds moved between wallet addresses only (for example a
+    /// transaction consisting of change or a sweep).
+    SelfTransfer,
+    /// Coinbase (mining reward) funds received by the wallet.
+    Coinbase,
+}
+
+impl std::fmt::Display for TransactionCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            TransactionCategory::Incoming => "incoming",
+            TransactionCategory::Outgoing => "outgoing",
+            TransactionCategory::SelfTransfer => "self-transfer",
+            TransactionCategory::Coinbase => "coinbase",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl TransactionRecord {
+    /// Categorizes the transaction given a predicate matching the wallet addresses.
+    /// Returns `None` for [`Reorg`](TransactionKind::Reorg) and [`Stasis`](TransactionKind::Stasis)
+    /// records which are not meant to be presented to the user.
+    pub fn category<F>(&self, is_wallet_address: F) -> Option<TransactionCategory>
+    where
+        F: Fn(&Address) -> bool,
+    {
+        let owns = |utxo_entries: &[UtxoRecord]| utxo_entries.iter().filter_map(|utxo| utxo.address.as_ref()).any(&is_wallet_address);
+
+        match &self.transaction_data {
+            TransactionData::Reorg { .. } | TransactionData::Stasis { .. } => None,
+            TransactionData::Incoming { utxo_entries, .. } => {
+                if utxo_entries.iter().any(|utxo| utxo.is_coinbase) {
+                    Some(TransactionCategory::Coinbase)
+                } else {
+                    Some(TransactionCategory::Incoming)
+                }
+            }
+            // UTXOs of an external transaction are wallet UTXOs spent by another wallet instance
+            TransactionData::External { .. } => Some(TransactionCategory::Outgoing),
+            TransactionData::Outgoing { transaction, utxo_entries, .. }
+            | TransactionData::Batch { transaction, utxo_entries, .. }
+            | TransactionData::TransferOutgoing { transaction, utxo_entries, .. } => {
+                Some(self.categorize_spending(transaction, owns(utxo_entries), &is_wallet_address))
+            }
+            // change is only produced by transactions spending wallet UTXOs
+            TransactionData::Change { transaction, .. } => Some(self.categorize_spending(transaction, true, &is_wallet_address)),
+            // UTXOs of an incoming transfer are outputs, the inputs belonging to another account
+            TransactionData::TransferIncoming { transaction, .. } => {
+                Some(self.categorize_spending(transaction, false, &is_wallet_address))
+            }
+        }
+    }
+
+    fn categorize_spending<F>(
+        &self,
+        transaction: &Transaction,
+        spends_wallet_utxos: bool,
+        is_wallet_address: &F,
+    ) -> TransactionCategory
+    where
+        F: Fn(&Address) -> bool,
+    {
+        if !spends_wallet_utxos {
+            return TransactionCategory::Incoming;
+        }
+
+        let prefix = Prefix::from(self.network_id);
+        let pays_foreign_address = transaction.outputs.iter().any(|output| {
+            extract_script_pub_key_address(&output.script_public_key, prefix)
+                .map(|address| !is_wallet_address(&address))
+                .unwrap_or(true)
+        });
+
+        if pays_foreign_address {
+            TransactionCategory::Outgoing
+        } else {
+            TransactionCategory::SelfTransfer
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{address, record, utxo};
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use kaspa_consensus_core::tx::{TransactionInput, TransactionOutpoint, TransactionOutput};
+    use kaspa_txscript::pay_to_address_script;
+
+    fn transaction(outputs: &[(u8, u64)]) -> Transaction {
+        let inputs = vec![TransactionInput::new(TransactionOutpoint::new(TransactionId::default(), 0), vec![], 0, 1)];
+        let outputs = outputs.iter().map(|(n, value)| TransactionOutput::new(*value, pay_to_address_script(&address(*n)))).collect();
+        Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![])
+    }
+
+    fn outgoing(utxo_entries: Vec<UtxoRecord>, outputs: &[(u8, u64)]) -> TransactionRecord {
+        record(TransactionData::Outgoing {
+            fees: 0,
+            aggregate_input_value: utxo_entries.iter().map(|utxo| utxo.amount).sum(),
+            aggregate_output_value: outputs.iter().map(|(_, value)| value).sum(),
+            transaction: transaction(outputs),
+            payment_value: None,
+            change_value: 0,
+            accepted_daa_score: None,
+            utxo_entries,
+        })
+    }
+
+    /// Addresses 1 to 9 belong to the wallet
+    fn is_wallet_address(address: &Address) -> bool {
+        (1..10).any(|n| self::address(n) == *address)
+    }
+
+    #[test]
+    fn test_transaction_category() {
+        let incoming = record(TransactionData::Incoming { utxo_entries: vec![utxo(1, 100, false)], aggregate_input_value: 100 });
+        assert_eq!(incoming.category(is_wallet_address), Some(TransactionCategory::Incoming));
+
+        let coinbase = record(TransactionData::Incoming { utxo_entries: vec![utxo(1, 500, true)], aggregate_input_value: 500 });
+        assert_eq!(coinbase.category(is_wallet_address), Some(TransactionCategory::Coinbase));
+
+        let payment = outgoing(vec![utxo(1, 300, false)], &[(20, 200), (2, 90)]);
+        assert_eq!(payment.category(is_wallet_address), Some(TransactionCategory::Outgoing));
+
+        let self_transfer = outgoing(vec![utxo(1, 300, false), utxo(3, 100, false)], &[(2, 390)]);
+        assert_eq!(self_transfer.category(is_wallet_address), Some(TransactionCategory::SelfTransfer));
+
+        // A transaction spending UTXOs of foreign addresses only is not sent by the wallet
+        let foreign = outgoing(vec![utxo(20, 300, false)], &[(2, 290)]);
+        assert_eq!(foreign.category(is_wallet_address), Some(TransactionCategory::Incoming));
+
+        let external = record(TransactionData::External { utxo_entries: vec![utxo(1, 100, false)], aggregate_input_value: 100 });
+        assert_eq!(external.category(is_wallet_address), Some(TransactionCategory::Outgoing));
+
+        let reorg = record(TransactionData::Reorg { utxo_entries: vec![utxo(1, 100, false)], aggregate_input_value: 100 });
+        assert_eq!(reorg.category(is_wallet_address), None);
+    }
+
+    #[test]
+    fn test_transfer_and_change_category() {
+        let transaction = transaction(&[(20, 200), (2, 90)]);
+        let change = record(TransactionData::Change {
+            aggregate_input_value: 300,
+            aggregate_output_value: 290,
+            transaction: transaction.clone(),
+            payment_value: Some(200),
+            change_value: 90,
+            accepted_daa_score: None,
+            utxo_entries: vec![utxo(2, 90, false)],
+        });
+        assert_eq!(change.category(is_wallet_address), Some(TransactionCategory::Outgoing));
+
+        // A transfer to another account appears as incoming from the point of view of the receiving account
+        let transfer = record(TransactionData::TransferIncoming {
+            fees: 10,
+            aggregate_input_value: 300,
+            aggregate_output_value: 290,
+            transaction,
+            payment_value: Some(200),
+            change_value: 90,
+            accepted_daa_score: None,
+            utxo_entries: vec![utxo(20, 200, false)],
+        });
+        assert_eq!(transfer.category(|address| *address == self::address(20)), Some(TransactionCategory::Incoming));
+    }
+}
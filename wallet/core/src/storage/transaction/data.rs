@@ -123,7 +123,7 @@ pub enum TransactionData {
 
 impl TransactionData {
     const STORAGE_MAGIC: u32 = 0x54445854;
-    const STORAGE_VERSION: u32 = 0;
+    const STORAGE_VERSION: u32 = 1;
 
     pub fn kind(&self) -> TransactionKind {
         match self {
@@ -138,6 +138,15 @@ impl TransactionData {
             TransactionData::Change { .. } => TransactionKind::Change,
         }
     }
+
+    // version 0 records were stored before the transaction mass became part of the borsh encoding
+    fn deserialize_transaction(version: u32, buf: &mut &[u8]) -> IoResult<Transaction> {
+        if version == 0 {
+            Transaction::deserialize_borsh_without_mass(buf)
+        } else {
+            BorshDeserialize::deserialize(buf)
+        }
+    }
 }
 
 impl BorshSerialize for TransactionData {
@@ -265,7 +274,7 @@ impl BorshSerialize for TransactionData {
 
 impl BorshDeserialize for TransactionData {
     fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
-        let StorageHeader { version: _, .. } =
+        let StorageHeader { version, .. } =
             StorageHeader::deserialize(buf)?.try_magic(Self::STORAGE_MAGIC)?.try_version(Self::STORAGE_VERSION)?;
 
         let kind: TransactionKind = BorshDeserialize::deserialize(buf)?;
@@ -295,7 +304,7 @@ impl BorshDeserialize for TransactionData {
                 let fees: u64 = BorshDeserialize::deserialize(buf)?;
                 let aggregate_input_value: u64 = BorshDeserialize::deserialize(buf)?;
                 let aggregate_output_value: u64 = BorshDeserialize::deserialize(buf)?;
-                let transaction: Transaction = BorshDeserialize::deserialize(buf)?;
+                let transaction = Self::deserialize_transaction(version, buf)?;
                 let payment_value: Option<u64> = BorshDeserialize::deserialize(buf)?;
                 let change_value: u64 = BorshDeserialize::deserialize(buf)?;
                 let accepted_daa_score: Option<u64> = BorshDeserialize::deserialize(buf)?;
@@ -315,7 +324,7 @@ impl BorshDeserialize for TransactionData {
                 let fees: u64 = BorshDeserialize::deserialize(buf)?;
                 let aggregate_input_value: u64 = BorshDeserialize::deserialize(buf)?;
                 let aggregate_output_value: u64 = BorshDeserialize::deserialize(buf)?;
-                let transaction: Transaction = BorshDeserialize::deserialize(buf)?;
+                let transaction = Self::deserialize_transaction(version, buf)?;
                 let payment_value: Option<u64> = BorshDeserialize::deserialize(buf)?;
                 let change_value: u64 = BorshDeserialize::deserialize(buf)?;
                 let accepted_daa_score: Option<u64> = BorshDeserialize::deserialize(buf)?;
@@ -335,7 +344,7 @@ impl BorshDeserialize for TransactionData {
                 let fees: u64 = BorshDeserialize::deserialize(buf)?;
                 let aggregate_input_value: u64 = BorshDeserialize::deserialize(buf)?;
                 let aggregate_output_value: u64 = BorshDeserialize::deserialize(buf)?;
-                let transaction: Transaction = BorshDeserialize::deserialize(buf)?;
+                let transaction = Self::deserialize_transaction(version, buf)?;
                 let payment_value: Option<u64> = BorshDeserialize::deserialize(buf)?;
                 let change_value: u64 = BorshDeserialize::deserialize(buf)?;
                 let accepted_daa_score: Option<u64> = BorshDeserialize::deserialize(buf)?;
@@ -355,7 +364,7 @@ impl BorshDeserialize for TransactionData {
                 let fees: u64 = BorshDeserialize::deserialize(buf)?;
                 let aggregate_input_value: u64 = BorshDeserialize::deserialize(buf)?;
                 let aggregate_output_value: u64 = BorshDeserialize::deserialize(buf)?;
-                let transaction: Transaction = BorshDeserialize::deserialize(buf)?;
+                let transaction = Self::deserialize_transaction(version, buf)?;
                 let payment_value: Option<u64> = BorshDeserialize::deserialize(buf)?;
                 let change_value: u64 = BorshDeserialize::deserialize(buf)?;
                 let accepted_daa_score: Option<u64> = BorshDeserialize::deserialize(buf)?;
@@ -374,7 +383,7 @@ impl BorshDeserialize for TransactionData {
             TransactionKind::Change => {
                 let aggregate_input_value: u64 = BorshDeserialize::deserialize(buf)?;
                 let aggregate_output_value: u64 = BorshDeserialize::deserialize(buf)?;
-                let transaction: Transaction = BorshDeserialize::deserialize(buf)?;
+                let transaction = Self::deserialize_transaction(version, buf)?;
                 let payment_value: Option<u64> = BorshDeserialize::deserialize(buf)?;
                 let change_value: u64 = BorshDeserialize::deserialize(buf)?;
                 let accepted_daa_score: Option<u64> = BorshDeserialize::deserialize(buf)?;
@@ -2,11 +2,13 @@
 //! Wallet transaction records.
 //!
 
+pub mod category;
 pub mod data;
 pub mod kind;
 pub mod record;
 pub mod utxo;
 
+pub use category::*;
 pub use data::*;
 pub use kind::*;
 pub use record::*;
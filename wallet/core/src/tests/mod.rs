@@ -11,3 +11,6 @@ pub use keys::*;
 
 mod storage;
 pub use storage::*;
+
+mod records;
+pub use records::*;
@@ -0,0 +1,31 @@
+use crate::imports::*;
+use crate::storage::transaction::{TransactionData, TransactionRecord, UtxoRecord};
+use crate::utxo::UtxoContextId;
+use kaspa_addresses::Version;
+use kaspa_txscript::pay_to_address_script;
+
+/// Testnet address with a payload filled with `n`
+pub fn address(n: u8) -> Address {
+    Address::new(Prefix::Testnet, Version::PubKey, &[n; 32])
+}
+
+/// UTXO of `amount` paying to [`address(n)`](address)
+pub fn utxo(n: u8, amount: u64, is_coinbase: bool) -> UtxoRecord {
+    let address = address(n);
+    UtxoRecord { index: 0, amount, script_public_key: pay_to_address_script(&address), address: Some(address), is_coinbase }
+}
+
+/// Undated testnet record of `transaction_data`
+pub fn record(transaction_data: TransactionData) -> TransactionRecord {
+    TransactionRecord {
+        id: TransactionId::default(),
+        unixtime_msec: None,
+        value: 0,
+        binding: Binding::Custom(UtxoContextId::default()),
+        block_daa_score: 0,
+        network_id: NetworkId::with_suffix(NetworkType::Testnet, 11),
+        transaction_data,
+        note: None,
+        metadata: None,
+    }
+}
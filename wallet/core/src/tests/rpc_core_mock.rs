@@ -2,14 +2,14 @@ use crate::imports::*;
 
 use async_channel::{unbounded, Receiver};
 use async_trait::async_trait;
-use kaspa_notify::events::EVENT_TYPE_ARRAY;
+use kaspa_notify::events::{EventType, EVENT_TYPE_ARRAY};
 use kaspa_notify::listener::ListenerId;
 use kaspa_notify::notifier::{Notifier, Notify};
 use kaspa_notify::scope::Scope;
 use kaspa_rpc_core::api::ctl::RpcCtl;
 use kaspa_rpc_core::{api::rpc::RpcApi, *};
 use kaspa_rpc_core::{notify::connection::ChannelConnection, RpcResult};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 pub type RpcCoreNotifier = Notifier<Notification, ChannelConnection>;
 
@@ -22,6 +22,14 @@ impl From<Arc<RpcCoreMock>> for Rpc {
 pub struct RpcCoreMock {
     ctl: RpcCtl,
     core_notifier: Arc<RpcCoreNotifier>,
+    submitted_transactions: Mutex<Vec<RpcTransaction>>,
+    mempool_entries: Mutex<Vec<RpcMempoolEntry>>,
+    rejected_transactions: Mutex<HashMap<RpcTransactionId, String>>,
+    ping_delay: Mutex<Duration>,
+    fee_estimate: Mutex<RpcFeeEstimate>,
+    fee_estimate_requests: AtomicUsize,
+    utxos_by_addresses: Mutex<Vec<RpcUtxosByAddressesEntry>>,
+    utxos_by_addresses_requests: AtomicUsize,
     _sync_receiver: Receiver<()>,
 }
 
@@ -58,6 +66,51 @@ impl RpcCoreMock {
     pub fn ctl(&self) -> RpcCtl {
         self.ctl.clone()
     }
+
+    /// Transactions received via `submit_transaction_call()`, in submission order.
+    pub fn submitted_transactions(&self) -> Vec<RpcTransaction> {
+        self.submitted_transactions.lock().unwrap().clone()
+    }
+
+    /// Causes `submit_transaction_call()` to reject the given transaction with the given reason.
+    pub fn reject_transaction(&self, transaction_id: RpcTransactionId, reason: &str) {
+        self.rejected_transactions.lock().unwrap().insert(transaction_id, reason.to_string());
+    }
+
+    /// Delays the response of `ping_call()` by `delay`.
+    pub fn set_ping_delay(&self, delay: Duration) {
+        *self.ping_delay.lock().unwrap() = delay;
+    }
+
+    /// Sets the estimate returned by `get_fee_estimate_call()`.
+    pub fn set_fee_estimate(&self, estimate: RpcFeeEstimate) {
+        *self.fee_estimate.lock().unwrap() = estimate;
+    }
+
+    /// Number of calls to `get_fee_estimate_call()`.
+    pub fn fee_estimate_requests(&self) -> usize {
+        self.fee_estimate_requests.load(Ordering::SeqCst)
+    }
+
+    /// Adds a UTXO returned by `get_utxos_by_addresses_call()` when its address is requested.
+    pub fn add_utxo_by_address(&self, entry: RpcUtxosByAddressesEntry) {
+        self.utxos_by_addresses.lock().unwrap().push(entry);
+    }
+
+    /// Number of calls to `get_utxos_by_addresses_call()`.
+    pub fn utxos_by_addresses_requests(&self) -> usize {
+        self.utxos_by_addresses_requests.load(Ordering::SeqCst)
+    }
+
+    /// Adds an entry returned by the mempool query methods.
+    pub fn add_mempool_entry(&self, entry: RpcMempoolEntry) {
+        self.mempool_entries.lock().unwrap().push(entry);
+    }
+
+    fn mempool_entries(&self, include_orphan_pool: bool, filter_transaction_pool: bool) -> Vec<RpcMempoolEntry> {
+        let entries = self.mempool_entries.lock().unwrap();
+        entries.iter().filter(|entry| if entry.is_orphan { include_orphan_pool } else { !filter_transaction_pool }).cloned().collect()
+    }
 }
 
 impl Default for RpcCoreMock {
@@ -65,7 +118,23 @@ impl Default for RpcCoreMock {
         let (sync_sender, sync_receiver) = unbounded();
         let core_notifier: Arc<RpcCoreNotifier> =
             Arc::new(Notifier::with_sync("rpc-core", EVENT_TYPE_ARRAY[..].into(), vec![], vec![], 10, Some(sync_sender)));
-        Self { core_notifier, _sync_receiver: sync_receiver, ctl: RpcCtl::new() }
+        Self {
+            core_notifier,
+            submitted_transactions: Mutex::new(vec![]),
+            mempool_entries: Mutex::new(vec![]),
+            rejected_transactions: Mutex::new(HashMap::new()),
+            ping_delay: Mutex::new(Duration::ZERO),
+            fee_estimate: Mutex::new(RpcFeeEstimate::new(
+                RpcFeeRateBucket::new(1.0, 1.0),
+                RpcFeeRateBucket::new(1.0, 10.0),
+                RpcFeeRateBucket::new(1.0, 60.0),
+            )),
+            fee_estimate_requests: AtomicUsize::new(0),
+            utxos_by_addresses: Mutex::new(vec![]),
+            utxos_by_addresses_requests: AtomicUsize::new(0),
+            _sync_receiver: sync_receiver,
+            ctl: RpcCtl::new(),
+        }
     }
 }
 
@@ -85,7 +154,9 @@ impl RpcApi for RpcCoreMock {
     }
 
     async fn ping_call(&self, _request: PingRequest) -> RpcResult<PingResponse> {
-        Err(RpcError::NotImplemented)
+        let delay = *self.ping_delay.lock().unwrap();
+        sleep(delay).await;
+        Ok(PingResponse {})
     }
 
     async fn get_metrics_call(&self, _request: GetMetricsRequest) -> RpcResult<GetMetricsResponse> {
@@ -120,12 +191,17 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
-    async fn get_mempool_entry_call(&self, _request: GetMempoolEntryRequest) -> RpcResult<GetMempoolEntryResponse> {
-        Err(RpcError::NotImplemented)
+    async fn get_mempool_entry_call(&self, request: GetMempoolEntryRequest) -> RpcResult<GetMempoolEntryResponse> {
+        for entry in self.mempool_entries(request.include_orphan_pool, request.filter_transaction_pool) {
+            if kaspa_consensus_core::tx::Transaction::try_from(&entry.transaction)?.id() == request.transaction_id {
+                return Ok(GetMempoolEntryResponse::new(entry));
+            }
+        }
+        Err(RpcError::TransactionNotFound(request.transaction_id))
     }
 
-    async fn get_mempool_entries_call(&self, _request: GetMempoolEntriesRequest) -> RpcResult<GetMempoolEntriesResponse> {
-        Err(RpcError::NotImplemented)
+    async fn get_mempool_entries_call(&self, request: GetMempoolEntriesRequest) -> RpcResult<GetMempoolEntriesResponse> {
+        Ok(GetMempoolEntriesResponse::new(self.mempool_entries(request.include_orphan_pool, request.filter_transaction_pool)))
     }
 
     async fn get_connected_peer_info_call(&self, _request: GetConnectedPeerInfoRequest) -> RpcResult<GetConnectedPeerInfoResponse> {
@@ -136,8 +212,20 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
-    async fn submit_transaction_call(&self, _request: SubmitTransactionRequest) -> RpcResult<SubmitTransactionResponse> {
-        Err(RpcError::NotImplemented)
+    async fn submit_transaction_call(&self, request: SubmitTransactionRequest) -> RpcResult<SubmitTransactionResponse> {
+        let transaction_id = kaspa_consensus_core::tx::Transaction::try_from(&request.transaction)?.id();
+        if let Some(reason) = self.rejected_transactions.lock().unwrap().get(&transaction_id) {
+            return Err(RpcError::RejectedTransaction(transaction_id, reason.clone()));
+        }
+
+        let mut submitted_transactions = self.submitted_transactions.lock().unwrap();
+        for submitted in submitted_transactions.iter() {
+            if kaspa_consensus_core::tx::Transaction::try_from(submitted)?.id() == transaction_id {
+                return Ok(SubmitTransactionResponse::new_rejected(transaction_id, RpcSubmitTransactionError::Duplicate));
+            }
+        }
+        submitted_transactions.push(request.transaction);
+        Ok(SubmitTransactionResponse::new(transaction_id))
     }
 
     async fn get_block_call(&self, _request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
@@ -193,8 +281,17 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
-    async fn get_utxos_by_addresses_call(&self, _request: GetUtxosByAddressesRequest) -> RpcResult<GetUtxosByAddressesResponse> {
-        Err(RpcError::NotImplemented)
+    async fn get_utxos_by_addresses_call(&self, request: GetUtxosByAddressesRequest) -> RpcResult<GetUtxosByAddressesResponse> {
+        self.utxos_by_addresses_requests.fetch_add(1, Ordering::SeqCst);
+        let entries = self
+            .utxos_by_addresses
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.address.as_ref().is_some_and(|address| request.addresses.contains(address)))
+            .cloned()
+            .collect();
+        Ok(GetUtxosByAddressesResponse::new(entries))
     }
 
     async fn get_sink_blue_score_call(&self, _request: GetSinkBlueScoreRequest) -> RpcResult<GetSinkBlueScoreResponse> {
@@ -216,11 +313,27 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    // Entries are matched against transaction outputs only, `sending` entries
+    // would require the mock to resolve previous outpoints.
     async fn get_mempool_entries_by_addresses_call(
         &self,
-        _request: GetMempoolEntriesByAddressesRequest,
+        request: GetMempoolEntriesByAddressesRequest,
     ) -> RpcResult<GetMempoolEntriesByAddressesResponse> {
-        Err(RpcError::NotImplemented)
+        let entries = self.mempool_entries(request.include_orphan_pool, request.filter_transaction_pool);
+        let entries = request
+            .addresses
+            .into_iter()
+            .map(|address| {
+                let script_public_key = kaspa_txscript::pay_to_address_script(&address);
+                let receiving = entries
+                    .iter()
+                    .filter(|entry| entry.transaction.outputs.iter().any(|output| output.script_public_key == script_public_key))
+                    .cloned()
+                    .collect();
+                RpcMempoolEntryByAddress::new(address, vec![], receiving)
+            })
+            .collect();
+        Ok(GetMempoolEntriesByAddressesResponse::new(entries))
     }
 
     async fn get_coin_supply_call(&self, _request: GetCoinSupplyRequest) -> RpcResult<GetCoinSupplyResponse> {
@@ -234,6 +347,19 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_banned_peers_call(&self, _request: GetBannedPeersRequest) -> RpcResult<GetBannedPeersResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_fee_estimate_call(&self, _request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        self.fee_estimate_requests.fetch_add(1, Ordering::SeqCst);
+        Ok(GetFeeEstimateResponse::new(*self.fee_estimate.lock().unwrap()))
+    }
+
+    async fn set_log_level_call(&self, _request: SetLogLevelRequest) -> RpcResult<SetLogLevelResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -255,4 +381,14 @@ impl RpcApi for RpcCoreMock {
         self.core_notifier.try_stop_notify(id, scope)?;
         Ok(())
     }
+
+    async fn mute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()> {
+        self.core_notifier.try_mute(id, event)?;
+        Ok(())
+    }
+
+    async fn unmute_notify(&self, id: ListenerId, event: EventType) -> RpcResult<()> {
+        self.core_notifier.try_unmute(id, event)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,159 @@
+//!
+//! Submission of transaction chains with per-transaction outcomes.
+//!
+
+use crate::imports::*;
+use crate::tx::UnsignedTransaction;
+use kaspa_rpc_core::RpcError;
+
+/// Mempool rejection reasons denoting a transaction already known to the node.
+const ALREADY_KNOWN: &[&str] = &["already in the mempool", "already in the orphan pool", "already accepted by the consensus"];
+
+/// Outcome of a single transaction submission.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status", content = "reason")]
+pub enum BroadcastStatus {
+    /// The transaction was accepted by the node.
+    Accepted,
+    /// The node already has the transaction (in the mempool or in the DAG).
+    AlreadyKnown,
+    /// The transaction was rejected by the node.
+    Rejected(String),
+    /// The transaction was not submitted because a preceding
+    /// transaction of the chain has been rejected.
+    Skipped,
+}
+
+impl BroadcastStatus {
+    /// Returns `true` if the node has the transaction after the submission.
+    pub fn is_ok(&self) -> bool {
+        matches!(self, BroadcastStatus::Accepted | BroadcastStatus::AlreadyKnown)
+    }
+
+    fn from_error(err: Error) -> Self {
+        let reason = match err {
+            Error::KaspaRpcClientResult(RpcError::SubmitTransactionError(_, err)) if err.is_already_known() => {
+                return BroadcastStatus::AlreadyKnown;
+            }
+            Error::KaspaRpcClientResult(RpcError::SubmitTransactionError(_, err)) => err.to_string(),
+            Error::KaspaRpcClientResult(RpcError::RejectedTransaction(_, reason)) => reason,
+            err => err.to_string(),
+        };
+
+        if ALREADY_KNOWN.iter().any(|pattern| reason.contains(pattern)) {
+            BroadcastStatus::AlreadyKnown
+        } else {
+            BroadcastStatus::Rejected(reason)
+        }
+    }
+}
+
+impl std::fmt::Display for BroadcastStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BroadcastStatus::Accepted => write!(f, "accepted"),
+            BroadcastStatus::AlreadyKnown => write!(f, "already known"),
+            BroadcastStatus::Rejected(reason) => write!(f, "rejected: {reason}"),
+            BroadcastStatus::Skipped => write!(f, "skipped (a preceding transaction was rejected)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastResult {
+    pub id: TransactionId,
+    #[serde(flatten)]
+    pub status: BroadcastStatus,
+}
+
+/// Submits a chain of signed transactions in order. Each transaction may
+/// spend outputs of the preceding ones, so once a transaction is rejected
+/// the remaining transactions are skipped. Transactions submitted before
+/// the rejection remain accepted.
+pub async fn broadcast(rpc: &Arc<DynRpcApi>, transactions: &[UnsignedTransaction]) -> Vec<BroadcastResult> {
+    let mut results = Vec::with_capacity(transactions.len());
+    let mut rejected = false;
+    for transaction in transactions {
+        let status = if rejected {
+            BroadcastStatus::Skipped
+        } else {
+            match transaction.try_submit(rpc).await {
+                Ok(_) => BroadcastStatus::Accepted,
+                Err(err) => BroadcastStatus::from_error(err),
+            }
+        };
+        rejected |= !status.is_ok();
+        results.push(BroadcastResult { id: transaction.id(), status });
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::RpcCoreMock;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use kaspa_consensus_core::tx::{Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry};
+    use kaspa_txscript::pay_to_address_script;
+
+    /// Creates a chain of signed transactions, each spending the output of the preceding one.
+    fn make_transaction_chain(len: usize) -> Vec<UnsignedTransaction> {
+        let address = Address::try_from("kaspatest:qz7ulu4c25dh7fzec9zjyrmlhnkzrg4wmf89q7gzr3gfrsj3uz6xjceef60sd").unwrap();
+        let script_public_key = pay_to_address_script(&address);
+        let mut previous = TransactionId::from_slice(&[1; 32]);
+        (0..len as u64)
+            .map(|index| {
+                let value = 100_000 - index * 1_000;
+                let transaction = Transaction::new(
+                    0,
+                    vec![TransactionInput::new(TransactionOutpoint::new(previous, 0), vec![1; 66], 0, 1)],
+                    vec![TransactionOutput::new(value - 1_000, script_public_key.clone())],
+                    0,
+                    SUBNETWORK_ID_NATIVE,
+                    0,
+                    vec![],
+                );
+                previous = transaction.id();
+                let entries = vec![UtxoEntry::new(value, script_public_key.clone(), 10, false)];
+                let derivations = vec![crate::tx::InputDerivation::new(address.clone(), false, 0)];
+                UnsignedTransaction::new(NetworkId::with_suffix(NetworkType::Testnet, 11), transaction, entries, derivations)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_mid_chain_rejection() {
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let rpc: Arc<DynRpcApi> = rpc_mock.clone();
+        let chain = make_transaction_chain(4);
+        rpc_mock.reject_transaction(chain[2].id(), "transaction is not standard: mass exceeds the maximum");
+
+        let results = broadcast(&rpc, &chain).await;
+        let statuses = results.iter().map(|result| result.status.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                BroadcastStatus::Accepted,
+                BroadcastStatus::Accepted,
+                BroadcastStatus::Rejected("transaction is not standard: mass exceeds the maximum".to_string()),
+                BroadcastStatus::Skipped,
+            ]
+        );
+        assert_eq!(results.iter().map(|result| result.id).collect::<Vec<_>>(), chain.iter().map(|tx| tx.id()).collect::<Vec<_>>());
+        assert_eq!(rpc_mock.submitted_transactions().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_already_known() {
+        let rpc_mock = Arc::new(RpcCoreMock::new());
+        let rpc: Arc<DynRpcApi> = rpc_mock.clone();
+        let chain = make_transaction_chain(2);
+
+        broadcast(&rpc, &chain[..1]).await;
+        let results = broadcast(&rpc, &chain).await;
+        assert_eq!(results[0].status, BroadcastStatus::AlreadyKnown);
+        assert_eq!(results[1].status, BroadcastStatus::Accepted);
+        assert!(results.iter().all(|result| result.status.is_ok()));
+    }
+}
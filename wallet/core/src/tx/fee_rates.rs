@@ -0,0 +1,144 @@
+//!
+//! Fee rates estimated by the node from the content of its mempool.
+//!
+//! Rates are obtained via [`Wallet::estimate_fee_rates()`](crate::wallet::Wallet::estimate_fee_rates)
+//! and can be converted into the priority [`Fees`] of a transaction of a given mass.
+//!
+
+use crate::imports::*;
+use crate::tx::{Fees, MINIMUM_RELAY_TRANSACTION_FEE};
+use kaspa_rpc_core::RpcFeeEstimate;
+use std::time::Duration;
+use workflow_core::time::Instant;
+
+/// Default time during which the wallet retains the fee rates obtained from the node.
+pub const DEFAULT_FEE_RATES_TTL: Duration = Duration::from_secs(10);
+
+/// Minimum fee rate, in sompi per gram of mass, required to relay a transaction.
+pub const MINIMUM_FEE_RATE: f64 = MINIMUM_RELAY_TRANSACTION_FEE as f64 / 1000.0;
+
+/// Fee rate bucket, ie. how fast a transaction is expected to be mined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeeRateBucket {
+    /// Within the next 60 blocks
+    Low,
+    /// Within the next 10 blocks
+    Normal,
+    /// Within the next block
+    Priority,
+}
+
+impl FromStr for FeeRateBucket {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(FeeRateBucket::Low),
+            "normal" => Ok(FeeRateBucket::Normal),
+            "priority" => Ok(FeeRateBucket::Priority),
+            _ => Err(Error::custom(format!("invalid fee rate bucket '{s}' (expected low, normal or priority)"))),
+        }
+    }
+}
+
+/// Fee rates in sompi per gram of mass.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeRates {
+    pub low: f64,
+    pub normal: f64,
+    pub priority: f64,
+}
+
+/// The minimum fee rate for all buckets, used when the node can not provide a meaningful estimate.
+impl Default for FeeRates {
+    fn default() -> Self {
+        Self { low: MINIMUM_FEE_RATE, normal: MINIMUM_FEE_RATE, priority: MINIMUM_FEE_RATE }
+    }
+}
+
+/// Sanitizes the estimate of the node: rates are never below the minimum fee rate
+/// and a faster bucket never has a lower rate than a slower one.
+impl From<RpcFeeEstimate> for FeeRates {
+    fn from(estimate: RpcFeeEstimate) -> Self {
+        let sanitize = |rate: f64| if rate.is_finite() { rate.max(MINIMUM_FEE_RATE) } else { MINIMUM_FEE_RATE };
+        let low = sanitize(estimate.low_bucket.feerate);
+        let normal = sanitize(estimate.normal_bucket.feerate).max(low);
+        let priority = sanitize(estimate.priority_bucket.feerate).max(normal);
+        Self { low, normal, priority }
+    }
+}
+
+impl FeeRates {
+    pub fn rate(&self, bucket: FeeRateBucket) -> f64 {
+        match bucket {
+            FeeRateBucket::Low => self.low,
+            FeeRateBucket::Normal => self.normal,
+            FeeRateBucket::Priority => self.priority,
+        }
+    }
+
+    /// Returns the priority fees a transaction of `mass` should pay, in addition to the
+    /// relay fees, for the fee rate of `bucket`.
+    pub fn priority_fees(&self, bucket: FeeRateBucket, mass: u64) -> Fees {
+        Fees::SenderPays(((self.rate(bucket) - MINIMUM_FEE_RATE) * mass as f64).ceil() as u64)
+    }
+}
+
+/// Fee rates cached for a TTL.
+pub(crate) struct FeeRatesCache {
+    ttl: Duration,
+    rates: Mutex<Option<(FeeRates, Instant)>>,
+}
+
+impl Default for FeeRatesCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_FEE_RATES_TTL)
+    }
+}
+
+impl FeeRatesCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, rates: Mutex::new(None) }
+    }
+
+    pub fn get(&self) -> Option<FeeRates> {
+        self.rates.lock().unwrap().as_ref().filter(|(_, ts)| ts.elapsed() < self.ttl).map(|(rates, _)| *rates)
+    }
+
+    pub fn store(&self, rates: FeeRates) {
+        self.rates.lock().unwrap().replace((rates, Instant::now()));
+    }
+
+    pub fn clear(&self) {
+        self.rates.lock().unwrap().take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_rpc_core::RpcFeeRateBucket;
+
+    fn estimate(low: f64, normal: f64, priority: f64) -> RpcFeeEstimate {
+        RpcFeeEstimate::new(
+            RpcFeeRateBucket::new(priority, 1.0),
+            RpcFeeRateBucket::new(normal, 10.0),
+            RpcFeeRateBucket::new(low, 60.0),
+        )
+    }
+
+    #[test]
+    fn test_fee_rates_sanitization() {
+        let rates = FeeRates::from(estimate(0.5, 3.0, 2.0));
+        assert_eq!(rates, FeeRates { low: MINIMUM_FEE_RATE, normal: 3.0, priority: 3.0 });
+        assert_eq!(FeeRates::from(estimate(f64::NAN, f64::INFINITY, -1.0)), FeeRates::default());
+
+        let rates = FeeRates::from(estimate(1.0, 2.5, 10.0));
+        assert_eq!(rates.priority_fees(FeeRateBucket::Low, 2000).additional(), 0);
+        assert_eq!(rates.priority_fees(FeeRateBucket::Normal, 2001).additional(), 3002);
+        assert_eq!(rates.priority_fees(FeeRateBucket::Priority, 2000).additional(), 18000);
+        assert_eq!("Priority".parse::<FeeRateBucket>().unwrap(), FeeRateBucket::Priority);
+        assert!("fast".parse::<FeeRateBucket>().is_err());
+    }
+}
@@ -34,6 +34,12 @@
 //! Transactions within a single stage are independent of one another
 //! and as such can be processed in parallel.
 //!
+//! If the payment outputs do not fit in a single transaction, they are
+//! split into chunks paid by a chain of "split" transactions, the change
+//! of each split transaction funding the next one. Only the last chunk
+//! is paid by the final transaction (carrying the priority fee and the
+//! payload).
+//!
 //! The [`Generator`] acts as a transaction iterator, yielding transactions
 //! for each iteration. These transactions can be obtained via an iterator
 //! interface or via an async Stream interface.
@@ -56,19 +62,25 @@
 //!   UTXO sets will result in a browser UI freezing.
 //!
 
+use crate::derivation::AddressManager;
 use crate::imports::*;
 use crate::result::Result;
 use crate::tx::{
-    mass::*, Fees, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, PendingTransactionIterator,
-    PendingTransactionStream,
+    mass::*, Fees, GeneratorProgress, GeneratorProgressNotifier, GeneratorSettings, GeneratorSummary, PaymentDestination,
+    PendingTransaction, PendingTransactionIterator, PendingTransactionStream,
 };
 use crate::utxo::{NetworkParams, UtxoContext, UtxoEntryReference};
+use kaspa_consensus_core::config::params::Params;
 use kaspa_consensus_core::constants::UNACCEPTED_DAA_SCORE;
+use kaspa_consensus_core::errors::tx::TxRuleError;
 use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
 use kaspa_consensus_core::tx as cctx;
-use kaspa_consensus_core::tx::{Transaction, TransactionInput, TransactionOutpoint, TransactionOutput};
+use kaspa_consensus_core::tx::{
+    check_transaction_sig_op_count, inputs_sig_op_count, Transaction, TransactionIndexType, TransactionInput, TransactionOutpoint,
+    TransactionOutput,
+};
 use kaspa_consensus_wasm::UtxoEntry;
-use kaspa_txscript::pay_to_address_script;
+use kaspa_txscript::{extract_script_pub_key_address, pay_to_address_script};
 use std::collections::VecDeque;
 
 use super::SignerT;
@@ -84,6 +96,10 @@ const TRANSACTION_MASS_BOUNDARY_FOR_ADDITIONAL_INPUT_ACCUMULATION: u64 = MAXIMUM
 // output amount
 const TRANSACTION_MASS_BOUNDARY_FOR_STAGE_INPUT_ACCUMULATION: u64 = MAXIMUM_STANDARD_TRANSACTION_MASS / 5 * 4;
 
+// split boundary - payment outputs are split across multiple
+// transactions when their mass exceeds this threshold
+const TRANSACTION_MASS_BOUNDARY_FOR_PAYMENT_OUTPUTS: u64 = MAXIMUM_STANDARD_TRANSACTION_MASS / 5 * 4;
+
 /// Mutable [`Generator`] state used to track the current transaction generation process.
 struct Context {
     /// iterator containing UTXO entries available for transaction generation
@@ -95,8 +111,16 @@ struct Context {
     aggregate_fees: u64,
     /// number of generated transactions
     number_of_transactions: usize,
+    /// total value of UTXO entries from the source iterator
+    /// consumed by the generated transactions
+    aggregate_source_value: u64,
+    /// ids of relay transactions, used to distinguish relay
+    /// UTXO entries from the UTXO entries of the source iterator
+    relay_transaction_ids: HashSet<TransactionId>,
     /// current tree stage
     stage: Option<Box<Stage>>,
+    /// index of the payment chunk paid by the current transaction tree
+    payment_chunk: usize,
     /// Rejected or "stashed" UTXO entries that are consumed before polling
     /// the iterator. This store is used in edge cases when UTXO entry from the
     /// iterator has been consumed but was rejected due to mass constraints or
@@ -171,6 +195,10 @@ pub enum DataKind {
     /// Final transaction combining the entire aggregated UTXO set
     /// into a single set of supplied outputs.
     Final,
+    /// A payment transaction paying a chunk of the supplied outputs when
+    /// the outputs do not fit in a single transaction. The change of this
+    /// transaction funds the next payment transaction.
+    Split,
 }
 
 impl DataKind {
@@ -183,6 +211,13 @@ impl DataKind {
     pub fn is_stage_edge(&self) -> bool {
         matches!(self, DataKind::Edge)
     }
+    pub fn is_split(&self) -> bool {
+        matches!(self, DataKind::Split)
+    }
+    /// Transaction paying (a part of) the supplied outputs
+    pub fn is_payment(&self) -> bool {
+        matches!(self, DataKind::Final | DataKind::Split)
+    }
 }
 
 ///
@@ -228,8 +263,69 @@ impl Data {
 struct FinalTransaction {
     /// Total output value required for the final transaction
     value_no_fees: u64,
-    /// Total output value required for the final transaction + priority fees
-    value_with_priority_fee: u64,
+}
+
+/// Chunk of the final transaction outputs paid by a single payment transaction
+struct PaymentChunk {
+    /// Outputs paid by the transaction
+    outputs: Vec<TransactionOutput>,
+    /// Pre-calculated partial harmonic of the outputs (does not include change)
+    outputs_harmonic: u64,
+    /// Compute mass of the outputs
+    outputs_compute_mass: u64,
+    /// Payload mass (the payload is issued only in the final transaction)
+    payload_mass: u64,
+    /// Total output value of the chunk
+    value_no_fees: u64,
+    /// Priority fee (applies only to the final transaction)
+    priority_fee: u64,
+    /// Signifies that the chunk is paid by the final transaction
+    is_final: bool,
+}
+
+impl PaymentChunk {
+    fn value_with_priority_fee(&self) -> u64 {
+        self.value_no_fees + self.priority_fee
+    }
+
+    fn kind(&self) -> DataKind {
+        if self.is_final {
+            DataKind::Final
+        } else {
+            DataKind::Split
+        }
+    }
+
+    /// Split `outputs` into chunks of at most `max_outputs` outputs whose mass, including
+    /// the `reserved_mass` (change output and payload), is within the payment outputs boundary.
+    /// The storage mass harmonic of the outputs is accounted for as it bounds the storage mass
+    /// contributed by the outputs.
+    fn split(
+        calc: &MassCalculator,
+        outputs: Vec<TransactionOutput>,
+        max_outputs: usize,
+        reserved_mass: u64,
+    ) -> Vec<Vec<TransactionOutput>> {
+        let mut chunks: Vec<Vec<TransactionOutput>> = vec![];
+        let mut chunk_mass = 0;
+        for output in outputs {
+            let output_mass = calc.calc_mass_for_output(&output) + calc.calc_storage_mass_output_harmonic_single(output.value);
+            match chunks.last_mut() {
+                Some(chunk)
+                    if chunk.len() < max_outputs
+                        && reserved_mass + chunk_mass + output_mass <= TRANSACTION_MASS_BOUNDARY_FOR_PAYMENT_OUTPUTS =>
+                {
+                    chunk_mass += output_mass;
+                    chunk.push(output);
+                }
+                _ => {
+                    chunk_mass = output_mass;
+                    chunks.push(vec![output]);
+                }
+            }
+        }
+        chunks
+    }
 }
 
 /// Helper struct for obtaining properties related to
@@ -261,6 +357,8 @@ struct Inner {
     network_id: NetworkId,
     // Current network params
     network_params: NetworkParams,
+    // Current consensus params (used for the transaction sig op limit)
+    consensus_params: Params,
 
     // Source Utxo Context (Used for source UtxoEntry aggregation)
     source_utxo_context: Option<UtxoContext>,
@@ -271,10 +369,11 @@ struct Inner {
     // typically a number of keys required to sign the transaction
     sig_op_count: u8,
     // number of minimum signatures required to sign the transaction
-    #[allow(dead_code)]
     minimum_signatures: u16,
     // change address
     change_address: Address,
+    // change address manager (used when change address rotation is enabled)
+    change_address_manager: Option<Arc<AddressManager>>,
     // change_output: TransactionOutput,
     standard_change_output_compute_mass: u64,
     // signature mass per input
@@ -284,16 +383,17 @@ struct Inner {
     final_transaction: Option<FinalTransaction>,
     // applies only to the final transaction
     final_transaction_priority_fee: Fees,
-    // issued only in the final transaction
-    final_transaction_outputs: Vec<TransactionOutput>,
-    // pre-calculated partial harmonic for user outputs (does not include change)
-    final_transaction_outputs_harmonic: u64,
-    // mass of the final transaction
-    final_transaction_outputs_compute_mass: u64,
+    // final transaction outputs split into chunks fitting in a single transaction
+    // (empty for sweep transactions)
+    payment_chunks: Vec<PaymentChunk>,
     // final transaction payload
     final_transaction_payload: Vec<u8>,
-    // final transaction payload mass
-    final_transaction_payload_mass: u64,
+    // optional progress notifier invoked for each generated transaction
+    progress_notifier: Option<GeneratorProgressNotifier>,
+    // pending transaction replaced by the generated transaction (fee bumping)
+    replaced_transaction: Option<PendingTransaction>,
+    // maximum number of inputs of each generated transaction
+    max_inputs_per_transaction: usize,
     // execution context
     context: Mutex<Context>,
 }
@@ -317,15 +417,35 @@ impl Generator {
             sig_op_count,
             minimum_signatures,
             change_address,
+            change_address_manager,
             final_transaction_priority_fee,
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context,
+            progress_notifier,
+            replaced_transaction,
+            max_inputs_per_transaction,
+            max_outputs_per_transaction,
         } = settings;
 
         let network_type = NetworkType::from(network_id);
         let network_params = NetworkParams::from(network_id);
         let mass_calculator = MassCalculator::new(&network_id.into(), &network_params);
+        let consensus_params = Params::from(network_id);
+
+        // every transaction needs to fit at least one input
+        if sig_op_count as u64 > consensus_params.max_transaction_sig_ops() {
+            return Err(TxRuleError::TooManySigOps(sig_op_count as u64, consensus_params.max_transaction_sig_ops()).into());
+        }
+
+        // relay transactions need to consolidate at least two inputs for the stages to converge
+        if max_inputs_per_transaction < 2 {
+            return Err(Error::GeneratorMaxInputsPerTransaction(max_inputs_per_transaction));
+        }
+
+        if max_outputs_per_transaction == 0 {
+            return Err(Error::GeneratorMaxOutputsPerTransaction(max_outputs_per_transaction));
+        }
 
         let (final_transaction_outputs, final_transaction_amount) = match final_transaction_destination {
             PaymentDestination::Change => {
@@ -344,6 +464,9 @@ impl Generator {
                     if output.amount == 0 {
                         return Err(Error::GeneratorPaymentOutputZeroAmount);
                     }
+                    if mass_calculator.is_dust(output.amount) {
+                        return Err(Error::DustOutput { address: output.address.clone(), amount: output.amount });
+                    }
                 }
 
                 (
@@ -351,7 +474,7 @@ impl Generator {
                         .iter()
                         .map(|output| TransactionOutput::new(output.amount, pay_to_address_script(&output.address)))
                         .collect(),
-                    Some(outputs.iter().map(|output| output.amount).sum()),
+                    Some(outputs.iter().try_fold(0u64, |total, output| total.checked_add(output.amount)).ok_or(Error::Overflow)?),
                 )
             }
         };
@@ -360,6 +483,11 @@ impl Generator {
             return Err(Error::GeneratorIncludeFeesRequiresOneOutput);
         }
 
+        // replacement fees are deducted from the change, recipient outputs remain intact
+        if replaced_transaction.is_some() && !final_transaction_priority_fee.sender_pays() {
+            return Err(Error::GeneratorReplacementFees);
+        }
+
         // sanity check
         if NetworkType::try_from(change_address.prefix)? != network_type {
             return Err(Error::GeneratorChangeAddressNetworkTypeMismatch);
@@ -368,29 +496,63 @@ impl Generator {
         let standard_change_output_mass =
             mass_calculator.calc_mass_for_output(&TransactionOutput::new(0, pay_to_address_script(&change_address)));
         let signature_mass_per_input = mass_calculator.calc_signature_mass(minimum_signatures);
-        let final_transaction_outputs_compute_mass = mass_calculator.calc_mass_for_outputs(&final_transaction_outputs);
         let final_transaction_payload = final_transaction_payload.unwrap_or_default();
+        if final_transaction_payload.len() > mass_calculator.max_payload_size() {
+            return Err(Error::GeneratorPayloadTooLarge {
+                size: final_transaction_payload.len(),
+                max: mass_calculator.max_payload_size(),
+            });
+        }
         let final_transaction_payload_mass = mass_calculator.calc_mass_for_payload(final_transaction_payload.len());
-        let final_transaction_outputs_harmonic =
-            mass_calculator.calc_storage_mass_output_harmonic(&final_transaction_outputs).ok_or(Error::MassCalculationError)?;
 
-        // reject transactions where the payload and outputs are more than 2/3rds of the maximum tx mass
-        let final_transaction = final_transaction_amount.map(|amount| FinalTransaction {
-            value_no_fees: amount,
-            value_with_priority_fee: amount + final_transaction_priority_fee.additional(),
-        });
+        let final_transaction = final_transaction_amount.map(|amount| FinalTransaction { value_no_fees: amount });
+
+        // split outputs that do not fit in a single transaction across multiple payment transactions
+        let chunks = PaymentChunk::split(
+            &mass_calculator,
+            final_transaction_outputs,
+            max_outputs_per_transaction,
+            standard_change_output_mass + final_transaction_payload_mass,
+        );
+        if chunks.len() > 1 && final_transaction_priority_fee.receiver_pays() {
+            return Err(Error::GeneratorIncludeFeesRequiresOneOutput);
+        }
+
+        let number_of_chunks = chunks.len();
+        let mut payment_chunks = Vec::with_capacity(number_of_chunks);
+        for (index, outputs) in chunks.into_iter().enumerate() {
+            let is_final = index + 1 == number_of_chunks;
+            let outputs_compute_mass = mass_calculator.calc_mass_for_outputs(&outputs);
+            let payload_mass = if is_final { final_transaction_payload_mass } else { 0 };
+
+            // reject transactions where the payload and outputs are more than 4/5ths of the maximum tx mass
+            let mass_sanity_check = standard_change_output_mass + outputs_compute_mass + payload_mass;
+            if mass_sanity_check > MAXIMUM_STANDARD_TRANSACTION_MASS / 5 * 4 {
+                return Err(Error::GeneratorTransactionOutputsAreTooHeavy { mass: mass_sanity_check, kind: "compute mass" });
+            }
 
-        let mass_sanity_check = standard_change_output_mass + final_transaction_outputs_compute_mass + final_transaction_payload_mass;
-        if mass_sanity_check > MAXIMUM_STANDARD_TRANSACTION_MASS / 5 * 4 {
-            return Err(Error::GeneratorTransactionOutputsAreTooHeavy { mass: mass_sanity_check, kind: "compute mass" });
+            let value_no_fees =
+                outputs.iter().try_fold(0u64, |total, output| total.checked_add(output.value)).ok_or(Error::Overflow)?;
+            payment_chunks.push(PaymentChunk {
+                outputs_harmonic: mass_calculator.calc_storage_mass_output_harmonic(&outputs).ok_or(Error::MassCalculationError)?,
+                value_no_fees,
+                priority_fee: if is_final { final_transaction_priority_fee.additional() } else { 0 },
+                outputs,
+                outputs_compute_mass,
+                payload_mass,
+                is_final,
+            });
         }
 
         let context = Mutex::new(Context {
             utxo_source_iterator: utxo_iterator,
             number_of_transactions: 0,
+            aggregate_source_value: 0,
+            relay_transaction_ids: HashSet::default(),
             aggregated_utxos: 0,
             aggregate_fees: 0,
             stage: Some(Box::default()),
+            payment_chunk: 0,
             utxo_stash: VecDeque::default(),
             final_transaction_id: None,
             is_done: false,
@@ -399,6 +561,7 @@ impl Generator {
         let inner = Inner {
             network_id,
             network_params,
+            consensus_params,
             multiplexer,
             context,
             signer,
@@ -408,16 +571,17 @@ impl Generator {
             sig_op_count,
             minimum_signatures,
             change_address,
+            change_address_manager,
             standard_change_output_compute_mass: standard_change_output_mass,
             signature_mass_per_input,
             final_transaction,
             final_transaction_priority_fee,
-            final_transaction_outputs,
-            final_transaction_outputs_harmonic,
-            final_transaction_outputs_compute_mass,
+            payment_chunks,
             final_transaction_payload,
-            final_transaction_payload_mass,
             destination_utxo_context,
+            progress_notifier,
+            replaced_transaction,
+            max_inputs_per_transaction,
         };
         Ok(Self { inner: Arc::new(inner) })
     }
@@ -447,6 +611,26 @@ impl Generator {
         &self.inner.destination_utxo_context
     }
 
+    /// Change address used by generated transactions (when change address rotation is disabled)
+    pub fn change_address(&self) -> &Address {
+        &self.inner.change_address
+    }
+
+    /// Number of signature operations of each transaction input
+    pub fn sig_op_count(&self) -> u8 {
+        self.inner.sig_op_count
+    }
+
+    /// Number of minimum signatures required to sign the transaction
+    pub fn minimum_signatures(&self) -> u16 {
+        self.inner.minimum_signatures
+    }
+
+    /// Maximum number of inputs of each generated transaction
+    pub fn max_inputs_per_transaction(&self) -> usize {
+        self.inner.max_inputs_per_transaction
+    }
+
     /// Core [`Multiplexer<Events>`] (if available)
     pub fn multiplexer(&self) -> &Option<Multiplexer<Box<Events>>> {
         &self.inner.multiplexer
@@ -546,10 +730,12 @@ impl Generator {
                 utxo_entry_reference
             } else {
                 // UTXO sources are depleted
-                if let Some(final_transaction) = &self.inner.final_transaction {
+                if self.inner.final_transaction.is_some() {
                     // reject transaction
+                    let remaining_payments = &self.inner.payment_chunks[context.payment_chunk..];
+                    let value_needed = remaining_payments.iter().map(PaymentChunk::value_with_priority_fee).sum::<u64>();
                     return Err(Error::InsufficientFunds {
-                        additional_needed: final_transaction.value_with_priority_fee.saturating_sub(stage.aggregate_input_value),
+                        additional_needed: value_needed.saturating_sub(stage.aggregate_input_value),
                         origin: "accumulator",
                     });
                 } else {
@@ -562,16 +748,16 @@ impl Generator {
                 return Ok((node, data));
             }
 
-            if let Some(final_transaction) = &self.inner.final_transaction {
-                // try finish a stage or produce a final transaction with target value
+            if let Some(payment) = self.inner.payment_chunks.get(context.payment_chunk) {
+                // try finish a stage or produce a payment transaction with target value
                 // use basic condition checks to avoid unnecessary processing
                 if data.aggregate_mass > TRANSACTION_MASS_BOUNDARY_FOR_STAGE_INPUT_ACCUMULATION
                     || (self.inner.final_transaction_priority_fee.sender_pays()
-                        && stage.aggregate_input_value >= final_transaction.value_with_priority_fee)
+                        && stage.aggregate_input_value >= payment.value_with_priority_fee())
                     || (self.inner.final_transaction_priority_fee.receiver_pays()
-                        && stage.aggregate_input_value >= final_transaction.value_no_fees.saturating_sub(context.aggregate_fees))
+                        && stage.aggregate_input_value >= payment.value_no_fees.saturating_sub(context.aggregate_fees))
                 {
-                    if let Some(kind) = self.try_finish_standard_stage_processing(context, stage, &mut data, final_transaction)? {
+                    if let Some(kind) = self.try_finish_standard_stage_processing(context, stage, &mut data, payment)? {
                         return Ok((kind, data));
                     }
                 }
@@ -606,12 +792,14 @@ impl Generator {
         let input_compute_mass = calc.calc_mass_for_input(&input) + self.inner.signature_mass_per_input;
 
         // NOTE: relay transactions have no storage mass
-        // mass threshold reached, yield transaction
+        // mass, sig op or input count threshold reached, yield transaction
         if data.aggregate_mass
             + input_compute_mass
             + self.inner.standard_change_output_compute_mass
             + self.inner.network_params.additional_compound_transaction_mass
             > MAXIMUM_STANDARD_TRANSACTION_MASS
+            || inputs_sig_op_count(data.inputs.iter().chain([&input])) > self.inner.consensus_params.max_transaction_sig_ops()
+            || data.inputs.len() >= self.inner.max_inputs_per_transaction
         {
             // note, we've used input for mass boundary calc and now abandon it
             // while preserving the UTXO entry reference to be used in the next iteration
@@ -625,6 +813,9 @@ impl Generator {
             Some(DataKind::Node)
         } else {
             context.aggregated_utxos += 1;
+            if !context.relay_transaction_ids.contains(utxo.outpoint.transaction_id_as_ref()) {
+                context.aggregate_source_value += input_amount;
+            }
             stage.aggregate_input_value += input_amount;
             data.aggregate_input_value += input_amount;
             data.aggregate_mass += input_compute_mass;
@@ -670,26 +861,26 @@ impl Generator {
         calc.calc_storage_mass(output_harmonics, data.aggregate_input_value, data.inputs.len() as u64)
     }
 
-    /// Check if the current state has sufficient funds for the final transaction,
+    /// Check if the current state has sufficient funds for the `payment` transaction,
     /// initiate new stage if necessary, or finish stage processing creating the
-    /// final transaction.
+    /// payment transaction (the final transaction or a split payment transaction).
     fn try_finish_standard_stage_processing(
         &self,
         context: &mut Context,
         stage: &mut Stage,
         data: &mut Data,
-        final_transaction: &FinalTransaction,
+        payment: &PaymentChunk,
     ) -> Result<Option<DataKind>> {
         let calc = &self.inner.mass_calculator;
 
         // calculate storage mass
         let MassDisposition { transaction_mass, storage_mass, transaction_fees, absorb_change_to_fees } =
-            self.calculate_mass(stage, data, final_transaction.value_with_priority_fee)?;
+            self.calculate_mass(stage, data, payment)?;
 
         let total_stage_value_needed = if self.inner.final_transaction_priority_fee.sender_pays() {
-            final_transaction.value_with_priority_fee + stage.aggregate_fees + transaction_fees
+            payment.value_with_priority_fee() + stage.aggregate_fees + transaction_fees
         } else {
-            final_transaction.value_with_priority_fee
+            payment.value_with_priority_fee()
         };
 
         let reject = match self.inner.final_transaction_priority_fee {
@@ -711,7 +902,7 @@ impl Generator {
             // also doing this can result in reduction of the wallet UTXO set, which later results
             // in additional fees for the user.
             if storage_mass > 0
-                && data.inputs.len() < self.inner.final_transaction_outputs.len() * 2
+                && data.inputs.len() < payment.outputs.len() * 2
                 && transaction_mass < TRANSACTION_MASS_BOUNDARY_FOR_ADDITIONAL_INPUT_ACCUMULATION
             {
                 // fetch UTXO from the iterator and if exists, make it available on the next iteration via utxo_stash.
@@ -722,15 +913,15 @@ impl Generator {
             // ---
 
             let (mut transaction_fees, change_output_value) = match self.inner.final_transaction_priority_fee {
-                Fees::SenderPays(priority_fees) => {
-                    let transaction_fees = transaction_fees + priority_fees;
-                    let change_output_value = data.aggregate_input_value - final_transaction.value_no_fees - transaction_fees;
+                Fees::SenderPays(_) => {
+                    let transaction_fees = transaction_fees + payment.priority_fee;
+                    let change_output_value = data.aggregate_input_value - payment.value_no_fees - transaction_fees;
                     (transaction_fees, change_output_value)
                 }
                 // TODO - currently unreachable at the API level
                 Fees::ReceiverPays(priority_fees) => {
                     let transaction_fees = transaction_fees + priority_fees;
-                    let change_output_value = data.aggregate_input_value.saturating_sub(final_transaction.value_no_fees);
+                    let change_output_value = data.aggregate_input_value.saturating_sub(payment.value_no_fees);
                     (transaction_fees, change_output_value)
                 }
                 Fees::None => unreachable!("Fees::None is not allowed for final transactions"),
@@ -744,19 +935,16 @@ impl Generator {
                 // as we might absorb an input as a part of the receiver
                 // pays fee reduction, we should update the mass to make
                 // sure internal metrics and unit tests check out.
-                let compute_mass = data.aggregate_mass
-                    + self.inner.final_transaction_outputs_compute_mass
-                    + self.inner.final_transaction_payload_mass;
-                let storage_mass = self.calc_storage_mass(data, self.inner.final_transaction_outputs_harmonic);
+                let compute_mass = data.aggregate_mass + payment.outputs_compute_mass + payment.payload_mass;
+                let storage_mass = self.calc_storage_mass(data, payment.outputs_harmonic);
 
                 data.aggregate_mass = calc.combine_mass(compute_mass, storage_mass);
 
-                transaction_fees += change_output_value;
                 data.transaction_fees = transaction_fees;
                 stage.aggregate_fees += transaction_fees;
                 context.aggregate_fees += transaction_fees;
 
-                Ok(Some(DataKind::Final))
+                Ok(Some(payment.kind()))
             } else {
                 data.aggregate_mass = transaction_mass;
                 data.transaction_fees = transaction_fees;
@@ -764,20 +952,19 @@ impl Generator {
                 context.aggregate_fees += transaction_fees;
                 data.change_output_value = Some(change_output_value);
 
-                Ok(Some(DataKind::Final))
+                Ok(Some(payment.kind()))
             }
         }
     }
 
-    fn calculate_mass(&self, stage: &Stage, data: &Data, transaction_target_value: u64) -> Result<MassDisposition> {
+    fn calculate_mass(&self, stage: &Stage, data: &Data, payment: &PaymentChunk) -> Result<MassDisposition> {
         let calc = &self.inner.mass_calculator;
+        let transaction_target_value = payment.value_with_priority_fee();
 
         let mut absorb_change_to_fees = false;
 
-        let compute_mass_with_change = data.aggregate_mass
-            + self.inner.standard_change_output_compute_mass
-            + self.inner.final_transaction_outputs_compute_mass
-            + self.inner.final_transaction_payload_mass;
+        let compute_mass_with_change =
+            data.aggregate_mass + self.inner.standard_change_output_compute_mass + payment.outputs_compute_mass + payment.payload_mass;
 
         let storage_mass = if stage.number_of_transactions > 0 {
             // calculate for edge transaction boundaries
@@ -793,17 +980,17 @@ impl Generator {
             }
         } else if data.aggregate_input_value <= transaction_target_value {
             // calculate for final transaction boundaries
-            self.calc_storage_mass(data, self.inner.final_transaction_outputs_harmonic)
+            self.calc_storage_mass(data, payment.outputs_harmonic)
         } else {
             // calculate for final transaction boundaries
             let change_value = data.aggregate_input_value - transaction_target_value;
 
             if self.inner.mass_calculator.is_dust(change_value) {
                 absorb_change_to_fees = true;
-                self.calc_storage_mass(data, self.inner.final_transaction_outputs_harmonic)
+                self.calc_storage_mass(data, payment.outputs_harmonic)
             } else {
                 let output_harmonic_with_change =
-                    calc.calc_storage_mass_output_harmonic_single(change_value) + self.inner.final_transaction_outputs_harmonic;
+                    calc.calc_storage_mass_output_harmonic_single(change_value) + payment.outputs_harmonic;
                 let storage_mass_with_change = self.calc_storage_mass(data, output_harmonic_with_change);
 
                 if storage_mass_with_change == 0
@@ -812,7 +999,7 @@ impl Generator {
                 {
                     0
                 } else {
-                    let storage_mass_no_change = self.calc_storage_mass(data, self.inner.final_transaction_outputs_harmonic);
+                    let storage_mass_no_change = self.calc_storage_mass(data, payment.outputs_harmonic);
                     if storage_mass_with_change < storage_mass_no_change {
                         storage_mass_with_change
                     } else {
@@ -886,13 +1073,47 @@ impl Generator {
     /// an intermediate "batch" transaction sending funds to the change address
     /// or creating a final transaction with the requested set of outputs and the
     /// payload.
+    ///
+    /// If a progress notifier has been supplied via [`GeneratorSettings`],
+    /// it is invoked with the [`GeneratorProgress`] of each generated transaction.
     pub fn generate_transaction(&self) -> Result<Option<PendingTransaction>> {
+        let pending_transaction = self.generate_pending_transaction()?;
+
+        // the notifier is invoked outside of the context lock
+        // as it may call back into the generator
+        if let (Some(progress_notifier), Some(pending_transaction)) = (self.inner.progress_notifier.as_ref(), &pending_transaction) {
+            progress_notifier(&self.progress(pending_transaction.is_final()));
+        }
+
+        Ok(pending_transaction)
+    }
+
+    /// Produces [`GeneratorProgress`] for the last generated transaction.
+    fn progress(&self, is_final: bool) -> GeneratorProgress {
+        let context = self.context();
+        let index = context.number_of_transactions.saturating_sub(1);
+        match self.final_transaction_value_no_fees() {
+            Some(amount) => {
+                let sent = if is_final { amount } else { context.aggregate_source_value.min(amount) };
+                GeneratorProgress { index, is_final, sent, remaining: amount - sent }
+            }
+            None => GeneratorProgress { index, is_final, sent: context.aggregate_source_value, remaining: 0 },
+        }
+    }
+
+    fn generate_pending_transaction(&self) -> Result<Option<PendingTransaction>> {
         let mut context = self.context();
 
         if context.is_done {
             return Ok(None);
         }
 
+        if let Some(original) = self.inner.replaced_transaction.as_ref() {
+            context.is_done = true;
+            context.stage.take();
+            return self.generate_replacement_transaction(&mut context, original).map(Some);
+        }
+
         let mut stage = context.stage.take().unwrap();
         let (kind, data) = self.generate_transaction_data(&mut context, &mut stage)?;
         context.stage.replace(stage);
@@ -903,9 +1124,9 @@ impl Generator {
                 context.stage.take();
                 Ok(None)
             }
-            (DataKind::Final, data) => {
-                context.is_done = true;
-                context.stage.take();
+            (DataKind::Final | DataKind::Split, data) => {
+                // `None` for sweep transactions
+                let payment = self.inner.payment_chunks.get(context.payment_chunk);
 
                 let Data {
                     inputs,
@@ -920,7 +1141,7 @@ impl Generator {
 
                 let change_output_value = change_output_value.unwrap_or(0);
 
-                let mut final_outputs = self.inner.final_transaction_outputs.clone();
+                let mut final_outputs = payment.map(|payment| payment.outputs.clone()).unwrap_or_default();
                 // let mut final_outputs = context.final_transaction_outputs.clone();
 
                 if self.inner.final_transaction_priority_fee.receiver_pays() {
@@ -930,12 +1151,23 @@ impl Generator {
                     } else {
                         output.value -= transaction_fees;
                     }
+
+                    if self.inner.mass_calculator.is_dust(output.value) {
+                        let address = extract_script_pub_key_address(&output.script_public_key, self.inner.network_id.into())?;
+                        return Err(Error::DustOutput { address, amount: output.value });
+                    }
                 }
 
-                if change_output_value > 0 {
-                    let output = TransactionOutput::new(change_output_value, pay_to_address_script(&self.inner.change_address));
+                let mut change_address = None;
+                let derived_change_address = if change_output_value > 0 {
+                    let (address, derived_change_address) = self.next_change_address()?;
+                    let output = TransactionOutput::new(change_output_value, pay_to_address_script(&address));
                     final_outputs.push(output);
-                }
+                    change_address = Some(address);
+                    derived_change_address
+                } else {
+                    None
+                };
 
                 let aggregate_output_value = final_outputs.iter().map(|output| output.value).sum::<u64>();
                 // TODO - validate that this is still correct
@@ -947,31 +1179,50 @@ impl Generator {
                     });
                 }
 
-                let tx = Transaction::new(
-                    0,
-                    inputs,
-                    final_outputs,
-                    0,
-                    SUBNETWORK_ID_NATIVE,
-                    0,
-                    self.inner.final_transaction_payload.clone(),
-                );
-
-                context.final_transaction_id = Some(tx.id());
+                let payload = if kind.is_final() { self.inner.final_transaction_payload.clone() } else { vec![] };
+                let tx = Transaction::new(0, inputs, final_outputs, 0, SUBNETWORK_ID_NATIVE, 0, payload);
+
+                check_transaction_sig_op_count(&tx, &self.inner.consensus_params)?;
+
                 context.number_of_transactions += 1;
+                if kind.is_final() {
+                    context.is_done = true;
+                    context.stage.take();
+                    context.final_transaction_id = Some(tx.id());
+                } else {
+                    // the next chunk is paid by a new transaction tree, funded first by the change of this
+                    // transaction followed by the remaining relay UTXOs of the current stage
+                    context.payment_chunk += 1;
+                    let utxo_iterator = context.stage.take().and_then(|stage| stage.utxo_iterator);
+                    context.stage.replace(Box::new(Stage { utxo_iterator, ..Default::default() }));
+                    if let Some(change_address) = change_address.as_ref() {
+                        let change_output = tx.outputs.last().expect("change output");
+                        let utxo_entry_reference = Self::create_batch_utxo_entry_reference(
+                            tx.id(),
+                            (tx.outputs.len() - 1) as TransactionIndexType,
+                            change_output.value,
+                            change_output.script_public_key.clone(),
+                            change_address,
+                        );
+                        context.relay_transaction_ids.insert(tx.id());
+                        context.utxo_stash.push_front(utxo_entry_reference);
+                    }
+                }
 
                 Ok(Some(PendingTransaction::try_new(
                     self,
                     tx,
                     utxo_entry_references,
                     addresses.into_iter().collect(),
-                    self.final_transaction_value_no_fees(),
+                    payment.map(|payment| payment.value_no_fees),
                     change_output_value,
                     aggregate_input_value,
                     aggregate_output_value,
                     aggregate_mass,
                     transaction_fees,
                     kind,
+                    derived_change_address,
+                    None,
                 )?))
             }
             (kind, data) => {
@@ -989,13 +1240,16 @@ impl Generator {
                 assert_eq!(change_output_value, None);
 
                 let output_value = aggregate_input_value - transaction_fees;
-                let script_public_key = pay_to_address_script(&self.inner.change_address);
+                let (change_address, derived_change_address) = self.next_change_address()?;
+                let script_public_key = pay_to_address_script(&change_address);
                 let output = TransactionOutput::new(output_value, script_public_key.clone());
                 let tx = Transaction::new(0, inputs, vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+                check_transaction_sig_op_count(&tx, &self.inner.consensus_params)?;
                 context.number_of_transactions += 1;
+                context.relay_transaction_ids.insert(tx.id());
 
                 let utxo_entry_reference =
-                    Self::create_batch_utxo_entry_reference(tx.id(), output_value, script_public_key, &self.inner.change_address);
+                    Self::create_batch_utxo_entry_reference(tx.id(), 0, output_value, script_public_key, &change_address);
 
                 match kind {
                     DataKind::Node => {
@@ -1026,19 +1280,122 @@ impl Generator {
                     aggregate_mass,
                     transaction_fees,
                     kind,
+                    derived_change_address,
+                    None,
                 )?))
             }
         }
     }
 
+    /// Generates a replacement of the `original` pending transaction spending the same inputs
+    /// and paying the same recipient outputs, with the priority fee deducted from the change
+    /// output. If the remaining change is dust, it is absorbed into the transaction fees.
+    fn generate_replacement_transaction(&self, context: &mut Context, original: &PendingTransaction) -> Result<PendingTransaction> {
+        let calc = &self.inner.mass_calculator;
+        let original_tx = original.transaction();
+        let additional_fee = self.inner.final_transaction_priority_fee.additional();
+
+        let change = original.change_value();
+        let change_output_value = change
+            .checked_sub(additional_fee)
+            .ok_or(Error::GeneratorReplacementInsufficientChange { additional_fee, change })?;
+        let change_output_value = if calc.is_dust(change_output_value) { 0 } else { change_output_value };
+
+        let mut outputs = original.recipient_outputs();
+        if change_output_value > 0 {
+            outputs.push(TransactionOutput::new(change_output_value, pay_to_address_script(original.change_address())));
+        }
+
+        // inputs are re-created without signatures, preserving the order of the original inputs
+        let inputs = original_tx
+            .inputs
+            .iter()
+            .map(|input| TransactionInput::new(input.previous_outpoint, vec![], input.sequence, input.sig_op_count))
+            .collect::<Vec<_>>();
+        let utxo_entry_references = inputs
+            .iter()
+            .map(|input| {
+                original
+                    .utxo_entries()
+                    .iter()
+                    .find(|utxo| TransactionOutpoint::from(utxo.utxo.outpoint.clone()) == input.previous_outpoint)
+                    .cloned()
+                    .ok_or(Error::GeneratorReplacementAltersOutputs)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let tx = Transaction::new(
+            original_tx.version,
+            inputs,
+            outputs,
+            original_tx.lock_time,
+            original_tx.subnetwork_id.clone(),
+            original_tx.gas,
+            original_tx.payload.clone(),
+        );
+        validate_replacement_transaction(&original_tx, &tx, original.recipient_outputs().len())?;
+        check_transaction_sig_op_count(&tx, &self.inner.consensus_params)?;
+
+        let aggregate_input_value = original.aggregate_input_value();
+        let aggregate_output_value = tx.outputs.iter().map(|output| output.value).sum::<u64>();
+        let transaction_fees = aggregate_input_value - aggregate_output_value;
+
+        // a lower change value increases the storage mass of the transaction
+        let compute_mass = calc.calc_mass_for_signed_transaction(&tx, self.inner.minimum_signatures);
+        let storage_mass =
+            calc.calc_storage_mass_for_transaction(false, &utxo_entry_references, &tx.outputs).ok_or(Error::MassCalculationError)?;
+        let transaction_mass = calc.combine_mass(compute_mass, storage_mass);
+        if transaction_mass > MAXIMUM_STANDARD_TRANSACTION_MASS {
+            return Err(Error::GeneratorTransactionIsTooHeavy);
+        }
+        let minimum_fees = calc.calc_minimum_transaction_fee_from_mass(transaction_mass);
+        if transaction_fees < minimum_fees || transaction_fees <= original.fees() {
+            return Err(Error::GeneratorReplacementFees);
+        }
+
+        context.aggregated_utxos += utxo_entry_references.len();
+        context.aggregate_fees += transaction_fees;
+        context.final_transaction_id = Some(tx.id());
+        context.number_of_transactions += 1;
+
+        PendingTransaction::try_new(
+            self,
+            tx,
+            utxo_entry_references,
+            original.addresses().clone(),
+            original.payment_value(),
+            change_output_value,
+            aggregate_input_value,
+            aggregate_output_value,
+            transaction_mass,
+            transaction_fees,
+            DataKind::Final,
+            None,
+            Some(original.id()),
+        )
+    }
+
+    /// Returns the change address for the next transaction. When change address
+    /// rotation is enabled, a new address is derived from the change address manager
+    /// and also returned as the second tuple element so that it can be persisted.
+    fn next_change_address(&self) -> Result<(Address, Option<Address>)> {
+        if let Some(change_address_manager) = self.inner.change_address_manager.as_ref() {
+            let change_address = change_address_manager.new_address()?;
+            Ok((change_address.clone(), Some(change_address)))
+        } else {
+            Ok((self.inner.change_address.clone(), None))
+        }
+    }
+
     fn create_batch_utxo_entry_reference(
         txid: TransactionId,
+        index: TransactionIndexType,
         amount: u64,
         script_public_key: ScriptPublicKey,
         address: &Address,
     ) -> UtxoEntryReference {
         let entry = cctx::UtxoEntry { amount, script_public_key, block_daa_score: UNACCEPTED_DAA_SCORE, is_coinbase: false };
-        let outpoint = TransactionOutpoint::new(txid, 0);
+        let outpoint = TransactionOutpoint::new(txid, index);
         let utxo = UtxoEntry { address: Some(address.clone()), outpoint: outpoint.into(), entry };
         UtxoEntryReference { utxo: Arc::new(utxo) }
     }
@@ -1055,6 +1412,23 @@ impl Generator {
             final_transaction_amount: self.final_transaction_value_no_fees(),
             final_transaction_id: context.final_transaction_id,
             number_of_generated_transactions: context.number_of_transactions,
+            number_of_payment_transactions: self.inner.payment_chunks.len(),
         }
     }
 }
+
+/// Ensures that the `replacement` transaction spends the same inputs as the `original`
+/// transaction and that the first `recipient_outputs` outputs paying the recipients are
+/// unchanged. Only the change output (the last output, if any) may differ.
+pub fn validate_replacement_transaction(original: &Transaction, replacement: &Transaction, recipient_outputs: usize) -> Result<()> {
+    let same_inputs = original.inputs.len() == replacement.inputs.len()
+        && original.inputs.iter().zip(replacement.inputs.iter()).all(|(a, b)| a.previous_outpoint == b.previous_outpoint);
+    let same_recipients = original.outputs.len() >= recipient_outputs
+        && replacement.outputs.len() >= recipient_outputs
+        && replacement.outputs.len() <= recipient_outputs + 1
+        && original.outputs[..recipient_outputs] == replacement.outputs[..recipient_outputs];
+    if !same_inputs || !same_recipients || original.payload != replacement.payload {
+        return Err(Error::GeneratorReplacementAltersOutputs);
+    }
+    Ok(())
+}
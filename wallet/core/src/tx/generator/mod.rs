@@ -7,6 +7,7 @@
 pub mod generator;
 pub mod iterator;
 pub mod pending;
+pub mod progress;
 pub mod settings;
 pub mod signer;
 pub mod stream;
@@ -15,6 +16,7 @@ pub mod summary;
 pub use generator::*;
 pub use iterator::*;
 pub use pending::*;
+pub use progress::*;
 pub use settings::*;
 pub use signer::*;
 pub use stream::*;
@@ -6,10 +6,10 @@
 use crate::imports::*;
 use crate::result::Result;
 use crate::rpc::DynRpcApi;
-use crate::tx::{DataKind, Generator};
-use crate::utxo::{UtxoContext, UtxoEntryReference};
+use crate::tx::{DataKind, Generator, SignerT};
+use crate::utxo::{UtxoContext, UtxoEntryReference, UTXO_RESERVATION_TIMEOUT};
 use kaspa_consensus_core::sign::sign_with_multiple_v2;
-use kaspa_consensus_core::tx::{SignableTransaction, Transaction, TransactionId};
+use kaspa_consensus_core::tx::{SignableTransaction, Transaction, TransactionId, TransactionOutput};
 use kaspa_rpc_core::{RpcTransaction, RpcTransactionId};
 
 pub(crate) struct PendingTransactionInner {
@@ -39,6 +39,22 @@ pub(crate) struct PendingTransactionInner {
     pub(crate) fees: u64,
     /// Indicates the type of the transaction
     pub(crate) kind: DataKind,
+    /// Change address derived for this transaction (if change address rotation is enabled)
+    pub(crate) derived_change_address: Option<Address>,
+    /// Id of the pending transaction replaced by this transaction (fee bumping)
+    pub(crate) replaced_transaction_id: Option<TransactionId>,
+}
+
+impl Drop for PendingTransactionInner {
+    fn drop(&mut self) {
+        // release UTXO entries of a transaction that has been abandoned
+        // (submitted transaction entries are released on submission)
+        if !self.is_submitted.load(Ordering::SeqCst) {
+            if let Some(utxo_context) = self.generator.source_utxo_context() {
+                utxo_context.release(self.utxo_entries.iter());
+            }
+        }
+    }
 }
 
 impl std::fmt::Debug for PendingTransaction {
@@ -53,6 +69,8 @@ impl std::fmt::Debug for PendingTransaction {
             .field("mass", &self.inner.mass)
             .field("fees", &self.inner.fees)
             .field("kind", &self.inner.kind)
+            .field("derived_change_address", &self.inner.derived_change_address)
+            .field("replaced_transaction_id", &self.inner.replaced_transaction_id)
             .field("transaction", &transaction)
             .finish()
     }
@@ -80,11 +98,18 @@ impl PendingTransaction {
         mass: u64,
         fees: u64,
         kind: DataKind,
+        derived_change_address: Option<Address>,
+        replaced_transaction_id: Option<TransactionId>,
     ) -> Result<Self> {
         let id = transaction.id();
         let entries = utxo_entries.iter().map(|e| e.utxo.entry.clone()).collect::<Vec<_>>();
         let signable_tx = Mutex::new(SignableTransaction::with_entries(transaction, entries));
         let utxo_entries = utxo_entries.into_iter().collect::<AHashSet<_>>();
+        // prevent concurrent generators from selecting the same UTXO entries
+        // until the transaction is submitted or abandoned
+        if let Some(utxo_context) = generator.source_utxo_context() {
+            utxo_context.reserve(utxo_entries.iter(), UTXO_RESERVATION_TIMEOUT);
+        }
         Ok(Self {
             inner: Arc::new(PendingTransactionInner {
                 generator: generator.clone(),
@@ -100,6 +125,8 @@ impl PendingTransaction {
                 mass,
                 fees,
                 kind,
+                derived_change_address,
+                replaced_transaction_id,
             }),
         })
     }
@@ -125,6 +152,34 @@ impl PendingTransaction {
         &self.inner.addresses
     }
 
+    /// Change address derived for this transaction when the [`Generator`]
+    /// is configured with change address rotation. The address index has
+    /// been advanced in memory and should be persisted before submission.
+    pub fn derived_change_address(&self) -> Option<&Address> {
+        self.inner.derived_change_address.as_ref()
+    }
+
+    /// Address receiving the change of this transaction
+    pub fn change_address(&self) -> &Address {
+        self.inner.derived_change_address.as_ref().unwrap_or_else(|| self.inner.generator.change_address())
+    }
+
+    /// Id of the pending transaction replaced by this transaction
+    /// when it has been created as a fee bumping replacement.
+    pub fn replaced_transaction_id(&self) -> Option<TransactionId> {
+        self.inner.replaced_transaction_id
+    }
+
+    /// Transaction outputs paying the recipients (the change output,
+    /// when present, is always the last output of a final transaction)
+    pub fn recipient_outputs(&self) -> Vec<TransactionOutput> {
+        let mut outputs = self.inner.signable_tx.lock().unwrap().tx.outputs.clone();
+        if self.inner.change_output_value > 0 {
+            outputs.pop();
+        }
+        outputs
+    }
+
     /// Get UTXO entries [`AHashSet<UtxoEntryReference>`] of the pending transaction
     pub fn utxo_entries(&self) -> &AHashSet<UtxoEntryReference> {
         &self.inner.utxo_entries
@@ -154,8 +209,16 @@ impl PendingTransaction {
         self.inner.kind.is_final()
     }
 
+    /// Signifies a relay transaction consolidating UTXOs to the change address
+    /// (as opposed to a transaction paying the supplied outputs)
     pub fn is_batch(&self) -> bool {
-        !self.inner.kind.is_final()
+        !self.inner.kind.is_payment()
+    }
+
+    /// Signifies a transaction paying a part of the supplied outputs
+    /// that do not fit in the final transaction
+    pub fn is_split(&self) -> bool {
+        self.inner.kind.is_split()
     }
 
     pub fn network_type(&self) -> NetworkType {
@@ -214,6 +277,11 @@ impl PendingTransaction {
 
     pub fn try_sign(&self) -> Result<()> {
         let signer = self.inner.generator.signer().as_ref().expect("no signer in tx generator");
+        self.try_sign_with_signer(signer)
+    }
+
+    /// Sign the transaction with the supplied signer, for transactions produced by a generator without a signer
+    pub fn try_sign_with_signer(&self, signer: &Arc<dyn SignerT>) -> Result<()> {
         let signed_tx = signer.try_sign(self.inner.signable_tx.lock()?.clone(), self.addresses())?;
         *self.inner.signable_tx.lock().unwrap() = signed_tx;
         Ok(())
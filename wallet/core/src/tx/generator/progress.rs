@@ -0,0 +1,28 @@
+//!
+//! [`GeneratorProgress`] is a struct reported by the [`Generator`](crate::tx::Generator)
+//! for each generated transaction, allowing clients to track the progress
+//! of sends that require multiple chained transactions.
+//!
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Optional callback invoked by the [`Generator`](crate::tx::Generator) for
+/// each generated transaction before the transaction is signed.
+pub type GeneratorProgressNotifier = Arc<dyn Fn(&GeneratorProgress) + Send + Sync>;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratorProgress {
+    /// Zero-based index of the generated transaction
+    pub index: usize,
+    /// `true` if this is the final transaction of the chain
+    pub is_final: bool,
+    /// Running total (in SOMPI) of the account funds moved by the transactions
+    /// generated so far, capped by the final transaction amount. For sweep
+    /// transactions this is the total value compounded so far.
+    pub sent: u64,
+    /// Amount (in SOMPI) that remains to be moved before the final transaction
+    /// amount is reached. Always zero for sweep transactions.
+    pub remaining: u64,
+}
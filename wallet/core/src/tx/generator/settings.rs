@@ -3,12 +3,14 @@
 //! constructing and instance of the [`Generator`](crate::tx::Generator).
 //!
 
+use crate::derivation::AddressManager;
 use crate::events::Events;
 use crate::imports::*;
 use crate::result::Result;
-use crate::tx::{Fees, PaymentDestination};
-use crate::utxo::{UtxoContext, UtxoEntryReference, UtxoIterator};
+use crate::tx::{Fees, GeneratorProgressNotifier, PaymentDestination, PaymentOutput, PaymentOutputs, PendingTransaction};
+use crate::utxo::{NetworkParams, UtxoContext, UtxoEntryReference, UtxoIterator};
 use kaspa_addresses::Address;
+use kaspa_txscript::extract_script_pub_key_address;
 use workflow_core::channel::Multiplexer;
 
 pub struct GeneratorSettings {
@@ -26,6 +28,9 @@ pub struct GeneratorSettings {
     pub minimum_signatures: u16,
     // change address
     pub change_address: Address,
+    // optional change address manager; when present, a new change address
+    // is derived for each generated transaction instead of `change_address`
+    pub change_address_manager: Option<Arc<AddressManager>>,
     // applies only to the final transaction
     pub final_transaction_priority_fee: Fees,
     // final transaction outputs
@@ -34,6 +39,14 @@ pub struct GeneratorSettings {
     pub final_transaction_payload: Option<Vec<u8>>,
     // transaction is a transfer between accounts
     pub destination_utxo_context: Option<UtxoContext>,
+    // optional callback reporting progress for each generated transaction
+    pub progress_notifier: Option<GeneratorProgressNotifier>,
+    // pending transaction replaced by the generated transaction (fee bumping)
+    pub replaced_transaction: Option<PendingTransaction>,
+    // maximum number of inputs of each generated transaction
+    pub max_inputs_per_transaction: usize,
+    // maximum number of payment outputs (excluding change) of each generated transaction
+    pub max_outputs_per_transaction: usize,
 }
 
 impl GeneratorSettings {
@@ -57,6 +70,7 @@ impl GeneratorSettings {
             sig_op_count,
             minimum_signatures,
             change_address,
+            change_address_manager: None,
             utxo_iterator: Box::new(utxo_iterator),
             source_utxo_context: Some(account.utxo_context().clone()),
 
@@ -64,6 +78,10 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            progress_notifier: None,
+            replaced_transaction: None,
+            max_inputs_per_transaction: NetworkParams::from(network_id).max_inputs_per_transaction,
+            max_outputs_per_transaction: NetworkParams::from(network_id).max_outputs_per_transaction,
         };
 
         Ok(settings)
@@ -88,6 +106,7 @@ impl GeneratorSettings {
             sig_op_count,
             minimum_signatures,
             change_address,
+            change_address_manager: None,
             utxo_iterator: Box::new(utxo_iterator),
             source_utxo_context: Some(utxo_context),
 
@@ -95,6 +114,10 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            progress_notifier: None,
+            replaced_transaction: None,
+            max_inputs_per_transaction: NetworkParams::from(network_id).max_inputs_per_transaction,
+            max_outputs_per_transaction: NetworkParams::from(network_id).max_outputs_per_transaction,
         };
 
         Ok(settings)
@@ -117,6 +140,7 @@ impl GeneratorSettings {
             sig_op_count,
             minimum_signatures,
             change_address,
+            change_address_manager: None,
             utxo_iterator: Box::new(utxo_iterator),
             source_utxo_context: None,
 
@@ -124,6 +148,58 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            progress_notifier: None,
+            replaced_transaction: None,
+            max_inputs_per_transaction: NetworkParams::from(network_id).max_inputs_per_transaction,
+            max_outputs_per_transaction: NetworkParams::from(network_id).max_outputs_per_transaction,
+        };
+
+        Ok(settings)
+    }
+
+    /// Create settings for a replacement of the `original` pending transaction (fee bumping).
+    /// The replacement spends the same inputs and pays the same recipient outputs as the
+    /// original transaction, deducting `additional_fee` from its change output. The generator
+    /// produces a single transaction that must be signed again before submission.
+    pub fn try_new_with_replacement(original: &PendingTransaction, additional_fee: u64) -> Result<Self> {
+        if !original.is_final() || original.payment_value().is_none() || original.replaced_transaction_id().is_some() {
+            return Err(Error::GeneratorReplacementNotSupported);
+        }
+        if additional_fee == 0 {
+            return Err(Error::GeneratorReplacementFees);
+        }
+
+        let generator = original.generator();
+        let transaction = original.transaction();
+        let outputs = original
+            .recipient_outputs()
+            .iter()
+            .map(|output| {
+                let address = extract_script_pub_key_address(&output.script_public_key, generator.network_id().into())?;
+                Ok(PaymentOutput::new(address, output.value))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // the replacement pays the recipients in a single transaction like the original
+        let max_outputs_per_transaction = outputs.len().max(1);
+
+        let settings = GeneratorSettings {
+            network_id: generator.network_id(),
+            multiplexer: generator.multiplexer().clone(),
+            sig_op_count: generator.sig_op_count(),
+            minimum_signatures: generator.minimum_signatures(),
+            change_address: original.change_address().clone(),
+            change_address_manager: None,
+            utxo_iterator: Box::new(std::iter::empty()),
+            source_utxo_context: generator.source_utxo_context().clone(),
+
+            final_transaction_priority_fee: Fees::SenderPays(additional_fee),
+            final_transaction_destination: PaymentOutputs { outputs }.into(),
+            final_transaction_payload: Some(transaction.payload.clone()),
+            destination_utxo_context: generator.destination_utxo_context().clone(),
+            progress_notifier: None,
+            replaced_transaction: Some(original.clone()),
+            max_inputs_per_transaction: generator.max_inputs_per_transaction(),
+            max_outputs_per_transaction,
         };
 
         Ok(settings)
@@ -133,4 +209,35 @@ impl GeneratorSettings {
         self.destination_utxo_context = Some(destination_utxo_context.clone());
         self
     }
+
+    /// Derive a fresh change address from the supplied [`AddressManager`] for every
+    /// generated transaction. Derived addresses advance the manager index and must be
+    /// persisted by the caller (see [`DerivationCapableAccount::commit_change_address`](crate::account::DerivationCapableAccount::commit_change_address)).
+    pub fn change_address_rotation(mut self, change_address_manager: Arc<AddressManager>) -> Self {
+        self.change_address_manager = Some(change_address_manager);
+        self
+    }
+
+    /// Report [`GeneratorProgress`](crate::tx::GeneratorProgress) for each generated
+    /// transaction. The notifier is invoked before the transaction is signed.
+    pub fn progress_notifier(mut self, progress_notifier: GeneratorProgressNotifier) -> Self {
+        self.progress_notifier = Some(progress_notifier);
+        self
+    }
+
+    /// Limit the number of inputs of each generated transaction (the default is network
+    /// specific, see [`NetworkParams`]). Inputs exceeding the limit are consolidated by
+    /// relay transactions, in the same way as inputs exceeding the transaction mass.
+    pub fn max_inputs_per_transaction(mut self, max_inputs_per_transaction: usize) -> Self {
+        self.max_inputs_per_transaction = max_inputs_per_transaction;
+        self
+    }
+
+    /// Limit the number of payment outputs of each generated transaction (the default is
+    /// network specific, see [`NetworkParams`]). Outputs exceeding the limit or the mass
+    /// of a single transaction are paid by a chain of split payment transactions.
+    pub fn max_outputs_per_transaction(mut self, max_outputs_per_transaction: usize) -> Self {
+        self.max_outputs_per_transaction = max_outputs_per_transaction;
+        self
+    }
 }
@@ -2,16 +2,37 @@
 //! Transaction signing trait and generic signer implementations..
 //!
 
+use crate::derivation::{build_derivate_paths, build_derivate_paths_with_account_path};
 use crate::imports::*;
 use crate::result::Result;
 use crate::secret::Secret;
-use kaspa_bip32::PrivateKey;
+use kaspa_bip32::{ChildNumber, DerivationPath, PrivateKey};
+use kaspa_consensus_core::hashing::sighash_type::SIG_HASH_ALL;
 use kaspa_consensus_core::{sign::sign_with_multiple_v2, tx::SignableTransaction};
+use kaspa_hashes::Hash;
+use kaspa_txscript::extract_script_pub_key_address;
+use secp256k1::schnorr::Signature;
 
 pub trait SignerT: Send + Sync + 'static {
     fn try_sign(&self, transaction: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction>;
 }
 
+/// Signer of individual transaction inputs. Receives the signature hash of an input
+/// (see [`MutableTransaction::signing_hashes`](kaspa_consensus_core::tx::MutableTransaction::signing_hashes))
+/// and the derivation path of the key that must sign it. This trait is implemented
+/// by [`Signer`] and can be implemented by external devices such as hardware wallets.
+/// Use [`AccountInputSigner`] to sign account transactions with an input signer.
+pub trait InputSignerT: Send + Sync + 'static {
+    fn sign_input(&self, digest: &Hash, derivation_path: &DerivationPath) -> Result<Signature>;
+}
+
+/// Creates a schnorr signature of the digest.
+fn sign_digest(digest: &Hash, secret_key: &secp256k1::SecretKey) -> Result<Signature> {
+    let keypair = secp256k1::KeyPair::from_secret_key(secp256k1::SECP256K1, secret_key);
+    let msg = secp256k1::Message::from_slice(digest.as_bytes().as_slice())?;
+    Ok(keypair.sign_schnorr(msg))
+}
+
 struct Inner {
     keydata: PrvKeyData,
     account: Arc<dyn Account>,
@@ -56,6 +77,77 @@ impl SignerT for Signer {
     }
 }
 
+impl InputSignerT for Signer {
+    fn sign_input(&self, digest: &Hash, derivation_path: &DerivationPath) -> Result<Signature> {
+        let xkey = self.inner.keydata.get_xprv(self.inner.payment_secret.as_ref())?;
+        sign_digest(digest, xkey.derive_path(derivation_path.clone())?.private_key())
+    }
+}
+
+// ---
+
+/// [`SignerT`] implementation that signs each input of an account transaction using
+/// an [`InputSignerT`]. Derivation paths of the input keys are resolved from the
+/// derivation indexes of the account addresses. Only BIP32 accounts are supported:
+/// legacy account keys are not derived along a BIP32 path and multisig inputs
+/// require multisig signature scripts.
+pub struct AccountInputSigner {
+    account: Arc<dyn DerivationCapableAccount>,
+    signer: Arc<dyn InputSignerT>,
+}
+
+impl AccountInputSigner {
+    pub fn try_new(account: Arc<dyn Account>, signer: Arc<dyn InputSignerT>) -> Result<Self> {
+        if account.account_kind().as_ref() != BIP32_ACCOUNT_KIND {
+            return Err(Error::custom(format!("Input signers are not supported by {} accounts", account.account_kind())));
+        }
+        Ok(Self { account: account.as_derivation_capable()?, signer })
+    }
+
+    fn derivation_paths<'l>(&self, addresses: &[&'l Address]) -> Result<HashMap<&'l Address, DerivationPath>> {
+        let account = &self.account;
+        let (receive_path, change_path) = if let Some(account_path) = account.account_path() {
+            build_derivate_paths_with_account_path(account_path)?
+        } else {
+            build_derivate_paths(&account.account_kind(), account.account_index(), account.cosigner_index())?
+        };
+        let (receive, change) = account.derivation().addresses_indexes(addresses)?;
+        let mut paths = HashMap::new();
+        for (path, indexes) in [(receive_path, receive), (change_path, change)] {
+            for (address, index) in indexes {
+                let mut path = path.clone();
+                path.push(ChildNumber::new(index, false)?);
+                paths.insert(address, path);
+            }
+        }
+        Ok(paths)
+    }
+}
+
+impl SignerT for AccountInputSigner {
+    fn try_sign(&self, mut mutable_tx: SignableTransaction, _addresses: &[Address]) -> Result<SignableTransaction> {
+        let digests = mutable_tx.signing_hashes(SIG_HASH_ALL)?;
+
+        let prefix = Prefix::from(self.account.wallet().network_id()?);
+        let addresses = mutable_tx
+            .entries
+            .iter()
+            .flatten()
+            .map(|entry| Ok(extract_script_pub_key_address(&entry.script_public_key, prefix)?))
+            .collect::<Result<Vec<_>>>()?;
+        let paths = self.derivation_paths(&addresses.iter().collect::<Vec<_>>())?;
+
+        for (input, (address, digest)) in mutable_tx.tx.inputs.iter_mut().zip(addresses.iter().zip(digests)) {
+            let path = paths.get(address).ok_or_else(|| Error::custom(format!("Address {address} does not belong to the account")))?;
+            let signature = self.signer.sign_input(&digest, path)?;
+            // This represents OP_DATA_65 <SIGNATURE+SIGHASH_TYPE> (since signature length is 64 bytes and SIGHASH_TYPE is one byte)
+            input.signature_script = std::iter::once(65u8).chain(*signature.as_ref()).chain([SIG_HASH_ALL.to_u8()]).collect();
+        }
+
+        Ok(mutable_tx)
+    }
+}
+
 // ---
 
 struct KeydataSignerInner {
@@ -18,6 +18,7 @@ pub struct GeneratorSummary {
     pub aggregated_utxos: usize,
     pub aggregated_fees: u64,
     pub number_of_generated_transactions: usize,
+    pub number_of_payment_transactions: usize,
     pub final_transaction_amount: Option<u64>,
     pub final_transaction_id: Option<TransactionId>,
 }
@@ -43,6 +44,12 @@ impl GeneratorSummary {
         self.number_of_generated_transactions
     }
 
+    /// Number of transactions paying the supplied outputs (more than one
+    /// if the outputs do not fit in a single transaction, zero for sweeps)
+    pub fn number_of_payment_transactions(&self) -> usize {
+        self.number_of_payment_transactions
+    }
+
     pub fn final_transaction_amount(&self) -> Option<u64> {
         self.final_transaction_amount
     }
@@ -54,11 +61,14 @@ impl GeneratorSummary {
 
 impl fmt::Display for GeneratorSummary {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let transactions = if self.number_of_generated_transactions == 1 {
+        let mut transactions = if self.number_of_generated_transactions == 1 {
             "".to_string()
         } else {
             format!("Batch Transactions: {}", self.number_of_generated_transactions)
         };
+        if self.number_of_payment_transactions > 1 {
+            transactions.push_str(&format!("  Payment Transactions: {}", self.number_of_payment_transactions));
+        }
 
         if let Some(final_transaction_amount) = self.final_transaction_amount {
             let total = final_transaction_amount + self.aggregated_fees;
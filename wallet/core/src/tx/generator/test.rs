@@ -1,17 +1,24 @@
 #![allow(clippy::inconsistent_digit_grouping)]
 
+use crate::abortable::Abortable;
 use crate::error::Error;
 use crate::result::Result;
-use crate::tx::{Fees, MassCalculator, PaymentDestination};
-use crate::utxo::UtxoEntryReference;
+use crate::tx::{Fees, MassCalculator, PaymentDestination, PaymentOutput};
+use crate::utxo::{NetworkParams, UtxoEntryReference};
 use crate::{tx::PaymentOutputs, utils::kaspa_to_sompi};
 use kaspa_addresses::Address;
+use kaspa_consensus_core::config::params::Params;
+use kaspa_consensus_core::errors::tx::TxRuleError;
 use kaspa_consensus_core::network::{NetworkId, NetworkType};
-use kaspa_consensus_core::tx::Transaction;
+use kaspa_consensus_core::tx::{transaction_sig_op_count, Transaction, TransactionOutpoint};
+use kaspa_txscript::pay_to_address_script;
 use rand::prelude::*;
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use workflow_log::style;
 
 use super::*;
@@ -107,6 +114,10 @@ impl GeneratorSummaryExtension for GeneratorSummary {
         );
         let aggregated_fees = accumulator.list.iter().map(|pt| pt.fees()).sum::<u64>();
         assert_eq!(self.aggregated_fees, aggregated_fees, "aggregated fees");
+        if self.final_transaction_amount.is_some() {
+            let payment_transactions = accumulator.list.iter().filter(|pt| !pt.is_batch()).count();
+            assert_eq!(self.number_of_payment_transactions, payment_transactions, "number of payment transactions");
+        }
         self
     }
 }
@@ -168,7 +179,7 @@ fn validate(pt: &PendingTransaction) {
     );
 
     let calc = MassCalculator::new(&pt.network_type().into(), network_params);
-    let additional_mass = if pt.is_final() { 0 } else { network_params.additional_compound_transaction_mass };
+    let additional_mass = if pt.is_batch() { network_params.additional_compound_transaction_mass } else { 0 };
     let compute_mass = calc.calc_mass_for_signed_transaction(&tx, 1);
 
     let utxo_entries = pt.utxo_entries().iter().cloned().collect::<Vec<_>>();
@@ -198,7 +209,7 @@ where
 
     let pt_fees = pt.fees();
     let calc = MassCalculator::new(&pt.network_type().into(), network_params);
-    let additional_mass = if pt.is_final() { 0 } else { network_params.additional_compound_transaction_mass };
+    let additional_mass = if pt.is_batch() { network_params.additional_compound_transaction_mass } else { 0 };
 
     let compute_mass = calc.calc_mass_for_signed_transaction(&tx, 1);
 
@@ -380,6 +391,46 @@ pub(crate) fn make_generator<F>(
     change_address: F,
     final_transaction_destination: PaymentDestination,
 ) -> Result<Generator>
+where
+    F: FnOnce(NetworkType) -> Address,
+{
+    make_generator_with_payload(network_id, head, tail, fees, change_address, final_transaction_destination, None, None)
+}
+
+pub(crate) fn make_generator_with_payload<F>(
+    network_id: NetworkId,
+    head: &[f64],
+    tail: &[f64],
+    fees: Fees,
+    change_address: F,
+    final_transaction_destination: PaymentDestination,
+    final_transaction_payload: Option<Vec<u8>>,
+    abortable: Option<&Abortable>,
+) -> Result<Generator>
+where
+    F: FnOnce(NetworkType) -> Address,
+{
+    let settings = make_generator_settings(
+        network_id,
+        head,
+        tail,
+        fees,
+        change_address,
+        final_transaction_destination,
+        final_transaction_payload,
+    );
+    Generator::try_new(settings, None, abortable)
+}
+
+pub(crate) fn make_generator_settings<F>(
+    network_id: NetworkId,
+    head: &[f64],
+    tail: &[f64],
+    fees: Fees,
+    change_address: F,
+    final_transaction_destination: PaymentDestination,
+    final_transaction_payload: Option<Vec<u8>>,
+) -> GeneratorSettings
 where
     F: FnOnce(NetworkType) -> Address,
 {
@@ -394,24 +445,26 @@ where
     let source_utxo_context = None;
     let destination_utxo_context = None;
     let final_priority_fee = fees;
-    let final_transaction_payload = None;
     let change_address = change_address(network_id.into());
 
-    let settings = GeneratorSettings {
+    GeneratorSettings {
         network_id,
         multiplexer,
         sig_op_count,
         minimum_signatures,
         change_address,
+        change_address_manager: None,
         utxo_iterator,
         source_utxo_context,
         destination_utxo_context,
         final_transaction_priority_fee: final_priority_fee,
         final_transaction_destination,
         final_transaction_payload,
-    };
-
-    Generator::try_new(settings, None, None)
+        progress_notifier: None,
+        replaced_transaction: None,
+        max_inputs_per_transaction: NetworkParams::from(network_id).max_inputs_per_transaction,
+        max_outputs_per_transaction: NetworkParams::from(network_id).max_outputs_per_transaction,
+    }
 }
 
 pub(crate) fn change_address(network_type: NetworkType) -> Address {
@@ -679,3 +732,374 @@ fn test_generator_inputs_903_outputs_2_fees_exclude() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_generator_dust_payment_output() -> Result<()> {
+    let result = generator(test_network_id(), &[10.0; 2], &[], Fees::sender(Kaspa(0.0)), [(output_address, Sompi(100))].as_slice());
+    match result {
+        Err(Error::DustOutput { address, amount }) => {
+            assert_eq!(address, output_address(NetworkType::Testnet));
+            assert_eq!(amount, 100);
+        }
+        Err(err) => panic!("expecting dust output error, received: {:?}", err),
+        Ok(_) => panic!("expecting dust output error, instead received a generator"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_generator_dust_change_absorbed_into_fees() -> Result<()> {
+    // leave a remainder that covers the transaction fees plus a dust-sized change amount
+    generator(test_network_id(), &[10.0], &[], Fees::sender(Kaspa(0.0)), [(output_address, Sompi(10_0000_0000 - 2_200))].as_slice())
+        .unwrap()
+        .harness()
+        .fetch(&Expected {
+            is_final: true,
+            input_count: 1,
+            aggregate_input_value: Kaspa(10.0),
+            output_count: 1,
+            priority_fees: FeesExpected::sender(Kaspa(0.0)),
+        })
+        .finalize();
+
+    Ok(())
+}
+
+fn payload_generator(payload: Option<Vec<u8>>) -> Result<Generator> {
+    let network_id = test_network_id();
+    let outputs = PaymentOutputs::from((output_address(network_id.into()), kaspa_to_sompi(1.0)));
+    make_generator_with_payload(network_id, &[10.0], &[], Fees::sender(Kaspa(0.0)), change_address, outputs.into(), payload, None)
+}
+
+#[test]
+fn test_generator_payload_too_large() -> Result<()> {
+    let network_id = test_network_id();
+    let max = MassCalculator::new(&network_id.into(), &NetworkParams::from(network_id)).max_payload_size();
+
+    match payload_generator(Some(vec![0; max + 1])) {
+        Err(Error::GeneratorPayloadTooLarge { size, max: limit }) => {
+            assert_eq!(size, max + 1);
+            assert_eq!(limit, max);
+        }
+        Err(err) => panic!("expecting payload too large error, received: {:?}", err),
+        Ok(_) => panic!("expecting payload too large error, instead received a generator"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_generator_payload_mass() -> Result<()> {
+    let payload = vec![0xab; 1000];
+    let with_payload = payload_generator(Some(payload.clone()))?.generate_transaction()?.expect("transaction with payload");
+    let without_payload = payload_generator(None)?.generate_transaction()?.expect("transaction without payload");
+
+    // the generated mass is cross-checked against the mass of the signed transaction
+    validate(&with_payload);
+    assert_eq!(with_payload.transaction().payload, payload);
+
+    let calc = MassCalculator::new(&test_network_id().into(), with_payload.generator().network_params());
+    let payload_mass = calc.calc_mass_for_payload(payload.len());
+    assert_eq!(with_payload.inner.mass, without_payload.inner.mass + payload_mass);
+    assert_eq!(with_payload.fees(), calc.calc_minimum_transaction_fee_from_mass(with_payload.inner.mass));
+    assert!(with_payload.fees() > without_payload.fees());
+
+    Ok(())
+}
+
+#[test]
+fn test_generator_sig_op_limit() -> Result<()> {
+    let network_id = test_network_id();
+    let max = Params::from(network_id).max_transaction_sig_ops();
+    let settings = |sig_op_count: u8| {
+        let outputs = PaymentOutputs::from((output_address(network_id.into()), kaspa_to_sompi(50.0)));
+        let settings =
+            make_generator_settings(network_id, &[10.0; 10], &[], Fees::sender(Kaspa(0.0)), change_address, outputs.into(), None);
+        GeneratorSettings { sig_op_count, ..settings }
+    };
+
+    // a single input above the limit can not fit in any transaction
+    match Generator::try_new(settings(max as u8 + 1), None, None) {
+        Err(Error::TxRuleError(TxRuleError::TooManySigOps(sig_ops, limit))) => {
+            assert_eq!(sig_ops, max + 1);
+            assert_eq!(limit, max);
+        }
+        Err(err) => panic!("expecting too many sig ops error, received: {:?}", err),
+        Ok(_) => panic!("expecting too many sig ops error, instead received a generator"),
+    }
+
+    // inputs are split across transactions within the limit
+    let generator = Generator::try_new(settings((max / 4) as u8), None, None)?;
+    let mut transactions = 0;
+    while let Some(pending) = generator.generate_transaction()? {
+        assert!(transaction_sig_op_count(&pending.transaction()) <= max);
+        transactions += 1;
+    }
+    assert!(transactions > 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_generator_max_inputs_per_transaction() -> Result<()> {
+    let network_id = test_network_id();
+    let settings = |max_inputs_per_transaction: usize| {
+        let outputs = PaymentOutputs::from((output_address(network_id.into()), kaspa_to_sompi(250.0)));
+        make_generator_settings(network_id, &[10.0; 30], &[], Fees::sender(Kaspa(0.0)), change_address, outputs.into(), None)
+            .max_inputs_per_transaction(max_inputs_per_transaction)
+    };
+
+    assert!(matches!(Generator::try_new(settings(1), None, None), Err(Error::GeneratorMaxInputsPerTransaction(1))));
+
+    // the 26 inputs needed are consolidated by relay transactions within the limit
+    let generator = Generator::try_new(settings(8), None, None)?;
+    let mut transactions = vec![];
+    while let Some(pending) = generator.generate_transaction()? {
+        assert!(pending.transaction().inputs.len() <= 8);
+        transactions.push(pending);
+    }
+    assert!(transactions.len() > 1);
+    let last = transactions.last().unwrap();
+    assert!(last.is_final());
+    assert_eq!(last.payment_value(), Some(kaspa_to_sompi(250.0)));
+
+    // without the limit, the inputs fit in a single transaction
+    let generator = Generator::try_new(settings(NetworkParams::from(network_id).max_inputs_per_transaction), None, None)?;
+    let pending = generator.generate_transaction()?.unwrap();
+    assert!(pending.is_final());
+    assert!(generator.generate_transaction()?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_generator_split_payment_outputs() -> Result<()> {
+    let network_id = test_network_id();
+    let recipient = output_address(network_id.into());
+    // distinct amounts identify the payment of each recipient output
+    let payments = (0..300).map(|n| kaspa_to_sompi(10.0) + n).collect::<Vec<_>>();
+    let outputs = PaymentOutputs { outputs: payments.iter().map(|amount| PaymentOutput::new(recipient.clone(), *amount)).collect() };
+    let settings = |max_outputs_per_transaction: usize| {
+        make_generator_settings(network_id, &[100.0; 50], &[], Fees::sender(Kaspa(5.0)), change_address, outputs.clone().into(), None)
+            .max_outputs_per_transaction(max_outputs_per_transaction)
+    };
+
+    // returns the number of payment transactions after validating the generated transactions
+    let check = |generator: Generator, max_outputs_per_transaction: usize| -> usize {
+        let harness = generator.harness().validate();
+        let transactions = harness.accumulator.borrow().list.clone();
+        harness.finalize();
+
+        // the final transaction is preceded by split payment transactions
+        let payment_transactions = transactions.iter().filter(|pt| !pt.is_batch()).collect::<Vec<_>>();
+        let (last, splits) = payment_transactions.split_last().unwrap();
+        assert!(last.is_final() && transactions.last().unwrap().is_final());
+        assert!(last.fees() >= kaspa_to_sompi(5.0));
+        for pt in splits {
+            assert!(pt.is_split());
+            assert!(pt.fees() < kaspa_to_sompi(5.0), "priority fees apply only to the final transaction");
+        }
+
+        // every recipient is paid exactly once
+        let change_script = pay_to_address_script(&change_address(network_id.into()));
+        let mut paid = vec![];
+        for pt in payment_transactions.iter() {
+            let recipient_outputs =
+                pt.transaction().outputs.into_iter().filter(|output| output.script_public_key != change_script).collect::<Vec<_>>();
+            assert!(recipient_outputs.len() <= max_outputs_per_transaction);
+            assert_eq!(pt.payment_value(), Some(recipient_outputs.iter().map(|output| output.value).sum()));
+            paid.extend(recipient_outputs.into_iter().map(|output| output.value));
+        }
+        paid.sort();
+        assert_eq!(paid, payments);
+
+        // the change of each split payment transaction funds the following transactions
+        let spent =
+            transactions.iter().flat_map(|pt| pt.transaction().inputs).map(|input| input.previous_outpoint).collect::<HashSet<_>>();
+        for pt in splits.iter().filter(|pt| pt.change_value() > 0) {
+            let change_index = pt.transaction().outputs.len() as u32 - 1;
+            assert!(spent.contains(&TransactionOutpoint::new(pt.id(), change_index)));
+        }
+
+        payment_transactions.len()
+    };
+
+    // outputs exceeding the transaction mass are split
+    let max = NetworkParams::from(network_id).max_outputs_per_transaction;
+    assert!(check(Generator::try_new(settings(max), None, None)?, max) > 1);
+
+    // outputs exceeding the maximum number of outputs per transaction are split
+    assert_eq!(check(Generator::try_new(settings(40), None, None)?, 40), 8);
+
+    assert!(matches!(Generator::try_new(settings(0), None, None), Err(Error::GeneratorMaxOutputsPerTransaction(0))));
+
+    // the total value of the outputs must not overflow
+    let outputs = PaymentOutputs { outputs: vec![PaymentOutput::new(recipient, u64::MAX / 2 + 1); 2] };
+    let settings =
+        make_generator_settings(network_id, &[100.0; 50], &[], Fees::sender(Kaspa(5.0)), change_address, outputs.into(), None);
+    assert!(matches!(Generator::try_new(settings, None, None), Err(Error::Overflow)));
+
+    Ok(())
+}
+
+#[test]
+fn test_generator_abort_timeout() -> Result<()> {
+    let network_id = test_network_id();
+    let abortable = Abortable::with_timeout(Duration::from_millis(500));
+    let outputs = PaymentOutputs::from((output_address(network_id.into()), kaspa_to_sompi(9_000.0)));
+    let generator = make_generator_with_payload(
+        network_id,
+        &[10.0; 1_000],
+        &[],
+        Fees::sender(Kaspa(5.0)),
+        change_address,
+        outputs.into(),
+        None,
+        Some(&abortable),
+    )?;
+
+    // the first transaction of the chain is generated before the timeout elapses
+    assert!(!generator.generate_transaction()?.expect("first chained transaction").is_final());
+    std::thread::sleep(Duration::from_millis(600));
+
+    match generator.generate_transaction() {
+        Err(Error::AbortedWithReason(reason)) => assert_eq!(reason, "timed out after 500ms"),
+        Err(err) => panic!("expecting timeout error, received: {:?}", err),
+        Ok(_) => panic!("expecting timeout error, instead received a transaction"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_generator_progress() -> Result<()> {
+    let network_id = test_network_id();
+    let amount = kaspa_to_sompi(9_000.0);
+    let outputs = PaymentOutputs::from((output_address(network_id.into()), amount));
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let progress_ = progress.clone();
+    let settings =
+        make_generator_settings(network_id, &[10.0; 1_000], &[], Fees::sender(Kaspa(5.0)), change_address, outputs.into(), None)
+            .progress_notifier(Arc::new(move |progress| progress_.lock().unwrap().push(progress.clone())));
+    let generator = Generator::try_new(settings, None, None)?;
+
+    let mut transactions = vec![];
+    while let Some(pt) = generator.generate_transaction()? {
+        // the progress of each transaction is reported before it is returned for signing
+        assert_eq!(progress.lock().unwrap().len(), transactions.len() + 1);
+        transactions.push(pt);
+    }
+
+    let progress = progress.lock().unwrap().clone();
+    assert!(transactions.len() > 1, "expecting a chain of transactions");
+    assert_eq!(progress.len(), transactions.len());
+
+    // relay transactions report the value of the consumed UTXO entries,
+    // relay UTXO entries spent by the following transactions are not counted twice
+    let relay_transaction_ids = transactions.iter().map(|pt| pt.id()).collect::<HashSet<_>>();
+    let mut sent = 0;
+    for (index, (progress, pt)) in progress.iter().zip(transactions.iter()).enumerate() {
+        assert_eq!(progress.index, index);
+        assert_eq!(progress.is_final, pt.is_final());
+        if !pt.is_final() {
+            sent += pt
+                .utxo_entries()
+                .iter()
+                .filter(|utxo| !relay_transaction_ids.contains(utxo.utxo.outpoint.transaction_id_as_ref()))
+                .map(|utxo| utxo.amount())
+                .sum::<u64>();
+            assert_eq!(progress.sent, sent.min(amount));
+        }
+        assert_eq!(progress.sent + progress.remaining, amount);
+    }
+
+    let last = progress.last().unwrap();
+    assert!(last.is_final);
+    assert_eq!((last.sent, last.remaining), (amount, 0));
+    assert!(progress.windows(2).all(|pair| pair[0].sent <= pair[1].sent));
+
+    Ok(())
+}
+
+fn make_replacement(original: &PendingTransaction, additional_fee: u64) -> Result<Option<PendingTransaction>> {
+    let settings = GeneratorSettings::try_new_with_replacement(original, additional_fee)?;
+    Generator::try_new(settings, None, None)?.generate_transaction()
+}
+
+#[test]
+fn test_generator_replacement_fee_bump() -> Result<()> {
+    let network_id = test_network_id();
+    let generator =
+        generator(network_id, &[10.0; 2], &[], Fees::sender(Kaspa(0.0)), [(output_address, Kaspa(15.0))].as_slice()).unwrap();
+    let original = generator.generate_transaction()?.expect("original transaction").validate();
+    assert!(original.is_final());
+
+    let additional_fee = kaspa_to_sompi(0.1);
+    let settings = GeneratorSettings::try_new_with_replacement(&original, additional_fee)?;
+    let generator = Generator::try_new(settings, None, None)?;
+    let replacement = generator.generate_transaction()?.expect("replacement transaction").validate();
+    assert!(generator.generate_transaction()?.is_none(), "a single replacement transaction is expected");
+
+    let (original_tx, replacement_tx) = (original.transaction(), replacement.transaction());
+    assert_eq!(replacement.replaced_transaction_id(), Some(original.id()));
+    assert_ne!(replacement.id(), original.id());
+    assert!(replacement.is_final());
+    assert_eq!(replacement.payment_value(), original.payment_value());
+    assert_eq!(replacement.fees(), original.fees() + additional_fee);
+    assert_eq!(replacement.change_value(), original.change_value() - additional_fee);
+    assert_eq!(replacement.recipient_outputs(), original.recipient_outputs());
+    assert_eq!(replacement_tx.outputs.last().unwrap().script_public_key, original_tx.outputs.last().unwrap().script_public_key);
+    let outpoints = |tx: &Transaction| tx.inputs.iter().map(|input| input.previous_outpoint).collect::<Vec<_>>();
+    assert_eq!(outpoints(&replacement_tx), outpoints(&original_tx));
+    assert!(replacement_tx.inputs.iter().all(|input| input.signature_script.is_empty()));
+    assert_eq!(generator.summary().aggregated_fees, replacement.fees());
+    validate_replacement_transaction(&original_tx, &replacement_tx, 1)?;
+
+    // a replacement can not be replaced again
+    assert!(matches!(make_replacement(&replacement, additional_fee), Err(Error::GeneratorReplacementNotSupported)));
+    // the additional fee is deducted from the change only
+    assert!(matches!(
+        make_replacement(&original, original.change_value() + 1),
+        Err(Error::GeneratorReplacementInsufficientChange { .. })
+    ));
+    assert!(matches!(make_replacement(&original, 0), Err(Error::GeneratorReplacementFees)));
+
+    Ok(())
+}
+
+#[test]
+fn test_generator_replacement_rejects_altered_outputs() -> Result<()> {
+    let network_id = test_network_id();
+    let generator =
+        generator(network_id, &[10.0; 2], &[], Fees::sender(Kaspa(0.0)), [(output_address, Kaspa(15.0))].as_slice()).unwrap();
+    let original = generator.generate_transaction()?.expect("original transaction");
+    let original_tx = original.transaction();
+    let replacement_tx = make_replacement(&original, kaspa_to_sompi(0.1))?.expect("replacement transaction").transaction();
+
+    let is_rejected = |tx: &Transaction| {
+        matches!(validate_replacement_transaction(&original_tx, tx, 1), Err(Error::GeneratorReplacementAltersOutputs))
+    };
+
+    // recipient output value reduced to pay the fee
+    let mut altered = replacement_tx.clone();
+    altered.outputs[0].value -= kaspa_to_sompi(0.1);
+    assert!(is_rejected(&altered));
+
+    // recipient output redirected to the change address
+    let mut altered = replacement_tx.clone();
+    altered.outputs[0].script_public_key = pay_to_address_script(&change_address(network_id.into()));
+    assert!(is_rejected(&altered));
+
+    // additional output
+    let mut altered = replacement_tx.clone();
+    altered.outputs.push(altered.outputs[0].clone());
+    assert!(is_rejected(&altered));
+
+    // missing input
+    let mut altered = replacement_tx.clone();
+    altered.inputs.pop();
+    assert!(is_rejected(&altered));
+
+    Ok(())
+}
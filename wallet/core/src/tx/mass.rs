@@ -8,6 +8,8 @@ use kaspa_consensus_core::{config::params::Params, constants::*, subnets::SUBNET
 use kaspa_consensus_wasm::UtxoEntryReference;
 use kaspa_hashes::HASH_SIZE;
 
+pub use kaspa_consensus_core::constants::MAXIMUM_STANDARD_TRANSACTION_MASS;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MassCombinationStrategy {
     /// `MassCombinator::Add` adds the storage and compute mass.
@@ -24,10 +26,6 @@ pub const SIGNATURE_SIZE: u64 = 1 + 64 + 1; //1 byte for OP_DATA_65 + 64 (length
 /// the mempool and relayed. It is specified in sompi per 1kg (or 1000 grams) of transaction mass.
 pub(crate) const MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
 
-/// MAXIMUM_STANDARD_TRANSACTION_MASS is the maximum mass allowed for transactions that
-/// are considered standard and will therefore be relayed and considered for mining.
-pub const MAXIMUM_STANDARD_TRANSACTION_MASS: u64 = 100_000;
-
 /// minimum_required_transaction_relay_fee returns the minimum transaction fee required
 /// for a transaction with the passed mass to be accepted into the mempool and relayed.
 pub fn calc_minimum_required_transaction_relay_fee(mass: u64) -> u64 {
@@ -254,6 +252,13 @@ impl MassCalculator {
         blank_transaction_serialized_byte_size() * self.mass_per_tx_byte
     }
 
+    /// Maximum payload size (in bytes) of a standard transaction: the mass of
+    /// the payload together with the mass of a blank transaction must not
+    /// exceed [`MAXIMUM_STANDARD_TRANSACTION_MASS`].
+    pub fn max_payload_size(&self) -> usize {
+        (MAXIMUM_STANDARD_TRANSACTION_MASS.saturating_sub(self.blank_transaction_mass()) / self.mass_per_tx_byte) as usize
+    }
+
     pub fn calc_mass_for_payload(&self, payload_byte_size: usize) -> u64 {
         payload_byte_size as u64 * self.mass_per_tx_byte
     }
@@ -2,14 +2,20 @@
 //! Transaction generation and processing primitives.
 //!
 
+pub mod broadcast;
 pub mod consensus;
+pub mod fee_rates;
 pub mod fees;
 pub mod generator;
 pub mod mass;
 pub mod payment;
+pub mod unsigned;
 
+pub use broadcast::*;
 pub use consensus::*;
+pub use fee_rates::*;
 pub use fees::*;
 pub use generator::*;
 pub use mass::*;
 pub use payment::*;
+pub use unsigned::*;
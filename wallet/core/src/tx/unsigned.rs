@@ -0,0 +1,260 @@
+//!
+//! Serializable unsigned transaction container used for offline (air-gapped) signing.
+//!
+
+use crate::derivation::AddressDerivationManagerTrait;
+use crate::imports::*;
+use crate::result::Result;
+use crate::serializer::{IoResult, StorageHeader};
+use crate::tx::PendingTransaction;
+use kaspa_consensus_core::tx::{SignableTransaction, Transaction, UtxoEntry};
+use kaspa_rpc_core::RpcTransaction;
+use kaspa_txscript::extract_script_pub_key_address;
+
+/// Derivation information of the address that owns a transaction input.
+/// Allows the signing wallet to derive the required private key directly,
+/// without having to discover the address through a derivation scan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputDerivation {
+    pub address: Address,
+    pub change: bool,
+    pub index: u32,
+}
+
+impl InputDerivation {
+    pub fn new(address: Address, change: bool, index: u32) -> Self {
+        Self { address, change, index }
+    }
+}
+
+/// Container carrying a transaction together with the UTXO entries and derivation
+/// data of its inputs. Created by a watch-only (online) wallet using
+/// [`Account::create_unsigned_transactions`](crate::account::Account::create_unsigned_transactions),
+/// signed by a wallet holding the private keys using
+/// [`Account::sign_unsigned_transaction`](crate::account::Account::sign_unsigned_transaction)
+/// and then submitted to the network by any wallet.
+///
+/// The container can be encoded as JSON (human-readable, for file exchange)
+/// or Borsh (compact, for QR codes and similar transports).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedTransaction {
+    pub version: u32,
+    #[serde(rename = "network")]
+    pub network_id: NetworkId,
+    pub transaction: Transaction,
+    pub entries: Vec<UtxoEntry>,
+    pub derivations: Vec<InputDerivation>,
+}
+
+impl UnsignedTransaction {
+    const STORAGE_MAGIC: u32 = 0x4e53554b;
+    pub const VERSION: u32 = 1;
+
+    pub fn new(network_id: NetworkId, transaction: Transaction, entries: Vec<UtxoEntry>, derivations: Vec<InputDerivation>) -> Self {
+        Self { version: Self::VERSION, network_id, transaction, entries, derivations }
+    }
+
+    pub(crate) fn try_from_pending(
+        network_id: NetworkId,
+        pending: &PendingTransaction,
+        derivation: &Arc<dyn AddressDerivationManagerTrait>,
+    ) -> Result<Self> {
+        let prefix = Prefix::from(network_id);
+        let signable_tx = pending.inner.signable_tx.lock()?;
+        let entries = signable_tx
+            .entries
+            .iter()
+            .map(|entry| entry.clone().ok_or_else(|| Error::custom("Pending transaction is missing UTXO entry data")))
+            .collect::<Result<Vec<_>>>()?;
+
+        let derivations = entries
+            .iter()
+            .map(|entry| {
+                let address = extract_script_pub_key_address(&entry.script_public_key, prefix)?;
+                let (receive, change) = derivation.addresses_indexes(&[&address])?;
+                let derivation = if let Some((_, index)) = receive.first() {
+                    InputDerivation::new(address.clone(), false, *index)
+                } else if let Some((_, index)) = change.first() {
+                    InputDerivation::new(address.clone(), true, *index)
+                } else {
+                    return Err(Error::custom(format!("Unable to locate derivation index for address {address}")));
+                };
+                Ok(derivation)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::new(network_id, signable_tx.tx.clone(), entries, derivations))
+    }
+
+    pub fn id(&self) -> TransactionId {
+        self.transaction.id()
+    }
+
+    /// Ensures the container was produced by a supported version of the
+    /// software and is destined for the given network.
+    pub fn validate(&self, network_id: &NetworkId) -> Result<()> {
+        if self.version > Self::VERSION {
+            return Err(Error::UnsignedTransactionVersion(self.version, Self::VERSION));
+        }
+
+        if &self.network_id != network_id {
+            return Err(Error::UnsignedTransactionNetworkMismatch(network_id.to_string(), self.network_id.to_string()));
+        }
+
+        if self.entries.len() != self.transaction.inputs.len() || self.derivations.len() != self.transaction.inputs.len() {
+            return Err(Error::custom("Unsigned transaction input data does not match the number of transaction inputs"));
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if every transaction input carries a signature script.
+    pub fn is_signed(&self) -> bool {
+        !self.transaction.inputs.is_empty() && self.transaction.inputs.iter().all(|input| !input.signature_script.is_empty())
+    }
+
+    pub fn signable_transaction(&self) -> SignableTransaction {
+        SignableTransaction::with_entries(self.transaction.clone(), self.entries.clone())
+    }
+
+    pub fn rpc_transaction(&self) -> RpcTransaction {
+        (&self.transaction).into()
+    }
+
+    /// Submit a signed transaction to the network.
+    pub async fn try_submit(&self, rpc: &Arc<DynRpcApi>) -> Result<TransactionId> {
+        if !self.is_signed() {
+            return Err(Error::UnsignedTransactionNotSigned);
+        }
+
+        Ok(rpc.submit_transaction(self.rpc_transaction(), false).await?)
+    }
+
+    pub fn try_to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn try_from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn try_to_borsh(&self) -> Result<Vec<u8>> {
+        Ok(self.try_to_vec()?)
+    }
+
+    pub fn try_from_borsh(data: &[u8]) -> Result<Self> {
+        Ok(Self::try_from_slice(data)?)
+    }
+}
+
+impl BorshSerialize for UnsignedTransaction {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        // the header records the version of the binary layout, which is always the current one
+        StorageHeader::new(Self::STORAGE_MAGIC, Self::VERSION).serialize(writer)?;
+        BorshSerialize::serialize(&self.network_id, writer)?;
+        BorshSerialize::serialize(&self.transaction, writer)?;
+        BorshSerialize::serialize(&self.entries, writer)?;
+        BorshSerialize::serialize(&self.derivations, writer)?;
+
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for UnsignedTransaction {
+    fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
+        let StorageHeader { version, .. } =
+            StorageHeader::deserialize(buf)?.try_magic(Self::STORAGE_MAGIC)?.try_version(Self::VERSION)?;
+
+        let network_id = BorshDeserialize::deserialize(buf)?;
+        // version 0 containers predate the transaction mass in the borsh encoding
+        let transaction =
+            if version == 0 { Transaction::deserialize_borsh_without_mass(buf)? } else { BorshDeserialize::deserialize(buf)? };
+        let entries = BorshDeserialize::deserialize(buf)?;
+        let derivations = BorshDeserialize::deserialize(buf)?;
+
+        Ok(Self { version, network_id, transaction, entries, derivations })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use kaspa_consensus_core::tx::{TransactionInput, TransactionOutpoint, TransactionOutput};
+    use kaspa_txscript::pay_to_address_script;
+
+    fn make_unsigned_transaction() -> UnsignedTransaction {
+        let address = Address::try_from("kaspatest:qz7ulu4c25dh7fzec9zjyrmlhnkzrg4wmf89q7gzr3gfrsj3uz6xjceef60sd").unwrap();
+        let script_public_key = pay_to_address_script(&address);
+        let outpoint = TransactionOutpoint::new(TransactionId::from_slice(&[1; 32]), 0);
+        let transaction = Transaction::new(
+            0,
+            vec![TransactionInput::new(outpoint, vec![], 0, 1)],
+            vec![TransactionOutput::new(1000, script_public_key.clone())],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let entries = vec![UtxoEntry::new(2000, script_public_key, 10, false)];
+        let derivations = vec![InputDerivation::new(address, false, 3)];
+        UnsignedTransaction::new(NetworkId::with_suffix(NetworkType::Testnet, 11), transaction, entries, derivations)
+    }
+
+    #[test]
+    fn test_unsigned_transaction_encoding() -> Result<()> {
+        let unsigned = make_unsigned_transaction();
+
+        let json = unsigned.try_to_json()?;
+        let from_json = UnsignedTransaction::try_from_json(&json)?;
+        assert_eq!(from_json.id(), unsigned.id());
+        assert_eq!(from_json.network_id, unsigned.network_id);
+        assert_eq!(from_json.entries, unsigned.entries);
+        assert_eq!(from_json.derivations, unsigned.derivations);
+
+        let borsh = unsigned.try_to_borsh()?;
+        let from_borsh = UnsignedTransaction::try_from_borsh(&borsh)?;
+        assert_eq!(from_borsh.id(), unsigned.id());
+        assert_eq!(from_borsh.network_id, unsigned.network_id);
+        assert_eq!(from_borsh.entries, unsigned.entries);
+        assert_eq!(from_borsh.derivations, unsigned.derivations);
+
+        // version 0 containers encode the transaction without the mass field
+        unsigned.transaction.set_mass(1000);
+        let mut legacy = vec![];
+        StorageHeader::new(UnsignedTransaction::STORAGE_MAGIC, 0).serialize(&mut legacy)?;
+        BorshSerialize::serialize(&unsigned.network_id, &mut legacy)?;
+        let transaction = unsigned.transaction.try_to_vec()?;
+        legacy.extend_from_slice(&transaction[..transaction.len() - 40]);
+        legacy.extend_from_slice(&transaction[transaction.len() - 32..]);
+        BorshSerialize::serialize(&unsigned.entries, &mut legacy)?;
+        BorshSerialize::serialize(&unsigned.derivations, &mut legacy)?;
+        let from_legacy = UnsignedTransaction::try_from_borsh(&legacy)?;
+        assert_eq!(from_legacy.version, 0);
+        assert_eq!(from_legacy.id(), unsigned.id());
+        assert_eq!(from_legacy.transaction.mass(), 0);
+        assert_eq!(from_legacy.entries, unsigned.entries);
+        assert_eq!(UnsignedTransaction::try_from_borsh(&from_legacy.try_to_borsh()?)?.id(), unsigned.id());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsigned_transaction_validation() {
+        let mut unsigned = make_unsigned_transaction();
+        assert!(!unsigned.is_signed());
+        assert!(unsigned.validate(&NetworkId::with_suffix(NetworkType::Testnet, 11)).is_ok());
+        assert!(matches!(
+            unsigned.validate(&NetworkId::new(NetworkType::Mainnet)),
+            Err(Error::UnsignedTransactionNetworkMismatch(_, _))
+        ));
+
+        unsigned.version = UnsignedTransaction::VERSION + 1;
+        assert!(matches!(
+            unsigned.validate(&NetworkId::with_suffix(NetworkType::Testnet, 11)),
+            Err(Error::UnsignedTransactionVersion(_, _))
+        ));
+    }
+}
@@ -2,11 +2,12 @@
 //! Kaspa value formatting and parsing utilities.
 //!
 
+use crate::error::Error;
 use crate::result::Result;
 use kaspa_addresses::Address;
 use kaspa_consensus_core::constants::*;
 use kaspa_consensus_core::network::NetworkType;
-use separator::{separated_float, separated_int, separated_uint_with_output, Separatable};
+use separator::Separatable;
 use workflow_log::style;
 
 pub fn try_kaspa_str_to_sompi<S: Into<String>>(s: S) -> Result<Option<u64>> {
@@ -26,8 +27,12 @@ pub fn try_kaspa_str_to_sompi_i64<S: Into<String>>(s: S) -> Result<Option<i64>>
         return Ok(None);
     }
 
-    let amount = amount.parse::<f64>()? * SOMPI_PER_KASPA as f64;
-    Ok(Some(amount as i64))
+    let (negative, amount) = match amount.strip_prefix('-') {
+        Some(amount) => (true, amount),
+        None => (false, amount),
+    };
+    let sompi = i64::try_from(str_to_sompi(amount)?).map_err(|_| Error::AmountOverflow(amount.to_string()))?;
+    Ok(Some(if negative { -sompi } else { sompi }))
 }
 
 #[inline]
@@ -40,14 +45,39 @@ pub fn kaspa_to_sompi(kaspa: f64) -> u64 {
     (kaspa * SOMPI_PER_KASPA as f64) as u64
 }
 
+/// Formatting options for [`sompi_to_kaspa_string_with_options`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct KaspaStringOptions {
+    /// Group the integer part using thousands separators.
+    pub grouping: bool,
+    /// Always render all 8 decimal places instead of trimming trailing zeroes.
+    pub trailing_zeroes: bool,
+    /// Append the `KAS` suffix of the given network type.
+    pub suffix: Option<NetworkType>,
+}
+
+/// Formats a SOMPI value as a KAS string. The conversion is performed
+/// using integer arithmetic, so no precision is lost for large values.
+pub fn sompi_to_kaspa_string_with_options(sompi: u64, options: &KaspaStringOptions) -> String {
+    let integer = sompi / SOMPI_PER_KASPA;
+    let integer = if options.grouping { integer.separated_string() } else { integer.to_string() };
+    let decimal = format!("{:08}", sompi % SOMPI_PER_KASPA);
+    let decimal = if options.trailing_zeroes { decimal.as_str() } else { decimal.trim_end_matches('0') };
+    let kas = if decimal.is_empty() { integer } else { format!("{integer}.{decimal}") };
+    match options.suffix {
+        Some(network_type) => format!("{kas} {}", kaspa_suffix(&network_type)),
+        None => kas,
+    }
+}
+
 #[inline]
 pub fn sompi_to_kaspa_string(sompi: u64) -> String {
-    sompi_to_kaspa(sompi).separated_string()
+    sompi_to_kaspa_string_with_options(sompi, &KaspaStringOptions { grouping: true, ..Default::default() })
 }
 
 #[inline]
 pub fn sompi_to_kaspa_string_with_trailing_zeroes(sompi: u64) -> String {
-    separated_float!(format!("{:.8}", sompi_to_kaspa(sompi)))
+    sompi_to_kaspa_string_with_options(sompi, &KaspaStringOptions { grouping: true, trailing_zeroes: true, ..Default::default() })
 }
 
 pub fn kaspa_suffix(network_type: &NetworkType) -> &'static str {
@@ -61,16 +91,18 @@ pub fn kaspa_suffix(network_type: &NetworkType) -> &'static str {
 
 #[inline]
 pub fn sompi_to_kaspa_string_with_suffix(sompi: u64, network_type: &NetworkType) -> String {
-    let kas = sompi_to_kaspa_string(sompi);
-    let suffix = kaspa_suffix(network_type);
-    format!("{kas} {suffix}")
+    sompi_to_kaspa_string_with_options(
+        sompi,
+        &KaspaStringOptions { grouping: true, suffix: Some(*network_type), ..Default::default() },
+    )
 }
 
 #[inline]
 pub fn sompi_to_kaspa_string_with_trailing_zeroes_and_suffix(sompi: u64, network_type: &NetworkType) -> String {
-    let kas = sompi_to_kaspa_string_with_trailing_zeroes(sompi);
-    let suffix = kaspa_suffix(network_type);
-    format!("{kas} {suffix}")
+    sompi_to_kaspa_string_with_options(
+        sompi,
+        &KaspaStringOptions { grouping: true, trailing_zeroes: true, suffix: Some(*network_type) },
+    )
 }
 
 pub fn format_address_colors(address: &Address, range: Option<usize>) -> String {
@@ -90,21 +122,95 @@ pub fn format_address_colors(address: &Address, range: Option<usize>) -> String
     format!("{prefix}:{left}:{center}:{right}")
 }
 
+/// Parses a KAS amount string into SOMPI. Only plain decimal notation
+/// is accepted: negative values, scientific notation, more than 8
+/// decimal places and values that overflow `u64` SOMPI are rejected.
 fn str_to_sompi(amount: &str) -> Result<u64> {
-    let Some(dot_idx) = amount.find('.') else {
-        return Ok(amount.parse::<u64>()? * SOMPI_PER_KASPA);
-    };
-    let integer = amount[..dot_idx].parse::<u64>()? * SOMPI_PER_KASPA;
-    let decimal = &amount[dot_idx + 1..];
-    let decimal_len = decimal.len();
-    let decimal = if decimal_len == 0 {
-        0
-    } else if decimal_len <= 8 {
-        decimal.parse::<u64>()? * 10u64.pow(8 - decimal_len as u32)
-    } else {
-        // TODO - discuss how to handle values longer than 8 decimal places
-        // (reject, truncate, ceil(), etc.)
-        decimal[..8].parse::<u64>()?
-    };
-    Ok(integer + decimal)
+    if amount.starts_with('-') {
+        return Err(Error::NegativeAmount(amount.to_string()));
+    }
+    if amount.contains(['e', 'E']) {
+        return Err(Error::ScientificNotationAmount(amount.to_string()));
+    }
+
+    let (integer, decimal) = amount.split_once('.').unwrap_or((amount, ""));
+    if (integer.is_empty() && decimal.is_empty())
+        || !integer.chars().all(|c| c.is_ascii_digit())
+        || !decimal.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(Error::InvalidAmount(amount.to_string()));
+    }
+    if decimal.len() > 8 {
+        return Err(Error::AmountPrecision(amount.to_string()));
+    }
+
+    let overflow = || Error::AmountOverflow(amount.to_string());
+    let integer = if integer.is_empty() { 0 } else { integer.parse::<u64>().map_err(|_| overflow())? };
+    let decimal = if decimal.is_empty() { 0 } else { decimal.parse::<u64>()? * 10u64.pow(8 - decimal.len() as u32) };
+    integer.checked_mul(SOMPI_PER_KASPA).and_then(|integer| integer.checked_add(decimal)).ok_or_else(overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kaspa_str_to_sompi() {
+        assert_eq!(try_kaspa_str_to_sompi("1").unwrap(), Some(SOMPI_PER_KASPA));
+        assert_eq!(try_kaspa_str_to_sompi("1.5").unwrap(), Some(150_000_000));
+        assert_eq!(try_kaspa_str_to_sompi(".00000001").unwrap(), Some(1));
+        assert_eq!(try_kaspa_str_to_sompi("1.23456789").unwrap(), Some(123_456_789));
+        assert_eq!(try_kaspa_str_to_sompi("  ").unwrap(), None);
+        assert_eq!(try_kaspa_str_to_sompi_i64("-1.5").unwrap(), Some(-150_000_000));
+    }
+
+    #[test]
+    fn test_kaspa_str_to_sompi_rejects_precision_overflow() {
+        assert!(matches!(try_kaspa_str_to_sompi("1.234567891"), Err(Error::AmountPrecision(_))));
+    }
+
+    #[test]
+    fn test_kaspa_str_to_sompi_rejects_value_overflow() {
+        assert!(matches!(try_kaspa_str_to_sompi("184467440737.09551616"), Err(Error::AmountOverflow(_))));
+        assert!(matches!(try_kaspa_str_to_sompi("99999999999999999999999"), Err(Error::AmountOverflow(_))));
+        assert!(matches!(try_kaspa_str_to_sompi_i64("100000000000"), Err(Error::AmountOverflow(_))));
+    }
+
+    #[test]
+    fn test_kaspa_str_to_sompi_rejects_negative_and_scientific() {
+        assert!(matches!(try_kaspa_str_to_sompi("-5"), Err(Error::NegativeAmount(_))));
+        assert!(matches!(try_kaspa_str_to_sompi("1e3"), Err(Error::ScientificNotationAmount(_))));
+        assert!(matches!(try_kaspa_str_to_sompi("1.5E-2"), Err(Error::ScientificNotationAmount(_))));
+        assert!(matches!(try_kaspa_str_to_sompi("+5"), Err(Error::InvalidAmount(_))));
+        assert!(matches!(try_kaspa_str_to_sompi("1.2.3"), Err(Error::InvalidAmount(_))));
+        assert!(matches!(try_kaspa_str_to_sompi("."), Err(Error::InvalidAmount(_))));
+    }
+
+    #[test]
+    fn test_sompi_to_kaspa_string_zero() {
+        assert_eq!(sompi_to_kaspa_string(0), "0");
+        assert_eq!(sompi_to_kaspa_string_with_trailing_zeroes(0), "0.00000000");
+        assert_eq!(sompi_to_kaspa_string_with_suffix(0, &NetworkType::Mainnet), "0 KAS");
+    }
+
+    #[test]
+    fn test_sompi_to_kaspa_string_below_one_kaspa() {
+        assert_eq!(sompi_to_kaspa_string(1), "0.00000001");
+        assert_eq!(sompi_to_kaspa_string(50_000_000), "0.5");
+        assert_eq!(sompi_to_kaspa_string_with_trailing_zeroes(50_000_000), "0.50000000");
+        assert_eq!(sompi_to_kaspa_string_with_suffix(12_300, &NetworkType::Testnet), "0.000123 TKAS");
+    }
+
+    #[test]
+    fn test_sompi_to_kaspa_string_grouping() {
+        let sompi = 123_456_789_012_345_678;
+        assert_eq!(sompi_to_kaspa_string(sompi), "1,234,567,890.12345678");
+        assert_eq!(sompi_to_kaspa_string_with_options(sompi, &KaspaStringOptions::default()), "1234567890.12345678");
+        assert_eq!(sompi_to_kaspa_string(1_000_000 * SOMPI_PER_KASPA), "1,000,000");
+        assert_eq!(
+            sompi_to_kaspa_string_with_trailing_zeroes_and_suffix(1_000_000 * SOMPI_PER_KASPA, &NetworkType::Mainnet),
+            "1,000,000.00000000 KAS"
+        );
+        assert_eq!(sompi_to_kaspa_string(u64::MAX), "184,467,440,737.09551615");
+    }
 }
@@ -16,6 +16,12 @@ use crate::utxo::{
 };
 use kaspa_hashes::Hash;
 use sorted_insert::SortedInsertBinaryByKey;
+use std::time::Duration;
+use workflow_core::time::Instant;
+
+/// Time after which a UTXO reservation of a transaction that has been
+/// neither submitted nor abandoned is released.
+pub const UTXO_RESERVATION_TIMEOUT: Duration = Duration::from_secs(60);
 
 static PROCESSOR_ID_SEQUENCER: AtomicU64 = AtomicU64::new(0);
 fn next_processor_id() -> Hash {
@@ -92,6 +98,9 @@ pub struct Context {
     /// Confirmation occurs when the transaction UTXOs are
     /// removed from the context by the UTXO change notification.
     pub(crate) outgoing: AHashMap<TransactionId, OutgoingTransaction>,
+    /// Mature UTXOs reserved by in-flight (generated but not yet submitted)
+    /// transactions, mapped to the reservation expiration time.
+    pub(crate) reserved: AHashMap<UtxoEntryId, Instant>,
     /// Total balance of all UTXOs in this context (mature, pending)
     balance: Option<Balance>,
     /// Addresses monitored by this UTXO context
@@ -106,6 +115,7 @@ impl Default for Context {
             stasis: AHashMap::default(),
             map: AHashMap::default(),
             outgoing: AHashMap::default(),
+            reserved: AHashMap::default(),
             balance: None,
             addresses: Arc::new(DashSet::new()),
         }
@@ -123,6 +133,7 @@ impl Context {
         self.stasis.clear();
         self.pending.clear();
         self.outgoing.clear();
+        self.reserved.clear();
         self.addresses.clear();
         self.balance = None;
     }
@@ -241,6 +252,30 @@ impl UtxoContext {
         Ok(balance)
     }
 
+    /// Reserve UTXO entries consumed by an in-flight transaction. Reserved entries are
+    /// skipped by the [`UtxoIterator`](crate::utxo::UtxoIterator) until they are released
+    /// or the reservation expires after `timeout`.
+    pub fn reserve<'a>(&self, utxo_entries: impl IntoIterator<Item = &'a UtxoEntryReference>, timeout: Duration) {
+        let mut context = self.context();
+        let now = Instant::now();
+        context.reserved.retain(|_, expiration| *expiration > now);
+        let expiration = now + timeout;
+        context.reserved.extend(utxo_entries.into_iter().map(|utxo_entry| (utxo_entry.id(), expiration)));
+    }
+
+    /// Release reservation of the UTXO entries (transaction has been submitted or abandoned).
+    pub fn release<'a>(&self, utxo_entries: impl IntoIterator<Item = &'a UtxoEntryReference>) {
+        let mut context = self.context();
+        utxo_entries.into_iter().for_each(|utxo_entry| {
+            context.reserved.remove(utxo_entry.id_as_ref());
+        });
+    }
+
+    /// Returns `true` if the UTXO entry is reserved by an in-flight transaction.
+    pub fn is_reserved(&self, utxo_entry: &UtxoEntryReference) -> bool {
+        self.context().reserved.get(utxo_entry.id_as_ref()).is_some_and(|expiration| *expiration > Instant::now())
+    }
+
     /// Process pending transaction. Remove mature UTXO entries and add them to the consumed set.
     /// Produces a notification on the even multiplexer.
     pub(crate) async fn register_outgoing_transaction(&self, pending_tx: &PendingTransaction) -> Result<()> {
@@ -251,6 +286,10 @@ impl UtxoContext {
             let mut context = self.context();
             let pending_utxo_entries = pending_tx.utxo_entries();
             context.mature.retain(|entry| !pending_utxo_entries.contains(entry));
+            // entries are now tracked by the outgoing transaction
+            pending_utxo_entries.iter().for_each(|entry| {
+                context.reserved.remove(entry.id_as_ref());
+            });
 
             let outgoing_transaction = OutgoingTransaction::new(current_daa_score, self.clone(), pending_tx.clone());
             self.processor().register_outgoing_transaction(outgoing_transaction.clone());
@@ -263,6 +302,13 @@ impl UtxoContext {
     pub(crate) async fn notify_outgoing_transaction(&self, pending_tx: &PendingTransaction) -> Result<()> {
         let outgoing_tx = self.processor().outgoing().get(&pending_tx.id()).expect("outgoing transaction for notification");
 
+        // a submitted replacement supersedes the original transaction spending
+        // the same UTXO entries, the original is no longer accounted for
+        if let Some(replaced_transaction_id) = pending_tx.replaced_transaction_id() {
+            self.processor().cancel_outgoing_transaction(replaced_transaction_id);
+            self.context().outgoing.remove(&replaced_transaction_id);
+        }
+
         if pending_tx.is_batch() {
             let record = TransactionRecord::new_batch(self, &outgoing_tx, None)?;
             self.processor().notify(Events::Pending { record }).await?;
@@ -282,9 +328,15 @@ impl UtxoContext {
         let mut context = self.context();
 
         let outgoing_transaction = context.outgoing.remove(&pending_tx.id()).expect("outgoing transaction");
-        outgoing_transaction.utxo_entries().iter().for_each(|entry| {
-            context.mature.push(entry.clone());
-        });
+        // UTXO entries of a rejected replacement remain consumed by the original transaction
+        let is_replacing_outgoing = pending_tx
+            .replaced_transaction_id()
+            .is_some_and(|replaced_transaction_id| context.outgoing.contains_key(&replaced_transaction_id));
+        if !is_replacing_outgoing {
+            outgoing_transaction.utxo_entries().iter().for_each(|entry| {
+                context.mature.push(entry.clone());
+            });
+        }
 
         Ok(())
     }
@@ -331,6 +383,8 @@ impl UtxoContext {
 
         for utxo in utxos.into_iter() {
             let id = utxo.id();
+            // removed entries can no longer be spent by in-flight transactions
+            context.reserved.remove(&id);
             // remove from local map
             if context.map.remove(&id).is_some() {
                 if let Some(pending) = context.pending.remove(&id) {
@@ -511,9 +565,88 @@ impl UtxoContext {
         Balance::new(mature, pending, outgoing, context.mature.len(), context.pending.len(), context.stasis.len())
     }
 
+    /// Reconcile the UTXO set after a chain reorganization.
+    ///
+    /// - `disconnected` - UTXO entries created by transactions of the disconnected blocks;
+    ///   these are removed from the set and reported via [`Events::Reorg`].
+    /// - `restored` - UTXO entries spent by transactions of the disconnected blocks that
+    ///   became spendable again; outgoing transactions spending these entries are dropped
+    ///   and the entries are returned to the set.
+    ///
+    /// Reservations of the removed entries and of the dropped transactions are released.
+    pub async fn reconcile_reorg(
+        &self,
+        disconnected: Vec<UtxoEntryReference>,
+        restored: Vec<UtxoEntryReference>,
+        current_daa_score: u64,
+    ) -> Result<()> {
+        let (disconnected, dropped_transactions) = {
+            let mut context = self.context();
+
+            let disconnected = disconnected.into_iter().filter(|utxo| context.map.contains_key(utxo.id_as_ref())).collect::<Vec<_>>();
+
+            let restored_ids = restored.iter().map(|utxo| utxo.id()).collect::<AHashSet<_>>();
+            let dropped_transactions = context
+                .outgoing
+                .values()
+                .filter(|outgoing| outgoing.utxo_entries().iter().any(|utxo| restored_ids.contains(utxo.id_as_ref())))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            for outgoing in dropped_transactions.iter() {
+                context.outgoing.remove(&outgoing.id());
+                self.processor().cancel_outgoing_transaction(outgoing.id());
+                outgoing.utxo_entries().iter().for_each(|utxo| {
+                    context.reserved.remove(utxo.id_as_ref());
+                });
+            }
+
+            // restored entries are retained in the map while consumed by an
+            // outgoing transaction, they are re-inserted based on their maturity
+            restored.iter().for_each(|utxo| {
+                context.map.remove(utxo.id_as_ref());
+                context.mature.retain(|entry| entry.id_as_ref() != utxo.id_as_ref());
+            });
+
+            (disconnected, dropped_transactions)
+        };
+
+        if !dropped_transactions.is_empty() {
+            log_info!("reorg: dropping {} outgoing transaction(s)", dropped_transactions.len());
+        }
+
+        for utxo in restored.into_iter() {
+            self.insert(utxo, current_daa_score, false).await?;
+        }
+
+        let removed = self.remove(disconnected).await?;
+        let removed = removed.into_iter().map(|entry| match entry {
+            UtxoEntryVariant::Mature(utxo) | UtxoEntryVariant::Pending(utxo) | UtxoEntryVariant::Stasis(utxo) => utxo,
+        });
+        let removed = HashMap::group_from(removed.map(|utxo| (utxo.transaction_id(), utxo)));
+        for (txid, utxos) in removed.into_iter() {
+            let record = TransactionRecord::new_reorg(self, txid, &utxos);
+            self.processor().notify(Events::Reorg { record }).await?;
+        }
+
+        self.update_balance().await?;
+
+        Ok(())
+    }
+
     pub(crate) async fn handle_utxo_added(&self, utxos: Vec<UtxoEntryReference>, current_daa_score: u64) -> Result<()> {
         // add UTXOs to account set
 
+        // inputs of outgoing transactions re-added by the node became spendable
+        // again as the transactions have been dropped by a chain reorganization
+        let (restored, utxos): (Vec<_>, Vec<_>) = {
+            let context = self.context();
+            utxos.into_iter().partition(|utxo| context.outgoing.values().any(|outgoing| outgoing.utxo_entries().contains(utxo)))
+        };
+        if !restored.is_empty() {
+            self.reconcile_reorg(vec![], restored, current_daa_score).await?;
+        }
+
         let params = NetworkParams::from(self.processor().network_id()?);
 
         let mut accepted_outgoing_transactions = AHashSet::new();
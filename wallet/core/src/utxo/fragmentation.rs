@@ -0,0 +1,155 @@
+//!
+//! [`UtxoFragmentationReport`] describing the fragmentation of a UTXO set
+//! and estimating the fee savings of consolidating it (by sweeping the account).
+//!
+//! Each UTXO spent by a transaction adds to the transaction mass, so spending
+//! many small UTXOs costs more in fees than spending a few large ones. Once the
+//! number of UTXOs exceeds the number of inputs that fit into a single standard
+//! transaction, spending the entire balance requires a chain of compound
+//! transactions. Consolidation is suggested exactly in this case, i.e. when
+//! the UTXO count exceeds [`UtxoFragmentationReport::max_inputs_per_transaction`].
+//!
+
+use crate::imports::*;
+use crate::tx::{MassCalculator, MAXIMUM_STANDARD_TRANSACTION_MASS};
+use crate::utxo::{NetworkParams, UtxoEntryReference};
+use kaspa_consensus_core::constants::SOMPI_PER_KASPA;
+use kaspa_consensus_core::tx::{TransactionInput, TransactionOutpoint, TransactionOutput};
+use kaspa_txscript::pay_to_address_script;
+
+/// Upper bounds (exclusive, in SOMPI) of the UTXO size buckets.
+const BUCKET_BOUNDS: [u64; 5] =
+    [SOMPI_PER_KASPA, 10 * SOMPI_PER_KASPA, 100 * SOMPI_PER_KASPA, 1_000 * SOMPI_PER_KASPA, 10_000 * SOMPI_PER_KASPA];
+
+/// Number and total value of UTXOs with a value in the `min..max` range (in SOMPI).
+/// The last bucket has no upper bound.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxoSizeBucket {
+    pub min: u64,
+    pub max: Option<u64>,
+    pub count: usize,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxoFragmentationReport {
+    /// Number of UTXOs
+    pub utxo_count: usize,
+    /// Total value of all UTXOs
+    pub total_amount: u64,
+    /// Distribution of the UTXO values
+    pub distribution: Vec<UtxoSizeBucket>,
+    /// Maximum number of UTXOs that can be spent by a single standard transaction
+    pub max_inputs_per_transaction: usize,
+    /// Estimated fees of consolidating all UTXOs into a single UTXO
+    pub consolidation_fees: u64,
+    /// Estimated fee saving of each transaction spending the entire balance
+    /// after the consolidation (spending a single UTXO instead of all UTXOs).
+    pub estimated_fee_saving: u64,
+    /// `true` if spending the entire balance requires more than one transaction
+    pub should_consolidate: bool,
+}
+
+impl UtxoFragmentationReport {
+    /// Analyzes the supplied UTXO entries. Fee estimates are based on
+    /// the compute mass of standard (P2PK) inputs and outputs.
+    pub fn new(network_id: NetworkId, utxo_entries: &[UtxoEntryReference], sig_op_count: u8, minimum_signatures: u16) -> Self {
+        let calc = MassCalculator::new(&network_id.into(), &NetworkParams::from(network_id));
+
+        let mut distribution = std::iter::once(0)
+            .chain(BUCKET_BOUNDS)
+            .zip(BUCKET_BOUNDS.into_iter().map(Some).chain(std::iter::once(None)))
+            .map(|(min, max)| UtxoSizeBucket { min, max, count: 0, amount: 0 })
+            .collect::<Vec<_>>();
+        for utxo_entry in utxo_entries {
+            let amount = utxo_entry.amount();
+            let index = BUCKET_BOUNDS.iter().position(|bound| amount < *bound).unwrap_or(BUCKET_BOUNDS.len());
+            distribution[index].count += 1;
+            distribution[index].amount += amount;
+        }
+
+        // a standard P2PK input and output (the address is irrelevant for the mass)
+        let address = Address::new(network_id.into(), kaspa_addresses::Version::PubKey, &[0; 32]);
+        let output = TransactionOutput::new(0, pay_to_address_script(&address));
+        let input = TransactionInput::new(TransactionOutpoint::new(Default::default(), 0), vec![], 0, sig_op_count);
+        let transaction_mass = calc.blank_transaction_mass() + calc.calc_mass_for_output(&output);
+        let input_mass = calc.calc_mass_for_input(&input) + calc.calc_signature_mass(minimum_signatures);
+        let max_inputs_per_transaction = ((MAXIMUM_STANDARD_TRANSACTION_MASS - transaction_mass) / input_mass) as usize;
+
+        // fees of spending `inputs` UTXOs, compounding them through
+        // multiple transactions if they do not fit a single transaction
+        let spend_fees = |mut inputs: usize| {
+            let mut mass = 0;
+            while inputs > 0 {
+                let transactions = inputs.div_ceil(max_inputs_per_transaction);
+                mass += transactions as u64 * transaction_mass + inputs as u64 * input_mass;
+                inputs = if transactions > 1 { transactions } else { 0 };
+            }
+            calc.calc_minimum_transaction_fee_from_mass(mass)
+        };
+
+        let utxo_count = utxo_entries.len();
+        let (consolidation_fees, estimated_fee_saving) =
+            if utxo_count > 1 { (spend_fees(utxo_count), spend_fees(utxo_count) - spend_fees(1)) } else { (0, 0) };
+
+        Self {
+            utxo_count,
+            total_amount: utxo_entries.iter().map(|utxo_entry| utxo_entry.amount()).sum(),
+            distribution,
+            max_inputs_per_transaction,
+            consolidation_fees,
+            estimated_fee_saving,
+            should_consolidate: utxo_count > max_inputs_per_transaction,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_report(values: &[u64]) -> UtxoFragmentationReport {
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+        let utxo_entries = values.iter().map(|value| UtxoEntryReference::simulated(*value)).collect::<Vec<_>>();
+        UtxoFragmentationReport::new(network_id, &utxo_entries, 1, 1)
+    }
+
+    #[test]
+    fn test_utxo_fragmentation_compact() {
+        let report = make_report(&[5 * SOMPI_PER_KASPA, 50 * SOMPI_PER_KASPA, 20_000 * SOMPI_PER_KASPA]);
+        assert_eq!(report.utxo_count, 3);
+        assert_eq!(report.total_amount, 20_055 * SOMPI_PER_KASPA);
+        assert_eq!(report.distribution.iter().map(|bucket| bucket.count).collect::<Vec<_>>(), vec![0, 1, 1, 0, 0, 1]);
+        assert_eq!(report.distribution.last().unwrap().max, None);
+        assert!(!report.should_consolidate);
+        assert!(report.estimated_fee_saving > 0);
+        assert!(report.estimated_fee_saving < report.consolidation_fees);
+
+        let single = make_report(&[SOMPI_PER_KASPA]);
+        assert_eq!((single.consolidation_fees, single.estimated_fee_saving), (0, 0));
+        assert!(!single.should_consolidate);
+    }
+
+    #[test]
+    fn test_utxo_fragmentation_fragmented() {
+        let values = vec![SOMPI_PER_KASPA / 10; 1_000];
+        let report = make_report(&values);
+        assert!(report.max_inputs_per_transaction > 0 && report.max_inputs_per_transaction < 1_000);
+        assert!(report.should_consolidate);
+        assert_eq!(
+            report.distribution[0],
+            UtxoSizeBucket { min: 0, max: Some(SOMPI_PER_KASPA), count: 1_000, amount: 100 * SOMPI_PER_KASPA }
+        );
+
+        // the saving grows with the number of fragmented UTXOs
+        let compact = make_report(&values[..10]);
+        assert!(report.estimated_fee_saving > compact.estimated_fee_saving * 50);
+
+        // the UTXO set fitting a single transaction does not need to be consolidated
+        let boundary = make_report(&values[..report.max_inputs_per_transaction]);
+        assert!(!boundary.should_consolidate);
+        assert!(make_report(&values[..=report.max_inputs_per_transaction]).should_consolidate);
+    }
+}
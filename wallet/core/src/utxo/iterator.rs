@@ -4,14 +4,18 @@
 
 use crate::utxo::{UtxoContext, UtxoEntryReference};
 
+/// Iterator over mature UTXO entries of the [`UtxoContext`]. Entries
+/// reserved by in-flight transactions are skipped at the time of iteration,
+/// so entries reserved after the iterator has been created are excluded as well.
 pub struct UtxoIterator {
+    utxo_context: UtxoContext,
     entries: Vec<UtxoEntryReference>,
     cursor: usize,
 }
 
 impl UtxoIterator {
     pub fn new(utxo_context: &UtxoContext) -> Self {
-        Self { entries: utxo_context.context().mature.clone(), cursor: 0 }
+        Self { utxo_context: utxo_context.clone(), entries: utxo_context.context().mature.clone(), cursor: 0 }
     }
 }
 
@@ -19,8 +23,12 @@ impl Iterator for UtxoIterator {
     type Item = UtxoEntryReference;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let entry = self.entries.get(self.cursor).cloned();
-        self.cursor += 1;
-        entry
+        while let Some(entry) = self.entries.get(self.cursor).cloned() {
+            self.cursor += 1;
+            if !self.utxo_context.is_reserved(&entry) {
+                return Some(entry);
+            }
+        }
+        None
     }
 }
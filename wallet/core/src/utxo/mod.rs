@@ -5,6 +5,7 @@
 pub mod balance;
 pub mod binding;
 pub mod context;
+pub mod fragmentation;
 pub mod iterator;
 pub mod outgoing;
 pub mod pending;
@@ -17,14 +18,15 @@ pub mod sync;
 
 pub use balance::Balance;
 pub use binding::UtxoContextBinding;
-pub use context::{UtxoContext, UtxoContextId};
+pub use context::{UtxoContext, UtxoContextId, UTXO_RESERVATION_TIMEOUT};
+pub use fragmentation::{UtxoFragmentationReport, UtxoSizeBucket};
 pub use iterator::UtxoIterator;
 pub use kaspa_consensus_wasm::UtxoEntryId;
 pub use outgoing::OutgoingTransaction;
 pub use pending::PendingUtxoEntryReference;
 pub use processor::UtxoProcessor;
 pub use reference::{Maturity, TryIntoUtxoEntryReferences, UtxoEntryReference, UtxoEntryReferenceExtension};
-pub use scan::{Scan, ScanExtent};
+pub use scan::{Scan, ScanControl, ScanExtent, ScanProgress, ScanProgressNotifier};
 pub use settings::*;
 pub use stream::UtxoStream;
 pub use sync::SyncMonitor;
@@ -9,6 +9,7 @@ use crate::imports::*;
 use kaspa_notify::{
     listener::ListenerId,
     scope::{Scope, UtxosChangedScope, VirtualDaaScoreChangedScope},
+    subscription::registry::UtxosChangedRegistry,
 };
 use kaspa_rpc_core::{
     api::{
@@ -16,7 +17,7 @@ use kaspa_rpc_core::{
         ops::RPC_API_VERSION,
     },
     message::UtxosChangedNotification,
-    GetServerInfoResponse,
+    GetServerInfoResponse, RpcUtxosByAddressesEntry,
 };
 use kaspa_wrpc_client::KaspaRpcClient;
 use workflow_core::channel::{Channel, DuplexChannel};
@@ -34,6 +35,9 @@ use kaspa_rpc_core::{
     Notification,
 };
 
+/// Number of recently seen UTXO entries retained by [`UtxoProcessor`]
+const UTXO_ENTRY_CACHE_CAPACITY: usize = 8192;
+
 pub struct Inner {
     /// Coinbase UTXOs in stasis
     stasis: DashMap<UtxoEntryId, PendingUtxoEntryReference>,
@@ -44,12 +48,19 @@ pub struct Inner {
     /// Address to UtxoContext map (maps all addresses used by
     /// all UtxoContexts to their respective UtxoContexts)
     address_to_utxo_context_map: DashMap<Arc<Address>, UtxoContext>,
+    /// Recently added UTXO entries (from UtxosChanged notifications), allowing
+    /// a 'removed' notification to resolve to the reference created when the
+    /// entry was 'added' instead of allocating a duplicate
+    utxo_entry_cache: Mutex<LruCache<UtxoEntryId, UtxoEntryReference>>,
     // ---
     current_daa_score: Arc<AtomicU64>,
     network_id: Arc<Mutex<Option<NetworkId>>>,
     rpc: Mutex<Option<Rpc>>,
     is_connected: AtomicBool,
     listener_id: Mutex<Option<ListenerId>>,
+    /// Reference counted addresses of the UtxosChanged subscription
+    /// shared by all UtxoContexts (via the single notification listener)
+    utxos_changed_registry: Mutex<UtxosChangedRegistry>,
     task_ctl: DuplexChannel,
     task_is_running: AtomicBool,
     notification_channel: Channel<Notification>,
@@ -71,11 +82,13 @@ impl Inner {
             pending: DashMap::new(),
             outgoing: DashMap::new(),
             address_to_utxo_context_map: DashMap::new(),
+            utxo_entry_cache: Mutex::new(LruCache::new(UTXO_ENTRY_CACHE_CAPACITY)),
             current_daa_score: Arc::new(AtomicU64::new(0)),
             network_id: Arc::new(Mutex::new(network_id)),
             rpc: Mutex::new(rpc.clone()),
             is_connected: AtomicBool::new(false),
             listener_id: Mutex::new(None),
+            utxos_changed_registry: Mutex::new(UtxosChangedRegistry::new()),
             task_ctl: DuplexChannel::oneshot(),
             task_is_running: AtomicBool::new(false),
             notification_channel: Channel::<Notification>::unbounded(),
@@ -186,6 +199,35 @@ impl UtxoProcessor {
         self.inner.address_to_utxo_context_map.get(address).map(|v| v.clone())
     }
 
+    /// Cache of recently seen UTXO entries; its hit and miss counters can be used for metrics
+    pub fn utxo_entry_cache(&self) -> MutexGuard<LruCache<UtxoEntryId, UtxoEntryReference>> {
+        self.inner.utxo_entry_cache.lock().unwrap()
+    }
+
+    /// Converts an 'added' notification entry into a [`UtxoEntryReference`] and caches it.
+    /// The fresh entry always replaces a cached entry of the same outpoint, which may have
+    /// been re-added with a different block DAA score by a chain reorganization.
+    fn cache_added_utxo_entry(&self, entry: RpcUtxosByAddressesEntry) -> UtxoEntryReference {
+        let utxo = UtxoEntryReference::from(entry);
+        self.utxo_entry_cache().put(utxo.id(), utxo.clone());
+        utxo
+    }
+
+    /// Converts a 'removed' notification entry into a [`UtxoEntryReference`], evicting
+    /// the cached reference of the same outpoint (reused instead of a duplicate allocation).
+    /// The lookup is tallied by the cache hit and miss counters.
+    fn evict_removed_utxo_entry(&self, entry: RpcUtxosByAddressesEntry) -> UtxoEntryReference {
+        let id = UtxoEntryId::from(entry.outpoint);
+        let mut cache = self.utxo_entry_cache();
+        match cache.get(&id).cloned() {
+            Some(utxo) => {
+                cache.remove(&id);
+                utxo
+            }
+            None => entry.into(),
+        }
+    }
+
     pub async fn register_addresses(&self, addresses: Vec<Arc<Address>>, utxo_context: &UtxoContext) -> Result<()> {
         addresses.iter().for_each(|address| {
             self.inner.address_to_utxo_context_map.insert(address.clone(), utxo_context.clone());
@@ -194,8 +236,19 @@ impl UtxoProcessor {
         if self.is_connected() {
             if !addresses.is_empty() {
                 let addresses = addresses.into_iter().map(|address| (*address).clone()).collect::<Vec<_>>();
-                let utxos_changed_scope = UtxosChangedScope { addresses };
-                self.rpc_api().start_notify(self.listener_id()?, Scope::UtxosChanged(utxos_changed_scope)).await?;
+                let utxos_changed_scope =
+                    self.inner.utxos_changed_registry.lock().unwrap().start(UtxosChangedScope { addresses: addresses.clone() });
+                if let Some(utxos_changed_scope) = utxos_changed_scope {
+                    let result = async {
+                        self.rpc_api().start_notify(self.listener_id()?, Scope::UtxosChanged(utxos_changed_scope)).await?;
+                        Ok::<_, Error>(())
+                    };
+                    if let Err(err) = result.await {
+                        // roll back the registration so that the addresses are not deemed subscribed
+                        self.inner.utxos_changed_registry.lock().unwrap().unregister(addresses);
+                        return Err(err);
+                    }
+                }
             } else {
                 log_error!("registering empty address list!");
             }
@@ -210,9 +263,29 @@ impl UtxoProcessor {
 
         if self.is_connected() {
             if !addresses.is_empty() {
-                let addresses = addresses.into_iter().map(|address| (*address).clone()).collect::<Vec<_>>();
-                let utxos_changed_scope = UtxosChangedScope { addresses };
-                self.rpc_api().stop_notify(self.listener_id()?, Scope::UtxosChanged(utxos_changed_scope)).await?;
+                // addresses still used by other UtxoContexts remain subscribed,
+                // unknown addresses are not unregistered
+                let (unregistered, utxos_changed_scope) = {
+                    let mut registry = self.inner.utxos_changed_registry.lock().unwrap();
+                    let unregistered = addresses
+                        .into_iter()
+                        .map(|address| (*address).clone())
+                        .filter(|address| registry.contains(address))
+                        .collect::<Vec<_>>();
+                    let utxos_changed_scope = registry.stop(UtxosChangedScope { addresses: unregistered.clone() });
+                    (unregistered, utxos_changed_scope)
+                };
+                if let Some(utxos_changed_scope) = utxos_changed_scope {
+                    let result = async {
+                        self.rpc_api().stop_notify(self.listener_id()?, Scope::UtxosChanged(utxos_changed_scope)).await?;
+                        Ok::<_, Error>(())
+                    };
+                    if let Err(err) = result.await {
+                        // the addresses remain subscribed, so restore the subscribers that were unregistered
+                        self.inner.utxos_changed_registry.lock().unwrap().register(unregistered);
+                        return Err(err);
+                    }
+                }
             } else {
                 log_error!("unregistering empty address list!");
             }
@@ -373,7 +446,7 @@ impl UtxoProcessor {
         for (address, entries) in removed.into_iter() {
             if let Some(utxo_context) = self.address_to_utxo_context(&address) {
                 updated_contexts.insert(utxo_context.clone());
-                let entries = entries.into_iter().map(|entry| entry.into()).collect::<Vec<_>>();
+                let entries = entries.into_iter().map(|entry| self.evict_removed_utxo_entry(entry)).collect::<Vec<_>>();
                 utxo_context.handle_utxo_removed(entries, current_daa_score).await?;
             } else {
                 log_error!("receiving UTXO Changed 'removed' notification for an unknown address: {}", address);
@@ -385,7 +458,7 @@ impl UtxoProcessor {
         for (address, entries) in added.into_iter() {
             if let Some(utxo_context) = self.address_to_utxo_context(&address) {
                 updated_contexts.insert(utxo_context.clone());
-                let entries = entries.into_iter().map(|entry| entry.into()).collect::<Vec<UtxoEntryReference>>();
+                let entries = entries.into_iter().map(|entry| self.cache_added_utxo_entry(entry)).collect::<Vec<_>>();
                 utxo_context.handle_utxo_added(entries, current_daa_score).await?;
             } else {
                 log_error!("receiving UTXO Changed 'added' notification for an unknown address: {}", address);
@@ -417,6 +490,7 @@ impl UtxoProcessor {
             is_synced,
             virtual_daa_score,
             rpc_api_version,
+            ..
         } = self.rpc_api().get_server_info().await?;
 
         if !has_utxo_index {
@@ -494,6 +568,7 @@ impl UtxoProcessor {
 
     async fn unregister_notification_listener(&self) -> Result<()> {
         let listener_id = self.inner.listener_id.lock().unwrap().take();
+        self.inner.utxos_changed_registry.lock().unwrap().clear();
         if let Some(id) = listener_id {
             // we do not need this as we are unregister the entire listener here...
             self.rpc_api().unregister_listener(id).await?;
@@ -635,6 +710,13 @@ pub(crate) mod mock {
             self.inner.is_connected.store(connected, Ordering::SeqCst);
         }
 
+        /// Marks the processor as connected and registers its notification listener,
+        /// which is required for registering addresses while connected.
+        pub async fn mock_connect(&self) -> Result<()> {
+            self.mock_set_connected(true);
+            self.register_notification_listener().await
+        }
+
         // pub fn mock_set_daa_score(&self, connected : bool) {
         //     self.inner.is_connected.store(connected, Ordering::SeqCst);
         // }
@@ -21,6 +21,63 @@ pub enum ScanExtent {
     Depth(u32),
 }
 
+/// Progress of an account scan.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ScanProgress {
+    /// Number of addresses scanned
+    pub scanned: usize,
+    /// Number of scanned addresses holding UTXOs
+    pub funded: usize,
+    /// `true` if the scan was aborted before completion
+    pub aborted: bool,
+}
+
+/// Scan progress callback type used by [`ScanControl`], invoked after each scanned address window.
+pub type ScanProgressNotifier = Arc<dyn Fn(ScanProgress) + Send + Sync>;
+
+/// Cancellation and progress reporting of account scans. Clones share their state,
+/// allowing the receive and change address scans of an account to report a combined
+/// progress. Aborting the [`Abortable`] stops the scans after the address window
+/// being processed, retaining the UTXOs found so far.
+#[derive(Clone, Default)]
+pub struct ScanControl {
+    abortable: Abortable,
+    notifier: Option<ScanProgressNotifier>,
+    scanned: Arc<AtomicUsize>,
+    funded: Arc<AtomicUsize>,
+}
+
+impl ScanControl {
+    pub fn new(abortable: &Abortable, notifier: Option<ScanProgressNotifier>) -> Self {
+        Self { abortable: abortable.clone(), notifier, ..Default::default() }
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.abortable.is_aborted()
+    }
+
+    pub fn progress(&self) -> ScanProgress {
+        ScanProgress {
+            scanned: self.scanned.load(Ordering::SeqCst),
+            funded: self.funded.load(Ordering::SeqCst),
+            aborted: self.is_aborted(),
+        }
+    }
+
+    fn record(&self, scanned: usize, funded: usize) {
+        self.scanned.fetch_add(scanned, Ordering::SeqCst);
+        self.funded.fetch_add(funded, Ordering::SeqCst);
+        if let Some(notifier) = self.notifier.as_ref() {
+            notifier(self.progress());
+        }
+    }
+}
+
+/// Returns the number of distinct addresses holding the UTXOs of `refs`
+fn funded_address_count(refs: &[UtxoEntryReference]) -> usize {
+    refs.iter().filter_map(|r| r.utxo.address.as_ref()).collect::<HashSet<_>>().len()
+}
+
 enum Provider {
     AddressManager(Arc<AddressManager>),
     AddressSet(HashSet<Address>),
@@ -32,6 +89,7 @@ pub struct Scan {
     extent: Option<ScanExtent>,
     balance: Arc<AtomicBalance>,
     current_daa_score: u64,
+    control: ScanControl,
 }
 
 impl Scan {
@@ -42,7 +100,14 @@ impl Scan {
         window_size: Option<usize>,
         extent: Option<ScanExtent>,
     ) -> Scan {
-        Scan { provider: Provider::AddressManager(address_manager), window_size, extent, balance: balance.clone(), current_daa_score }
+        Scan {
+            provider: Provider::AddressManager(address_manager),
+            window_size,
+            extent,
+            balance: balance.clone(),
+            current_daa_score,
+            control: ScanControl::default(),
+        }
     }
     pub fn new_with_address_set(addresses: HashSet<Address>, balance: &Arc<AtomicBalance>, current_daa_score: u64) -> Scan {
         Scan {
@@ -51,9 +116,16 @@ impl Scan {
             extent: None,
             balance: balance.clone(),
             current_daa_score,
+            control: ScanControl::default(),
         }
     }
 
+    /// Reports the progress of the scan to `control`, which also allows aborting it
+    pub fn with_control(mut self, control: &ScanControl) -> Self {
+        self.control = control.clone();
+        self
+    }
+
     pub async fn scan(&self, utxo_context: &UtxoContext) -> Result<()> {
         match &self.provider {
             Provider::AddressManager(address_manager) => self.scan_with_address_manager(address_manager, utxo_context).await,
@@ -71,6 +143,10 @@ impl Scan {
         let mut last_address_index = address_manager.index();
 
         'scan: loop {
+            if self.control.is_aborted() {
+                break 'scan;
+            }
+
             // scan first up to address index, then in window chunks
             let first = cursor;
             let last = if cursor == 0 { max(last_address_index + 1, window_size) } else { cursor + window_size };
@@ -94,6 +170,7 @@ impl Scan {
 
             if !resp.is_empty() {
                 let refs: Vec<UtxoEntryReference> = resp.into_iter().map(UtxoEntryReference::from).collect();
+                self.control.record((last - first) as usize, funded_address_count(&refs));
                 for utxo_ref in refs.iter() {
                     if let Some(address) = utxo_ref.utxo.address.as_ref() {
                         if let Some(utxo_address_index) = address_manager.inner().address_to_index_map.get(address) {
@@ -120,6 +197,7 @@ impl Scan {
 
                 self.balance.add(balance);
             } else {
+                self.control.record((last - first) as usize, 0);
                 match &extent {
                     ScanExtent::EmptyWindow => {
                         if cursor > last_address_index + window_size {
@@ -143,12 +221,17 @@ impl Scan {
     }
 
     pub async fn scan_with_address_set(&self, address_set: &HashSet<Address>, utxo_context: &UtxoContext) -> Result<()> {
+        if self.control.is_aborted() {
+            return Ok(());
+        }
+
         let params = utxo_context.processor().network_params()?;
         let address_vec = address_set.iter().cloned().collect::<Vec<_>>();
 
         utxo_context.register_addresses(&address_vec).await?;
         let resp = utxo_context.processor().rpc_api().get_utxos_by_addresses(address_vec).await?;
         let refs: Vec<UtxoEntryReference> = resp.into_iter().map(UtxoEntryReference::from).collect();
+        self.control.record(address_set.len(), funded_address_count(&refs));
 
         let balance: Balance = refs.iter().fold(Balance::default(), |mut balance, r| {
             let entry_balance = r.balance(params, self.current_daa_score);
@@ -11,6 +11,8 @@ pub struct NetworkParams {
     pub user_transaction_maturity_period_daa: u64,
     pub mass_combination_strategy: MassCombinationStrategy,
     pub additional_compound_transaction_mass: u64,
+    pub max_inputs_per_transaction: usize,
+    pub max_outputs_per_transaction: usize,
 }
 
 pub const MAINNET_NETWORK_PARAMS: NetworkParams = NetworkParams {
@@ -19,6 +21,8 @@ pub const MAINNET_NETWORK_PARAMS: NetworkParams = NetworkParams {
     user_transaction_maturity_period_daa: 10,
     mass_combination_strategy: MassCombinationStrategy::Add,
     additional_compound_transaction_mass: 0,
+    max_inputs_per_transaction: 100,
+    max_outputs_per_transaction: 100,
 };
 
 pub const TESTNET10_NETWORK_PARAMS: NetworkParams = NetworkParams {
@@ -27,6 +31,8 @@ pub const TESTNET10_NETWORK_PARAMS: NetworkParams = NetworkParams {
     user_transaction_maturity_period_daa: 10,
     mass_combination_strategy: MassCombinationStrategy::Add,
     additional_compound_transaction_mass: 100,
+    max_inputs_per_transaction: 100,
+    max_outputs_per_transaction: 100,
 };
 
 pub const TESTNET11_NETWORK_PARAMS: NetworkParams = NetworkParams {
@@ -35,6 +41,8 @@ pub const TESTNET11_NETWORK_PARAMS: NetworkParams = NetworkParams {
     user_transaction_maturity_period_daa: 100,
     mass_combination_strategy: MassCombinationStrategy::Add,
     additional_compound_transaction_mass: 100,
+    max_inputs_per_transaction: 100,
+    max_outputs_per_transaction: 100,
 };
 
 pub const DEVNET_NETWORK_PARAMS: NetworkParams = NetworkParams {
@@ -43,6 +51,8 @@ pub const DEVNET_NETWORK_PARAMS: NetworkParams = NetworkParams {
     user_transaction_maturity_period_daa: 10,
     mass_combination_strategy: MassCombinationStrategy::Add,
     additional_compound_transaction_mass: 0,
+    max_inputs_per_transaction: 100,
+    max_outputs_per_transaction: 100,
 };
 
 pub const SIMNET_NETWORK_PARAMS: NetworkParams = NetworkParams {
@@ -51,6 +61,8 @@ pub const SIMNET_NETWORK_PARAMS: NetworkParams = NetworkParams {
     user_transaction_maturity_period_daa: 10,
     mass_combination_strategy: MassCombinationStrategy::Add,
     additional_compound_transaction_mass: 0,
+    max_inputs_per_transaction: 100,
+    max_outputs_per_transaction: 100,
 };
 
 impl From<NetworkId> for &'static NetworkParams {
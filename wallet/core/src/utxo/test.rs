@@ -32,3 +32,185 @@ fn test_utxo_generator_empty_utxo_noop() -> Result<()> {
     // assert!(tx.is_none());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_utxo_reservation() -> Result<()> {
+    let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+    let rpc_api_mock = Arc::new(RpcCoreMock::new());
+    let processor = UtxoProcessor::new(Some(rpc_api_mock.clone().into()), Some(network_id), None, None);
+    let context = UtxoContext::new(&processor, UtxoContextBinding::default());
+    let change_address = change_address(network_id.into());
+    for _ in 0..4 {
+        context.insert(UtxoEntryReference::simulated_with_address(kaspa_to_sompi(10.0), &change_address), 0, true).await?;
+    }
+
+    let make_generator = || {
+        let payment_output = PaymentOutput::new(output_address(network_id.into()), kaspa_to_sompi(15.0));
+        let settings = GeneratorSettings::try_new_with_context(
+            context.clone(),
+            change_address.clone(),
+            1,
+            1,
+            payment_output.into(),
+            Fees::SenderPays(0),
+            None,
+            None,
+        )?;
+        Generator::try_new(settings, None, None)
+    };
+
+    // the second generator is created before the first selection takes place
+    let first = make_generator()?;
+    let second = make_generator()?;
+
+    let first_tx = first.generate_transaction()?.expect("first transaction");
+    let second_tx = second.generate_transaction()?.expect("second transaction");
+    assert_eq!(first_tx.utxo_entries().len(), 2);
+    assert_eq!(second_tx.utxo_entries().len(), 2);
+    assert!(first_tx.utxo_entries().iter().all(|entry| context.is_reserved(entry)));
+    assert!(first_tx.utxo_entries().is_disjoint(second_tx.utxo_entries()));
+
+    // all entries are reserved by the in-flight transactions
+    assert!(matches!(make_generator()?.generate_transaction(), Err(Error::InsufficientFunds { .. })));
+
+    // abandoned transaction releases its reservation
+    let first_entries = first_tx.utxo_entries().clone();
+    drop(first_tx);
+    assert!(first_entries.iter().all(|entry| !context.is_reserved(entry)));
+    let third_tx = make_generator()?.generate_transaction()?.expect("third transaction");
+    assert_eq!(third_tx.utxo_entries(), &first_entries);
+
+    // expired reservations are released
+    drop(third_tx);
+    context.reserve(first_entries.iter(), Duration::from_millis(10));
+    assert!(first_entries.iter().all(|entry| context.is_reserved(entry)));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(first_entries.iter().all(|entry| !context.is_reserved(entry)));
+
+    Ok(())
+}
+
+async fn make_reorg_context(network_id: NetworkId, values: &[f64]) -> Result<(UtxoContext, Vec<UtxoEntryReference>)> {
+    let rpc_api_mock = Arc::new(RpcCoreMock::new());
+    let processor = UtxoProcessor::new(Some(rpc_api_mock.clone().into()), Some(network_id), None, None);
+    processor.mock_set_connected(true);
+    processor.handle_daa_score_change(1_000_000).await?;
+    let context = UtxoContext::new(&processor, UtxoContextBinding::default());
+    let change_address = change_address(network_id.into());
+    let entries = values
+        .iter()
+        .map(|value| UtxoEntryReference::simulated_with_address(kaspa_to_sompi(*value), &change_address))
+        .collect::<Vec<_>>();
+    for entry in entries.iter() {
+        context.insert(entry.clone(), 1_000_000, false).await?;
+    }
+    context.update_balance().await?;
+    Ok((context, entries))
+}
+
+#[tokio::test]
+async fn test_utxo_reorg_disconnected_funding() -> Result<()> {
+    let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+    let (context, entries) = make_reorg_context(network_id, &[10.0, 20.0]).await?;
+    assert_eq!(context.balance().unwrap().mature, kaspa_to_sompi(30.0));
+
+    // the funding block of the second entry is disconnected while
+    // the entry is reserved by an in-flight transaction
+    context.reserve(&entries[1..], UTXO_RESERVATION_TIMEOUT);
+    context.reconcile_reorg(vec![entries[1].clone()], vec![], 1_000_001).await?;
+
+    assert_eq!(context.mature_utxo_size(), 1);
+    assert!(!context.is_reserved(&entries[1]));
+    assert_eq!(context.balance().unwrap().mature, kaspa_to_sompi(10.0));
+
+    // entries unknown to the context are ignored
+    context.reconcile_reorg(vec![entries[1].clone()], vec![], 1_000_001).await?;
+    assert_eq!(context.balance().unwrap().mature, kaspa_to_sompi(10.0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_utxo_reorg_restores_dropped_transaction_inputs() -> Result<()> {
+    let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+    let (context, entries) = make_reorg_context(network_id, &[10.0, 20.0]).await?;
+
+    let payment_output = PaymentOutput::new(output_address(network_id.into()), kaspa_to_sompi(5.0));
+    let settings = GeneratorSettings::try_new_with_context(
+        context.clone(),
+        change_address(network_id.into()),
+        1,
+        1,
+        payment_output.into(),
+        Fees::SenderPays(0),
+        None,
+        None,
+    )?;
+    let generator = Generator::try_new(settings, None, None)?;
+    let pending_tx = generator.generate_transaction()?.expect("transaction");
+    let inputs = pending_tx.utxo_entries().iter().cloned().collect::<Vec<_>>();
+    assert!(inputs.iter().all(|input| entries.contains(input)));
+
+    // the transaction is submitted and accepted, the node reports the inputs as spent
+    context.register_outgoing_transaction(&pending_tx).await?;
+    context.handle_utxo_removed(inputs.clone(), 1_000_001).await?;
+    assert_eq!(context.mature_utxo_size(), entries.len() - inputs.len());
+    assert!(context.processor().outgoing().contains_key(&pending_tx.id()));
+
+    // the accepting block is disconnected and the node reports the inputs as unspent again
+    context.handle_utxo_added(inputs.clone(), 1_000_002).await?;
+    assert!(context.context().outgoing.is_empty());
+    assert!(!context.processor().outgoing().contains_key(&pending_tx.id()));
+    assert_eq!(context.mature_utxo_size(), entries.len());
+    assert_eq!(context.balance().unwrap().mature, kaspa_to_sompi(30.0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_utxo_changed_reorg_refreshes_cached_entry() -> Result<()> {
+    use kaspa_consensus_core::tx::{TransactionId, TransactionOutpoint, UtxoEntry};
+    use kaspa_rpc_core::{RpcUtxosByAddressesEntry, UtxosChangedNotification};
+    use kaspa_txscript::pay_to_address_script;
+
+    let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+    let rpc_api_mock = Arc::new(RpcCoreMock::new());
+    let processor = UtxoProcessor::new(Some(rpc_api_mock.clone().into()), Some(network_id), None, None);
+    let context = UtxoContext::new(&processor, UtxoContextBinding::default());
+    let address = change_address(network_id.into());
+    processor.register_addresses(vec![Arc::new(address.clone())], &context).await?;
+    processor.mock_set_connected(true);
+    processor.handle_daa_score_change(1_000_000).await?;
+
+    let outpoint = TransactionOutpoint::new(TransactionId::from_u64_word(42), 0);
+    let entry = |block_daa_score: u64| RpcUtxosByAddressesEntry {
+        address: Some(address.clone()),
+        outpoint,
+        utxo_entry: UtxoEntry::new(kaspa_to_sompi(10.0), pay_to_address_script(&address), block_daa_score, false),
+    };
+    let notify = |added: Vec<RpcUtxosByAddressesEntry>, removed: Vec<RpcUtxosByAddressesEntry>| {
+        processor.handle_utxo_changed(UtxosChangedNotification { added: Arc::new(added), removed: Arc::new(removed) })
+    };
+    let block_daa_score = |context: &UtxoContext| context.context().map.values().next().map(|utxo| utxo.block_daa_score());
+
+    notify(vec![entry(100)], vec![]).await?;
+    assert_eq!(block_daa_score(&context), Some(100));
+
+    // the funding block is disconnected and the outpoint is re-added by a different block
+    notify(vec![], vec![entry(100)]).await?;
+    assert_eq!(block_daa_score(&context), None);
+    assert!(processor.utxo_entry_cache().is_empty());
+    notify(vec![entry(200)], vec![]).await?;
+    assert_eq!(block_daa_score(&context), Some(200));
+    assert_eq!(processor.utxo_entry_cache().peek(&UtxoEntryId::from(outpoint)).map(|utxo| utxo.block_daa_score()), Some(200));
+
+    // removal and re-addition delivered by the same notification
+    notify(vec![entry(300)], vec![entry(200)]).await?;
+    assert_eq!(block_daa_score(&context), Some(300));
+
+    // removed entries are resolved through the cache
+    let cache = processor.utxo_entry_cache();
+    assert_eq!((cache.hits(), cache.misses()), (2, 0));
+
+    Ok(())
+}
@@ -65,8 +65,8 @@ impl WalletApi for super::Wallet {
     // -------------------------------------------------------------------------------------
 
     async fn ping_call(self: Arc<Self>, request: PingRequest) -> Result<PingResponse> {
-        log_info!("Wallet received ping request '{:?}' ...", request.payload);
-        Ok(PingResponse { payload: request.payload })
+        let round_trip_time = self.ping_node(DEFAULT_PING_TIMEOUT).await?;
+        Ok(PingResponse { payload: request.payload, round_trip_time_usec: round_trip_time.as_micros() as u64 })
     }
 
     async fn batch_call(self: Arc<Self>, _request: BatchRequest) -> Result<BatchResponse> {
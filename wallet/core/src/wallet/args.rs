@@ -64,11 +64,19 @@ pub struct PrvKeyDataCreateArgs {
     pub name: Option<String>,
     pub payment_secret: Option<Secret>,
     pub mnemonic: String,
+    /// BIP39 mnemonic passphrase altering the derived seed. When set, the payment secret
+    /// is only used to encrypt the private key data; otherwise it doubles as the passphrase.
+    pub bip39_passphrase: Option<Secret>,
 }
 
 impl PrvKeyDataCreateArgs {
     pub fn new(name: Option<String>, payment_secret: Option<Secret>, mnemonic: String) -> Self {
-        Self { name, payment_secret, mnemonic }
+        Self { name, payment_secret, mnemonic, bip39_passphrase: None }
+    }
+
+    pub fn with_bip39_passphrase(mut self, bip39_passphrase: Option<Secret>) -> Self {
+        self.bip39_passphrase = bip39_passphrase;
+        self
     }
 }
 
@@ -82,11 +90,19 @@ impl Zeroize for PrvKeyDataCreateArgs {
 pub struct AccountCreateArgsBip32 {
     pub account_name: Option<String>,
     pub account_index: Option<u64>,
+    /// Custom hardened derivation path of the account (e.g. `m/44'/111111'/5'`),
+    /// mutually exclusive with `account_index`.
+    pub account_path: Option<String>,
 }
 
 impl AccountCreateArgsBip32 {
     pub fn new(account_name: Option<String>, account_index: Option<u64>) -> Self {
-        Self { account_name, account_index }
+        Self { account_name, account_index, account_path: None }
+    }
+
+    pub fn with_account_path(mut self, account_path: Option<String>) -> Self {
+        self.account_path = account_path;
+        self
     }
 }
 
@@ -128,7 +144,7 @@ impl AccountCreateArgs {
         account_index: Option<u64>,
     ) -> Self {
         let prv_key_data_args = PrvKeyDataArgs { prv_key_data_id, payment_secret };
-        let account_args = AccountCreateArgsBip32 { account_name, account_index };
+        let account_args = AccountCreateArgsBip32::new(account_name, account_index);
         AccountCreateArgs::Bip32 { prv_key_data_args, account_args }
     }
 
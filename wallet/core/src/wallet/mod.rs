@@ -3,12 +3,15 @@
 //!
 
 use crate::account::ScanNotifier;
+use crate::daa::DaaScoreTimeEstimator;
+use crate::derivation::parse_account_derivation_path;
 use crate::factory::try_load_account;
 use crate::imports::*;
 use crate::settings::{SettingsStore, WalletSettings};
 use crate::storage::interface::{OpenArgs, StorageDescriptor};
 use crate::storage::local::interface::LocalStore;
 use crate::storage::local::Storage;
+use crate::tx::{BroadcastResult, FeeRates, FeeRatesCache, UnsignedTransaction};
 use crate::wallet::maps::ActiveAccountMap;
 use chacha20poly1305::{aead::AeadMut, Key, KeyInit};
 use kaspa_bip32::{ExtendedKey, Language, Mnemonic, Prefix as KeyPrefix, WordCount};
@@ -17,15 +20,19 @@ use kaspa_notify::{
     scope::{Scope, VirtualDaaScoreChangedScope},
 };
 use kaspa_rpc_core::notify::mode::NotificationMode;
+use kaspa_rpc_core::{RpcError, RpcMempoolEntry, RpcMempoolEntryByAddress};
 use kaspa_wrpc_client::{KaspaRpcClient, WrpcEncoding};
 use workflow_core::task::spawn;
+use workflow_core::time::unixtime_as_millis_u64;
 
 use crate::error::Error::Custom;
 
 pub mod api;
 pub mod args;
 pub mod maps;
+pub mod registry;
 pub use args::*;
+pub use registry::*;
 
 #[derive(Debug)]
 pub struct EncryptedMnemonic<T: AsRef<[u8]>> {
@@ -75,6 +82,56 @@ impl<'a, T: AsRef<[u8]>> MultisigWalletFileV1<'a, T> {
     const NUM_THREADS: u32 = 8;
 }
 
+#[derive(Debug, Deserialize)]
+pub struct KaspaWalletEncryptedMnemonic {
+    #[serde(with = "kaspa_utils::serde_bytes")]
+    pub cipher: Vec<u8>,
+    #[serde(with = "kaspa_utils::serde_bytes")]
+    pub salt: Vec<u8>,
+}
+
+/// Contents of the `keys.json` file produced by the golang `kaspawallet`
+/// (covers both version 0 and version 1 of the file format).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KaspaWalletFile {
+    pub version: u32,
+    pub num_threads: Option<u32>,
+    pub encrypted_mnemonics: Vec<KaspaWalletEncryptedMnemonic>,
+    pub public_keys: Vec<String>,
+    pub minimum_signatures: u16,
+    pub cosigner_index: u8,
+    #[serde(default)]
+    pub ecdsa: bool,
+}
+
+impl KaspaWalletFile {
+    pub fn try_from_json(json: &str) -> Result<Self> {
+        let file: Self = serde_json::from_str(json).map_err(|err| Error::KaspaWalletFile(err.to_string()))?;
+        if file.version > 1 {
+            return Err(Error::KaspaWalletFileVersion(file.version));
+        }
+        if file.version == 0 && file.num_threads.is_none() {
+            return Err(Error::KaspaWalletFile("missing 'numThreads' in a version 0 file".to_string()));
+        }
+        if file.encrypted_mnemonics.is_empty() {
+            return Err(Error::KaspaWalletFile("no encrypted mnemonics".to_string()));
+        }
+        if file.public_keys.is_empty() {
+            return Err(Error::KaspaWalletFile("no public keys".to_string()));
+        }
+        Ok(file)
+    }
+
+    pub fn is_multisig(&self) -> bool {
+        self.encrypted_mnemonics.len() > 1 || self.public_keys.len() > 1
+    }
+}
+
+/// Time after which [`Wallet::ping_node()`] gives up waiting for the node response
+/// when pinging the node through the wallet API.
+pub const DEFAULT_PING_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
 pub enum WalletBusMessage {
     Discovery { record: TransactionRecord },
@@ -92,6 +149,8 @@ pub struct Inner {
     multiplexer: Multiplexer<Box<Events>>,
     wallet_bus: Channel<WalletBusMessage>,
     estimation_abortables: Mutex<HashMap<AccountId, Abortable>>,
+    fee_rates: FeeRatesCache,
+    change_address_rotation: AtomicBool,
 }
 
 /// `Wallet` data structure
@@ -123,6 +182,17 @@ impl Wallet {
     }
 
     pub fn try_with_rpc(rpc: Option<Rpc>, store: Arc<dyn Interface>, network_id: Option<NetworkId>) -> Result<Wallet> {
+        let settings = SettingsStore::new_with_storage(Storage::default_settings_store());
+        Self::try_with_rpc_and_settings(rpc, store, network_id, settings)
+    }
+
+    /// Creates a wallet using the supplied `settings` store, which can be shared with other wallets.
+    pub fn try_with_rpc_and_settings(
+        rpc: Option<Rpc>,
+        store: Arc<dyn Interface>,
+        network_id: Option<NetworkId>,
+        settings: SettingsStore<WalletSettings>,
+    ) -> Result<Wallet> {
         let multiplexer = Multiplexer::<Box<Events>>::new();
         let wallet_bus = Channel::unbounded();
         let utxo_processor =
@@ -137,10 +207,12 @@ impl Wallet {
                 listener_id: Mutex::new(None),
                 task_ctl: DuplexChannel::oneshot(),
                 selected_account: Mutex::new(None),
-                settings: SettingsStore::new_with_storage(Storage::default_settings_store()),
+                settings,
                 utxo_processor: utxo_processor.clone(),
                 wallet_bus,
                 estimation_abortables: Mutex::new(HashMap::new()),
+                fee_rates: FeeRatesCache::default(),
+                change_address_rotation: AtomicBool::new(false),
             }),
         };
 
@@ -226,6 +298,7 @@ impl Wallet {
         if self.is_open() {
             self.reset(true).await?;
             self.store().close().await?;
+            self.settings().lock()?;
             self.notify(Events::WalletClose).await?;
         }
 
@@ -287,6 +360,11 @@ impl Wallet {
         self.store().open(wallet_secret, OpenArgs::new(filename)).await?;
         let wallet_name = self.store().descriptor();
 
+        // settings may be encrypted with the secret of another wallet
+        if let Err(err) = self.settings().unlock(wallet_secret).await {
+            log_warning!("Unable to unlock settings: {err}");
+        }
+
         if was_open {
             self.notify(Events::WalletClose).await?;
         }
@@ -414,6 +492,25 @@ impl Wallet {
         self.inner.store.as_prv_key_data_store()?.load_key_data(wallet_secret, id).await
     }
 
+    /// Creates the watch-only export of `account`. The wallet secret is verified against
+    /// the private key data of the account, while no payment secret is required since the
+    /// export doesn't contain any private key material.
+    pub async fn export_account_descriptor(
+        &self,
+        wallet_secret: &Secret,
+        account: &Arc<dyn Account>,
+    ) -> Result<AccountExportDescriptor> {
+        let descriptor = account.descriptor()?;
+        let prv_key_data_store = self.inner.store.as_prv_key_data_store()?;
+        for prv_key_data_id in descriptor.prv_key_data_ids() {
+            prv_key_data_store
+                .load_key_data(wallet_secret, &prv_key_data_id)
+                .await?
+                .ok_or(Error::PrivateKeyNotFound(prv_key_data_id))?;
+        }
+        descriptor.to_export_descriptor(crate::utxo::scan::DEFAULT_WINDOW_SIZE)
+    }
+
     pub async fn get_prv_key_info(&self, account: &Arc<dyn Account>) -> Result<Option<Arc<PrvKeyDataInfo>>> {
         self.inner.store.as_prv_key_data_store()?.load_key_info(account.prv_key_data_id()?).await
     }
@@ -440,6 +537,7 @@ impl Wallet {
 
     pub async fn bind_rpc(self: &Arc<Self>, rpc: Option<Rpc>) -> Result<()> {
         self.utxo_processor().bind_rpc(rpc).await?;
+        self.inner.fee_rates.clear();
         Ok(())
     }
 
@@ -455,10 +553,28 @@ impl Wallet {
         &self.inner.settings
     }
 
+    /// Whether transactions derive a fresh change address instead of reusing
+    /// the current change address of the account (disabled by default).
+    pub fn change_address_rotation(&self) -> bool {
+        self.inner.change_address_rotation.load(Ordering::SeqCst)
+    }
+
+    pub fn set_change_address_rotation(&self, change_address_rotation: bool) {
+        self.inner.change_address_rotation.store(change_address_rotation, Ordering::SeqCst);
+    }
+
     pub fn current_daa_score(&self) -> Option<u64> {
         self.utxo_processor().current_daa_score()
     }
 
+    /// Returns an estimator of the time of DAA scores referenced at the current
+    /// DAA score and time, if the wallet is connected to a network.
+    pub fn daa_score_time_estimator(&self) -> Option<DaaScoreTimeEstimator> {
+        let network_id = self.network_id().ok()?;
+        let current_daa_score = self.current_daa_score()?;
+        Some(DaaScoreTimeEstimator::new(network_id, current_daa_score, unixtime_as_millis_u64()))
+    }
+
     pub async fn load_settings(&self) -> Result<()> {
         self.settings().try_load().await?;
 
@@ -468,6 +584,10 @@ impl Wallet {
             self.set_network_id(network_type).unwrap_or_else(|_| log_error!("Unable to select network type: `{}`", network_type));
         }
 
+        if let Some(change_address_rotation) = settings.get(WalletSettings::ChangeAddressRotation) {
+            self.set_change_address_rotation(change_address_rotation);
+        }
+
         if let Some(url) = settings.get::<String>(WalletSettings::Server) {
             if let Some(wrpc_client) = self.wrpc_client() {
                 wrpc_client.set_url(url.as_str()).unwrap_or_else(|_| log_error!("Unable to set rpc url: `{}`", url));
@@ -477,13 +597,29 @@ impl Wallet {
         Ok(())
     }
 
+    /// Encrypts the sensitive application settings with the secret of the open wallet.
+    pub async fn enable_settings_encryption(&self, wallet_secret: &Secret) -> Result<()> {
+        if !self.is_open() {
+            return Err(Error::WalletNotOpen);
+        }
+        self.store().verify_secret(wallet_secret).await?;
+        self.settings().enable_encryption(wallet_secret).await
+    }
+
+    /// Stores the application settings in plaintext.
+    pub async fn disable_settings_encryption(&self, wallet_secret: &Secret) -> Result<()> {
+        if !self.is_open() {
+            return Err(Error::WalletNotOpen);
+        }
+        self.store().verify_secret(wallet_secret).await?;
+        self.settings().disable_encryption(wallet_secret).await
+    }
+
     // intended for starting async management tasks
     pub async fn start(self: &Arc<Self>) -> Result<()> {
         // self.load_settings().await.unwrap_or_else(|_| log_error!("Unable to load settings, discarding..."));
 
-        // internal event loop
-        self.start_task().await?;
-        self.utxo_processor().start().await?;
+        self.start_with_shared_rpc().await?;
         // rpc services (notifier)
         if let Some(rpc_client) = self.wrpc_client() {
             rpc_client.start().await?;
@@ -492,6 +628,15 @@ impl Wallet {
         Ok(())
     }
 
+    /// Starts the wallet without starting its RPC client, for wallets sharing
+    /// the RPC client of another, already started, wallet.
+    pub async fn start_with_shared_rpc(self: &Arc<Self>) -> Result<()> {
+        // internal event loop
+        self.start_task().await?;
+        self.utxo_processor().start().await?;
+        Ok(())
+    }
+
     // intended for stopping async management task
     pub async fn stop(&self) -> Result<()> {
         self.utxo_processor().stop().await?;
@@ -518,8 +663,37 @@ impl Wallet {
         Ok(())
     }
 
-    pub async fn broadcast(&self) -> Result<()> {
-        Ok(())
+    /// Submits a chain of signed transactions, returning the outcome of each
+    /// transaction. Transactions following a rejected transaction are skipped.
+    /// All transactions are validated against the current network before any
+    /// transaction is submitted.
+    pub async fn broadcast(&self, transactions: &[UnsignedTransaction]) -> Result<Vec<BroadcastResult>> {
+        let network_id = self.network_id()?;
+        for transaction in transactions {
+            transaction.validate(&network_id)?;
+        }
+        Ok(crate::tx::broadcast(&self.rpc_api(), transactions).await)
+    }
+
+    /// Returns the mempool entry (fee, mass and orphan status) of the given
+    /// transaction or `None` if the transaction is not in the node mempool.
+    pub async fn get_mempool_entry(&self, transaction_id: &TransactionId) -> Result<Option<RpcMempoolEntry>> {
+        match self.rpc_api().get_mempool_entry(*transaction_id, true, false).await {
+            Ok(entry) => Ok(Some(entry)),
+            Err(RpcError::TransactionNotFound(_)) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Checks whether a (just broadcast) transaction has been accepted
+    /// into the node mempool, including the orphan pool.
+    pub async fn is_in_mempool(&self, transaction_id: &TransactionId) -> Result<bool> {
+        Ok(self.get_mempool_entry(transaction_id).await?.is_some())
+    }
+
+    /// Returns mempool entries sending from or to the given addresses.
+    pub async fn get_mempool_entries_by_addresses(&self, addresses: Vec<Address>) -> Result<Vec<RpcMempoolEntryByAddress>> {
+        Ok(self.rpc_api().get_mempool_entries_by_addresses(addresses, true, false).await?)
     }
 
     pub fn set_network_id(&self, network_id: NetworkId) -> Result<()> {
@@ -527,6 +701,7 @@ impl Wallet {
             return Err(Error::NetworkTypeConnected);
         }
         self.utxo_processor().set_network_id(network_id);
+        self.inner.fee_rates.clear();
         Ok(())
     }
 
@@ -671,19 +846,40 @@ impl Wallet {
             .await?
             .ok_or_else(|| Error::PrivateKeyNotFound(prv_key_data_id))?;
 
-        let AccountCreateArgsBip32 { account_name, account_index } = account_args;
+        let AccountCreateArgsBip32 { account_name, account_index, account_path } = account_args;
 
-        let account_index = if let Some(account_index) = account_index {
-            account_index
-        } else {
-            account_store.clone().len(Some(prv_key_data_id)).await? as u64
+        let account_path = account_path.as_deref().map(parse_account_derivation_path).transpose()?;
+
+        let account_index = match (account_index, &account_path) {
+            (Some(_), Some(_)) => return Err(Error::custom("account index and derivation path are mutually exclusive")),
+            (Some(account_index), None) => account_index,
+            (None, Some(account_path)) => {
+                account_path.iter().last().map(|child_number| child_number.index() as u64).unwrap_or_default()
+            }
+            (None, None) => account_store.clone().len(Some(prv_key_data_id)).await? as u64,
         };
 
-        let xpub_key = prv_key_data.create_xpub(payment_secret, BIP32_ACCOUNT_KIND.into(), account_index).await?;
+        let xpub_key = if let Some(account_path) = &account_path {
+            prv_key_data.create_xpub_with_path(payment_secret, account_path).await?
+        } else {
+            prv_key_data.create_xpub(payment_secret, BIP32_ACCOUNT_KIND.into(), account_index).await?
+        };
         let xpub_keys = Arc::new(vec![xpub_key]);
+        let bip39_passphrase = prv_key_data.payload.decrypt(payment_secret)?.has_bip39_passphrase();
 
-        let account: Arc<dyn Account> =
-            Arc::new(bip32::Bip32::try_new(self, account_name, prv_key_data.id, account_index, xpub_keys, false).await?);
+        let account: Arc<dyn Account> = Arc::new(
+            bip32::Bip32::try_new_with_account_path(
+                self,
+                account_name,
+                prv_key_data.id,
+                account_index,
+                account_path,
+                xpub_keys,
+                false,
+            )
+            .await?
+            .with_bip39_passphrase(bip39_passphrase),
+        );
 
         if account_store.load_single(account.id()).await?.is_some() {
             return Err(Error::AccountAlreadyExists(*account.id()));
@@ -749,8 +945,9 @@ impl Wallet {
         prv_key_data_create_args: PrvKeyDataCreateArgs,
     ) -> Result<PrvKeyDataId> {
         let mnemonic = Mnemonic::new(prv_key_data_create_args.mnemonic, Language::default())?;
-        let prv_key_data = PrvKeyData::try_from_mnemonic(
+        let prv_key_data = PrvKeyData::try_new_from_mnemonic_with_bip39_passphrase(
             mnemonic.clone(),
+            prv_key_data_create_args.bip39_passphrase.as_ref(),
             prv_key_data_create_args.payment_secret.as_ref(),
             self.store().encryption_kind()?,
         )?;
@@ -829,6 +1026,35 @@ impl Wallet {
         self.utxo_processor().is_connected()
     }
 
+    /// Pings the connected node and returns the round-trip time. Fails with
+    /// [`Error::PingTimeout`] if the node does not respond within `timeout`.
+    pub async fn ping_node(&self, timeout: Duration) -> Result<Duration> {
+        let rpc_api = self.rpc_api();
+        let start = Instant::now();
+        select! {
+            result = rpc_api.ping().fuse() => {
+                result?;
+                Ok(start.elapsed())
+            },
+            _ = sleep(timeout).fuse() => Err(Error::PingTimeout(timeout)),
+        }
+    }
+
+    /// Returns the fee rates estimated by the node from the content of its mempool, cached for
+    /// [`DEFAULT_FEE_RATES_TTL`](crate::tx::DEFAULT_FEE_RATES_TTL). The minimum fee rate is returned
+    /// for all buckets while the node is not synced, since its mempool is not representative then.
+    pub async fn estimate_fee_rates(&self) -> Result<FeeRates> {
+        if !self.is_synced() {
+            return Ok(FeeRates::default());
+        }
+        if let Some(rates) = self.inner.fee_rates.get() {
+            return Ok(rates);
+        }
+        let rates = FeeRates::from(self.rpc_api().get_fee_estimate().await?);
+        self.inner.fee_rates.store(rates);
+        Ok(rates)
+    }
+
     pub(crate) async fn handle_discovery(&self, record: TransactionRecord) -> Result<()> {
         let transaction_store = self.store().as_transaction_record_store()?;
 
@@ -950,6 +1176,23 @@ impl Wallet {
         self.inner.store.exists(name).await
     }
 
+    /// Enumerates wallets available in the storage together with their
+    /// creation time and account count. Wallet data is not decrypted.
+    pub async fn enumerate(&self) -> Result<Vec<WalletInfo>> {
+        self.inner.store.wallet_info_list().await
+    }
+
+    /// Renames a wallet in the storage. The wallet must not be open.
+    pub async fn rename_wallet(&self, filename: &str, new_filename: &str) -> Result<()> {
+        self.inner.store.wallet_rename(filename, new_filename).await
+    }
+
+    /// Deletes a wallet from the storage. The wallet must not be open
+    /// and the wallet secret must be supplied to confirm the deletion.
+    pub async fn delete_wallet(&self, wallet_secret: &Secret, filename: &str) -> Result<()> {
+        self.inner.store.wallet_delete(wallet_secret, filename).await
+    }
+
     pub async fn keys(&self) -> Result<impl Stream<Item = Result<Arc<PrvKeyDataInfo>>>> {
         self.inner.store.as_prv_key_data_store()?.iter().await
     }
@@ -1108,6 +1351,64 @@ impl Wallet {
     //     Ok(Box::pin(stream))
     // }
 
+    /// Import a golang `kaspawallet` `keys.json` file, decrypting its mnemonics
+    /// with `import_secret` and creating a matching bip32 or multisig account.
+    pub async fn import_kaspawallet_golang(
+        self: &Arc<Wallet>,
+        import_secret: &Secret,
+        wallet_secret: &Secret,
+        json: &str,
+    ) -> Result<Arc<dyn Account>> {
+        let file = KaspaWalletFile::try_from_json(json)?;
+        let multisig = file.is_multisig();
+        let KaspaWalletFile { version, num_threads, encrypted_mnemonics, public_keys, minimum_signatures, cosigner_index, ecdsa } =
+            file;
+
+        let mut encrypted_mnemonics = encrypted_mnemonics
+            .into_iter()
+            .map(|KaspaWalletEncryptedMnemonic { cipher, salt }| EncryptedMnemonic { cipher, salt })
+            .collect::<Vec<_>>();
+        let xpublic_keys = public_keys.iter().map(String::as_str).collect::<Vec<_>>();
+
+        match (multisig, version) {
+            (false, 0) => {
+                let file = SingleWalletFileV0 {
+                    num_threads: num_threads.unwrap_or_default(),
+                    encrypted_mnemonic: encrypted_mnemonics.remove(0),
+                    xpublic_key: xpublic_keys[0],
+                    ecdsa,
+                };
+                self.import_kaspawallet_golang_single_v0(import_secret, wallet_secret, file).await
+            }
+            (false, _) => {
+                let file =
+                    SingleWalletFileV1 { encrypted_mnemonic: encrypted_mnemonics.remove(0), xpublic_key: xpublic_keys[0], ecdsa };
+                self.import_kaspawallet_golang_single_v1(import_secret, wallet_secret, file).await
+            }
+            (true, 0) => {
+                let file = MultisigWalletFileV0 {
+                    num_threads: num_threads.unwrap_or_default(),
+                    encrypted_mnemonics,
+                    xpublic_keys,
+                    required_signatures: minimum_signatures,
+                    cosigner_index,
+                    ecdsa,
+                };
+                self.import_kaspawallet_golang_multisig_v0(import_secret, wallet_secret, file).await
+            }
+            (true, _) => {
+                let file = MultisigWalletFileV1 {
+                    encrypted_mnemonics,
+                    xpublic_keys,
+                    required_signatures: minimum_signatures,
+                    cosigner_index,
+                    ecdsa,
+                };
+                self.import_kaspawallet_golang_multisig_v1(import_secret, wallet_secret, file).await
+            }
+        }
+    }
+
     pub async fn import_kaspawallet_golang_single_v1<T: AsRef<[u8]>>(
         self: &Arc<Wallet>,
         import_secret: &Secret,
@@ -1127,7 +1428,7 @@ impl Wallet {
         if prv_key_data.create_xpub(None, BIP32_ACCOUNT_KIND.into(), 0).await?.to_string(Some(prefix)) != file.xpublic_key {
             return Err(Custom("imported xpub does not equal derived one".to_owned()));
         }
-        self.import_with_mnemonic(wallet_secret, None, mnemonic, BIP32_ACCOUNT_KIND.into()).await
+        self.import_with_mnemonic(wallet_secret, None, None, mnemonic, BIP32_ACCOUNT_KIND.into()).await
     }
 
     pub async fn import_kaspawallet_golang_single_v0<T: AsRef<[u8]>>(
@@ -1148,7 +1449,7 @@ impl Wallet {
         if prv_key_data.create_xpub(None, BIP32_ACCOUNT_KIND.into(), 0).await.unwrap().to_string(Some(prefix)) != file.xpublic_key {
             return Err(Custom("imported xpub does not equal derived one".to_owned()));
         }
-        self.import_with_mnemonic(wallet_secret, None, mnemonic, BIP32_ACCOUNT_KIND.into()).await
+        self.import_with_mnemonic(wallet_secret, None, None, mnemonic, BIP32_ACCOUNT_KIND.into()).await
     }
 
     pub async fn import_kaspawallet_golang_multisig_v0<T: AsRef<[u8]>>(
@@ -1307,14 +1608,48 @@ impl Wallet {
         Ok(())
     }
 
+    /// Imports an account from a mnemonic. When `bip39_passphrase` is provided, the seed is derived
+    /// with it and `payment_secret` only encrypts the private key data; otherwise the payment secret
+    /// doubles as the BIP39 passphrase.
     pub async fn import_with_mnemonic(
         self: &Arc<Wallet>,
         wallet_secret: &Secret,
         payment_secret: Option<&Secret>,
+        bip39_passphrase: Option<&Secret>,
         mnemonic: Mnemonic,
         account_kind: AccountKind,
     ) -> Result<Arc<dyn Account>> {
-        let prv_key_data = storage::PrvKeyData::try_new_from_mnemonic(mnemonic, payment_secret, self.store().encryption_kind()?)?;
+        let (account, _) = self
+            .import_with_mnemonic_and_scan_control(
+                wallet_secret,
+                payment_secret,
+                bip39_passphrase,
+                mnemonic,
+                account_kind,
+                &ScanControl::default(),
+            )
+            .await?;
+        Ok(account)
+    }
+
+    /// Imports an account from a mnemonic like [`Wallet::import_with_mnemonic`], reporting the
+    /// progress of the scan of its addresses to `control`. If the scan is aborted, the account is
+    /// imported with the UTXOs found so far and the returned progress is marked as aborted.
+    pub async fn import_with_mnemonic_and_scan_control(
+        self: &Arc<Wallet>,
+        wallet_secret: &Secret,
+        payment_secret: Option<&Secret>,
+        bip39_passphrase: Option<&Secret>,
+        mnemonic: Mnemonic,
+        account_kind: AccountKind,
+        control: &ScanControl,
+    ) -> Result<(Arc<dyn Account>, ScanProgress)> {
+        let prv_key_data = storage::PrvKeyData::try_new_from_mnemonic_with_bip39_passphrase(
+            mnemonic,
+            bip39_passphrase,
+            payment_secret,
+            self.store().encryption_kind()?,
+        )?;
         let prv_key_data_store = self.store().as_prv_key_data_store()?;
         if prv_key_data_store.load_key_data(wallet_secret, &prv_key_data.id).await?.is_some() {
             return Err(Error::PrivateKeyAlreadyExists(prv_key_data.id));
@@ -1326,8 +1661,13 @@ impl Wallet {
                 let xpub_key = prv_key_data.create_xpub(payment_secret, account_kind, account_index).await?;
                 let xpub_keys = Arc::new(vec![xpub_key]);
                 let ecdsa = false;
+                let bip39_passphrase = bip39_passphrase.is_some_and(|bip39_passphrase| !bip39_passphrase.as_ref().is_empty());
                 // ---
-                Arc::new(bip32::Bip32::try_new(self, None, prv_key_data.id, account_index, xpub_keys, ecdsa).await?)
+                Arc::new(
+                    bip32::Bip32::try_new(self, None, prv_key_data.id, account_index, xpub_keys, ecdsa)
+                        .await?
+                        .with_bip39_passphrase(bip39_passphrase),
+                )
             }
             LEGACY_ACCOUNT_KIND => Arc::new(legacy::Legacy::try_new(self, None, prv_key_data.id).await?),
             _ => {
@@ -1337,17 +1677,19 @@ impl Wallet {
 
         let account_store = self.inner.store.as_account_store()?;
         self.inner.store.batch().await?;
+        prv_key_data_store.store(wallet_secret, prv_key_data).await?;
         account_store.store_single(&account.to_storage()?, None).await?;
         self.inner.store.flush(wallet_secret).await?;
 
-        if let Ok(legacy_account) = account.clone().as_legacy_account() {
+        let progress = if let Ok(legacy_account) = account.clone().as_legacy_account() {
             self.legacy_accounts().insert(account.clone());
             legacy_account.create_private_context(wallet_secret, None, None).await?;
-            legacy_account.clone().start().await?;
+            let progress = self.start_imported_account(&account, control).await?;
             legacy_account.clear_private_context().await?;
+            progress
         } else {
-            account.clone().start().await?;
-        }
+            self.start_imported_account(&account, control).await?
+        };
 
         // if is_legacy {
         //     account.clone().initialize_private_data(wallet_secret, None, None).await?;
@@ -1363,7 +1705,18 @@ impl Wallet {
         //     account.clone().clear_private_data().await?;
         // }
 
-        Ok(account)
+        Ok((account, progress))
+    }
+
+    /// Activates an imported account like [`Account::start()`] does, scanning
+    /// its addresses with `control` if the wallet is connected.
+    async fn start_imported_account(self: &Arc<Wallet>, account: &Arc<dyn Account>, control: &ScanControl) -> Result<ScanProgress> {
+        let vacated = self.active_accounts().insert(account.clone());
+        if vacated.is_none() && self.is_connected() {
+            account.clone().scan_with_control(None, None, control).await
+        } else {
+            Ok(ScanProgress::default())
+        }
     }
 
     /// Perform a "2d" scan of account derivations while scanning addresses
@@ -1486,16 +1839,21 @@ fn decrypt_mnemonic<T: AsRef<[u8]>>(
     EncryptedMnemonic { cipher, salt }: EncryptedMnemonic<T>,
     pass: &[u8],
 ) -> Result<String> {
-    let params = argon2::ParamsBuilder::new().t_cost(1).m_cost(64 * 1024).p_cost(num_threads).output_len(32).build().unwrap();
+    let params = argon2::ParamsBuilder::new().t_cost(1).m_cost(64 * 1024).p_cost(num_threads).output_len(32).build()?;
     let mut key = [0u8; 32];
-    argon2::Argon2::new(argon2::Algorithm::Argon2id, Default::default(), params)
-        .hash_password_into(pass, salt.as_ref(), &mut key[..])
-        .unwrap();
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, Default::default(), params).hash_password_into(
+        pass,
+        salt.as_ref(),
+        &mut key[..],
+    )?;
     let mut aead = chacha20poly1305::XChaCha20Poly1305::new(Key::from_slice(&key));
+    if cipher.as_ref().len() < 24 {
+        return Err(Error::KaspaWalletFile("encrypted mnemonic is too short".to_string()));
+    }
     let (nonce, ciphertext) = cipher.as_ref().split_at(24);
 
-    let decrypted = aead.decrypt(nonce.into(), ciphertext).unwrap();
-    Ok(unsafe { String::from_utf8_unchecked(decrypted) })
+    let decrypted = aead.decrypt(nonce.into(), ciphertext).map_err(|_| Error::KaspaWalletDecrypt)?;
+    String::from_utf8(decrypted).map_err(|_| Error::KaspaWalletDecrypt)
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -1505,6 +1863,7 @@ mod test {
     use std::{str::FromStr, thread::sleep, time};
 
     use super::*;
+    use crate::api::WalletApi;
     use crate::derivation::gen1;
     use crate::utxo::{UtxoContext, UtxoContextBinding, UtxoIterator};
     use kaspa_addresses::{Address, Prefix, Version};
@@ -1514,6 +1873,223 @@ mod test {
     use kaspa_txscript::pay_to_address_script;
     use workflow_rpc::client::ConnectOptions;
 
+    async fn create_wallet_for_import(
+        rpc_mock: Option<&Arc<crate::tests::RpcCoreMock>>,
+        wallet_secret: &Secret,
+    ) -> Result<Arc<Wallet>> {
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+        let rpc = rpc_mock.map(|rpc_mock| rpc_mock.clone().into());
+        let wallet = Arc::new(Wallet::try_with_rpc(rpc, Wallet::resident_store()?, Some(network_id))?);
+        let wallet_args = WalletCreateArgs::new(None, None, EncryptionKind::XChaCha20Poly1305, None, false);
+        wallet.create_wallet(wallet_secret, wallet_args).await?;
+        if rpc_mock.is_some() {
+            wallet.utxo_processor().mock_connect().await?;
+            wallet.utxo_processor().handle_daa_score_change(1).await?;
+        }
+        Ok(wallet)
+    }
+
+    #[tokio::test]
+    async fn test_import_scan_progress_and_abort() -> Result<()> {
+        use crate::utils::kaspa_to_sompi;
+        use kaspa_bip32::WordCount;
+        use kaspa_consensus_core::tx::{TransactionId, TransactionOutpoint, UtxoEntry};
+        use kaspa_rpc_core::RpcUtxosByAddressesEntry;
+
+        let wallet_secret = Secret::from("wallet-secret");
+        let phrase = Mnemonic::random(WordCount::Words24, Default::default())?.phrase().to_string();
+        let mnemonic = || Mnemonic::new(phrase.as_str(), Language::English);
+
+        // derive the account receive addresses without scanning
+        let wallet = create_wallet_for_import(None, &wallet_secret).await?;
+        let account = wallet.import_with_mnemonic(&wallet_secret, None, None, mnemonic()?, BIP32_ACCOUNT_KIND.into()).await?;
+        let receive_addresses = account.as_derivation_capable()?.derivation().receive_address_manager().get_range(0..16)?;
+
+        // funded addresses in the first and the second scan windows
+        let rpc_mock = Arc::new(crate::tests::RpcCoreMock::new());
+        for (id, index) in [(1, 2), (2, 12)] {
+            let address = receive_addresses[index].clone();
+            let utxo_entry = UtxoEntry::new(kaspa_to_sompi(1.0), pay_to_address_script(&address), 0, false);
+            let outpoint = TransactionOutpoint::new(TransactionId::from_u64_word(id), 0);
+            rpc_mock.add_utxo_by_address(RpcUtxosByAddressesEntry { address: Some(address), outpoint, utxo_entry });
+        }
+        let balance =
+            |account: &Arc<dyn Account>| account.balance().map(|balance| balance.mature + balance.pending).unwrap_or_default();
+
+        // a complete scan reports the progress of all scanned windows
+        let reports = Arc::new(Mutex::new(vec![]));
+        let reports_ = reports.clone();
+        let control = ScanControl::new(&Abortable::new(), Some(Arc::new(move |progress| reports_.lock().unwrap().push(progress))));
+        let wallet = create_wallet_for_import(Some(&rpc_mock), &wallet_secret).await?;
+        let (account, progress) = wallet
+            .import_with_mnemonic_and_scan_control(&wallet_secret, None, None, mnemonic()?, BIP32_ACCOUNT_KIND.into(), &control)
+            .await?;
+        // receive addresses: windows 0..8, 8..16 and 16..24; change addresses: windows 0..8 and 8..16
+        assert_eq!(progress, ScanProgress { scanned: 40, funded: 2, aborted: false });
+        assert_eq!(reports.lock().unwrap().last(), Some(&progress));
+        assert_eq!(balance(&account), kaspa_to_sompi(2.0));
+        let requests = rpc_mock.utxos_by_addresses_requests();
+        assert_eq!(requests, 5);
+
+        // aborting on the first progress report stops the scan after the first window
+        let abortable = Abortable::new();
+        let abortable_ = abortable.clone();
+        let control = ScanControl::new(&abortable, Some(Arc::new(move |_| abortable_.abort())));
+        let wallet = create_wallet_for_import(Some(&rpc_mock), &wallet_secret).await?;
+        let (account, progress) = wallet
+            .import_with_mnemonic_and_scan_control(&wallet_secret, None, None, mnemonic()?, BIP32_ACCOUNT_KIND.into(), &control)
+            .await?;
+        assert!(progress.aborted);
+        assert_eq!(progress.funded, 1);
+        assert!(progress.scanned <= 16, "the scan was not aborted promptly: {progress:?}");
+        assert!(rpc_mock.utxos_by_addresses_requests() - requests <= 2);
+
+        // the partial results are retained by the imported account
+        assert_eq!(balance(&account), kaspa_to_sompi(1.0));
+        assert_eq!(account.clone().as_derivation_capable()?.derivation().receive_address_manager().index(), 2);
+        assert!(wallet.active_accounts().get(account.id()).is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_with_mnemonic() -> Result<()> {
+        use crate::derivation::gen1::{PubkeyDerivationManager, WalletDerivationManager};
+        use crate::derivation::WalletDerivationManagerTrait;
+
+        // reference vector of the gen1 wallet derivation (`address_test_by_ktrv`)
+        let phrase = "hunt bitter praise lift buyer topic crane leopard uniform network inquiry over grain pass match crush marine strike doll relax fortune trumpet sunny silk";
+        let ktrv = "ktrv5himbbCxArFU2CHiEQyVHP1ABS1tA1SY88CwePzGeM8gHfWmkNBXehhKsESH7UwcxpjpDdMNbwtBfyPoZ7W59kYfVnUXKRgv8UguDns2FQb";
+        let derivation = WalletDerivationManager::from_master_xprv(ktrv, false, 0, None)?;
+        let first = PubkeyDerivationManager::create_address(&derivation.derive_receive_pubkey(0)?, Prefix::Testnet, false)?;
+
+        let wallet_secret = Secret::from("wallet-secret");
+        let wallet = create_wallet_for_import(None, &wallet_secret).await?;
+        let mnemonic = Mnemonic::new(phrase, Language::English)?;
+        let account = wallet.import_with_mnemonic(&wallet_secret, None, None, mnemonic.clone(), BIP32_ACCOUNT_KIND.into()).await?;
+        let addresses = account.clone().as_derivation_capable()?.derivation().receive_address_manager().get_range(0..2)?;
+        assert_eq!(account.receive_address()?, first);
+        assert_eq!(addresses[0], first);
+        assert_eq!(addresses[1].to_string(), "kaspatest:qrc2959g0pqda53glnfd238cdnmk24zxzkj8n5x83rkktx4h73dkc4ave6wyg");
+
+        // the private key data is stored along with the account
+        let prv_key_data_id = *account.prv_key_data_id()?;
+        assert!(wallet.get_prv_key_data(&wallet_secret, &prv_key_data_id).await?.is_some());
+        let stored = wallet.get_account_by_id(account.id()).await?.expect("account must be stored");
+        assert_eq!(stored.receive_address()?, first);
+
+        // importing the same mnemonic again is rejected
+        assert!(matches!(
+            wallet.import_with_mnemonic(&wallet_secret, None, None, mnemonic, BIP32_ACCOUNT_KIND.into()).await,
+            Err(Error::PrivateKeyAlreadyExists(id)) if id == prv_key_data_id
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_with_mnemonic_and_bip39_passphrase() -> Result<()> {
+        use crate::derivation::gen1::PubkeyDerivationManager;
+        use crate::account::descriptor::AccountDescriptorValue;
+        use kaspa_bip32::{DerivationPath, SecretKeyExt};
+
+        // BIP39 reference vector (mnemonic and "TREZOR" passphrase) and its master key
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let master_xprv =
+            "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF";
+        let xprv = ExtendedPrivateKey::<SecretKey>::from_str(master_xprv)?;
+        let key = xprv.derive_path("m/44'/111111'/0'/0/0".parse::<DerivationPath>()?)?;
+        let expected = PubkeyDerivationManager::create_address(&key.private_key().get_public_key(), Prefix::Testnet, false)?;
+
+        // the payment secret only encrypts the private key data, so it does not alter the derived addresses
+        let wallet_secret = Secret::from("wallet-secret");
+        let bip39_passphrase = Secret::from("TREZOR");
+        for payment_secret in [None, Some(Secret::from("payment-secret"))] {
+            let wallet = create_wallet_for_import(None, &wallet_secret).await?;
+            let mnemonic = Mnemonic::new(phrase, Language::English)?;
+            let account = wallet
+                .import_with_mnemonic(
+                    &wallet_secret,
+                    payment_secret.as_ref(),
+                    Some(&bip39_passphrase),
+                    mnemonic,
+                    BIP32_ACCOUNT_KIND.into(),
+                )
+                .await?;
+            assert_eq!(account.receive_address()?, expected);
+            assert!(matches!(
+                account.descriptor()?.properties.get(&AccountDescriptorProperty::Bip39Passphrase),
+                Some(AccountDescriptorValue::Bool(true))
+            ));
+        }
+
+        // without the passphrase the same mnemonic derives different keys
+        let wallet = create_wallet_for_import(None, &wallet_secret).await?;
+        let mnemonic = Mnemonic::new(phrase, Language::English)?;
+        let payment_secret = Secret::from("payment-secret");
+        let account = wallet
+            .import_with_mnemonic(&wallet_secret, Some(&payment_secret), Some(&Secret::from("")), mnemonic, BIP32_ACCOUNT_KIND.into())
+            .await?;
+        assert_ne!(account.receive_address()?, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ping_round_trip_time() -> Result<()> {
+        let rpc_mock = Arc::new(crate::tests::RpcCoreMock::new());
+        let wallet = Arc::new(Wallet::try_with_rpc(Some(rpc_mock.clone().into()), Wallet::resident_store()?, None)?);
+
+        rpc_mock.set_ping_delay(time::Duration::from_millis(50));
+        let round_trip_time = wallet.clone().ping().await?;
+        assert!(
+            (time::Duration::from_millis(50)..time::Duration::from_millis(500)).contains(&round_trip_time),
+            "unexpected round-trip time {round_trip_time:?}"
+        );
+
+        rpc_mock.set_ping_delay(time::Duration::from_secs(2));
+        match wallet.ping_node(time::Duration::from_millis(50)).await {
+            Err(Error::PingTimeout(timeout)) => assert_eq!(timeout, time::Duration::from_millis(50)),
+            result => panic!("expected a ping timeout, got {result:?}"),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee_rates() -> Result<()> {
+        use kaspa_rpc_core::{RpcFeeEstimate, RpcFeeRateBucket};
+
+        let estimate = |low: f64, normal: f64, priority: f64| {
+            RpcFeeEstimate::new(
+                RpcFeeRateBucket::new(priority, 1.0),
+                RpcFeeRateBucket::new(normal, 10.0),
+                RpcFeeRateBucket::new(low, 60.0),
+            )
+        };
+        let rpc_mock = Arc::new(crate::tests::RpcCoreMock::new());
+        let wallet = Arc::new(Wallet::try_with_rpc(Some(rpc_mock.clone().into()), Wallet::resident_store()?, None)?);
+        rpc_mock.set_fee_estimate(estimate(1.5, 4.0, 12.0));
+
+        // the node is not queried while not synced
+        assert_eq!(wallet.estimate_fee_rates().await?, FeeRates::default());
+        assert_eq!(rpc_mock.fee_estimate_requests(), 0);
+
+        wallet.utxo_processor().sync_proc().track(true).await?;
+        let rates = wallet.estimate_fee_rates().await?;
+        assert_eq!(rates, FeeRates { low: 1.5, normal: 4.0, priority: 12.0 });
+
+        // the rates are cached
+        rpc_mock.set_fee_estimate(estimate(1.0, 1.0, 1.0));
+        assert_eq!(wallet.estimate_fee_rates().await?, rates);
+        assert_eq!(rpc_mock.fee_estimate_requests(), 1);
+
+        // an empty mempool yields the minimum fee rate
+        wallet.inner.fee_rates.clear();
+        assert_eq!(wallet.estimate_fee_rates().await?, FeeRates::default());
+        assert_eq!(rpc_mock.fee_estimate_requests(), 2);
+        Ok(())
+    }
+
     async fn create_utxos_context_with_addresses(
         rpc: Arc<DynRpcApi>,
         addresses: Vec<Address>,
@@ -1529,6 +2105,73 @@ mod test {
         Ok(utxo_context)
     }
 
+    #[tokio::test]
+    async fn test_wallet_mempool_entries() -> Result<()> {
+        use kaspa_consensus_core::tx;
+        use kaspa_rpc_core::RpcTransaction;
+
+        let rpc_mock = Arc::new(crate::tests::RpcCoreMock::new());
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+        let wallet = Wallet::try_with_rpc(Some(rpc_mock.clone().into()), Wallet::resident_store()?, Some(network_id))?;
+
+        let address = Address::try_from("kaspatest:qz7ulu4c25dh7fzec9zjyrmlhnkzrg4wmf89q7gzr3gfrsj3uz6xjceef60sd")?;
+        let transaction = |value: u64, payload: u8| {
+            let output = tx::TransactionOutput::new(value, pay_to_address_script(&address));
+            tx::Transaction::new(0, vec![], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![payload])
+        };
+        let accepted = transaction(1000, 1);
+        let orphan = transaction(2000, 2);
+        let unknown = transaction(3000, 3);
+
+        let mut accepted_rpc = RpcTransaction::from(&accepted);
+        accepted_rpc.mass = 1500;
+        rpc_mock.add_mempool_entry(RpcMempoolEntry::new(100, accepted_rpc, false));
+        rpc_mock.add_mempool_entry(RpcMempoolEntry::new(200, RpcTransaction::from(&orphan), true));
+
+        let entry = wallet.get_mempool_entry(&accepted.id()).await?.expect("accepted transaction entry");
+        assert_eq!((entry.fee, entry.transaction.mass, entry.is_orphan), (100, 1500, false));
+        assert!(wallet.get_mempool_entry(&orphan.id()).await?.expect("orphan transaction entry").is_orphan);
+        assert!(wallet.is_in_mempool(&accepted.id()).await?);
+        assert!(!wallet.is_in_mempool(&unknown.id()).await?);
+
+        let entries = wallet.get_mempool_entries_by_addresses(vec![address.clone()]).await?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].address, address);
+        assert_eq!(entries[0].receiving.iter().map(|entry| entry.fee).collect::<Vec<_>>(), vec![100, 200]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wallet_enumerate() -> Result<()> {
+        let folder = tempfile::tempdir()?;
+        let store = Arc::new(LocalStore::try_new_with_folder(false, folder.path().to_str().unwrap())?);
+        let wallet = Arc::new(Wallet::try_new(store, None)?);
+        let wallet_secret = Secret::from("enumerate");
+
+        let args = WalletCreateArgs::new(Some("Empty".to_string()), None, EncryptionKind::XChaCha20Poly1305, None, false);
+        wallet.create_wallet(&wallet_secret, args).await?;
+        let args = WalletCreateArgs::new(None, Some("accounts".to_string()), EncryptionKind::XChaCha20Poly1305, None, false);
+        wallet.create_wallet_with_accounts(&wallet_secret, args, None, None, WordCount::Words12, None).await?;
+        wallet.close().await?;
+        std::fs::write(folder.path().join("corrupt.wallet"), b"not a wallet")?;
+
+        let list = wallet.enumerate().await?;
+
+        let filenames = list.iter().map(|info| info.descriptor.filename.as_str()).collect::<Vec<_>>();
+        assert_eq!(filenames, vec!["accounts", "corrupt", "empty"]);
+        assert_eq!(list[0].account_count, Some(1));
+        assert!(list[0].created.is_some());
+        assert_eq!(
+            list[1],
+            WalletInfo { descriptor: WalletDescriptor::new(None, "corrupt".to_string()), created: None, account_count: None }
+        );
+        assert_eq!(list[2].descriptor.title.as_deref(), Some("Empty"));
+        assert_eq!(list[2].account_count, Some(0));
+
+        Ok(())
+    }
+
     #[allow(dead_code)]
     // #[tokio::test]
     async fn wallet_test() -> Result<()> {
@@ -1731,6 +2374,67 @@ mod test {
         );
     }
 
+    const GOLANG_SINGLE_WALLET_JSON_V1: &str = r#"{"version":1,"encryptedMnemonics":[{"cipher":"2022041df1a5bdcc26445952c53f96518641118bf0f990a01747d631d4607e5b53af3c9f4c07d6e3b84bc766445191b13d1f1fdf7ac96eae9c8859a9add660ac15b938356f936fdf614640d89627d368c57b22cf62844b1e1bcf3feceecbc6bf655df9519d7e3cfede6fe19d87a49e5709211b0b95c8d68781c70c4722bd8e25361492ef38d5cca21664a7f0838e4a1e2994d30c6d4b81d1397169570375ce56608439ae00e84c1f6acdd805f0ee22d4ba7b354c7f7cd4b2d18ce4fd6b8af785f95ed2a69361f318bc","salt":"044f5b890e48af4a7dcd7e7766af9380"}],"publicKeys":["kpub2KUE88roSn5peP1rEZnbRuKYw1fEPbhqBoXVWW7mLfkrLvQBAjUqwx7m1ezeSfqfecv9RUYePuHf99iW51i31WjwWjnzKDCUcTucBSiBbJA"],"minimumSignatures":1,"cosignerIndex":0,"lastUsedExternalIndex":0,"lastUsedInternalIndex":0,"ecdsa":false}"#;
+
+    async fn create_golang_import_test_wallet() -> Arc<Wallet> {
+        let resident_store = Wallet::resident_store().unwrap();
+        let wallet = Arc::new(Wallet::try_new(resident_store, Some(NetworkId::new(NetworkType::Mainnet))).unwrap());
+        wallet
+            .create_wallet(
+                &Secret::new(vec![]),
+                WalletCreateArgs {
+                    title: None,
+                    filename: None,
+                    encryption_kind: EncryptionKind::XChaCha20Poly1305,
+                    user_hint: None,
+                    overwrite_wallet_storage: false,
+                },
+            )
+            .await
+            .unwrap();
+        wallet
+    }
+
+    #[tokio::test]
+    async fn import_golang_wallet_json_test() {
+        let wallet = create_golang_import_test_wallet().await;
+        let wallet_secret = Secret::new(vec![]);
+
+        let acc = wallet.import_kaspawallet_golang(&Secret::new(vec![]), &wallet_secret, GOLANG_SINGLE_WALLET_JSON_V1).await.unwrap();
+        assert_eq!(
+            acc.receive_address().unwrap(),
+            Address::try_from("kaspa:qpuvlauc6a5syze9g70dnxzzvykhkuatsjrx87mxqccqh7kf9kcssdkp9ec7w").unwrap(), // taken from golang impl
+        );
+        assert_eq!(acc.account_kind(), BIP32_ACCOUNT_KIND);
+    }
+
+    #[tokio::test]
+    async fn import_golang_wallet_json_wrong_password_test() {
+        let wallet = create_golang_import_test_wallet().await;
+        let wallet_secret = Secret::new(vec![]);
+
+        let result =
+            wallet.import_kaspawallet_golang(&Secret::new(b"wrong".to_vec()), &wallet_secret, GOLANG_SINGLE_WALLET_JSON_V1).await;
+        assert!(matches!(result, Err(Error::KaspaWalletDecrypt)));
+        assert_eq!(wallet.store().as_account_store().unwrap().len(None).await.unwrap(), 0);
+    }
+
+    #[test]
+    fn parse_golang_wallet_json_test() {
+        let file = KaspaWalletFile::try_from_json(GOLANG_SINGLE_WALLET_JSON_V1).unwrap();
+        assert_eq!(file.version, 1);
+        assert!(!file.is_multisig());
+        assert_eq!(file.encrypted_mnemonics[0].salt, hex!("044f5b890e48af4a7dcd7e7766af9380"));
+
+        let json = GOLANG_SINGLE_WALLET_JSON_V1.replace(r#""version":1"#, r#""version":2"#);
+        assert!(matches!(KaspaWalletFile::try_from_json(&json), Err(Error::KaspaWalletFileVersion(2))));
+
+        let json = GOLANG_SINGLE_WALLET_JSON_V1.replace(r#""version":1"#, r#""version":0"#);
+        assert!(matches!(KaspaWalletFile::try_from_json(&json), Err(Error::KaspaWalletFile(_))));
+
+        assert!(matches!(KaspaWalletFile::try_from_json("{}"), Err(Error::KaspaWalletFile(_))));
+    }
+
     #[test]
     fn deser_golang_wallet_test() {
         #[derive(Debug)]
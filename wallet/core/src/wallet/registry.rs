@@ -0,0 +1,192 @@
+//!
+//! Implementation of a [`WalletRegistry`] holding the wallets that are open
+//! at the same time, keyed by their storage name.
+//!
+//! The registered wallets share the RPC connection and the settings of the
+//! primary wallet, while each wallet tracks its own accounts with its own
+//! [`UtxoProcessor`], so that UTXO notifications are delivered to the accounts
+//! of the wallet that registered the notified addresses.
+//!
+
+use crate::imports::*;
+
+/// Creates the storage of the wallets opened alongside the primary wallet.
+pub type StoreFactory = Arc<dyn Fn() -> Result<Arc<dyn Interface>> + Send + Sync>;
+
+pub struct WalletRegistry {
+    primary: Arc<Wallet>,
+    store_factory: StoreFactory,
+    wallets: Mutex<Vec<Arc<Wallet>>>,
+    current: Mutex<Arc<Wallet>>,
+}
+
+impl WalletRegistry {
+    pub fn new(primary: Arc<Wallet>, store_factory: StoreFactory) -> Self {
+        Self { wallets: Mutex::new(vec![primary.clone()]), current: Mutex::new(primary.clone()), primary, store_factory }
+    }
+
+    /// The wallet owning the RPC connection and the settings shared by all wallets.
+    pub fn primary(&self) -> &Arc<Wallet> {
+        &self.primary
+    }
+
+    /// The wallet subject to the wallet operations of the application.
+    pub fn current(&self) -> Arc<Wallet> {
+        self.current.lock().unwrap().clone()
+    }
+
+    /// Returns the storage names of the open wallets.
+    pub fn names(&self) -> Vec<String> {
+        self.wallets.lock().unwrap().iter().filter_map(|wallet| wallet.descriptor()).map(|descriptor| descriptor.filename).collect()
+    }
+
+    /// Returns the open wallet with the storage name `name`.
+    pub fn get(&self, name: &str) -> Option<Arc<Wallet>> {
+        self.wallets
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|wallet| wallet.descriptor().is_some_and(|descriptor| descriptor.filename == name))
+            .cloned()
+    }
+
+    /// Makes the open wallet `name` the current wallet.
+    pub fn select(&self, name: &str) -> Result<Arc<Wallet>> {
+        let wallet = self.get(name).ok_or_else(|| Error::WalletNameNotOpen(name.to_string()))?;
+        *self.current.lock().unwrap() = wallet.clone();
+        Ok(wallet)
+    }
+
+    /// Opens the wallet `name` alongside the open wallets and makes it the current wallet.
+    /// Accounts are not activated by this call.
+    pub async fn open(
+        &self,
+        wallet_secret: &Secret,
+        name: &str,
+        args: WalletOpenArgs,
+    ) -> Result<(Arc<Wallet>, Option<Vec<AccountDescriptor>>)> {
+        if self.get(name).is_some() {
+            return Err(Error::WalletIsOpen(name.to_string()));
+        }
+
+        let wallet = self.idle_wallet().await?;
+        let account_descriptors = wallet.open(wallet_secret, Some(name.to_string()), args).await?;
+        *self.current.lock().unwrap() = wallet.clone();
+        Ok((wallet, account_descriptors))
+    }
+
+    /// Applies `network_id` to all wallets.
+    pub fn set_network_id(&self, network_id: NetworkId) -> Result<()> {
+        self.wallets.lock().unwrap().iter().try_for_each(|wallet| wallet.set_network_id(network_id))
+    }
+
+    /// Stops all wallets, including the primary wallet.
+    pub async fn stop(&self) -> Result<()> {
+        let wallets = self.wallets.lock().unwrap().clone();
+        for wallet in wallets.iter().filter(|wallet| !Arc::ptr_eq(wallet, &self.primary)) {
+            wallet.stop().await?;
+        }
+        self.primary.stop().await
+    }
+
+    /// Returns a wallet that is not open, creating and starting a new
+    /// wallet sharing the RPC connection of the primary wallet if needed.
+    async fn idle_wallet(&self) -> Result<Arc<Wallet>> {
+        let idle = self.wallets.lock().unwrap().iter().find(|wallet| !wallet.is_open()).cloned();
+        if let Some(wallet) = idle {
+            return Ok(wallet);
+        }
+
+        let rpc = self.primary.has_rpc().then(|| Rpc::new(self.primary.rpc_api(), self.primary.rpc_ctl()));
+        let store = (self.store_factory)()?;
+        let network_id = self.primary.network_id().ok();
+        let wallet = Arc::new(Wallet::try_with_rpc_and_settings(rpc, store, network_id, self.primary.settings().clone())?);
+        wallet.start_with_shared_rpc().await?;
+        self.wallets.lock().unwrap().push(wallet.clone());
+        Ok(wallet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::local::interface::LocalStore;
+    use crate::utils::kaspa_to_sompi;
+    use kaspa_bip32::WordCount;
+    use kaspa_consensus_core::tx::{TransactionId, TransactionOutpoint, UtxoEntry};
+    use kaspa_rpc_core::RpcUtxosByAddressesEntry;
+    use kaspa_txscript::pay_to_address_script;
+
+    #[tokio::test]
+    async fn test_wallet_registry() -> Result<()> {
+        let folder = tempfile::tempdir()?;
+        let path = folder.path().to_str().unwrap().to_string();
+        let store_factory: StoreFactory = Arc::new(move || Ok(Arc::new(LocalStore::try_new_with_folder(false, &path)?)));
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 11);
+        let rpc_mock = Arc::new(crate::tests::RpcCoreMock::new());
+        let wallet = Arc::new(Wallet::try_with_rpc(Some(rpc_mock.clone().into()), store_factory()?, Some(network_id))?);
+        let wallet_secret = Secret::from("registry");
+
+        // create the hot and cold wallets, funding the first receive address of the hot wallet
+        for name in ["hot", "cold"] {
+            let args = WalletCreateArgs::new(None, Some(name.to_string()), EncryptionKind::XChaCha20Poly1305, None, false);
+            let (_, _, _, account) =
+                wallet.create_wallet_with_accounts(&wallet_secret, args, None, None, WordCount::Words12, None).await?;
+            if name == "hot" {
+                let address = account.receive_address()?;
+                let utxo_entry = UtxoEntry::new(kaspa_to_sompi(1.0), pay_to_address_script(&address), 0, false);
+                let outpoint = TransactionOutpoint::new(TransactionId::from_u64_word(1), 0);
+                rpc_mock.add_utxo_by_address(RpcUtxosByAddressesEntry { address: Some(address), outpoint, utxo_entry });
+            }
+            wallet.close().await?;
+        }
+
+        let registry = WalletRegistry::new(wallet.clone(), store_factory);
+        let open = |name: &'static str| {
+            let registry = &registry;
+            let wallet_secret = &wallet_secret;
+            async move {
+                let (wallet, _) = registry.open(wallet_secret, name, WalletOpenArgs::default_with_legacy_accounts()).await?;
+                wallet.utxo_processor().mock_connect().await?;
+                wallet.utxo_processor().handle_daa_score_change(1).await?;
+                wallet.activate_accounts(None).await?;
+                Result::<Arc<Wallet>>::Ok(wallet)
+            }
+        };
+
+        // the first wallet is opened in the primary wallet, the second one alongside it
+        let hot = open("hot").await?;
+        assert!(Arc::ptr_eq(&hot, &wallet));
+        let cold = open("cold").await?;
+        assert!(!Arc::ptr_eq(&cold, &hot));
+        assert!(Arc::ptr_eq(&registry.current(), &cold));
+        assert_eq!(registry.names(), vec!["hot".to_string(), "cold".to_string()]);
+        assert!(matches!(open("hot").await, Err(Error::WalletIsOpen(_))));
+
+        // each wallet only tracks the addresses of its own accounts
+        let balance = |wallet: &Arc<Wallet>| {
+            let account = wallet.active_accounts().first().unwrap();
+            account.balance().map(|balance| balance.mature + balance.pending).unwrap_or_default()
+        };
+        assert_eq!(balance(&hot), kaspa_to_sompi(1.0));
+        assert_eq!(balance(&cold), 0);
+        let hot_addresses = hot.utxo_processor().address_to_utxo_context_map().iter().map(|e| e.key().clone()).collect::<Vec<_>>();
+        assert!(!hot_addresses.is_empty());
+        assert!(hot_addresses.iter().all(|address| cold.utxo_processor().address_to_utxo_context(address).is_none()));
+
+        // switching between the open wallets
+        assert!(Arc::ptr_eq(&registry.select("hot")?, &hot));
+        assert!(Arc::ptr_eq(&registry.current(), &hot));
+        assert!(matches!(registry.select("unknown"), Err(Error::WalletNameNotOpen(_))));
+
+        // a closed wallet instance is reused to open another wallet
+        cold.close().await?;
+        assert_eq!(registry.names(), vec!["hot".to_string()]);
+        assert!(matches!(registry.select("cold"), Err(Error::WalletNameNotOpen(_))));
+        assert!(Arc::ptr_eq(&open("cold").await?, &cold));
+
+        cold.stop().await?;
+        std::fs::remove_dir_all(&folder)?;
+        Ok(())
+    }
+}
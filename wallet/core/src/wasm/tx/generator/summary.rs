@@ -28,6 +28,11 @@ impl GeneratorSummary {
         self.inner.number_of_generated_transactions()
     }
 
+    #[wasm_bindgen(getter, js_name = paymentTransactions)]
+    pub fn number_of_payment_transactions(&self) -> usize {
+        self.inner.number_of_payment_transactions()
+    }
+
     #[wasm_bindgen(getter, js_name = finalAmount)]
     pub fn final_transaction_amount(&self) -> Option<BigInt> {
         self.inner.final_transaction_amount().map(BigInt::from)
@@ -5,7 +5,6 @@ use crate::secret::Secret;
 use crate::tx::PaymentOutputs;
 use crate::wasm::utxo::UtxoContext;
 use kaspa_consensus_wasm::Keypair;
-use workflow_core::abortable::Abortable;
 use workflow_wasm::abi::ref_from_abi;
 
 #[wasm_bindgen(inspectable)]
@@ -120,7 +119,12 @@ impl TryFrom<JsValue> for AccountSendArgs {
 
             let priority_fee_sompi = object.get_u64("priorityFee").ok();
             let include_fees_in_amount = object.get_bool("includeFeesInAmount").unwrap_or(false);
-            let abortable = object.get("abortable").ok().and_then(|v| ref_from_abi!(Abortable, &v).ok()).unwrap_or_default();
+            let abortable = object
+                .get("abortable")
+                .ok()
+                .and_then(|v| ref_from_abi!(workflow_core::abortable::Abortable, &v).ok())
+                .map(Abortable::from)
+                .unwrap_or_default();
 
             let wallet_secret = object.get_string("walletSecret")?.into();
             let payment_secret = object.get_value("paymentSecret")?.as_string().map(|s| s.into());